@@ -26,7 +26,7 @@
  * SOFTWARE.
  */
 // Deps
-use crate::filetransfer::{FileTransferParams, FileTransferProtocol};
+use crate::filetransfer::{FileTransferParams, TransferOpts};
 use crate::host::{HostError, Localhost};
 use crate::system::config_client::ConfigClient;
 use crate::system::environment;
@@ -40,7 +40,10 @@ use crate::ui::context::Context;
 // Namespaces
 use std::path::{Path, PathBuf};
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Amount of time without events after which the activity manager switches to the idle ticks
+const IDLE_AFTER: Duration = Duration::from_secs(1);
 
 /// ### NextActivity
 ///
@@ -57,14 +60,87 @@ pub enum NextActivity {
 pub struct ActivityManager {
     context: Option<Context>,
     interval: Duration,
+    idle_interval: Duration,
     local_dir: PathBuf,
 }
 
+/// ### TickRate
+///
+/// TickRate chooses how long the activity manager has to sleep between two draws.
+/// The fast interval is used while the activity is busy; once the activity has been idle for `IDLE_AFTER`,
+/// the idle interval is used, until the activity becomes busy again
+struct TickRate {
+    fast: Duration,
+    idle: Duration,
+    last_busy: Instant,
+}
+
+impl TickRate {
+    /// ### new
+    ///
+    /// Instantiates a new `TickRate`. The idle interval can't be faster than the fast interval
+    fn new(fast: Duration, idle: Duration) -> Self {
+        Self {
+            fast,
+            idle: idle.max(fast),
+            last_busy: Instant::now(),
+        }
+    }
+
+    /// ### next
+    ///
+    /// Returns the interval to sleep for, given whether the activity has been busy on the last draw
+    fn next(&mut self, busy: bool) -> Duration {
+        self.next_at(busy, Instant::now())
+    }
+
+    /// ### next_at
+    ///
+    /// Returns the interval to sleep for at `now`, given whether the activity has been busy on the last draw
+    fn next_at(&mut self, busy: bool, now: Instant) -> Duration {
+        if busy {
+            self.last_busy = now;
+        }
+        match now.saturating_duration_since(self.last_busy) >= IDLE_AFTER {
+            true => self.idle,
+            false => self.fast,
+        }
+    }
+}
+
+/// ### initial_local_dir
+///
+/// Get the directory the local explorer starts at.
+/// The directory provided by the user (`cli`) has priority over the one stored at the end of the last session,
+/// which is used only if it still exists; otherwise the current working directory (`cwd`) is used
+fn initial_local_dir(cli: Option<&Path>, stored: Option<&Path>, cwd: &Path) -> PathBuf {
+    match (cli, stored) {
+        (Some(dir), _) => dir.to_path_buf(),
+        (None, Some(dir)) if dir.is_dir() => dir.to_path_buf(),
+        (None, Some(dir)) => {
+            warn!(
+                "Last local working directory {} doesn't exist anymore; using {}",
+                dir.display(),
+                cwd.display()
+            );
+            cwd.to_path_buf()
+        }
+        (None, None) => cwd.to_path_buf(),
+    }
+}
+
 impl ActivityManager {
     /// ### new
     ///
-    /// Initializes a new Activity Manager
-    pub fn new(local_dir: &Path, interval: Duration) -> Result<ActivityManager, HostError> {
+    /// Initializes a new Activity Manager.
+    /// The local explorer starts at `local_dir` if provided, otherwise at the directory where the last session ended,
+    /// falling back to `cwd`
+    pub fn new(
+        local_dir: Option<&Path>,
+        cwd: &Path,
+        interval: Duration,
+        idle_interval: Duration,
+    ) -> Result<ActivityManager, HostError> {
         // Prepare Context
         // Initialize configuration client
         let (config_client, error): (ConfigClient, Option<String>) =
@@ -75,12 +151,18 @@ impl ActivityManager {
                     (ConfigClient::degraded(), Some(err))
                 }
             };
+        let local_dir: PathBuf = initial_local_dir(
+            local_dir,
+            config_client.get_last_local_wrkdir().as_deref(),
+            cwd,
+        );
         let theme_provider: ThemeProvider = Self::init_theme_provider();
         let ctx: Context = Context::new(config_client, theme_provider, error);
         Ok(ActivityManager {
             context: Some(ctx),
-            local_dir: local_dir.to_path_buf(),
+            local_dir,
             interval,
+            idle_interval,
         })
     }
 
@@ -92,6 +174,13 @@ impl ActivityManager {
         self.context.as_mut().unwrap().set_ftparams(params);
     }
 
+    /// ### set_transfer_opts
+    ///
+    /// Set options for file transfers
+    pub fn set_transfer_opts(&mut self, opts: TransferOpts) {
+        self.context.as_mut().unwrap().set_transfer_opts(opts);
+    }
+
     /// ### run
     ///
     ///
@@ -136,6 +225,7 @@ impl ActivityManager {
         };
         // Create activity
         activity.on_create(ctx);
+        let mut ticks: TickRate = TickRate::new(self.interval, self.idle_interval);
         loop {
             // Draw activity
             activity.on_draw();
@@ -163,7 +253,7 @@ impl ActivityManager {
                 }
             }
             // Sleep for ticks
-            sleep(self.interval);
+            sleep(ticks.next(activity.is_busy()));
         }
         // Destroy activity
         self.context = activity.on_destroy();
@@ -195,7 +285,6 @@ impl ActivityManager {
             }
         };
         // Prepare activity
-        let protocol: FileTransferProtocol = ft_params.protocol;
         let host: Localhost = match Localhost::new(self.local_dir.clone()) {
             Ok(host) => host,
             Err(err) => {
@@ -205,11 +294,13 @@ impl ActivityManager {
                 return None;
             }
         };
-        let mut activity: FileTransferActivity = FileTransferActivity::new(host, protocol);
+        let mut activity: FileTransferActivity =
+            FileTransferActivity::new(host, ft_params, ctx.transfer_opts());
         // Prepare result
         let result: Option<NextActivity>;
         // Create activity
         activity.on_create(ctx);
+        let mut ticks: TickRate = TickRate::new(self.interval, self.idle_interval);
         loop {
             // Draw activity
             activity.on_draw();
@@ -231,10 +322,18 @@ impl ActivityManager {
                 }
             }
             // Sleep for ticks
-            sleep(self.interval);
+            sleep(ticks.next(activity.is_busy()));
         }
         // Destroy activity
         self.context = activity.on_destroy();
+        // Start from the last local working directory on reconnect
+        if let Some(dir) = self
+            .context
+            .as_ref()
+            .and_then(|x| x.config().get_last_local_wrkdir())
+        {
+            self.local_dir = dir;
+        }
         result
     }
 
@@ -256,6 +355,7 @@ impl ActivityManager {
         };
         // Create activity
         activity.on_create(ctx);
+        let mut ticks: TickRate = TickRate::new(self.interval, self.idle_interval);
         loop {
             // Draw activity
             activity.on_draw();
@@ -265,7 +365,7 @@ impl ActivityManager {
                 break;
             }
             // Sleep for ticks
-            sleep(self.interval);
+            sleep(ticks.next(activity.is_busy()));
         }
         // Destroy activity
         self.context = activity.on_destroy();
@@ -332,3 +432,100 @@ impl ActivityManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_activity_manager_tick_rate_idle() {
+        let fast: Duration = Duration::from_millis(10);
+        let idle: Duration = Duration::from_millis(100);
+        let mut ticks: TickRate = TickRate::new(fast, idle);
+        let start: Instant = ticks.last_busy;
+        // Still fast before idle timeout
+        assert_eq!(ticks.next_at(false, start), fast);
+        assert_eq!(
+            ticks.next_at(false, start + Duration::from_millis(500)),
+            fast
+        );
+        // Back off after idle timeout
+        assert_eq!(ticks.next_at(false, start + IDLE_AFTER), idle);
+        assert_eq!(ticks.next_at(false, start + Duration::from_secs(60)), idle);
+    }
+
+    #[test]
+    fn test_activity_manager_tick_rate_busy() {
+        let fast: Duration = Duration::from_millis(10);
+        let idle: Duration = Duration::from_millis(100);
+        let mut ticks: TickRate = TickRate::new(fast, idle);
+        let start: Instant = ticks.last_busy;
+        assert_eq!(ticks.next_at(false, start + Duration::from_secs(2)), idle);
+        // Input snaps back to fast ticks
+        assert_eq!(ticks.next_at(true, start + Duration::from_secs(3)), fast);
+        // And stays fast until idle timeout elapses again
+        assert_eq!(
+            ticks.next_at(false, start + Duration::from_millis(3500)),
+            fast
+        );
+        // Keep busy (e.g. transfer running)
+        assert_eq!(
+            ticks.next_at(true, start + Duration::from_millis(3900)),
+            fast
+        );
+        assert_eq!(ticks.next_at(true, start + Duration::from_secs(10)), fast);
+        assert_eq!(
+            ticks.next_at(false, start + Duration::from_millis(10500)),
+            fast
+        );
+        assert_eq!(ticks.next_at(false, start + Duration::from_secs(11)), idle);
+    }
+
+    #[test]
+    fn test_activity_manager_initial_local_dir() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let cwd: &Path = Path::new("/");
+        // Cli arg has priority
+        assert_eq!(
+            initial_local_dir(Some(Path::new("/tmp/foo")), Some(tmp_dir.path()), cwd),
+            PathBuf::from("/tmp/foo")
+        );
+        // Then stored directory
+        assert_eq!(
+            initial_local_dir(None, Some(tmp_dir.path()), cwd),
+            tmp_dir.path().to_path_buf()
+        );
+        // Then cwd
+        assert_eq!(initial_local_dir(None, None, cwd), PathBuf::from("/"));
+    }
+
+    #[test]
+    fn test_activity_manager_initial_local_dir_missing() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let missing: PathBuf = tmp_dir.path().join("gone");
+        let cwd: &Path = Path::new("/");
+        assert_eq!(
+            initial_local_dir(None, Some(missing.as_path()), cwd),
+            PathBuf::from("/")
+        );
+        // Stored path must be a directory
+        let file: PathBuf = tmp_dir.path().join("file.txt");
+        std::fs::write(file.as_path(), b"hello").unwrap();
+        assert_eq!(
+            initial_local_dir(None, Some(file.as_path()), cwd),
+            PathBuf::from("/")
+        );
+    }
+
+    #[test]
+    fn test_activity_manager_tick_rate_idle_not_faster() {
+        let fast: Duration = Duration::from_millis(50);
+        let mut ticks: TickRate = TickRate::new(fast, Duration::from_millis(5));
+        let start: Instant = ticks.last_busy;
+        assert_eq!(ticks.next_at(false, start + Duration::from_secs(2)), fast);
+        assert_eq!(ticks.next(true), fast);
+    }
+}
@@ -25,8 +25,13 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use crate::utils::fmt::REDACTED_PASSWORD;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Deserialize, Serialize, std::fmt::Debug)]
 /// ## UserHosts
@@ -38,7 +43,7 @@ pub struct UserHosts {
     pub recents: HashMap<String, Bookmark>,
 }
 
-#[derive(Deserialize, Serialize, std::fmt::Debug, PartialEq)]
+#[derive(Deserialize, Serialize, PartialEq)]
 /// ## Bookmark
 ///
 /// Bookmark describes a single bookmark entry in the user hosts storage
@@ -48,6 +53,67 @@ pub struct Bookmark {
     pub protocol: String,
     pub username: String,
     pub password: Option<String>, // Password is optional; base64, aes-128 encrypted password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_path: Option<PathBuf>, // Private key used for SSH protocols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_jump: Option<String>, // Host to connect through, for SSH protocols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_key_fingerprint: Option<String>, // Fingerprint of the host key accepted on first connection, for SSH protocols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u64>, // Last time the bookmark was used to connect (seconds since UNIX epoch)
+}
+
+/// ## BookmarkSorting
+///
+/// BookmarkSorting defines how the bookmarks list is sorted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BookmarkSorting {
+    #[default]
+    Name,
+    Protocol,
+    Recent,
+}
+
+impl fmt::Debug for Bookmark {
+    /// Password is redacted, even if encrypted
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bookmark")
+            .field("address", &self.address)
+            .field("port", &self.port)
+            .field("protocol", &self.protocol)
+            .field("username", &self.username)
+            .field(
+                "password",
+                &self.password.as_ref().map(|_| REDACTED_PASSWORD),
+            )
+            .field("ssh_key_path", &self.ssh_key_path)
+            .field("proxy_jump", &self.proxy_jump)
+            .field("host_key_fingerprint", &self.host_key_fingerprint)
+            .field("last_used", &self.last_used)
+            .finish()
+    }
+}
+
+impl fmt::Display for BookmarkSorting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BookmarkSorting::Name => "name",
+            BookmarkSorting::Protocol => "protocol",
+            BookmarkSorting::Recent => "recent",
+        })
+    }
+}
+
+impl FromStr for BookmarkSorting {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "name" => Ok(BookmarkSorting::Name),
+            "protocol" => Ok(BookmarkSorting::Protocol),
+            "recent" => Ok(BookmarkSorting::Recent),
+            _ => Err(()),
+        }
+    }
 }
 
 impl Default for UserHosts {
@@ -82,6 +148,10 @@ mod tests {
             protocol: String::from("SFTP"),
             username: String::from("root"),
             password: Some(String::from("password")),
+            ssh_key_path: None,
+            proxy_jump: None,
+            host_key_fingerprint: None,
+            last_used: None,
         };
         let recent: Bookmark = Bookmark {
             address: String::from("192.168.1.2"),
@@ -89,6 +159,10 @@ mod tests {
             protocol: String::from("SCP"),
             username: String::from("admin"),
             password: Some(String::from("password")),
+            ssh_key_path: None,
+            proxy_jump: None,
+            host_key_fingerprint: None,
+            last_used: None,
         };
         let mut bookmarks: HashMap<String, Bookmark> = HashMap::with_capacity(1);
         bookmarks.insert(String::from("test"), bookmark);
@@ -121,4 +195,51 @@ mod tests {
             String::from("password")
         );
     }
+    #[test]
+    fn test_bookmarks_bookmark_debug() {
+        let bookmark: Bookmark = Bookmark {
+            address: String::from("192.168.1.1"),
+            port: 22,
+            protocol: String::from("SFTP"),
+            username: String::from("root"),
+            password: Some(String::from("secret-password")),
+            ssh_key_path: None,
+            proxy_jump: None,
+            host_key_fingerprint: None,
+            last_used: None,
+        };
+        let debug: String = format!("{:?}", bookmark);
+        assert!(debug.contains("***"));
+        assert!(debug.contains("192.168.1.1"));
+        assert!(!debug.contains("secret-password"));
+        // Through user hosts
+        let mut bookmarks: HashMap<String, Bookmark> = HashMap::with_capacity(1);
+        bookmarks.insert(String::from("test"), bookmark);
+        let hosts: UserHosts = UserHosts {
+            bookmarks,
+            recents: HashMap::new(),
+        };
+        assert!(!format!("{:?}", hosts).contains("secret-password"));
+    }
+
+    #[test]
+    fn test_bookmarks_bookmark_sorting() {
+        assert_eq!(BookmarkSorting::default(), BookmarkSorting::Name);
+        assert_eq!(BookmarkSorting::Name.to_string(), "name");
+        assert_eq!(BookmarkSorting::Protocol.to_string(), "protocol");
+        assert_eq!(BookmarkSorting::Recent.to_string(), "recent");
+        assert_eq!(
+            BookmarkSorting::from_str("name").ok().unwrap(),
+            BookmarkSorting::Name
+        );
+        assert_eq!(
+            BookmarkSorting::from_str("PROTOCOL").ok().unwrap(),
+            BookmarkSorting::Protocol
+        );
+        assert_eq!(
+            BookmarkSorting::from_str("recent").ok().unwrap(),
+            BookmarkSorting::Recent
+        );
+        assert!(BookmarkSorting::from_str("size").is_err());
+    }
 }
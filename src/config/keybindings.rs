@@ -0,0 +1,365 @@
+//! ## KeyBindings
+//!
+//! `keybindings` is the module which provides the association between the file explorer actions and the keys which trigger them
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// Ext
+use std::collections::HashMap;
+
+/// ## KeyAction
+///
+/// Describes an action of the file explorer which can be bound to a key
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KeyAction {
+    ToggleHiddenFiles,
+    SortBy,
+    Copy,
+    MakeDir,
+    Delete,
+    Find,
+    GoTo,
+    Help,
+    Info,
+    Jump,
+    Symlink,
+    Reload,
+    NewFile,
+    Edit,
+    Pull,
+    Quit,
+    Rename,
+    SaveAs,
+    Mirror,
+    GoToParent,
+    Open,
+    OpenWith,
+    Exec,
+    SyncBrowsing,
+    DryRun,
+}
+
+impl KeyAction {
+    /// ### all
+    ///
+    /// Returns all the actions which can be bound to a key
+    pub const ALL: [KeyAction; 25] = [
+        KeyAction::ToggleHiddenFiles,
+        KeyAction::SortBy,
+        KeyAction::Copy,
+        KeyAction::MakeDir,
+        KeyAction::Delete,
+        KeyAction::Find,
+        KeyAction::GoTo,
+        KeyAction::Help,
+        KeyAction::Info,
+        KeyAction::Jump,
+        KeyAction::Symlink,
+        KeyAction::Reload,
+        KeyAction::NewFile,
+        KeyAction::Edit,
+        KeyAction::Pull,
+        KeyAction::Quit,
+        KeyAction::Rename,
+        KeyAction::SaveAs,
+        KeyAction::Mirror,
+        KeyAction::GoToParent,
+        KeyAction::Open,
+        KeyAction::OpenWith,
+        KeyAction::Exec,
+        KeyAction::SyncBrowsing,
+        KeyAction::DryRun,
+    ];
+
+    /// ### name
+    ///
+    /// Returns the name of the action, as written in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::ToggleHiddenFiles => "toggle_hidden_files",
+            KeyAction::SortBy => "sort_by",
+            KeyAction::Copy => "copy",
+            KeyAction::MakeDir => "make_dir",
+            KeyAction::Delete => "delete",
+            KeyAction::Find => "find",
+            KeyAction::GoTo => "go_to",
+            KeyAction::Help => "help",
+            KeyAction::Info => "info",
+            KeyAction::Jump => "jump",
+            KeyAction::Symlink => "symlink",
+            KeyAction::Reload => "reload",
+            KeyAction::NewFile => "new_file",
+            KeyAction::Edit => "edit",
+            KeyAction::Pull => "pull",
+            KeyAction::Quit => "quit",
+            KeyAction::Rename => "rename",
+            KeyAction::SaveAs => "save_as",
+            KeyAction::Mirror => "mirror",
+            KeyAction::GoToParent => "go_to_parent",
+            KeyAction::Open => "open",
+            KeyAction::OpenWith => "open_with",
+            KeyAction::Exec => "exec",
+            KeyAction::SyncBrowsing => "sync_browsing",
+            KeyAction::DryRun => "dry_run",
+        }
+    }
+
+    /// ### default_key
+    ///
+    /// Returns the key the action is bound to by default
+    pub fn default_key(&self) -> char {
+        match self {
+            KeyAction::ToggleHiddenFiles => 'a',
+            KeyAction::SortBy => 'b',
+            KeyAction::Copy => 'c',
+            KeyAction::MakeDir => 'd',
+            KeyAction::Delete => 'e',
+            KeyAction::Find => 'f',
+            KeyAction::GoTo => 'g',
+            KeyAction::Help => 'h',
+            KeyAction::Info => 'i',
+            KeyAction::Jump => 'j',
+            KeyAction::Symlink => 'k',
+            KeyAction::Reload => 'l',
+            KeyAction::NewFile => 'n',
+            KeyAction::Edit => 'o',
+            KeyAction::Pull => 'p',
+            KeyAction::Quit => 'q',
+            KeyAction::Rename => 'r',
+            KeyAction::SaveAs => 's',
+            KeyAction::Mirror => 't',
+            KeyAction::GoToParent => 'u',
+            KeyAction::Open => 'v',
+            KeyAction::OpenWith => 'w',
+            KeyAction::Exec => 'x',
+            KeyAction::SyncBrowsing => 'y',
+            KeyAction::DryRun => 'z',
+        }
+    }
+
+    /// ### from_name
+    ///
+    /// Get action from its name in the configuration
+    pub fn from_name(name: &str) -> Option<KeyAction> {
+        Self::ALL.iter().find(|x| x.name() == name).copied()
+    }
+}
+
+/// ## KeyBindings
+///
+/// KeyBindings associates each file explorer action to the key which triggers it
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    keys: HashMap<KeyAction, char>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: KeyAction::ALL
+                .iter()
+                .map(|x| (*x, x.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// ### new
+    ///
+    /// Instantiates a new `KeyBindings` from the configured association between action names and keys.
+    /// Actions which are not configured keep their default key.
+    /// Returns error if an action or a key is invalid or if two actions are bound to the same key
+    pub fn new(mapping: &HashMap<String, String>) -> Result<Self, String> {
+        let mut bindings: Self = Self::default();
+        for (name, key) in mapping.iter() {
+            let action: KeyAction = KeyAction::from_name(name.as_str())
+                .ok_or_else(|| format!("Unknown action '{}'", name))?;
+            let key: char = parse_key(key.as_str())
+                .ok_or_else(|| format!("Invalid key '{}' for action '{}'", key, name))?;
+            bindings.keys.insert(action, key);
+        }
+        // Check for conflicts
+        for (i, action) in KeyAction::ALL.iter().enumerate() {
+            let key: char = bindings.key(*action);
+            if let Some(other) = KeyAction::ALL[i + 1..]
+                .iter()
+                .find(|x| bindings.key(**x) == key)
+            {
+                return Err(format!(
+                    "Actions '{}' and '{}' are both bound to key '{}'",
+                    action.name(),
+                    other.name(),
+                    key
+                ));
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// ### key
+    ///
+    /// Returns the key bound to `action`
+    pub fn key(&self, action: KeyAction) -> char {
+        self.keys
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_key())
+    }
+
+    /// ### action
+    ///
+    /// Returns the action bound to `key`, if any
+    pub fn action(&self, key: char) -> Option<KeyAction> {
+        KeyAction::ALL
+            .iter()
+            .find(|x| self.key(**x) == key)
+            .copied()
+    }
+
+    /// ### translate
+    ///
+    /// Translate the key pressed by the user into the default key of the action it is bound to.
+    /// Keys which are not bound to any action are returned as they are
+    pub fn translate(&self, key: char) -> char {
+        self.action(key).map(|x| x.default_key()).unwrap_or(key)
+    }
+}
+
+/// ### parse_key
+///
+/// Parse key from configuration. Only letters can be bound, except for `m`, which is used to mark files
+fn parse_key(key: &str) -> Option<char> {
+    let key: String = key.trim().to_lowercase();
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_lowercase() && ch != 'm' => Some(ch),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_config_keybindings_default() {
+        let bindings: KeyBindings = KeyBindings::default();
+        assert_eq!(bindings.key(KeyAction::Copy), 'c');
+        assert_eq!(bindings.key(KeyAction::DryRun), 'z');
+        assert_eq!(bindings.action('q'), Some(KeyAction::Quit));
+        assert_eq!(bindings.action('m'), None);
+        assert_eq!(
+            KeyBindings::new(&HashMap::new()).ok().unwrap(),
+            KeyBindings::default()
+        );
+        // Default keys must all be different
+        KeyAction::ALL
+            .iter()
+            .for_each(|x| assert_eq!(bindings.action(x.default_key()), Some(*x)));
+    }
+
+    #[test]
+    fn test_config_keybindings_action_names() {
+        KeyAction::ALL
+            .iter()
+            .for_each(|x| assert_eq!(KeyAction::from_name(x.name()), Some(*x)));
+        assert_eq!(KeyAction::from_name("teleport"), None);
+    }
+
+    #[test]
+    fn test_config_keybindings_new() {
+        let bindings: KeyBindings = KeyBindings::new(&mapping(&[
+            ("copy", "e"),
+            ("delete", " D "),
+            ("make_dir", "C"),
+        ]))
+        .ok()
+        .unwrap();
+        assert_eq!(bindings.key(KeyAction::Copy), 'e');
+        assert_eq!(bindings.key(KeyAction::Delete), 'd');
+        assert_eq!(bindings.key(KeyAction::MakeDir), 'c');
+        // Unmapped actions fall back to defaults
+        assert_eq!(bindings.key(KeyAction::Quit), 'q');
+        assert_eq!(bindings.key(KeyAction::Rename), 'r');
+        assert_eq!(bindings.action('q'), Some(KeyAction::Quit));
+    }
+
+    #[test]
+    fn test_config_keybindings_new_conflict() {
+        // Two configured actions on the same key
+        assert_eq!(
+            KeyBindings::new(&mapping(&[("copy", "x"), ("rename", "x")]))
+                .err()
+                .unwrap(),
+            String::from("Actions 'copy' and 'rename' are both bound to key 'x'")
+        );
+        // Configured action on the default key of an unmapped action
+        assert_eq!(
+            KeyBindings::new(&mapping(&[("quit", "x")])).err().unwrap(),
+            String::from("Actions 'quit' and 'exec' are both bound to key 'x'")
+        );
+        // Swapping keys is fine
+        assert!(KeyBindings::new(&mapping(&[("quit", "x"), ("exec", "q")])).is_ok());
+    }
+
+    #[test]
+    fn test_config_keybindings_new_bad_values() {
+        assert_eq!(
+            KeyBindings::new(&mapping(&[("teleport", "x")]))
+                .err()
+                .unwrap(),
+            String::from("Unknown action 'teleport'")
+        );
+        assert_eq!(
+            KeyBindings::new(&mapping(&[("copy", "cc")])).err().unwrap(),
+            String::from("Invalid key 'cc' for action 'copy'")
+        );
+        assert!(KeyBindings::new(&mapping(&[("copy", "")])).is_err());
+        assert!(KeyBindings::new(&mapping(&[("copy", "1")])).is_err());
+        // 'm' is reserved to mark files
+        assert!(KeyBindings::new(&mapping(&[("copy", "m")])).is_err());
+    }
+
+    #[test]
+    fn test_config_keybindings_translate() {
+        let bindings: KeyBindings = KeyBindings::new(&mapping(&[("copy", "e"), ("delete", "c")]))
+            .ok()
+            .unwrap();
+        assert_eq!(bindings.translate('e'), 'c');
+        assert_eq!(bindings.translate('c'), 'e');
+        assert_eq!(bindings.translate('q'), 'q');
+        assert_eq!(bindings.translate('m'), 'm');
+    }
+
+    fn mapping(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+}
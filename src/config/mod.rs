@@ -29,6 +29,7 @@
 pub use params::*;
 
 pub mod bookmarks;
+pub mod keybindings;
 pub mod params;
 pub mod serialization;
 pub mod themes;
@@ -31,7 +31,9 @@ use crate::filetransfer::FileTransferProtocol;
 // Ext
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Deserialize, Serialize, std::fmt::Debug)]
 /// ## UserConfig
@@ -41,6 +43,7 @@ use std::path::PathBuf;
 pub struct UserConfig {
     pub user_interface: UserInterfaceConfig,
     pub remote: RemoteConfig,
+    pub keybindings: Option<HashMap<String, String>>, // @! Since 0.7.0
 }
 
 #[derive(Deserialize, Serialize, std::fmt::Debug)]
@@ -48,13 +51,39 @@ pub struct UserConfig {
 ///
 /// UserInterfaceConfig provides all the keys to configure the user interface
 pub struct UserInterfaceConfig {
-    pub text_editor: PathBuf,
+    #[serde(default)]
+    pub text_editor: PathBuf, // Empty if missing: `$EDITOR` is used
     pub default_protocol: String,
     pub show_hidden_files: bool,
     pub check_for_updates: Option<bool>, // @! Since 0.3.3
     pub group_dirs: Option<String>,
     pub file_fmt: Option<String>, // Refers to local host (for backward compatibility)
     pub remote_file_fmt: Option<String>, // @! Since 0.5.0
+    pub max_parallel_transfers: Option<usize>, // @! Since 0.7.0; local copies only
+    pub resume_transfers: Option<bool>, // @! Since 0.7.0
+    pub prompt_on_file_replace: Option<bool>, // @! Since 0.7.0
+    pub find_max_depth: Option<usize>, // @! Since 0.7.0
+    pub hidden_patterns: Option<Vec<String>>, // @! Since 0.7.0
+    pub last_local_wrkdir: Option<PathBuf>, // @! Since 0.7.0
+    pub known_hosts: Option<PathBuf>, // @! Since 0.7.0
+    pub ssh_config: Option<String>, // @! Since 0.7.0
+    pub strict_host_key_checking: Option<bool>, // @! Since 0.7.0
+    pub size_unit: Option<String>, // @! Since 0.7.0
+    pub transfer_max_attempts: Option<usize>, // @! Since 0.7.0
+    pub max_recents: Option<usize>, // @! Since 0.7.0
+    pub ssh_compression: Option<bool>, // @! Since 0.7.0
+    pub confirm_on_quit: Option<bool>, // @! Since 0.7.0
+    pub bookmarks_sorting: Option<String>, // @! Since 0.7.0
+    pub max_preview_bytes: Option<u64>, // @! Since 0.7.0
+    pub max_edit_bytes: Option<u64>, // @! Since 0.7.0
+    pub startup: Option<String>,  // @! Since 0.7.0
+    pub watch_local_dir: Option<bool>, // @! Since 0.7.0
+    pub create_missing_wrkdir: Option<bool>, // @! Since 0.7.0
+    pub read_only: Option<bool>,  // @! Since 0.7.0
+    pub show_banner: Option<bool>, // @! Since 0.7.0
+    pub lock_dirs_first: Option<bool>, // @! Since 0.7.0
+    pub transfer_bell: Option<bool>, // @! Since 0.7.0
+    pub transfer_bell_threshold: Option<u64>, // @! Since 0.7.0
 }
 
 #[derive(Deserialize, Serialize, std::fmt::Debug)]
@@ -70,6 +99,7 @@ impl Default for UserConfig {
         UserConfig {
             user_interface: UserInterfaceConfig::default(),
             remote: RemoteConfig::default(),
+            keybindings: None,
         }
     }
 }
@@ -87,6 +117,31 @@ impl Default for UserInterfaceConfig {
             group_dirs: None,
             file_fmt: None,
             remote_file_fmt: None,
+            max_parallel_transfers: None,
+            resume_transfers: None,
+            prompt_on_file_replace: None,
+            find_max_depth: None,
+            hidden_patterns: None,
+            last_local_wrkdir: None,
+            known_hosts: None,
+            ssh_config: None,
+            strict_host_key_checking: None,
+            size_unit: None,
+            transfer_max_attempts: None,
+            max_recents: None,
+            ssh_compression: None,
+            confirm_on_quit: None,
+            bookmarks_sorting: None,
+            max_preview_bytes: None,
+            max_edit_bytes: None,
+            startup: None,
+            watch_local_dir: None,
+            create_missing_wrkdir: None,
+            read_only: None,
+            show_banner: None,
+            lock_dirs_first: None,
+            transfer_bell: None,
+            transfer_bell_threshold: None,
         }
     }
 }
@@ -99,6 +154,47 @@ impl Default for RemoteConfig {
     }
 }
 
+/// ## StartupActivity
+///
+/// StartupActivity defines what termscp opens when no remote is provided from CLI
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum StartupActivity {
+    #[default]
+    Authentication,
+    Setup,
+    Bookmark(String), // Connect to the bookmark with this name
+}
+
+impl fmt::Display for StartupActivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartupActivity::Authentication => f.write_str("auth"),
+            StartupActivity::Setup => f.write_str("setup"),
+            StartupActivity::Bookmark(name) => write!(f, "bookmark:{}", name),
+        }
+    }
+}
+
+impl FromStr for StartupActivity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((kind, name)) if kind.eq_ignore_ascii_case("bookmark") && !name.is_empty() => {
+                Ok(StartupActivity::Bookmark(name.to_string()))
+            }
+            Some(_) => Err(format!("Invalid startup activity \"{}\"", s)),
+            None => match s.to_ascii_lowercase().as_str() {
+                "auth" => Ok(StartupActivity::Authentication),
+                "setup" => Ok(StartupActivity::Setup),
+                _ => Err(format!(
+                    "Invalid startup activity \"{}\" (expected auth, setup or bookmark:<name>)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
 // Tests
 
 #[cfg(test)]
@@ -123,6 +219,31 @@ mod tests {
             group_dirs: Some(String::from("first")),
             file_fmt: Some(String::from("{NAME}")),
             remote_file_fmt: Some(String::from("{USER}")),
+            max_parallel_transfers: Some(4),
+            resume_transfers: Some(true),
+            prompt_on_file_replace: Some(false),
+            find_max_depth: Some(8),
+            hidden_patterns: Some(vec![String::from("*.bak")]),
+            last_local_wrkdir: Some(PathBuf::from("/home/omar")),
+            known_hosts: Some(PathBuf::from("/home/omar/.ssh/known_hosts")),
+            ssh_config: Some(String::from("~/.ssh/config")),
+            strict_host_key_checking: Some(true),
+            size_unit: Some(String::from("binary")),
+            transfer_max_attempts: Some(5),
+            max_recents: Some(8),
+            ssh_compression: Some(true),
+            confirm_on_quit: Some(false),
+            bookmarks_sorting: Some(String::from("recent")),
+            max_preview_bytes: Some(1048576),
+            max_edit_bytes: Some(10485760),
+            startup: Some(String::from("setup")),
+            watch_local_dir: Some(true),
+            create_missing_wrkdir: Some(true),
+            read_only: Some(true),
+            show_banner: Some(true),
+            lock_dirs_first: Some(true),
+            transfer_bell: Some(true),
+            transfer_bell_threshold: Some(30),
         };
         assert_eq!(ui.default_protocol, String::from("SFTP"));
         assert_eq!(ui.text_editor, PathBuf::from("nano"));
@@ -133,6 +254,7 @@ mod tests {
         let cfg: UserConfig = UserConfig {
             user_interface: ui,
             remote: remote,
+            keybindings: Some(HashMap::new()),
         };
         assert_eq!(
             *cfg.remote
@@ -151,5 +273,76 @@ mod tests {
             cfg.user_interface.remote_file_fmt,
             Some(String::from("{USER}"))
         );
+        assert_eq!(cfg.user_interface.max_parallel_transfers, Some(4));
+        assert_eq!(cfg.user_interface.resume_transfers, Some(true));
+        assert_eq!(cfg.user_interface.prompt_on_file_replace, Some(false));
+        assert_eq!(cfg.user_interface.find_max_depth, Some(8));
+        assert_eq!(
+            cfg.user_interface.hidden_patterns,
+            Some(vec![String::from("*.bak")])
+        );
+        assert_eq!(
+            cfg.user_interface.last_local_wrkdir,
+            Some(PathBuf::from("/home/omar"))
+        );
+        assert_eq!(
+            cfg.user_interface.known_hosts,
+            Some(PathBuf::from("/home/omar/.ssh/known_hosts"))
+        );
+        assert_eq!(cfg.user_interface.strict_host_key_checking, Some(true));
+        assert_eq!(cfg.user_interface.size_unit, Some(String::from("binary")));
+        assert_eq!(cfg.user_interface.transfer_max_attempts, Some(5));
+        assert_eq!(cfg.user_interface.max_recents, Some(8));
+        assert_eq!(cfg.user_interface.ssh_compression, Some(true));
+        assert_eq!(cfg.user_interface.confirm_on_quit, Some(false));
+        assert_eq!(
+            cfg.user_interface.bookmarks_sorting,
+            Some(String::from("recent"))
+        );
+        assert_eq!(cfg.user_interface.max_preview_bytes, Some(1048576));
+        assert_eq!(cfg.user_interface.max_edit_bytes, Some(10485760));
+        assert_eq!(cfg.user_interface.startup, Some(String::from("setup")));
+        assert_eq!(cfg.user_interface.watch_local_dir, Some(true));
+        assert_eq!(cfg.user_interface.create_missing_wrkdir, Some(true));
+        assert_eq!(cfg.user_interface.read_only, Some(true));
+        assert_eq!(cfg.user_interface.show_banner, Some(true));
+        assert_eq!(cfg.user_interface.lock_dirs_first, Some(true));
+        assert_eq!(cfg.user_interface.transfer_bell, Some(true));
+        assert_eq!(cfg.user_interface.transfer_bell_threshold, Some(30));
+    }
+
+    #[test]
+    fn test_config_mod_startup_activity() {
+        assert_eq!(
+            StartupActivity::from_str("auth").unwrap(),
+            StartupActivity::Authentication
+        );
+        assert_eq!(
+            StartupActivity::from_str("SETUP").unwrap(),
+            StartupActivity::Setup
+        );
+        assert_eq!(
+            StartupActivity::from_str("bookmark:raspberry").unwrap(),
+            StartupActivity::Bookmark(String::from("raspberry"))
+        );
+        // Bookmark names are case sensitive
+        assert_eq!(
+            StartupActivity::from_str("Bookmark:My Server").unwrap(),
+            StartupActivity::Bookmark(String::from("My Server"))
+        );
+        assert!(StartupActivity::from_str("bookmark:").is_err());
+        assert!(StartupActivity::from_str("filetransfer").is_err());
+        assert_eq!(StartupActivity::default(), StartupActivity::Authentication);
+        // Display
+        for startup in [
+            StartupActivity::Authentication,
+            StartupActivity::Setup,
+            StartupActivity::Bookmark(String::from("raspberry")),
+        ] {
+            assert_eq!(
+                StartupActivity::from_str(startup.to_string().as_str()).unwrap(),
+                startup
+            );
+        }
     }
 }
@@ -97,7 +97,8 @@ where
             ))
         }
     };
-    trace!("Serialized new bookmarks data: {}", data);
+    // NOTE: don't log data, since it may contain passwords
+    trace!("Serialized new bookmarks data ({} bytes)", data.len());
     // Write file
     match writable.write_all(data.as_bytes()) {
         Ok(_) => Ok(()),
@@ -123,7 +124,7 @@ where
             err.to_string(),
         ));
     }
-    trace!("Read bookmarks from file: {}", data);
+    trace!("Read bookmarks from file ({} bytes)", data.len());
     // Deserialize
     match toml::de::from_str(data.as_str()) {
         Ok(deserialized) => {
@@ -211,6 +212,49 @@ mod tests {
             cfg.user_interface.remote_file_fmt,
             Some(String::from("{NAME} {USER}")),
         );
+        assert_eq!(cfg.user_interface.max_parallel_transfers, Some(4));
+        assert_eq!(cfg.user_interface.resume_transfers, Some(true));
+        assert_eq!(cfg.user_interface.prompt_on_file_replace, Some(false));
+        assert_eq!(cfg.user_interface.find_max_depth, Some(8));
+        assert_eq!(
+            cfg.user_interface.hidden_patterns,
+            Some(vec![String::from("*.bak"), String::from("*~")])
+        );
+        assert_eq!(
+            cfg.user_interface.last_local_wrkdir,
+            Some(PathBuf::from("/home/omar/Documents"))
+        );
+        assert_eq!(
+            cfg.user_interface.known_hosts,
+            Some(PathBuf::from("/home/omar/.ssh/known_hosts"))
+        );
+        assert_eq!(
+            cfg.user_interface.ssh_config,
+            Some(String::from("~/.ssh/config"))
+        );
+        assert_eq!(cfg.user_interface.strict_host_key_checking, Some(true));
+        assert_eq!(cfg.user_interface.size_unit, Some(String::from("binary")));
+        assert_eq!(cfg.user_interface.transfer_max_attempts, Some(5));
+        assert_eq!(cfg.user_interface.max_recents, Some(8));
+        assert_eq!(cfg.user_interface.ssh_compression, Some(true));
+        assert_eq!(cfg.user_interface.confirm_on_quit, Some(false));
+        assert_eq!(
+            cfg.user_interface.bookmarks_sorting,
+            Some(String::from("protocol"))
+        );
+        assert_eq!(cfg.user_interface.max_preview_bytes, Some(1048576));
+        assert_eq!(cfg.user_interface.max_edit_bytes, Some(10485760));
+        assert_eq!(
+            cfg.user_interface.startup,
+            Some(String::from("bookmark:raspberry"))
+        );
+        assert_eq!(cfg.user_interface.watch_local_dir, Some(true));
+        assert_eq!(cfg.user_interface.create_missing_wrkdir, Some(true));
+        assert_eq!(cfg.user_interface.read_only, Some(true));
+        assert_eq!(cfg.user_interface.show_banner, Some(true));
+        assert_eq!(cfg.user_interface.lock_dirs_first, Some(true));
+        assert_eq!(cfg.user_interface.transfer_bell, Some(true));
+        assert_eq!(cfg.user_interface.transfer_bell_threshold, Some(30));
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -247,6 +291,31 @@ mod tests {
         assert!(cfg.user_interface.check_for_updates.is_none());
         assert!(cfg.user_interface.file_fmt.is_none());
         assert!(cfg.user_interface.remote_file_fmt.is_none());
+        assert!(cfg.user_interface.max_parallel_transfers.is_none());
+        assert!(cfg.user_interface.resume_transfers.is_none());
+        assert!(cfg.user_interface.prompt_on_file_replace.is_none());
+        assert!(cfg.user_interface.find_max_depth.is_none());
+        assert!(cfg.user_interface.hidden_patterns.is_none());
+        assert!(cfg.user_interface.last_local_wrkdir.is_none());
+        assert!(cfg.user_interface.known_hosts.is_none());
+        assert!(cfg.user_interface.ssh_config.is_none());
+        assert!(cfg.user_interface.strict_host_key_checking.is_none());
+        assert!(cfg.user_interface.size_unit.is_none());
+        assert!(cfg.user_interface.transfer_max_attempts.is_none());
+        assert!(cfg.user_interface.max_recents.is_none());
+        assert!(cfg.user_interface.ssh_compression.is_none());
+        assert!(cfg.user_interface.confirm_on_quit.is_none());
+        assert!(cfg.user_interface.bookmarks_sorting.is_none());
+        assert!(cfg.user_interface.max_preview_bytes.is_none());
+        assert!(cfg.user_interface.max_edit_bytes.is_none());
+        assert!(cfg.user_interface.startup.is_none());
+        assert!(cfg.user_interface.watch_local_dir.is_none());
+        assert!(cfg.user_interface.create_missing_wrkdir.is_none());
+        assert!(cfg.user_interface.read_only.is_none());
+        assert!(cfg.user_interface.show_banner.is_none());
+        assert!(cfg.user_interface.lock_dirs_first.is_none());
+        assert!(cfg.user_interface.transfer_bell.is_none());
+        assert!(cfg.user_interface.transfer_bell_threshold.is_none());
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -265,6 +334,24 @@ mod tests {
         assert!(cfg.remote.ssh_keys.get(&String::from("1.1.1.1")).is_none());
     }
 
+    #[test]
+    fn test_config_serialization_params_deserialize_no_text_editor() {
+        let mut toml_file: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
+        let file_content: &str = r#"
+        [user_interface]
+        default_protocol = "SFTP"
+        show_hidden_files = false
+
+        [remote.ssh_keys]
+        "#;
+        toml_file.write_all(file_content.as_bytes()).unwrap();
+        toml_file.as_file().sync_all().unwrap();
+        toml_file.as_file().seek(SeekFrom::Start(0)).unwrap();
+        // Missing text editor is empty
+        let cfg: UserConfig = deserialize(Box::new(toml_file)).ok().unwrap();
+        assert_eq!(cfg.user_interface.text_editor, PathBuf::new());
+    }
+
     #[test]
     fn test_config_serialization_params_deserialize_nok() {
         let toml_file: tempfile::NamedTempFile = create_bad_toml_bookmarks_params();
@@ -321,6 +408,31 @@ mod tests {
         group_dirs = "last"
         file_fmt = "{NAME} {PEX}"
         remote_file_fmt = "{NAME} {USER}"
+        max_parallel_transfers = 4
+        resume_transfers = true
+        prompt_on_file_replace = false
+        find_max_depth = 8
+        hidden_patterns = ["*.bak", "*~"]
+        last_local_wrkdir = "/home/omar/Documents"
+        known_hosts = "/home/omar/.ssh/known_hosts"
+        ssh_config = "~/.ssh/config"
+        strict_host_key_checking = true
+        size_unit = "binary"
+        transfer_max_attempts = 5
+        max_recents = 8
+        ssh_compression = true
+        confirm_on_quit = false
+        bookmarks_sorting = "protocol"
+        max_preview_bytes = 1048576
+        max_edit_bytes = 10485760
+        startup = "bookmark:raspberry"
+        watch_local_dir = true
+        create_missing_wrkdir = true
+        read_only = true
+        show_banner = true
+        lock_dirs_first = true
+        transfer_bell = true
+        transfer_bell_threshold = 30
 
         [remote.ssh_keys]
         "192.168.1.31" = "/home/omar/.ssh/raspberry.key"
@@ -389,12 +501,24 @@ mod tests {
         assert_eq!(host.protocol, String::from("SFTP"));
         assert_eq!(host.username, String::from("root"));
         assert_eq!(*host.password.as_ref().unwrap(), String::from("mypassword"));
+        // Bookmarks without connection options are still valid
+        assert!(host.ssh_key_path.is_none());
+        assert!(host.proxy_jump.is_none());
+        assert!(host.host_key_fingerprint.is_none());
+        assert!(host.last_used.is_none());
         let host: &Bookmark = hosts.bookmarks.get("msi-estrem").unwrap();
         assert_eq!(host.address, String::from("192.168.1.30"));
         assert_eq!(host.port, 22);
         assert_eq!(host.protocol, String::from("SFTP"));
         assert_eq!(host.username, String::from("cvisintin"));
         assert_eq!(*host.password.as_ref().unwrap(), String::from("mysecret"));
+        assert_eq!(
+            host.ssh_key_path,
+            Some(PathBuf::from("/home/cvisintin/.ssh/id_ed25519"))
+        );
+        assert_eq!(host.proxy_jump.as_deref().unwrap(), "omar@bastion:2222");
+        assert_eq!(host.host_key_fingerprint.as_deref().unwrap(), "SHA256:abcd");
+        assert_eq!(host.last_used, Some(1623321000));
         let host: &Bookmark = hosts.bookmarks.get("aws-server-prod1").unwrap();
         assert_eq!(host.address, String::from("51.23.67.12"));
         assert_eq!(host.port, 21);
@@ -424,6 +548,10 @@ mod tests {
                 protocol: String::from("SFTP"),
                 username: String::from("root"),
                 password: None,
+                ssh_key_path: None,
+                proxy_jump: None,
+                host_key_fingerprint: None,
+                last_used: None,
             },
         );
         bookmarks.insert(
@@ -434,6 +562,10 @@ mod tests {
                 protocol: String::from("SFTP"),
                 username: String::from("cvisintin"),
                 password: Some(String::from("password")),
+                ssh_key_path: Some(PathBuf::from("/home/cvisintin/.ssh/id_rsa")),
+                proxy_jump: Some(String::from("bastion")),
+                host_key_fingerprint: Some(String::from("SHA256:abcd")),
+                last_used: Some(1623321000),
             },
         );
         let mut recents: HashMap<String, Bookmark> = HashMap::with_capacity(1);
@@ -445,12 +577,30 @@ mod tests {
                 protocol: String::from("SCP"),
                 username: String::from("omar"),
                 password: Some(String::from("aaa")),
+                ssh_key_path: None,
+                proxy_jump: None,
+                host_key_fingerprint: None,
+                last_used: None,
             },
         );
         let tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
+        let (reader, writer) = create_file_ioers(tmpfile.path());
         // Serialize
         let hosts: UserHosts = UserHosts { bookmarks, recents };
-        assert!(serialize(&hosts, Box::new(tmpfile)).is_ok());
+        assert!(serialize(&hosts, Box::new(writer)).is_ok());
+        // Connection options survive the round-trip
+        let hosts: UserHosts = deserialize(Box::new(reader)).ok().unwrap();
+        let host: &Bookmark = hosts.bookmarks.get("msi-estrem").unwrap();
+        assert_eq!(
+            host.ssh_key_path,
+            Some(PathBuf::from("/home/cvisintin/.ssh/id_rsa"))
+        );
+        assert_eq!(host.proxy_jump.as_deref().unwrap(), "bastion");
+        assert_eq!(host.host_key_fingerprint.as_deref().unwrap(), "SHA256:abcd");
+        let host: &Bookmark = hosts.bookmarks.get("raspberrypi2").unwrap();
+        assert!(host.ssh_key_path.is_none());
+        assert!(host.proxy_jump.is_none());
+        assert!(host.host_key_fingerprint.is_none());
     }
 
     #[test]
@@ -483,7 +633,7 @@ mod tests {
         let file_content: &str = r#"
         [bookmarks]
         raspberrypi2 = { address = "192.168.1.31", port = 22, protocol = "SFTP", username = "root", password = "mypassword" }
-        msi-estrem = { address = "192.168.1.30", port = 22, protocol = "SFTP", username = "cvisintin", password = "mysecret" }
+        msi-estrem = { address = "192.168.1.30", port = 22, protocol = "SFTP", username = "cvisintin", password = "mysecret", ssh_key_path = "/home/cvisintin/.ssh/id_ed25519", proxy_jump = "omar@bastion:2222", host_key_fingerprint = "SHA256:abcd", last_used = 1623321000 }
         aws-server-prod1 = { address = "51.23.67.12", port = 21, protocol = "FTPS", username = "aws001" }
 
         [recents]
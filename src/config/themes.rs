@@ -211,10 +211,11 @@ fn deserialize_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    // NOTE: an owned string is required by formats which can't borrow from input (e.g. JSON read from a file)
+    let s: String = Deserialize::deserialize(deserializer)?;
     // Parse color
-    match parse_color(s) {
-        None => Err(DeError::custom("Invalid color")),
+    match parse_color(s.as_str()) {
+        None => Err(DeError::custom(format!("Invalid color '{}'", s))),
         Some(color) => Ok(color),
     }
 }
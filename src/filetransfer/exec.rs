@@ -0,0 +1,297 @@
+//! ## Exec
+//!
+//! `exec` runs commands on the remote host through the exec channel of an SSH session
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{FileTransferError, FileTransferErrorType};
+
+use ssh2::{Channel, Session};
+use std::io::{self, ErrorKind, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Maximum time a command may run for; once elapsed, the command is interrupted
+const EXEC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// ## ExecOutput
+///
+/// ExecOutput describes the outcome of a command executed on the remote host
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+impl ExecOutput {
+    /// ### lines
+    ///
+    /// Assemble the lines to display for the output of `cmd`:
+    /// the command, its standard output, its standard error (if any) and its exit code
+    pub fn lines(&self, cmd: &str) -> Vec<String> {
+        let mut lines: Vec<String> = vec![format!("$ {}", cmd)];
+        lines.extend(self.stdout.lines().map(String::from));
+        if !self.stderr.trim().is_empty() {
+            lines.push(String::from("--- stderr ---"));
+            lines.extend(self.stderr.lines().map(String::from));
+        }
+        if self.stdout.trim().is_empty() && self.stderr.trim().is_empty() {
+            lines.push(String::from("(no output)"));
+        }
+        lines.push(format!("[exit code: {}]", self.exit_code));
+        lines
+    }
+}
+
+/// ### channel_exec
+///
+/// Execute `cmd` on a new channel of `session`, collecting its output and its exit code.
+/// This operation is blocking, up to `EXEC_TIMEOUT`
+pub(crate) fn channel_exec(session: &Session, cmd: &str) -> Result<ExecOutput, FileTransferError> {
+    debug!("Running command: {}", cmd);
+    let mut channel: Channel = session.channel_session().map_err(|err| {
+        FileTransferError::new_ex(
+            FileTransferErrorType::ProtocolError,
+            format!("Could not open channel: {}", err),
+        )
+    })?;
+    channel.exec(cmd).map_err(|err| {
+        FileTransferError::new_ex(
+            FileTransferErrorType::ProtocolError,
+            format!("Could not execute command \"{}\": {}", cmd, err),
+        )
+    })?;
+    // Read output; stdout and stderr are read together, so that a command filling the stderr pipe can't stall
+    session.set_blocking(false);
+    let result: io::Result<(Vec<u8>, Vec<u8>)> = read_streams(
+        &mut channel.stream(0),
+        &mut channel.stderr(),
+        &|| channel.eof(),
+        EXEC_TIMEOUT,
+    );
+    session.set_blocking(true);
+    let (stdout, stderr): (Vec<u8>, Vec<u8>) = match result {
+        Ok(output) => output,
+        Err(err) => {
+            let _ = channel.close();
+            return Err(FileTransferError::new_ex(
+                FileTransferErrorType::ProtocolError,
+                format!("Could not read output: {}", err),
+            ));
+        }
+    };
+    let mut output: ExecOutput = ExecOutput {
+        stdout: String::from_utf8_lossy(stdout.as_slice()).to_string(),
+        stderr: String::from_utf8_lossy(stderr.as_slice()).to_string(),
+        exit_code: -1,
+    };
+    // Wait close and get exit status
+    let _ = channel.wait_close();
+    output.exit_code = channel.exit_status().unwrap_or(-1);
+    debug!("Command exited with {}", output.exit_code);
+    Ok(output)
+}
+
+/// ### read_streams
+///
+/// Read `stdout` and `stderr`, which must be non-blocking, alternately until `eof` reports the end of both streams.
+/// Fails with `TimedOut` if the streams are still open once `timeout` has elapsed
+fn read_streams(
+    stdout: &mut dyn Read,
+    stderr: &mut dyn Read,
+    eof: &dyn Fn() -> bool,
+    timeout: Duration,
+) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let started: Instant = Instant::now();
+    let mut output: (Vec<u8>, Vec<u8>) = (Vec::new(), Vec::new());
+    let mut buffer: [u8; 8192] = [0; 8192];
+    loop {
+        let read: bool = read_available(stdout, &mut output.0, &mut buffer)?
+            | read_available(stderr, &mut output.1, &mut buffer)?;
+        if read {
+            continue;
+        }
+        if eof() {
+            return Ok(output);
+        }
+        if started.elapsed() >= timeout {
+            return Err(io::Error::new(
+                ErrorKind::TimedOut,
+                format!(
+                    "the command didn't terminate within {} seconds",
+                    timeout.as_secs()
+                ),
+            ));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// ### read_available
+///
+/// Read the data currently available on the non-blocking `stream` into `dest`.
+/// Returns whether any data has been read
+fn read_available(
+    stream: &mut dyn Read,
+    dest: &mut Vec<u8>,
+    buffer: &mut [u8],
+) -> io::Result<bool> {
+    match stream.read(buffer) {
+        Ok(0) => Ok(false),
+        Ok(bytes) => {
+            dest.extend_from_slice(&buffer[..bytes]);
+            Ok(true)
+        }
+        Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) => {
+            Ok(false)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::collections::VecDeque;
+
+    /// Non-blocking stream returning `chunks` in order, then EOF
+    struct MockStream {
+        chunks: VecDeque<io::Result<Vec<u8>>>,
+    }
+
+    impl MockStream {
+        fn new(chunks: Vec<io::Result<&[u8]>>) -> Self {
+            Self {
+                chunks: chunks.into_iter().map(|x| x.map(|x| x.to_vec())).collect(),
+            }
+        }
+    }
+
+    impl Read for MockStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.chunks.pop_front() {
+                None => Ok(0),
+                Some(Ok(chunk)) => {
+                    buf[..chunk.len()].copy_from_slice(chunk.as_slice());
+                    Ok(chunk.len())
+                }
+                Some(Err(err)) => Err(err),
+            }
+        }
+    }
+
+    fn would_block<T>() -> io::Result<T> {
+        Err(io::Error::from(ErrorKind::WouldBlock))
+    }
+
+    #[test]
+    fn test_filetransfer_exec_read_streams() {
+        // Streams are read alternately
+        let mut stdout = MockStream::new(vec![Ok(b"a.txt\n"), would_block(), Ok(b"b.txt\n")]);
+        let mut stderr = MockStream::new(vec![would_block(), Ok(b"denied\n"), Ok(b"failed\n")]);
+        let (out, err) =
+            read_streams(&mut stdout, &mut stderr, &|| true, Duration::from_secs(5)).unwrap();
+        assert_eq!(out.as_slice(), b"a.txt\nb.txt\n");
+        assert_eq!(err.as_slice(), b"denied\nfailed\n");
+        // Errors
+        let mut stdout = MockStream::new(vec![Ok(b"a.txt\n")]);
+        let mut stderr = MockStream::new(vec![Err(io::Error::from(ErrorKind::ConnectionReset))]);
+        assert_eq!(
+            read_streams(&mut stdout, &mut stderr, &|| true, Duration::from_secs(5))
+                .err()
+                .unwrap()
+                .kind(),
+            ErrorKind::ConnectionReset
+        );
+    }
+
+    #[test]
+    fn test_filetransfer_exec_read_streams_timeout() {
+        let mut stdout = MockStream::new(vec![Ok(b"sleeping...\n")]);
+        let mut stderr = MockStream::new(vec![]);
+        assert_eq!(
+            read_streams(
+                &mut stdout,
+                &mut stderr,
+                &|| false,
+                Duration::from_millis(50)
+            )
+            .err()
+            .unwrap()
+            .kind(),
+            ErrorKind::TimedOut
+        );
+    }
+
+    #[test]
+    fn test_filetransfer_exec_output_lines() {
+        let output: ExecOutput = ExecOutput {
+            stdout: String::from("a.txt\nb.txt\n"),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        assert_eq!(
+            output.lines("ls"),
+            vec![
+                String::from("$ ls"),
+                String::from("a.txt"),
+                String::from("b.txt"),
+                String::from("[exit code: 0]"),
+            ]
+        );
+        // With stderr
+        let output: ExecOutput = ExecOutput {
+            stdout: String::from("a.txt\n"),
+            stderr: String::from("ls: cannot access 'c.txt': No such file or directory\n"),
+            exit_code: 2,
+        };
+        assert_eq!(
+            output.lines("ls a.txt c.txt"),
+            vec![
+                String::from("$ ls a.txt c.txt"),
+                String::from("a.txt"),
+                String::from("--- stderr ---"),
+                String::from("ls: cannot access 'c.txt': No such file or directory"),
+                String::from("[exit code: 2]"),
+            ]
+        );
+        // No output
+        let output: ExecOutput = ExecOutput {
+            exit_code: 1,
+            ..ExecOutput::default()
+        };
+        assert_eq!(
+            output.lines("false"),
+            vec![
+                String::from("$ false"),
+                String::from("(no output)"),
+                String::from("[exit code: 1]"),
+            ]
+        );
+    }
+}
@@ -27,7 +27,7 @@
  */
 use super::{FileTransfer, FileTransferError, FileTransferErrorType};
 use crate::fs::{FsDirectory, FsEntry, FsFile};
-use crate::utils::fmt::{fmt_time, shadow_password};
+use crate::utils::fmt::{fmt_time, redact_password};
 use crate::utils::parser::{parse_datetime, parse_lstime};
 
 // Includes
@@ -451,7 +451,7 @@ impl FileTransfer for FtpFileTransfer {
         info!(
             "Signin in with username: {}, password: {}",
             username,
-            shadow_password(password.as_str())
+            redact_password(password.as_str())
         );
         if let Err(err) = stream.login(username.as_str(), password.as_str()) {
             error!("Login failed: {}", err);
@@ -734,6 +734,17 @@ impl FileTransfer for FtpFileTransfer {
         }
     }
 
+    /// ### symlink
+    ///
+    /// Create a symlink at `src` pointing at `dst`
+    fn symlink(&mut self, _src: &Path, _dst: &Path) -> Result<(), FileTransferError> {
+        // FTP doesn't support symlinks
+        debug!("SYMLINK issues (will fail, since unsupported)");
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+
     /// ### stat
     ///
     /// Stat file and return FsEntry
@@ -936,12 +947,6 @@ mod tests {
         // List dir
         let list: Vec<FsEntry> = ftp.list_dir(PathBuf::from("/home").as_path()).ok().unwrap();
         assert_eq!(list.len(), 2);
-        // Find
-        assert!(ftp.change_dir(PathBuf::from("/").as_path()).is_ok());
-        assert_eq!(ftp.find("*.txt").ok().unwrap().len(), 1);
-        assert_eq!(ftp.find("*.md").ok().unwrap().len(), 1);
-        assert_eq!(ftp.find("*.jpeg").ok().unwrap().len(), 0);
-        assert!(ftp.change_dir(PathBuf::from("/home").as_path()).is_ok());
         // Rename
         assert!(ftp.mkdir(PathBuf::from("/uploads").as_path()).is_ok());
         assert!(ftp
@@ -1310,6 +1315,13 @@ mod tests {
             .is_err());
         assert!(ftp.pwd().is_err());
         assert!(ftp.stat(Path::new("/tmp")).is_err());
+        assert_eq!(
+            ftp.symlink(Path::new("/tmp/omar.txt"), Path::new("/omar.txt"))
+                .err()
+                .unwrap()
+                .kind(),
+            FileTransferErrorType::UnsupportedFeature
+        );
         assert!(ftp.recv_file(&file).is_err());
         assert!(ftp.send_file(&file, Path::new("/tmp/omar.txt")).is_err());
         let (_, temp): (FsFile, tempfile::NamedTempFile) = create_sample_file_entry();
@@ -26,19 +26,28 @@
  * SOFTWARE.
  */
 // locals
-use crate::fs::{FsEntry, FsFile};
+use crate::fs::{DiskUsage, FsEntry, FsFile};
+use crate::utils::fmt::shell_quote;
+use crate::utils::parser::parse_df;
 // ext
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
-use wildmatch::WildMatch;
 // exports
+pub mod exec;
 pub mod ftp_transfer;
 pub mod params;
+pub mod proxy;
+pub mod read_only;
+pub mod resume;
+pub mod retry;
 pub mod scp_transfer;
 pub mod sftp_transfer;
+mod ssh;
+pub mod wrkdir;
 
-pub use params::FileTransferParams;
+pub use exec::ExecOutput;
+pub use params::{FileTransferParams, TransferOpts};
 
 /// ## FileTransferProtocol
 ///
@@ -86,6 +95,12 @@ pub enum FileTransferErrorType {
     DirStatFailed,
     #[error("Failed to create file")]
     FileCreateDenied,
+    #[error("Unknown host key")]
+    HostKeyUnknown,
+    #[error("Host key has changed")]
+    HostKeyMismatch,
+    #[error("Host key has been revoked")]
+    HostKeyRevoked,
     #[error("No such file or directory")]
     NoSuchFileOrDirectory,
     #[error("Not enough permissions")]
@@ -129,7 +144,7 @@ impl std::fmt::Display for FileTransferError {
 ///
 /// File transfer trait must be implemented by all the file transfers and defines the method used by a generic file transfer
 
-pub trait FileTransfer {
+pub trait FileTransfer: Send {
     /// ### connect
     ///
     /// Connect to the remote server
@@ -193,6 +208,11 @@ pub trait FileTransfer {
     /// Rename file or a directory
     fn rename(&mut self, file: &FsEntry, dst: &Path) -> Result<(), FileTransferError>;
 
+    /// ### symlink
+    ///
+    /// Create a symlink at `src` pointing at `dst`
+    fn symlink(&mut self, src: &Path, dst: &Path) -> Result<(), FileTransferError>;
+
     /// ### stat
     ///
     /// Stat file and return FsEntry
@@ -203,6 +223,17 @@ pub trait FileTransfer {
     /// Execute a command on remote host
     fn exec(&mut self, cmd: &str) -> Result<String, FileTransferError>;
 
+    /// ### exec_output
+    ///
+    /// Execute a command in the remote working directory, collecting its standard output,
+    /// its standard error and its exit code.
+    /// By default `UnsupportedFeature` is returned
+    fn exec_output(&mut self, _cmd: &str) -> Result<ExecOutput, FileTransferError> {
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+
     /// ### send_file
     ///
     /// Send file to remote
@@ -221,6 +252,95 @@ pub trait FileTransfer {
     /// Returns file and its size
     fn recv_file(&mut self, file: &FsFile) -> Result<Box<dyn Read>, FileTransferError>;
 
+    /// ### send_file_at
+    ///
+    /// Send file to remote, writing from `offset`; the remote file is not truncated.
+    /// This is used to resume interrupted uploads.
+    /// Protocols which support ranged transfers must implement both this method and `recv_file_at`;
+    /// by default `UnsupportedFeature` is returned
+    fn send_file_at(
+        &mut self,
+        _local: &FsFile,
+        _file_name: &Path,
+        _offset: u64,
+    ) -> Result<Box<dyn Write>, FileTransferError> {
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+
+    /// ### recv_file_at
+    ///
+    /// Receive file from remote, reading from `offset`.
+    /// This is used to resume interrupted downloads.
+    /// By default `UnsupportedFeature` is returned
+    fn recv_file_at(
+        &mut self,
+        _file: &FsFile,
+        _offset: u64,
+    ) -> Result<Box<dyn Read>, FileTransferError> {
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+
+    /// ### disk_usage
+    ///
+    /// Get the usage of the file system which contains `path`.
+    /// By default this is obtained running `df` on the remote host, so it's available only if `exec` is supported
+    fn disk_usage(&mut self, path: &Path) -> Result<DiskUsage, FileTransferError> {
+        let output: String = self.exec(format!("df -Pk {}", shell_quote(path)).as_str())?;
+        parse_df(output.as_str()).ok_or_else(|| {
+            FileTransferError::new_ex(
+                FileTransferErrorType::ProtocolError,
+                String::from("could not parse df output"),
+            )
+        })
+    }
+
+    /// ### chmod
+    ///
+    /// Change the permissions (user, group, others) of the file at `path`.
+    /// By default this is done running `chmod` on the remote host, so it's available only if `exec` is supported
+    fn chmod(&mut self, path: &Path, pex: (u8, u8, u8)) -> Result<(), FileTransferError> {
+        let output: String = self.exec(
+            format!(
+                "chmod {}{}{} {}; echo $?",
+                pex.0,
+                pex.1,
+                pex.2,
+                shell_quote(path)
+            )
+            .as_str(),
+        )?;
+        match output.trim().lines().last() {
+            Some("0") => Ok(()),
+            _ => Err(FileTransferError::new_ex(
+                FileTransferErrorType::PexError,
+                format!("\"{}\"", path.display()),
+            )),
+        }
+    }
+
+    /// ### trust_host_key
+    ///
+    /// Trust the host key which made the last connection fail with `HostKeyUnknown` or `HostKeyMismatch`,
+    /// writing it to the known hosts file. The next connection to the host will then succeed.
+    /// By default `UnsupportedFeature` is returned
+    fn trust_host_key(&mut self) -> Result<(), FileTransferError> {
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+
+    /// ### host_key_fingerprint
+    ///
+    /// Returns the fingerprint of the host key accepted when connecting, if the protocol has host keys.
+    /// By default `None` is returned
+    fn host_key_fingerprint(&self) -> Option<String> {
+        None
+    }
+
     /// ### on_sent
     ///
     /// Finalize send method.
@@ -238,66 +358,14 @@ pub trait FileTransfer {
     /// This mighe be necessary for some protocols.
     /// You must call this method each time you want to finalize the read of the remote file.
     fn on_recv(&mut self, readable: Box<dyn Read>) -> Result<(), FileTransferError>;
+}
 
-    /// ### find
-    ///
-    /// Find files from current directory (in all subdirectories) whose name matches the provided search
-    /// Search supports wildcards ('?', '*')
-    fn find(&mut self, search: &str) -> Result<Vec<FsEntry>, FileTransferError> {
-        match self.is_connected() {
-            true => {
-                // Starting from current directory, iter dir
-                match self.pwd() {
-                    Ok(p) => self.iter_search(p.as_path(), &WildMatch::new(search)),
-                    Err(err) => Err(err),
-                }
-            }
-            false => Err(FileTransferError::new(
-                FileTransferErrorType::UninitializedSession,
-            )),
-        }
-    }
-
-    /// ### iter_search
+impl FileTransferProtocol {
+    /// ### supports_exec
     ///
-    /// Search recursively in `dir` for file matching the wildcard.
-    /// NOTE: DON'T RE-IMPLEMENT THIS FUNCTION, unless the file transfer provides a faster way to do so
-    /// NOTE: don't call this method from outside; consider it as private
-    fn iter_search(
-        &mut self,
-        dir: &Path,
-        filter: &WildMatch,
-    ) -> Result<Vec<FsEntry>, FileTransferError> {
-        let mut drained: Vec<FsEntry> = Vec::new();
-        // Scan directory
-        match self.list_dir(dir) {
-            Ok(entries) => {
-                /* For each entry:
-                - if is dir: call iter_search with `dir`
-                    - push `iter_search` result to `drained`
-                - if is file: check if it matches `filter`
-                    - if it matches `filter`: push to to filter
-                */
-                for entry in entries.iter() {
-                    match entry {
-                        FsEntry::Directory(dir) => {
-                            // If directory name, matches wildcard, push it to drained
-                            if filter.matches(dir.name.as_str()) {
-                                drained.push(FsEntry::Directory(dir.clone()));
-                            }
-                            drained.append(&mut self.iter_search(dir.abs_path.as_path(), filter)?);
-                        }
-                        FsEntry::File(file) => {
-                            if filter.matches(file.name.as_str()) {
-                                drained.push(FsEntry::File(file.clone()));
-                            }
-                        }
-                    }
-                }
-                Ok(drained)
-            }
-            Err(err) => Err(err),
-        }
+    /// Returns whether commands can be executed on the remote host with this protocol
+    pub fn supports_exec(&self) -> bool {
+        matches!(self, FileTransferProtocol::Sftp | FileTransferProtocol::Scp)
     }
 }
 
@@ -340,6 +408,14 @@ mod tests {
     use std::str::FromStr;
     use std::string::ToString;
 
+    #[test]
+    fn test_filetransfer_mod_protocol_supports_exec() {
+        assert_eq!(FileTransferProtocol::Sftp.supports_exec(), true);
+        assert_eq!(FileTransferProtocol::Scp.supports_exec(), true);
+        assert_eq!(FileTransferProtocol::Ftp(false).supports_exec(), false);
+        assert_eq!(FileTransferProtocol::Ftp(true).supports_exec(), false);
+    }
+
     #[test]
     fn test_filetransfer_mod_protocol() {
         assert_eq!(
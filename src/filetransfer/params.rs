@@ -26,7 +26,9 @@
  * SOFTWARE.
  */
 use super::FileTransferProtocol;
+use crate::utils::fmt::REDACTED_PASSWORD;
 
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// ### FileTransferParams
@@ -40,6 +42,9 @@ pub struct FileTransferParams {
     pub username: Option<String>,
     pub password: Option<String>,
     pub entry_directory: Option<PathBuf>,
+    pub ssh_key_path: Option<PathBuf>,
+    pub proxy_jump: Option<String>,
+    pub host_key_fingerprint: Option<String>,
 }
 
 impl FileTransferParams {
@@ -54,6 +59,9 @@ impl FileTransferParams {
             username: None,
             password: None,
             entry_directory: None,
+            ssh_key_path: None,
+            proxy_jump: None,
+            host_key_fingerprint: None,
         }
     }
 
@@ -96,6 +104,30 @@ impl FileTransferParams {
         self.entry_directory = dir.map(|x| x.as_ref().to_path_buf());
         self
     }
+
+    /// ### ssh_key_path
+    ///
+    /// Set the private key used to authenticate with SSH protocols, instead of the key from the key storage
+    pub fn ssh_key_path<P: AsRef<Path>>(mut self, path: Option<P>) -> Self {
+        self.ssh_key_path = path.map(|x| x.as_ref().to_path_buf());
+        self
+    }
+
+    /// ### proxy_jump
+    ///
+    /// Set the host (`[user@]host[:port]`) to connect through, with SSH protocols
+    pub fn proxy_jump<S: AsRef<str>>(mut self, jump: Option<S>) -> Self {
+        self.proxy_jump = jump.map(|x| x.as_ref().to_string());
+        self
+    }
+
+    /// ### host_key_fingerprint
+    ///
+    /// Set the fingerprint the host key must have, with SSH protocols
+    pub fn host_key_fingerprint<S: AsRef<str>>(mut self, fingerprint: Option<S>) -> Self {
+        self.host_key_fingerprint = fingerprint.map(|x| x.as_ref().to_string());
+        self
+    }
 }
 
 impl Default for FileTransferParams {
@@ -104,6 +136,152 @@ impl Default for FileTransferParams {
     }
 }
 
+impl fmt::Debug for FileTransferParams {
+    /// Password is redacted, so params can be logged safely
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileTransferParams")
+            .field("address", &self.address)
+            .field("port", &self.port)
+            .field("protocol", &self.protocol)
+            .field("username", &self.username)
+            .field(
+                "password",
+                &self.password.as_ref().map(|_| REDACTED_PASSWORD),
+            )
+            .field("entry_directory", &self.entry_directory)
+            .field("ssh_key_path", &self.ssh_key_path)
+            .field("proxy_jump", &self.proxy_jump)
+            .field("host_key_fingerprint", &self.host_key_fingerprint)
+            .finish()
+    }
+}
+
+/// ### TransferOpts
+///
+/// Holds the options which alter how transfers are performed
+#[derive(Clone, Debug, Default)]
+pub struct TransferOpts {
+    pub skip_existing: bool,
+    pub delete: bool,
+    pub dry_run: bool,
+    pub jobs: Option<usize>,
+    pub resume: bool,
+    pub known_hosts: Option<PathBuf>,
+    pub strict_host_key_checking: bool,
+    pub max_attempts: Option<usize>,
+    pub compression: Option<bool>,
+    pub max_preview_bytes: Option<u64>,
+    pub max_edit_bytes: Option<u64>,
+    pub create_wrkdir: bool,
+    pub read_only: bool,
+}
+
+impl TransferOpts {
+    /// ### skip_existing
+    ///
+    /// Set whether files which are unchanged on the destination must be skipped
+    pub fn skip_existing(mut self, skip: bool) -> Self {
+        self.skip_existing = skip;
+        self
+    }
+
+    /// ### delete
+    ///
+    /// Set whether mirror must delete the entries which don't exist on source
+    pub fn delete(mut self, delete: bool) -> Self {
+        self.delete = delete;
+        self
+    }
+
+    /// ### dry_run
+    ///
+    /// Set whether batch operations must only be reported, without being executed
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// ### jobs
+    ///
+    /// Set the maximum amount of local copies to run in parallel; if `None`, configuration value is used
+    pub fn jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// ### resume
+    ///
+    /// Set whether transfers must continue from the partial destination file, if any
+    pub fn resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// ### known_hosts
+    ///
+    /// Set the path of the known hosts file; if `None`, configuration value is used
+    pub fn known_hosts(mut self, path: Option<PathBuf>) -> Self {
+        self.known_hosts = path;
+        self
+    }
+
+    /// ### strict_host_key_checking
+    ///
+    /// Set whether unknown and changed host keys must be rejected without prompting
+    pub fn strict_host_key_checking(mut self, strict: bool) -> Self {
+        self.strict_host_key_checking = strict;
+        self
+    }
+
+    /// ### max_attempts
+    ///
+    /// Set the maximum amount of attempts made to transfer a file on transient errors; if `None`, configuration value is used
+    pub fn max_attempts(mut self, attempts: Option<usize>) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// ### compression
+    ///
+    /// Set whether SSH compression must be enabled; if `None`, configuration value is used
+    pub fn compression(mut self, compression: Option<bool>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// ### max_preview_bytes
+    ///
+    /// Set the size limit of the files which can be previewed without asking; if `None`, configuration value is used
+    pub fn max_preview_bytes(mut self, bytes: Option<u64>) -> Self {
+        self.max_preview_bytes = bytes;
+        self
+    }
+
+    /// ### max_edit_bytes
+    ///
+    /// Set the size limit of the files which can be edited without asking; if `None`, configuration value is used
+    pub fn max_edit_bytes(mut self, bytes: Option<u64>) -> Self {
+        self.max_edit_bytes = bytes;
+        self
+    }
+
+    /// ### create_wrkdir
+    ///
+    /// Set whether the remote working directory must be created if it doesn't exist; if `false`, configuration value is used
+    pub fn create_wrkdir(mut self, create: bool) -> Self {
+        self.create_wrkdir = create;
+        self
+    }
+
+    /// ### read_only
+    ///
+    /// Set whether mutating actions are disabled; if `false`, configuration value is used
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -117,12 +295,24 @@ mod test {
             .protocol(FileTransferProtocol::Scp)
             .username(Some("omar"))
             .password(Some("foobar"))
-            .entry_directory(Some(&Path::new("/tmp")));
+            .entry_directory(Some(&Path::new("/tmp")))
+            .ssh_key_path(Some(&Path::new("/home/omar/.ssh/id_rsa")))
+            .proxy_jump(Some("omar@bastion:2222"))
+            .host_key_fingerprint(Some("SHA256:abcd"));
         assert_eq!(params.address.as_str(), "test.rebex.net");
         assert_eq!(params.port, 2222);
         assert_eq!(params.protocol, FileTransferProtocol::Scp);
         assert_eq!(params.username.as_ref().unwrap(), "omar");
         assert_eq!(params.password.as_ref().unwrap(), "foobar");
+        assert_eq!(
+            params.ssh_key_path.as_deref().unwrap(),
+            Path::new("/home/omar/.ssh/id_rsa")
+        );
+        assert_eq!(params.proxy_jump.as_deref().unwrap(), "omar@bastion:2222");
+        assert_eq!(
+            params.host_key_fingerprint.as_deref().unwrap(),
+            "SHA256:abcd"
+        );
     }
 
     #[test]
@@ -133,5 +323,70 @@ mod test {
         assert_eq!(params.protocol, FileTransferProtocol::Sftp);
         assert!(params.username.is_none());
         assert!(params.password.is_none());
+        assert!(params.ssh_key_path.is_none());
+        assert!(params.proxy_jump.is_none());
+        assert!(params.host_key_fingerprint.is_none());
+    }
+
+    #[test]
+    fn test_filetransfer_params_debug() {
+        let params: FileTransferParams = FileTransferParams::new("test.rebex.net")
+            .username(Some("omar"))
+            .password(Some("foobar"));
+        let debug: String = format!("{:?}", params);
+        assert!(debug.contains("password: Some(\"***\")"));
+        assert!(debug.contains("omar"));
+        assert!(!debug.contains("foobar"));
+        // Alternate format
+        assert!(!format!("{:#?}", params).contains("foobar"));
+        // Without password
+        let debug: String = format!("{:?}", FileTransferParams::default());
+        assert!(debug.contains("password: None"));
+        assert!(!debug.contains("***"));
+    }
+
+    #[test]
+    fn test_filetransfer_transfer_opts() {
+        let opts: TransferOpts = TransferOpts::default();
+        assert_eq!(opts.skip_existing, false);
+        assert_eq!(opts.delete, false);
+        assert_eq!(opts.dry_run, false);
+        assert_eq!(opts.jobs, None);
+        assert_eq!(opts.resume, false);
+        assert!(opts.known_hosts.is_none());
+        assert_eq!(opts.strict_host_key_checking, false);
+        assert_eq!(opts.max_attempts, None);
+        assert_eq!(opts.compression, None);
+        assert_eq!(opts.max_preview_bytes, None);
+        assert_eq!(opts.max_edit_bytes, None);
+        assert_eq!(opts.create_wrkdir, false);
+        assert_eq!(opts.read_only, false);
+        let opts: TransferOpts = opts
+            .skip_existing(true)
+            .delete(true)
+            .dry_run(true)
+            .jobs(Some(4))
+            .resume(true)
+            .known_hosts(Some(PathBuf::from("/tmp/known_hosts")))
+            .strict_host_key_checking(true)
+            .max_attempts(Some(5))
+            .compression(Some(false))
+            .max_preview_bytes(Some(1024))
+            .max_edit_bytes(Some(2048))
+            .create_wrkdir(true)
+            .read_only(true);
+        assert_eq!(opts.skip_existing, true);
+        assert_eq!(opts.delete, true);
+        assert_eq!(opts.dry_run, true);
+        assert_eq!(opts.jobs, Some(4));
+        assert_eq!(opts.resume, true);
+        assert_eq!(opts.known_hosts, Some(PathBuf::from("/tmp/known_hosts")));
+        assert_eq!(opts.strict_host_key_checking, true);
+        assert_eq!(opts.max_attempts, Some(5));
+        assert_eq!(opts.compression, Some(false));
+        assert_eq!(opts.max_preview_bytes, Some(1024));
+        assert_eq!(opts.max_edit_bytes, Some(2048));
+        assert_eq!(opts.create_wrkdir, true);
+        assert_eq!(opts.read_only, true);
     }
 }
@@ -0,0 +1,195 @@
+//! ## Proxy
+//!
+//! `proxy` opens the stream to an SSH server through a jump host, using the `ssh` client installed on the system
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{FileTransferError, FileTransferErrorType};
+
+#[cfg(unix)]
+use std::os::unix::io::OwnedFd;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+#[cfg(unix)]
+use std::thread;
+
+/// ### proxy_command_args
+///
+/// Returns the arguments for `ssh` to forward its standard input and output to `address:port`,
+/// connecting through `jump` (`[user@]host[:port]`)
+pub fn proxy_command_args(jump: &str, address: &str, port: u16) -> Result<Vec<String>, String> {
+    // Split user info
+    let (user, host): (Option<&str>, &str) = match jump.rfind('@') {
+        Some(idx) => (Some(&jump[..idx]), &jump[idx + 1..]),
+        None => (None, jump),
+    };
+    // Split port; IPv6 addresses must be enclosed in brackets to have a port
+    let (host, jump_port): (&str, Option<&str>) = match host.strip_prefix('[') {
+        Some(host) => match host.split_once(']') {
+            Some((host, "")) => (host, None),
+            Some((host, port)) => match port.strip_prefix(':') {
+                Some(port) => (host, Some(port)),
+                None => return Err(format!("Invalid jump host '{}'", jump)),
+            },
+            None => return Err(format!("Invalid jump host '{}'", jump)),
+        },
+        None => match host.split_once(':') {
+            Some((h, port)) if !port.contains(':') => (h, Some(port)),
+            _ => (host, None),
+        },
+    };
+    if host.is_empty() || user.map(str::is_empty).unwrap_or(false) {
+        return Err(format!("Invalid jump host '{}'", jump));
+    }
+    let mut args: Vec<String> = vec![
+        // Never prompt, since the terminal is in use
+        String::from("-o"),
+        String::from("BatchMode=yes"),
+    ];
+    if let Some(jump_port) = jump_port {
+        if jump_port.parse::<u16>().is_err() {
+            return Err(format!("Invalid port in jump host '{}'", jump));
+        }
+        args.push(String::from("-p"));
+        args.push(jump_port.to_string());
+    }
+    args.push(String::from("-W"));
+    args.push(match address.contains(':') {
+        true => format!("[{}]:{}", address, port),
+        false => format!("{}:{}", address, port),
+    });
+    args.push(match user {
+        Some(user) => format!("{}@{}", user, host),
+        None => host.to_string(),
+    });
+    Ok(args)
+}
+
+/// ### connect
+///
+/// Open a stream to `address:port` through `jump`.
+/// The stream is one end of a socket pair, whose other end is the standard input and output of `ssh`
+#[cfg(unix)]
+pub fn connect(jump: &str, address: &str, port: u16) -> Result<UnixStream, FileTransferError> {
+    let args: Vec<String> = proxy_command_args(jump, address, port)
+        .map_err(|e| FileTransferError::new_ex(FileTransferErrorType::BadAddress, e))?;
+    let connection_error = |err: std::io::Error| {
+        FileTransferError::new_ex(FileTransferErrorType::ConnectionError, err.to_string())
+    };
+    let (stream, proxy_stream): (UnixStream, UnixStream) =
+        UnixStream::pair().map_err(connection_error)?;
+    let proxy_stdout: UnixStream = proxy_stream.try_clone().map_err(connection_error)?;
+    debug!("Starting ssh {}", args.join(" "));
+    let mut child = Command::new("ssh")
+        .args(args)
+        .stdin(Stdio::from(OwnedFd::from(proxy_stream)))
+        .stdout(Stdio::from(OwnedFd::from(proxy_stdout)))
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| {
+            error!("Could not start ssh: {}", err);
+            FileTransferError::new_ex(
+                FileTransferErrorType::ConnectionError,
+                format!("Could not start ssh to connect through {}: {}", jump, err),
+            )
+        })?;
+    // Reap ssh once it exits, which happens when the stream is closed
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(stream)
+}
+
+/// ### connect
+///
+/// Jump hosts are not supported on this platform
+#[cfg(not(unix))]
+pub fn connect(
+    _jump: &str,
+    _address: &str,
+    _port: u16,
+) -> Result<std::net::TcpStream, FileTransferError> {
+    Err(FileTransferError::new_ex(
+        FileTransferErrorType::UnsupportedFeature,
+        String::from("Jump hosts are not supported on this platform"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filetransfer_proxy_command_args() {
+        assert_eq!(
+            proxy_command_args("bastion", "10.0.0.4", 22).unwrap(),
+            vec!["-o", "BatchMode=yes", "-W", "10.0.0.4:22", "bastion"]
+        );
+        assert_eq!(
+            proxy_command_args("omar@bastion.example.com:2222", "internal", 2022).unwrap(),
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-p",
+                "2222",
+                "-W",
+                "internal:2022",
+                "omar@bastion.example.com"
+            ]
+        );
+        // IPv6
+        assert_eq!(
+            proxy_command_args("[::1]:2222", "fe80::1", 22).unwrap(),
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-p",
+                "2222",
+                "-W",
+                "[fe80::1]:22",
+                "::1"
+            ]
+        );
+        assert_eq!(
+            proxy_command_args("::1", "10.0.0.4", 22).unwrap(),
+            vec!["-o", "BatchMode=yes", "-W", "10.0.0.4:22", "::1"]
+        );
+    }
+
+    #[test]
+    fn test_filetransfer_proxy_command_args_invalid() {
+        assert!(proxy_command_args("", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("omar@", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("@bastion", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("bastion:ssh", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("bastion:70000", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("[::1", "10.0.0.4", 22).is_err());
+        assert!(proxy_command_args("[::1]2222", "10.0.0.4", 22).is_err());
+    }
+}
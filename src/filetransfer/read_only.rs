@@ -0,0 +1,142 @@
+//! ## ReadOnly
+//!
+//! `read_only` describes the operations which are disabled in read-only mode
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+/// ## Operation
+///
+/// Describes an operation performed in the file explorer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Chmod,
+    Copy,
+    Delete,
+    Download,
+    Edit,
+    Exec,
+    Mkdir,
+    Move,
+    NewFile,
+    Rename,
+    Symlink,
+    Upload,
+}
+
+impl Operation {
+    /// ### is_mutation
+    ///
+    /// Returns whether the operation modifies files. Downloads are not considered mutations,
+    /// since they only write the local copy of remote files
+    pub fn is_mutation(&self) -> bool {
+        !matches!(self, Operation::Download)
+    }
+
+    /// ### verb
+    ///
+    /// Describe the operation in messages
+    fn verb(&self) -> &'static str {
+        match self {
+            Operation::Chmod => "change permissions",
+            Operation::Copy => "copy",
+            Operation::Delete => "delete",
+            Operation::Download => "download",
+            Operation::Edit => "edit",
+            Operation::Exec => "execute commands",
+            Operation::Mkdir => "create directories",
+            Operation::Move => "move",
+            Operation::NewFile => "create files",
+            Operation::Rename => "rename",
+            Operation::Symlink => "create symlinks",
+            Operation::Upload => "upload",
+        }
+    }
+}
+
+/// ### check_read_only
+///
+/// Check whether `op` can be performed; if `read_only` is enabled, mutations are refused
+/// with a message for the user
+pub fn check_read_only(read_only: bool, op: Operation) -> Result<(), String> {
+    match read_only && op.is_mutation() {
+        true => Err(format!("Can't {}: read-only mode", op.verb())),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    const MUTATIONS: [Operation; 11] = [
+        Operation::Chmod,
+        Operation::Copy,
+        Operation::Delete,
+        Operation::Edit,
+        Operation::Exec,
+        Operation::Mkdir,
+        Operation::Move,
+        Operation::NewFile,
+        Operation::Rename,
+        Operation::Symlink,
+        Operation::Upload,
+    ];
+
+    #[test]
+    fn test_filetransfer_read_only_blocked() {
+        for op in MUTATIONS.iter() {
+            assert!(op.is_mutation());
+            assert!(check_read_only(true, *op).is_err());
+        }
+        assert_eq!(
+            check_read_only(true, Operation::Delete)
+                .err()
+                .unwrap()
+                .as_str(),
+            "Can't delete: read-only mode"
+        );
+        assert_eq!(
+            check_read_only(true, Operation::Chmod)
+                .err()
+                .unwrap()
+                .as_str(),
+            "Can't change permissions: read-only mode"
+        );
+    }
+
+    #[test]
+    fn test_filetransfer_read_only_allowed() {
+        // Downloads are always allowed
+        assert_eq!(Operation::Download.is_mutation(), false);
+        assert!(check_read_only(true, Operation::Download).is_ok());
+        assert!(check_read_only(false, Operation::Download).is_ok());
+        // Mutations are allowed when read-only mode is disabled
+        for op in MUTATIONS.iter() {
+            assert!(check_read_only(false, *op).is_ok());
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! ## Resume
+//!
+//! `resume` contains the functions to resume an interrupted transfer from the partial destination file
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::FileTransfer;
+use crate::fs::{FsEntry, FsFile};
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Amount of bytes compared between source and partial destination before resuming a transfer
+pub const RESUME_CHECK_LEN: usize = 65536;
+
+/// ### resume_offset
+///
+/// Calculate the offset where to resume a transfer from, given the size of the source
+/// and the size of the partial destination, if it exists.
+/// Returns 0 if the transfer must restart from scratch (no destination or a destination larger than source);
+/// returns `src_size` if the destination is already complete
+pub fn resume_offset(src_size: usize, dest_size: Option<usize>) -> usize {
+    match dest_size {
+        Some(dest_size) if dest_size <= src_size => dest_size,
+        _ => 0,
+    }
+}
+
+/// ### same_prefix
+///
+/// Returns whether the first `len` bytes of `a` and `b` are the same.
+/// If any of the two readers ends before `len` bytes, false is returned
+pub fn same_prefix(a: &mut dyn Read, b: &mut dyn Read, len: usize) -> bool {
+    let mut a_buf: Vec<u8> = vec![0; len];
+    let mut b_buf: Vec<u8> = vec![0; len];
+    a.read_exact(&mut a_buf).is_ok() && b.read_exact(&mut b_buf).is_ok() && a_buf == b_buf
+}
+
+/// ### upload_resume_offset
+///
+/// Get the offset where to resume the upload of `local`, read from `fhnd`, to `remote` from.
+/// Returns `Some(0)` if there's nothing to resume or the protocol doesn't support ranged reads,
+/// and `None` if the remote file doesn't start with the same bytes of the local file, so it must be overwritten
+pub fn upload_resume_offset(
+    client: &mut dyn FileTransfer,
+    local: &FsFile,
+    fhnd: &mut File,
+    remote: &Path,
+) -> Option<usize> {
+    let dest: FsFile = match client.stat(remote) {
+        Ok(FsEntry::File(dest)) => dest,
+        _ => return Some(0),
+    };
+    let offset: usize = resume_offset(local.size, Some(dest.size));
+    if offset == 0 {
+        return Some(0);
+    }
+    // Compare prefix; if ranged reads are not supported, resume is not supported either
+    let mut rhnd = match client.recv_file_at(&dest, 0) {
+        Ok(rhnd) => rhnd,
+        Err(_) => return Some(0),
+    };
+    let matches: bool = fhnd.seek(SeekFrom::Start(0)).is_ok()
+        && same_prefix(fhnd, rhnd.as_mut(), offset.min(RESUME_CHECK_LEN));
+    let _ = client.on_recv(rhnd);
+    match matches {
+        true => Some(offset),
+        false => None,
+    }
+}
+
+/// ### download_resume_offset
+///
+/// Get the offset where to resume the download of `remote` from, given the partial local file
+/// of `local_size` bytes, read from `fhnd`.
+/// Returns `Some(0)` if there's nothing to resume or the protocol doesn't support ranged reads,
+/// and `None` if the local file doesn't start with the same bytes of the remote file, so it must be overwritten
+pub fn download_resume_offset(
+    client: &mut dyn FileTransfer,
+    remote: &FsFile,
+    local_size: usize,
+    fhnd: &mut dyn Read,
+) -> Option<usize> {
+    let offset: usize = resume_offset(remote.size, Some(local_size));
+    if offset == 0 {
+        return Some(0);
+    }
+    // Compare prefix; if ranged reads are not supported, resume is not supported either
+    let mut rhnd = match client.recv_file_at(remote, 0) {
+        Ok(rhnd) => rhnd,
+        Err(_) => return Some(0),
+    };
+    let matches: bool = same_prefix(fhnd, rhnd.as_mut(), offset.min(RESUME_CHECK_LEN));
+    let _ = client.on_recv(rhnd);
+    match matches {
+        true => Some(offset),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_filetransfer_resume_offset() {
+        // No destination
+        assert_eq!(resume_offset(1024, None), 0);
+        // Empty destination
+        assert_eq!(resume_offset(1024, Some(0)), 0);
+        // Partial destination
+        assert_eq!(resume_offset(1024, Some(512)), 512);
+        // Complete destination
+        assert_eq!(resume_offset(1024, Some(1024)), 1024);
+        // Destination larger than source; overwrite
+        assert_eq!(resume_offset(1024, Some(2048)), 0);
+        // Empty source
+        assert_eq!(resume_offset(0, Some(0)), 0);
+    }
+
+    #[test]
+    fn test_filetransfer_resume_same_prefix() {
+        let mut a = Cursor::new(b"hello world".to_vec());
+        let mut b = Cursor::new(b"hello".to_vec());
+        assert_eq!(same_prefix(&mut a, &mut b, 5), true);
+        let mut a = Cursor::new(b"hello world".to_vec());
+        let mut b = Cursor::new(b"jello".to_vec());
+        assert_eq!(same_prefix(&mut a, &mut b, 5), false);
+        // Too short
+        let mut a = Cursor::new(b"hello world".to_vec());
+        let mut b = Cursor::new(b"hell".to_vec());
+        assert_eq!(same_prefix(&mut a, &mut b, 5), false);
+        // Nothing to compare
+        let mut a = Cursor::new(Vec::new());
+        let mut b = Cursor::new(Vec::new());
+        assert_eq!(same_prefix(&mut a, &mut b, 0), true);
+    }
+}
@@ -0,0 +1,138 @@
+//! ## Retry
+//!
+//! `retry` is the module which decides whether a failed file transfer must be retried and when
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::FileTransferErrorType;
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+/// Delay before the first retry; it's doubled at each following retry
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// Maximum delay between two attempts
+const MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// ### is_transient_error
+///
+/// Returns whether a file transfer error of kind `kind` may be temporary (e.g. the connection dropped),
+/// so that the transfer is worth retrying.
+/// Errors such as missing files or denied permissions won't change on retry
+pub fn is_transient_error(kind: FileTransferErrorType) -> bool {
+    matches!(kind, FileTransferErrorType::ConnectionError)
+}
+
+/// ### is_transient_io_error
+///
+/// Returns whether an I/O error occurred while streaming from or to the remote may be temporary
+pub fn is_transient_io_error(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// ### backoff_delay
+///
+/// Get the time to wait before the `retry`-th retry (starting from 1).
+/// The delay grows exponentially from `BASE_DELAY` up to `MAX_DELAY`
+pub fn backoff_delay(retry: usize) -> Duration {
+    match retry {
+        0 => Duration::ZERO,
+        retry => {
+            let factor: u32 = 1u32.checked_shl((retry - 1) as u32).unwrap_or(u32::MAX);
+            BASE_DELAY
+                .checked_mul(factor)
+                .unwrap_or(MAX_DELAY)
+                .min(MAX_DELAY)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filetransfer_retry_transient_error() {
+        assert_eq!(
+            is_transient_error(FileTransferErrorType::ConnectionError),
+            true
+        );
+        for kind in [
+            FileTransferErrorType::AuthenticationFailed,
+            FileTransferErrorType::BadAddress,
+            FileTransferErrorType::SslError,
+            FileTransferErrorType::DirStatFailed,
+            FileTransferErrorType::FileCreateDenied,
+            FileTransferErrorType::HostKeyUnknown,
+            FileTransferErrorType::HostKeyMismatch,
+            FileTransferErrorType::HostKeyRevoked,
+            FileTransferErrorType::NoSuchFileOrDirectory,
+            FileTransferErrorType::PexError,
+            FileTransferErrorType::ProtocolError,
+            FileTransferErrorType::UninitializedSession,
+            FileTransferErrorType::UnsupportedFeature,
+        ] {
+            assert_eq!(is_transient_error(kind), false, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_filetransfer_retry_transient_io_error() {
+        assert_eq!(is_transient_io_error(ErrorKind::ConnectionReset), true);
+        assert_eq!(is_transient_io_error(ErrorKind::ConnectionAborted), true);
+        assert_eq!(is_transient_io_error(ErrorKind::BrokenPipe), true);
+        assert_eq!(is_transient_io_error(ErrorKind::TimedOut), true);
+        assert_eq!(is_transient_io_error(ErrorKind::Interrupted), true);
+        assert_eq!(is_transient_io_error(ErrorKind::UnexpectedEof), true);
+        assert_eq!(is_transient_io_error(ErrorKind::PermissionDenied), false);
+        assert_eq!(is_transient_io_error(ErrorKind::NotFound), false);
+        assert_eq!(is_transient_io_error(ErrorKind::AlreadyExists), false);
+        assert_eq!(is_transient_io_error(ErrorKind::Other), false);
+    }
+
+    #[test]
+    fn test_filetransfer_retry_backoff_delay() {
+        assert_eq!(backoff_delay(0), Duration::ZERO);
+        assert_eq!(backoff_delay(1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2), Duration::from_secs(1));
+        assert_eq!(backoff_delay(3), Duration::from_secs(2));
+        assert_eq!(backoff_delay(4), Duration::from_secs(4));
+        assert_eq!(backoff_delay(5), Duration::from_secs(8));
+        // Capped
+        assert_eq!(backoff_delay(6), Duration::from_secs(8));
+        assert_eq!(backoff_delay(64), Duration::from_secs(8));
+        assert_eq!(backoff_delay(usize::MAX), Duration::from_secs(8));
+    }
+}
@@ -26,20 +26,20 @@
  * SOFTWARE.
  */
 // Locals
-use super::{FileTransfer, FileTransferError, FileTransferErrorType};
+use super::{exec, proxy, ssh, ExecOutput, FileTransfer, FileTransferError, FileTransferErrorType};
 use crate::fs::{FsDirectory, FsEntry, FsFile};
+use crate::system::known_hosts::HostKeyVerifier;
 use crate::system::sshkey_storage::SshKeyStorage;
-use crate::utils::fmt::{fmt_time, shadow_password};
+use crate::utils::fmt::{fmt_time, shell_quote};
 use crate::utils::parser::parse_lstime;
 
 // Includes
 use regex::Regex;
 use ssh2::{Channel, Session};
 use std::io::{BufReader, BufWriter, Read, Write};
-use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
 /// ## ScpFileTransfer
 ///
@@ -48,6 +48,10 @@ pub struct ScpFileTransfer {
     session: Option<Session>,
     wrkdir: PathBuf,
     key_storage: SshKeyStorage,
+    host_key_verifier: HostKeyVerifier,
+    compression: bool,
+    ssh_key: Option<PathBuf>,
+    proxy_jump: Option<String>,
 }
 
 impl ScpFileTransfer {
@@ -59,9 +63,45 @@ impl ScpFileTransfer {
             session: None,
             wrkdir: PathBuf::from("~"),
             key_storage,
+            host_key_verifier: HostKeyVerifier::default(),
+            compression: false,
+            ssh_key: None,
+            proxy_jump: None,
         }
     }
 
+    /// ### host_key_verifier
+    ///
+    /// Set the verifier for the host keys presented by the server
+    pub fn host_key_verifier(mut self, verifier: HostKeyVerifier) -> Self {
+        self.host_key_verifier = verifier;
+        self
+    }
+
+    /// ### compression
+    ///
+    /// Set whether the SSH session must be compressed
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// ### ssh_key
+    ///
+    /// Set the private key to authenticate with; if `None`, the key is resolved from the key storage
+    pub fn ssh_key(mut self, key: Option<PathBuf>) -> Self {
+        self.ssh_key = key;
+        self
+    }
+
+    /// ### proxy_jump
+    ///
+    /// Set the host (`[user@]host[:port]`) to connect through
+    pub fn proxy_jump(mut self, jump: Option<String>) -> Self {
+        self.proxy_jump = jump;
+        self
+    }
+
     /// ### resolve
     ///
     /// Fix provided path; on Windows fixes the backslashes, converting them to slashes
@@ -254,6 +294,13 @@ impl ScpFileTransfer {
         (filename, symlink)
     }
 
+    /// ### symlink_cmd
+    ///
+    /// Returns the shell command which creates the symlink `src` pointing to `dst` and prints its exit code
+    fn symlink_cmd(src: &Path, dst: &Path) -> String {
+        format!("ln -s {} {}; echo $?", shell_quote(dst), shell_quote(src))
+    }
+
     /// ### perform_shell_cmd_with
     ///
     /// Perform a shell command, but change directory to specified path first
@@ -323,42 +370,6 @@ impl FileTransfer for ScpFileTransfer {
         username: Option<String>,
         password: Option<String>,
     ) -> Result<Option<String>, FileTransferError> {
-        // Setup tcp stream
-        info!("Connecting to {}:{}", address, port);
-        let socket_addresses: Vec<SocketAddr> =
-            match format!("{}:{}", address, port).to_socket_addrs() {
-                Ok(s) => s.collect(),
-                Err(err) => {
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::BadAddress,
-                        err.to_string(),
-                    ))
-                }
-            };
-        let mut tcp: Option<TcpStream> = None;
-        // Try addresses
-        for socket_addr in socket_addresses.iter() {
-            debug!("Trying socket address {}", socket_addr);
-            match TcpStream::connect_timeout(&socket_addr, Duration::from_secs(30)) {
-                Ok(stream) => {
-                    debug!("{} succeded", socket_addr);
-                    tcp = Some(stream);
-                    break;
-                }
-                Err(_) => continue,
-            }
-        }
-        // If stream is None, return connection timeout
-        let tcp: TcpStream = match tcp {
-            Some(t) => t,
-            None => {
-                error!("No suitable socket address found; connection timeout");
-                return Err(FileTransferError::new_ex(
-                    FileTransferErrorType::ConnectionError,
-                    String::from("Connection timeout"),
-                ));
-            }
-        };
         // Create session
         let mut session: Session = match Session::new() {
             Ok(s) => s,
@@ -370,8 +381,16 @@ impl FileTransfer for ScpFileTransfer {
                 ));
             }
         };
-        // Set TCP stream
-        session.set_tcp_stream(tcp);
+        // Set stream; through the jump host, if any
+        match self.proxy_jump.as_deref() {
+            Some(jump) => {
+                info!("Connecting to {}:{} through {}", address, port, jump);
+                session.set_tcp_stream(proxy::connect(jump, address.as_str(), port)?);
+            }
+            None => session.set_tcp_stream(ssh::connect_tcp(address.as_str(), port)?),
+        }
+        // Compression must be negotiated during handshake
+        session.set_compress(self.compression);
         // Open connection
         debug!("Initializing handshake");
         if let Err(err) = session.handshake() {
@@ -381,54 +400,26 @@ impl FileTransfer for ScpFileTransfer {
                 err.to_string(),
             ));
         }
+        // Verify host key
+        self.host_key_verifier
+            .verify(&session, address.as_str(), port)?;
         let username: String = match username {
             Some(u) => u,
             None => String::from(""),
         };
-        // Check if it is possible to authenticate using a RSA key
-        match self
-            .key_storage
-            .resolve(address.as_str(), username.as_str())
-        {
-            Some(rsa_key) => {
-                debug!(
-                    "Authenticating with user {} and RSA key {}",
-                    username,
-                    rsa_key.display()
-                );
-                // Authenticate with RSA key
-                if let Err(err) = session.userauth_pubkey_file(
-                    username.as_str(),
-                    None,
-                    rsa_key.as_path(),
-                    password.as_deref(),
-                ) {
-                    error!("Authentication failed: {}", err);
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::AuthenticationFailed,
-                        err.to_string(),
-                    ));
-                }
-            }
-            None => {
-                // Proceeed with username/password authentication
-                debug!(
-                    "Authenticating with username {} and password {}",
-                    username,
-                    shadow_password(password.as_deref().unwrap_or(""))
-                );
-                if let Err(err) = session.userauth_password(
-                    username.as_str(),
-                    password.unwrap_or_else(|| String::from("")).as_str(),
-                ) {
-                    error!("Authentication failed: {}", err);
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::AuthenticationFailed,
-                        err.to_string(),
-                    ));
-                }
-            }
-        }
+        // Authenticate with the RSA key set for this host, or the one resolved from the key storage, if any;
+        // password authentication is used as fallback
+        let ssh_key: Option<PathBuf> = self.ssh_key.clone().or_else(|| {
+            self.key_storage
+                .resolve(address.as_str(), username.as_str())
+                .cloned()
+        });
+        ssh::userauth(
+            &session,
+            username.as_str(),
+            password.as_deref(),
+            ssh_key.as_deref(),
+        )?;
         // Get banner
         let banner: Option<String> = session.banner().map(String::from);
         debug!(
@@ -759,6 +750,47 @@ impl FileTransfer for ScpFileTransfer {
         }
     }
 
+    /// ### symlink
+    ///
+    /// Create a symlink at `src` pointing at `dst`
+    fn symlink(&mut self, src: &Path, dst: &Path) -> Result<(), FileTransferError> {
+        match self.is_connected() {
+            true => {
+                let src: PathBuf = Self::resolve(src);
+                let dst: PathBuf = Self::resolve(dst);
+                info!(
+                    "Creating symlink {} pointing to {}",
+                    src.display(),
+                    dst.display()
+                );
+                let p: PathBuf = self.wrkdir.clone();
+                match self.perform_shell_cmd_with_path(
+                    p.as_path(),
+                    Self::symlink_cmd(src.as_path(), dst.as_path()).as_str(),
+                ) {
+                    Ok(output) => {
+                        // Check if output is 0
+                        match output.as_str().trim() == "0" {
+                            true => Ok(()), // Symlink created
+                            false => Err(FileTransferError::new_ex(
+                                // Could not create symlink
+                                FileTransferErrorType::FileCreateDenied,
+                                format!("\"{}\"", src.display()),
+                            )),
+                        }
+                    }
+                    Err(err) => Err(FileTransferError::new_ex(
+                        FileTransferErrorType::ProtocolError,
+                        err.to_string(),
+                    )),
+                }
+            }
+            false => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+        }
+    }
+
     /// ### stat
     ///
     /// Stat file and return FsEntry
@@ -833,6 +865,22 @@ impl FileTransfer for ScpFileTransfer {
         }
     }
 
+    /// ### exec_output
+    ///
+    /// Execute a command in the working directory, collecting its output and its exit code
+    fn exec_output(&mut self, cmd: &str) -> Result<ExecOutput, FileTransferError> {
+        match self.session.as_ref() {
+            Some(session) => {
+                info!("Executing command {}", cmd);
+                let cmd: String = format!("cd {}; {}", shell_quote(self.wrkdir.as_path()), cmd);
+                exec::channel_exec(session, cmd.as_str())
+            }
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+        }
+    }
+
     /// ### send_file
     ///
     /// Send file to remote
@@ -935,6 +983,20 @@ impl FileTransfer for ScpFileTransfer {
         }
     }
 
+    /// ### trust_host_key
+    ///
+    /// Trust the host key which made the last connection fail
+    fn trust_host_key(&mut self) -> Result<(), FileTransferError> {
+        self.host_key_verifier.trust()
+    }
+
+    /// ### host_key_fingerprint
+    ///
+    /// Returns the fingerprint of the host key accepted when connecting
+    fn host_key_fingerprint(&self) -> Option<String> {
+        self.host_key_verifier.accepted().map(|x| x.to_string())
+    }
+
     /// ### on_sent
     ///
     /// Finalize send method.
@@ -1081,10 +1143,6 @@ mod tests {
             .ok()
             .unwrap();
         assert_eq!(list.len(), 2);
-        // Find
-        assert_eq!(client.find("*.txt").ok().unwrap().len(), 1);
-        assert_eq!(client.find("*.md").ok().unwrap().len(), 1);
-        assert_eq!(client.find("*.jpeg").ok().unwrap().len(), 0);
         // Rename
         assert!(client
             .mkdir(PathBuf::from("/tmp/uploads").as_path())
@@ -1295,6 +1353,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filetransfer_scp_symlink_cmd() {
+        assert_eq!(
+            ScpFileTransfer::symlink_cmd(Path::new("/tmp/link"), Path::new("/tmp/omar.txt")),
+            String::from("ln -s '/tmp/omar.txt' '/tmp/link'; echo $?")
+        );
+        // Quotes and shell expansions are not interpreted
+        let cmd: String = ScpFileTransfer::symlink_cmd(
+            Path::new("/tmp/it's a \"link\""),
+            Path::new("/tmp/$(whoami)`id`.txt"),
+        );
+        assert_eq!(
+            cmd.as_str(),
+            "ln -s '/tmp/$(whoami)`id`.txt' '/tmp/it'\\''s a \"link\"'; echo $?"
+        );
+        #[cfg(target_family = "unix")]
+        {
+            let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+            let src: PathBuf = tmpdir.path().join("it's a \"link\"");
+            let dst: PathBuf = tmpdir.path().join("$(whoami)`id`.txt");
+            let output = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(ScpFileTransfer::symlink_cmd(src.as_path(), dst.as_path()))
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "0");
+            assert_eq!(std::fs::read_link(src.as_path()).unwrap(), dst);
+        }
+    }
+
     #[test]
     fn test_filetransfer_scp_uninitialized() {
         let file: FsFile = FsFile {
@@ -1328,6 +1416,9 @@ mod tests {
             )
             .is_err());
         assert!(scp.stat(Path::new("/tmp")).is_err());
+        assert!(scp
+            .symlink(Path::new("/tmp/omar.txt"), Path::new("/omar.txt"))
+            .is_err());
         assert!(scp.recv_file(&file).is_err());
         assert!(scp.send_file(&file, Path::new("/tmp/omar.txt")).is_err());
     }
@@ -26,15 +26,15 @@
  * SOFTWARE.
  */
 // Locals
-use super::{FileTransfer, FileTransferError, FileTransferErrorType};
+use super::{exec, proxy, ssh, ExecOutput, FileTransfer, FileTransferError, FileTransferErrorType};
 use crate::fs::{FsDirectory, FsEntry, FsFile};
+use crate::system::known_hosts::HostKeyVerifier;
 use crate::system::sshkey_storage::SshKeyStorage;
-use crate::utils::fmt::{fmt_time, shadow_password};
+use crate::utils::fmt::{fmt_time, shell_quote};
 
 // Includes
 use ssh2::{Channel, FileStat, OpenFlags, OpenType, Session, Sftp};
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
@@ -46,6 +46,10 @@ pub struct SftpFileTransfer {
     sftp: Option<Sftp>,
     wrkdir: PathBuf,
     key_storage: SshKeyStorage,
+    host_key_verifier: HostKeyVerifier,
+    compression: bool,
+    ssh_key: Option<PathBuf>,
+    proxy_jump: Option<String>,
 }
 
 impl SftpFileTransfer {
@@ -58,9 +62,45 @@ impl SftpFileTransfer {
             sftp: None,
             wrkdir: PathBuf::from("~"),
             key_storage,
+            host_key_verifier: HostKeyVerifier::default(),
+            compression: false,
+            ssh_key: None,
+            proxy_jump: None,
         }
     }
 
+    /// ### host_key_verifier
+    ///
+    /// Set the verifier for the host keys presented by the server
+    pub fn host_key_verifier(mut self, verifier: HostKeyVerifier) -> Self {
+        self.host_key_verifier = verifier;
+        self
+    }
+
+    /// ### compression
+    ///
+    /// Set whether the SSH session must be compressed
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// ### ssh_key
+    ///
+    /// Set the private key to authenticate with; if `None`, the key is resolved from the key storage
+    pub fn ssh_key(mut self, key: Option<PathBuf>) -> Self {
+        self.ssh_key = key;
+        self
+    }
+
+    /// ### proxy_jump
+    ///
+    /// Set the host (`[user@]host[:port]`) to connect through
+    pub fn proxy_jump(mut self, jump: Option<String>) -> Self {
+        self.proxy_jump = jump;
+        self
+    }
+
     /// ### get_abs_path
     ///
     /// Get absolute path from path argument and check if it exists
@@ -266,41 +306,6 @@ impl FileTransfer for SftpFileTransfer {
         username: Option<String>,
         password: Option<String>,
     ) -> Result<Option<String>, FileTransferError> {
-        // Setup tcp stream
-        info!("Connecting to {}:{}", address, port);
-        let socket_addresses: Vec<SocketAddr> =
-            match format!("{}:{}", address, port).to_socket_addrs() {
-                Ok(s) => s.collect(),
-                Err(err) => {
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::BadAddress,
-                        err.to_string(),
-                    ))
-                }
-            };
-        let mut tcp: Option<TcpStream> = None;
-        // Try addresses
-        for socket_addr in socket_addresses.iter() {
-            debug!("Trying socket address {}", socket_addr);
-            match TcpStream::connect_timeout(&socket_addr, Duration::from_secs(30)) {
-                Ok(stream) => {
-                    tcp = Some(stream);
-                    break;
-                }
-                Err(_) => continue,
-            }
-        }
-        // If stream is None, return connection timeout
-        let tcp: TcpStream = match tcp {
-            Some(t) => t,
-            None => {
-                error!("No suitable socket address found; connection timeout");
-                return Err(FileTransferError::new_ex(
-                    FileTransferErrorType::ConnectionError,
-                    String::from("Connection timeout"),
-                ));
-            }
-        };
         // Create session
         let mut session: Session = match Session::new() {
             Ok(s) => s,
@@ -312,8 +317,16 @@ impl FileTransfer for SftpFileTransfer {
                 ));
             }
         };
-        // Set TCP stream
-        session.set_tcp_stream(tcp);
+        // Set stream; through the jump host, if any
+        match self.proxy_jump.as_deref() {
+            Some(jump) => {
+                info!("Connecting to {}:{} through {}", address, port, jump);
+                session.set_tcp_stream(proxy::connect(jump, address.as_str(), port)?);
+            }
+            None => session.set_tcp_stream(ssh::connect_tcp(address.as_str(), port)?),
+        }
+        // Compression must be negotiated during handshake
+        session.set_compress(self.compression);
         // Open connection
         debug!("Initializing handshake");
         if let Err(err) = session.handshake() {
@@ -323,54 +336,26 @@ impl FileTransfer for SftpFileTransfer {
                 err.to_string(),
             ));
         }
+        // Verify host key
+        self.host_key_verifier
+            .verify(&session, address.as_str(), port)?;
         let username: String = match username {
             Some(u) => u,
             None => String::from(""),
         };
-        // Check if it is possible to authenticate using a RSA key
-        match self
-            .key_storage
-            .resolve(address.as_str(), username.as_str())
-        {
-            Some(rsa_key) => {
-                debug!(
-                    "Authenticating with user {} and RSA key {}",
-                    username,
-                    rsa_key.display()
-                );
-                // Authenticate with RSA key
-                if let Err(err) = session.userauth_pubkey_file(
-                    username.as_str(),
-                    None,
-                    rsa_key.as_path(),
-                    password.as_deref(),
-                ) {
-                    error!("Authentication failed: {}", err);
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::AuthenticationFailed,
-                        err.to_string(),
-                    ));
-                }
-            }
-            None => {
-                // Proceeed with username/password authentication
-                debug!(
-                    "Authenticating with username {} and password {}",
-                    username,
-                    shadow_password(password.as_deref().unwrap_or(""))
-                );
-                if let Err(err) = session.userauth_password(
-                    username.as_str(),
-                    password.unwrap_or_else(|| String::from("")).as_str(),
-                ) {
-                    error!("Authentication failed: {}", err);
-                    return Err(FileTransferError::new_ex(
-                        FileTransferErrorType::AuthenticationFailed,
-                        err.to_string(),
-                    ));
-                }
-            }
-        }
+        // Authenticate with the RSA key set for this host, or the one resolved from the key storage, if any;
+        // password authentication is used as fallback
+        let ssh_key: Option<PathBuf> = self.ssh_key.clone().or_else(|| {
+            self.key_storage
+                .resolve(address.as_str(), username.as_str())
+                .cloned()
+        });
+        ssh::userauth(
+            &session,
+            username.as_str(),
+            password.as_deref(),
+            ssh_key.as_deref(),
+        )?;
         // Set blocking to true
         session.set_blocking(true);
         // Get Sftp client
@@ -647,6 +632,34 @@ impl FileTransfer for SftpFileTransfer {
         }
     }
 
+    /// ### symlink
+    ///
+    /// Create a symlink at `src` pointing at `dst`
+    fn symlink(&mut self, src: &Path, dst: &Path) -> Result<(), FileTransferError> {
+        match self.sftp.as_ref() {
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+            Some(sftp) => {
+                // Resolve link path
+                let abs_src: PathBuf = self.get_abs_path(src);
+                info!(
+                    "Creating symlink {} pointing to {}",
+                    abs_src.display(),
+                    dst.display()
+                );
+                // NOTE: ssh2 creates the symlink at the second argument
+                match sftp.symlink(dst, abs_src.as_path()) {
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(FileTransferError::new_ex(
+                        FileTransferErrorType::FileCreateDenied,
+                        err.to_string(),
+                    )),
+                }
+            }
+        }
+    }
+
     /// ### stat
     ///
     /// Stat file and return FsEntry
@@ -671,6 +684,36 @@ impl FileTransfer for SftpFileTransfer {
         }
     }
 
+    /// ### chmod
+    ///
+    /// Change the permissions of the file at `path` through the SFTP protocol
+    fn chmod(&mut self, path: &Path, pex: (u8, u8, u8)) -> Result<(), FileTransferError> {
+        match self.sftp.as_ref() {
+            Some(sftp) => {
+                let path: PathBuf = self.get_remote_path(path)?;
+                info!("Changing mode of {}", path.display());
+                let mode: u32 = ((pex.0 as u32) << 6) | ((pex.1 as u32) << 3) | (pex.2 as u32);
+                let stat: FileStat = FileStat {
+                    size: None,
+                    uid: None,
+                    gid: None,
+                    perm: Some(mode),
+                    atime: None,
+                    mtime: None,
+                };
+                sftp.setstat(path.as_path(), stat).map_err(|err| {
+                    FileTransferError::new_ex(
+                        FileTransferErrorType::PexError,
+                        format!("\"{}\": {}", path.display(), err),
+                    )
+                })
+            }
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+        }
+    }
+
     /// ### exec
     ///
     /// Execute a command on remote host
@@ -690,6 +733,22 @@ impl FileTransfer for SftpFileTransfer {
         }
     }
 
+    /// ### exec_output
+    ///
+    /// Execute a command in the working directory, collecting its output and its exit code
+    fn exec_output(&mut self, cmd: &str) -> Result<ExecOutput, FileTransferError> {
+        match self.session.as_ref() {
+            Some(session) => {
+                info!("Executing command {}", cmd);
+                let cmd: String = format!("cd {}; {}", shell_quote(self.wrkdir.as_path()), cmd);
+                exec::channel_exec(session, cmd.as_str())
+            }
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+        }
+    }
+
     /// ### send_file
     ///
     /// Send file to remote
@@ -757,6 +816,106 @@ impl FileTransfer for SftpFileTransfer {
         }
     }
 
+    /// ### send_file_at
+    ///
+    /// Send file to remote, writing from `offset`
+    fn send_file_at(
+        &mut self,
+        local: &FsFile,
+        file_name: &Path,
+        offset: u64,
+    ) -> Result<Box<dyn Write>, FileTransferError> {
+        match self.sftp.as_ref() {
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+            Some(sftp) => {
+                let remote_path: PathBuf = self.get_abs_path(file_name);
+                info!(
+                    "Sending file {} to {} from offset {}",
+                    local.abs_path.display(),
+                    remote_path.display(),
+                    offset
+                );
+                // Open without truncating
+                let mut file = match sftp.open_mode(
+                    remote_path.as_path(),
+                    OpenFlags::WRITE,
+                    0o644,
+                    OpenType::File,
+                ) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        return Err(FileTransferError::new_ex(
+                            FileTransferErrorType::FileCreateDenied,
+                            err.to_string(),
+                        ))
+                    }
+                };
+                match file.seek(SeekFrom::Start(offset)) {
+                    Ok(_) => Ok(Box::new(BufWriter::with_capacity(65536, file))),
+                    Err(err) => Err(FileTransferError::new_ex(
+                        FileTransferErrorType::ProtocolError,
+                        err.to_string(),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// ### recv_file_at
+    ///
+    /// Receive file from remote, reading from `offset`
+    fn recv_file_at(
+        &mut self,
+        file: &FsFile,
+        offset: u64,
+    ) -> Result<Box<dyn Read>, FileTransferError> {
+        match self.sftp.as_ref() {
+            None => Err(FileTransferError::new(
+                FileTransferErrorType::UninitializedSession,
+            )),
+            Some(sftp) => {
+                let remote_path: PathBuf = self.get_remote_path(file.abs_path.as_path())?;
+                info!(
+                    "Receiving file {} from offset {}",
+                    remote_path.display(),
+                    offset
+                );
+                let mut file = match sftp.open(remote_path.as_path()) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        return Err(FileTransferError::new_ex(
+                            FileTransferErrorType::NoSuchFileOrDirectory,
+                            err.to_string(),
+                        ))
+                    }
+                };
+                match file.seek(SeekFrom::Start(offset)) {
+                    Ok(_) => Ok(Box::new(BufReader::with_capacity(65536, file))),
+                    Err(err) => Err(FileTransferError::new_ex(
+                        FileTransferErrorType::ProtocolError,
+                        err.to_string(),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// ### trust_host_key
+    ///
+    /// Trust the host key which made the last connection fail
+    fn trust_host_key(&mut self) -> Result<(), FileTransferError> {
+        self.host_key_verifier.trust()
+    }
+
+    /// ### host_key_fingerprint
+    ///
+    /// Returns the fingerprint of the host key accepted when connecting
+    fn host_key_fingerprint(&self) -> Option<String> {
+        self.host_key_verifier.accepted().map(|x| x.to_string())
+    }
+
     /// ### on_sent
     ///
     /// Finalize send method. This method must be implemented only if necessary.
@@ -880,10 +1039,6 @@ mod tests {
             .ok()
             .unwrap();
         assert_eq!(list.len(), 2);
-        // Find
-        assert_eq!(client.find("*.txt").ok().unwrap().len(), 1);
-        assert_eq!(client.find("*.md").ok().unwrap().len(), 1);
-        assert_eq!(client.find("*.jpeg").ok().unwrap().len(), 0);
         // Rename
         assert!(client
             .mkdir(PathBuf::from("/tmp/uploads").as_path())
@@ -1084,6 +1239,9 @@ mod tests {
             )
             .is_err());
         assert!(sftp.stat(Path::new("/tmp")).is_err());
+        assert!(sftp
+            .symlink(Path::new("/tmp/omar.txt"), Path::new("/omar.txt"))
+            .is_err());
         assert!(sftp.recv_file(&file).is_err());
         assert!(sftp.send_file(&file, Path::new("/tmp/omar.txt")).is_err());
     }
@@ -0,0 +1,110 @@
+//! ## Ssh
+//!
+//! `ssh` contains the helpers shared by the SSH based file transfers (SCP and SFTP)
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{FileTransferError, FileTransferErrorType};
+use crate::utils::fmt::redact_password;
+
+use ssh2::Session;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// ### connect_tcp
+///
+/// Open a TCP stream to `address:port`, trying all the addresses it resolves to
+pub(crate) fn connect_tcp(address: &str, port: u16) -> Result<TcpStream, FileTransferError> {
+    info!("Connecting to {}:{}", address, port);
+    let socket_addresses: Vec<SocketAddr> = match format!("{}:{}", address, port).to_socket_addrs()
+    {
+        Ok(s) => s.collect(),
+        Err(err) => {
+            return Err(FileTransferError::new_ex(
+                FileTransferErrorType::BadAddress,
+                err.to_string(),
+            ))
+        }
+    };
+    // Try addresses
+    for socket_addr in socket_addresses.iter() {
+        debug!("Trying socket address {}", socket_addr);
+        if let Ok(stream) = TcpStream::connect_timeout(socket_addr, Duration::from_secs(30)) {
+            debug!("{} succeded", socket_addr);
+            return Ok(stream);
+        }
+    }
+    // No address could be reached
+    error!("No suitable socket address found; connection timeout");
+    Err(FileTransferError::new_ex(
+        FileTransferErrorType::ConnectionError,
+        String::from("Connection timeout"),
+    ))
+}
+
+/// ### userauth
+///
+/// Authenticate `username` on `session`.
+/// If `ssh_key` is provided, public key authentication is tried first, using `password` as passphrase;
+/// if it fails, or no key is provided, password authentication is tried
+pub(crate) fn userauth(
+    session: &Session,
+    username: &str,
+    password: Option<&str>,
+    ssh_key: Option<&Path>,
+) -> Result<(), FileTransferError> {
+    if let Some(ssh_key) = ssh_key {
+        debug!(
+            "Authenticating with user {} and RSA key {}",
+            username,
+            ssh_key.display()
+        );
+        match session.userauth_pubkey_file(username, None, ssh_key, password) {
+            Ok(_) => return Ok(()),
+            Err(err) if password.is_none() => {
+                error!("Authentication failed: {}", err);
+                return Err(FileTransferError::new_ex(
+                    FileTransferErrorType::AuthenticationFailed,
+                    err.to_string(),
+                ));
+            }
+            Err(err) => warn!(
+                "Public key authentication failed: {}; trying password authentication",
+                err
+            ),
+        }
+    }
+    debug!(
+        "Authenticating with username {} and password {}",
+        username,
+        redact_password(password.unwrap_or(""))
+    );
+    session
+        .userauth_password(username, password.unwrap_or(""))
+        .map_err(|err| {
+            error!("Authentication failed: {}", err);
+            FileTransferError::new_ex(FileTransferErrorType::AuthenticationFailed, err.to_string())
+        })
+}
@@ -0,0 +1,113 @@
+//! ## Wrkdir
+//!
+//! `wrkdir` decides how to enter the remote working directory requested for a connection
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::path::{Path, PathBuf};
+
+/// ## EntryDirAction
+///
+/// Describes how to enter the remote working directory requested for a connection
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryDirAction {
+    ChangeDir, // The directory exists: enter it
+    Create,    // The directory is missing: create it and its parents, then enter it
+    Warn,      // The directory is missing: stay in the default directory, reporting it
+}
+
+/// ### entry_dir_action
+///
+/// Decide how to enter the requested working directory, given whether it `exists`
+/// and whether missing directories must be created
+pub fn entry_dir_action(exists: bool, create: bool) -> EntryDirAction {
+    match (exists, create) {
+        (true, _) => EntryDirAction::ChangeDir,
+        (false, true) => EntryDirAction::Create,
+        (false, false) => EntryDirAction::Warn,
+    }
+}
+
+/// ### missing_dirs
+///
+/// Returns the directories to create, from the outermost, so that the absolute `path` exists (like `mkdir -p`).
+/// `exists` tells whether a directory exists; ancestors are checked up to the first existing one
+pub fn missing_dirs<F>(path: &Path, mut exists: F) -> Vec<PathBuf>
+where
+    F: FnMut(&Path) -> bool,
+{
+    let mut missing: Vec<PathBuf> = path
+        .ancestors()
+        .filter(|x| x.parent().is_some())
+        .take_while(|x| !exists(x))
+        .map(Path::to_path_buf)
+        .collect();
+    missing.reverse();
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filetransfer_wrkdir_entry_dir_action() {
+        // Exists; cd
+        assert_eq!(entry_dir_action(true, false), EntryDirAction::ChangeDir);
+        assert_eq!(entry_dir_action(true, true), EntryDirAction::ChangeDir);
+        // Missing and create; mkdir then cd
+        assert_eq!(entry_dir_action(false, true), EntryDirAction::Create);
+        // Missing and don't create; warn
+        assert_eq!(entry_dir_action(false, false), EntryDirAction::Warn);
+    }
+
+    #[test]
+    fn test_filetransfer_wrkdir_missing_dirs() {
+        let existing: Vec<PathBuf> = vec![PathBuf::from("/"), PathBuf::from("/home")];
+        let mut checked: Vec<PathBuf> = Vec::new();
+        assert_eq!(
+            missing_dirs(Path::new("/home/omar/backup/daily"), |p| {
+                checked.push(p.to_path_buf());
+                existing.iter().any(|x| x == p)
+            }),
+            vec![
+                PathBuf::from("/home/omar"),
+                PathBuf::from("/home/omar/backup"),
+                PathBuf::from("/home/omar/backup/daily"),
+            ]
+        );
+        // Stops at the first existing ancestor
+        assert_eq!(checked.last().unwrap(), Path::new("/home"));
+        // Nothing to create
+        assert!(missing_dirs(Path::new("/home"), |p| existing.iter().any(|x| x == p)).is_empty());
+        // Root is never created
+        assert_eq!(
+            missing_dirs(Path::new("/srv/www"), |_| false),
+            vec![PathBuf::from("/srv"), PathBuf::from("/srv/www")]
+        );
+    }
+}
@@ -28,8 +28,10 @@
 // Locals
 use super::formatter::Formatter;
 use super::{ExplorerOpts, FileExplorer, FileSorting, GroupDirs};
+use crate::utils::fmt::SizeUnit;
 // Ext
 use std::collections::VecDeque;
+use wildmatch::WildMatch;
 
 /// ## FileExplorerBuilder
 ///
@@ -68,6 +70,55 @@ impl FileExplorerBuilder {
         self
     }
 
+    /// ### with_relative_paths
+    ///
+    /// Enable RELATIVE_PATHS option
+    pub fn with_relative_paths(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            match val {
+                true => e.opts.insert(ExplorerOpts::RELATIVE_PATHS),
+                false => e.opts.remove(ExplorerOpts::RELATIVE_PATHS),
+            }
+        }
+        self
+    }
+
+    /// ### with_always_show_parent
+    ///
+    /// Enable ALWAYS_SHOW_PARENT option: the parent directory entry (`..`) is listed even if hidden files are not
+    pub fn with_always_show_parent(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            match val {
+                true => e.opts.insert(ExplorerOpts::ALWAYS_SHOW_PARENT),
+                false => e.opts.remove(ExplorerOpts::ALWAYS_SHOW_PARENT),
+            }
+        }
+        self
+    }
+
+    /// ### with_dirs_first_locked
+    ///
+    /// Enable LOCK_DIRS_FIRST option: directories always come before files, whatever the sorting and the grouping
+    pub fn with_dirs_first_locked(&mut self, val: bool) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            match val {
+                true => e.opts.insert(ExplorerOpts::LOCK_DIRS_FIRST),
+                false => e.opts.remove(ExplorerOpts::LOCK_DIRS_FIRST),
+            }
+        }
+        self
+    }
+
+    /// ### with_hidden_patterns
+    ///
+    /// Hide also the files whose name matches one of the provided patterns (wildcards are supported)
+    pub fn with_hidden_patterns(&mut self, patterns: &[String]) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.hidden_patterns = patterns.iter().map(|x| WildMatch::new(x)).collect();
+        }
+        self
+    }
+
     /// ### with_file_sorting
     ///
     /// Set sorting method
@@ -78,9 +129,9 @@ impl FileExplorerBuilder {
         self
     }
 
-    /// ### with_dirs_first
+    /// ### with_group_dirs
     ///
-    /// Enable DIRS_FIRST option
+    /// Set how directories are grouped when sorting files
     pub fn with_group_dirs(&mut self, group_dirs: Option<GroupDirs>) -> &mut FileExplorerBuilder {
         if let Some(e) = self.explorer.as_mut() {
             e.group_dirs_by(group_dirs);
@@ -105,28 +156,47 @@ impl FileExplorerBuilder {
     pub fn with_formatter(&mut self, fmt_str: Option<&str>) -> &mut FileExplorerBuilder {
         if let Some(e) = self.explorer.as_mut() {
             if let Some(fmt_str) = fmt_str {
+                // Keep size unit
+                let size_unit: SizeUnit = e.fmt.size_unit();
                 e.fmt = Formatter::new(fmt_str);
+                e.fmt.set_size_unit(size_unit);
             }
         }
         self
     }
+
+    /// ### with_size_unit
+    ///
+    /// Set the unit used by the formatter to display sizes
+    pub fn with_size_unit(&mut self, unit: SizeUnit) -> &mut FileExplorerBuilder {
+        if let Some(e) = self.explorer.as_mut() {
+            e.fmt.set_size_unit(unit);
+        }
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::fs::{FsEntry, FsFile};
 
     use pretty_assertions::assert_eq;
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
 
     #[test]
     fn test_fs_explorer_builder_new_default() {
         let explorer: FileExplorer = FileExplorerBuilder::new().build();
         // Verify
         assert!(!explorer.opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES));
+        assert!(!explorer.opts.intersects(ExplorerOpts::ALWAYS_SHOW_PARENT));
+        assert!(!explorer.opts.intersects(ExplorerOpts::LOCK_DIRS_FIRST));
         assert_eq!(explorer.file_sorting, FileSorting::ByName); // Default
         assert_eq!(explorer.group_dirs, None);
         assert_eq!(explorer.stack_size, 16);
+        assert!(explorer.hidden_patterns.is_empty());
     }
 
     #[test]
@@ -135,13 +205,100 @@ mod tests {
             .with_file_sorting(FileSorting::ByModifyTime)
             .with_group_dirs(Some(GroupDirs::First))
             .with_hidden_files(true)
+            .with_always_show_parent(true)
+            .with_dirs_first_locked(true)
+            .with_hidden_patterns(&[String::from("*.bak")])
             .with_stack_size(24)
             .with_formatter(Some("{NAME}"))
             .build();
         // Verify
         assert!(explorer.opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES));
+        assert!(explorer.opts.intersects(ExplorerOpts::ALWAYS_SHOW_PARENT));
+        assert!(explorer.opts.intersects(ExplorerOpts::LOCK_DIRS_FIRST));
         assert_eq!(explorer.file_sorting, FileSorting::ByModifyTime); // Default
         assert_eq!(explorer.group_dirs, Some(GroupDirs::First));
         assert_eq!(explorer.stack_size, 24);
+        assert_eq!(explorer.hidden_patterns.len(), 1);
+    }
+
+    #[test]
+    fn test_fs_explorer_builder_options() {
+        let mut explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_file_sorting(FileSorting::BySize)
+            .with_group_dirs(Some(GroupDirs::Last))
+            .with_hidden_files(false)
+            .with_stack_size(2)
+            .with_formatter(Some("{NAME}"))
+            .build();
+        assert_eq!(explorer.get_file_sorting(), FileSorting::BySize);
+        assert_eq!(explorer.group_dirs, Some(GroupDirs::Last));
+        assert_eq!(explorer.hidden_files_visible(), false);
+        assert_eq!(explorer.relative_paths(), false);
+        assert_eq!(
+            FileExplorerBuilder::new()
+                .with_relative_paths(true)
+                .build()
+                .relative_paths(),
+            true
+        );
+        let entry: FsEntry = make_fs_entry("README.md");
+        assert_eq!(explorer.fmt_file(&entry).trim_end(), "README.md");
+        // Stack size is honoured
+        assert_eq!(explorer.stack_size, 2);
+        explorer.pushd(Path::new("/tmp"));
+        explorer.pushd(Path::new("/home"));
+        explorer.pushd(Path::new("/home/omar"));
+        assert_eq!(explorer.dirstack.len(), 2);
+        assert_eq!(explorer.popd().unwrap(), PathBuf::from("/home/omar"));
+        assert_eq!(explorer.popd().unwrap(), PathBuf::from("/home"));
+        assert!(explorer.popd().is_none());
+        // Without a format string the default formatter is kept
+        let explorer: FileExplorer = FileExplorerBuilder::new().with_formatter(None).build();
+        assert_eq!(explorer.fmt_file(&entry), Formatter::default().fmt(&entry));
+    }
+
+    #[test]
+    fn test_fs_explorer_builder_size_unit() {
+        let entry: FsEntry = match make_fs_entry("README.md") {
+            FsEntry::File(mut file) => {
+                file.size = 8192;
+                FsEntry::File(file)
+            }
+            entry => entry,
+        };
+        // Default is SI
+        let explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_formatter(Some("{SIZE}"))
+            .build();
+        assert_eq!(explorer.fmt_file(&entry).trim_end(), "8.2 KB");
+        // Size unit is kept, whatever the order
+        let explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_size_unit(SizeUnit::Binary)
+            .with_formatter(Some("{SIZE}"))
+            .build();
+        assert_eq!(explorer.fmt_file(&entry).trim_end(), "8 KiB");
+        let explorer: FileExplorer = FileExplorerBuilder::new()
+            .with_formatter(Some("{SIZE}"))
+            .with_size_unit(SizeUnit::Binary)
+            .build();
+        assert_eq!(explorer.fmt_file(&entry).trim_end(), "8 KiB");
+    }
+
+    fn make_fs_entry(name: &str) -> FsEntry {
+        let t_now: SystemTime = SystemTime::now();
+        FsEntry::File(FsFile {
+            name: name.to_string(),
+            abs_path: PathBuf::from(name),
+            last_change_time: t_now,
+            last_access_time: t_now,
+            creation_time: t_now,
+            size: 64,
+            ftype: None,
+            readonly: false,
+            symlink: None,
+            user: Some(0),
+            group: Some(0),
+            unix_pex: Some((6, 4, 4)),
+        })
     }
 }
@@ -26,13 +26,13 @@
  * SOFTWARE.
  */
 // Locals
+use super::resolver::IdResolver;
 use super::FsEntry;
-use crate::utils::fmt::{fmt_path_elide, fmt_pex, fmt_time};
+use crate::utils::file::relative_path;
+use crate::utils::fmt::{fmt_bytes, fmt_path_elide, fmt_pex, fmt_time, SizeUnit};
 // Ext
-use bytesize::ByteSize;
 use regex::Regex;
-#[cfg(target_family = "unix")]
-use users::{get_group_by_gid, get_user_by_uid};
+use std::path::Path;
 // Types
 // FmtCallback: Formatter, fsentry: &FsEntry, cur_str, prefix, length, extra
 type FmtCallback = fn(&Formatter, &FsEntry, &str, &str, Option<&usize>, Option<&String>) -> String;
@@ -53,12 +53,22 @@ const FMT_DEFAULT_STX: &str = "{NAME} {PEX} {USER} {SIZE} {MTIME}";
 lazy_static! {
     /**
      * Regex matches:
-     *  - group 0: KEY NAME
-     *  - group 1?: LENGTH
-     *  - group 2?: EXTRA
+     *  - group 1: KEY NAME
+     *  - group 2?: ALIGNMENT (`<` or `>`)
+     *  - group 3?: LENGTH
+     *  - group 4?: EXTRA
      */
     static ref FMT_KEY_REGEX: Regex = Regex::new(r"\{(.*?)\}").ok().unwrap();
-    static ref FMT_ATTR_REGEX: Regex = Regex::new(r"(?:([A-Z]+))(:?([0-9]+))?(:?(.+))?").ok().unwrap();
+    static ref FMT_ATTR_REGEX: Regex = Regex::new(r"^([A-Za-z]+)(?::([<>])?([0-9]+)?)?(?::(.+))?$").ok().unwrap();
+}
+
+/// ## FmtAlign
+///
+/// Describes how a field is aligned in the space reserved for it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FmtAlign {
+    Left,
+    Right,
 }
 
 /// ## CallChainBlock
@@ -72,6 +82,7 @@ struct CallChainBlock {
     prefix: String,
     fmt_len: Option<usize>,
     fmt_extra: Option<String>,
+    fmt_align: FmtAlign,
     next_block: Option<Box<CallChainBlock>>,
 }
 
@@ -90,23 +101,52 @@ impl CallChainBlock {
             prefix,
             fmt_len,
             fmt_extra,
+            fmt_align: FmtAlign::Left,
             next_block: None,
         }
     }
 
+    /// ### aligned
+    ///
+    /// Set the alignment of the field formatted by the block
+    fn aligned(mut self, align: FmtAlign) -> Self {
+        self.fmt_align = align;
+        self
+    }
+
     /// ### next
     ///
     /// Call next callback in the CallChain
     pub fn next(&self, fmt: &Formatter, fsentry: &FsEntry, cur_str: &str) -> String {
         // Call func
-        let new_str: String = (self.func)(
-            fmt,
-            fsentry,
-            cur_str,
-            self.prefix.as_str(),
-            self.fmt_len.as_ref(),
-            self.fmt_extra.as_ref(),
-        );
+        let new_str: String = match self.fmt_align {
+            FmtAlign::Left => (self.func)(
+                fmt,
+                fsentry,
+                cur_str,
+                self.prefix.as_str(),
+                self.fmt_len.as_ref(),
+                self.fmt_extra.as_ref(),
+            ),
+            FmtAlign::Right => {
+                // Format the field alone, then move its padding to the left
+                let field: String = (self.func)(
+                    fmt,
+                    fsentry,
+                    "",
+                    "",
+                    self.fmt_len.as_ref(),
+                    self.fmt_extra.as_ref(),
+                );
+                format!(
+                    "{}{}{:>width$}",
+                    cur_str,
+                    self.prefix,
+                    field.trim_end(),
+                    width = field.chars().count()
+                )
+            }
+        };
         // If next is some, call next, otherwise (END OF CHAIN) return new_str
         match &self.next_block {
             Some(block) => block.next(fmt, fsentry, new_str.as_str()),
@@ -116,22 +156,12 @@ impl CallChainBlock {
 
     /// ### push
     ///
-    /// Push func to the last element in the Call chain
-    pub fn push(
-        &mut self,
-        func: FmtCallback,
-        prefix: String,
-        fmt_len: Option<usize>,
-        fmt_extra: Option<String>,
-    ) {
+    /// Push block to the last element in the Call chain
+    pub fn push(&mut self, block: CallChainBlock) {
         // Call recursively until an element with next_block equal to None is found
         match &mut self.next_block {
-            None => {
-                self.next_block = Some(Box::new(CallChainBlock::new(
-                    func, prefix, fmt_len, fmt_extra,
-                )))
-            }
-            Some(block) => block.push(func, prefix, fmt_len, fmt_extra),
+            None => self.next_block = Some(Box::new(block)),
+            Some(next) => next.push(block),
         }
     }
 }
@@ -143,6 +173,8 @@ impl CallChainBlock {
 /// at each fmt call.
 pub struct Formatter {
     call_chain: CallChainBlock,
+    resolver: IdResolver,
+    size_unit: SizeUnit,
 }
 
 impl Default for Formatter {
@@ -151,7 +183,9 @@ impl Default for Formatter {
     /// Instantiates a Formatter with the default fmt syntax
     fn default() -> Self {
         Formatter {
-            call_chain: Self::make_callchain(FMT_DEFAULT_STX),
+            call_chain: Self::make_callchain(FMT_DEFAULT_STX).ok().unwrap(),
+            resolver: IdResolver::default(),
+            size_unit: SizeUnit::default(),
         }
     }
 }
@@ -159,13 +193,39 @@ impl Default for Formatter {
 impl Formatter {
     /// ### new
     ///
-    /// Instantiates a new `Formatter` with the provided format string
+    /// Instantiates a new `Formatter` with the provided format string.
+    /// If the format string is invalid, the default syntax is used
     pub fn new(fmt_str: &str) -> Self {
-        Formatter {
-            call_chain: Self::make_callchain(fmt_str),
+        match Self::make_callchain(fmt_str) {
+            Ok(call_chain) => Formatter {
+                call_chain,
+                resolver: IdResolver::default(),
+                size_unit: SizeUnit::default(),
+            },
+            Err(err) => {
+                warn!(
+                    "Invalid file formatter syntax \"{}\": {}; using default syntax",
+                    fmt_str, err
+                );
+                Self::default()
+            }
         }
     }
 
+    /// ### size_unit
+    ///
+    /// Get the unit used to format sizes
+    pub fn size_unit(&self) -> SizeUnit {
+        self.size_unit
+    }
+
+    /// ### set_size_unit
+    ///
+    /// Set the unit used to format sizes
+    pub fn set_size_unit(&mut self, unit: SizeUnit) {
+        self.size_unit = unit;
+    }
+
     /// ### fmt
     ///
     /// Format fsentry
@@ -174,6 +234,22 @@ impl Formatter {
         self.call_chain.next(self, fsentry, "")
     }
 
+    /// ### fmt_relative
+    ///
+    /// Format fsentry, displaying its path relative to `base` in place of its name.
+    /// If the entry is not under `base`, its absolute path is displayed
+    pub fn fmt_relative(&self, fsentry: &FsEntry, base: &Path) -> String {
+        let mut fsentry: FsEntry = fsentry.clone();
+        let name: String = relative_path(base, fsentry.get_abs_path().as_path())
+            .to_string_lossy()
+            .to_string();
+        match &mut fsentry {
+            FsEntry::Directory(dir) => dir.name = name,
+            FsEntry::File(file) => file.name = name,
+        }
+        self.fmt(&fsentry)
+    }
+
     // Fmt methods
 
     /// ### fmt_atime
@@ -245,18 +321,9 @@ impl Formatter {
         fmt_len: Option<&usize>,
         _fmt_extra: Option<&String>,
     ) -> String {
-        // Get username
-        #[cfg(target_family = "unix")]
+        // Get group name
         let group: String = match fsentry.get_group() {
-            Some(gid) => match get_group_by_gid(gid) {
-                Some(user) => user.name().to_string_lossy().to_string(),
-                None => gid.to_string(),
-            },
-            None => 0.to_string(),
-        };
-        #[cfg(target_os = "windows")]
-        let group: String = match fsentry.get_group() {
-            Some(gid) => gid.to_string(),
+            Some(gid) => self.resolver.group(gid),
             None => 0.to_string(),
         };
         // Add to cur str, prefix and the key value
@@ -314,15 +381,10 @@ impl Formatter {
             Some(l) => *l,
             None => 24,
         };
-        let name: &str = fsentry.get_name();
-        let last_idx: usize = match fsentry.is_dir() {
-            // NOTE: For directories is l - 2, since we push '/' to name
-            true => file_len - 2,
-            false => file_len - 1,
-        };
-        let mut name: String = match name.len() >= file_len {
-            false => name.to_string(),
-            true => format!("{}…", &name[0..last_idx]),
+        let mut name: String = match fsentry.is_dir() {
+            // NOTE: For directories is l - 1, since we push '/' to name
+            true => Self::elide_name(fsentry.get_name(), file_len.saturating_sub(1), false),
+            false => Self::elide_name(fsentry.get_name(), file_len, true),
         };
         if fsentry.is_dir() {
             name.push('/');
@@ -331,6 +393,31 @@ impl Formatter {
         format!("{}{}{:0width$}", cur_str, prefix, name, width = file_len)
     }
 
+    /// ### elide_name
+    ///
+    /// Truncate `name` to `width` characters, replacing the last visible characters with an ellipsis.
+    /// If `keep_ext` is true, the extension is preserved whenever it takes less than half of the width
+    /// (e.g. `verylongname.txt` becomes `veryl….txt` with a width of 10)
+    fn elide_name(name: &str, width: usize, keep_ext: bool) -> String {
+        if name.chars().count() <= width {
+            return name.to_string();
+        }
+        if width == 0 {
+            return String::new();
+        }
+        let ext: Option<&str> = match keep_ext {
+            true => name
+                .rfind('.')
+                .filter(|x| *x > 0 && *x < name.len() - 1)
+                .map(|x| &name[x..])
+                .filter(|x| x.chars().count() * 2 < width),
+            false => None,
+        };
+        let ext: &str = ext.unwrap_or("");
+        let head: String = name.chars().take(width - 1 - ext.chars().count()).collect();
+        format!("{}…{}", head, ext)
+    }
+
     /// ### fmt_pex
     ///
     /// Format file permissions
@@ -368,18 +455,22 @@ impl Formatter {
         fsentry: &FsEntry,
         cur_str: &str,
         prefix: &str,
-        _fmt_len: Option<&usize>,
+        fmt_len: Option<&usize>,
         _fmt_extra: Option<&String>,
     ) -> String {
-        if fsentry.is_file() {
+        let size: String = match fsentry.is_file() {
             // Get byte size
-            let size: ByteSize = ByteSize(fsentry.get_size() as u64);
-            // Add to cur str, prefix and the key value
-            format!("{}{}{:10}", cur_str, prefix, size.to_string())
-        } else {
-            // Add to cur str, prefix and the key value
-            format!("{}{}          ", cur_str, prefix)
-        }
+            true => fmt_bytes(fsentry.get_size() as u64, self.size_unit),
+            false => String::new(),
+        };
+        // Add to cur str, prefix and the key value
+        format!(
+            "{}{}{:width$}",
+            cur_str,
+            prefix,
+            size,
+            width = fmt_len.unwrap_or(&10)
+        )
     }
 
     /// ### fmt_symlink
@@ -422,25 +513,22 @@ impl Formatter {
         fsentry: &FsEntry,
         cur_str: &str,
         prefix: &str,
-        _fmt_len: Option<&usize>,
+        fmt_len: Option<&usize>,
         _fmt_extra: Option<&String>,
     ) -> String {
         // Get username
-        #[cfg(target_family = "unix")]
         let username: String = match fsentry.get_user() {
-            Some(uid) => match get_user_by_uid(uid) {
-                Some(user) => user.name().to_string_lossy().to_string(),
-                None => uid.to_string(),
-            },
-            None => 0.to_string(),
-        };
-        #[cfg(target_os = "windows")]
-        let username: String = match fsentry.get_user() {
-            Some(uid) => uid.to_string(),
+            Some(uid) => self.resolver.user(uid),
             None => 0.to_string(),
         };
         // Add to cur str, prefix and the key value
-        format!("{}{}{:12}", cur_str, prefix, username)
+        format!(
+            "{}{}{:width$}",
+            cur_str,
+            prefix,
+            username,
+            width = fmt_len.unwrap_or(&12)
+        )
     }
 
     /// ### fmt_fallback
@@ -463,8 +551,9 @@ impl Formatter {
 
     /// ### make_callchain
     ///
-    /// Make a callchain starting from the fmt str
-    fn make_callchain(fmt_str: &str) -> CallChainBlock {
+    /// Make a callchain starting from the fmt str.
+    /// Returns error if a key is unknown or its attributes are malformed
+    fn make_callchain(fmt_str: &str) -> Result<CallChainBlock, String> {
         // Init chain block
         let mut callchain: Option<CallChainBlock> = None;
         // Track index of the last match found, to get the prefix for each token
@@ -472,60 +561,64 @@ impl Formatter {
         // Match fmt str against regex
         for regex_match in FMT_KEY_REGEX.captures_iter(fmt_str) {
             // Get match index (unwrap is safe, since always exists)
-            let index: usize = fmt_str.find(&regex_match[0]).unwrap();
+            let index: usize = last_index + fmt_str[last_index..].find(&regex_match[0]).unwrap();
             // Get prefix
             let prefix: String = String::from(&fmt_str[last_index..index]);
             // Increment last index (sum prefix lenght and the length of the key)
             last_index += prefix.len() + regex_match[0].len();
             // Match attributes
-            match FMT_ATTR_REGEX.captures(&regex_match[1]) {
-                Some(regex_match) => {
-                    // Match group 0 (which is name)
-                    let callback: FmtCallback = match &regex_match.get(1) {
-                        Some(key) => match key.as_str() {
-                            FMT_KEY_ATIME => Self::fmt_atime,
-                            FMT_KEY_CTIME => Self::fmt_ctime,
-                            FMT_KEY_GROUP => Self::fmt_group,
-                            FMT_KEY_MTIME => Self::fmt_mtime,
-                            FMT_KEY_NAME => Self::fmt_name,
-                            FMT_KEY_PEX => Self::fmt_pex,
-                            FMT_KEY_SIZE => Self::fmt_size,
-                            FMT_KEY_SYMLINK => Self::fmt_symlink,
-                            FMT_KEY_USER => Self::fmt_user,
-                            _ => Self::fmt_fallback,
-                        },
-                        None => Self::fmt_fallback,
-                    };
-                    // Match format length: group 3
-                    let fmt_len: Option<usize> = match &regex_match.get(3) {
-                        Some(len) => match len.as_str().parse::<usize>() {
-                            Ok(len) => Some(len),
-                            Err(_) => None,
-                        },
-                        None => None,
-                    };
-                    // Match format extra: group 2 + 1
-                    let fmt_extra: Option<String> = regex_match
-                        .get(5)
-                        .as_ref()
-                        .map(|extra| extra.as_str().to_string());
-                    // Create a callchain or push new element to its back
-                    match callchain.as_mut() {
-                        None => {
-                            callchain =
-                                Some(CallChainBlock::new(callback, prefix, fmt_len, fmt_extra))
-                        }
-                        Some(chain_block) => chain_block.push(callback, prefix, fmt_len, fmt_extra),
-                    }
-                }
-                None => continue,
+            let attrs = FMT_ATTR_REGEX
+                .captures(&regex_match[1])
+                .ok_or_else(|| format!("invalid key \"{}\"", &regex_match[0]))?;
+            // Match group 1 (which is name); keys are case insensitive
+            let callback: FmtCallback = match attrs[1].to_ascii_uppercase().as_str() {
+                FMT_KEY_ATIME => Self::fmt_atime,
+                FMT_KEY_CTIME => Self::fmt_ctime,
+                FMT_KEY_GROUP => Self::fmt_group,
+                FMT_KEY_MTIME => Self::fmt_mtime,
+                FMT_KEY_NAME => Self::fmt_name,
+                FMT_KEY_PEX => Self::fmt_pex,
+                FMT_KEY_SIZE => Self::fmt_size,
+                FMT_KEY_SYMLINK => Self::fmt_symlink,
+                FMT_KEY_USER => Self::fmt_user,
+                key => return Err(format!("unknown key \"{}\"", key)),
+            };
+            // Match alignment: group 2
+            let fmt_align: FmtAlign = match attrs.get(2).map(|x| x.as_str()) {
+                Some(">") => FmtAlign::Right,
+                _ => FmtAlign::Left,
+            };
+            // Match format length: group 3
+            let fmt_len: Option<usize> = match attrs.get(3) {
+                Some(len) => Some(
+                    len.as_str()
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid length in \"{}\"", &regex_match[0]))?,
+                ),
+                None => None,
+            };
+            // Match format extra: group 4
+            let fmt_extra: Option<String> = attrs.get(4).map(|extra| extra.as_str().to_string());
+            // Create a callchain or push new element to its back
+            let block: CallChainBlock =
+                CallChainBlock::new(callback, prefix, fmt_len, fmt_extra).aligned(fmt_align);
+            match callchain.as_mut() {
+                None => callchain = Some(block),
+                Some(chain_block) => chain_block.push(block),
             }
         }
+        // Text after the last key is kept as it is
+        let suffix: String = String::from(&fmt_str[last_index..]);
         // Finalize and return
-        match callchain {
-            Some(callchain) => callchain,
-            None => CallChainBlock::new(Self::fmt_fallback, String::new(), None, None),
-        }
+        Ok(match callchain {
+            Some(mut callchain) => {
+                if !suffix.is_empty() {
+                    callchain.push(CallChainBlock::new(Self::fmt_fallback, suffix, None, None));
+                }
+                callchain
+            }
+            None => CallChainBlock::new(Self::fmt_fallback, suffix, None, None),
+        })
     }
 }
 
@@ -537,7 +630,7 @@ mod tests {
 
     use pretty_assertions::assert_eq;
     use std::path::PathBuf;
-    use std::time::SystemTime;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_fs_explorer_formatter_callchain() {
@@ -569,10 +662,30 @@ mod tests {
             String::from("hA")
         );
         // Push 4 new blocks
-        callchain.push(dummy_fmt, String::from("h"), None, None);
-        callchain.push(dummy_fmt, String::from("h"), None, None);
-        callchain.push(dummy_fmt, String::from("h"), None, None);
-        callchain.push(dummy_fmt, String::from("h"), None, None);
+        callchain.push(CallChainBlock::new(
+            dummy_fmt,
+            String::from("h"),
+            None,
+            None,
+        ));
+        callchain.push(CallChainBlock::new(
+            dummy_fmt,
+            String::from("h"),
+            None,
+            None,
+        ));
+        callchain.push(CallChainBlock::new(
+            dummy_fmt,
+            String::from("h"),
+            None,
+            None,
+        ));
+        callchain.push(CallChainBlock::new(
+            dummy_fmt,
+            String::from("h"),
+            None,
+            None,
+        ));
         // Verify
         assert_eq!(
             callchain.next(&dummy_formatter, &dummy_entry, ""),
@@ -635,7 +748,7 @@ mod tests {
         assert_eq!(
             formatter.fmt(&entry),
             format!(
-                "piroparoporoperoperupup… -rw-r--r-- root         8.2 KB     {}",
+                "piroparoporoperoper….txt -rw-r--r-- root         8.2 KB     {}",
                 fmt_time(t, "%b %d %Y %H:%M")
             )
         );
@@ -643,7 +756,7 @@ mod tests {
         assert_eq!(
             formatter.fmt(&entry),
             format!(
-                "piroparoporoperoperupup… -rw-r--r-- 0            8.2 KB     {}",
+                "piroparoporoperoper….txt -rw-r--r-- 0            8.2 KB     {}",
                 fmt_time(t, "%b %d %Y %H:%M")
             )
         );
@@ -891,6 +1004,236 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_fs_explorer_formatter_width_and_alignment() {
+        let t: SystemTime = SystemTime::now();
+        let entry: FsEntry = FsEntry::File(FsFile {
+            name: String::from("bar.txt"),
+            abs_path: PathBuf::from("/bar.txt"),
+            last_change_time: t,
+            last_access_time: t,
+            creation_time: t,
+            size: 8192,
+            readonly: false,
+            ftype: Some(String::from("txt")),
+            symlink: None,             // UNIX only
+            user: None,                // UNIX only
+            group: None,               // UNIX only
+            unix_pex: Some((6, 4, 4)), // UNIX only
+        });
+        // Left alignment is the default
+        let formatter: Formatter = Formatter::new("{NAME:12}|{SIZE}|");
+        assert_eq!(
+            formatter.fmt(&entry),
+            String::from("bar.txt     |8.2 KB    |")
+        );
+        let formatter: Formatter = Formatter::new("{NAME:<12}|{SIZE:<8}|");
+        assert_eq!(
+            formatter.fmt(&entry),
+            String::from("bar.txt     |8.2 KB  |")
+        );
+        // Right alignment
+        let formatter: Formatter = Formatter::new("{NAME:>12}|{SIZE:>10}|");
+        assert_eq!(
+            formatter.fmt(&entry),
+            String::from("     bar.txt|    8.2 KB|")
+        );
+        // Right alignment with extra
+        let formatter: Formatter = Formatter::new("[{MTIME:>20:%Y}]");
+        assert_eq!(
+            formatter.fmt(&entry),
+            format!("[{:>20}]", fmt_time(t, "%Y"))
+        );
+        // Directories have a blank size
+        let entry: FsEntry = FsEntry::Directory(FsDirectory {
+            name: String::from("projects"),
+            abs_path: PathBuf::from("/home/cvisintin/projects"),
+            last_change_time: t,
+            last_access_time: t,
+            creation_time: t,
+            readonly: false,
+            symlink: None,             // UNIX only
+            user: None,                // UNIX only
+            group: None,               // UNIX only
+            unix_pex: Some((7, 5, 5)), // UNIX only
+        });
+        let formatter: Formatter = Formatter::new("{NAME:>12}|{SIZE:>6}|");
+        assert_eq!(formatter.fmt(&entry), String::from("   projects/|      |"));
+    }
+
+    #[test]
+    fn test_fs_explorer_formatter_elide_name() {
+        let t: SystemTime = SystemTime::now();
+        let file = |name: &str| {
+            FsEntry::File(FsFile {
+                name: name.to_string(),
+                abs_path: PathBuf::from("/").join(name),
+                last_change_time: t,
+                last_access_time: t,
+                creation_time: t,
+                size: 8192,
+                readonly: false,
+                ftype: None,
+                symlink: None,
+                user: None,
+                group: None,
+                unix_pex: None,
+            })
+        };
+        let formatter: Formatter = Formatter::new("{NAME:12}|{SIZE}|");
+        // With extension
+        assert_eq!(
+            formatter.fmt(&file("a_very_long_file_name.tar.gz")),
+            String::from("a_very_l….gz|8.2 KB    |")
+        );
+        // Without extension
+        assert_eq!(
+            formatter.fmt(&file("a_very_long_file_name")),
+            String::from("a_very_long…|8.2 KB    |")
+        );
+        // Extension too long to be kept
+        assert_eq!(
+            formatter.fmt(&file("archive.backup_of_today")),
+            String::from("archive.bac…|8.2 KB    |")
+        );
+        // Multi-byte characters; exact width is not truncated
+        assert_eq!(
+            formatter.fmt(&file("ñññññññññññññ.txt")),
+            String::from("ñññññññ….txt|8.2 KB    |")
+        );
+        assert_eq!(
+            formatter.fmt(&file("twelve_chars")),
+            String::from("twelve_chars|8.2 KB    |")
+        );
+        // The name width follows the column width
+        assert_eq!(
+            Formatter::new("{NAME:8}|").fmt(&file("a_very_long_file_name.md")),
+            String::from("a_ve….md|")
+        );
+        // Directories never keep the extension
+        let dir: FsEntry = FsEntry::Directory(FsDirectory {
+            name: String::from("a_very_long_dir.d"),
+            abs_path: PathBuf::from("/a_very_long_dir.d"),
+            last_change_time: t,
+            last_access_time: t,
+            creation_time: t,
+            readonly: false,
+            symlink: None,
+            user: None,
+            group: None,
+            unix_pex: None,
+        });
+        assert_eq!(
+            formatter.fmt(&dir),
+            String::from("a_very_lon…/|          |")
+        );
+    }
+
+    #[test]
+    fn test_fs_explorer_formatter_case_insensitive_keys() {
+        let t: SystemTime = SystemTime::now();
+        let entry: FsEntry = FsEntry::File(FsFile {
+            name: String::from("bar.txt"),
+            abs_path: PathBuf::from("/bar.txt"),
+            last_change_time: t,
+            last_access_time: t,
+            creation_time: t,
+            size: 8192,
+            readonly: false,
+            ftype: Some(String::from("txt")),
+            symlink: None,             // UNIX only
+            user: None,                // UNIX only
+            group: None,               // UNIX only
+            unix_pex: Some((6, 4, 4)), // UNIX only
+        });
+        let formatter: Formatter = Formatter::new("{name:10} {size:>10} {mtime:20:%Y} {user:4}|");
+        assert_eq!(
+            formatter.fmt(&entry),
+            format!("bar.txt        8.2 KB {:20} 0   |", fmt_time(t, "%Y"))
+        );
+        // Same as uppercase
+        assert_eq!(
+            formatter.fmt(&entry),
+            Formatter::new("{NAME:10} {SIZE:>10} {MTIME:20:%Y} {USER:4}|").fmt(&entry)
+        );
+    }
+
+    #[test]
+    fn test_fs_explorer_formatter_times() {
+        // Each time is in a different year
+        let mtime: SystemTime = UNIX_EPOCH + Duration::from_secs(1600000000);
+        let atime: SystemTime = UNIX_EPOCH + Duration::from_secs(1640000000);
+        let ctime: SystemTime = UNIX_EPOCH + Duration::from_secs(1500000000);
+        let entry: FsEntry = FsEntry::File(FsFile {
+            name: String::from("bar.txt"),
+            abs_path: PathBuf::from("/bar.txt"),
+            last_change_time: mtime,
+            last_access_time: atime,
+            creation_time: ctime,
+            size: 8192,
+            readonly: false,
+            ftype: Some(String::from("txt")),
+            symlink: None,             // UNIX only
+            user: None,                // UNIX only
+            group: None,               // UNIX only
+            unix_pex: Some((6, 4, 4)), // UNIX only
+        });
+        let formatter: Formatter = Formatter::new("{atime}|{ctime}|{mtime}");
+        assert_eq!(
+            formatter.fmt(&entry),
+            format!(
+                "{}|{}|{}",
+                fmt_time(atime, "%b %d %Y %H:%M"),
+                fmt_time(ctime, "%b %d %Y %H:%M"),
+                fmt_time(mtime, "%b %d %Y %H:%M")
+            )
+        );
+        // Custom time format
+        let formatter: Formatter = Formatter::new("{atime:4:%Y}|{ctime:4:%Y}|{mtime:4:%Y}");
+        assert_eq!(
+            formatter.fmt(&entry),
+            format!(
+                "{}|{}|{}",
+                fmt_time(atime, "%Y"),
+                fmt_time(ctime, "%Y"),
+                fmt_time(mtime, "%Y")
+            )
+        );
+        assert_ne!(fmt_time(atime, "%Y"), fmt_time(mtime, "%Y"));
+        assert_ne!(fmt_time(ctime, "%Y"), fmt_time(mtime, "%Y"));
+    }
+
+    #[test]
+    fn test_fs_explorer_formatter_invalid_syntax() {
+        let t: SystemTime = SystemTime::now();
+        let entry: FsEntry = FsEntry::File(FsFile {
+            name: String::from("bar.txt"),
+            abs_path: PathBuf::from("/bar.txt"),
+            last_change_time: t,
+            last_access_time: t,
+            creation_time: t,
+            size: 8192,
+            readonly: false,
+            ftype: Some(String::from("txt")),
+            symlink: None,             // UNIX only
+            user: None,                // UNIX only
+            group: None,               // UNIX only
+            unix_pex: Some((6, 4, 4)), // UNIX only
+        });
+        // Unknown key
+        assert!(Formatter::make_callchain("{NAME} {FOOBAR}").is_err());
+        // Malformed key
+        assert!(Formatter::make_callchain("{NAME 12}").is_err());
+        // Bad length
+        assert!(Formatter::make_callchain("{SIZE:>99999999999999999999999}").is_err());
+        // Valid
+        assert!(Formatter::make_callchain("{name:30} {size:>10} {mtime} {user}").is_ok());
+        // Formatter falls back to default syntax
+        let default_fmt: String = Formatter::default().fmt(&entry);
+        assert_eq!(Formatter::new("{NAME} {FOOBAR}").fmt(&entry), default_fmt);
+        assert_eq!(Formatter::new("{NAME 12}").fmt(&entry), default_fmt);
+    }
+
     /// ### dummy_fmt
     ///
     /// Dummy formatter, just yelds an 'A' at the end of the current string
@@ -28,15 +28,17 @@
 // Mods
 pub(crate) mod builder;
 mod formatter;
+mod resolver;
 // Locals
 use super::FsEntry;
 use formatter::Formatter;
 // Ext
 use std::cmp::Reverse;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
+use wildmatch::WildMatch;
 
 bitflags! {
     /// ## ExplorerOpts
@@ -44,6 +46,9 @@ bitflags! {
     /// ExplorerOpts are bit options which provides different behaviours to `FileExplorer`
     pub(crate) struct ExplorerOpts: u32 {
         const SHOW_HIDDEN_FILES = 0b00000001;
+        const RELATIVE_PATHS = 0b00000010;
+        const ALWAYS_SHOW_PARENT = 0b00000100;
+        const LOCK_DIRS_FIRST = 0b00001000;
     }
 }
 
@@ -58,27 +63,50 @@ pub enum FileSorting {
     BySize,
 }
 
+/// ## PageDirection
+///
+/// PageDirection defines in which direction the selection is moved by a page
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PageDirection {
+    Up,
+    Down,
+}
+
 /// ## GroupDirs
 ///
 /// GroupDirs defines how directories should be grouped in sorting files
-#[derive(PartialEq, std::fmt::Debug)]
+#[derive(Clone, Copy, PartialEq, std::fmt::Debug)]
 pub enum GroupDirs {
     First,
     Last,
 }
 
+/// ## LoadState
+///
+/// LoadState describes whether the entries of the working directory have been listed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadState {
+    Loading,
+    Loaded,
+    PermissionDenied,
+    Failed,
+}
+
 /// ## FileExplorer
 ///
 /// File explorer states
 pub struct FileExplorer {
-    pub wrkdir: PathBuf,                      // Current directory
-    pub(crate) dirstack: VecDeque<PathBuf>,   // Stack of visited directory (max 16)
-    pub(crate) stack_size: usize,             // Directory stack size
-    pub(crate) file_sorting: FileSorting,     // File sorting criteria
-    pub(crate) group_dirs: Option<GroupDirs>, // If Some, defines how to group directories
-    pub(crate) opts: ExplorerOpts,            // Explorer options
-    pub(crate) fmt: Formatter,                // FsEntry formatter
-    files: Vec<FsEntry>,                      // Files in directory
+    pub wrkdir: PathBuf,                        // Current directory
+    pub(crate) dirstack: VecDeque<PathBuf>,     // Stack of visited directory (max 16)
+    pub(crate) stack_size: usize,               // Directory stack size
+    pub(crate) file_sorting: FileSorting,       // File sorting criteria
+    pub(crate) group_dirs: Option<GroupDirs>,   // If Some, defines how to group directories
+    pub(crate) opts: ExplorerOpts,              // Explorer options
+    pub(crate) fmt: Formatter,                  // FsEntry formatter
+    pub(crate) hidden_patterns: Vec<WildMatch>, // Names matching these patterns are hidden too
+    files: Vec<FsEntry>,                        // Files in directory
+    marks: HashSet<usize>, // Indexes of the marked entries among the listed ones
+    load_state: LoadState, // Whether the working directory has been listed
 }
 
 impl Default for FileExplorer {
@@ -91,7 +119,10 @@ impl Default for FileExplorer {
             group_dirs: None,
             opts: ExplorerOpts::empty(),
             fmt: Formatter::default(),
+            hidden_patterns: Vec::new(),
             files: Vec::new(),
+            marks: HashSet::new(),
+            load_state: LoadState::Loading,
         }
     }
 }
@@ -123,19 +154,128 @@ impl FileExplorer {
     /// Once all sorting have been performed, index is moved to first valid entry.
     pub fn set_files(&mut self, files: Vec<FsEntry>) {
         self.files = files;
+        self.load_state = LoadState::Loaded;
         // Sort
         self.sort();
     }
 
+    /// ### append_files
+    ///
+    /// Append entries to the listed ones, without sorting them again, so the listed entries keep their position.
+    /// If directories first is locked, directories are moved before files anyway
+    #[allow(dead_code)]
+    pub fn append_files(&mut self, files: Vec<FsEntry>) {
+        self.files.extend(files);
+        self.load_state = LoadState::Loaded;
+        self.regroup_locked_dirs();
+    }
+
+    /// ### reload_preserving_selection
+    ///
+    /// Set Explorer files, like `set_files`, but entries which were marked are marked again
+    /// if an entry with the same name is still listed
+    pub fn reload_preserving_selection(&mut self, files: Vec<FsEntry>) {
+        let marked: HashSet<String> = self
+            .marked_entries()
+            .into_iter()
+            .map(|x| x.get_name().to_string())
+            .collect();
+        self.set_files(files);
+        self.marks = self
+            .iter_files()
+            .enumerate()
+            .filter(|(_, x)| marked.contains(x.get_name()))
+            .map(|(idx, _)| idx)
+            .collect();
+    }
+
     /// ### del_entry
     ///
-    /// Delete file at provided index
+    /// Delete file at provided relative index.
+    /// Filters are applied based on current options (e.g. hidden files are not considered)
     pub fn del_entry(&mut self, idx: usize) {
-        if self.files.len() > idx {
+        if let Some(idx) = self.absolute_index(idx) {
             self.files.remove(idx);
+            self.clear_marks();
+            self.regroup_locked_dirs();
+        }
+    }
+
+    /// ### del_entry_by_name
+    ///
+    /// Delete file with provided name, regardless of current filters
+    #[allow(dead_code)]
+    pub fn del_entry_by_name(&mut self, name: &str) {
+        let idx: Option<usize> = self.iter_files().position(|x| x.get_name() == name);
+        match idx {
+            Some(idx) => self.del_entry(idx),
+            None => {
+                // Entry is filtered out (e.g. hidden)
+                let idx: Option<usize> = self.files.iter().position(|x| x.get_name() == name);
+                if let Some(idx) = idx {
+                    self.files.remove(idx);
+                    self.clear_marks();
+                    self.regroup_locked_dirs();
+                }
+            }
         }
     }
 
+    /// ### absolute_index
+    ///
+    /// Translate a relative index (filters applied) into the index of the entry in `files`
+    fn absolute_index(&self, idx: usize) -> Option<usize> {
+        let show_hidden: bool = self.hidden_files_visible();
+        self.files
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| show_hidden || !self.is_hidden(x))
+            .nth(idx)
+            .map(|(idx, _)| idx)
+    }
+
+    /// ### is_empty
+    ///
+    /// Returns whether there are no listed entries.
+    /// Filters are applied based on current options (e.g. hidden files are not considered)
+    pub fn is_empty(&self) -> bool {
+        self.iter_files().next().is_none()
+    }
+
+    /// ### load_state
+    ///
+    /// Returns whether the working directory has been listed
+    pub fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    /// ### set_loading
+    ///
+    /// Mark the working directory as being listed
+    pub fn set_loading(&mut self) {
+        self.load_state = LoadState::Loading;
+    }
+
+    /// ### set_load_failed
+    ///
+    /// Mark the working directory as not listable; the entries listed so far are removed
+    pub fn set_load_failed(&mut self, permission_denied: bool) {
+        self.clear();
+        self.load_state = match permission_denied {
+            true => LoadState::PermissionDenied,
+            false => LoadState::Failed,
+        };
+    }
+
+    /// ### clear
+    ///
+    /// Remove all the entries and their marks.
+    /// Sorting, formatter, hidden patterns, options and working directory are preserved
+    pub fn clear(&mut self) {
+        self.files.clear();
+        self.clear_marks();
+    }
+
     /*
     /// ### count
     ///
@@ -157,12 +297,38 @@ impl FileExplorer {
             let mut pass: bool = true;
             // If hidden files SHOULDN'T be shown, AND pass with not hidden
             if !opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES) {
-                pass &= !x.is_hidden();
+                pass &= !self.is_hidden(x);
             }
             pass
         }))
     }
 
+    /// ### first_index
+    ///
+    /// Get the index of the first listed entry
+    pub fn first_index(&self) -> usize {
+        0
+    }
+
+    /// ### last_index
+    ///
+    /// Get the index of the last listed entry; 0 if there are no entries
+    pub fn last_index(&self) -> usize {
+        self.iter_files().count().saturating_sub(1)
+    }
+
+    /// ### page_index
+    ///
+    /// Get the index reached moving by `page_size` entries from `current` in `direction`,
+    /// clamped between the first and the last listed entry
+    pub fn page_index(&self, current: usize, page_size: usize, direction: PageDirection) -> usize {
+        let last: usize = self.last_index();
+        match direction {
+            PageDirection::Up => current.min(last).saturating_sub(page_size),
+            PageDirection::Down => current.saturating_add(page_size).min(last),
+        }
+    }
+
     /// ### iter_files_all
     ///
     /// Iterate all files; doesn't care about options
@@ -183,7 +349,7 @@ impl FileExplorer {
                 let mut pass: bool = true;
                 // If hidden files SHOULDN'T be shown, AND pass with not hidden
                 if !opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES) {
-                    pass &= !x.is_hidden();
+                    pass &= !self.is_hidden(x);
                 }
                 pass
             })
@@ -191,13 +357,80 @@ impl FileExplorer {
         filtered.get(idx).copied()
     }
 
+    /// ### get_by_path
+    ///
+    /// Get the entry at absolute path `path` along with its relative index.
+    /// Filters are applied based on current options (e.g. hidden files are not considered)
+    pub fn get_by_path(&self, path: &Path) -> Option<(usize, &FsEntry)> {
+        self.iter_files()
+            .enumerate()
+            .find(|(_, x)| x.get_abs_path() == path)
+    }
+
+    // Marks
+
+    /// ### toggle_mark
+    ///
+    /// Mark or unmark the entry at relative index `idx`
+    pub fn toggle_mark(&mut self, idx: usize) {
+        if !self.marks.remove(&idx) && idx < self.iter_files().count() {
+            self.marks.insert(idx);
+        }
+    }
+
+    /// ### mark_all
+    ///
+    /// Mark all the listed entries
+    pub fn mark_all(&mut self) {
+        self.marks = (0..self.iter_files().count()).collect();
+    }
+
+    /// ### clear_marks
+    ///
+    /// Unmark all the entries
+    pub fn clear_marks(&mut self) {
+        self.marks.clear();
+    }
+
+    /// ### invert_marks
+    ///
+    /// Mark the listed entries which are not marked and unmark the others
+    pub fn invert_marks(&mut self) {
+        self.marks = (0..self.iter_files().count())
+            .filter(|x| !self.marks.contains(x))
+            .collect();
+    }
+
+    /// ### marks
+    ///
+    /// Get the relative indexes of the marked entries, sorted
+    pub fn marks(&self) -> Vec<usize> {
+        let mut marks: Vec<usize> = self.marks.iter().copied().collect();
+        marks.sort_unstable();
+        marks
+    }
+
+    /// ### marked_entries
+    ///
+    /// Get the marked entries, in the order they're listed
+    pub fn marked_entries(&self) -> Vec<&FsEntry> {
+        self.iter_files()
+            .enumerate()
+            .filter(|(idx, _)| self.marks.contains(idx))
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
     // Formatting
 
     /// ### fmt_file
     ///
     /// Format a file entry
     pub fn fmt_file(&self, entry: &FsEntry) -> String {
-        self.fmt.fmt(entry)
+        match self.relative_paths() {
+            true => self.fmt.fmt_relative(entry, self.wrkdir.as_path()),
+            false => self.fmt.fmt(entry),
+        }
     }
 
     // Sorting
@@ -213,6 +446,49 @@ impl FileExplorer {
         }
     }
 
+    /// ### cycle_sort
+    ///
+    /// Sort files by the next sorting method, in the order name, size, modify time and creation time,
+    /// or by the previous one if `reverse` is true.
+    /// Creation time is skipped if it isn't applicable, i.e. if it is the same as the modify time for all the entries
+    /// (e.g. on remote hosts which don't report it), since it would give the same order.
+    /// Returns the new sorting method
+    pub fn cycle_sort(&mut self, reverse: bool) -> FileSorting {
+        let cycle: Vec<FileSorting> = [
+            FileSorting::ByName,
+            FileSorting::BySize,
+            FileSorting::ByModifyTime,
+            FileSorting::ByCreationTime,
+        ]
+        .iter()
+        .copied()
+        .filter(|x| self.sorting_applicable(*x))
+        .collect();
+        let pos: usize = cycle
+            .iter()
+            .position(|x| *x == self.file_sorting)
+            .unwrap_or(0);
+        let sorting: FileSorting = match reverse {
+            false => cycle[(pos + 1) % cycle.len()],
+            true => cycle[(pos + cycle.len() - 1) % cycle.len()],
+        };
+        self.sort_by(sorting);
+        sorting
+    }
+
+    /// ### sorting_applicable
+    ///
+    /// Returns whether sorting by `sorting` makes sense for the current entries
+    fn sorting_applicable(&self, sorting: FileSorting) -> bool {
+        match sorting {
+            FileSorting::ByCreationTime => self
+                .files
+                .iter()
+                .any(|x| x.get_creation_time() != x.get_last_change_time()),
+            _ => true,
+        }
+    }
+
     /// ### get_file_sorting
     ///
     /// Get current file sorting method
@@ -235,6 +511,8 @@ impl FileExplorer {
     ///
     /// Sort files based on Explorer options.
     fn sort(&mut self) {
+        // Indexes change, so marks are not valid anymore
+        self.clear_marks();
         // Choose sorting method
         match &self.file_sorting {
             FileSorting::ByName => self.sort_files_by_name(),
@@ -244,7 +522,7 @@ impl FileExplorer {
         }
         // Directories first (NOTE: MUST COME AFTER OTHER SORTING)
         // Group directories if necessary
-        if let Some(group_dirs) = &self.group_dirs {
+        if let Some(group_dirs) = self.effective_group_dirs() {
             match group_dirs {
                 GroupDirs::First => self.sort_files_directories_first(),
                 GroupDirs::Last => self.sort_files_directories_last(),
@@ -252,6 +530,31 @@ impl FileExplorer {
         }
     }
 
+    /// ### effective_group_dirs
+    ///
+    /// Get how directories are actually grouped: if directories first is locked, they always come first
+    fn effective_group_dirs(&self) -> Option<GroupDirs> {
+        match self.opts.intersects(ExplorerOpts::LOCK_DIRS_FIRST) {
+            true => Some(GroupDirs::First),
+            false => self.group_dirs,
+        }
+    }
+
+    /// ### regroup_locked_dirs
+    ///
+    /// If directories first is locked, move directories before files again after the entries have changed.
+    /// Marks are cleared only if entries have actually been moved
+    fn regroup_locked_dirs(&mut self) {
+        let grouped: bool = self
+            .files
+            .windows(2)
+            .all(|x| !(x[0].is_file() && x[1].is_dir()));
+        if self.opts.intersects(ExplorerOpts::LOCK_DIRS_FIRST) && !grouped {
+            self.clear_marks();
+            self.sort_files_directories_first();
+        }
+    }
+
     /// ### sort_files_by_name
     ///
     /// Sort explorer files by their name. All names are converted to lowercase
@@ -303,6 +606,7 @@ impl FileExplorer {
     /// Enable/disable hidden files
     pub fn toggle_hidden_files(&mut self) {
         self.opts.toggle(ExplorerOpts::SHOW_HIDDEN_FILES);
+        self.clear_marks();
     }
 
     /// ### hidden_files_visible
@@ -311,6 +615,28 @@ impl FileExplorer {
     pub fn hidden_files_visible(&self) -> bool {
         self.opts.intersects(ExplorerOpts::SHOW_HIDDEN_FILES)
     }
+
+    /// ### relative_paths
+    ///
+    /// Returns whether entries are displayed with their path relative to the working directory
+    pub fn relative_paths(&self) -> bool {
+        self.opts.intersects(ExplorerOpts::RELATIVE_PATHS)
+    }
+
+    /// ### is_hidden
+    ///
+    /// Returns whether `entry` is hidden; that is if it's a dotfile or if its name matches one of the hidden patterns.
+    /// The parent directory entry (`..`) is never hidden if `ALWAYS_SHOW_PARENT` is set
+    fn is_hidden(&self, entry: &FsEntry) -> bool {
+        if self.opts.intersects(ExplorerOpts::ALWAYS_SHOW_PARENT) && entry.get_name() == ".." {
+            return false;
+        }
+        entry.is_hidden()
+            || self
+                .hidden_patterns
+                .iter()
+                .any(|x| x.matches(entry.get_name()))
+    }
 }
 
 // Traits
@@ -444,6 +770,150 @@ mod tests {
         assert_eq!(explorer.iter_files().count(), 6); // All files are returned now
     }
 
+    #[test]
+    fn test_fs_explorer_files_hidden_patterns() {
+        let mut explorer: FileExplorer = FileExplorer {
+            hidden_patterns: vec![WildMatch::new("*.bak"), WildMatch::new("*~")],
+            ..Default::default()
+        };
+        explorer.set_files(vec![
+            make_fs_entry("README.md", false),
+            make_fs_entry("README.md.bak", false),
+            make_fs_entry("main.rs~", false),
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("src/", true),
+        ]);
+        // Backups and dotfiles are hidden
+        let names: Vec<&str> = explorer.iter_files().map(|x| x.get_name()).collect();
+        assert_eq!(names, vec!["README.md", "src/"]);
+        assert_eq!(explorer.get(1).unwrap().get_name(), "src/");
+        assert!(explorer.get(2).is_none());
+        // Show hidden files
+        explorer.toggle_hidden_files();
+        assert_eq!(explorer.iter_files().count(), 5);
+        assert_eq!(
+            explorer
+                .iter_files()
+                .filter(|x| x.get_name().ends_with(".bak"))
+                .count(),
+            1
+        );
+        // Without patterns, only dotfiles are hidden
+        explorer.toggle_hidden_files();
+        explorer.hidden_patterns.clear();
+        assert_eq!(explorer.iter_files().count(), 4);
+    }
+
+    #[test]
+    fn test_fs_explorer_always_show_parent() {
+        let mut explorer: FileExplorer = FileExplorer {
+            hidden_patterns: vec![WildMatch::new(".*")],
+            ..Default::default()
+        };
+        explorer.set_files(vec![
+            make_fs_entry("..", true),
+            make_fs_entry(".git/", true),
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("README.md", false),
+        ]);
+        // Parent is hidden as any other dotfile
+        let names: Vec<&str> = explorer.iter_files().map(|x| x.get_name()).collect();
+        assert_eq!(names, vec!["README.md"]);
+        // Always show parent
+        explorer.opts.insert(ExplorerOpts::ALWAYS_SHOW_PARENT);
+        let names: Vec<&str> = explorer.iter_files().map(|x| x.get_name()).collect();
+        assert_eq!(names, vec!["..", "README.md"]);
+        assert_eq!(explorer.get(0).unwrap().get_name(), "..");
+        assert_eq!(explorer.get(1).unwrap().get_name(), "README.md");
+        // Showing hidden files doesn't duplicate it
+        explorer.toggle_hidden_files();
+        assert_eq!(explorer.iter_files().count(), 4);
+    }
+
+    #[test]
+    fn test_fs_explorer_is_empty() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        assert_eq!(explorer.is_empty(), true);
+        // Only hidden files
+        explorer.set_files(vec![
+            make_fs_entry(".git/", true),
+            make_fs_entry(".gitignore", false),
+        ]);
+        assert_eq!(explorer.is_empty(), true);
+        explorer.toggle_hidden_files();
+        assert_eq!(explorer.is_empty(), false);
+        explorer.toggle_hidden_files();
+        // Add a visible file
+        explorer.set_files(vec![
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("README.md", false),
+        ]);
+        assert_eq!(explorer.is_empty(), false);
+        explorer.clear();
+        assert_eq!(explorer.is_empty(), true);
+    }
+
+    #[test]
+    fn test_fs_explorer_load_state() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        // Nothing listed yet
+        assert_eq!(explorer.load_state(), LoadState::Loading);
+        assert_eq!(explorer.is_empty(), true);
+        // Empty directory
+        explorer.set_files(vec![]);
+        assert_eq!(explorer.load_state(), LoadState::Loaded);
+        assert_eq!(explorer.is_empty(), true);
+        // Only hidden entries are considered empty too
+        explorer.set_files(vec![make_fs_entry(".gitignore", false)]);
+        assert_eq!(explorer.is_empty(), true);
+        // Entries listed
+        explorer.set_files(vec![make_fs_entry("README.md", false)]);
+        assert_eq!(explorer.load_state(), LoadState::Loaded);
+        assert_eq!(explorer.is_empty(), false);
+        // Listing again: entries are still displayed while loading
+        explorer.set_loading();
+        assert_eq!(explorer.load_state(), LoadState::Loading);
+        assert_eq!(explorer.is_empty(), false);
+        // Failure removes entries
+        explorer.set_load_failed(true);
+        assert_eq!(explorer.load_state(), LoadState::PermissionDenied);
+        assert_eq!(explorer.is_empty(), true);
+        explorer.set_load_failed(false);
+        assert_eq!(explorer.load_state(), LoadState::Failed);
+        assert_eq!(explorer.is_empty(), true);
+        // Recovered
+        explorer.reload_preserving_selection(vec![make_fs_entry("README.md", false)]);
+        assert_eq!(explorer.load_state(), LoadState::Loaded);
+        assert_eq!(explorer.is_empty(), false);
+    }
+
+    #[test]
+    fn test_fs_explorer_clear() {
+        let mut explorer: FileExplorer = FileExplorer {
+            hidden_patterns: vec![WildMatch::new("*.bak")],
+            ..Default::default()
+        };
+        explorer.wrkdir = PathBuf::from("/home/omar");
+        explorer.sort_by(FileSorting::BySize);
+        explorer.group_dirs_by(Some(GroupDirs::First));
+        explorer.toggle_hidden_files();
+        explorer.set_files(vec![
+            make_fs_entry("README.md", false),
+            make_fs_entry("src/", true),
+        ]);
+        explorer.mark_all();
+        assert_eq!(explorer.marks().len(), 2);
+        explorer.clear();
+        assert_eq!(explorer.iter_files_all().count(), 0);
+        assert!(explorer.marks().is_empty());
+        // Options are preserved
+        assert_eq!(explorer.wrkdir.as_path(), Path::new("/home/omar"));
+        assert_eq!(explorer.get_file_sorting(), FileSorting::BySize);
+        assert_eq!(explorer.group_dirs, Some(GroupDirs::First));
+        assert_eq!(explorer.hidden_files_visible(), true);
+        assert_eq!(explorer.hidden_patterns.len(), 1);
+    }
+
     #[test]
     fn test_fs_explorer_sort_by_name() {
         let mut explorer: FileExplorer = FileExplorer::default();
@@ -504,6 +974,49 @@ mod tests {
         assert_eq!(explorer.files.get(1).unwrap().get_name(), "README.md");
     }
 
+    #[test]
+    fn test_fs_explorer_cycle_sort() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        let mut entry: FsFile = make_fs_entry("README.md", false).unwrap_file();
+        entry.creation_time = entry.last_change_time - Duration::from_secs(60);
+        explorer.set_files(vec![
+            FsEntry::File(entry),
+            make_fs_entry("CODE_OF_CONDUCT.md", false),
+        ]);
+        assert_eq!(explorer.get_file_sorting(), FileSorting::ByName);
+        // Cycle order
+        assert_eq!(explorer.cycle_sort(false), FileSorting::BySize);
+        assert_eq!(explorer.get_file_sorting(), FileSorting::BySize);
+        assert_eq!(explorer.cycle_sort(false), FileSorting::ByModifyTime);
+        assert_eq!(explorer.cycle_sort(false), FileSorting::ByCreationTime);
+        assert_eq!(explorer.files.get(1).unwrap().get_name(), "README.md");
+        assert_eq!(explorer.cycle_sort(false), FileSorting::ByName);
+        // Reverse
+        assert_eq!(explorer.cycle_sort(true), FileSorting::ByCreationTime);
+        assert_eq!(explorer.cycle_sort(true), FileSorting::ByModifyTime);
+        assert_eq!(explorer.cycle_sort(true), FileSorting::BySize);
+        assert_eq!(explorer.cycle_sort(true), FileSorting::ByName);
+    }
+
+    #[test]
+    fn test_fs_explorer_cycle_sort_skips_creation_time() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        // Creation time is the same as modify time
+        explorer.set_files(vec![
+            make_fs_entry("README.md", false),
+            make_fs_entry("src/", true),
+        ]);
+        explorer.sort_by(FileSorting::ByModifyTime);
+        assert_eq!(explorer.cycle_sort(false), FileSorting::ByName);
+        assert_eq!(explorer.cycle_sort(true), FileSorting::ByModifyTime);
+        // Sorting by creation time when it's not applicable
+        explorer.sort_by(FileSorting::ByCreationTime);
+        assert_eq!(explorer.cycle_sort(false), FileSorting::BySize);
+        // Empty directory
+        explorer.set_files(vec![]);
+        assert_eq!(explorer.cycle_sort(true), FileSorting::ByName);
+    }
+
     #[test]
     fn test_fs_explorer_sort_by_size() {
         let mut explorer: FileExplorer = FileExplorer::default();
@@ -547,6 +1060,84 @@ mod tests {
         assert_eq!(explorer.files.get(9).unwrap().get_name(), "README.md");
     }
 
+    #[test]
+    fn test_fs_explorer_lock_dirs_first() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.opts.insert(ExplorerOpts::LOCK_DIRS_FIRST);
+        // Grouping dirs last is ignored
+        explorer.group_dirs_by(Some(GroupDirs::Last));
+        explorer.set_files(vec![
+            make_fs_entry_with_size("README.md", false, 1024),
+            make_fs_entry_with_size("src/", true, 4096),
+            make_fs_entry_with_size("Cargo.lock", false, 8192),
+        ]);
+        assert_eq!(explorer.files.first().unwrap().get_name(), "src/");
+        // Changing sorting never moves directories below files
+        explorer.sort_by(FileSorting::BySize);
+        assert_eq!(explorer.files.first().unwrap().get_name(), "src/");
+        assert_eq!(explorer.files.get(1).unwrap().get_name(), "Cargo.lock");
+        assert_eq!(explorer.files.get(2).unwrap().get_name(), "README.md");
+        explorer.group_dirs_by(None);
+        assert_eq!(explorer.files.first().unwrap().get_name(), "src/");
+    }
+
+    #[test]
+    fn test_fs_explorer_append_files() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry_with_size("README.md", false, 1024),
+            make_fs_entry_with_size("Cargo.lock", false, 8192),
+        ]);
+        explorer.sort_by(FileSorting::BySize);
+        explorer.toggle_mark(1);
+        // Without lock, entries are appended at the end and marks are kept
+        explorer.append_files(vec![
+            make_fs_entry_with_size("docs/", true, 4096),
+            make_fs_entry_with_size("LICENSE", false, 16384),
+        ]);
+        assert_eq!(explorer.files.first().unwrap().get_name(), "Cargo.lock");
+        assert_eq!(explorer.files.get(2).unwrap().get_name(), "docs/");
+        assert_eq!(explorer.files.get(3).unwrap().get_name(), "LICENSE");
+        assert_eq!(explorer.marks(), vec![1]);
+    }
+
+    #[test]
+    fn test_fs_explorer_append_files_lock_dirs_first() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.opts.insert(ExplorerOpts::LOCK_DIRS_FIRST);
+        explorer.set_files(vec![
+            make_fs_entry_with_size("README.md", false, 1024),
+            make_fs_entry_with_size("src/", true, 4096),
+            make_fs_entry_with_size("Cargo.lock", false, 8192),
+        ]);
+        explorer.sort_by(FileSorting::BySize);
+        explorer.toggle_mark(1);
+        // Appending files only keeps directories first, without clearing marks
+        explorer.append_files(vec![make_fs_entry_with_size("LICENSE", false, 16384)]);
+        assert_eq!(explorer.marks(), vec![1]);
+        // Appended directories are moved before files
+        explorer.append_files(vec![
+            make_fs_entry_with_size("docs/", true, 4096),
+            make_fs_entry_with_size("CHANGELOG.md", false, 2048),
+        ]);
+        assert!(explorer.marks().is_empty());
+        let names: Vec<&str> = explorer.files.iter().map(|x| x.get_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "src/",
+                "docs/",
+                "Cargo.lock",
+                "README.md",
+                "LICENSE",
+                "CHANGELOG.md"
+            ]
+        );
+        // Deleting entries keeps grouping
+        explorer.del_entry(0);
+        assert_eq!(explorer.files.first().unwrap().get_name(), "docs/");
+    }
+
     #[test]
     fn test_fs_explorer_sort_by_name_and_dirs_last() {
         let mut explorer: FileExplorer = FileExplorer::default();
@@ -660,6 +1251,257 @@ mod tests {
         assert_eq!(explorer.files.len(), 3);
     }
 
+    #[test]
+    fn test_fs_explorer_del_entry_hidden_files() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry(".git/", true),
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("README.md", false),
+            make_fs_entry("src/", true),
+        ]);
+        assert_eq!(explorer.hidden_files_visible(), false);
+        // Relative index 1 is README.md, although hidden files come first
+        explorer.del_entry(1);
+        assert_eq!(explorer.files.len(), 4);
+        assert!(explorer.files.iter().all(|x| x.get_name() != "README.md"));
+        assert_eq!(explorer.get(1).unwrap().get_name(), "src/");
+        // Hidden files are never removed by relative index
+        explorer.del_entry(0);
+        explorer.del_entry(0);
+        explorer.del_entry(0);
+        assert_eq!(explorer.files.len(), 2);
+        assert!(explorer.is_empty());
+        // Show hidden files
+        explorer.toggle_hidden_files();
+        explorer.del_entry(1);
+        assert_eq!(explorer.files.len(), 1);
+        assert_eq!(explorer.get(0).unwrap().get_name(), ".git/");
+    }
+
+    #[test]
+    fn test_fs_explorer_get_by_path() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("README.md", false),
+        ]);
+        // Relative index doesn't consider hidden files
+        let (idx, entry): (usize, &FsEntry) = explorer.get_by_path(Path::new("README.md")).unwrap();
+        assert_eq!(idx, 1);
+        assert_eq!(entry.get_name(), "README.md");
+        // Not present
+        assert!(explorer.get_by_path(Path::new("LICENSE")).is_none());
+        // Filtered out
+        assert!(explorer.get_by_path(Path::new(".gitignore")).is_none());
+        explorer.toggle_hidden_files();
+        let (idx, entry): (usize, &FsEntry) =
+            explorer.get_by_path(Path::new(".gitignore")).unwrap();
+        assert_eq!(idx, 0);
+        assert_eq!(entry.get_name(), ".gitignore");
+        assert_eq!(explorer.get_by_path(Path::new("README.md")).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_fs_explorer_del_entry_by_name() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry(".gitignore", false),
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("README.md", false),
+        ]);
+        explorer.toggle_mark(0);
+        // Hidden entries are removed too
+        explorer.del_entry_by_name(".gitignore");
+        assert_eq!(explorer.files.len(), 2);
+        assert!(explorer.marks().is_empty());
+        explorer.del_entry_by_name("README.md");
+        assert_eq!(explorer.files.len(), 1);
+        assert_eq!(explorer.get(0).unwrap().get_name(), "CONTRIBUTING.md");
+        // Unexisting entry
+        explorer.del_entry_by_name("LICENSE");
+        assert_eq!(explorer.files.len(), 1);
+    }
+
+    #[test]
+    fn test_fs_explorer_page_index() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        // Empty
+        assert_eq!(explorer.first_index(), 0);
+        assert_eq!(explorer.last_index(), 0);
+        assert_eq!(explorer.page_index(0, 8, PageDirection::Down), 0);
+        assert_eq!(explorer.page_index(0, 8, PageDirection::Up), 0);
+        // 20 files, 2 of them hidden
+        let mut files: Vec<FsEntry> = (0..18)
+            .map(|x| make_fs_entry(format!("file{:02}.txt", x).as_str(), false))
+            .collect();
+        files.push(make_fs_entry(".git/", true));
+        files.push(make_fs_entry(".gitignore", false));
+        explorer.set_files(files);
+        // Hidden files are not listed
+        explorer.opts.remove(ExplorerOpts::SHOW_HIDDEN_FILES);
+        assert_eq!(explorer.first_index(), 0);
+        assert_eq!(explorer.last_index(), 17);
+        // Page down
+        assert_eq!(explorer.page_index(0, 8, PageDirection::Down), 8);
+        assert_eq!(explorer.page_index(8, 8, PageDirection::Down), 16);
+        assert_eq!(explorer.page_index(16, 8, PageDirection::Down), 17);
+        assert_eq!(explorer.page_index(17, 8, PageDirection::Down), 17);
+        // Page up
+        assert_eq!(explorer.page_index(17, 8, PageDirection::Up), 9);
+        assert_eq!(explorer.page_index(9, 8, PageDirection::Up), 1);
+        assert_eq!(explorer.page_index(1, 8, PageDirection::Up), 0);
+        assert_eq!(explorer.page_index(0, 8, PageDirection::Up), 0);
+        // Page size larger than list
+        assert_eq!(explorer.page_index(3, 64, PageDirection::Down), 17);
+        assert_eq!(explorer.page_index(3, 64, PageDirection::Up), 0);
+        // Current out of range is clamped
+        assert_eq!(explorer.page_index(30, 8, PageDirection::Down), 17);
+        assert_eq!(explorer.page_index(30, 8, PageDirection::Up), 9);
+        assert_eq!(explorer.page_index(usize::MAX, 8, PageDirection::Down), 17);
+        // Hidden files are listed
+        explorer.opts.insert(ExplorerOpts::SHOW_HIDDEN_FILES);
+        assert_eq!(explorer.last_index(), 19);
+        assert_eq!(explorer.page_index(16, 8, PageDirection::Down), 19);
+    }
+
+    #[test]
+    fn test_fs_explorer_marks() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        // Create files (files are then sorted by name)
+        explorer.set_files(vec![
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("docs/", true),
+            make_fs_entry(".git/", true),
+            make_fs_entry("README.md", false),
+        ]);
+        assert!(explorer.marks().is_empty());
+        // Toggle
+        explorer.toggle_mark(1);
+        explorer.toggle_mark(2);
+        explorer.toggle_mark(3); // Not listed: .git is hidden
+        assert_eq!(explorer.marks(), vec![1, 2]);
+        explorer.toggle_mark(1);
+        assert_eq!(explorer.marks(), vec![2]);
+        assert_eq!(
+            explorer
+                .marked_entries()
+                .iter()
+                .map(|x| x.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["README.md"]
+        );
+        // Invert
+        explorer.invert_marks();
+        assert_eq!(explorer.marks(), vec![0, 1]);
+        assert_eq!(
+            explorer
+                .marked_entries()
+                .iter()
+                .map(|x| x.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["CONTRIBUTING.md", "docs/"]
+        );
+        // Mark all
+        explorer.mark_all();
+        assert_eq!(explorer.marks(), vec![0, 1, 2]);
+        assert_eq!(explorer.marked_entries().len(), 3);
+        // Clear
+        explorer.clear_marks();
+        assert!(explorer.marks().is_empty());
+        assert!(explorer.marked_entries().is_empty());
+        // Showing hidden files changes indexes
+        explorer.mark_all();
+        explorer.toggle_hidden_files();
+        assert!(explorer.marks().is_empty());
+    }
+
+    #[test]
+    fn test_fs_explorer_reload_preserving_selection() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("docs/", true),
+            make_fs_entry("LICENSE", false),
+            make_fs_entry("README.md", false),
+        ]);
+        // Mark CONTRIBUTING.md, LICENSE and README.md
+        explorer.toggle_mark(0);
+        explorer.toggle_mark(2);
+        explorer.toggle_mark(3);
+        // Reload: LICENSE has been deleted and a new file has been created
+        explorer.reload_preserving_selection(vec![
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("docs/", true),
+            make_fs_entry("Cargo.toml", false),
+            make_fs_entry("README.md", false),
+        ]);
+        assert_eq!(
+            explorer
+                .marked_entries()
+                .iter()
+                .map(|x| x.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["CONTRIBUTING.md", "README.md"]
+        );
+        assert_eq!(explorer.marks(), vec![1, 3]);
+        // Nothing marked
+        explorer.clear_marks();
+        explorer.reload_preserving_selection(vec![make_fs_entry("README.md", false)]);
+        assert!(explorer.marks().is_empty());
+    }
+
+    #[test]
+    fn test_fs_explorer_set_files_resets_marks() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        explorer.set_files(vec![
+            make_fs_entry("CONTRIBUTING.md", false),
+            make_fs_entry("README.md", false),
+        ]);
+        explorer.mark_all();
+        assert_eq!(explorer.marks(), vec![0, 1]);
+        explorer.set_files(vec![make_fs_entry("README.md", false)]);
+        assert!(explorer.marks().is_empty());
+        // Sorting resets marks too
+        explorer.mark_all();
+        explorer.sort_by(FileSorting::BySize);
+        assert!(explorer.marks().is_empty());
+    }
+
+    #[test]
+    fn test_fs_explorer_relative_paths() {
+        let mut explorer: FileExplorer = FileExplorer {
+            wrkdir: PathBuf::from("/home/omar"),
+            fmt: Formatter::new("{NAME:32}"),
+            ..Default::default()
+        };
+        let file: FsEntry = make_fs_entry("/home/omar/src/main.rs", false);
+        let dir: FsEntry = make_fs_entry("/home/omar/src", true);
+        let outside: FsEntry = make_fs_entry("/tmp/main.rs", false);
+        // Names by default
+        assert_eq!(explorer.relative_paths(), false);
+        assert_eq!(
+            explorer.fmt_file(&file).trim_end(),
+            "/home/omar/src/main.rs"
+        );
+        // Relative paths
+        explorer.opts.insert(ExplorerOpts::RELATIVE_PATHS);
+        assert_eq!(explorer.relative_paths(), true);
+        assert_eq!(explorer.fmt_file(&file).trim_end(), "src/main.rs");
+        assert_eq!(explorer.fmt_file(&dir).trim_end(), "src/");
+        // Outside working directory: full path
+        assert_eq!(explorer.fmt_file(&outside).trim_end(), "/tmp/main.rs");
+        // Names again
+        explorer.opts.remove(ExplorerOpts::RELATIVE_PATHS);
+        assert_eq!(explorer.relative_paths(), false);
+        assert_eq!(
+            explorer.fmt_file(&file).trim_end(),
+            "/home/omar/src/main.rs"
+        );
+    }
+
     fn make_fs_entry(name: &str, is_dir: bool) -> FsEntry {
         let t_now: SystemTime = SystemTime::now();
         match is_dir {
@@ -0,0 +1,194 @@
+//! ## Resolver
+//!
+//! `resolver` is the module which resolves user and group ids to their names, caching the results
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// Ext
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+#[cfg(target_family = "unix")]
+use users::{get_group_by_gid, get_user_by_uid};
+
+/// Amount of ids kept in each cache
+const CACHE_CAPACITY: usize = 256;
+
+/// ## IdCache
+///
+/// Least-recently-used cache which associates an id to its name
+struct IdCache {
+    names: HashMap<u32, Option<String>>,
+    order: VecDeque<u32>, // Least recently used at the front
+    capacity: usize,
+}
+
+impl IdCache {
+    /// ### new
+    ///
+    /// Instantiates a new `IdCache` which can hold up to `capacity` ids
+    fn new(capacity: usize) -> Self {
+        IdCache {
+            names: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// ### get_or_resolve
+    ///
+    /// Get name for `id` from cache; if not cached, resolve it with `resolve` and cache it.
+    /// Unresolvable ids are cached too, so they're not looked up again
+    fn get_or_resolve<F>(&mut self, id: u32, resolve: F) -> Option<String>
+    where
+        F: FnOnce(u32) -> Option<String>,
+    {
+        if let Some(name) = self.names.get(&id).cloned() {
+            // Move id to the back
+            self.order.retain(|x| *x != id);
+            self.order.push_back(id);
+            return name;
+        }
+        let name: Option<String> = resolve(id);
+        // Evict least recently used id
+        if self.names.len() >= self.capacity {
+            if let Some(lru) = self.order.pop_front() {
+                self.names.remove(&lru);
+            }
+        }
+        self.names.insert(id, name.clone());
+        self.order.push_back(id);
+        name
+    }
+}
+
+/// ## IdResolver
+///
+/// Resolves uid to username and gid to group name using the system databases.
+/// If an id can't be resolved, the number is returned instead
+pub struct IdResolver {
+    users: RefCell<IdCache>,
+    groups: RefCell<IdCache>,
+}
+
+impl Default for IdResolver {
+    fn default() -> Self {
+        IdResolver {
+            users: RefCell::new(IdCache::new(CACHE_CAPACITY)),
+            groups: RefCell::new(IdCache::new(CACHE_CAPACITY)),
+        }
+    }
+}
+
+impl IdResolver {
+    /// ### user
+    ///
+    /// Get username for `uid`; returns the uid as string if the user doesn't exist
+    pub fn user(&self, uid: u32) -> String {
+        self.users
+            .borrow_mut()
+            .get_or_resolve(uid, Self::lookup_user)
+            .unwrap_or_else(|| uid.to_string())
+    }
+
+    /// ### group
+    ///
+    /// Get group name for `gid`; returns the gid as string if the group doesn't exist
+    pub fn group(&self, gid: u32) -> String {
+        self.groups
+            .borrow_mut()
+            .get_or_resolve(gid, Self::lookup_group)
+            .unwrap_or_else(|| gid.to_string())
+    }
+
+    #[cfg(target_family = "unix")]
+    fn lookup_user(uid: u32) -> Option<String> {
+        get_user_by_uid(uid).map(|x| x.name().to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn lookup_user(_uid: u32) -> Option<String> {
+        None
+    }
+
+    #[cfg(target_family = "unix")]
+    fn lookup_group(gid: u32) -> Option<String> {
+        get_group_by_gid(gid).map(|x| x.name().to_string_lossy().to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn lookup_group(_gid: u32) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    /// An id which is very unlikely to exist
+    const UNKNOWN_ID: u32 = 4294967000;
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_fs_explorer_resolver_known_ids() {
+        let resolver: IdResolver = IdResolver::default();
+        assert_eq!(resolver.user(0).as_str(), "root");
+        // Resolved twice; second time from cache
+        assert_eq!(resolver.user(0).as_str(), "root");
+        assert_eq!(resolver.users.borrow().names.len(), 1);
+        assert!(!resolver.group(0).is_empty());
+        assert_eq!(resolver.groups.borrow().names.len(), 1);
+    }
+
+    #[test]
+    fn test_fs_explorer_resolver_unknown_ids() {
+        let resolver: IdResolver = IdResolver::default();
+        assert_eq!(resolver.user(UNKNOWN_ID), UNKNOWN_ID.to_string());
+        assert_eq!(resolver.group(UNKNOWN_ID), UNKNOWN_ID.to_string());
+        // Unresolved ids are cached too
+        assert_eq!(resolver.users.borrow().names.get(&UNKNOWN_ID), Some(&None));
+    }
+
+    #[test]
+    fn test_fs_explorer_resolver_cache_eviction() {
+        let mut cache: IdCache = IdCache::new(2);
+        let resolve = |id: u32| Some(format!("user{}", id));
+        assert_eq!(cache.get_or_resolve(1, resolve).unwrap().as_str(), "user1");
+        assert_eq!(cache.get_or_resolve(2, resolve).unwrap().as_str(), "user2");
+        // Use 1, so that 2 becomes the least recently used
+        assert_eq!(
+            cache.get_or_resolve(1, |_| panic!("should be cached")),
+            Some(String::from("user1"))
+        );
+        assert_eq!(cache.get_or_resolve(3, resolve).unwrap().as_str(), "user3");
+        assert_eq!(cache.names.len(), 2);
+        assert!(cache.names.contains_key(&1));
+        assert!(!cache.names.contains_key(&2));
+        assert!(cache.names.contains_key(&3));
+        assert_eq!(cache.order, VecDeque::from(vec![1, 3]));
+    }
+}
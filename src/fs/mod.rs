@@ -59,6 +59,52 @@ pub struct FsDirectory {
     pub unix_pex: Option<(u8, u8, u8)>, // UNIX only
 }
 
+/// ### DiskUsage
+///
+/// DiskUsage describes the space of the file system which contains a path (in bytes)
+#[derive(Clone, Copy, std::fmt::Debug, Eq, PartialEq)]
+pub struct DiskUsage {
+    pub total: u64,
+    pub used: u64,
+    pub free: u64, // Available to the user
+}
+
+/// ### DirListing
+///
+/// DirListing is the result of a directory listing which may have failed only partially:
+/// it holds the entries which have been read and, for each path which couldn't be read, the reason of the failure
+#[derive(Debug, Default)]
+pub struct DirListing {
+    pub entries: Vec<FsEntry>,
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl DirListing {
+    /// ### from_results
+    ///
+    /// Assemble a listing from the result of reading each path of the directory
+    pub fn from_results<I>(results: I) -> Self
+    where
+        I: IntoIterator<Item = (PathBuf, Result<FsEntry, String>)>,
+    {
+        let mut listing: DirListing = DirListing::default();
+        for (path, result) in results {
+            match result {
+                Ok(entry) => listing.entries.push(entry),
+                Err(err) => listing.errors.push((path, err)),
+            }
+        }
+        listing
+    }
+
+    /// ### is_partial
+    ///
+    /// Returns whether some paths of the directory couldn't be read
+    pub fn is_partial(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
 /// ### FsFile
 ///
 /// FsFile provides an interface to file system files
@@ -498,4 +544,66 @@ mod tests {
             PathBuf::from("/home/cvisintin/projects")
         );
     }
+
+    #[test]
+    fn test_fs_dir_listing() {
+        let t_now: SystemTime = SystemTime::now();
+        let make_file = |name: &str| {
+            FsEntry::File(FsFile {
+                name: name.to_string(),
+                abs_path: PathBuf::from(format!("/home/{}", name)),
+                last_change_time: t_now,
+                last_access_time: t_now,
+                creation_time: t_now,
+                size: 8,
+                ftype: None,
+                readonly: false,
+                symlink: None,
+                user: None,
+                group: None,
+                unix_pex: None,
+            })
+        };
+        // Partial result
+        let listing: DirListing = DirListing::from_results(vec![
+            (PathBuf::from("/home/a.txt"), Ok(make_file("a.txt"))),
+            (
+                PathBuf::from("/home/secret"),
+                Err(String::from("Permission denied")),
+            ),
+            (PathBuf::from("/home/b.txt"), Ok(make_file("b.txt"))),
+            (
+                PathBuf::from("/home/gone"),
+                Err(String::from("No such file")),
+            ),
+        ]);
+        assert_eq!(listing.is_partial(), true);
+        assert_eq!(
+            listing
+                .entries
+                .iter()
+                .map(|x| x.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["a.txt", "b.txt"]
+        );
+        assert_eq!(
+            listing.errors,
+            vec![
+                (
+                    PathBuf::from("/home/secret"),
+                    String::from("Permission denied")
+                ),
+                (PathBuf::from("/home/gone"), String::from("No such file")),
+            ]
+        );
+        // Complete result
+        let listing: DirListing =
+            DirListing::from_results(vec![(PathBuf::from("/home/a.txt"), Ok(make_file("a.txt")))]);
+        assert_eq!(listing.is_partial(), false);
+        assert_eq!(listing.entries.len(), 1);
+        // Empty directory
+        let listing: DirListing = DirListing::from_results(Vec::new());
+        assert_eq!(listing.is_partial(), false);
+        assert!(listing.entries.is_empty());
+    }
 }
@@ -30,7 +30,6 @@ use std::fs::{self, File, Metadata, OpenOptions};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use thiserror::Error;
-use wildmatch::WildMatch;
 // Metadata ext
 #[cfg(target_family = "unix")]
 use std::fs::set_permissions;
@@ -38,7 +37,7 @@ use std::fs::set_permissions;
 use std::os::unix::fs::{MetadataExt, PermissionsExt};
 
 // Locals
-use crate::fs::{FsDirectory, FsEntry, FsFile};
+use crate::fs::{DirListing, DiskUsage, FsDirectory, FsEntry, FsFile};
 
 /// ## HostErrorType
 ///
@@ -84,6 +83,30 @@ impl HostError {
             path: Some(p.to_path_buf()),
         }
     }
+
+    /// ### is_permission_denied
+    ///
+    /// Returns whether the error has been caused by missing permissions
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(&self.ioerr, Some(err) if err.kind() == std::io::ErrorKind::PermissionDenied)
+    }
+
+    /// ### is_cross_device
+    ///
+    /// Returns whether the error has been caused by an operation across different file systems
+    #[cfg(target_family = "unix")]
+    pub fn is_cross_device(&self) -> bool {
+        matches!(&self.ioerr, Some(err) if err.raw_os_error() == Some(libc::EXDEV))
+    }
+
+    /// ### is_cross_device
+    ///
+    /// Returns whether the error has been caused by an operation across different file systems
+    /// (`ERROR_NOT_SAME_DEVICE`)
+    #[cfg(target_os = "windows")]
+    pub fn is_cross_device(&self) -> bool {
+        matches!(&self.ioerr, Some(err) if err.raw_os_error() == Some(17))
+    }
 }
 
 impl From<HostErrorType> for HostError {
@@ -361,6 +384,38 @@ impl Localhost {
     ///
     /// Copy file to destination path
     pub fn copy(&mut self, entry: &FsEntry, dst: &Path) -> Result<(), HostError> {
+        // Get absolute path of dest
+        let dst: PathBuf = self.to_abs_path(dst);
+        self.copy_entry(entry, dst.as_path())?;
+        // Reload directory if dst is pwd
+        match dst.is_dir() {
+            true => {
+                if dst == self.pwd().as_path() {
+                    self.files = self.scan_dir(self.wrkdir.as_path())?;
+                } else if let Some(parent) = dst.parent() {
+                    // If parent is pwd, scan directory
+                    if parent == self.pwd().as_path() {
+                        self.files = self.scan_dir(self.wrkdir.as_path())?;
+                    }
+                }
+            }
+            false => {
+                if let Some(parent) = dst.parent() {
+                    // If parent is pwd, scan directory
+                    if parent == self.pwd().as_path() {
+                        self.files = self.scan_dir(self.wrkdir.as_path())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// ### copy_entry
+    ///
+    /// Copy file to destination path without reloading the working directory.
+    /// Since it doesn't require a mutable reference, copies can be run from many threads at once
+    pub fn copy_entry(&self, entry: &FsEntry, dst: &Path) -> Result<(), HostError> {
         // Get absolute path of dest
         let dst: PathBuf = self.to_abs_path(dst);
         info!(
@@ -396,7 +451,14 @@ impl Localhost {
                 // If destination path doesn't exist, create destination
                 if !dst.exists() {
                     debug!("Directory {} doesn't exist; creating it", dst.display());
-                    self.mkdir(dst.as_path())?;
+                    if let Err(err) = std::fs::create_dir(dst.as_path()) {
+                        error!("Could not make directory: {}", err);
+                        return Err(HostError::new(
+                            HostErrorType::CouldNotCreateFile,
+                            Some(err),
+                            dst.as_path(),
+                        ));
+                    }
                 }
                 // Scan dir
                 let dir_files: Vec<FsEntry> = self.scan_dir(dir.abs_path.as_path())?;
@@ -406,32 +468,47 @@ impl Localhost {
                     let mut sub_dst: PathBuf = dst.clone();
                     sub_dst.push(dir_entry.get_name());
                     // Call function recursively
-                    self.copy(dir_entry, sub_dst.as_path())?;
+                    self.copy_entry(dir_entry, sub_dst.as_path())?;
                 }
             }
         }
-        // Reload directory if dst is pwd
-        match dst.is_dir() {
-            true => {
-                if dst == self.pwd().as_path() {
-                    self.files = self.scan_dir(self.wrkdir.as_path())?;
-                } else if let Some(parent) = dst.parent() {
-                    // If parent is pwd, scan directory
-                    if parent == self.pwd().as_path() {
-                        self.files = self.scan_dir(self.wrkdir.as_path())?;
-                    }
-                }
+        Ok(())
+    }
+
+    /// ### symlink
+    ///
+    /// Create a symlink at `src` pointing at `dst`
+    pub fn symlink(&mut self, src: &Path, dst: &Path) -> Result<(), HostError> {
+        let src: PathBuf = self.to_abs_path(src);
+        info!(
+            "Creating symlink {} pointing to {}",
+            src.display(),
+            dst.display()
+        );
+        #[cfg(target_family = "unix")]
+        let result = std::os::unix::fs::symlink(dst, src.as_path());
+        #[cfg(target_os = "windows")]
+        let result = std::os::windows::fs::symlink_file(dst, src.as_path());
+        match result {
+            Ok(_) => {
+                // Scan dir
+                self.files = self.scan_dir(self.wrkdir.as_path())?;
+                Ok(())
             }
-            false => {
-                if let Some(parent) = dst.parent() {
-                    // If parent is pwd, scan directory
-                    if parent == self.pwd().as_path() {
-                        self.files = self.scan_dir(self.wrkdir.as_path())?;
-                    }
-                }
+            Err(err) => {
+                error!(
+                    "Failed to create symlink {} pointing to {}: {}",
+                    src.display(),
+                    dst.display(),
+                    err
+                );
+                Err(HostError::new(
+                    HostErrorType::CouldNotCreateFile,
+                    Some(err),
+                    src.as_path(),
+                ))
             }
         }
-        Ok(())
     }
 
     /// ### stat
@@ -641,6 +718,32 @@ impl Localhost {
         }
     }
 
+    /// ### set_mtime
+    ///
+    /// Set last modification time for file
+    pub fn set_mtime(&self, path: &Path, mtime: SystemTime) -> Result<(), HostError> {
+        let path: PathBuf = self.to_abs_path(path);
+        match OpenOptions::new()
+            .write(true)
+            .open(path.as_path())
+            .and_then(|f| f.set_modified(mtime))
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!(
+                    "Could not set modification time for {}: {}",
+                    path.display(),
+                    err
+                );
+                Err(HostError::new(
+                    HostErrorType::FileNotAccessible,
+                    Some(err),
+                    path.as_path(),
+                ))
+            }
+        }
+    }
+
     /// ### open_file_read
     ///
     /// Open file for read
@@ -704,6 +807,62 @@ impl Localhost {
         }
     }
 
+    /// ### disk_usage
+    ///
+    /// Get the usage of the file system which contains `path`.
+    /// Returns `None` if it's not available
+    #[cfg(target_family = "unix")]
+    pub fn disk_usage(&self, path: &Path) -> Option<DiskUsage> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+        let path: PathBuf = self.to_abs_path(path);
+        let c_path: CString = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            error!(
+                "Could not get disk usage for {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+        let block_size: u64 = stat.f_frsize as u64;
+        let total: u64 = stat.f_blocks as u64 * block_size;
+        Some(DiskUsage {
+            total,
+            used: total - (stat.f_bfree as u64 * block_size),
+            free: stat.f_bavail as u64 * block_size,
+        })
+    }
+
+    /// ### disk_usage
+    ///
+    /// Get the usage of the file system which contains `path`.
+    /// Not available on this platform
+    #[cfg(target_os = "windows")]
+    pub fn disk_usage(&self, _path: &Path) -> Option<DiskUsage> {
+        None
+    }
+
+    /// ### open_file_append
+    ///
+    /// Open file for write, without truncating it; data is written at the end of the file
+    pub fn open_file_append(&self, file: &Path) -> Result<File, HostError> {
+        let file: PathBuf = self.to_abs_path(file);
+        info!("Opening file {} for append", file.display());
+        match OpenOptions::new().append(true).open(file.as_path()) {
+            Ok(f) => Ok(f),
+            Err(err) => {
+                error!("Failed to open file: {}", err);
+                Err(HostError::new(
+                    HostErrorType::FileNotAccessible,
+                    Some(err),
+                    file.as_path(),
+                ))
+            }
+        }
+    }
+
     /// ### file_exists
     ///
     /// Returns whether provided file path exists
@@ -713,20 +872,33 @@ impl Localhost {
 
     /// ### scan_dir
     ///
-    /// Get content of the current directory as a list of fs entry
+    /// Get content of the current directory as a list of fs entry.
+    /// Entries which can't be read are skipped
     pub fn scan_dir(&self, dir: &Path) -> Result<Vec<FsEntry>, HostError> {
+        self.scan_dir_listing(dir).map(|x| x.entries)
+    }
+
+    /// ### scan_dir_listing
+    ///
+    /// Get content of the directory. Only failing to read the directory is an error;
+    /// entries which can't be read are reported in the listing errors
+    pub fn scan_dir_listing(&self, dir: &Path) -> Result<DirListing, HostError> {
         info!("Reading directory {}", dir.display());
         match std::fs::read_dir(dir) {
             Ok(e) => {
-                let mut fs_entries: Vec<FsEntry> = Vec::new();
-                for entry in e.flatten() {
-                    // NOTE: 0.4.1, don't fail if stat for one file fails
-                    match self.stat(entry.path().as_path()) {
-                        Ok(entry) => fs_entries.push(entry),
-                        Err(e) => error!("Failed to stat {}: {}", entry.path().display(), e),
+                // NOTE: 0.4.1, don't fail if stat for one file fails
+                let listing: DirListing = DirListing::from_results(e.map(|entry| match entry {
+                    Ok(entry) => {
+                        let path: PathBuf = entry.path();
+                        let result = self.stat(path.as_path()).map_err(|x| x.to_string());
+                        (path, result)
                     }
+                    Err(err) => (dir.to_path_buf(), Err(err.to_string())),
+                }));
+                for (path, err) in listing.errors.iter() {
+                    error!("Failed to stat {}: {}", path.display(), err);
                 }
-                Ok(fs_entries)
+                Ok(listing)
             }
             Err(err) => Err(HostError::new(
                 HostErrorType::DirNotAccessible,
@@ -736,55 +908,8 @@ impl Localhost {
         }
     }
 
-    /// ### find
-    ///
-    /// Find files matching `search` on localhost starting from current directory. Search supports recursive search of course.
-    /// The `search` argument supports wilcards ('*', '?')
-    pub fn find(&self, search: &str) -> Result<Vec<FsEntry>, HostError> {
-        self.iter_search(self.wrkdir.as_path(), &WildMatch::new(search))
-    }
-
     // -- privates
 
-    /// ### iter_search
-    ///
-    /// Recursive call for `find` method.
-    /// Search in current directory for files which match `filter`.
-    /// If a directory is found in current directory, `iter_search` will be called using that dir as argument.
-    fn iter_search(&self, dir: &Path, filter: &WildMatch) -> Result<Vec<FsEntry>, HostError> {
-        // Scan directory
-        let mut drained: Vec<FsEntry> = Vec::new();
-        match self.scan_dir(dir) {
-            Err(err) => Err(err),
-            Ok(entries) => {
-                // Iter entries
-                /* For each entry:
-                - if is dir: call iter_search with `dir`
-                    - push `iter_search` result to `drained`
-                - if is file: check if it matches `filter`
-                    - if it matches `filter`: push to to filter
-                */
-                for entry in entries.iter() {
-                    match entry {
-                        FsEntry::Directory(dir) => {
-                            // If directory matches; push directory to drained
-                            if filter.matches(dir.name.as_str()) {
-                                drained.push(FsEntry::Directory(dir.clone()));
-                            }
-                            drained.append(&mut self.iter_search(dir.abs_path.as_path(), filter)?);
-                        }
-                        FsEntry::File(file) => {
-                            if filter.matches(file.name.as_str()) {
-                                drained.push(FsEntry::File(file.clone()));
-                            }
-                        }
-                    }
-                }
-                Ok(drained)
-            }
-        }
-    }
-
     /// ### u32_to_mode
     ///
     /// Return string with format xxxxxx to tuple of permissions (user, group, others)
@@ -824,9 +949,9 @@ impl Localhost {
 mod tests {
 
     use super::*;
+    use crate::utils::test_helpers::make_file_at;
     #[cfg(target_family = "unix")]
     use crate::utils::test_helpers::{create_sample_file, make_fsentry};
-    use crate::utils::test_helpers::{make_dir_at, make_file_at};
 
     use pretty_assertions::assert_eq;
     #[cfg(target_family = "unix")]
@@ -836,6 +961,8 @@ mod tests {
 
     #[cfg(target_family = "unix")]
     use std::os::unix::fs::{symlink, PermissionsExt};
+    #[cfg(target_family = "unix")]
+    use std::time::Duration;
 
     #[test]
     fn test_host_error_new() {
@@ -843,6 +970,23 @@ mod tests {
             HostError::new(HostErrorType::CouldNotCreateFile, None, Path::new("/tmp"));
         assert!(error.ioerr.is_none());
         assert_eq!(error.path.as_ref().unwrap(), Path::new("/tmp"));
+        assert_eq!(error.is_permission_denied(), false);
+        let error: HostError = HostError::new(
+            HostErrorType::DirNotAccessible,
+            Some(std::io::Error::from(std::io::ErrorKind::PermissionDenied)),
+            Path::new("/root"),
+        );
+        assert_eq!(error.is_permission_denied(), true);
+        assert_eq!(error.is_cross_device(), false);
+        #[cfg(target_family = "unix")]
+        {
+            let error: HostError = HostError::new(
+                HostErrorType::CouldNotCreateFile,
+                Some(std::io::Error::from_raw_os_error(libc::EXDEV)),
+                Path::new("/mnt/usb"),
+            );
+            assert_eq!(error.is_cross_device(), true);
+        }
     }
 
     #[test]
@@ -901,6 +1045,22 @@ mod tests {
         assert_eq!(host.list_dir().len(), counter);
     }
 
+    #[test]
+    fn test_host_localhost_scan_dir_listing() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        assert!(make_file_at(tmpdir.path(), "a.txt").is_ok());
+        assert!(make_file_at(tmpdir.path(), "b.txt").is_ok());
+        let listing: DirListing = host.scan_dir_listing(tmpdir.path()).ok().unwrap();
+        assert_eq!(listing.entries.len(), 2);
+        assert_eq!(listing.is_partial(), false);
+        assert_eq!(host.scan_dir(tmpdir.path()).ok().unwrap().len(), 2);
+        // Not accessible directory fails entirely
+        assert!(host
+            .scan_dir_listing(Path::new("/omargabber/123/345"))
+            .is_err());
+    }
+
     #[test]
     #[cfg(target_family = "unix")]
     fn test_host_localhost_change_dir() {
@@ -976,6 +1136,38 @@ mod tests {
         assert!(host.open_file_write(file.path()).is_err());
     }
 
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_host_localhost_disk_usage() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).ok().unwrap();
+        let usage: DiskUsage = host.disk_usage(tmpdir.path()).unwrap();
+        assert!(usage.total > 0);
+        assert!(usage.used <= usage.total);
+        assert!(usage.free <= usage.total);
+        // Doesn't exist
+        assert!(host
+            .disk_usage(Path::new("/this/path/doesnt/exist"))
+            .is_none());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_host_localhost_open_append() {
+        let host: Localhost = Localhost::new(PathBuf::from("/dev")).ok().unwrap();
+        let file: tempfile::NamedTempFile = create_sample_file();
+        let size: u64 = fs::metadata(file.path()).unwrap().len();
+        let mut fhnd: File = host.open_file_append(file.path()).ok().unwrap();
+        assert!(fhnd.write_all(b"foo").is_ok());
+        drop(fhnd);
+        // Data has been appended
+        assert_eq!(fs::metadata(file.path()).unwrap().len(), size + 3);
+        // Doesn't create file
+        assert!(host
+            .open_file_append(Path::new("/tmp/this-file-doesnt-exist.bin"))
+            .is_err());
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn test_host_localhost_symlinks() {
@@ -1023,6 +1215,36 @@ mod tests {
         };
     }
 
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_host_localhost_make_symlink() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let mut host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        // Create sample file
+        assert!(make_file_at(tmpdir.path(), "foo.txt").is_ok());
+        let mut target: PathBuf = PathBuf::from(tmpdir.path());
+        target.push("foo.txt");
+        // Create symlink with relative path
+        assert!(host.symlink(Path::new("bar.txt"), target.as_path()).is_ok());
+        assert_eq!(host.files.len(), 2);
+        let mut link: PathBuf = PathBuf::from(tmpdir.path());
+        link.push("bar.txt");
+        assert_eq!(
+            host.stat(link.as_path())
+                .ok()
+                .unwrap()
+                .unwrap_file()
+                .symlink
+                .unwrap()
+                .get_abs_path(),
+            target
+        );
+        // Symlink already exists
+        assert!(host
+            .symlink(Path::new("bar.txt"), target.as_path())
+            .is_err());
+    }
+
     #[test]
     #[cfg(target_family = "unix")]
     fn test_host_localhost_mkdir() {
@@ -1125,6 +1347,28 @@ mod tests {
             .is_err());
     }
 
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_host_set_mtime() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let file: tempfile::NamedTempFile = create_sample_file();
+        let host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        let mtime: SystemTime = SystemTime::UNIX_EPOCH + Duration::from_secs(1612164210);
+        assert!(host.set_mtime(file.path(), mtime).is_ok());
+        assert_eq!(
+            host.stat(file.path())
+                .ok()
+                .unwrap()
+                .unwrap_file()
+                .last_change_time,
+            mtime
+        );
+        // Error
+        assert!(host
+            .set_mtime(Path::new("/tmp/krgiogoiegj/kwrgnoerig"), mtime)
+            .is_err());
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn test_host_copy_file_absolute() {
@@ -1208,6 +1452,30 @@ mod tests {
         assert!(host.stat(test_file_path.as_path()).is_ok());
     }
 
+    #[test]
+    fn test_host_copy_directory_duplicate() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let dir_src: PathBuf = tmpdir.path().join("test_dir");
+        assert!(std::fs::create_dir_all(dir_src.join("nested")).is_ok());
+        let mut file: File = File::create(dir_src.join("nested").join("foo.txt"))
+            .ok()
+            .unwrap();
+        assert!(file.write_all(b"Hello world!\n").is_ok());
+        let host: Localhost = Localhost::new(PathBuf::from(tmpdir.path())).ok().unwrap();
+        let dir_src_entry: FsEntry = host.files.first().unwrap().clone();
+        // Duplicate in the same directory
+        let dest: PathBuf =
+            crate::utils::file::duplicate_path(dir_src.as_path(), dir_src_entry.is_dir(), |p| {
+                p.exists()
+            });
+        assert_eq!(dest, tmpdir.path().join("test_dir copy"));
+        assert!(host.copy_entry(&dir_src_entry, dest.as_path()).is_ok());
+        // Content is copied recursively
+        assert!(host
+            .stat(dest.join("nested").join("foo.txt").as_path())
+            .is_ok());
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn test_host_copy_directory_relative() {
@@ -1249,39 +1517,6 @@ mod tests {
         assert_eq!(host.exec("echo 5").ok().unwrap().as_str(), "5\r\n");
     }
 
-    #[test]
-    fn test_host_find() {
-        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
-        let dir_path: &Path = tmpdir.path();
-        // Make files
-        assert!(make_file_at(dir_path, "pippo.txt").is_ok());
-        assert!(make_file_at(dir_path, "foo.jpg").is_ok());
-        // Make nested struct
-        assert!(make_dir_at(dir_path, "examples").is_ok());
-        let mut subdir: PathBuf = PathBuf::from(dir_path);
-        subdir.push("examples/");
-        assert!(make_file_at(subdir.as_path(), "omar.txt").is_ok());
-        assert!(make_file_at(subdir.as_path(), "errors.txt").is_ok());
-        assert!(make_file_at(subdir.as_path(), "screenshot.png").is_ok());
-        assert!(make_file_at(subdir.as_path(), "examples.csv").is_ok());
-        let host: Localhost = Localhost::new(PathBuf::from(dir_path)).ok().unwrap();
-        // Find txt files
-        let mut result: Vec<FsEntry> = host.find("*.txt").ok().unwrap();
-        result.sort_by_key(|x: &FsEntry| x.get_name().to_lowercase());
-        // There should be 3 entries
-        assert_eq!(result.len(), 3);
-        // Check names (they should be sorted alphabetically already; NOTE: examples/ comes before pippo.txt)
-        assert_eq!(result[0].get_name(), "errors.txt");
-        assert_eq!(result[1].get_name(), "omar.txt");
-        assert_eq!(result[2].get_name(), "pippo.txt");
-        // Search for directory
-        let mut result: Vec<FsEntry> = host.find("examples*").ok().unwrap();
-        result.sort_by_key(|x: &FsEntry| x.get_name().to_lowercase());
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].get_name(), "examples");
-        assert_eq!(result[1].get_name(), "examples.csv");
-    }
-
     #[test]
     fn test_host_fmt_error() {
         let err: HostError = HostError::new(
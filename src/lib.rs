@@ -29,7 +29,7 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-
+extern crate base64;
 #[macro_use]
 extern crate bitflags;
 extern crate bytesize;
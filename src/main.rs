@@ -40,6 +40,7 @@ extern crate rpassword;
 // External libs
 use argh::FromArgs;
 use std::env;
+use std::io::{self, IsTerminal};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -56,27 +57,140 @@ mod utils;
 
 // namespaces
 use activity_manager::{ActivityManager, NextActivity};
-use filetransfer::FileTransferParams;
-use system::logging;
+use config::params::StartupActivity;
+use filetransfer::{FileTransferParams, TransferOpts};
+use support::{Confirm, Transfer};
+use system::logging::{self, LogBuffer};
+use utils::parser::parse_bytes;
 
 enum Task {
     Activity(NextActivity),
+    ExportTheme(PathBuf),
     ImportTheme(PathBuf),
+    Transfer(Transfer),
+    ValidateTheme(PathBuf),
 }
 
 #[derive(FromArgs)]
 #[argh(description = "
-where positional can be: [protocol://user@address:port:wrkdir] [local-wrkdir]
+where positional can be: [protocol://user@address:port:wrkdir | bookmark-name] [local-wrkdir]
 
 Please, report issues to <https://github.com/veeso/termscp>
 Please, consider supporting the author <https://www.buymeacoffee.com/veeso>")]
 struct Args {
+    #[argh(
+        switch,
+        short = 'b',
+        description = "resolve the address argument as a bookmark name"
+    )]
+    address_as_bookmark: bool,
+    #[argh(
+        switch,
+        description = "enable SSH compression for SCP/SFTP sessions, even if disabled in configuration; useful on slow links"
+    )]
+    compression: bool,
     #[argh(switch, short = 'c', description = "open termscp configuration")]
     config: bool,
     #[argh(option, short = 'P', description = "provide password from CLI")]
     password: Option<String>,
+    #[argh(
+        switch,
+        description = "create the remote working directory provided with the address if it doesn't exist"
+    )]
+    create_wrkdir: bool,
+    #[argh(
+        switch,
+        description = "when mirroring, delete entries which don't exist on source"
+    )]
+    delete: bool,
+    #[argh(
+        switch,
+        description = "report batch operations (mirror, pull, copy, delete, transfers) without executing them"
+    )]
+    dry_run: bool,
+    #[argh(option, description = "export the theme currently in use to file")]
+    export_theme: Option<String>,
+    #[argh(
+        option,
+        description = "download the provided remote file and exit, without starting the UI; the second positional argument is the local destination"
+    )]
+    get: Option<String>,
+    #[argh(
+        option,
+        default = "100",
+        description = "set UI ticks when idle; default 100ms"
+    )]
+    idle_ticks: u64,
+    #[argh(
+        option,
+        short = 'j',
+        description = "maximum amount of local copies to run in parallel; overrides configuration"
+    )]
+    jobs: Option<usize>,
+    #[argh(
+        option,
+        description = "path of the known hosts file used to verify SSH host keys; default ~/.ssh/known_hosts"
+    )]
+    known_hosts: Option<String>,
+    #[argh(
+        option,
+        description = "maximum amount of attempts made to transfer a file on network errors; overrides configuration"
+    )]
+    max_attempts: Option<usize>,
+    #[argh(
+        option,
+        from_str_fn(parse_bytes),
+        description = "size limit of the files opened in the text editor without asking (e.g. 50MiB); overrides configuration"
+    )]
+    max_edit_bytes: Option<u64>,
+    #[argh(
+        option,
+        from_str_fn(parse_bytes),
+        description = "size limit of the files previewed without asking (e.g. 5MB); overrides configuration"
+    )]
+    max_preview_bytes: Option<u64>,
+    #[argh(
+        switch,
+        description = "disable SSH compression for SCP/SFTP sessions, even if enabled in configuration"
+    )]
+    no_compression: bool,
+    #[argh(
+        option,
+        description = "use the configuration, bookmarks and keys of the provided profile"
+    )]
+    profile: Option<String>,
+    #[argh(
+        option,
+        description = "upload the provided local file and exit, without starting the UI; the second positional argument is the remote destination"
+    )]
+    put: Option<String>,
     #[argh(switch, short = 'q', description = "disable logging")]
     quiet: bool,
+    #[argh(
+        switch,
+        description = "disable the actions which modify files, except for downloads"
+    )]
+    read_only: bool,
+    #[argh(
+        switch,
+        description = "resume interrupted transfers when the protocol supports it"
+    )]
+    resume: bool,
+    #[argh(
+        switch,
+        description = "skip files which are unchanged on destination (same size and mtime)"
+    )]
+    skip_existing: bool,
+    #[argh(
+        option,
+        description = "what to open when no address is provided: auth, setup or bookmark:<name>; overrides configuration"
+    )]
+    startup: Option<StartupActivity>,
+    #[argh(
+        switch,
+        description = "reject unknown and changed SSH host keys instead of asking whether to trust them"
+    )]
+    strict_host_key_checking: bool,
     #[argh(option, short = 't', description = "import specified theme")]
     theme: Option<String>,
     #[argh(
@@ -86,8 +200,25 @@ struct Args {
         description = "set UI ticks; default 10ms"
     )]
     ticks: u64,
+    #[argh(
+        switch,
+        description = "along with --theme, validate the theme without importing it"
+    )]
+    validate_only: bool,
+    #[argh(
+        switch,
+        short = 'V',
+        description = "print the log messages to stderr once termscp exits"
+    )]
+    verbose: bool,
     #[argh(switch, short = 'v', description = "print version")]
     version: bool,
+    #[argh(
+        switch,
+        short = 'y',
+        description = "accept the confirmations of theme import and export and the overwrites of --get and --put without asking"
+    )]
+    yes: bool,
     // -- positional
     #[argh(
         positional,
@@ -98,8 +229,13 @@ struct Args {
 
 struct RunOpts {
     remote: Option<FileTransferParams>,
+    local_dir: Option<PathBuf>,
+    transfer_opts: TransferOpts,
     ticks: Duration,
+    idle_ticks: Duration,
     log_enabled: bool,
+    verbose: bool,
+    confirm: Confirm,
     task: Task,
 }
 
@@ -107,8 +243,13 @@ impl Default for RunOpts {
     fn default() -> Self {
         Self {
             remote: None,
+            local_dir: None,
+            transfer_opts: TransferOpts::default(),
             ticks: Duration::from_millis(10),
+            idle_ticks: Duration::from_millis(100),
             log_enabled: true,
+            verbose: false,
+            confirm: Confirm::Ask,
             task: Task::Activity(NextActivity::Authentication),
         }
     }
@@ -125,9 +266,11 @@ fn main() {
         }
     };
     // Setup logging
+    let mut log_buffer: Option<LogBuffer> = None;
     if run_opts.log_enabled {
-        if let Err(err) = logging::init() {
-            eprintln!("Failed to initialize logging: {}", err);
+        match logging::init(run_opts.verbose) {
+            Ok(buffer) => log_buffer = buffer,
+            Err(err) => eprintln!("Failed to initialize logging: {}", err),
         }
     }
     // Read password from remote
@@ -140,6 +283,12 @@ fn main() {
     info!("Starting activity manager...");
     let rc: i32 = run(run_opts);
     info!("termscp terminated");
+    // Print log messages, now that the UI has been terminated
+    if let Some(buffer) = log_buffer {
+        if let Err(err) = buffer.flush_to(&mut io::stderr()) {
+            eprintln!("Failed to print log messages: {}", err);
+        }
+    }
     // Then return
     std::process::exit(rc);
 }
@@ -158,25 +307,85 @@ fn parse_args(args: Args) -> Result<RunOpts, String> {
             TERMSCP_VERSION, TERMSCP_AUTHORS,
         ));
     }
-    // Setup activity?
-    if args.config {
-        run_opts.task = Task::Activity(NextActivity::SetupActivity);
+    // Profile; must be set before configuration is accessed
+    if let Some(profile) = args.profile.as_deref() {
+        if !system::environment::is_valid_profile_name(profile) {
+            return Err(String::from(
+                "Bad profile option: only letters, digits, '-' and '_' are allowed",
+            ));
+        }
     }
+    system::environment::set_profile(args.profile);
     // Logging
+    if args.quiet && args.verbose {
+        return Err(String::from("--quiet and --verbose can't be used together"));
+    }
     if args.quiet {
         run_opts.log_enabled = false;
     }
+    run_opts.verbose = args.verbose;
+    // Confirmations
+    if args.yes {
+        run_opts.confirm = Confirm::AssumeYes;
+    }
+    // Transfer options
+    if args.compression && args.no_compression {
+        return Err(String::from(
+            "--compression and --no-compression can't be used together",
+        ));
+    }
+    let compression: Option<bool> = match (args.compression, args.no_compression) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    };
+    run_opts.transfer_opts = TransferOpts::default()
+        .skip_existing(args.skip_existing)
+        .delete(args.delete)
+        .dry_run(args.dry_run)
+        .jobs(args.jobs)
+        .resume(args.resume)
+        .known_hosts(args.known_hosts.map(PathBuf::from))
+        .strict_host_key_checking(args.strict_host_key_checking)
+        .max_attempts(args.max_attempts)
+        .compression(compression)
+        .max_preview_bytes(args.max_preview_bytes)
+        .max_edit_bytes(args.max_edit_bytes)
+        .create_wrkdir(args.create_wrkdir)
+        .read_only(args.read_only);
+    if args.jobs == Some(0) {
+        return Err(String::from("Bad jobs option: must be at least 1"));
+    }
+    if args.max_attempts == Some(0) {
+        return Err(String::from("Bad max-attempts option: must be at least 1"));
+    }
     // Match ticks
     run_opts.ticks = Duration::from_millis(args.ticks);
+    run_opts.idle_ticks = Duration::from_millis(args.idle_ticks);
     // @! extra modes
     if let Some(theme) = args.theme {
-        run_opts.task = Task::ImportTheme(PathBuf::from(theme));
+        run_opts.task = match args.validate_only {
+            true => Task::ValidateTheme(PathBuf::from(theme)),
+            false => Task::ImportTheme(PathBuf::from(theme)),
+        };
+    } else if args.validate_only {
+        return Err(String::from(
+            "--validate-only requires a theme to be specified",
+        ));
+    }
+    if let Some(theme) = args.export_theme {
+        run_opts.task = Task::ExportTheme(PathBuf::from(theme));
     }
     // @! Ordinary mode
     // Remote argument
     if let Some(remote) = args.positional.get(0) {
-        // Parse address
-        match utils::parser::parse_remote_opt(remote.as_str()) {
+        // Parse address or bookmark name
+        match utils::parser::parse_remote_arg(
+            remote.as_str(),
+            args.address_as_bookmark,
+            support::load_bookmark,
+            &support::load_ssh_config(),
+        ) {
             Ok(mut remote) => {
                 // If password is provided, set password
                 if let Some(passwd) = args.password {
@@ -192,25 +401,63 @@ fn parse_args(args: Args) -> Result<RunOpts, String> {
             }
         }
     }
-    // Local directory
-    if let Some(localdir) = args.positional.get(1) {
+    // One-shot transfer
+    let wrkdir: PathBuf = env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
+    if let Some(transfer) = support::make_transfer(
+        args.get,
+        args.put,
+        args.positional.get(1).cloned(),
+        run_opts.remote.is_some(),
+        wrkdir.as_path(),
+    )? {
+        run_opts.task = Task::Transfer(transfer);
+    }
+    // Startup activity, unless a remote or another task has been provided
+    if run_opts.remote.is_none() && matches!(run_opts.task, Task::Activity(_)) {
+        match support::select_startup(args.config, args.startup, support::load_startup) {
+            StartupActivity::Authentication => {}
+            StartupActivity::Setup => {
+                run_opts.task = Task::Activity(NextActivity::SetupActivity);
+            }
+            StartupActivity::Bookmark(name) => match support::load_bookmark(name.as_str()) {
+                Ok(Some(remote)) => {
+                    run_opts.remote = Some(remote);
+                    run_opts.task = Task::Activity(NextActivity::FileTransfer);
+                }
+                Ok(None) => {
+                    return Err(format!(
+                        "Bad startup option: bookmark \"{}\" doesn't exist (use `--startup auth` to open the authentication form)",
+                        name
+                    ));
+                }
+                Err(err) => return Err(format!("Bad startup option: {}", err)),
+            },
+        }
+    }
+    // Local directory; with a one-shot transfer, it's the destination instead
+    if let Some(localdir) = args
+        .positional
+        .get(1)
+        .filter(|_| !matches!(run_opts.task, Task::Transfer(_)))
+    {
         // Change working directory if local dir is set
         let localdir: PathBuf = PathBuf::from(localdir);
         if let Err(err) = env::set_current_dir(localdir.as_path()) {
             return Err(format!("Bad working directory argument: {}", err));
         }
+        run_opts.local_dir = env::current_dir().ok();
     }
     Ok(run_opts)
 }
 
 /// ### read_password
 ///
-/// Read password from tty if address is specified
+/// Read password from tty if address is specified, the password is required and it hasn't been provided
 fn read_password(run_opts: &mut RunOpts) -> Result<(), String> {
     // Initialize client if necessary
     if let Some(remote) = run_opts.remote.as_mut() {
-        debug!("User has specified remote options: address: {:?}, port: {:?}, protocol: {:?}, user: {:?}, password: {}", remote.address, remote.port, remote.protocol, remote.username, utils::fmt::shadow_password(remote.password.as_deref().unwrap_or("")));
-        if remote.password.is_none() {
+        debug!("User has specified remote options: {:?}", remote);
+        if support::password_prompt_needed(remote, io::stdin().is_terminal()) {
             // Ask password if unspecified
             remote.password = match rpassword::read_password_from_tty(Some("Password: ")) {
                 Ok(p) => {
@@ -219,7 +466,7 @@ fn read_password(run_opts: &mut RunOpts) -> Result<(), String> {
                     } else {
                         debug!(
                             "Read password from tty: {}",
-                            utils::fmt::shadow_password(p.as_str())
+                            utils::fmt::redact_password(p.as_str())
                         );
                         Some(p)
                     }
@@ -237,10 +484,55 @@ fn read_password(run_opts: &mut RunOpts) -> Result<(), String> {
 ///
 /// Run task and return rc
 fn run(mut run_opts: RunOpts) -> i32 {
+    let confirm: Confirm = run_opts.confirm;
     match run_opts.task {
-        Task::ImportTheme(theme) => match support::import_theme(theme.as_path()) {
+        Task::ExportTheme(theme) => {
+            match support::get_config_dir().and_then(|cfg_dir| {
+                support::export_theme(theme.as_path(), cfg_dir.as_path(), confirm)
+            }) {
+                Ok(_) => {
+                    println!("Theme has been successfully exported!");
+                    0
+                }
+                Err(err) => {
+                    eprintln!("{}", err);
+                    1
+                }
+            }
+        }
+        Task::ImportTheme(theme) => {
+            match support::get_config_dir().and_then(|cfg_dir| {
+                support::import_theme(theme.as_path(), cfg_dir.as_path(), confirm)
+            }) {
+                Ok(_) => {
+                    println!("Theme has been successfully imported!");
+                    0
+                }
+                Err(err) => {
+                    eprintln!("{}", err);
+                    1
+                }
+            }
+        }
+        Task::Transfer(transfer) => {
+            let remote: FileTransferParams = match run_opts.remote.take() {
+                Some(remote) => remote,
+                None => {
+                    eprintln!("No remote host has been provided");
+                    return 1;
+                }
+            };
+            match support::transfer(&transfer, &remote, &run_opts.transfer_opts, confirm) {
+                Ok(_) => 0,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    1
+                }
+            }
+        }
+        Task::ValidateTheme(theme) => match support::validate_theme(theme.as_path()) {
             Ok(_) => {
-                println!("Theme has been successfully imported!");
+                println!("Theme is valid!");
                 0
             }
             Err(err) => {
@@ -255,18 +547,23 @@ fn run(mut run_opts: RunOpts) -> i32 {
                 Err(_) => PathBuf::from("/"),
             };
             // Create activity manager (and context too)
-            let mut manager: ActivityManager =
-                match ActivityManager::new(wrkdir.as_path(), run_opts.ticks) {
-                    Ok(m) => m,
-                    Err(err) => {
-                        eprintln!("Could not start activity manager: {}", err);
-                        return 1;
-                    }
-                };
+            let mut manager: ActivityManager = match ActivityManager::new(
+                run_opts.local_dir.as_deref(),
+                wrkdir.as_path(),
+                run_opts.ticks,
+                run_opts.idle_ticks,
+            ) {
+                Ok(m) => m,
+                Err(err) => {
+                    eprintln!("Could not start activity manager: {}", err);
+                    return 1;
+                }
+            };
             // Set file transfer params if set
             if let Some(remote) = run_opts.remote.take() {
                 manager.set_filetransfer_params(remote);
             }
+            manager.set_transfer_opts(run_opts.transfer_opts);
             manager.run(activity);
             0
         }
@@ -26,35 +26,971 @@
  * SOFTWARE.
  */
 // mod
-use crate::system::{environment, theme_provider::ThemeProvider};
-use std::fs;
+use crate::config::{
+    params::StartupActivity,
+    serialization::{deserialize, serialize},
+    themes::Theme,
+};
+use crate::filetransfer::read_only::{check_read_only, Operation};
+use crate::filetransfer::resume::{download_resume_offset, upload_resume_offset};
+use crate::filetransfer::retry::{backoff_delay, is_transient_error, is_transient_io_error};
+use crate::filetransfer::wrkdir::{entry_dir_action, missing_dirs, EntryDirAction};
+use crate::filetransfer::{
+    FileTransfer, FileTransferError, FileTransferErrorType, FileTransferParams,
+    FileTransferProtocol, TransferOpts,
+};
+use crate::fs::{FsEntry, FsFile};
+use crate::host::Localhost;
+use crate::system::bookmarks_client::BookmarksClient;
+use crate::system::config_client::{ConfigClient, DEFAULT_MAX_RECENTS};
+use crate::system::environment;
+use crate::system::ssh_config::{self, SshConfig};
+use crate::ui::activities::filetransfer::FileTransferActivity;
+use crate::utils::fmt::{fmt_bytes, fmt_millis, SizeUnit};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+
+/// ## Confirm
+///
+/// Describes how the confirmations asked by the non-interactive tasks are answered
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Confirm {
+    Ask,       // Ask the user on the terminal
+    AssumeYes, // Accept without asking (`--yes`)
+}
+
+impl Confirm {
+    /// ### confirm
+    ///
+    /// Ask `question` on stdout and read the answer from stdin, unless confirmations are assumed
+    pub fn confirm(&self, question: &str) -> bool {
+        self.confirm_with(question, &mut io::stdin().lock(), &mut io::stdout())
+    }
+
+    /// ### confirm_with
+    ///
+    /// Ask `question` writing it to `output` and reading the answer from `input`, unless confirmations are assumed.
+    /// Only `y` and `yes` are accepted; anything else is a refusal
+    fn confirm_with(
+        &self,
+        question: &str,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> bool {
+        if *self == Confirm::AssumeYes {
+            return true;
+        }
+        if write!(output, "{} [y/N] ", question)
+            .and_then(|_| output.flush())
+            .is_err()
+        {
+            return false;
+        }
+        let mut answer: String = String::new();
+        match input.read_line(&mut answer) {
+            Ok(_) => matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"),
+            Err(_) => false,
+        }
+    }
+}
+
+/// ## Transfer
+///
+/// Describes the single transfer performed by `--get` and `--put`, without starting the user interface
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Transfer {
+    Get(PathBuf, PathBuf), // Remote file to download and local destination (file or directory)
+    Put(PathBuf, Option<PathBuf>), // Local file to upload and remote destination (file or directory); the remote wrkdir if not provided
+}
+
+/// ## Job
+///
+/// A transfer with the file to transfer and its destination resolved
+enum Job {
+    Download(FsFile, PathBuf), // Remote file and local destination
+    Upload(FsFile, PathBuf),   // Local file and remote destination
+}
+
+impl Job {
+    /// ### dest
+    ///
+    /// Get the destination of the job
+    fn dest(&self) -> &Path {
+        match self {
+            Job::Download(_, dest) | Job::Upload(_, dest) => dest.as_path(),
+        }
+    }
+}
+
+/// ## TransferError
+///
+/// Describes why an attempt of a one-shot transfer failed
+struct TransferError {
+    message: String,
+    transient: bool, // Whether the transfer is worth retrying
+}
+
+impl TransferError {
+    /// ### fatal
+    ///
+    /// Instantiate an error which won't change on retry
+    fn fatal(message: String) -> Self {
+        Self {
+            message,
+            transient: false,
+        }
+    }
+
+    /// ### remote
+    ///
+    /// Instantiate an error caused by a file transfer error of kind `kind`
+    fn remote(message: String, kind: FileTransferErrorType) -> Self {
+        Self {
+            message,
+            transient: is_transient_error(kind),
+        }
+    }
+
+    /// ### io
+    ///
+    /// Instantiate an error caused by an I/O error of kind `kind` occurred while streaming from or to the remote
+    fn io(message: String, kind: io::ErrorKind) -> Self {
+        Self {
+            message,
+            transient: is_transient_io_error(kind),
+        }
+    }
+}
+
+/// ## CopyError
+///
+/// Describes which end of `copy_with_progress` failed
+#[derive(Debug)]
+enum CopyError {
+    Read(io::Error),
+    Write(io::Error),
+}
+
+/// ## ThemeFormat
+///
+/// Format of a theme file, detected from its extension.
+/// Files with an unknown extension are expected to be in the native (TOML) format
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ThemeFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ThemeFormat {
+    /// ### from_path
+    ///
+    /// Get theme format from file extension
+    fn from_path(p: &Path) -> Self {
+        match p
+            .extension()
+            .and_then(|x| x.to_str())
+            .map(|x| x.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("json") => ThemeFormat::Json,
+            Some("yaml") | Some("yml") => ThemeFormat::Yaml,
+            _ => ThemeFormat::Toml,
+        }
+    }
+}
+
+impl fmt::Display for ThemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            ThemeFormat::Json => "JSON",
+            ThemeFormat::Toml => "TOML",
+            ThemeFormat::Yaml => "YAML",
+        };
+        write!(f, "{}", name)
+    }
+}
 
 /// ### import_theme
 ///
-/// Import theme at provided path into termscp
-pub fn import_theme(p: &Path) -> Result<(), String> {
+/// Import theme at provided path into the termscp configuration directory `cfg_dir`.
+/// The theme may be in TOML, JSON or YAML format, according to the file extension;
+/// it is always stored in the native format. Replacing the theme in use must be confirmed
+pub fn import_theme(p: &Path, cfg_dir: &Path, confirm: Confirm) -> Result<(), String> {
     if !p.exists() {
         return Err(String::from(
             "Could not import theme: No such file or directory",
         ));
     }
     // Validate theme file
-    ThemeProvider::new(p).map_err(|e| format!("Invalid theme error: {}", e))?;
-    // get config dir
-    let cfg_dir: PathBuf = get_config_dir()?;
+    let theme: Theme = validate_theme(p)?;
+    // Get theme directory
+    let theme_file: PathBuf = environment::get_theme_path(cfg_dir);
+    if theme_file.exists() && !confirm.confirm("A theme is already in use; replace it?") {
+        return Err(String::from("Theme import aborted"));
+    }
+    // Write theme to theme_dir
+    write_theme(&theme, theme_file.as_path()).map_err(|e| format!("Could not import theme: {}", e))
+}
+
+/// ### export_theme
+///
+/// Export the theme currently in use by termscp, stored in the configuration directory `cfg_dir`,
+/// to the provided path, in the format given by its extension.
+/// If no theme has been configured yet, the default theme is exported. Overwriting an existing file must be confirmed
+pub fn export_theme(p: &Path, cfg_dir: &Path, confirm: Confirm) -> Result<(), String> {
+    if p.exists()
+        && !confirm.confirm(format!("\"{}\" already exists; overwrite it?", p.display()).as_str())
+    {
+        return Err(String::from("Theme export aborted"));
+    }
     // Get theme directory
-    let theme_file: PathBuf = environment::get_theme_path(cfg_dir.as_path());
-    // Copy theme to theme_dir
-    fs::copy(p, theme_file.as_path())
+    let theme_file: PathBuf = environment::get_theme_path(cfg_dir);
+    let theme: Theme = match theme_file.exists() {
+        true => validate_theme(theme_file.as_path())?,
+        false => Theme::default(),
+    };
+    write_theme(&theme, p).map_err(|e| format!("Could not export theme: {}", e))
+}
+
+/// ### write_theme
+///
+/// Serialize theme into the file at provided path, using the format given by its extension
+fn write_theme(theme: &Theme, p: &Path) -> Result<(), String> {
+    let writer: File = File::create(p).map_err(|e| e.to_string())?;
+    match ThemeFormat::from_path(p) {
+        ThemeFormat::Json => serde_json::to_writer_pretty(writer, theme).map_err(|e| e.to_string()),
+        ThemeFormat::Toml => serialize(theme, Box::new(writer)).map_err(|e| e.to_string()),
+        ThemeFormat::Yaml => serde_yaml::to_writer(writer, theme).map_err(|e| e.to_string()),
+    }
+}
+
+/// ### validate_theme
+///
+/// Parse theme at provided path, in the format given by its extension, checking that every color is set and valid.
+/// Returns the theme on success or a message reporting the faulty key otherwise
+pub fn validate_theme(p: &Path) -> Result<Theme, String> {
+    let reader: File = File::open(p).map_err(|e| format!("Could not open theme file: {}", e))?;
+    let format: ThemeFormat = ThemeFormat::from_path(p);
+    match format {
+        ThemeFormat::Json => serde_json::from_reader(reader).map_err(|e| e.to_string()),
+        ThemeFormat::Toml => deserialize(Box::new(reader)).map_err(|e| e.to_string()),
+        ThemeFormat::Yaml => serde_yaml::from_reader(reader).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("Invalid theme (expected {} format): {}", format, e))
+}
+
+/// ### load_bookmark
+///
+/// Get the parameters of the bookmark with the provided name, if it exists
+pub fn load_bookmark(name: &str) -> Result<Option<FileTransferParams>, String> {
+    let cfg_dir: PathBuf = get_config_dir()?;
+    let bookmarks_file: PathBuf = environment::get_bookmarks_paths(cfg_dir.as_path());
+    // Don't create the bookmarks file just to look it up
+    if !bookmarks_file.exists() {
+        return Ok(None);
+    }
+    // NOTE: recents are never written back from here, so their limit doesn't matter
+    let client: BookmarksClient = BookmarksClient::new(
+        bookmarks_file.as_path(),
+        cfg_dir.as_path(),
+        DEFAULT_MAX_RECENTS,
+    )
+    .map_err(|e| format!("Could not read bookmarks: {}", e))?;
+    Ok(client.get_bookmark(name))
+}
+
+/// ### load_ssh_config
+///
+/// Load the OpenSSH client configuration, using the path set in the termscp configuration, if any
+pub fn load_ssh_config() -> SshConfig {
+    let config: ConfigClient = FileTransferActivity::init_config_client();
+    ssh_config::load_user_ssh_config(config.get_ssh_config())
+}
+
+/// ### load_startup
+///
+/// Get the startup activity from configuration.
+/// If configuration can't be read, the authentication form is opened, which reports the error
+pub fn load_startup() -> StartupActivity {
+    let cfg_dir: PathBuf = match get_config_dir() {
+        Ok(dir) => dir,
+        Err(_) => return StartupActivity::default(),
+    };
+    let (config_path, ssh_dir): (PathBuf, PathBuf) =
+        environment::get_config_paths(cfg_dir.as_path());
+    match ConfigClient::new(config_path.as_path(), ssh_dir.as_path()) {
+        Ok(client) => client.get_startup(),
+        Err(_) => StartupActivity::default(),
+    }
+}
+
+/// ### select_startup
+///
+/// Select the activity to start with, when no remote is provided from CLI.
+/// `--config` takes precedence over `--startup`, which takes precedence over the `configured` one
+pub fn select_startup<F>(
+    open_config: bool,
+    cli: Option<StartupActivity>,
+    configured: F,
+) -> StartupActivity
+where
+    F: FnOnce() -> StartupActivity,
+{
+    match (open_config, cli) {
+        (true, _) => StartupActivity::Setup,
+        (false, Some(startup)) => startup,
+        (false, None) => configured(),
+    }
+}
+
+/// ### password_prompt_needed
+///
+/// Returns whether the password of the remote provided from CLI must be read from the tty:
+/// that's the case if it hasn't been supplied, the protocol requires it and stdin is a tty.
+/// Otherwise termscp goes on without password
+pub fn password_prompt_needed(params: &FileTransferParams, stdin_is_tty: bool) -> bool {
+    let required: bool = match params.protocol {
+        // Without username, the anonymous login is used
+        FileTransferProtocol::Ftp(_) => params.username.is_some(),
+        // The private key is used to authenticate instead
+        FileTransferProtocol::Sftp | FileTransferProtocol::Scp => params.ssh_key_path.is_none(),
+    };
+    stdin_is_tty && required && params.password.is_none()
+}
+
+/// ### make_transfer
+///
+/// Make the transfer to perform from the `--get` and `--put` options, which require a remote to be provided.
+/// `dest` is where the file is saved to: the local path for `--get` and the remote path for `--put`.
+/// Local paths are resolved from `wrkdir`, which is also where downloads are saved to if `dest` is not provided,
+/// while remote paths are resolved after connecting.
+/// Returns `None` if neither option has been provided
+pub fn make_transfer(
+    get: Option<String>,
+    put: Option<String>,
+    dest: Option<String>,
+    has_remote: bool,
+    wrkdir: &Path,
+) -> Result<Option<Transfer>, String> {
+    if dest.as_deref() == Some("") {
+        return Err(String::from("Bad destination: path can't be empty"));
+    }
+    let transfer: Transfer = match (get, put) {
+        (None, None) => return Ok(None),
+        (Some(_), Some(_)) => {
+            return Err(String::from("--get and --put can't be used together"));
+        }
+        (Some(remote), None) if remote.is_empty() => {
+            return Err(String::from("Bad get option: path can't be empty"));
+        }
+        (None, Some(local)) if local.is_empty() => {
+            return Err(String::from("Bad put option: path can't be empty"));
+        }
+        (Some(remote), None) => Transfer::Get(
+            PathBuf::from(remote),
+            dest.map(|x| wrkdir.join(x))
+                .unwrap_or_else(|| wrkdir.to_path_buf()),
+        ),
+        (None, Some(local)) => Transfer::Put(wrkdir.join(local), dest.map(PathBuf::from)),
+    };
+    match has_remote {
+        true => Ok(Some(transfer)),
+        false => Err(String::from(
+            "--get and --put require the address of the remote host",
+        )),
+    }
+}
+
+/// ### transfer
+///
+/// Connect to the remote host, perform `transfer` and disconnect.
+/// Existing files are overwritten only if `confirm` accepts it, unless the transfer is resumed;
+/// transfers failed because of network errors are retried after reconnecting.
+/// The progress is reported on stderr
+pub fn transfer(
+    transfer: &Transfer,
+    params: &FileTransferParams,
+    opts: &TransferOpts,
+    confirm: Confirm,
+) -> Result<(), String> {
+    let config: ConfigClient = FileTransferActivity::init_config_client();
+    let read_only: bool = opts.read_only || config.get_read_only();
+    if let Transfer::Put(..) = transfer {
+        check_read_only(read_only, Operation::Upload)?;
+    }
+    let resume: bool = opts.resume || config.get_resume_transfers();
+    let max_attempts: usize = match opts.max_attempts {
+        Some(attempts) => attempts.max(1),
+        None => config.get_transfer_max_attempts(),
+    };
+    let unit: SizeUnit = config.get_size_unit();
+    let mut client: Box<dyn FileTransfer> =
+        FileTransferActivity::make_client(params, &config, opts);
+    if let Err(err) = connect(client.as_mut(), params) {
+        return Err(match err.kind() {
+            FileTransferErrorType::HostKeyUnknown => format!(
+                "Could not connect to {}: unknown host key; connect once without --get or --put to trust it",
+                params.address
+            ),
+            _ => format!("Could not connect to {}: {}", params.address, err),
+        });
+    }
+    let create: bool = (opts.create_wrkdir || config.get_create_missing_wrkdir()) && !read_only;
+    let result: Result<(), String> = enter_wrkdir(
+        client.as_mut(),
+        params.entry_directory.as_deref(),
+        create,
+        opts.dry_run,
+    )
+    .and_then(|wrkdir| {
+        let job: Job = match make_job(client.as_mut(), transfer, wrkdir.as_path(), opts.dry_run)? {
+            Some(job) => job,
+            None => return Ok(()),
+        };
+        if !confirm_overwrite(client.as_mut(), &job, resume, confirm) {
+            return Err(format!(
+                "\"{}\" already exists and won't be overwritten",
+                job.dest().display()
+            ));
+        }
+        let mut attempt: usize = 1;
+        loop {
+            let result: Result<(), TransferError> = match attempt {
+                1 => run_job(client.as_mut(), &job, resume, unit),
+                _ => reconnect(client.as_mut(), params, wrkdir.as_path())
+                    .and_then(|_| run_job(client.as_mut(), &job, resume, unit)),
+            };
+            match result {
+                Err(err) if err.transient && attempt < max_attempts => {
+                    let delay: Duration = backoff_delay(attempt);
+                    eprintln!(
+                        "{}; retrying in {} seconds (attempt {} of {})",
+                        err.message,
+                        fmt_millis(delay),
+                        attempt + 1,
+                        max_attempts
+                    );
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result.map_err(|err| err.message),
+            }
+        }
+    });
+    if let Err(err) = client.disconnect() {
+        warn!("Could not disconnect: {}", err);
+    }
+    result
+}
+
+/// ### connect
+///
+/// Connect `client` to the remote host described by `params`
+fn connect(
+    client: &mut dyn FileTransfer,
+    params: &FileTransferParams,
+) -> Result<(), FileTransferError> {
+    client
+        .connect(
+            params.address.clone(),
+            params.port,
+            params.username.clone(),
+            params.password.clone(),
+        )
         .map(|_| ())
-        .map_err(|e| format!("Could not import theme: {}", e))
+}
+
+/// ### reconnect
+///
+/// Close the session of `client`, which may have been dropped, then connect again and enter `wrkdir`
+fn reconnect(
+    client: &mut dyn FileTransfer,
+    params: &FileTransferParams,
+    wrkdir: &Path,
+) -> Result<(), TransferError> {
+    let _ = client.disconnect();
+    connect(client, params)
+        .and_then(|_| client.change_dir(wrkdir).map(|_| ()))
+        .map_err(|err| {
+            TransferError::remote(
+                format!("Could not reconnect to {}: {}", params.address, err),
+                err.kind(),
+            )
+        })
+}
+
+/// ### enter_wrkdir
+///
+/// Enter the remote working directory provided with the address, if any, creating it if missing and `create` is true.
+/// With `dry_run`, missing directories are only reported.
+/// Returns the remote working directory
+fn enter_wrkdir(
+    client: &mut dyn FileTransfer,
+    dir: Option<&Path>,
+    create: bool,
+    dry_run: bool,
+) -> Result<PathBuf, String> {
+    let wrkdir: PathBuf = client
+        .pwd()
+        .map_err(|e| format!("Could not get remote working directory: {}", e))?;
+    let dir: PathBuf = match dir {
+        Some(dir) => wrkdir.join(dir),
+        None => return Ok(wrkdir),
+    };
+    let exists: bool = client.change_dir(dir.as_path()).is_ok();
+    match entry_dir_action(exists, create) {
+        EntryDirAction::ChangeDir => {}
+        EntryDirAction::Create => {
+            for missing in missing_dirs(dir.as_path(), |p| client.change_dir(p).is_ok()) {
+                if dry_run {
+                    eprintln!("[dry-run] Create directory \"{}\"", missing.display());
+                    continue;
+                }
+                client.mkdir(missing.as_path()).map_err(|e| {
+                    format!(
+                        "Could not create directory \"{}\": {}",
+                        missing.display(),
+                        e
+                    )
+                })?;
+            }
+            if !dry_run {
+                client.change_dir(dir.as_path()).map_err(|e| {
+                    format!("Could not change directory to \"{}\": {}", dir.display(), e)
+                })?;
+            }
+        }
+        EntryDirAction::Warn => {
+            return Err(format!(
+                "Working directory \"{}\" doesn't exist or can't be entered; use `--create-wrkdir` to create it",
+                dir.display()
+            ));
+        }
+    }
+    Ok(dir)
+}
+
+/// ### make_job
+///
+/// Resolve the file to transfer and its destination for `transfer`, with a connected `client` in the remote `wrkdir`.
+/// A destination which is an existing directory gets the name of the transferred file.
+/// With `dry_run`, the transfer is only reported and `None` is returned
+fn make_job(
+    client: &mut dyn FileTransfer,
+    transfer: &Transfer,
+    wrkdir: &Path,
+    dry_run: bool,
+) -> Result<Option<Job>, String> {
+    let job: Job = match transfer {
+        Transfer::Get(remote, local) => {
+            let remote: PathBuf = wrkdir.join(remote);
+            let file: FsFile = match client.stat(remote.as_path()) {
+                Ok(FsEntry::File(file)) => file,
+                Ok(FsEntry::Directory(_)) => {
+                    return Err(format!(
+                        "\"{}\" is a directory: only files can be transferred",
+                        remote.display()
+                    ));
+                }
+                Err(err) => {
+                    return Err(format!("Could not stat \"{}\": {}", remote.display(), err))
+                }
+            };
+            let dest: PathBuf = match local.is_dir() {
+                true => local.join(file.name.as_str()),
+                false => local.clone(),
+            };
+            if dest.is_dir() {
+                return Err(format!("\"{}\" is a directory", dest.display()));
+            }
+            Job::Download(file, dest)
+        }
+        Transfer::Put(local, remote) => {
+            let parent: PathBuf = local
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("/"));
+            let file: FsFile = match Localhost::new(parent).and_then(|host| host.stat(local)) {
+                Ok(FsEntry::File(file)) => file,
+                Ok(FsEntry::Directory(_)) => {
+                    return Err(format!(
+                        "\"{}\" is a directory: only files can be transferred",
+                        local.display()
+                    ));
+                }
+                Err(err) => return Err(format!("Could not stat \"{}\": {}", local.display(), err)),
+            };
+            let dest: PathBuf = match remote {
+                None => wrkdir.join(file.name.as_str()),
+                Some(remote) => {
+                    let remote: PathBuf = wrkdir.join(remote);
+                    match client.stat(remote.as_path()) {
+                        Ok(FsEntry::Directory(_)) => remote.join(file.name.as_str()),
+                        _ => remote,
+                    }
+                }
+            };
+            if let Ok(FsEntry::Directory(_)) = client.stat(dest.as_path()) {
+                return Err(format!("\"{}\" is a directory", dest.display()));
+            }
+            Job::Upload(file, dest)
+        }
+    };
+    if dry_run {
+        match &job {
+            Job::Download(file, dest) => eprintln!(
+                "[dry-run] Download \"{}\" to \"{}\"",
+                file.abs_path.display(),
+                dest.display()
+            ),
+            Job::Upload(file, dest) => eprintln!(
+                "[dry-run] Upload \"{}\" to \"{}\"",
+                file.abs_path.display(),
+                dest.display()
+            ),
+        }
+        return Ok(None);
+    }
+    Ok(Some(job))
+}
+
+/// ### confirm_overwrite
+///
+/// Returns whether the destination of `job` can be written: either it doesn't exist,
+/// the transfer is going to resume it or `confirm` accepts to overwrite it
+fn confirm_overwrite(
+    client: &mut dyn FileTransfer,
+    job: &Job,
+    resume: bool,
+    confirm: Confirm,
+) -> bool {
+    let exists: bool = match job {
+        Job::Download(_, dest) => dest.exists(),
+        Job::Upload(_, dest) => client.stat(dest.as_path()).is_ok(),
+    };
+    if !exists || (resume && resume_offset_of(client, job) > 0) {
+        return true;
+    }
+    confirm.confirm(format!("\"{}\" already exists: overwrite it?", job.dest().display()).as_str())
+}
+
+/// ### resume_offset_of
+///
+/// Get the offset where to resume `job` from; 0 if the destination must be written from the beginning
+fn resume_offset_of(client: &mut dyn FileTransfer, job: &Job) -> usize {
+    let offset: Option<usize> = match job {
+        Job::Download(file, dest) => match File::open(dest.as_path()) {
+            Ok(mut fhnd) => {
+                let size: usize = fhnd.metadata().map(|x| x.len() as usize).unwrap_or(0);
+                download_resume_offset(client, file, size, &mut fhnd)
+            }
+            Err(_) => Some(0),
+        },
+        Job::Upload(file, dest) => match File::open(file.abs_path.as_path()) {
+            Ok(mut fhnd) => upload_resume_offset(client, file, &mut fhnd, dest.as_path()),
+            Err(_) => Some(0),
+        },
+    };
+    offset.unwrap_or(0)
+}
+
+/// ### run_job
+///
+/// Perform a single attempt of `job`, resuming it from the partial destination if `resume` is true.
+/// Without `resume`, downloads are written to a temporary file which replaces the destination once complete,
+/// so a failed download never leaves a truncated file behind
+fn run_job(
+    client: &mut dyn FileTransfer,
+    job: &Job,
+    resume: bool,
+    unit: SizeUnit,
+) -> Result<(), TransferError> {
+    let offset: usize = match resume {
+        true => resume_offset_of(client, job),
+        false => 0,
+    };
+    match job {
+        Job::Download(file, dest) if offset >= file.size => {
+            eprintln!("\"{}\" has already been downloaded", dest.display());
+            Ok(())
+        }
+        Job::Upload(file, dest) if offset >= file.size => {
+            eprintln!("\"{}\" has already been uploaded", dest.display());
+            Ok(())
+        }
+        Job::Download(file, dest) if resume => {
+            let mut writer: File = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(offset > 0)
+                .truncate(offset == 0)
+                .open(dest.as_path())
+                .map_err(|e| {
+                    TransferError::fatal(format!("Could not open \"{}\": {}", dest.display(), e))
+                })?;
+            download(client, file, &mut writer, offset, unit)?;
+            set_downloaded_mode(dest.as_path(), file, None)
+        }
+        Job::Download(file, dest) => {
+            let dir: &Path = match dest.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            let mut tmpfile: NamedTempFile = NamedTempFile::new_in(dir).map_err(|e| {
+                TransferError::fatal(format!(
+                    "Could not create a temporary file in \"{}\": {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+            download(client, file, tmpfile.as_file_mut(), 0, unit)?;
+            let old_mode: Option<fs::Permissions> =
+                fs::metadata(dest.as_path()).ok().map(|x| x.permissions());
+            tmpfile.persist(dest.as_path()).map_err(|e| {
+                TransferError::fatal(format!("Could not write \"{}\": {}", dest.display(), e))
+            })?;
+            set_downloaded_mode(dest.as_path(), file, old_mode)
+        }
+        Job::Upload(file, dest) => {
+            let mut reader: File = File::open(file.abs_path.as_path()).map_err(|e| {
+                TransferError::fatal(format!(
+                    "Could not open \"{}\": {}",
+                    file.abs_path.display(),
+                    e
+                ))
+            })?;
+            if offset > 0 {
+                reader.seek(SeekFrom::Start(offset as u64)).map_err(|e| {
+                    TransferError::fatal(format!(
+                        "Could not seek \"{}\": {}",
+                        file.abs_path.display(),
+                        e
+                    ))
+                })?;
+            }
+            upload(client, file, &mut reader, dest.as_path(), offset, unit)
+        }
+    }
+}
+
+/// ### download
+///
+/// Download `file` from `offset` into `writer`, checking that the whole file has been received
+fn download(
+    client: &mut dyn FileTransfer,
+    file: &FsFile,
+    writer: &mut dyn Write,
+    offset: usize,
+    unit: SizeUnit,
+) -> Result<(), TransferError> {
+    let path: &Path = file.abs_path.as_path();
+    let mut reader: Box<dyn Read> = match offset {
+        0 => client.recv_file(file),
+        offset => client.recv_file_at(file, offset as u64),
+    }
+    .map_err(|e| {
+        TransferError::remote(
+            format!("Could not read \"{}\": {}", path.display(), e),
+            e.kind(),
+        )
+    })?;
+    let copied: Result<u64, TransferError> = copy_with_progress(
+        reader.as_mut(),
+        writer,
+        offset as u64,
+        file.size as u64,
+        file.name.as_str(),
+        unit,
+        &mut io::stderr(),
+    )
+    .map_err(|e| match e {
+        CopyError::Read(e) => TransferError::io(
+            format!("Could not download \"{}\": {}", path.display(), e),
+            e.kind(),
+        ),
+        CopyError::Write(e) => {
+            TransferError::fatal(format!("Could not download \"{}\": {}", path.display(), e))
+        }
+    });
+    let finalized: Result<(), TransferError> = client.on_recv(reader).map_err(|e| {
+        TransferError::remote(
+            format!("Could not finalize \"{}\": {}", path.display(), e),
+            e.kind(),
+        )
+    });
+    let copied: u64 = copied?;
+    finalized?;
+    check_size(path, file.size, offset as u64 + copied)
+}
+
+/// ### upload
+///
+/// Upload `file`, read from `reader`, to `dest` from `offset`, checking that the whole file has been sent
+fn upload(
+    client: &mut dyn FileTransfer,
+    file: &FsFile,
+    reader: &mut dyn Read,
+    dest: &Path,
+    offset: usize,
+    unit: SizeUnit,
+) -> Result<(), TransferError> {
+    let mut writer: Box<dyn Write> = match offset {
+        0 => client.send_file(file, dest),
+        offset => client.send_file_at(file, dest, offset as u64),
+    }
+    .map_err(|e| {
+        TransferError::remote(
+            format!("Could not write \"{}\": {}", dest.display(), e),
+            e.kind(),
+        )
+    })?;
+    let path: &Path = file.abs_path.as_path();
+    let copied: Result<u64, TransferError> = copy_with_progress(
+        reader,
+        writer.as_mut(),
+        offset as u64,
+        file.size as u64,
+        file.name.as_str(),
+        unit,
+        &mut io::stderr(),
+    )
+    .map_err(|e| match e {
+        CopyError::Read(e) => {
+            TransferError::fatal(format!("Could not upload \"{}\": {}", path.display(), e))
+        }
+        CopyError::Write(e) => TransferError::io(
+            format!("Could not upload \"{}\": {}", path.display(), e),
+            e.kind(),
+        ),
+    });
+    let finalized: Result<(), TransferError> = client.on_sent(writer).map_err(|e| {
+        TransferError::remote(
+            format!("Could not finalize \"{}\": {}", dest.display(), e),
+            e.kind(),
+        )
+    });
+    let copied: u64 = copied?;
+    finalized?;
+    check_size(path, file.size, offset as u64 + copied)
+}
+
+/// ### check_size
+///
+/// Check that all the `size` bytes of `path` have been transferred.
+/// An incomplete transfer is worth retrying, since the connection has probably been dropped
+fn check_size(path: &Path, size: usize, transferred: u64) -> Result<(), TransferError> {
+    match transferred == size as u64 {
+        true => Ok(()),
+        false => Err(TransferError {
+            message: format!(
+                "Transfer of \"{}\" is incomplete: {} out of {} bytes have been transferred",
+                path.display(),
+                transferred,
+                size
+            ),
+            transient: true,
+        }),
+    }
+}
+
+/// ### set_downloaded_mode
+///
+/// Set the permissions of the downloaded `dest`: those of the remote `file` if known,
+/// otherwise `old_mode` (the permissions of the replaced file) or 644
+fn set_downloaded_mode(
+    dest: &Path,
+    file: &FsFile,
+    old_mode: Option<fs::Permissions>,
+) -> Result<(), TransferError> {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode: fs::Permissions = match (file.unix_pex, old_mode) {
+            (Some((u, g, o)), _) => {
+                fs::Permissions::from_mode(((u as u32) << 6) | ((g as u32) << 3) | o as u32)
+            }
+            (None, Some(old_mode)) => old_mode,
+            (None, None) => fs::Permissions::from_mode(0o644),
+        };
+        fs::set_permissions(dest, mode).map_err(|e| {
+            TransferError::fatal(format!(
+                "Could not set permissions of \"{}\": {}",
+                dest.display(),
+                e
+            ))
+        })
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = (dest, file, old_mode);
+        Ok(())
+    }
+}
+
+/// ### copy_with_progress
+///
+/// Copy `reader` into `writer`, reporting the progress of the transfer of `name` (`total` bytes long) on `progress`
+/// each time the percentage changes. The copy starts from `offset` bytes, already transferred before.
+/// Returns the amount of bytes copied
+fn copy_with_progress(
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+    offset: u64,
+    total: u64,
+    name: &str,
+    unit: SizeUnit,
+    progress: &mut dyn Write,
+) -> Result<u64, CopyError> {
+    let mut buffer: [u8; 65536] = [0; 65536];
+    let mut copied: u64 = 0;
+    let mut last_percent: Option<u64> = None;
+    loop {
+        let done: u64 = offset + copied;
+        let percent: u64 = match total {
+            0 => 100,
+            total => (done * 100 / total).min(100),
+        };
+        if last_percent != Some(percent) {
+            let _ = write!(
+                progress,
+                "\r{}: {}% ({}/{})",
+                name,
+                percent,
+                fmt_bytes(done, unit),
+                fmt_bytes(total, unit)
+            );
+            last_percent = Some(percent);
+        }
+        let bytes: usize = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                let _ = writeln!(progress);
+                return Err(CopyError::Read(err));
+            }
+        };
+        if let Err(err) = writer.write_all(&buffer[..bytes]) {
+            let _ = writeln!(progress);
+            return Err(CopyError::Write(err));
+        }
+        copied += bytes as u64;
+    }
+    let _ = writeln!(progress);
+    writer.flush().map_err(CopyError::Write)?;
+    Ok(copied)
 }
 
 /// ### get_config_dir
 ///
 /// Get configuration directory
-fn get_config_dir() -> Result<PathBuf, String> {
+pub fn get_config_dir() -> Result<PathBuf, String> {
     match environment::init_config_dir() {
         Ok(Some(config_dir)) => Ok(config_dir),
         Ok(None) => Err(String::from(
@@ -66,3 +1002,518 @@ fn get_config_dir() -> Result<PathBuf, String> {
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use tuirealm::tui::style::Color;
+
+    #[test]
+    fn test_support_validate_theme_ok() {
+        let file: NamedTempFile = theme_file(THEME.to_string());
+        let theme: Theme = validate_theme(file.path()).ok().unwrap();
+        assert_eq!(theme.auth_address, Color::Yellow);
+        assert_eq!(theme.transfer_status_sync_browsing, Color::LightGreen);
+    }
+
+    #[test]
+    fn test_support_validate_theme_missing_key() {
+        let file: NamedTempFile = theme_file(THEME.replace("auth_port = \"LightCyan\"\n", ""));
+        let err: String = validate_theme(file.path()).err().unwrap();
+        assert!(err.contains("missing field `auth_port`"));
+    }
+
+    #[test]
+    fn test_support_validate_theme_bad_color() {
+        let file: NamedTempFile = theme_file(THEME.replace("\"LightMagenta\"", "\"verdazzurro\""));
+        let err: String = validate_theme(file.path()).err().unwrap();
+        assert!(err.contains("Invalid color 'verdazzurro'"));
+        assert!(err.contains("auth_username"));
+    }
+
+    #[test]
+    fn test_support_validate_theme_formats() {
+        let toml: Theme = validate_theme(theme_file(THEME.to_string()).path())
+            .ok()
+            .unwrap();
+        let json: NamedTempFile = theme_file_ex(theme_json(), ".json");
+        assert_eq!(validate_theme(json.path()).ok().unwrap(), toml);
+        let yaml: NamedTempFile = theme_file_ex(theme_yaml(), ".yaml");
+        assert_eq!(validate_theme(yaml.path()).ok().unwrap(), toml);
+        let yml: NamedTempFile = theme_file_ex(theme_yaml(), ".YML");
+        assert_eq!(validate_theme(yml.path()).ok().unwrap(), toml);
+    }
+
+    #[test]
+    fn test_support_validate_theme_format_mismatch() {
+        let file: NamedTempFile = theme_file_ex(THEME.to_string(), ".json");
+        let err: String = validate_theme(file.path()).err().unwrap();
+        assert!(err.contains("expected JSON format"));
+        let file: NamedTempFile = theme_file_ex(theme_json(), ".toml");
+        let err: String = validate_theme(file.path()).err().unwrap();
+        assert!(err.contains("expected TOML format"));
+    }
+
+    #[test]
+    fn test_support_validate_theme_json_bad_color() {
+        let file: NamedTempFile = theme_file_ex(
+            theme_json().replace("\"LightMagenta\"", "\"verdazzurro\""),
+            ".json",
+        );
+        let err: String = validate_theme(file.path()).err().unwrap();
+        assert!(err.contains("Invalid color 'verdazzurro'"));
+    }
+
+    #[test]
+    fn test_support_theme_format_from_path() {
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("theme.json")),
+            ThemeFormat::Json
+        );
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("theme.yaml")),
+            ThemeFormat::Yaml
+        );
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("theme.yml")),
+            ThemeFormat::Yaml
+        );
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("theme.toml")),
+            ThemeFormat::Toml
+        );
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("theme")),
+            ThemeFormat::Toml
+        );
+    }
+
+    #[test]
+    fn test_support_validate_theme_no_such_file() {
+        assert!(validate_theme(Path::new("/tmp/this/theme/does/not/exist.toml")).is_err());
+    }
+
+    #[test]
+    fn test_support_import_theme_invalid() {
+        let file: NamedTempFile = theme_file(THEME.replace("\"Red\"", "\"notacolor\""));
+        let cfg_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        assert!(import_theme(file.path(), cfg_dir.path(), Confirm::AssumeYes).is_err());
+        assert!(!environment::get_theme_path(cfg_dir.path()).exists());
+    }
+
+    #[test]
+    fn test_support_import_theme_assume_yes() {
+        let file: NamedTempFile = theme_file(THEME.to_string());
+        let cfg_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        // The second import replaces the theme without prompting
+        assert!(import_theme(file.path(), cfg_dir.path(), Confirm::AssumeYes).is_ok());
+        assert!(import_theme(file.path(), cfg_dir.path(), Confirm::AssumeYes).is_ok());
+        assert!(validate_theme(environment::get_theme_path(cfg_dir.path()).as_path()).is_ok());
+    }
+
+    #[test]
+    fn test_support_export_theme_assume_yes() {
+        let file: NamedTempFile = theme_file(String::from("garbage"));
+        let cfg_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        // Existing file is overwritten without prompting
+        assert!(export_theme(file.path(), cfg_dir.path(), Confirm::AssumeYes).is_ok());
+        assert!(validate_theme(file.path()).is_ok());
+    }
+
+    #[test]
+    fn test_support_password_prompt_needed() {
+        let params: FileTransferParams = FileTransferParams::new("10.0.0.1").username(Some("pi"));
+        // Omitted on tty
+        assert_eq!(password_prompt_needed(&params, true), true);
+        // Omitted, but not on tty
+        assert_eq!(password_prompt_needed(&params, false), false);
+        // Supplied
+        let supplied: FileTransferParams = params.clone().password(Some("secret"));
+        assert_eq!(password_prompt_needed(&supplied, true), false);
+        assert_eq!(password_prompt_needed(&supplied, false), false);
+        // Private key is used instead
+        let key: FileTransferParams = params
+            .clone()
+            .ssh_key_path(Some(Path::new("/home/pi/.ssh/id_ed25519")));
+        assert_eq!(password_prompt_needed(&key, true), false);
+        // FTP needs it only with username
+        let ftp: FileTransferParams = params.clone().protocol(FileTransferProtocol::Ftp(false));
+        assert_eq!(password_prompt_needed(&ftp, true), true);
+        let anonymous: FileTransferParams = ftp.username(None::<&str>);
+        assert_eq!(password_prompt_needed(&anonymous, true), false);
+    }
+
+    #[test]
+    fn test_support_make_transfer() {
+        let wrkdir: &Path = Path::new("/home/omar");
+        // No transfer
+        assert_eq!(make_transfer(None, None, None, true, wrkdir).unwrap(), None);
+        assert_eq!(
+            make_transfer(None, None, None, false, wrkdir).unwrap(),
+            None
+        );
+        // Get keeps the remote path as it is and saves into wrkdir by default
+        assert_eq!(
+            make_transfer(Some(String::from("/tmp/a.txt")), None, None, true, wrkdir).unwrap(),
+            Some(Transfer::Get(
+                PathBuf::from("/tmp/a.txt"),
+                PathBuf::from("/home/omar")
+            ))
+        );
+        assert_eq!(
+            make_transfer(Some(String::from("a.txt")), None, None, true, wrkdir).unwrap(),
+            Some(Transfer::Get(
+                PathBuf::from("a.txt"),
+                PathBuf::from("/home/omar")
+            ))
+        );
+        // Get resolves the local destination from wrkdir
+        assert_eq!(
+            make_transfer(
+                Some(String::from("a.txt")),
+                None,
+                Some(String::from("backup/b.txt")),
+                true,
+                wrkdir
+            )
+            .unwrap(),
+            Some(Transfer::Get(
+                PathBuf::from("a.txt"),
+                PathBuf::from("/home/omar/backup/b.txt")
+            ))
+        );
+        assert_eq!(
+            make_transfer(
+                Some(String::from("a.txt")),
+                None,
+                Some(String::from("/tmp")),
+                true,
+                wrkdir
+            )
+            .unwrap(),
+            Some(Transfer::Get(PathBuf::from("a.txt"), PathBuf::from("/tmp")))
+        );
+        // Put resolves the local path from wrkdir
+        assert_eq!(
+            make_transfer(None, Some(String::from("docs/b.txt")), None, true, wrkdir).unwrap(),
+            Some(Transfer::Put(PathBuf::from("/home/omar/docs/b.txt"), None))
+        );
+        assert_eq!(
+            make_transfer(None, Some(String::from("/tmp/b.txt")), None, true, wrkdir).unwrap(),
+            Some(Transfer::Put(PathBuf::from("/tmp/b.txt"), None))
+        );
+        // Put keeps the remote destination as it is
+        assert_eq!(
+            make_transfer(
+                None,
+                Some(String::from("b.txt")),
+                Some(String::from("reports/c.txt")),
+                true,
+                wrkdir
+            )
+            .unwrap(),
+            Some(Transfer::Put(
+                PathBuf::from("/home/omar/b.txt"),
+                Some(PathBuf::from("reports/c.txt"))
+            ))
+        );
+        // Errors
+        assert!(make_transfer(
+            Some(String::from("a.txt")),
+            Some(String::from("b.txt")),
+            None,
+            true,
+            wrkdir
+        )
+        .is_err());
+        assert!(make_transfer(Some(String::from("a.txt")), None, None, false, wrkdir).is_err());
+        assert!(make_transfer(None, Some(String::from("b.txt")), None, false, wrkdir).is_err());
+        assert!(make_transfer(Some(String::new()), None, None, true, wrkdir).is_err());
+        assert!(make_transfer(None, Some(String::new()), None, true, wrkdir).is_err());
+        assert!(make_transfer(
+            Some(String::from("a.txt")),
+            None,
+            Some(String::new()),
+            true,
+            wrkdir
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_support_copy_with_progress() {
+        let data: Vec<u8> = vec![0xcd; 150000];
+        let mut reader: &[u8] = data.as_slice();
+        let mut writer: Vec<u8> = Vec::new();
+        let mut progress: Vec<u8> = Vec::new();
+        assert_eq!(
+            copy_with_progress(
+                &mut reader,
+                &mut writer,
+                0,
+                150000,
+                "omar.bin",
+                SizeUnit::Si,
+                &mut progress
+            )
+            .unwrap(),
+            150000
+        );
+        assert_eq!(writer, data);
+        let progress: String = String::from_utf8(progress).unwrap();
+        assert!(progress.starts_with("\romar.bin: 0% (0 B/150.0 KB)"));
+        assert!(progress.contains("\romar.bin: 43% "));
+        assert!(progress.ends_with("\romar.bin: 100% (150.0 KB/150.0 KB)\n"));
+        // Resumed copy
+        let mut reader: &[u8] = &data[100000..];
+        let mut writer: Vec<u8> = Vec::new();
+        let mut progress: Vec<u8> = Vec::new();
+        assert_eq!(
+            copy_with_progress(
+                &mut reader,
+                &mut writer,
+                100000,
+                150000,
+                "omar.bin",
+                SizeUnit::Si,
+                &mut progress
+            )
+            .unwrap(),
+            50000
+        );
+        assert_eq!(writer.as_slice(), &data[100000..]);
+        let progress: String = String::from_utf8(progress).unwrap();
+        assert!(progress.starts_with("\romar.bin: 66% (100.0 KB/150.0 KB)"));
+        assert!(progress.ends_with("\romar.bin: 100% (150.0 KB/150.0 KB)\n"));
+        // Empty file
+        let mut reader: &[u8] = &[];
+        let mut progress: Vec<u8> = Vec::new();
+        assert_eq!(
+            copy_with_progress(
+                &mut reader,
+                &mut Vec::new(),
+                0,
+                0,
+                "empty",
+                SizeUnit::Si,
+                &mut progress
+            )
+            .unwrap(),
+            0
+        );
+        assert_eq!(
+            String::from_utf8(progress).unwrap().as_str(),
+            "\rempty: 100% (0 B/0 B)\n"
+        );
+        // Failing reader
+        let mut progress: Vec<u8> = Vec::new();
+        assert!(matches!(
+            copy_with_progress(
+                &mut FailingReader,
+                &mut Vec::new(),
+                0,
+                10,
+                "broken",
+                SizeUnit::Si,
+                &mut progress
+            ),
+            Err(CopyError::Read(_))
+        ));
+    }
+
+    #[test]
+    fn test_support_check_size() {
+        assert!(check_size(Path::new("/tmp/a.txt"), 1024, 1024).is_ok());
+        let err: TransferError = check_size(Path::new("/tmp/a.txt"), 1024, 512)
+            .err()
+            .unwrap();
+        assert_eq!(err.transient, true);
+        assert_eq!(
+            err.message.as_str(),
+            "Transfer of \"/tmp/a.txt\" is incomplete: 512 out of 1024 bytes have been transferred"
+        );
+    }
+
+    #[test]
+    fn test_support_select_startup() {
+        let bookmark = || StartupActivity::Bookmark(String::from("raspberry"));
+        // Default
+        assert_eq!(
+            select_startup(false, None, StartupActivity::default),
+            StartupActivity::Authentication
+        );
+        // Configured
+        assert_eq!(select_startup(false, None, bookmark), bookmark());
+        // CLI overrides configuration
+        assert_eq!(
+            select_startup(false, Some(StartupActivity::Authentication), bookmark),
+            StartupActivity::Authentication
+        );
+        assert_eq!(
+            select_startup(false, Some(bookmark()), || StartupActivity::Setup),
+            bookmark()
+        );
+        // Config switch overrides both
+        assert_eq!(
+            select_startup(true, Some(bookmark()), bookmark),
+            StartupActivity::Setup
+        );
+        assert_eq!(
+            select_startup(true, None, StartupActivity::default),
+            StartupActivity::Setup
+        );
+        // Configuration is not read when not needed
+        assert_eq!(
+            select_startup(false, Some(StartupActivity::Setup), || panic!(
+                "configuration read"
+            )),
+            StartupActivity::Setup
+        );
+    }
+
+    #[test]
+    fn test_support_confirm() {
+        let ask = |confirm: Confirm, answer: &str| -> (bool, String) {
+            let mut output: Vec<u8> = Vec::new();
+            let result: bool = confirm.confirm_with(
+                "Overwrite?",
+                &mut io::Cursor::new(answer.as_bytes()),
+                &mut output,
+            );
+            (result, String::from_utf8(output).unwrap())
+        };
+        assert_eq!(
+            ask(Confirm::Ask, "y\n"),
+            (true, String::from("Overwrite? [y/N] "))
+        );
+        assert_eq!(ask(Confirm::Ask, "YES\n").0, true);
+        assert_eq!(ask(Confirm::Ask, "n\n").0, false);
+        assert_eq!(ask(Confirm::Ask, "\n").0, false);
+        assert_eq!(ask(Confirm::Ask, "").0, false);
+        // Nothing is asked when confirmations are assumed
+        assert_eq!(ask(Confirm::AssumeYes, ""), (true, String::new()));
+    }
+
+    #[test]
+    fn test_support_write_theme_round_trip() {
+        let theme: Theme = Theme {
+            auth_address: Color::Rgb(220, 20, 60),
+            misc_keys: Color::Rgb(18, 52, 86),
+            transfer_local_explorer_background: Color::Reset,
+            transfer_status_sync_browsing: Color::LightMagenta,
+            ..Theme::default()
+        };
+        let file: NamedTempFile = NamedTempFile::new().unwrap();
+        assert!(write_theme(&theme, file.path()).is_ok());
+        // Re-import
+        assert_eq!(validate_theme(file.path()).ok().unwrap(), theme);
+    }
+
+    #[test]
+    fn test_support_write_theme_round_trip_formats() {
+        let theme: Theme = Theme {
+            auth_address: Color::Rgb(220, 20, 60),
+            misc_keys: Color::Rgb(18, 52, 86),
+            ..Theme::default()
+        };
+        for suffix in [".json", ".yaml", ".toml"].iter() {
+            let file: NamedTempFile = theme_file_ex(String::new(), suffix);
+            assert!(write_theme(&theme, file.path()).is_ok());
+            assert_eq!(validate_theme(file.path()).ok().unwrap(), theme);
+        }
+    }
+
+    #[test]
+    fn test_support_write_theme_exports_every_key() {
+        let file: NamedTempFile = NamedTempFile::new().unwrap();
+        assert!(write_theme(&Theme::default(), file.path()).is_ok());
+        let exported: String = fs::read_to_string(file.path()).unwrap();
+        THEME
+            .lines()
+            .filter_map(|x| x.split('=').next())
+            .map(|x| x.trim())
+            .for_each(|key| assert!(exported.contains(key), "{} not exported", key));
+    }
+
+    #[test]
+    fn test_support_write_theme_bad_path() {
+        assert!(write_theme(
+            &Theme::default(),
+            Path::new("/tmp/this/path/does/not/exist.toml")
+        )
+        .is_err());
+    }
+
+    const THEME: &str = r##"auth_address = "Yellow"
+auth_bookmarks = "LightGreen"
+auth_password = "LightBlue"
+auth_port = "LightCyan"
+auth_protocol = "LightGreen"
+auth_recents = "LightBlue"
+auth_username = "LightMagenta"
+misc_error_dialog = "Red"
+misc_input_dialog = "240,240,240"
+misc_keys = "Cyan"
+misc_quit_dialog = "Yellow"
+misc_save_dialog = "Cyan"
+misc_warn_dialog = "LightRed"
+transfer_local_explorer_background = "rgb(240, 240, 240)"
+transfer_local_explorer_foreground = "rgb(60, 60, 60)"
+transfer_local_explorer_highlighted = "Yellow"
+transfer_log_background = "255, 255, 255"
+transfer_log_window = "LightGreen"
+transfer_progress_bar_full = "forestgreen"
+transfer_progress_bar_partial = "Green"
+transfer_remote_explorer_background = "#f0f0f0"
+transfer_remote_explorer_foreground = "rgb(40, 40, 40)"
+transfer_remote_explorer_highlighted = "LightBlue"
+transfer_status_hidden = "LightBlue"
+transfer_status_sorting = "LightYellow"
+transfer_status_sync_browsing = "LightGreen"
+"##;
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::from(io::ErrorKind::ConnectionReset))
+        }
+    }
+
+    fn theme_file(content: String) -> NamedTempFile {
+        let mut file: NamedTempFile = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn theme_file_ex(content: String, suffix: &str) -> NamedTempFile {
+        let mut file: NamedTempFile = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    /// Express `THEME` as JSON
+    fn theme_json() -> String {
+        let entries: Vec<String> = THEME
+            .lines()
+            .filter_map(|x| x.split_once(" = "))
+            .map(|(key, value)| format!("  \"{}\": {}", key, value))
+            .collect();
+        format!("{{\n{}\n}}\n", entries.join(",\n"))
+    }
+
+    /// Express `THEME` as YAML
+    fn theme_yaml() -> String {
+        THEME
+            .lines()
+            .filter_map(|x| x.split_once(" = "))
+            .map(|(key, value)| format!("{}: {}\n", key, value))
+            .collect()
+    }
+}
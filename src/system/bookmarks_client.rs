@@ -27,14 +27,16 @@
  */
 // Crate
 #[cfg(feature = "with-keyring")]
+use super::environment::{get_keyring_service, get_profile};
+#[cfg(feature = "with-keyring")]
 use super::keys::keyringstorage::KeyringStorage;
 use super::keys::{filestorage::FileStorage, KeyStorage, KeyStorageError};
 // Local
 use crate::config::{
-    bookmarks::{Bookmark, UserHosts},
+    bookmarks::{Bookmark, BookmarkSorting, UserHosts},
     serialization::{deserialize, serialize, SerializerError, SerializerErrorKind},
 };
-use crate::filetransfer::FileTransferProtocol;
+use crate::filetransfer::{FileTransferParams, FileTransferProtocol};
 use crate::utils::crypto;
 use crate::utils::fmt::fmt_time;
 use crate::utils::random::random_alphanumeric_with_len;
@@ -43,7 +45,7 @@ use std::fs::OpenOptions;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// ## BookmarksClient
 ///
@@ -71,7 +73,7 @@ impl BookmarksClient {
         debug!("Setting up bookmarks client...");
         // Make a key storage (with-keyring)
         #[cfg(feature = "with-keyring")]
-        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
+        let (key_storage, service_id): (Box<dyn KeyStorage>, String) = {
             debug!("Setting up KeyStorage");
             let username: String = whoami::username();
             let storage: KeyringStorage = KeyringStorage::new(username.as_str());
@@ -83,26 +85,35 @@ impl BookmarksClient {
             match storage.is_supported() {
                 true => {
                     debug!("Using KeyringStorage");
-                    (Box::new(storage), app_name)
+                    // Each profile has its own key in the keyring
+                    let service_id: String =
+                        get_keyring_service(app_name, get_profile().as_deref());
+                    (Box::new(storage), service_id)
                 }
                 false => {
                     warn!("KeyringStorage is not supported; using FileStorage");
-                    (Box::new(FileStorage::new(storage_path)), "bookmarks")
+                    (
+                        Box::new(FileStorage::new(storage_path)),
+                        String::from("bookmarks"),
+                    )
                 }
             }
         };
         // Make a key storage (wno-keyring)
         #[cfg(not(feature = "with-keyring"))]
-        let (key_storage, service_id): (Box<dyn KeyStorage>, &str) = {
+        let (key_storage, service_id): (Box<dyn KeyStorage>, String) = {
             #[cfg(not(test))]
             let app_name: &str = "bookmarks";
             #[cfg(test)] // NOTE: when running test, add -test
             let app_name: &str = "bookmarks-test";
             debug!("Using FileStorage");
-            (Box::new(FileStorage::new(storage_path)), app_name)
+            (
+                Box::new(FileStorage::new(storage_path)),
+                app_name.to_string(),
+            )
         };
         // Load key
-        let key: String = match key_storage.get_key(service_id) {
+        let key: String = match key_storage.get_key(service_id.as_str()) {
             Ok(k) => {
                 debug!("Key loaded with success");
                 k
@@ -112,7 +123,7 @@ impl BookmarksClient {
                     // If no such key, generate key and set it into the storage
                     let key: String = Self::generate_key();
                     debug!("Key doesn't exist yet or could not be loaded; generated a new key");
-                    if let Err(e) = key_storage.set_key(service_id, key.as_str()) {
+                    if let Err(e) = key_storage.set_key(service_id.as_str(), key.as_str()) {
                         error!("Failed to set new key into storage: {}", e);
                         return Err(SerializerError::new_ex(
                             SerializerErrorKind::IoError,
@@ -150,6 +161,8 @@ impl BookmarksClient {
                 error!("Failed to load bookmarks: {}", err);
                 return Err(err);
             }
+            // Limit may have been lowered since recents were saved
+            client.prune_recents(recents_size);
         }
         info!("Bookmarks client initialized");
         // Load key
@@ -166,62 +179,109 @@ impl BookmarksClient {
     /// ### get_bookmark
     ///
     /// Get bookmark associated to key
-    pub fn get_bookmark(
-        &self,
-        key: &str,
-    ) -> Option<(String, u16, FileTransferProtocol, String, Option<String>)> {
+    pub fn get_bookmark(&self, key: &str) -> Option<FileTransferParams> {
         let entry: &Bookmark = self.hosts.bookmarks.get(key)?;
         debug!("Getting bookmark {}", key);
-        Some((
-            entry.address.clone(),
-            entry.port,
-            match FileTransferProtocol::from_str(entry.protocol.as_str()) {
-                Ok(proto) => proto,
+        let password: Option<String> = match &entry.password {
+            // Decrypted password if Some; if decryption fails return None
+            Some(pwd) => match self.decrypt_str(pwd.as_str()) {
+                Ok(decrypted_pwd) => Some(decrypted_pwd),
                 Err(err) => {
-                    error!(
-                        "Found invalid protocol in bookmarks: {}; defaulting to SFTP",
-                        err
-                    );
-                    FileTransferProtocol::Sftp // Default
+                    error!("Failed to decrypt password for bookmark: {}", err);
+                    None
                 }
             },
-            entry.username.clone(),
-            match &entry.password {
-                // Decrypted password if Some; if decryption fails return None
-                Some(pwd) => match self.decrypt_str(pwd.as_str()) {
-                    Ok(decrypted_pwd) => Some(decrypted_pwd),
-                    Err(err) => {
-                        error!("Failed to decrypt password for bookmark: {}", err);
-                        None
-                    }
-                },
-                None => None,
-            },
-        ))
+            None => None,
+        };
+        Some(Self::make_params(entry).password(password))
     }
 
-    /// ### add_recent
+    /// ### add_bookmark
     ///
-    /// Add a new recent to bookmarks
-    pub fn add_bookmark(
-        &mut self,
-        name: String,
-        addr: String,
-        port: u16,
-        protocol: FileTransferProtocol,
-        username: String,
-        password: Option<String>,
-    ) {
+    /// Add a new bookmark. The password is saved only if set in `params`
+    pub fn add_bookmark(&mut self, name: String, params: FileTransferParams) {
         if name.is_empty() {
             error!("Fatal error; bookmark name is empty");
             panic!("Bookmark name can't be empty");
         }
         // Make bookmark
-        info!("Added bookmark {} with address {}", name, addr);
-        let host: Bookmark = self.make_bookmark(addr, port, protocol, username, password);
+        info!("Added bookmark {} with address {}", name, params.address);
+        let mut host: Bookmark = self.make_bookmark(params);
+        // Keep the last time the bookmark has been used and its host key, if replaced
+        if let Some(prev) = self.hosts.bookmarks.get(&name) {
+            host.last_used = prev.last_used;
+            if host.host_key_fingerprint.is_none()
+                && prev.address == host.address
+                && prev.port == host.port
+            {
+                host.host_key_fingerprint = prev.host_key_fingerprint.clone();
+            }
+        }
         self.hosts.bookmarks.insert(name, host);
     }
 
+    /// ### mark_bookmarks_used
+    ///
+    /// Record now as the last time the bookmarks pointing to the host of `params` have been used
+    pub fn mark_bookmarks_used(&mut self, params: &FileTransferParams) {
+        let now: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or(0);
+        for (name, bookmark) in self
+            .hosts
+            .bookmarks
+            .iter_mut()
+            .filter(|(_, x)| Self::is_bookmark_for(x, params))
+        {
+            debug!("Bookmark {} has been used", name);
+            bookmark.last_used = Some(now);
+        }
+    }
+
+    /// ### pin_host_key
+    ///
+    /// Save `fingerprint` as the host key of the bookmarks pointing to the host of `params`,
+    /// which either have no host key yet or expected the one in `params`.
+    /// Returns whether any bookmark has changed
+    pub fn pin_host_key(&mut self, params: &FileTransferParams, fingerprint: &str) -> bool {
+        let mut changed: bool = false;
+        for (name, bookmark) in self.hosts.bookmarks.iter_mut().filter(|(_, x)| {
+            Self::is_bookmark_for(x, params)
+                && (x.host_key_fingerprint.is_none()
+                    || x.host_key_fingerprint == params.host_key_fingerprint)
+        }) {
+            if bookmark.host_key_fingerprint.as_deref() != Some(fingerprint) {
+                info!("Saving host key {} for bookmark {}", fingerprint, name);
+                bookmark.host_key_fingerprint = Some(fingerprint.to_string());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// ### sort_bookmarks
+    ///
+    /// Sort bookmarks `names` according to `sorting`. Ties are sorted by name (case insensitive)
+    pub fn sort_bookmarks(&self, names: &mut [String], sorting: BookmarkSorting) {
+        names.sort_by(|a, b| {
+            let (x, y) = (self.hosts.bookmarks.get(a), self.hosts.bookmarks.get(b));
+            let by_name = || a.to_lowercase().cmp(&b.to_lowercase());
+            match sorting {
+                BookmarkSorting::Name => by_name(),
+                BookmarkSorting::Protocol => x
+                    .map(|x| x.protocol.as_str())
+                    .cmp(&y.map(|y| y.protocol.as_str()))
+                    .then_with(by_name),
+                // Most recently used first; never used last
+                BookmarkSorting::Recent => y
+                    .and_then(|y| y.last_used)
+                    .cmp(&x.and_then(|x| x.last_used))
+                    .then_with(by_name),
+            }
+        });
+    }
+
     /// ### del_bookmark
     ///
     /// Delete entry from bookmarks
@@ -239,39 +299,23 @@ impl BookmarksClient {
     /// ### get_recent
     ///
     /// Get recent associated to key
-    pub fn get_recent(&self, key: &str) -> Option<(String, u16, FileTransferProtocol, String)> {
+    pub fn get_recent(&self, key: &str) -> Option<FileTransferParams> {
         // NOTE: password is not decrypted; recents will never have password
         info!("Getting bookmark {}", key);
         let entry: &Bookmark = self.hosts.recents.get(key)?;
-        Some((
-            entry.address.clone(),
-            entry.port,
-            match FileTransferProtocol::from_str(entry.protocol.as_str()) {
-                Ok(proto) => proto,
-                Err(err) => {
-                    error!(
-                        "Found invalid protocol in bookmarks: {}; defaulting to SFTP",
-                        err
-                    );
-                    FileTransferProtocol::Sftp // Default
-                }
-            },
-            entry.username.clone(),
-        ))
+        Some(Self::make_params(entry))
     }
 
     /// ### add_recent
     ///
-    /// Add a new recent to bookmarks
-    pub fn add_recent(
-        &mut self,
-        addr: String,
-        port: u16,
-        protocol: FileTransferProtocol,
-        username: String,
-    ) {
+    /// Add a new recent to bookmarks. The password and the host key are never saved
+    pub fn add_recent(&mut self, params: FileTransferParams) {
         // Make bookmark
-        let host: Bookmark = self.make_bookmark(addr, port, protocol, username, None);
+        let host: Bookmark = self.make_bookmark(
+            params
+                .password(None::<&str>)
+                .host_key_fingerprint(None::<&str>),
+        );
         // Check if duplicated
         for recent_host in self.hosts.recents.values() {
             if *recent_host == host {
@@ -280,30 +324,30 @@ impl BookmarksClient {
                 return;
             }
         }
-        // If hosts size is bigger than self.recents_size; pop last
-        if self.hosts.recents.len() >= self.recents_size {
-            // Get keys
-            let mut keys: Vec<String> = Vec::with_capacity(self.hosts.recents.len());
-            for key in self.hosts.recents.keys() {
-                keys.push(key.clone());
-            }
-            // Sort keys; NOTE: most recent is the last element
-            keys.sort();
-            // Delete keys starting from the last one
-            for key in keys.iter() {
-                let _ = self.hosts.recents.remove(key);
-                debug!("Removed recent bookmark {}", key);
-                // If length is < self.recents_size; break
-                if self.hosts.recents.len() < self.recents_size {
-                    break;
-                }
-            }
-        }
+        // Make room for the new recent, dropping the oldest ones
+        self.prune_recents(self.recents_size.saturating_sub(1));
         let name: String = fmt_time(SystemTime::now(), "ISO%Y%m%dT%H%M%S");
         info!("Saved recent host {} ({})", name, host.address);
         self.hosts.recents.insert(name, host);
     }
 
+    /// ### prune_recents
+    ///
+    /// Remove the oldest recents, until at most `max` are left
+    fn prune_recents(&mut self, max: usize) {
+        if self.hosts.recents.len() <= max {
+            return;
+        }
+        let mut keys: Vec<String> = self.hosts.recents.keys().cloned().collect();
+        // Sort keys; NOTE: most recent is the last element
+        keys.sort();
+        let excess: usize = keys.len() - max;
+        for key in keys.iter().take(excess) {
+            let _ = self.hosts.recents.remove(key);
+            debug!("Removed recent bookmark {}", key);
+        }
+    }
+
     /// ### del_recent
     ///
     /// Delete entry from recents
@@ -373,26 +417,60 @@ impl BookmarksClient {
         random_alphanumeric_with_len(256)
     }
 
+    /// ### is_bookmark_for
+    ///
+    /// Returns whether `bookmark` points to the same host, with the same protocol and username, of `params`
+    fn is_bookmark_for(bookmark: &Bookmark, params: &FileTransferParams) -> bool {
+        bookmark.address == params.address
+            && bookmark.port == params.port
+            && bookmark.protocol == params.protocol.to_string()
+            && bookmark.username == params.username.as_deref().unwrap_or_default()
+    }
+
     /// ### make_bookmark
     ///
-    /// Make bookmark from credentials
-    fn make_bookmark(
-        &self,
-        addr: String,
-        port: u16,
-        protocol: FileTransferProtocol,
-        username: String,
-        password: Option<String>,
-    ) -> Bookmark {
+    /// Make bookmark from connection parameters; the password, if any, is encrypted
+    fn make_bookmark(&self, params: FileTransferParams) -> Bookmark {
         Bookmark {
-            address: addr,
-            port,
-            username,
-            protocol: protocol.to_string(),
-            password: password.map(|p| self.encrypt_str(p.as_str())),
+            address: params.address,
+            port: params.port,
+            username: params.username.unwrap_or_default(),
+            protocol: params.protocol.to_string(),
+            password: params.password.map(|p| self.encrypt_str(p.as_str())),
+            ssh_key_path: params.ssh_key_path,
+            proxy_jump: params.proxy_jump,
+            host_key_fingerprint: params.host_key_fingerprint,
+            last_used: None,
         }
     }
 
+    /// ### make_params
+    ///
+    /// Make connection parameters from bookmark. The password is not set
+    fn make_params(entry: &Bookmark) -> FileTransferParams {
+        FileTransferParams::new(entry.address.as_str())
+            .port(entry.port)
+            .protocol(
+                match FileTransferProtocol::from_str(entry.protocol.as_str()) {
+                    Ok(proto) => proto,
+                    Err(err) => {
+                        error!(
+                            "Found invalid protocol in bookmarks: {}; defaulting to SFTP",
+                            err
+                        );
+                        FileTransferProtocol::Sftp // Default
+                    }
+                },
+            )
+            .username(match entry.username.is_empty() {
+                true => None,
+                false => Some(entry.username.as_str()),
+            })
+            .ssh_key_path(entry.ssh_key_path.as_ref())
+            .proxy_jump(entry.proxy_jump.as_ref())
+            .host_key_fingerprint(entry.host_key_fingerprint.as_ref())
+    }
+
     /// ### encrypt_str
     ///
     /// Encrypt provided string using AES-128. Encrypted buffer is then converted to BASE64
@@ -474,17 +552,17 @@ mod tests {
         // Add some bookmarks
         client.add_bookmark(
             String::from("raspberry"),
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
-            Some(String::from("mypassword")),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi"))
+                .password(Some("mypassword")),
         );
         client.add_recent(
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         let recent_key: String = String::from(client.iter_recents().next().unwrap());
         assert!(client.write_bookmarks().is_ok());
@@ -494,19 +572,17 @@ mod tests {
             BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
         // Verify it loaded parameters correctly
         assert_eq!(client.key, key);
-        let bookmark: (String, u16, FileTransferProtocol, String, Option<String>) =
-            client.get_bookmark(&String::from("raspberry")).unwrap();
-        assert_eq!(bookmark.0, String::from("192.168.1.31"));
-        assert_eq!(bookmark.1, 22);
-        assert_eq!(bookmark.2, FileTransferProtocol::Sftp);
-        assert_eq!(bookmark.3, String::from("pi"));
-        assert_eq!(*bookmark.4.as_ref().unwrap(), String::from("mypassword"));
-        let bookmark: (String, u16, FileTransferProtocol, String) =
-            client.get_recent(&recent_key).unwrap();
-        assert_eq!(bookmark.0, String::from("192.168.1.31"));
-        assert_eq!(bookmark.1, 22);
-        assert_eq!(bookmark.2, FileTransferProtocol::Sftp);
-        assert_eq!(bookmark.3, String::from("pi"));
+        let bookmark: FileTransferParams = client.get_bookmark(&String::from("raspberry")).unwrap();
+        assert_eq!(bookmark.address, String::from("192.168.1.31"));
+        assert_eq!(bookmark.port, 22);
+        assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
+        assert_eq!(bookmark.username.as_deref().unwrap(), "pi");
+        assert_eq!(bookmark.password.as_deref().unwrap(), "mypassword");
+        let bookmark: FileTransferParams = client.get_recent(&recent_key).unwrap();
+        assert_eq!(bookmark.address, String::from("192.168.1.31"));
+        assert_eq!(bookmark.port, 22);
+        assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
+        assert_eq!(bookmark.username.as_deref().unwrap(), "pi");
     }
 
     #[test]
@@ -520,30 +596,29 @@ mod tests {
         // Add bookmark
         client.add_bookmark(
             String::from("raspberry"),
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
-            Some(String::from("mypassword")),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi"))
+                .password(Some("mypassword")),
         );
         client.add_bookmark(
             String::from("raspberry2"),
-            String::from("192.168.1.32"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
-            Some(String::from("mypassword2")),
+            FileTransferParams::new("192.168.1.32")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi"))
+                .password(Some("mypassword2")),
         );
         // Iter
         assert_eq!(client.iter_bookmarks().count(), 2);
         // Get bookmark
-        let bookmark: (String, u16, FileTransferProtocol, String, Option<String>) =
-            client.get_bookmark(&String::from("raspberry")).unwrap();
-        assert_eq!(bookmark.0, String::from("192.168.1.31"));
-        assert_eq!(bookmark.1, 22);
-        assert_eq!(bookmark.2, FileTransferProtocol::Sftp);
-        assert_eq!(bookmark.3, String::from("pi"));
-        assert_eq!(*bookmark.4.as_ref().unwrap(), String::from("mypassword"));
+        let bookmark: FileTransferParams = client.get_bookmark(&String::from("raspberry")).unwrap();
+        assert_eq!(bookmark.address, String::from("192.168.1.31"));
+        assert_eq!(bookmark.port, 22);
+        assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
+        assert_eq!(bookmark.username.as_deref().unwrap(), "pi");
+        assert_eq!(bookmark.password.as_deref().unwrap(), "mypassword");
         // Write bookmarks
         assert!(client.write_bookmarks().is_ok());
         // Delete bookmark
@@ -554,6 +629,201 @@ mod tests {
         assert!(client.write_bookmarks().is_ok());
     }
 
+    #[test]
+    fn test_system_bookmarks_connection_options() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        let params: FileTransferParams = FileTransferParams::new("10.0.0.4")
+            .username(Some("pi"))
+            .ssh_key_path(Some(Path::new("/home/pi/.ssh/id_ed25519")))
+            .proxy_jump(Some("omar@bastion:2222"));
+        client.add_bookmark(String::from("internal"), params.clone());
+        client.add_recent(params);
+        let recent_key: String = String::from(client.iter_recents().next().unwrap());
+        assert!(client.write_bookmarks().is_ok());
+        // Options are kept after reloading
+        let client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        for params in [
+            client.get_bookmark("internal").unwrap(),
+            client.get_recent(recent_key.as_str()).unwrap(),
+        ] {
+            assert_eq!(params.address.as_str(), "10.0.0.4");
+            assert_eq!(
+                params.ssh_key_path.as_deref().unwrap(),
+                Path::new("/home/pi/.ssh/id_ed25519")
+            );
+            assert_eq!(params.proxy_jump.as_deref().unwrap(), "omar@bastion:2222");
+        }
+    }
+
+    #[test]
+    fn test_system_bookmarks_sort_bookmarks() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        for (name, protocol, last_used) in [
+            ("raspberry", FileTransferProtocol::Sftp, Some(300)),
+            ("aws", FileTransferProtocol::Ftp(true), None),
+            ("Backup", FileTransferProtocol::Scp, Some(100)),
+            ("nas", FileTransferProtocol::Ftp(false), Some(200)),
+            ("work", FileTransferProtocol::Sftp, None),
+        ] {
+            client.add_bookmark(
+                String::from(name),
+                FileTransferParams::new("10.0.0.1").protocol(protocol),
+            );
+            client.hosts.bookmarks.get_mut(name).unwrap().last_used = last_used;
+        }
+        let mut names: Vec<String> = client.iter_bookmarks().cloned().collect();
+        // By name
+        client.sort_bookmarks(&mut names, BookmarkSorting::Name);
+        assert_eq!(names, vec!["aws", "Backup", "nas", "raspberry", "work"]);
+        // By protocol
+        client.sort_bookmarks(&mut names, BookmarkSorting::Protocol);
+        assert_eq!(names, vec!["nas", "aws", "Backup", "raspberry", "work"]);
+        // Most recently used
+        client.sort_bookmarks(&mut names, BookmarkSorting::Recent);
+        assert_eq!(names, vec!["raspberry", "nas", "Backup", "aws", "work"]);
+    }
+
+    #[test]
+    fn test_system_bookmarks_mark_bookmarks_used() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        let params: FileTransferParams = FileTransferParams::new("10.0.0.1").username(Some("pi"));
+        client.add_bookmark(String::from("raspberry"), params.clone());
+        client.add_bookmark(
+            String::from("other"),
+            FileTransferParams::new("10.0.0.2").username(Some("pi")),
+        );
+        assert!(client
+            .hosts
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .last_used
+            .is_none());
+        client.mark_bookmarks_used(&params);
+        assert!(client
+            .hosts
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .last_used
+            .is_some());
+        assert!(client
+            .hosts
+            .bookmarks
+            .get("other")
+            .unwrap()
+            .last_used
+            .is_none());
+        // Last use is kept when replacing the bookmark
+        client.add_bookmark(String::from("raspberry"), params.port(2222));
+        assert!(client
+            .hosts
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .last_used
+            .is_some());
+        // And after reloading
+        assert!(client.write_bookmarks().is_ok());
+        let client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        assert!(client
+            .hosts
+            .bookmarks
+            .get("raspberry")
+            .unwrap()
+            .last_used
+            .is_some());
+    }
+
+    #[test]
+    fn test_system_bookmarks_pin_host_key() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        let params: FileTransferParams = FileTransferParams::new("10.0.0.1").username(Some("pi"));
+        client.add_bookmark(String::from("raspberry"), params.clone());
+        client.add_bookmark(
+            String::from("other"),
+            FileTransferParams::new("10.0.0.2").username(Some("pi")),
+        );
+        // First connection populates the host key
+        assert!(client
+            .get_bookmark("raspberry")
+            .unwrap()
+            .host_key_fingerprint
+            .is_none());
+        assert!(client.pin_host_key(&params, "SHA256:first"));
+        let params: FileTransferParams = client.get_bookmark("raspberry").unwrap();
+        assert_eq!(
+            params.host_key_fingerprint.as_deref().unwrap(),
+            "SHA256:first"
+        );
+        assert!(client
+            .get_bookmark("other")
+            .unwrap()
+            .host_key_fingerprint
+            .is_none());
+        // Same key; nothing changes
+        assert!(!client.pin_host_key(&params, "SHA256:first"));
+        // Bookmarks expecting another key are left untouched
+        assert!(!client.pin_host_key(
+            &params.clone().host_key_fingerprint(Some("SHA256:other")),
+            "SHA256:second"
+        ));
+        // The key trusted in place of the expected one replaces it
+        assert!(client.pin_host_key(&params, "SHA256:second"));
+        assert_eq!(
+            client
+                .get_bookmark("raspberry")
+                .unwrap()
+                .host_key_fingerprint
+                .as_deref()
+                .unwrap(),
+            "SHA256:second"
+        );
+        // Host key is kept when replacing the bookmark, unless the host changes
+        client.add_bookmark(
+            String::from("raspberry"),
+            FileTransferParams::new("10.0.0.1").username(Some("root")),
+        );
+        assert!(client
+            .get_bookmark("raspberry")
+            .unwrap()
+            .host_key_fingerprint
+            .is_some());
+        client.add_bookmark(
+            String::from("raspberry"),
+            FileTransferParams::new("10.0.0.3").username(Some("pi")),
+        );
+        assert!(client
+            .get_bookmark("raspberry")
+            .unwrap()
+            .host_key_fingerprint
+            .is_none());
+        // Recents never have the host key
+        client.add_recent(
+            FileTransferParams::new("10.0.0.1").host_key_fingerprint(Some("SHA256:first")),
+        );
+        let recent_key: String = String::from(client.iter_recents().next().unwrap());
+        assert!(client
+            .get_recent(recent_key.as_str())
+            .unwrap()
+            .host_key_fingerprint
+            .is_none());
+    }
+
     #[test]
     #[should_panic]
 
@@ -566,11 +836,11 @@ mod tests {
         // Add bookmark
         client.add_bookmark(
             String::from(""),
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
-            Some(String::from("mypassword")),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi"))
+                .password(Some("mypassword")),
         );
     }
 
@@ -584,21 +854,20 @@ mod tests {
             BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
         // Add bookmark
         client.add_recent(
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         // Iter
         assert_eq!(client.iter_recents().count(), 1);
         let key: String = String::from(client.iter_recents().next().unwrap());
         // Get bookmark
-        let bookmark: (String, u16, FileTransferProtocol, String) =
-            client.get_recent(&key).unwrap();
-        assert_eq!(bookmark.0, String::from("192.168.1.31"));
-        assert_eq!(bookmark.1, 22);
-        assert_eq!(bookmark.2, FileTransferProtocol::Sftp);
-        assert_eq!(bookmark.3, String::from("pi"));
+        let bookmark: FileTransferParams = client.get_recent(&key).unwrap();
+        assert_eq!(bookmark.address, String::from("192.168.1.31"));
+        assert_eq!(bookmark.port, 22);
+        assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
+        assert_eq!(bookmark.username.as_deref().unwrap(), "pi");
         // Write bookmarks
         assert!(client.write_bookmarks().is_ok());
         // Delete bookmark
@@ -619,16 +888,16 @@ mod tests {
             BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
         // Add bookmark
         client.add_recent(
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         client.add_recent(
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         // There should be only one recent
         assert_eq!(client.iter_recents().count(), 1);
@@ -645,26 +914,26 @@ mod tests {
         // Add recent, wait 1 second for each one (cause the name depends on time)
         // 1
         client.add_recent(
-            String::from("192.168.1.1"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.1")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         sleep(Duration::from_secs(1));
         // 2
         client.add_recent(
-            String::from("192.168.1.2"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.2")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         sleep(Duration::from_secs(1));
         // 3
         client.add_recent(
-            String::from("192.168.1.3"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
+            FileTransferParams::new("192.168.1.3")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi")),
         );
         // Limit is 2
         assert_eq!(client.iter_recents().count(), 2);
@@ -681,6 +950,37 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_system_bookmarks_prune_recents() {
+        let tmp_dir: tempfile::TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 16).unwrap();
+        for i in 1..=4 {
+            let recent: Bookmark = client.make_bookmark(
+                FileTransferParams::new(format!("192.168.1.{}", i)).username(Some("pi")),
+            );
+            client
+                .hosts
+                .recents
+                .insert(format!("ISO2021010{}T000000", i), recent);
+        }
+        // Nothing to prune
+        client.prune_recents(4);
+        assert_eq!(client.iter_recents().count(), 4);
+        // Oldest are removed first
+        client.prune_recents(3);
+        assert_eq!(client.iter_recents().count(), 3);
+        assert!(!client.hosts.recents.contains_key("ISO20210101T000000"));
+        assert!(client.write_bookmarks().is_ok());
+        // Lowering the limit prunes recents on load
+        let client: BookmarksClient =
+            BookmarksClient::new(cfg_path.as_path(), key_path.as_path(), 2).unwrap();
+        let mut recents: Vec<&String> = client.iter_recents().collect();
+        recents.sort();
+        assert_eq!(recents, vec!["ISO20210103T000000", "ISO20210104T000000"]);
+    }
+
     #[test]
     #[should_panic]
     fn test_system_bookmarks_add_bookmark_empty() {
@@ -692,11 +992,11 @@ mod tests {
         // Add bookmark
         client.add_bookmark(
             String::from(""),
-            String::from("192.168.1.31"),
-            22,
-            FileTransferProtocol::Sftp,
-            String::from("pi"),
-            Some(String::from("mypassword")),
+            FileTransferParams::new("192.168.1.31")
+                .port(22)
+                .protocol(FileTransferProtocol::Sftp)
+                .username(Some("pi"))
+                .password(Some("mypassword")),
         );
     }
 
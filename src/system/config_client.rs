@@ -27,21 +27,45 @@
  */
 // Locals
 use crate::config::{
-    params::UserConfig,
+    bookmarks::BookmarkSorting,
+    keybindings::KeyBindings,
+    params::{StartupActivity, UserConfig},
     serialization::{deserialize, serialize, SerializerError, SerializerErrorKind},
 };
 use crate::filetransfer::FileTransferProtocol;
 use crate::fs::explorer::GroupDirs;
+use crate::utils::file::expand_path;
+use crate::utils::fmt::SizeUnit;
 // Ext
+use std::env;
 use std::fs::{create_dir, remove_file, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
+use std::time::Duration;
+
+#[cfg(windows)]
+const DEFAULT_TEXT_EDITOR: &str = "notepad.exe";
+#[cfg(not(windows))]
+const DEFAULT_TEXT_EDITOR: &str = "nano";
 
 // Types
 pub type SshHost = (String, String, PathBuf); // 0: host, 1: username, 2: RSA key path
 
+/// Default amount of directory levels scanned when searching for files
+pub const DEFAULT_FIND_MAX_DEPTH: usize = 16;
+/// Default amount of attempts made to transfer a file, when it fails because of transient errors
+pub const DEFAULT_TRANSFER_MAX_ATTEMPTS: usize = 3;
+/// Default amount of recent connections kept
+pub const DEFAULT_MAX_RECENTS: usize = 16;
+/// Default size limit of the files which can be previewed
+pub const DEFAULT_MAX_PREVIEW_BYTES: u64 = 5242880; // 5MiB
+/// Default size limit of the files which can be opened in the text editor
+pub const DEFAULT_MAX_EDIT_BYTES: u64 = 52428800; // 50MiB
+/// Default amount of seconds a transfer must last before the terminal bell is rung on completion
+pub const DEFAULT_TRANSFER_BELL_THRESHOLD: u64 = 10;
+
 /// ## ConfigClient
 ///
 /// ConfigClient provides a high level API to communicate with the termscp configuration
@@ -134,6 +158,37 @@ impl ConfigClient {
         self.config.user_interface.text_editor = path;
     }
 
+    /// ### resolve_text_editor
+    ///
+    /// Get the text editor to launch: `~` and environment variables in the configured value are expanded.
+    /// If no editor is configured, `$EDITOR`, `$VISUAL` or the platform default editor is used
+    pub fn resolve_text_editor(&self) -> PathBuf {
+        Self::resolve_editor(
+            self.config.user_interface.text_editor.as_path(),
+            dirs::home_dir().as_deref(),
+            |name| env::var(name).ok(),
+        )
+    }
+
+    /// ### resolve_editor
+    ///
+    /// Resolve the editor to launch from the `configured` value, looking environment variables up with `lookup`
+    fn resolve_editor<F>(configured: &Path, home: Option<&Path>, lookup: F) -> PathBuf
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let configured: String = configured.to_string_lossy().trim().to_string();
+        if !configured.is_empty() {
+            return expand_path(configured.as_str(), home, &lookup);
+        }
+        ["EDITOR", "VISUAL"]
+            .iter()
+            .filter_map(|name| lookup(name))
+            .find(|editor| !editor.trim().is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_TEXT_EDITOR))
+    }
+
     // Default protocol
 
     /// ### get_default_protocol
@@ -237,6 +292,354 @@ impl ConfigClient {
         };
     }
 
+    /// ### get_max_parallel_transfers
+    ///
+    /// Get the maximum amount of local copies which can run at the same time (at least 1).
+    /// Uploads and downloads don't use it: they always run one at a time on the remote session
+    pub fn get_max_parallel_transfers(&self) -> usize {
+        self.config
+            .user_interface
+            .max_parallel_transfers
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// ### set_max_parallel_transfers
+    ///
+    /// Set the maximum amount of local copies which can run at the same time
+    pub fn set_max_parallel_transfers(&mut self, value: usize) {
+        self.config.user_interface.max_parallel_transfers = Some(value.max(1));
+    }
+
+    /// ### get_resume_transfers
+    ///
+    /// Get value of `resume_transfers`
+    pub fn get_resume_transfers(&self) -> bool {
+        self.config.user_interface.resume_transfers.unwrap_or(false)
+    }
+
+    /// ### set_resume_transfers
+    ///
+    /// Set new value for `resume_transfers`
+    pub fn set_resume_transfers(&mut self, value: bool) {
+        self.config.user_interface.resume_transfers = Some(value);
+    }
+
+    /// ### get_prompt_on_file_replace
+    ///
+    /// Get value of `prompt_on_file_replace`; enabled if not set, so existing configurations are prompted too
+    pub fn get_prompt_on_file_replace(&self) -> bool {
+        self.config
+            .user_interface
+            .prompt_on_file_replace
+            .unwrap_or(true)
+    }
+
+    /// ### set_prompt_on_file_replace
+    ///
+    /// Set new value for `prompt_on_file_replace`
+    pub fn set_prompt_on_file_replace(&mut self, value: bool) {
+        self.config.user_interface.prompt_on_file_replace = Some(value);
+    }
+
+    /// ### get_find_max_depth
+    ///
+    /// Get value of `find_max_depth`; this is the amount of directory levels scanned when searching for files
+    pub fn get_find_max_depth(&self) -> usize {
+        self.config
+            .user_interface
+            .find_max_depth
+            .unwrap_or(DEFAULT_FIND_MAX_DEPTH)
+            .max(1)
+    }
+
+    /// ### get_last_local_wrkdir
+    ///
+    /// Get the local working directory termscp was at when the last session ended
+    pub fn get_last_local_wrkdir(&self) -> Option<PathBuf> {
+        self.config.user_interface.last_local_wrkdir.clone()
+    }
+
+    /// ### set_last_local_wrkdir
+    ///
+    /// Set the local working directory to reuse on next launch
+    pub fn set_last_local_wrkdir(&mut self, dir: PathBuf) {
+        self.config.user_interface.last_local_wrkdir = Some(dir);
+    }
+
+    /// ### get_known_hosts
+    ///
+    /// Get the configured path of the known hosts file
+    pub fn get_known_hosts(&self) -> Option<PathBuf> {
+        self.config.user_interface.known_hosts.clone()
+    }
+
+    /// ### get_ssh_config
+    ///
+    /// Get the configured path of the OpenSSH client configuration, as written in the configuration
+    pub fn get_ssh_config(&self) -> Option<&str> {
+        self.config.user_interface.ssh_config.as_deref()
+    }
+
+    /// ### get_strict_host_key_checking
+    ///
+    /// Get whether unknown and changed host keys must be rejected without prompting
+    pub fn get_strict_host_key_checking(&self) -> bool {
+        self.config
+            .user_interface
+            .strict_host_key_checking
+            .unwrap_or(false)
+    }
+
+    /// ### get_ssh_compression
+    ///
+    /// Get whether SSH compression must be enabled for SCP/SFTP sessions;
+    /// `None` if it's not configured, so the value in the ssh config is used
+    pub fn get_ssh_compression(&self) -> Option<bool> {
+        self.config.user_interface.ssh_compression
+    }
+
+    /// ### set_ssh_compression
+    ///
+    /// Set whether SSH compression must be enabled for SCP/SFTP sessions; `None` unsets it
+    pub fn set_ssh_compression(&mut self, value: Option<bool>) {
+        self.config.user_interface.ssh_compression = value;
+    }
+
+    /// ### get_watch_local_dir
+    ///
+    /// Get whether the local explorer must be reloaded when the working directory changes on disk
+    pub fn get_watch_local_dir(&self) -> bool {
+        self.config.user_interface.watch_local_dir.unwrap_or(false)
+    }
+
+    /// ### set_watch_local_dir
+    ///
+    /// Set whether the local explorer must be reloaded when the working directory changes on disk
+    #[allow(dead_code)]
+    pub fn set_watch_local_dir(&mut self, value: bool) {
+        self.config.user_interface.watch_local_dir = Some(value);
+    }
+
+    /// ### get_create_missing_wrkdir
+    ///
+    /// Get whether the remote working directory requested on connect must be created if it doesn't exist
+    pub fn get_create_missing_wrkdir(&self) -> bool {
+        self.config
+            .user_interface
+            .create_missing_wrkdir
+            .unwrap_or(false)
+    }
+
+    /// ### set_create_missing_wrkdir
+    ///
+    /// Set whether the remote working directory requested on connect must be created if it doesn't exist
+    #[allow(dead_code)]
+    pub fn set_create_missing_wrkdir(&mut self, value: bool) {
+        self.config.user_interface.create_missing_wrkdir = Some(value);
+    }
+
+    /// ### get_read_only
+    ///
+    /// Get whether mutating actions are disabled in the file explorer
+    pub fn get_read_only(&self) -> bool {
+        self.config.user_interface.read_only.unwrap_or(false)
+    }
+
+    /// ### set_read_only
+    ///
+    /// Set whether mutating actions are disabled in the file explorer
+    #[allow(dead_code)]
+    pub fn set_read_only(&mut self, value: bool) {
+        self.config.user_interface.read_only = Some(value);
+    }
+
+    /// ### get_lock_dirs_first
+    ///
+    /// Get whether directories must always come before files in the explorers, whatever the sorting
+    pub fn get_lock_dirs_first(&self) -> bool {
+        self.config.user_interface.lock_dirs_first.unwrap_or(false)
+    }
+
+    /// ### set_lock_dirs_first
+    ///
+    /// Set whether directories must always come before files in the explorers, whatever the sorting
+    #[allow(dead_code)]
+    pub fn set_lock_dirs_first(&mut self, value: bool) {
+        self.config.user_interface.lock_dirs_first = Some(value);
+    }
+
+    /// ### get_transfer_bell
+    ///
+    /// Get whether the terminal bell must be rung when a transfer completes
+    pub fn get_transfer_bell(&self) -> bool {
+        self.config.user_interface.transfer_bell.unwrap_or(false)
+    }
+
+    /// ### set_transfer_bell
+    ///
+    /// Set whether the terminal bell must be rung when a transfer completes
+    #[allow(dead_code)]
+    pub fn set_transfer_bell(&mut self, value: bool) {
+        self.config.user_interface.transfer_bell = Some(value);
+    }
+
+    /// ### get_transfer_bell_threshold
+    ///
+    /// Get the minimum duration a transfer must last for the bell to be rung on completion
+    pub fn get_transfer_bell_threshold(&self) -> Duration {
+        Duration::from_secs(
+            self.config
+                .user_interface
+                .transfer_bell_threshold
+                .unwrap_or(DEFAULT_TRANSFER_BELL_THRESHOLD),
+        )
+    }
+
+    /// ### set_transfer_bell_threshold
+    ///
+    /// Set the minimum amount of seconds a transfer must last for the bell to be rung on completion
+    #[allow(dead_code)]
+    pub fn set_transfer_bell_threshold(&mut self, secs: u64) {
+        self.config.user_interface.transfer_bell_threshold = Some(secs);
+    }
+
+    /// ### get_show_banner
+    ///
+    /// Get whether the banner sent by the server must be displayed after connecting
+    pub fn get_show_banner(&self) -> bool {
+        self.config.user_interface.show_banner.unwrap_or(false)
+    }
+
+    /// ### set_show_banner
+    ///
+    /// Set whether the banner sent by the server must be displayed after connecting
+    #[allow(dead_code)]
+    pub fn set_show_banner(&mut self, value: bool) {
+        self.config.user_interface.show_banner = Some(value);
+    }
+
+    /// ### get_confirm_on_quit
+    ///
+    /// Get whether a confirmation must be asked before quitting
+    pub fn get_confirm_on_quit(&self) -> bool {
+        self.config.user_interface.confirm_on_quit.unwrap_or(true)
+    }
+
+    /// ### set_confirm_on_quit
+    ///
+    /// Set whether a confirmation must be asked before quitting
+    pub fn set_confirm_on_quit(&mut self, value: bool) {
+        self.config.user_interface.confirm_on_quit = Some(value);
+    }
+
+    /// ### get_size_unit
+    ///
+    /// Get the unit used to display sizes; SI if not set or invalid
+    pub fn get_size_unit(&self) -> SizeUnit {
+        self.config
+            .user_interface
+            .size_unit
+            .as_deref()
+            .and_then(|x| SizeUnit::from_str(x).ok())
+            .unwrap_or_default()
+    }
+
+    /// ### get_bookmarks_sorting
+    ///
+    /// Get how the bookmarks list is sorted; by name if not set or invalid
+    pub fn get_bookmarks_sorting(&self) -> BookmarkSorting {
+        self.config
+            .user_interface
+            .bookmarks_sorting
+            .as_deref()
+            .and_then(|x| BookmarkSorting::from_str(x).ok())
+            .unwrap_or_default()
+    }
+
+    /// ### get_transfer_max_attempts
+    ///
+    /// Get the maximum amount of attempts made to transfer a file, when it fails because of transient errors (at least 1)
+    pub fn get_transfer_max_attempts(&self) -> usize {
+        self.config
+            .user_interface
+            .transfer_max_attempts
+            .unwrap_or(DEFAULT_TRANSFER_MAX_ATTEMPTS)
+            .max(1)
+    }
+
+    /// ### get_max_preview_bytes
+    ///
+    /// Get the size limit of the files which can be previewed without asking the user
+    pub fn get_max_preview_bytes(&self) -> u64 {
+        self.config
+            .user_interface
+            .max_preview_bytes
+            .unwrap_or(DEFAULT_MAX_PREVIEW_BYTES)
+    }
+
+    /// ### get_max_edit_bytes
+    ///
+    /// Get the size limit of the files which can be opened in the text editor without asking the user
+    pub fn get_max_edit_bytes(&self) -> u64 {
+        self.config
+            .user_interface
+            .max_edit_bytes
+            .unwrap_or(DEFAULT_MAX_EDIT_BYTES)
+    }
+
+    /// ### get_startup
+    ///
+    /// Get what termscp opens when no remote is provided; the authentication form if not set or invalid
+    pub fn get_startup(&self) -> StartupActivity {
+        self.config
+            .user_interface
+            .startup
+            .as_deref()
+            .and_then(|x| StartupActivity::from_str(x).ok())
+            .unwrap_or_default()
+    }
+
+    /// ### get_max_recents
+    ///
+    /// Get the maximum amount of recent connections kept (at least 1)
+    pub fn get_max_recents(&self) -> usize {
+        self.config
+            .user_interface
+            .max_recents
+            .unwrap_or(DEFAULT_MAX_RECENTS)
+            .max(1)
+    }
+
+    /// ### set_max_recents
+    ///
+    /// Set the maximum amount of recent connections kept
+    pub fn set_max_recents(&mut self, value: usize) {
+        self.config.user_interface.max_recents = Some(value.max(1));
+    }
+
+    /// ### get_hidden_patterns
+    ///
+    /// Get the patterns of the file names to hide, besides dotfiles, when hidden files are not shown
+    pub fn get_hidden_patterns(&self) -> Vec<String> {
+        self.config
+            .user_interface
+            .hidden_patterns
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// ### get_keybindings
+    ///
+    /// Get the key bindings for the file explorer actions.
+    /// Returns error if the configured key bindings are invalid
+    pub fn get_keybindings(&self) -> Result<KeyBindings, String> {
+        match self.config.keybindings.as_ref() {
+            None => Ok(KeyBindings::default()),
+            Some(mapping) => KeyBindings::new(mapping),
+        }
+    }
+
     // SSH Keys
 
     /// ### save_ssh_key
@@ -442,10 +845,11 @@ impl ConfigClient {
 mod tests {
 
     use super::*;
-    use crate::config::UserConfig;
+    use crate::config::{keybindings::KeyAction, UserConfig};
     use crate::utils::random::random_alphanumeric_with_len;
 
     use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
     use std::io::Read;
     use tempfile::TempDir;
 
@@ -539,6 +943,55 @@ mod tests {
             .unwrap();
         client.set_text_editor(PathBuf::from("mcedit"));
         assert_eq!(client.get_text_editor(), PathBuf::from("mcedit"));
+        assert_eq!(client.resolve_text_editor(), PathBuf::from("mcedit"));
+    }
+
+    #[test]
+    fn test_system_config_resolve_text_editor() {
+        let home: &Path = Path::new("/home/omar");
+        let env = |name: &str| match name {
+            "EDITOR" => Some(String::from("vim")),
+            "VISUAL" => Some(String::from("code")),
+            "EDITORS" => Some(String::from("/opt/editors")),
+            _ => None,
+        };
+        // Configured value is expanded
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new("~/bin/editor"), Some(home), env),
+            PathBuf::from("/home/omar/bin/editor")
+        );
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new("$EDITORS/hx"), Some(home), env),
+            PathBuf::from("/opt/editors/hx")
+        );
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new("$EDITOR"), Some(home), env),
+            PathBuf::from("vim")
+        );
+        // Empty: fallback to $EDITOR
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new(""), Some(home), env),
+            PathBuf::from("vim")
+        );
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new("  "), Some(home), env),
+            PathBuf::from("vim")
+        );
+        // Then $VISUAL
+        let env = |name: &str| match name {
+            "EDITOR" => Some(String::new()),
+            "VISUAL" => Some(String::from("code")),
+            _ => None,
+        };
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new(""), Some(home), env),
+            PathBuf::from("code")
+        );
+        // Then platform default
+        assert_eq!(
+            ConfigClient::resolve_editor(Path::new(""), Some(home), |_| None),
+            PathBuf::from(DEFAULT_TEXT_EDITOR)
+        );
     }
 
     #[test]
@@ -626,6 +1079,365 @@ mod tests {
         assert_eq!(client.get_remote_file_fmt(), None);
     }
 
+    #[test]
+    fn test_system_config_max_parallel_transfers() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_max_parallel_transfers(), 1);
+        client.set_max_parallel_transfers(4);
+        assert_eq!(client.get_max_parallel_transfers(), 4);
+        // 0 is not allowed
+        client.set_max_parallel_transfers(0);
+        assert_eq!(client.get_max_parallel_transfers(), 1);
+    }
+
+    #[test]
+    fn test_system_config_resume_transfers() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_resume_transfers(), false);
+        client.set_resume_transfers(true);
+        assert_eq!(client.get_resume_transfers(), true);
+    }
+
+    #[test]
+    fn test_system_config_prompt_on_file_replace() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_prompt_on_file_replace(), true);
+        client.set_prompt_on_file_replace(false);
+        assert_eq!(client.get_prompt_on_file_replace(), false);
+    }
+
+    #[test]
+    fn test_system_config_find_max_depth() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_find_max_depth(), DEFAULT_FIND_MAX_DEPTH);
+        client.config.user_interface.find_max_depth = Some(4);
+        assert_eq!(client.get_find_max_depth(), 4);
+        client.config.user_interface.find_max_depth = Some(0);
+        assert_eq!(client.get_find_max_depth(), 1);
+    }
+
+    #[test]
+    fn test_system_config_last_local_wrkdir() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_last_local_wrkdir().is_none());
+        client.set_last_local_wrkdir(PathBuf::from("/home/omar"));
+        assert_eq!(
+            client.get_last_local_wrkdir(),
+            Some(PathBuf::from("/home/omar"))
+        );
+    }
+
+    #[test]
+    fn test_system_config_known_hosts() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_known_hosts().is_none());
+        client.config.user_interface.known_hosts =
+            Some(PathBuf::from("/home/omar/.ssh/known_hosts"));
+        assert_eq!(
+            client.get_known_hosts(),
+            Some(PathBuf::from("/home/omar/.ssh/known_hosts"))
+        );
+        assert_eq!(client.get_strict_host_key_checking(), false);
+        client.config.user_interface.strict_host_key_checking = Some(true);
+        assert_eq!(client.get_strict_host_key_checking(), true);
+    }
+
+    #[test]
+    fn test_system_config_ssh_config() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_ssh_config().is_none());
+        client.config.user_interface.ssh_config = Some(String::from("~/.ssh/config"));
+        assert_eq!(client.get_ssh_config(), Some("~/.ssh/config"));
+    }
+
+    #[test]
+    fn test_system_config_ssh_compression() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Not configured by default
+        assert_eq!(client.get_ssh_compression(), None);
+        client.set_ssh_compression(Some(true));
+        assert_eq!(client.get_ssh_compression(), Some(true));
+        client.set_ssh_compression(Some(false));
+        assert_eq!(client.get_ssh_compression(), Some(false));
+        client.set_ssh_compression(None);
+        assert_eq!(client.get_ssh_compression(), None);
+    }
+
+    #[test]
+    fn test_system_config_watch_local_dir() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_watch_local_dir(), false);
+        client.set_watch_local_dir(true);
+        assert_eq!(client.get_watch_local_dir(), true);
+    }
+
+    #[test]
+    fn test_system_config_create_missing_wrkdir() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_create_missing_wrkdir(), false);
+        client.set_create_missing_wrkdir(true);
+        assert_eq!(client.get_create_missing_wrkdir(), true);
+    }
+
+    #[test]
+    fn test_system_config_read_only() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_read_only(), false);
+        client.set_read_only(true);
+        assert_eq!(client.get_read_only(), true);
+    }
+
+    #[test]
+    fn test_system_config_lock_dirs_first() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_lock_dirs_first(), false);
+        client.set_lock_dirs_first(true);
+        assert_eq!(client.get_lock_dirs_first(), true);
+    }
+
+    #[test]
+    fn test_system_config_transfer_bell() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_transfer_bell(), false);
+        assert_eq!(
+            client.get_transfer_bell_threshold(),
+            Duration::from_secs(DEFAULT_TRANSFER_BELL_THRESHOLD)
+        );
+        client.set_transfer_bell(true);
+        client.set_transfer_bell_threshold(0);
+        assert_eq!(client.get_transfer_bell(), true);
+        assert_eq!(client.get_transfer_bell_threshold(), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_system_config_show_banner() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Disabled by default
+        assert_eq!(client.get_show_banner(), false);
+        client.set_show_banner(true);
+        assert_eq!(client.get_show_banner(), true);
+    }
+
+    #[test]
+    fn test_system_config_confirm_on_quit() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Enabled by default
+        assert_eq!(client.get_confirm_on_quit(), true);
+        client.set_confirm_on_quit(false);
+        assert_eq!(client.get_confirm_on_quit(), false);
+    }
+
+    #[test]
+    fn test_system_config_size_unit() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_size_unit(), SizeUnit::Si);
+        client.config.user_interface.size_unit = Some(String::from("binary"));
+        assert_eq!(client.get_size_unit(), SizeUnit::Binary);
+        client.config.user_interface.size_unit = Some(String::from("SI"));
+        assert_eq!(client.get_size_unit(), SizeUnit::Si);
+        // Invalid unit
+        client.config.user_interface.size_unit = Some(String::from("bits"));
+        assert_eq!(client.get_size_unit(), SizeUnit::Si);
+    }
+
+    #[test]
+    fn test_system_config_bookmarks_sorting() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Default
+        assert_eq!(client.get_bookmarks_sorting(), BookmarkSorting::Name);
+        client.config.user_interface.bookmarks_sorting = Some(String::from("recent"));
+        assert_eq!(client.get_bookmarks_sorting(), BookmarkSorting::Recent);
+        client.config.user_interface.bookmarks_sorting = Some(String::from("Protocol"));
+        assert_eq!(client.get_bookmarks_sorting(), BookmarkSorting::Protocol);
+        // Invalid
+        client.config.user_interface.bookmarks_sorting = Some(String::from("size"));
+        assert_eq!(client.get_bookmarks_sorting(), BookmarkSorting::Name);
+    }
+
+    #[test]
+    fn test_system_config_startup() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        // Default
+        assert_eq!(client.get_startup(), StartupActivity::Authentication);
+        client.config.user_interface.startup = Some(String::from("setup"));
+        assert_eq!(client.get_startup(), StartupActivity::Setup);
+        client.config.user_interface.startup = Some(String::from("bookmark:raspberry"));
+        assert_eq!(
+            client.get_startup(),
+            StartupActivity::Bookmark(String::from("raspberry"))
+        );
+        // Invalid
+        client.config.user_interface.startup = Some(String::from("filetransfer"));
+        assert_eq!(client.get_startup(), StartupActivity::Authentication);
+    }
+
+    #[test]
+    fn test_system_config_transfer_max_attempts() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_transfer_max_attempts(),
+            DEFAULT_TRANSFER_MAX_ATTEMPTS
+        );
+        client.config.user_interface.transfer_max_attempts = Some(5);
+        assert_eq!(client.get_transfer_max_attempts(), 5);
+        // At least one attempt
+        client.config.user_interface.transfer_max_attempts = Some(0);
+        assert_eq!(client.get_transfer_max_attempts(), 1);
+    }
+
+    #[test]
+    fn test_system_config_max_preview_and_edit_bytes() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_max_preview_bytes(), DEFAULT_MAX_PREVIEW_BYTES);
+        assert_eq!(client.get_max_edit_bytes(), DEFAULT_MAX_EDIT_BYTES);
+        client.config.user_interface.max_preview_bytes = Some(1024);
+        client.config.user_interface.max_edit_bytes = Some(2048);
+        assert_eq!(client.get_max_preview_bytes(), 1024);
+        assert_eq!(client.get_max_edit_bytes(), 2048);
+    }
+
+    #[test]
+    fn test_system_config_max_recents() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_max_recents(), DEFAULT_MAX_RECENTS);
+        client.set_max_recents(4);
+        assert_eq!(client.get_max_recents(), 4);
+        // At least one recent
+        client.set_max_recents(0);
+        assert_eq!(client.get_max_recents(), 1);
+        client.config.user_interface.max_recents = Some(0);
+        assert_eq!(client.get_max_recents(), 1);
+    }
+
+    #[test]
+    fn test_system_config_hidden_patterns() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_hidden_patterns().is_empty());
+        client.config.user_interface.hidden_patterns = Some(vec![String::from("*.bak")]);
+        assert_eq!(client.get_hidden_patterns(), vec![String::from("*.bak")]);
+    }
+
+    #[test]
+    fn test_system_config_keybindings() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_keybindings().ok().unwrap(),
+            KeyBindings::default()
+        );
+        let mut mapping: HashMap<String, String> = HashMap::new();
+        mapping.insert(String::from("quit"), String::from("x"));
+        mapping.insert(String::from("exec"), String::from("q"));
+        client.config.keybindings = Some(mapping.clone());
+        // Write and read back
+        assert!(client.write_config().is_ok());
+        assert!(client.read_config().is_ok());
+        let bindings: KeyBindings = client.get_keybindings().ok().unwrap();
+        assert_eq!(bindings.key(KeyAction::Quit), 'x');
+        assert_eq!(bindings.key(KeyAction::Exec), 'q');
+        assert_eq!(bindings.key(KeyAction::Copy), 'c');
+        // Conflict
+        mapping.remove("exec");
+        client.config.keybindings = Some(mapping);
+        assert!(client.get_keybindings().is_err());
+    }
+
     #[test]
     fn test_system_config_ssh_keys() {
         let tmp_dir: TempDir = TempDir::new().ok().unwrap();
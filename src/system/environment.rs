@@ -27,10 +27,68 @@
  */
 // Ext
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+lazy_static! {
+    /// Name of the profile in use; `None` for the default profile
+    static ref PROFILE: RwLock<Option<String>> = RwLock::new(None);
+}
+
+/// ### set_profile
+///
+/// Set the profile whose configuration, bookmarks and keys are used from now on.
+/// Must be set at startup, before any of them is accessed
+pub fn set_profile(profile: Option<String>) {
+    if let Ok(mut current) = PROFILE.write() {
+        *current = profile;
+    }
+}
+
+/// ### get_profile
+///
+/// Get the name of the profile in use; `None` for the default profile
+pub fn get_profile() -> Option<String> {
+    PROFILE.read().ok().and_then(|x| x.clone())
+}
+
+/// ### is_valid_profile_name
+///
+/// Returns whether `name` can be used as profile name; only letters, digits, `-` and `_` are allowed,
+/// so that the name is always a valid directory and keyring service name
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_')
+}
+
+/// ### get_profile_dir
+///
+/// Get the configuration directory of `profile`, given the termscp directory.
+/// The default profile uses the termscp directory itself, while the other ones are stored in `profiles/{name}/`
+pub fn get_profile_dir(termscp_dir: &Path, profile: Option<&str>) -> PathBuf {
+    let mut profile_dir: PathBuf = PathBuf::from(termscp_dir);
+    if let Some(profile) = profile {
+        profile_dir.push("profiles");
+        profile_dir.push(profile);
+    }
+    profile_dir
+}
+
+/// ### get_keyring_service
+///
+/// Get the service name used to store keys of `profile` in the keyring
+#[cfg(feature = "with-keyring")]
+pub fn get_keyring_service(app_name: &str, profile: Option<&str>) -> String {
+    match profile {
+        None => app_name.to_string(),
+        Some(profile) => format!("{}-{}", app_name, profile),
+    }
+}
 
 /// ### get_config_dir
 ///
-/// Get termscp configuration directory path.
+/// Get termscp configuration directory path for the profile in use.
 /// Returns None, if it's not possible to get it
 pub fn init_config_dir() -> Result<Option<PathBuf>, String> {
     // Get file
@@ -47,10 +105,11 @@ pub fn init_config_dir() -> Result<Option<PathBuf>, String> {
         let mut p: PathBuf = CONF_DIR.as_ref().unwrap().clone();
         // Append termscp dir
         p.push("termscp/");
+        let p: PathBuf = get_profile_dir(p.as_path(), get_profile().as_deref());
         // If directory doesn't exist, create it
         match p.exists() {
             true => Ok(Some(p)),
-            false => match std::fs::create_dir(p.as_path()) {
+            false => match std::fs::create_dir_all(p.as_path()) {
                 Ok(_) => Ok(Some(p)),
                 Err(err) => Err(err.to_string()),
             },
@@ -93,6 +152,15 @@ pub fn get_log_paths(config_dir: &Path) -> PathBuf {
     log_file
 }
 
+/// ### get_known_hosts_path
+///
+/// Get path of the file where the host keys of the trusted SSH servers are stored
+pub fn get_known_hosts_path(config_dir: &Path) -> PathBuf {
+    let mut known_hosts_file: PathBuf = PathBuf::from(config_dir);
+    known_hosts_file.push("known_hosts");
+    known_hosts_file
+}
+
 /// ### get_theme_path
 ///
 /// Get paths for theme provider
@@ -142,6 +210,69 @@ mod tests {
         assert!(std::fs::remove_file(conf_dir.as_path()).is_ok());
     }
 
+    #[test]
+    fn test_system_environment_profile_dir() {
+        let termscp_dir: &Path = Path::new("/home/omar/.config/termscp/");
+        assert_eq!(
+            get_profile_dir(termscp_dir, None),
+            PathBuf::from("/home/omar/.config/termscp/")
+        );
+        let work: PathBuf = get_profile_dir(termscp_dir, Some("work"));
+        let home: PathBuf = get_profile_dir(termscp_dir, Some("home"));
+        assert_eq!(
+            work.as_path(),
+            Path::new("/home/omar/.config/termscp/profiles/work")
+        );
+        assert_eq!(
+            home.as_path(),
+            Path::new("/home/omar/.config/termscp/profiles/home")
+        );
+        // Profiles don't share any file
+        let (work_config, work_keys) = get_config_paths(work.as_path());
+        let (home_config, home_keys) = get_config_paths(home.as_path());
+        let (default_config, default_keys) = get_config_paths(termscp_dir);
+        assert_ne!(work_config, home_config);
+        assert_ne!(work_config, default_config);
+        assert_ne!(work_keys, home_keys);
+        assert_ne!(work_keys, default_keys);
+        assert_eq!(
+            work_config,
+            PathBuf::from("/home/omar/.config/termscp/profiles/work/config.toml")
+        );
+        assert_eq!(
+            home_keys,
+            PathBuf::from("/home/omar/.config/termscp/profiles/home/.ssh/")
+        );
+        assert_ne!(
+            get_bookmarks_paths(work.as_path()),
+            get_bookmarks_paths(home.as_path())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "with-keyring")]
+    fn test_system_environment_keyring_service() {
+        assert_eq!(get_keyring_service("termscp", None).as_str(), "termscp");
+        assert_eq!(
+            get_keyring_service("termscp", Some("work")).as_str(),
+            "termscp-work"
+        );
+        assert_ne!(
+            get_keyring_service("termscp", Some("work")),
+            get_keyring_service("termscp", Some("home"))
+        );
+    }
+
+    #[test]
+    fn test_system_environment_valid_profile_name() {
+        assert_eq!(is_valid_profile_name("work"), true);
+        assert_eq!(is_valid_profile_name("home_2-test"), true);
+        assert_eq!(is_valid_profile_name(""), false);
+        assert_eq!(is_valid_profile_name(".."), false);
+        assert_eq!(is_valid_profile_name("foo/bar"), false);
+        assert_eq!(is_valid_profile_name("foo bar"), false);
+    }
+
     #[test]
     fn test_system_environment_get_bookmarks_paths() {
         assert_eq!(
@@ -176,4 +307,12 @@ mod tests {
             PathBuf::from("/home/omar/.config/termscp/theme.toml"),
         );
     }
+
+    #[test]
+    fn test_system_environment_get_known_hosts_path() {
+        assert_eq!(
+            get_known_hosts_path(Path::new("/home/omar/.config/termscp/")),
+            PathBuf::from("/home/omar/.config/termscp/known_hosts"),
+        );
+    }
 }
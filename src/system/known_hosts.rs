@@ -0,0 +1,957 @@
+//! ## KnownHosts
+//!
+//! `known_hosts` is the module which verifies the host keys of SSH servers against the known hosts file
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// Locals
+use crate::filetransfer::{FileTransferError, FileTransferErrorType};
+// Ext
+use ring::hmac;
+use ssh2::{HashType, HostKeyType, Session};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+use wildmatch::WildMatch;
+
+/// ## HostKeyCheck
+///
+/// Describes the result of the verification of a host key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HostKeyCheck {
+    Match,    // Host is known and key matches
+    Mismatch, // Host is known, but key has changed
+    NotFound, // Host is unknown
+    Revoked,  // Key has been revoked
+}
+
+/// ## HostKey
+///
+/// Host key presented by a SSH server
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HostKey {
+    pub key_type: String,    // e.g. `ssh-ed25519`
+    pub key: String,         // Key encoded in base64
+    pub fingerprint: String, // e.g. `SHA256:...`
+}
+
+impl HostKey {
+    /// ### new
+    ///
+    /// Instantiates a new `HostKey`, whose fingerprint is computed from the raw SHA256 hash of the key
+    pub fn new(key_type: &str, key: &[u8], sha256: &[u8]) -> Self {
+        Self {
+            key_type: key_type.to_string(),
+            key: base64::encode(key),
+            fingerprint: format!(
+                "SHA256:{}",
+                base64::encode_config(sha256, base64::STANDARD_NO_PAD)
+            ),
+        }
+    }
+
+    /// ### from_session
+    ///
+    /// Get the host key presented by the server the session has made the handshake with
+    pub fn from_session(session: &Session) -> Option<Self> {
+        let (key, key_type): (&[u8], HostKeyType) = session.host_key()?;
+        let key_type: &str = match key_type {
+            HostKeyType::Rsa => "ssh-rsa",
+            HostKeyType::Dss => "ssh-dss",
+            HostKeyType::Ecdsa256 => "ecdsa-sha2-nistp256",
+            HostKeyType::Ecdsa384 => "ecdsa-sha2-nistp384",
+            HostKeyType::Ecdsa521 => "ecdsa-sha2-nistp521",
+            HostKeyType::Ed255219 => "ssh-ed25519",
+            HostKeyType::Unknown => return None,
+        };
+        let sha256: &[u8] = session.host_key_hash(HashType::Sha256)?;
+        Some(Self::new(key_type, key, sha256))
+    }
+}
+
+/// ## HostKeyVerifier
+///
+/// Verifies the host keys presented by SSH servers against the fingerprint saved in the bookmark, if any,
+/// and then against the known hosts file.
+/// The last key which failed the verification is kept, so that it can be trusted by the user
+#[derive(Default)]
+pub struct HostKeyVerifier {
+    path: Option<PathBuf>, // Known hosts file; if `None` keys are not verified
+    strict: bool,          // If true, keys which fail the verification can't be trusted
+    fingerprint: Option<String>, // Fingerprint expected for the host, saved in its bookmark
+    pending: Option<(String, u16, HostKey)>, // Host, port and key which failed the verification
+    accepted: Option<String>, // Fingerprint of the key accepted on the last verification
+}
+
+impl HostKeyVerifier {
+    /// ### new
+    ///
+    /// Instantiates a new `HostKeyVerifier` using the known hosts file at `path`.
+    /// If `path` is `None` any host key is accepted
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            strict: false,
+            fingerprint: None,
+            pending: None,
+            accepted: None,
+        }
+    }
+
+    /// ### strict
+    ///
+    /// Set whether unknown and changed host keys must be rejected, without giving the chance to trust them
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// ### fingerprint
+    ///
+    /// Set the fingerprint the host key must have, as saved in the bookmark.
+    /// When set, it takes precedence over the known hosts file
+    pub fn fingerprint(mut self, fingerprint: Option<String>) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    /// ### accepted
+    ///
+    /// Returns the fingerprint of the host key accepted on the last verification, if any
+    pub fn accepted(&self) -> Option<&str> {
+        self.accepted.as_deref()
+    }
+
+    /// ### verify
+    ///
+    /// Verify the host key presented by `host` at `port` on `session`.
+    /// Returns `HostKeyUnknown` if the host is not in the known hosts file, `HostKeyMismatch` if its key has changed
+    /// and `HostKeyRevoked` if its key has been revoked; the error message reports the key fingerprint
+    pub fn verify(
+        &mut self,
+        session: &Session,
+        host: &str,
+        port: u16,
+    ) -> Result<(), FileTransferError> {
+        self.pending = None;
+        self.accepted = None;
+        let key: HostKey = match HostKey::from_session(session) {
+            Some(key) => key,
+            None if self.path.is_none() && self.fingerprint.is_none() => return Ok(()),
+            None => {
+                return Err(FileTransferError::new_ex(
+                    FileTransferErrorType::ConnectionError,
+                    String::from("could not get host key"),
+                ))
+            }
+        };
+        self.verify_key(key, host, port)
+    }
+
+    /// ### verify_key
+    ///
+    /// Verify `key` presented by `host` at `port` against the expected fingerprint and the known hosts file
+    fn verify_key(&mut self, key: HostKey, host: &str, port: u16) -> Result<(), FileTransferError> {
+        self.pending = None;
+        self.accepted = None;
+        // IPv6 addresses are written without brackets
+        let host: &str = host.trim_start_matches('[').trim_end_matches(']');
+        let known_hosts: Option<KnownHosts> = match self.path.as_deref() {
+            Some(path) => Some(KnownHosts::load(path).map_err(|err| {
+                FileTransferError::new_ex(
+                    FileTransferErrorType::ConnectionError,
+                    format!("could not read {}: {}", path.display(), err),
+                )
+            })?),
+            None => None,
+        };
+        let check: HostKeyCheck = match (
+            check_fingerprint(self.fingerprint.as_deref(), &key),
+            known_hosts.as_ref(),
+        ) {
+            // Revoked keys are rejected, even if the bookmark expects them
+            (_, Some(known_hosts)) if known_hosts.is_revoked(host, port, &key) => {
+                HostKeyCheck::Revoked
+            }
+            // The fingerprint saved in the bookmark takes precedence over known hosts
+            (HostKeyCheck::NotFound, Some(known_hosts)) => known_hosts.check(host, port, &key),
+            (HostKeyCheck::NotFound, None) => HostKeyCheck::Match,
+            (check, _) => check,
+        };
+        let kind: FileTransferErrorType = match check {
+            HostKeyCheck::Match => {
+                self.accepted = Some(key.fingerprint);
+                return Ok(());
+            }
+            HostKeyCheck::Mismatch => FileTransferErrorType::HostKeyMismatch,
+            HostKeyCheck::NotFound => FileTransferErrorType::HostKeyUnknown,
+            HostKeyCheck::Revoked => FileTransferErrorType::HostKeyRevoked,
+        };
+        warn!(
+            "Host key verification failed for {}:{}: {} {} ({})",
+            host, port, key.key_type, key.fingerprint, kind
+        );
+        let err: FileTransferError = match self.fingerprint.as_deref() {
+            Some(expected) => FileTransferError::new_ex(
+                kind,
+                format!(
+                    "{} {} (bookmark expects {})",
+                    key.key_type, key.fingerprint, expected
+                ),
+            ),
+            None => {
+                FileTransferError::new_ex(kind, format!("{} {}", key.key_type, key.fingerprint))
+            }
+        };
+        // In strict mode the key can't be trusted afterwards, nor a revoked one
+        if !self.strict && check != HostKeyCheck::Revoked {
+            self.pending = Some((host.to_string(), port, key));
+        }
+        Err(err)
+    }
+
+    /// ### trust
+    ///
+    /// Write the key which failed the last verification to the known hosts file.
+    /// If a fingerprint was expected, the trusted key becomes the expected one
+    pub fn trust(&mut self) -> Result<(), FileTransferError> {
+        let (host, port, key) = self.pending.take().ok_or_else(|| {
+            FileTransferError::new_ex(
+                FileTransferErrorType::UnsupportedFeature,
+                String::from("there's no host key to trust"),
+            )
+        })?;
+        info!(
+            "Trusting host key {} for {}:{}",
+            key.fingerprint, host, port
+        );
+        if let Some(path) = self.path.as_deref() {
+            let mut known_hosts: KnownHosts = KnownHosts::load(path).map_err(|err| {
+                FileTransferError::new_ex(FileTransferErrorType::FileCreateDenied, err.to_string())
+            })?;
+            known_hosts.update(host.as_str(), port, &key);
+            known_hosts.save(path).map_err(|err| {
+                FileTransferError::new_ex(FileTransferErrorType::FileCreateDenied, err.to_string())
+            })?;
+        }
+        if self.fingerprint.is_some() {
+            self.fingerprint = Some(key.fingerprint);
+        }
+        Ok(())
+    }
+}
+
+/// ### check_fingerprint
+///
+/// Check whether `key` has the `expected` fingerprint; if no fingerprint is expected, `NotFound` is returned
+pub fn check_fingerprint(expected: Option<&str>, key: &HostKey) -> HostKeyCheck {
+    match expected {
+        None => HostKeyCheck::NotFound,
+        Some(fingerprint) if fingerprint == key.fingerprint => HostKeyCheck::Match,
+        Some(_) => HostKeyCheck::Mismatch,
+    }
+}
+
+/// ### resolve_known_hosts_path
+///
+/// Get the path of the known hosts file to use.
+/// The path provided from CLI takes precedence over the one configured in termscp, then over the
+/// `UserKnownHostsFile` configured in `~/.ssh/config`; otherwise `~/.ssh/known_hosts` is used
+pub fn resolve_known_hosts_path(
+    cli: Option<&Path>,
+    config: Option<&Path>,
+    ssh_config: Option<&Path>,
+    home_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    cli.or(config)
+        .or(ssh_config)
+        .map(PathBuf::from)
+        .or_else(|| {
+            home_dir.map(|home| {
+                let mut path: PathBuf = PathBuf::from(home);
+                path.push(".ssh");
+                path.push("known_hosts");
+                path
+            })
+        })
+}
+
+/// ## Line
+///
+/// A line of the known hosts file
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Line {
+    Entry(Entry),
+    Other(String), // Comments and markers; kept as they are
+}
+
+/// ## Entry
+///
+/// Association between hosts and their key
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Entry {
+    revoked: bool, // Whether the line has the `@revoked` marker
+    hosts: Vec<String>,
+    key_type: String,
+    key: String,
+    comment: Option<String>,
+}
+
+impl Entry {
+    /// ### parse
+    ///
+    /// Parse entry from line. Hosts hashed with `HashKnownHosts` and the `@revoked` marker are supported,
+    /// while other markers (e.g. `@cert-authority`) are not
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace().peekable();
+        let revoked: bool = fields.next_if_eq(&REVOKED_MARKER).is_some();
+        let hosts: &str = fields.next()?;
+        if (hosts.starts_with('|') && !hosts.starts_with(HASHED_HOST_PREFIX))
+            || hosts.starts_with('@')
+        {
+            return None;
+        }
+        Some(Self {
+            revoked,
+            hosts: hosts.split(',').map(|x| x.to_string()).collect(),
+            key_type: fields.next()?.to_string(),
+            key: fields.next()?.to_string(),
+            comment: Some(fields.collect::<Vec<&str>>().join(" ")).filter(|x| !x.is_empty()),
+        })
+    }
+
+    /// ### matches
+    ///
+    /// Returns whether the entry refers to `host`. Patterns with wildcards are supported, as well as negated
+    /// and hashed ones
+    fn matches(&self, host: &str) -> bool {
+        let mut matches: bool = false;
+        for pattern in self.hosts.iter() {
+            match pattern.strip_prefix('!') {
+                Some(pattern) if pattern_matches(pattern, host) => return false,
+                Some(_) => {}
+                None => matches |= pattern_matches(pattern, host),
+            }
+        }
+        matches
+    }
+}
+
+/// Marker of the lines with revoked keys
+const REVOKED_MARKER: &str = "@revoked";
+
+/// Prefix of the hosts hashed with `HashKnownHosts`: `|1|<salt>|<hash>`
+const HASHED_HOST_PREFIX: &str = "|1|";
+
+/// ### pattern_matches
+///
+/// Returns whether the host `pattern` of a known hosts entry matches `host`
+fn pattern_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix(HASHED_HOST_PREFIX) {
+        Some(hashed) => hashed_host_matches(hashed, host),
+        None => WildMatch::new(pattern).matches(host),
+    }
+}
+
+/// ### hashed_host_matches
+///
+/// Returns whether `hashed` (`<salt>|<hash>`, both base64 encoded) is the hash of `host`.
+/// As in OpenSSH, the hash is the HMAC-SHA1 of the host keyed with the salt
+fn hashed_host_matches(hashed: &str, host: &str) -> bool {
+    let (salt, hash): (&str, &str) = match hashed.split_once('|') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    match (base64::decode(salt), base64::decode(hash)) {
+        (Ok(salt), Ok(hash)) => {
+            let key: hmac::Key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, &salt);
+            hmac::verify(&key, host.as_bytes(), &hash).is_ok()
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.revoked {
+            write!(f, "{} ", REVOKED_MARKER)?;
+        }
+        write!(f, "{} {} {}", self.hosts.join(","), self.key_type, self.key)?;
+        match self.comment.as_ref() {
+            Some(comment) => write!(f, " {}", comment),
+            None => Ok(()),
+        }
+    }
+}
+
+/// ## KnownHosts
+///
+/// Known hosts file, as written by OpenSSH
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KnownHosts {
+    lines: Vec<Line>,
+}
+
+impl KnownHosts {
+    /// ### parse
+    ///
+    /// Parse known hosts file content
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content
+                .lines()
+                .map(|x| match Entry::parse(x) {
+                    Some(entry) if !x.trim_start().starts_with('#') => Line::Entry(entry),
+                    _ => Line::Other(x.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    /// ### load
+    ///
+    /// Load known hosts from file at `path`. If the file doesn't exist, known hosts are empty
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(content.as_str())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// ### save
+    ///
+    /// Write known hosts to file at `path`.
+    /// Content is written to a temporary file in the same directory, which then replaces `path`,
+    /// so the file is never left truncated; the permissions of the previous file are kept
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let dir: &Path = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut tmpfile: NamedTempFile = NamedTempFile::new_in(dir)?;
+        tmpfile.write_all(self.to_string().as_bytes())?;
+        tmpfile.as_file().sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(tmpfile.path(), metadata.permissions())?;
+        }
+        tmpfile.persist(path).map_err(|err| err.error)?;
+        Ok(())
+    }
+
+    /// ### check
+    ///
+    /// Check whether `key` is the known key for `host` at `port`.
+    /// If the host is known only with keys of other types, the key is reported as changed, as OpenSSH does
+    pub fn check(&self, host: &str, port: u16, key: &HostKey) -> HostKeyCheck {
+        if self.is_revoked(host, port, key) {
+            return HostKeyCheck::Revoked;
+        }
+        let host: String = host_pattern(host, port);
+        let mut result: HostKeyCheck = HostKeyCheck::NotFound;
+        for entry in self
+            .entries()
+            .filter(|x| !x.revoked && x.matches(host.as_str()))
+        {
+            if entry.key_type != key.key_type {
+                warn!(
+                    "Host {} is known with a {} key, but presented a {} key",
+                    host, entry.key_type, key.key_type
+                );
+            } else if entry.key == key.key {
+                return HostKeyCheck::Match;
+            }
+            result = HostKeyCheck::Mismatch;
+        }
+        result
+    }
+
+    /// ### is_revoked
+    ///
+    /// Returns whether `key` has been revoked for `host` at `port`
+    pub fn is_revoked(&self, host: &str, port: u16, key: &HostKey) -> bool {
+        let host: String = host_pattern(host, port);
+        self.entries().any(|x| {
+            x.revoked && x.key_type == key.key_type && x.key == key.key && x.matches(host.as_str())
+        })
+    }
+
+    /// ### update
+    ///
+    /// Set `key` as the known key for `host` at `port`, replacing the keys of the same type previously associated to it
+    pub fn update(&mut self, host: &str, port: u16, key: &HostKey) {
+        let host: String = host_pattern(host, port);
+        for line in self.lines.iter_mut() {
+            if let Line::Entry(entry) = line {
+                if !entry.revoked && entry.key_type == key.key_type {
+                    entry.hosts.retain(|x| {
+                        x != &host
+                            && !(x.starts_with(HASHED_HOST_PREFIX)
+                                && pattern_matches(x, host.as_str()))
+                    });
+                }
+            }
+        }
+        self.lines
+            .retain(|x| !matches!(x, Line::Entry(entry) if entry.hosts.is_empty()));
+        self.lines.push(Line::Entry(Entry {
+            revoked: false,
+            hosts: vec![host],
+            key_type: key.key_type.clone(),
+            key: key.key.clone(),
+            comment: None,
+        }));
+    }
+
+    /// ### entries
+    ///
+    /// Iterate over host entries
+    fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.lines.iter().filter_map(|x| match x {
+            Line::Entry(entry) => Some(entry),
+            Line::Other(_) => None,
+        })
+    }
+}
+
+impl fmt::Display for KnownHosts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.lines.iter() {
+            match line {
+                Line::Entry(entry) => writeln!(f, "{}", entry)?,
+                Line::Other(line) => writeln!(f, "{}", line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ### host_pattern
+///
+/// Get the name of `host` at `port` in the known hosts file; the port is written only if it's not the default one
+fn host_pattern(host: &str, port: u16) -> String {
+    match port {
+        22 => host.to_string(),
+        _ => format!("[{}]:{}", host, port),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn make_key(key: &str) -> HostKey {
+        HostKey {
+            key_type: String::from("ssh-ed25519"),
+            key: key.to_string(),
+            fingerprint: format!("SHA256:{}", key),
+        }
+    }
+
+    #[test]
+    fn test_system_known_hosts_host_key() {
+        let key: HostKey = HostKey::new("ssh-ed25519", b"hello", &[0xff; 32]);
+        assert_eq!(key.key_type.as_str(), "ssh-ed25519");
+        assert_eq!(key.key.as_str(), "aGVsbG8=");
+        assert_eq!(
+            key.fingerprint.as_str(),
+            "SHA256://////////////////////////////////////////8"
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_parse() {
+        let content: &str = "# comment\n\
+            github.com,140.82.121.4 ssh-ed25519 AAAAgithub\n\
+            |1|c2FsdA==|aGFzaA== ssh-ed25519 AAAAhashed\n\
+            @revoked * ssh-rsa AAAArevoked\n\
+            [localhost]:2222 ssh-rsa AAAAlocalhost comment\n\
+            \n";
+        let known_hosts: KnownHosts = KnownHosts::parse(content);
+        assert_eq!(known_hosts.entries().count(), 4);
+        let entry: &Entry = known_hosts.entries().next().unwrap();
+        assert_eq!(
+            entry.hosts,
+            vec![String::from("github.com"), String::from("140.82.121.4")]
+        );
+        assert_eq!(entry.key_type.as_str(), "ssh-ed25519");
+        assert_eq!(entry.key.as_str(), "AAAAgithub");
+        assert_eq!(
+            known_hosts.entries().nth(1).unwrap().hosts,
+            vec![String::from("|1|c2FsdA==|aGFzaA==")]
+        );
+        // Revoked key
+        let entry: &Entry = known_hosts.entries().nth(2).unwrap();
+        assert_eq!(entry.revoked, true);
+        assert_eq!(entry.hosts, vec![String::from("*")]);
+        assert_eq!(entry.key_type.as_str(), "ssh-rsa");
+        assert_eq!(entry.key.as_str(), "AAAArevoked");
+        assert_eq!(known_hosts.entries().nth(3).unwrap().revoked, false);
+        // Other markers are not supported
+        assert_eq!(
+            KnownHosts::parse("@cert-authority *.example.com ssh-rsa AAAAca\n")
+                .entries()
+                .count(),
+            0
+        );
+        // Lines are written back as they were
+        assert_eq!(
+            known_hosts.to_string().as_str(),
+            "# comment\n\
+            github.com,140.82.121.4 ssh-ed25519 AAAAgithub\n\
+            |1|c2FsdA==|aGFzaA== ssh-ed25519 AAAAhashed\n\
+            @revoked * ssh-rsa AAAArevoked\n\
+            [localhost]:2222 ssh-rsa AAAAlocalhost comment\n\
+            \n"
+        );
+        assert_eq!(KnownHosts::parse("").to_string().as_str(), "");
+    }
+
+    #[test]
+    fn test_system_known_hosts_check() {
+        let known_hosts: KnownHosts = KnownHosts::parse(
+            "github.com,140.82.121.4 ssh-ed25519 AAAAgithub\n\
+            [localhost]:2222 ssh-ed25519 AAAAlocalhost\n\
+            *.example.com,!bad.example.com ssh-ed25519 AAAAexample\n\
+            github.com ssh-rsa AAAArsa\n\
+            @revoked *.example.com ssh-ed25519 AAAArevoked\n\
+            gitlab.com ssh-rsa AAAAgitlab\n",
+        );
+        // Matching host
+        assert_eq!(
+            known_hosts.check("github.com", 22, &make_key("AAAAgithub")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("140.82.121.4", 22, &make_key("AAAAgithub")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("localhost", 2222, &make_key("AAAAlocalhost")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("www.example.com", 22, &make_key("AAAAexample")),
+            HostKeyCheck::Match
+        );
+        // Mismatching host
+        assert_eq!(
+            known_hosts.check("github.com", 22, &make_key("AAAAevil")),
+            HostKeyCheck::Mismatch
+        );
+        assert_eq!(
+            known_hosts.check("localhost", 2222, &make_key("AAAAgithub")),
+            HostKeyCheck::Mismatch
+        );
+        // New host
+        assert_eq!(
+            known_hosts.check("bitbucket.org", 22, &make_key("AAAAgithub")),
+            HostKeyCheck::NotFound
+        );
+        assert_eq!(
+            known_hosts.check("localhost", 22, &make_key("AAAAlocalhost")),
+            HostKeyCheck::NotFound
+        );
+        assert_eq!(
+            known_hosts.check("bad.example.com", 22, &make_key("AAAAexample")),
+            HostKeyCheck::NotFound
+        );
+        // Host known only with a key of a different type
+        assert_eq!(
+            known_hosts.check("gitlab.com", 22, &make_key("AAAAgitlab")),
+            HostKeyCheck::Mismatch
+        );
+        // Key of a different type, while the one of the same type matches
+        let key: HostKey = HostKey {
+            key_type: String::from("ssh-rsa"),
+            key: String::from("AAAArsa"),
+            fingerprint: String::from("SHA256:abc"),
+        };
+        assert_eq!(
+            known_hosts.check("github.com", 22, &key),
+            HostKeyCheck::Match
+        );
+        // Revoked key
+        assert_eq!(
+            known_hosts.check("www.example.com", 22, &make_key("AAAArevoked")),
+            HostKeyCheck::Revoked
+        );
+        assert_eq!(
+            known_hosts.check("deskichup", 22, &make_key("AAAArevoked")),
+            HostKeyCheck::NotFound
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_check_hashed() {
+        // Hashed with `ssh-keygen -H`
+        let mut known_hosts: KnownHosts = KnownHosts::parse(
+            "|1|KcT3bwFEpHtzrXbwXgy8JNrc7kg=|VMfvl380XszSh+D5jbR9KNz4WMU= ssh-ed25519 AAAAraspberry\n\
+            |1|v0VO1aPpYCD4tXIzLwmanKk1zzs=|b4KVupAaqoLsM4JwrUhddRnBthk= ssh-ed25519 AAAApi\n\
+            |1|bad ssh-ed25519 AAAAbad\n",
+        );
+        assert_eq!(
+            known_hosts.check("raspberry", 22, &make_key("AAAAraspberry")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("192.168.1.31", 4022, &make_key("AAAApi")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("raspberry", 22, &make_key("AAAAevil")),
+            HostKeyCheck::Mismatch
+        );
+        assert_eq!(
+            known_hosts.check("192.168.1.31", 22, &make_key("AAAApi")),
+            HostKeyCheck::NotFound
+        );
+        assert_eq!(
+            known_hosts.check("deskichup", 22, &make_key("AAAAraspberry")),
+            HostKeyCheck::NotFound
+        );
+        // Updating a hashed host replaces its entry
+        known_hosts.update("raspberry", 22, &make_key("AAAAnew"));
+        assert_eq!(
+            known_hosts.check("raspberry", 22, &make_key("AAAAraspberry")),
+            HostKeyCheck::Mismatch
+        );
+        assert_eq!(
+            known_hosts.to_string().as_str(),
+            "|1|v0VO1aPpYCD4tXIzLwmanKk1zzs=|b4KVupAaqoLsM4JwrUhddRnBthk= ssh-ed25519 AAAApi\n\
+            |1|bad ssh-ed25519 AAAAbad\n\
+            raspberry ssh-ed25519 AAAAnew\n"
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_update() {
+        let mut known_hosts: KnownHosts = KnownHosts::parse(
+            "# termscp\n\
+            github.com,140.82.121.4 ssh-ed25519 AAAAgithub\n\
+            [localhost]:2222 ssh-ed25519 AAAAlocalhost\n\
+            github.com ssh-rsa AAAArsa\n",
+        );
+        // New host
+        known_hosts.update("gitlab.com", 22, &make_key("AAAAgitlab"));
+        assert_eq!(
+            known_hosts.check("gitlab.com", 22, &make_key("AAAAgitlab")),
+            HostKeyCheck::Match
+        );
+        // Changed key
+        known_hosts.update("github.com", 22, &make_key("AAAAnew"));
+        assert_eq!(
+            known_hosts.check("github.com", 22, &make_key("AAAAnew")),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            known_hosts.check("140.82.121.4", 22, &make_key("AAAAgithub")),
+            HostKeyCheck::Match
+        );
+        known_hosts.update("localhost", 2222, &make_key("AAAAnew"));
+        assert_eq!(
+            known_hosts.to_string().as_str(),
+            "# termscp\n\
+            140.82.121.4 ssh-ed25519 AAAAgithub\n\
+            github.com ssh-rsa AAAArsa\n\
+            gitlab.com ssh-ed25519 AAAAgitlab\n\
+            github.com ssh-ed25519 AAAAnew\n\
+            [localhost]:2222 ssh-ed25519 AAAAnew\n"
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_load_save() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("known_hosts");
+        // Missing file
+        let mut known_hosts: KnownHosts = KnownHosts::load(path.as_path()).unwrap();
+        assert_eq!(known_hosts, KnownHosts::default());
+        known_hosts.update("github.com", 22, &make_key("AAAAgithub"));
+        assert!(known_hosts.save(path.as_path()).is_ok());
+        let mut known_hosts: KnownHosts = KnownHosts::load(path.as_path()).unwrap();
+        assert_eq!(
+            known_hosts.check("github.com", 22, &make_key("AAAAgithub")),
+            HostKeyCheck::Match
+        );
+        // File is replaced, keeping its permissions; no temporary file is left behind
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert!(fs::set_permissions(path.as_path(), fs::Permissions::from_mode(0o644)).is_ok());
+            known_hosts.update("gitlab.com", 22, &make_key("AAAAgitlab"));
+            assert!(known_hosts.save(path.as_path()).is_ok());
+            assert_eq!(
+                fs::metadata(path.as_path()).unwrap().permissions().mode() & 0o777,
+                0o644
+            );
+        }
+        assert_eq!(fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_system_known_hosts_verifier() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("known_hosts");
+        let mut verifier: HostKeyVerifier = HostKeyVerifier::new(Some(path.clone()));
+        // Unknown host can be trusted
+        let err = verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), FileTransferErrorType::HostKeyUnknown);
+        assert!(verifier.trust().is_ok());
+        assert!(verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .is_ok());
+        // Changed key
+        let err = verifier
+            .verify_key(make_key("AAAAchanged"), "github.com", 22)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), FileTransferErrorType::HostKeyMismatch);
+        // Revoked key can't be trusted, even if the bookmark expects it
+        assert!(fs::write(
+            path.as_path(),
+            "@revoked * ssh-ed25519 AAAArevoked\ngitlab.com ssh-ed25519 AAAArevoked\n"
+        )
+        .is_ok());
+        let mut verifier: HostKeyVerifier = HostKeyVerifier::new(Some(path.clone()))
+            .fingerprint(Some(make_key("AAAArevoked").fingerprint));
+        let err = verifier
+            .verify_key(make_key("AAAArevoked"), "gitlab.com", 22)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), FileTransferErrorType::HostKeyRevoked);
+        assert!(verifier.trust().is_err());
+        // Without a known hosts file any key is accepted
+        let mut verifier: HostKeyVerifier = HostKeyVerifier::default();
+        assert!(verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .is_ok());
+        assert_eq!(
+            verifier.accepted(),
+            Some(make_key("AAAAgithub").fingerprint.as_str())
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_check_fingerprint() {
+        let key: HostKey = make_key("AAAAgithub");
+        assert_eq!(check_fingerprint(None, &key), HostKeyCheck::NotFound);
+        assert_eq!(
+            check_fingerprint(Some(key.fingerprint.as_str()), &key),
+            HostKeyCheck::Match
+        );
+        assert_eq!(
+            check_fingerprint(Some(make_key("AAAAchanged").fingerprint.as_str()), &key),
+            HostKeyCheck::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_system_known_hosts_verifier_fingerprint() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("known_hosts");
+        let expected: String = make_key("AAAAgithub").fingerprint;
+        // Expected fingerprint is enough, even if the host is not in known hosts
+        let mut verifier: HostKeyVerifier =
+            HostKeyVerifier::new(Some(path.clone())).fingerprint(Some(expected.clone()));
+        assert!(verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .is_ok());
+        assert_eq!(verifier.accepted(), Some(expected.as_str()));
+        // Changed key fails, even if it's in known hosts
+        let mut known_hosts: KnownHosts = KnownHosts::default();
+        known_hosts.update("github.com", 22, &make_key("AAAAchanged"));
+        assert!(known_hosts.save(path.as_path()).is_ok());
+        let err = verifier
+            .verify_key(make_key("AAAAchanged"), "github.com", 22)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), FileTransferErrorType::HostKeyMismatch);
+        assert!(err.to_string().contains(expected.as_str()));
+        assert!(verifier.accepted().is_none());
+        // Once trusted, the new key is the expected one
+        assert!(verifier.trust().is_ok());
+        assert!(verifier
+            .verify_key(make_key("AAAAchanged"), "github.com", 22)
+            .is_ok());
+        assert_eq!(
+            verifier.accepted(),
+            Some(make_key("AAAAchanged").fingerprint.as_str())
+        );
+        // Known hosts are used when the fingerprint is not set
+        let mut verifier: HostKeyVerifier = HostKeyVerifier::new(Some(path)).fingerprint(None);
+        assert!(verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .is_err());
+        assert!(verifier.accepted().is_none());
+    }
+
+    #[test]
+    fn test_system_known_hosts_verifier_strict() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("known_hosts");
+        let mut verifier: HostKeyVerifier = HostKeyVerifier::new(Some(path.clone())).strict(true);
+        let err = verifier
+            .verify_key(make_key("AAAAgithub"), "github.com", 22)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), FileTransferErrorType::HostKeyUnknown);
+        // Key can't be trusted and file is left untouched
+        assert!(verifier.trust().is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_system_known_hosts_resolve_path() {
+        let cli: &Path = Path::new("/tmp/cli_known_hosts");
+        let config: &Path = Path::new("/tmp/config_known_hosts");
+        let ssh_config: &Path = Path::new("/tmp/ssh_known_hosts");
+        let home: &Path = Path::new("/home/omar");
+        assert_eq!(
+            resolve_known_hosts_path(Some(cli), Some(config), Some(ssh_config), Some(home)),
+            Some(PathBuf::from("/tmp/cli_known_hosts"))
+        );
+        assert_eq!(
+            resolve_known_hosts_path(None, Some(config), Some(ssh_config), Some(home)),
+            Some(PathBuf::from("/tmp/config_known_hosts"))
+        );
+        assert_eq!(
+            resolve_known_hosts_path(None, None, Some(ssh_config), Some(home)),
+            Some(PathBuf::from("/tmp/ssh_known_hosts"))
+        );
+        assert_eq!(
+            resolve_known_hosts_path(None, None, None, Some(home)),
+            Some(PathBuf::from("/home/omar/.ssh/known_hosts"))
+        );
+        assert_eq!(resolve_known_hosts_path(None, None, None, None), None);
+    }
+}
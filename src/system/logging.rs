@@ -29,14 +29,99 @@
 use crate::system::environment::{get_log_paths, init_config_dir};
 use crate::utils::file::open_file;
 // ext
-use simplelog::{ConfigBuilder, LevelFilter, WriteLogger};
+use simplelog::{CombinedLogger, ConfigBuilder, LevelFilter, SharedLogger, WriteLogger};
+use std::collections::VecDeque;
 use std::fs::File;
+use std::io::{self, Write};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Maximum amount of bytes kept by `LogBuffer`
+const LOG_BUFFER_CAPACITY: usize = 1048576;
+
+/// ## LogBuffer
+///
+/// LogBuffer keeps the log messages in memory, so that they can be written to stderr once the UI has been terminated;
+/// writing to stderr while the UI is running would corrupt the display.
+/// At most `capacity` bytes are kept: when full, the oldest messages are dropped
+#[derive(Clone)]
+pub struct LogBuffer {
+    buffer: Arc<Mutex<RingBuffer>>,
+}
+
+/// ## RingBuffer
+///
+/// Bytes of the log messages, along with whether older messages have been dropped
+struct RingBuffer {
+    data: VecDeque<u8>,
+    capacity: usize,
+    truncated: bool,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::with_capacity(LOG_BUFFER_CAPACITY)
+    }
+}
+
+impl LogBuffer {
+    /// ### with_capacity
+    ///
+    /// Instantiates a new `LogBuffer` which keeps at most `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(RingBuffer {
+                data: VecDeque::new(),
+                capacity,
+                truncated: false,
+            })),
+        }
+    }
+
+    /// ### flush_to
+    ///
+    /// Write the buffered log messages to `writer`, then clear the buffer.
+    /// If older messages have been dropped, a notice is written first
+    pub fn flush_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        if buffer.truncated {
+            writeln!(writer, "(older log messages have been dropped)")?;
+        }
+        let (head, tail) = buffer.data.as_slices();
+        writer.write_all(head)?;
+        writer.write_all(tail)?;
+        buffer.data.clear();
+        buffer.truncated = false;
+        writer.flush()
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        buffer.data.extend(buf.iter());
+        if buffer.data.len() > buffer.capacity {
+            // Drop the oldest bytes, then the rest of the line they belonged to
+            let excess: usize = buffer.data.len() - buffer.capacity;
+            buffer.data.drain(..excess);
+            if let Some(pos) = buffer.data.iter().position(|x| *x == b'\n') {
+                buffer.data.drain(..=pos);
+            }
+            buffer.truncated = true;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
 
 /// ### init
 ///
-/// Initialize logger
-pub fn init() -> Result<(), String> {
+/// Initialize logger.
+/// If `verbose` is true, log messages are also kept in the returned `LogBuffer`, which should be flushed to stderr on exit
+pub fn init(verbose: bool) -> Result<Option<LogBuffer>, String> {
     // Init config dir
     let config_dir: PathBuf = match init_config_dir() {
         Ok(Some(p)) => p,
@@ -55,8 +140,19 @@ pub fn init() -> Result<(), String> {
     let config = ConfigBuilder::new()
         .set_time_format_str("%Y-%m-%dT%H:%M:%S%z")
         .build();
-    // Make logger
-    WriteLogger::init(LevelFilter::Trace, config, file)
+    // Make loggers
+    let mut loggers: Vec<Box<dyn SharedLogger>> =
+        vec![WriteLogger::new(LevelFilter::Trace, config.clone(), file)];
+    let buffer: Option<LogBuffer> = match verbose {
+        true => {
+            let buffer: LogBuffer = LogBuffer::default();
+            loggers.push(WriteLogger::new(LevelFilter::Debug, config, buffer.clone()));
+            Some(buffer)
+        }
+        false => None,
+    };
+    CombinedLogger::init(loggers)
+        .map(|_| buffer)
         .map_err(|e| format!("Failed to initialize logger: {}", e))
 }
 
@@ -65,8 +161,52 @@ mod test {
 
     use super::*;
 
+    use pretty_assertions::assert_eq;
+
     #[test]
     fn test_system_logging_setup() {
-        assert!(init().is_ok());
+        assert!(init(true).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_system_logging_buffer_flush() {
+        let buffer: LogBuffer = LogBuffer::default();
+        let mut writer: LogBuffer = buffer.clone();
+        assert!(writeln!(writer, "connecting to 192.168.1.31").is_ok());
+        assert!(writeln!(writer, "connection established").is_ok());
+        let mut stderr: Vec<u8> = Vec::new();
+        assert!(buffer.flush_to(&mut stderr).is_ok());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap().as_str(),
+            "connecting to 192.168.1.31\nconnection established\n"
+        );
+        // Buffer is empty after flush
+        let mut stderr: Vec<u8> = Vec::new();
+        assert!(buffer.flush_to(&mut stderr).is_ok());
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn test_system_logging_buffer_capacity() {
+        let buffer: LogBuffer = LogBuffer::with_capacity(32);
+        let mut writer: LogBuffer = buffer.clone();
+        assert!(writeln!(writer, "connecting to 192.168.1.31").is_ok());
+        assert!(writeln!(writer, "connection established").is_ok());
+        assert!(writeln!(writer, "listing /home").is_ok());
+        // Oldest messages are dropped, entirely
+        let mut stderr: Vec<u8> = Vec::new();
+        assert!(buffer.flush_to(&mut stderr).is_ok());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap().as_str(),
+            "(older log messages have been dropped)\nlisting /home\n"
+        );
+        // Notice is written only once
+        assert!(writeln!(writer, "disconnected").is_ok());
+        let mut stderr: Vec<u8> = Vec::new();
+        assert!(buffer.flush_to(&mut stderr).is_ok());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap().as_str(),
+            "disconnected\n"
+        );
     }
 }
@@ -30,6 +30,8 @@ pub mod bookmarks_client;
 pub mod config_client;
 pub mod environment;
 pub(self) mod keys;
+pub mod known_hosts;
 pub mod logging;
+pub mod ssh_config;
 pub mod sshkey_storage;
 pub mod theme_provider;
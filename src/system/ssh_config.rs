@@ -0,0 +1,649 @@
+//! ## SshConfig
+//!
+//! `ssh_config` is the module which reads the identity files configured for SSH hosts in the OpenSSH client configuration
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// Locals
+use crate::utils::file::expand_path;
+// Ext
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use wildmatch::WildMatch;
+
+/// Maximum depth of nested `Include` directives, as in OpenSSH
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// ## HostBlock
+///
+/// Options which apply to the hosts matching `patterns`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct HostBlock {
+    patterns: Vec<String>,
+    host_name: Option<String>,
+    user: Option<String>,
+    port: Option<u16>,
+    identity_files: Vec<PathBuf>,
+    user_known_hosts_file: Option<PathBuf>,
+    compression: Option<bool>,
+}
+
+impl HostBlock {
+    /// ### new
+    ///
+    /// Instantiate a new block for hosts matching `patterns`
+    fn new(patterns: Vec<String>) -> Self {
+        Self {
+            patterns,
+            ..Self::default()
+        }
+    }
+
+    /// ### matches
+    ///
+    /// Returns whether the block applies to `host`. Patterns with wildcards are supported, as well as negated ones
+    fn matches(&self, host: &str) -> bool {
+        let mut matches: bool = false;
+        for pattern in self.patterns.iter() {
+            match pattern.strip_prefix('!') {
+                Some(pattern) if WildMatch::new(pattern).matches(host) => return false,
+                Some(_) => {}
+                None => matches |= WildMatch::new(pattern).matches(host),
+            }
+        }
+        matches
+    }
+}
+
+/// ## SshConfig
+///
+/// OpenSSH client configuration. Only the `HostName`, `User`, `Port`, `IdentityFile` and `UserKnownHostsFile`
+/// options are read
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SshConfig {
+    blocks: Vec<HostBlock>,
+}
+
+impl SshConfig {
+    /// ### host_name
+    ///
+    /// Get the real host name configured for `host`, which may be an alias; `%h` is replaced with `host`.
+    /// As in OpenSSH, the first value found is used
+    pub fn host_name(&self, host: &str) -> Option<String> {
+        self.blocks
+            .iter()
+            .filter(|x| x.matches(host))
+            .find_map(|x| x.host_name.as_deref())
+            .map(|x| x.replace("%h", host))
+    }
+
+    /// ### user
+    ///
+    /// Get the user configured for `host`; as in OpenSSH, the first value found is used
+    pub fn user(&self, host: &str) -> Option<&str> {
+        self.blocks
+            .iter()
+            .filter(|x| x.matches(host))
+            .find_map(|x| x.user.as_deref())
+    }
+
+    /// ### port
+    ///
+    /// Get the port configured for `host`; as in OpenSSH, the first value found is used
+    pub fn port(&self, host: &str) -> Option<u16> {
+        self.blocks
+            .iter()
+            .filter(|x| x.matches(host))
+            .find_map(|x| x.port)
+    }
+
+    /// ### compression
+    ///
+    /// Get whether compression is enabled for `host`; as in OpenSSH, the first value found is used
+    pub fn compression(&self, host: &str) -> Option<bool> {
+        self.blocks
+            .iter()
+            .filter(|x| x.matches(host))
+            .find_map(|x| x.compression)
+    }
+
+    /// ### user_known_hosts_file
+    ///
+    /// Get the known hosts file configured for `host`; as in OpenSSH, the first value found is used
+    pub fn user_known_hosts_file(&self, host: &str) -> Option<&PathBuf> {
+        self.blocks
+            .iter()
+            .filter(|x| x.matches(host))
+            .find_map(|x| x.user_known_hosts_file.as_ref())
+    }
+
+    /// ### identity_file
+    ///
+    /// Get the identity file to use for `host`: as in OpenSSH, files are tried in the order they are configured
+    /// and the first one which exists is used
+    pub fn identity_file(&self, host: &str) -> Option<&PathBuf> {
+        self.identity_files(host).find(|x| x.exists())
+    }
+
+    /// ### identity_files
+    ///
+    /// Iterate over the identity files configured for `host`, in the order they are configured
+    pub fn identity_files(&self, host: &str) -> impl Iterator<Item = &PathBuf> {
+        let blocks: Vec<&HostBlock> = self.blocks.iter().filter(|x| x.matches(host)).collect();
+        blocks.into_iter().flat_map(|x| x.identity_files.iter())
+    }
+
+    /// ### parse_file
+    ///
+    /// Parse the configuration at `path` with `content`, adding options to the block at index `current`.
+    /// `includes` contains the files which are being parsed, in order to detect include cycles
+    fn parse_file(
+        &mut self,
+        path: &Path,
+        content: &str,
+        mut current: usize,
+        ctx: &ParseContext,
+        includes: &mut HashSet<PathBuf>,
+    ) {
+        for line in content.lines() {
+            let (keyword, args): (String, Vec<String>) = match split_line(line) {
+                Some(line) => line,
+                None => continue,
+            };
+            match keyword.as_str() {
+                "host" => {
+                    self.blocks.push(HostBlock::new(args));
+                    current = self.blocks.len() - 1;
+                }
+                "match" => {
+                    // Match blocks are not supported; their options are never applied
+                    self.blocks.push(HostBlock::default());
+                    current = self.blocks.len() - 1;
+                }
+                "hostname" => {
+                    let block: &mut HostBlock = &mut self.blocks[current];
+                    if block.host_name.is_none() {
+                        block.host_name = args.first().cloned();
+                    }
+                }
+                "user" => {
+                    let block: &mut HostBlock = &mut self.blocks[current];
+                    if block.user.is_none() {
+                        block.user = args.first().cloned();
+                    }
+                }
+                "port" => match args.first().map(|x| x.parse::<u16>()) {
+                    Some(Ok(port)) => {
+                        let block: &mut HostBlock = &mut self.blocks[current];
+                        if block.port.is_none() {
+                            block.port = Some(port);
+                        }
+                    }
+                    Some(Err(_)) => warn!("Bad port in {}: {}", path.display(), line.trim()),
+                    None => {}
+                },
+                "compression" => {
+                    let value: Option<bool> =
+                        match args.first().map(|x| x.to_lowercase()).as_deref() {
+                            Some("yes") => Some(true),
+                            Some("no") => Some(false),
+                            _ => {
+                                warn!("Bad compression in {}: {}", path.display(), line.trim());
+                                None
+                            }
+                        };
+                    let block: &mut HostBlock = &mut self.blocks[current];
+                    if block.compression.is_none() {
+                        block.compression = value;
+                    }
+                }
+                "identityfile" => {
+                    if let Some(file) = args.first() {
+                        let file: PathBuf = ctx.expand(file);
+                        self.blocks[current].identity_files.push(file);
+                    }
+                }
+                // Only the first file is used; other files are not read by termscp
+                "userknownhostsfile" => {
+                    let block: &mut HostBlock = &mut self.blocks[current];
+                    if block.user_known_hosts_file.is_none() {
+                        block.user_known_hosts_file = args.first().map(|x| ctx.expand(x));
+                    }
+                }
+                "include" => {
+                    for pattern in args.iter() {
+                        for file in ctx.include_files(pattern) {
+                            self.include(file.as_path(), current, ctx, includes);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        debug!("Parsed SSH configuration {}", path.display());
+    }
+
+    /// ### include
+    ///
+    /// Parse the included file at `path`; its options are added to the block at index `current`
+    /// until the file declares another block. Files which are already being parsed are skipped
+    fn include(
+        &mut self,
+        path: &Path,
+        current: usize,
+        ctx: &ParseContext,
+        includes: &mut HashSet<PathBuf>,
+    ) {
+        let canonical: PathBuf = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if includes.contains(&canonical) {
+            warn!("Skipping {}: include cycle", path.display());
+            return;
+        }
+        if includes.len() > MAX_INCLUDE_DEPTH {
+            warn!("Skipping {}: too many nested includes", path.display());
+            return;
+        }
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                includes.insert(canonical.clone());
+                self.parse_file(path, content.as_str(), current, ctx, includes);
+                includes.remove(&canonical);
+            }
+            Err(err) => warn!("Could not read {}: {}", path.display(), err),
+        }
+    }
+}
+
+/// ## ParseContext
+///
+/// Paths used to resolve the files referred by the configuration
+struct ParseContext<'a> {
+    base_dir: &'a Path,         // Directory of relative includes
+    home_dir: Option<&'a Path>, // Replaces `~`
+}
+
+impl<'a> ParseContext<'a> {
+    /// ### expand
+    ///
+    /// Expand the leading `~` and the environment variables of `path`
+    fn expand(&self, path: &str) -> PathBuf {
+        expand_path(path, self.home_dir, |name| env::var(name).ok())
+    }
+
+    /// ### include_files
+    ///
+    /// Get the files to include for `pattern`, sorted by name.
+    /// Relative paths are resolved from the directory of the top-level configuration;
+    /// wildcards are supported in the file name only
+    fn include_files(&self, pattern: &str) -> Vec<PathBuf> {
+        let path: PathBuf = self.base_dir.join(self.expand(pattern));
+        let name: String = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => return Vec::new(),
+        };
+        if !name.contains('*') && !name.contains('?') {
+            return vec![path];
+        }
+        let dir: &Path = path.parent().unwrap_or(self.base_dir);
+        let matcher: WildMatch = WildMatch::new(name.as_str());
+        let mut files: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|x| matcher.matches(x.file_name().to_string_lossy().as_ref()))
+                .map(|x| x.path())
+                .filter(|x| x.is_file())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        files.sort();
+        files
+    }
+}
+
+/// ### parse_ssh2_config
+///
+/// Parse the OpenSSH client configuration at `path`, along with the files it includes.
+/// Included files which can't be read are skipped, as OpenSSH does
+pub fn parse_ssh2_config(path: &Path, home_dir: Option<&Path>) -> io::Result<SshConfig> {
+    let content: String = fs::read_to_string(path)?;
+    // Options declared before any `Host` apply to all hosts
+    let mut config: SshConfig = SshConfig {
+        blocks: vec![HostBlock::new(vec![String::from("*")])],
+    };
+    let ctx: ParseContext = ParseContext {
+        base_dir: path.parent().unwrap_or_else(|| Path::new("")),
+        home_dir,
+    };
+    let mut includes: HashSet<PathBuf> = HashSet::new();
+    includes.insert(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+    config.parse_file(path, content.as_str(), 0, &ctx, &mut includes);
+    Ok(config)
+}
+
+/// ### load_user_ssh_config
+///
+/// Load the OpenSSH client configuration of the user (the configured one or `~/.ssh/config`), if any.
+/// If it can't be read, an empty configuration is returned
+pub fn load_user_ssh_config(configured: Option<&str>) -> SshConfig {
+    let home_dir: Option<PathBuf> = dirs::home_dir();
+    let path: PathBuf = match resolve_ssh_config_path(configured, home_dir.as_deref()) {
+        Some(path) => path,
+        None => return SshConfig::default(),
+    };
+    match parse_ssh2_config(path.as_path(), home_dir.as_deref()) {
+        Ok(config) => config,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => SshConfig::default(),
+        Err(err) => {
+            error!("Failed to read {}: {}", path.display(), err);
+            SshConfig::default()
+        }
+    }
+}
+
+/// ### resolve_ssh_config_path
+///
+/// Get the path of the OpenSSH client configuration of the user.
+/// The configured path has `~` and environment variables expanded;
+/// if none is configured, `~/.ssh/config` is used, but only if it exists
+pub fn resolve_ssh_config_path(
+    configured: Option<&str>,
+    home_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    match configured {
+        Some(path) => Some(expand_path(path, home_dir, |x| env::var(x).ok())),
+        None => home_dir
+            .map(|home| home.join(".ssh").join("config"))
+            .filter(|path| path.exists()),
+    }
+}
+
+/// ### split_line
+///
+/// Split a configuration line into its lowercase keyword and its arguments.
+/// Both `Keyword value` and `Keyword=value` are supported, as well as quoted arguments.
+/// Returns `None` for empty lines and comments
+fn split_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line: &str = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let end: usize = line
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(line.len());
+    let keyword: String = line[..end].to_lowercase();
+    let rest: &str = line[end..].trim_start();
+    let rest: &str = rest.strip_prefix('=').unwrap_or(rest).trim_start();
+    let mut args: Vec<String> = Vec::new();
+    let mut arg: String = String::new();
+    let mut quoted: bool = false;
+    for c in rest.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !arg.is_empty() {
+                    args.push(std::mem::take(&mut arg));
+                }
+            }
+            c => arg.push(c),
+        }
+    }
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+    Some((keyword, args))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_system_ssh_config_parse() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        let content: &str = "# comment\n\
+            IdentityFile ~/.ssh/id_default\n\
+            \n\
+            Host raspberry 192.168.1.*\n\
+            \tHostName 192.168.1.31\n\
+            \tIdentityFile=\"/home/omar/.ssh/raspberry key\"\n\
+            \n\
+            Host *.example.com !bastion.example.com\n\
+            \x20 identityfile ~/.ssh/example\n";
+        assert!(fs::write(path.as_path(), content).is_ok());
+        let config: SshConfig =
+            parse_ssh2_config(path.as_path(), Some(Path::new("/home/omar"))).unwrap();
+        // Options before any host apply to every host, and the first value is used
+        assert_eq!(
+            config.identity_files("raspberry").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/id_default")
+        );
+        assert_eq!(config.blocks.len(), 3);
+        assert_eq!(
+            config.blocks[1].identity_files,
+            vec![PathBuf::from("/home/omar/.ssh/raspberry key")]
+        );
+        assert_eq!(config.blocks[2].matches("www.example.com"), true);
+        assert_eq!(config.blocks[2].matches("bastion.example.com"), false);
+        assert_eq!(config.blocks[1].matches("192.168.1.31"), true);
+        assert_eq!(config.blocks[1].matches("example.com"), false);
+        assert_eq!(
+            config.host_name("raspberry").as_deref(),
+            Some("192.168.1.31")
+        );
+        assert!(config.host_name("www.example.com").is_none());
+        // Unexisting file
+        assert!(parse_ssh2_config(tmp_dir.path().join("none").as_path(), None).is_err());
+    }
+
+    #[test]
+    fn test_system_ssh_config_host_options() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        let content: &str = "Host work\n\
+            \tHostName %h.example.com\n\
+            \tUser omar\n\
+            \tPort 4022\n\
+            \tPort 22\n\
+            \tCompression yes\n\
+            Host bad\n\
+            \tPort http\n\
+            \tCompression maybe\n\
+            Host *\n\
+            \tUser root\n\
+            \tPort 2222\n\
+            \tCompression no\n\
+            \tUserKnownHostsFile /tmp/known_hosts /tmp/known_hosts2\n";
+        assert!(fs::write(path.as_path(), content).is_ok());
+        let config: SshConfig = parse_ssh2_config(path.as_path(), None).unwrap();
+        // The first value found is used
+        assert_eq!(
+            config.host_name("work").as_deref(),
+            Some("work.example.com")
+        );
+        assert_eq!(config.user("work"), Some("omar"));
+        assert_eq!(config.port("work"), Some(4022));
+        assert_eq!(config.compression("work"), Some(true));
+        assert_eq!(
+            config.user_known_hosts_file("work").unwrap(),
+            &PathBuf::from("/tmp/known_hosts")
+        );
+        // Bad ports are ignored
+        assert_eq!(config.port("bad"), Some(2222));
+        assert!(config.host_name("bad").is_none());
+        assert_eq!(config.user("bad"), Some("root"));
+        // Bad values are ignored too
+        assert_eq!(config.compression("bad"), Some(false));
+        assert!(SshConfig::default().compression("work").is_none());
+    }
+
+    #[test]
+    fn test_system_ssh_config_include() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        assert!(fs::create_dir(tmp_dir.path().join("config.d")).is_ok());
+        assert!(fs::write(
+            path.as_path(),
+            "Include config.d/*\n\nHost *\n    IdentityFile /home/omar/.ssh/id_rsa\n"
+        )
+        .is_ok());
+        assert!(fs::write(
+            tmp_dir.path().join("config.d/raspberry"),
+            "Host raspberry\n    IdentityFile ~/.ssh/raspberry.key\n"
+        )
+        .is_ok());
+        assert!(fs::write(
+            tmp_dir.path().join("config.d/work.conf"),
+            "Host work\n    IdentityFile /home/omar/.ssh/work.key\n"
+        )
+        .is_ok());
+        let config: SshConfig =
+            parse_ssh2_config(path.as_path(), Some(Path::new("/home/omar"))).unwrap();
+        assert_eq!(
+            config.identity_files("raspberry").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/raspberry.key")
+        );
+        assert_eq!(
+            config.identity_files("work").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/work.key")
+        );
+        assert_eq!(
+            config.identity_files("192.168.1.31").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/id_rsa")
+        );
+    }
+
+    #[test]
+    fn test_system_ssh_config_include_in_host() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        let included: PathBuf = tmp_dir.path().join("raspberry.conf");
+        // Included options apply to the host which includes them; absolute paths and unexisting files are fine
+        assert!(fs::write(
+            path.as_path(),
+            format!(
+                "Host raspberry\n    Include {} unexisting.conf\n",
+                included.display()
+            )
+        )
+        .is_ok());
+        assert!(fs::write(included.as_path(), "IdentityFile /home/omar/.ssh/pi\n").is_ok());
+        let config: SshConfig = parse_ssh2_config(path.as_path(), None).unwrap();
+        assert_eq!(
+            config.identity_files("raspberry").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/pi")
+        );
+        assert!(config.identity_files("deskichup").next().is_none());
+    }
+
+    #[test]
+    fn test_system_ssh_config_include_cycle() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        assert!(fs::write(path.as_path(), "Include other\nInclude config\n").is_ok());
+        assert!(fs::write(
+            tmp_dir.path().join("other"),
+            "Include config\nHost raspberry\n    IdentityFile /home/omar/.ssh/pi\n"
+        )
+        .is_ok());
+        let config: SshConfig = parse_ssh2_config(path.as_path(), None).unwrap();
+        // Each file is parsed once
+        assert_eq!(config.blocks.len(), 2);
+        assert_eq!(
+            config.identity_files("raspberry").next().unwrap(),
+            &PathBuf::from("/home/omar/.ssh/pi")
+        );
+    }
+
+    #[test]
+    fn test_system_ssh_config_identity_file() {
+        let tmp_dir: TempDir = TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        assert!(fs::write(
+            path.as_path(),
+            "Host raspberry\n    IdentityFile ~/unexisting\n    IdentityFile ~/pi.key\n\
+            Host *\n    IdentityFile ~/unexisting\n"
+        )
+        .is_ok());
+        assert!(fs::write(tmp_dir.path().join("pi.key"), "").is_ok());
+        let config: SshConfig = parse_ssh2_config(path.as_path(), Some(tmp_dir.path())).unwrap();
+        // Identity files which don't exist are skipped
+        assert_eq!(config.identity_files("raspberry").count(), 3);
+        assert_eq!(
+            config.identity_file("raspberry").unwrap(),
+            &tmp_dir.path().join("pi.key")
+        );
+        assert!(config.identity_file("deskichup").is_none());
+    }
+
+    #[test]
+    fn test_system_ssh_config_resolve_path() {
+        let home: TempDir = TempDir::new().unwrap();
+        // Configured path is expanded
+        assert_eq!(
+            resolve_ssh_config_path(Some("~/.ssh/work_config"), Some(home.path())).unwrap(),
+            home.path().join(".ssh/work_config")
+        );
+        assert_eq!(
+            resolve_ssh_config_path(Some("/etc/ssh/ssh_config"), Some(home.path())).unwrap(),
+            PathBuf::from("/etc/ssh/ssh_config")
+        );
+        // Default is used only if it exists
+        assert!(resolve_ssh_config_path(None, Some(home.path())).is_none());
+        assert!(fs::create_dir(home.path().join(".ssh")).is_ok());
+        assert!(fs::write(home.path().join(".ssh").join("config"), "").is_ok());
+        assert_eq!(
+            resolve_ssh_config_path(None, Some(home.path())).unwrap(),
+            home.path().join(".ssh").join("config")
+        );
+        assert!(resolve_ssh_config_path(None, None).is_none());
+    }
+
+    #[test]
+    fn test_system_ssh_config_split_line() {
+        assert!(split_line("").is_none());
+        assert!(split_line("   # comment").is_none());
+        assert_eq!(
+            split_line("  IdentityFile = ~/.ssh/id_rsa").unwrap(),
+            (
+                String::from("identityfile"),
+                vec![String::from("~/.ssh/id_rsa")]
+            )
+        );
+        assert_eq!(
+            split_line("Include a.conf \"b c.conf\"").unwrap(),
+            (
+                String::from("include"),
+                vec![String::from("a.conf"), String::from("b c.conf")]
+            )
+        );
+    }
+}
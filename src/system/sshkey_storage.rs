@@ -27,12 +27,14 @@
  */
 // Locals
 use super::config_client::ConfigClient;
+use super::ssh_config::{self, SshConfig};
 // Ext
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 pub struct SshKeyStorage {
     hosts: HashMap<String, PathBuf>, // Association between {user}@{host} and RSA key path
+    ssh_config: SshConfig,           // Identity files configured in `~/.ssh/config`
 }
 
 impl SshKeyStorage {
@@ -61,7 +63,10 @@ impl SshKeyStorage {
             info!("Got SSH key for {}", key);
         }
         // Return storage
-        SshKeyStorage { hosts }
+        SshKeyStorage {
+            hosts,
+            ssh_config: ssh_config::load_user_ssh_config(cfg_client.get_ssh_config()),
+        }
     }
 
     /// ### empty
@@ -71,15 +76,20 @@ impl SshKeyStorage {
     pub fn empty() -> Self {
         SshKeyStorage {
             hosts: HashMap::new(),
+            ssh_config: SshConfig::default(),
         }
     }
 
     /// ### resolve
     ///
-    /// Return RSA key path from host and username
+    /// Return RSA key path from host and username.
+    /// If no key is stored in termscp for them, the first existing identity file configured for the host
+    /// in `~/.ssh/config` is used
     pub fn resolve(&self, host: &str, username: &str) -> Option<&PathBuf> {
         let key: String = Self::make_mapkey(host, username);
-        self.hosts.get(&key)
+        self.hosts
+            .get(&key)
+            .or_else(|| self.ssh_config.identity_file(host))
     }
 
     /// ### make_mapkey
@@ -149,6 +159,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_system_sshkey_storage_ssh_config() {
+        let tmp_dir: tempfile::TempDir = tempfile::TempDir::new().ok().unwrap();
+        let path: PathBuf = tmp_dir.path().join("config");
+        assert!(std::fs::write(path.as_path(), "Include config.d/*\n").is_ok());
+        assert!(std::fs::create_dir(tmp_dir.path().join("config.d")).is_ok());
+        let key_path: PathBuf = tmp_dir.path().join("raspberry.key");
+        assert!(std::fs::write(
+            tmp_dir.path().join("config.d/raspberry"),
+            format!(
+                "Host raspberry\n    IdentityFile {}\nHost deskichup\n    IdentityFile /unexisting.key\n",
+                key_path.display()
+            )
+        )
+        .is_ok());
+        assert!(std::fs::write(key_path.as_path(), "").is_ok());
+        let mut storage: SshKeyStorage = SshKeyStorage {
+            hosts: HashMap::new(),
+            ssh_config: ssh_config::parse_ssh2_config(path.as_path(), None).unwrap(),
+        };
+        assert_eq!(*storage.resolve("raspberry", "pi").unwrap(), key_path);
+        // Identity files which don't exist are not used
+        assert!(storage.resolve("deskichup", "veeso").is_none());
+        // Keys stored in termscp take precedence
+        storage.add_key("raspberry", "pi", PathBuf::from("/tmp/omar"));
+        assert_eq!(
+            *storage.resolve("raspberry", "pi").unwrap(),
+            PathBuf::from("/tmp/omar")
+        );
+    }
+
     /// ### get_paths
     ///
     /// Get paths for configuration and keys directory
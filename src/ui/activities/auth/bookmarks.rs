@@ -26,7 +26,9 @@
  * SOFTWARE.
  */
 // Locals
-use super::{AuthActivity, FileTransferProtocol};
+use super::{AuthActivity, FileTransferParams};
+use crate::config::bookmarks::BookmarkSorting;
+use crate::filetransfer::FileTransferProtocol;
 use crate::system::bookmarks_client::BookmarksClient;
 use crate::system::environment;
 
@@ -44,6 +46,10 @@ impl AuthActivity {
             // Iterate over kyes
             let name: Option<&String> = self.bookmarks_list.get(idx);
             if let Some(name) = name {
+                // Stop editing the deleted bookmark
+                if self.editing_bookmark.as_ref() == Some(name) {
+                    self.editing_bookmark = None;
+                }
                 bookmarks_cli.del_bookmark(&name);
                 // Write bookmarks
                 self.write_bookmarks();
@@ -53,18 +59,32 @@ impl AuthActivity {
         }
     }
 
+    /// ### edit_bookmark
+    ///
+    /// Load selected bookmark (at index) to input fields, so that it can be modified and saved again with the same name
+    pub(super) fn edit_bookmark(&mut self, idx: usize) {
+        self.load_bookmark(idx);
+        self.editing_bookmark = self.bookmarks_list.get(idx).cloned();
+    }
+
+    /// ### editing_bookmark
+    ///
+    /// Get the name of the bookmark which is being edited, if any
+    pub(super) fn editing_bookmark(&self) -> Option<&str> {
+        self.editing_bookmark.as_deref()
+    }
+
     /// ### load_bookmark
     ///
     /// Load selected bookmark (at index) to input fields
     pub(super) fn load_bookmark(&mut self, idx: usize) {
+        self.editing_bookmark = None;
         if let Some(bookmarks_cli) = self.bookmarks_client.as_ref() {
             // Iterate over bookmarks
             if let Some(key) = self.bookmarks_list.get(idx) {
                 if let Some(bookmark) = bookmarks_cli.get_bookmark(&key) {
                     // Load parameters into components
-                    self.load_bookmark_into_gui(
-                        bookmark.0, bookmark.1, bookmark.2, bookmark.3, bookmark.4,
-                    );
+                    self.load_bookmark_into_gui(bookmark);
                 }
             }
         }
@@ -74,7 +94,7 @@ impl AuthActivity {
     ///
     /// Save current input fields as a bookmark
     pub(super) fn save_bookmark(&mut self, name: String, save_password: bool) {
-        let (address, port, protocol, username, password) = self.get_input();
+        let params: FileTransferParams = self.get_input_params();
         if let Some(bookmarks_cli) = self.bookmarks_client.as_mut() {
             // Check if password must be saved
             let password: Option<String> = match save_password {
@@ -82,12 +102,12 @@ impl AuthActivity {
                     .view
                     .get_state(super::COMPONENT_RADIO_BOOKMARK_SAVE_PWD)
                 {
-                    Some(Payload::One(Value::Usize(0))) => Some(password), // Yes
-                    _ => None,                                             // No such component / No
+                    Some(Payload::One(Value::Usize(0))) => params.password.clone(), // Yes
+                    _ => None, // No such component / No
                 },
                 false => None,
             };
-            bookmarks_cli.add_bookmark(name.clone(), address, port, protocol, username, password);
+            bookmarks_cli.add_bookmark(name.clone(), params.password(password));
             // Save bookmarks
             self.write_bookmarks();
             // Remove `name` from bookmarks if exists
@@ -96,47 +116,76 @@ impl AuthActivity {
             self.bookmarks_list.push(name);
             self.sort_bookmarks();
         }
+        self.editing_bookmark = None;
     }
     /// ### del_recent
     ///
-    /// Delete recent
+    /// Delete recent at index in the filtered list
     pub(super) fn del_recent(&mut self, idx: usize) {
+        let name: Option<String> = self.filtered_recents().get(idx).cloned();
         if let Some(client) = self.bookmarks_client.as_mut() {
-            let name: Option<&String> = self.recents_list.get(idx);
             if let Some(name) = name {
                 client.del_recent(&name);
                 // Write bookmarks
                 self.write_bookmarks();
+                // Delete element from vec
+                self.recents_list.retain(|x| *x != name);
             }
-            // Delete element from vec
-            self.recents_list.remove(idx);
         }
     }
 
     /// ### load_recent
     ///
-    /// Load selected recent (at index) to input fields
+    /// Load selected recent (at index in the filtered list) to input fields
     pub(super) fn load_recent(&mut self, idx: usize) {
+        self.editing_bookmark = None;
+        let key: Option<String> = self.filtered_recents().get(idx).cloned();
         if let Some(client) = self.bookmarks_client.as_ref() {
             // Iterate over bookmarks
-            if let Some(key) = self.recents_list.get(idx) {
-                if let Some(bookmark) = client.get_recent(key) {
+            if let Some(key) = key {
+                if let Some(bookmark) = client.get_recent(&key) {
                     // Load parameters
-                    self.load_bookmark_into_gui(
-                        bookmark.0, bookmark.1, bookmark.2, bookmark.3, None,
-                    );
+                    self.load_bookmark_into_gui(bookmark);
                 }
             }
         }
     }
 
+    /// ### filtered_recents
+    ///
+    /// Get the keys of the recents displayed, which match the protocol filter
+    pub(super) fn filtered_recents(&self) -> Vec<String> {
+        match self.bookmarks_client.as_ref() {
+            Some(client) => self
+                .recents_list
+                .iter()
+                .filter(|x| {
+                    client
+                        .get_recent(x)
+                        .map(|x| recents_filter_matches(self.recents_filter, x.protocol))
+                        .unwrap_or(false)
+                })
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// ### cycle_recents_filter
+    ///
+    /// Display the recents of the next protocol only, or all recents after the last protocol
+    pub(super) fn cycle_recents_filter(&mut self) {
+        self.recents_filter = next_recents_filter(self.recents_filter);
+    }
+
     /// ### save_recent
     ///
-    /// Save current input fields as a "recent"
+    /// Save current input fields as a "recent" and mark the bookmarks for the same host as used
     pub(super) fn save_recent(&mut self) {
-        let (address, port, protocol, username, _password) = self.get_input();
+        let params: FileTransferParams = self.get_input_params();
         if let Some(bookmarks_cli) = self.bookmarks_client.as_mut() {
-            bookmarks_cli.add_recent(address, port, protocol, username);
+            bookmarks_cli.mark_bookmarks_used(&params);
+            bookmarks_cli.add_recent(params);
             // Save bookmarks
             self.write_bookmarks();
         }
@@ -165,10 +214,11 @@ impl AuthActivity {
                     let bookmarks_file: PathBuf =
                         environment::get_bookmarks_paths(config_dir_path.as_path());
                     // Initialize client
+                    let max_recents: usize = self.context().config().get_max_recents();
                     match BookmarksClient::new(
                         bookmarks_file.as_path(),
                         config_dir_path.as_path(),
-                        16,
+                        max_recents,
                     ) {
                         Ok(cli) => {
                             // Load bookmarks into list
@@ -216,11 +266,12 @@ impl AuthActivity {
 
     /// ### sort_bookmarks
     ///
-    /// Sort bookmarks in list
+    /// Sort bookmarks in list, as configured
     fn sort_bookmarks(&mut self) {
-        // Conver to lowercase when sorting
-        self.bookmarks_list
-            .sort_by(|a, b| a.to_lowercase().as_str().cmp(b.to_lowercase().as_str()));
+        let sorting: BookmarkSorting = self.context().config().get_bookmarks_sorting();
+        if let Some(bookmarks_cli) = self.bookmarks_client.as_ref() {
+            bookmarks_cli.sort_bookmarks(&mut self.bookmarks_list, sorting);
+        }
     }
 
     /// ### sort_recents
@@ -234,40 +285,241 @@ impl AuthActivity {
     /// ### load_bookmark_into_gui
     ///
     /// Load bookmark data into the gui components
-    fn load_bookmark_into_gui(
-        &mut self,
-        addr: String,
-        port: u16,
-        protocol: FileTransferProtocol,
-        username: String,
-        password: Option<String>,
-    ) {
+    fn load_bookmark_into_gui(&mut self, params: FileTransferParams) {
+        let fields: InputFields = InputFields::new(params);
+        // Protocol of bookmarks must not be inferred from port
+        self.protocol_chosen = true;
         // Load parameters into components
         if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_ADDR) {
-            let props = InputPropsBuilder::from(props).with_value(addr).build();
+            let props = InputPropsBuilder::from(props)
+                .with_value(fields.address)
+                .build();
             self.view.update(super::COMPONENT_INPUT_ADDR, props);
         }
         if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_PORT) {
             let props = InputPropsBuilder::from(props)
-                .with_value(port.to_string())
+                .with_value(fields.port)
                 .build();
             self.view.update(super::COMPONENT_INPUT_PORT, props);
         }
         if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_PROTOCOL) {
             let props = RadioPropsBuilder::from(props)
-                .with_value(Self::protocol_enum_to_opt(protocol))
+                .with_value(fields.protocol)
                 .build();
             self.view.update(super::COMPONENT_RADIO_PROTOCOL, props);
         }
         if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_USERNAME) {
-            let props = InputPropsBuilder::from(props).with_value(username).build();
+            let props = InputPropsBuilder::from(props)
+                .with_value(fields.username)
+                .build();
             self.view.update(super::COMPONENT_INPUT_USERNAME, props);
         }
-        if let Some(password) = password {
+        if let Some(password) = fields.password {
             if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_PASSWORD) {
                 let props = InputPropsBuilder::from(props).with_value(password).build();
                 self.view.update(super::COMPONENT_INPUT_PASSWORD, props);
             }
         }
+        if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_SSH_KEY) {
+            let props = InputPropsBuilder::from(props)
+                .with_value(fields.ssh_key_path)
+                .build();
+            self.view.update(super::COMPONENT_INPUT_SSH_KEY, props);
+        }
+        if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_PROXY_JUMP) {
+            let props = InputPropsBuilder::from(props)
+                .with_value(fields.proxy_jump)
+                .build();
+            self.view.update(super::COMPONENT_INPUT_PROXY_JUMP, props);
+        }
+    }
+}
+
+/// ## InputFields
+///
+/// Values of the authentication form fields
+#[derive(Debug, Eq, PartialEq)]
+struct InputFields {
+    address: String,
+    port: String,
+    protocol: usize, // Option of the protocol radio
+    username: String,
+    password: Option<String>,
+    ssh_key_path: String,
+    proxy_jump: String,
+}
+
+impl InputFields {
+    /// ### new
+    ///
+    /// Get the values of the form fields for the provided connection parameters
+    fn new(params: FileTransferParams) -> Self {
+        InputFields {
+            address: params.address,
+            port: params.port.to_string(),
+            protocol: AuthActivity::protocol_enum_to_opt(params.protocol),
+            username: params.username.unwrap_or_default(),
+            password: params.password,
+            ssh_key_path: params
+                .ssh_key_path
+                .map(|x| x.display().to_string())
+                .unwrap_or_default(),
+            proxy_jump: params.proxy_jump.unwrap_or_default(),
+        }
+    }
+}
+
+/// ### next_recents_filter
+///
+/// Get the protocol filter which follows `filter`: all recents, then each protocol
+fn next_recents_filter(filter: Option<FileTransferProtocol>) -> Option<FileTransferProtocol> {
+    match filter {
+        None => Some(FileTransferProtocol::Sftp),
+        Some(FileTransferProtocol::Sftp) => Some(FileTransferProtocol::Scp),
+        Some(FileTransferProtocol::Scp) => Some(FileTransferProtocol::Ftp(false)),
+        Some(FileTransferProtocol::Ftp(false)) => Some(FileTransferProtocol::Ftp(true)),
+        Some(FileTransferProtocol::Ftp(true)) => None,
+    }
+}
+
+/// ### recents_filter_matches
+///
+/// Returns whether a recent connection with `protocol` is displayed with `filter`
+fn recents_filter_matches(
+    filter: Option<FileTransferProtocol>,
+    protocol: FileTransferProtocol,
+) -> bool {
+    filter.map(|x| x == protocol).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_auth_bookmarks_input_fields() {
+        assert_eq!(
+            InputFields::new(
+                FileTransferParams::new("192.168.1.31")
+                    .port(2222)
+                    .username(Some("omar"))
+                    .password(Some("mypassword"))
+                    .ssh_key_path(Some(std::path::Path::new("/home/omar/.ssh/id_rsa")))
+                    .proxy_jump(Some("bastion"))
+            ),
+            InputFields {
+                address: String::from("192.168.1.31"),
+                port: String::from("2222"),
+                protocol: 0,
+                username: String::from("omar"),
+                password: Some(String::from("mypassword")),
+                ssh_key_path: String::from("/home/omar/.ssh/id_rsa"),
+                proxy_jump: String::from("bastion"),
+            }
+        );
+        // Without password
+        assert_eq!(
+            InputFields::new(
+                FileTransferParams::new("ftp.example.com")
+                    .port(21)
+                    .protocol(FileTransferProtocol::Ftp(true))
+                    .username(Some("anonymous"))
+            ),
+            InputFields {
+                address: String::from("ftp.example.com"),
+                port: String::from("21"),
+                protocol: 3,
+                username: String::from("anonymous"),
+                password: None,
+                ssh_key_path: String::new(),
+                proxy_jump: String::new(),
+            }
+        );
+        assert_eq!(
+            InputFields::new(
+                FileTransferParams::new("10.0.0.1").protocol(FileTransferProtocol::Scp)
+            )
+            .protocol,
+            1
+        );
+        assert_eq!(
+            InputFields::new(
+                FileTransferParams::new("10.0.0.1").protocol(FileTransferProtocol::Ftp(false))
+            )
+            .protocol,
+            2
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_auth_bookmarks_editing() {
+        let mut activity: AuthActivity = AuthActivity::default();
+        activity.bookmarks_list.push(String::from("raspberry"));
+        activity.bookmarks_list.push(String::from("work"));
+        assert!(activity.editing_bookmark().is_none());
+        // No bookmarks client: fields are not loaded, but the bookmark name is kept
+        activity.edit_bookmark(1);
+        assert_eq!(activity.editing_bookmark(), Some("work"));
+        // Loading a bookmark stops editing
+        activity.load_bookmark(0);
+        assert!(activity.editing_bookmark().is_none());
+        activity.edit_bookmark(0);
+        assert_eq!(activity.editing_bookmark(), Some("raspberry"));
+        activity.load_recent(0);
+        assert!(activity.editing_bookmark().is_none());
+        // Out of range
+        activity.edit_bookmark(5);
+        assert!(activity.editing_bookmark().is_none());
+    }
+
+    #[test]
+    fn test_ui_activities_auth_bookmarks_recents_filter() {
+        let recents: Vec<FileTransferParams> = vec![
+            FileTransferParams::new("192.168.1.31").protocol(FileTransferProtocol::Sftp),
+            FileTransferParams::new("ftp.example.com").protocol(FileTransferProtocol::Ftp(false)),
+            FileTransferParams::new("10.0.0.1").protocol(FileTransferProtocol::Scp),
+            FileTransferParams::new("ftps.example.com").protocol(FileTransferProtocol::Ftp(true)),
+            FileTransferParams::new("192.168.1.30").protocol(FileTransferProtocol::Sftp),
+        ];
+        let filter = |filter: Option<FileTransferProtocol>| -> Vec<&str> {
+            recents
+                .iter()
+                .filter(|x| recents_filter_matches(filter, x.protocol))
+                .map(|x| x.address.as_str())
+                .collect()
+        };
+        // Cycle through all protocols, then back to all recents
+        let mut recents_filter: Option<FileTransferProtocol> = None;
+        assert_eq!(filter(recents_filter).len(), 5);
+        recents_filter = next_recents_filter(recents_filter);
+        assert_eq!(filter(recents_filter), vec!["192.168.1.31", "192.168.1.30"]);
+        recents_filter = next_recents_filter(recents_filter);
+        assert_eq!(filter(recents_filter), vec!["10.0.0.1"]);
+        recents_filter = next_recents_filter(recents_filter);
+        assert_eq!(filter(recents_filter), vec!["ftp.example.com"]);
+        recents_filter = next_recents_filter(recents_filter);
+        assert_eq!(filter(recents_filter), vec!["ftps.example.com"]);
+        recents_filter = next_recents_filter(recents_filter);
+        assert!(recents_filter.is_none());
+        assert_eq!(filter(recents_filter).len(), 5);
+    }
+
+    #[test]
+    fn test_ui_activities_auth_bookmarks_filtered_recents() {
+        let mut activity: AuthActivity = AuthActivity::default();
+        // No bookmarks client: nothing is displayed
+        activity
+            .recents_list
+            .push(String::from("ISO20210101T100000"));
+        assert!(activity.filtered_recents().is_empty());
+        activity.cycle_recents_filter();
+        assert_eq!(activity.recents_filter, Some(FileTransferProtocol::Sftp));
+        // Deleting or loading out of the filtered list does nothing
+        activity.del_recent(0);
+        activity.load_recent(0);
+        assert_eq!(activity.recents_list.len(), 1);
     }
 }
@@ -26,6 +26,8 @@
  * SOFTWARE.
  */
 use super::{AuthActivity, FileTransferParams, FileTransferProtocol};
+use crate::system::ssh_config::{self, SshConfig};
+use crate::utils::parser::{parse_user_info, resolve_ssh_host};
 
 impl AuthActivity {
     /// ### protocol_opt_to_enum
@@ -62,6 +64,18 @@ impl AuthActivity {
         }
     }
 
+    /// ### get_protocol_for_port
+    ///
+    /// Infer the protocol from a well-known port; returns `None` if the port isn't associated to any protocol
+    pub(super) fn get_protocol_for_port(port: u16) -> Option<FileTransferProtocol> {
+        match port {
+            22 => Some(FileTransferProtocol::Sftp),
+            21 => Some(FileTransferProtocol::Ftp(false)),
+            990 => Some(FileTransferProtocol::Ftp(true)),
+            _ => None,
+        }
+    }
+
     /// ### is_port_standard
     ///
     /// Returns whether the port is standard or not
@@ -69,11 +83,21 @@ impl AuthActivity {
         port < 1024
     }
 
+    /// ### is_ssh_protocol
+    ///
+    /// Returns whether the SSH options (key path and jump host) apply to `protocol`
+    pub(super) fn is_ssh_protocol(protocol: FileTransferProtocol) -> bool {
+        matches!(
+            protocol,
+            FileTransferProtocol::Sftp | FileTransferProtocol::Scp
+        )
+    }
+
     /// ### check_minimum_window_size
     ///
     /// Check minimum window size window
     pub(super) fn check_minimum_window_size(&mut self, height: u16) {
-        if height < 25 {
+        if height < 28 {
             // Mount window error
             self.mount_size_err();
         } else {
@@ -85,13 +109,32 @@ impl AuthActivity {
     ///
     /// Get input values from fields or return an error if fields are invalid
     pub(super) fn collect_host_params(&self) -> Result<FileTransferParams, &'static str> {
-        let (address, port, protocol, username, password): (
+        let (address, port, _, username, password): (
             String,
             u16,
             FileTransferProtocol,
             String,
             String,
         ) = self.get_input();
+        // Address may contain credentials (`user:password@address`); fields have priority
+        let (address, username, password): (String, String, String) = match address.rfind('@') {
+            Some(idx) => {
+                let (user_info_username, user_info_password) =
+                    parse_user_info(&address[..idx]).map_err(|_| "Invalid credentials in host")?;
+                (
+                    address[idx + 1..].to_string(),
+                    match username.is_empty() {
+                        true => user_info_username,
+                        false => username,
+                    },
+                    match password.is_empty() {
+                        true => user_info_password.unwrap_or_default(),
+                        false => password,
+                    },
+                )
+            }
+            None => (address, username, password),
+        };
         if address.is_empty() {
             return Err("Invalid host");
         }
@@ -100,8 +143,6 @@ impl AuthActivity {
         }
         Ok(FileTransferParams {
             address,
-            port,
-            protocol,
             username: match username.is_empty() {
                 true => None,
                 false => Some(username),
@@ -110,7 +151,71 @@ impl AuthActivity {
                 true => None,
                 false => Some(password),
             },
-            entry_directory: None,
+            ..self.get_input_params()
         })
     }
+
+    /// ### apply_ssh_config
+    ///
+    /// Resolve the address of `params` through the OpenSSH client configuration.
+    /// The configured `User` and `Port` are applied only if the username is blank and the port is the protocol default
+    pub(super) fn apply_ssh_config(&self, params: FileTransferParams) -> FileTransferParams {
+        let keep_user: bool = params.username.is_some();
+        let keep_port: bool = params.port != Self::get_default_port_for_protocol(params.protocol);
+        let ssh_config: SshConfig =
+            ssh_config::load_user_ssh_config(self.context().config().get_ssh_config());
+        resolve_ssh_host(params, keep_user, keep_port, &ssh_config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_auth_misc_is_ssh_protocol() {
+        assert!(AuthActivity::is_ssh_protocol(FileTransferProtocol::Sftp));
+        assert!(AuthActivity::is_ssh_protocol(FileTransferProtocol::Scp));
+        assert!(!AuthActivity::is_ssh_protocol(FileTransferProtocol::Ftp(
+            false
+        )));
+        assert!(!AuthActivity::is_ssh_protocol(FileTransferProtocol::Ftp(
+            true
+        )));
+    }
+
+    #[test]
+    fn test_ui_activities_auth_misc_protocol_for_port() {
+        assert_eq!(
+            AuthActivity::get_protocol_for_port(22),
+            Some(FileTransferProtocol::Sftp)
+        );
+        assert_eq!(
+            AuthActivity::get_protocol_for_port(21),
+            Some(FileTransferProtocol::Ftp(false))
+        );
+        assert_eq!(
+            AuthActivity::get_protocol_for_port(990),
+            Some(FileTransferProtocol::Ftp(true))
+        );
+        assert_eq!(AuthActivity::get_protocol_for_port(2222), None);
+        assert_eq!(AuthActivity::get_protocol_for_port(0), None);
+        assert_eq!(AuthActivity::get_protocol_for_port(8080), None);
+        // Default ports are inferred back to a protocol of the same family
+        assert_eq!(
+            AuthActivity::get_protocol_for_port(AuthActivity::get_default_port_for_protocol(
+                FileTransferProtocol::Ftp(true)
+            )),
+            Some(FileTransferProtocol::Ftp(false))
+        );
+        assert_eq!(
+            AuthActivity::get_protocol_for_port(AuthActivity::get_default_port_for_protocol(
+                FileTransferProtocol::Scp
+            )),
+            Some(FileTransferProtocol::Sftp)
+        );
+    }
 }
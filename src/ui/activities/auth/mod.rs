@@ -56,6 +56,8 @@ const COMPONENT_INPUT_ADDR: &str = "INPUT_ADDRESS";
 const COMPONENT_INPUT_PORT: &str = "INPUT_PORT";
 const COMPONENT_INPUT_USERNAME: &str = "INPUT_USERNAME";
 const COMPONENT_INPUT_PASSWORD: &str = "INPUT_PASSWORD";
+const COMPONENT_INPUT_SSH_KEY: &str = "INPUT_SSH_KEY";
+const COMPONENT_INPUT_PROXY_JUMP: &str = "INPUT_PROXY_JUMP";
 const COMPONENT_INPUT_BOOKMARK_NAME: &str = "INPUT_BOOKMARK_NAME";
 const COMPONENT_RADIO_PROTOCOL: &str = "RADIO_PROTOCOL";
 const COMPONENT_RADIO_QUIT: &str = "RADIO_QUIT";
@@ -77,9 +79,13 @@ pub struct AuthActivity {
     context: Option<Context>,
     view: View,
     bookmarks_client: Option<BookmarksClient>,
-    redraw: bool,                // Should ui actually be redrawned?
-    bookmarks_list: Vec<String>, // List of bookmarks
-    recents_list: Vec<String>,   // list of recents
+    redraw: bool,                                 // Should ui actually be redrawned?
+    busy: bool,                                   // Has an event been handled on last draw?
+    bookmarks_list: Vec<String>,                  // List of bookmarks
+    recents_list: Vec<String>,                    // list of recents
+    recents_filter: Option<FileTransferProtocol>, // Protocol of the recents displayed; all if `None`
+    editing_bookmark: Option<String>,             // Name of the bookmark loaded for editing
+    protocol_chosen: bool,                        // Whether the protocol has been chosen explicitly
 }
 
 impl Default for AuthActivity {
@@ -99,8 +105,12 @@ impl AuthActivity {
             view: View::init(),
             bookmarks_client: None,
             redraw: true, // True at startup
+            busy: false,
             bookmarks_list: Vec::new(),
             recents_list: Vec::new(),
+            recents_filter: None,
+            editing_bookmark: None,
+            protocol_chosen: false,
         }
     }
 
@@ -211,9 +221,11 @@ impl Activity for AuthActivity {
             return;
         }
         // Read one event
+        self.busy = false;
         if let Ok(Some(event)) = self.context().input_hnd().read_event() {
             // Set redraw to true
             self.redraw = true;
+            self.busy = true;
             // Handle on resize
             if let Event::Resize(_, h) = event {
                 self.check_minimum_window_size(h);
@@ -231,6 +243,13 @@ impl Activity for AuthActivity {
         }
     }
 
+    /// ### is_busy
+    ///
+    /// `is_busy` reports to the activity manager whether the last call to `on_draw` has handled any event
+    fn is_busy(&self) -> bool {
+        self.busy
+    }
+
     /// ### will_umount
     ///
     /// `will_umount` is the method which must be able to report to the activity manager, whether
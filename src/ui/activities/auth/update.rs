@@ -27,15 +27,17 @@
  */
 // locals
 use super::{
-    AuthActivity, FileTransferProtocol, COMPONENT_BOOKMARKS_LIST, COMPONENT_INPUT_ADDR,
-    COMPONENT_INPUT_BOOKMARK_NAME, COMPONENT_INPUT_PASSWORD, COMPONENT_INPUT_PORT,
+    AuthActivity, FileTransferParams, FileTransferProtocol, COMPONENT_BOOKMARKS_LIST,
+    COMPONENT_INPUT_ADDR, COMPONENT_INPUT_BOOKMARK_NAME, COMPONENT_INPUT_PASSWORD,
+    COMPONENT_INPUT_PORT, COMPONENT_INPUT_PROXY_JUMP, COMPONENT_INPUT_SSH_KEY,
     COMPONENT_INPUT_USERNAME, COMPONENT_RADIO_BOOKMARK_DEL_BOOKMARK,
     COMPONENT_RADIO_BOOKMARK_DEL_RECENT, COMPONENT_RADIO_BOOKMARK_SAVE_PWD,
     COMPONENT_RADIO_PROTOCOL, COMPONENT_RADIO_QUIT, COMPONENT_RECENTS_LIST, COMPONENT_TEXT_ERROR,
     COMPONENT_TEXT_HELP, COMPONENT_TEXT_NEW_VERSION_NOTES, COMPONENT_TEXT_SIZE_ERR,
 };
 use crate::ui::keymap::*;
-use tuirealm::components::InputPropsBuilder;
+use std::convert::TryFrom;
+use tuirealm::components::{InputPropsBuilder, RadioPropsBuilder};
 use tuirealm::{Msg, Payload, PropsBuilder, Update, Value};
 
 // -- update
@@ -73,11 +75,34 @@ impl Update for AuthActivity {
                     None
                 }
                 (COMPONENT_INPUT_PASSWORD, &MSG_KEY_DOWN) => {
-                    // Give focus to port
+                    // Give focus to ssh key, if shown; protocol otherwise
+                    match Self::is_ssh_protocol(self.get_input_protocol()) {
+                        true => self.view.active(COMPONENT_INPUT_SSH_KEY),
+                        false => self.view.active(COMPONENT_RADIO_PROTOCOL),
+                    }
+                    None
+                }
+                (COMPONENT_INPUT_SSH_KEY, &MSG_KEY_DOWN) => {
+                    // Give focus to jump host
+                    self.view.active(COMPONENT_INPUT_PROXY_JUMP);
+                    None
+                }
+                (COMPONENT_INPUT_PROXY_JUMP, &MSG_KEY_DOWN) => {
+                    // Give focus to protocol
                     self.view.active(COMPONENT_RADIO_PROTOCOL);
                     None
                 }
                 // Focus ( UP )
+                (COMPONENT_INPUT_PROXY_JUMP, &MSG_KEY_UP) => {
+                    // Give focus to ssh key
+                    self.view.active(COMPONENT_INPUT_SSH_KEY);
+                    None
+                }
+                (COMPONENT_INPUT_SSH_KEY, &MSG_KEY_UP) => {
+                    // Give focus to password
+                    self.view.active(COMPONENT_INPUT_PASSWORD);
+                    None
+                }
                 (COMPONENT_INPUT_PASSWORD, &MSG_KEY_UP) => {
                     // Give focus to port
                     self.view.active(COMPONENT_INPUT_USERNAME);
@@ -99,12 +124,17 @@ impl Update for AuthActivity {
                     None
                 }
                 (COMPONENT_RADIO_PROTOCOL, &MSG_KEY_UP) => {
-                    // Give focus to port
-                    self.view.active(COMPONENT_INPUT_PASSWORD);
+                    // Give focus to jump host, if shown; password otherwise
+                    match Self::is_ssh_protocol(self.get_input_protocol()) {
+                        true => self.view.active(COMPONENT_INPUT_PROXY_JUMP),
+                        false => self.view.active(COMPONENT_INPUT_PASSWORD),
+                    }
                     None
                 }
                 // Protocol - On Change
                 (COMPONENT_RADIO_PROTOCOL, Msg::OnChange(Payload::One(Value::Usize(protocol)))) => {
+                    // Protocol has been chosen by the user; don't infer it from port anymore
+                    self.protocol_chosen = true;
                     // If port is standard, update the current port with default for selected protocol
                     let protocol: FileTransferProtocol = Self::protocol_opt_to_enum(*protocol);
                     // Get port
@@ -116,6 +146,19 @@ impl Update for AuthActivity {
                         }
                     }
                 }
+                // Port - On Change
+                (COMPONENT_INPUT_PORT, Msg::OnChange(Payload::One(Value::Usize(port)))) => {
+                    // Suggest protocol for well-known ports, unless the user has already chosen one
+                    let protocol: Option<FileTransferProtocol> = u16::try_from(*port)
+                        .ok()
+                        .and_then(Self::get_protocol_for_port);
+                    match protocol {
+                        Some(protocol) if !self.protocol_chosen => {
+                            self.update_input_protocol(protocol)
+                        }
+                        _ => None,
+                    }
+                }
                 // Bookmarks commands
                 // <RIGHT> / <LEFT>
                 (COMPONENT_BOOKMARKS_LIST, &MSG_KEY_RIGHT) => {
@@ -141,6 +184,22 @@ impl Update for AuthActivity {
                     self.mount_recent_del_dialog();
                     None
                 }
+                // <F>
+                (COMPONENT_RECENTS_LIST, &MSG_KEY_CHAR_F) => {
+                    self.cycle_recents_filter();
+                    self.view_recent_connections()
+                }
+                // <M>
+                (COMPONENT_BOOKMARKS_LIST, &MSG_KEY_CHAR_M) => {
+                    if let Some(Payload::One(Value::Usize(idx))) =
+                        self.view.get_state(COMPONENT_BOOKMARKS_LIST)
+                    {
+                        self.edit_bookmark(idx);
+                        // Give focus to input address
+                        self.view.active(COMPONENT_INPUT_ADDR);
+                    }
+                    None
+                }
                 // Enter
                 (COMPONENT_BOOKMARKS_LIST, Msg::OnSubmit(Payload::One(Value::Usize(idx)))) => {
                     self.load_bookmark(*idx);
@@ -335,6 +394,8 @@ impl Update for AuthActivity {
                         }
                         Ok(params) => {
                             self.save_recent();
+                            // Resolve ssh config aliases; the recent keeps the address as typed
+                            let params: FileTransferParams = self.apply_ssh_config(params);
                             // Set file transfer params to context
                             self.context_mut().set_ftparams(params);
                             // Set exit reason
@@ -346,7 +407,10 @@ impl Update for AuthActivity {
                 }
                 // <ESC> => Quit
                 (_, &MSG_KEY_ESC) => {
-                    self.mount_quit();
+                    match self.context().config().get_confirm_on_quit() {
+                        true => self.mount_quit(),
+                        false => self.exit_reason = Some(super::ExitReason::Quit),
+                    }
                     None
                 }
                 (_, _) => None, // Ignore other events
@@ -356,6 +420,18 @@ impl Update for AuthActivity {
 }
 
 impl AuthActivity {
+    fn update_input_protocol(&mut self, protocol: FileTransferProtocol) -> Option<(String, Msg)> {
+        match self.view.get_props(COMPONENT_RADIO_PROTOCOL) {
+            None => None,
+            Some(props) => {
+                let props = RadioPropsBuilder::from(props)
+                    .with_value(Self::protocol_enum_to_opt(protocol))
+                    .build();
+                self.view.update(COMPONENT_RADIO_PROTOCOL, props)
+            }
+        }
+    }
+
     fn update_input_port(&mut self, port: u16) -> Option<(String, Msg)> {
         match self.view.get_props(COMPONENT_INPUT_PORT) {
             None => None,
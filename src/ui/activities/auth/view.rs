@@ -26,7 +26,7 @@
  * SOFTWARE.
  */
 // Locals
-use super::{AuthActivity, Context, FileTransferProtocol};
+use super::{AuthActivity, Context, FileTransferParams, FileTransferProtocol};
 use crate::ui::components::{
     bookmark_list::{BookmarkList, BookmarkListPropsBuilder},
     msgbox::{MsgBox, MsgBoxPropsBuilder},
@@ -179,6 +179,28 @@ impl AuthActivity {
                     .build(),
             )),
         );
+        // SSH key
+        self.view.mount(
+            super::COMPONENT_INPUT_SSH_KEY,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_foreground(username_color)
+                    .with_borders(Borders::ALL, BorderType::Rounded, username_color)
+                    .with_label(String::from("SSH key path (optional)"))
+                    .build(),
+            )),
+        );
+        // Jump host
+        self.view.mount(
+            super::COMPONENT_INPUT_PROXY_JUMP,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_foreground(addr_color)
+                    .with_borders(Borders::ALL, BorderType::Rounded, addr_color)
+                    .with_label(String::from("Jump host (optional)"))
+                    .build(),
+            )),
+        );
         // Version notice
         if let Some(version) = self
             .context()
@@ -246,7 +268,7 @@ impl AuthActivity {
                 .margin(1)
                 .constraints(
                     [
-                        Constraint::Length(21), // Auth Form
+                        Constraint::Length(24), // Auth Form
                         Constraint::Min(3),     // Bookmarks
                     ]
                     .as_ref(),
@@ -264,6 +286,7 @@ impl AuthActivity {
                         Constraint::Length(3), // port
                         Constraint::Length(3), // username
                         Constraint::Length(3), // password
+                        Constraint::Length(3), // ssh options
                         Constraint::Length(3), // footer
                     ]
                     .as_ref(),
@@ -293,8 +316,19 @@ impl AuthActivity {
                 .render(super::COMPONENT_INPUT_USERNAME, f, auth_chunks[6]);
             self.view
                 .render(super::COMPONENT_INPUT_PASSWORD, f, auth_chunks[7]);
+            // SSH options are shown for SSH protocols only
+            if Self::is_ssh_protocol(self.get_input_protocol()) {
+                let ssh_chunks = Layout::default()
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                    .direction(Direction::Horizontal)
+                    .split(auth_chunks[8]);
+                self.view
+                    .render(super::COMPONENT_INPUT_SSH_KEY, f, ssh_chunks[0]);
+                self.view
+                    .render(super::COMPONENT_INPUT_PROXY_JUMP, f, ssh_chunks[1]);
+            }
             self.view
-                .render(super::COMPONENT_TEXT_FOOTER, f, auth_chunks[8]);
+                .render(super::COMPONENT_TEXT_FOOTER, f, auth_chunks[9]);
             // Bookmark chunks
             self.view
                 .render(super::COMPONENT_BOOKMARKS_LIST, f, bookmark_chunks[0]);
@@ -404,7 +438,7 @@ impl AuthActivity {
             .bookmarks_list
             .iter()
             .map(|x| {
-                let entry: (String, u16, FileTransferProtocol, String, _) = self
+                let entry: FileTransferParams = self
                     .bookmarks_client
                     .as_ref()
                     .unwrap()
@@ -413,10 +447,10 @@ impl AuthActivity {
                 format!(
                     "{} ({}://{}@{}:{})",
                     x,
-                    entry.2.to_string().to_lowercase(),
-                    entry.3,
-                    entry.0,
-                    entry.1
+                    entry.protocol.to_string().to_lowercase(),
+                    entry.username.unwrap_or_default(),
+                    entry.address,
+                    entry.port
                 )
             })
             .collect();
@@ -439,10 +473,10 @@ impl AuthActivity {
     /// View recent connections
     pub(super) fn view_recent_connections(&mut self) -> Option<(String, Msg)> {
         let bookmarks: Vec<String> = self
-            .recents_list
+            .filtered_recents()
             .iter()
             .map(|x| {
-                let entry: (String, u16, FileTransferProtocol, String) = self
+                let entry: FileTransferParams = self
                     .bookmarks_client
                     .as_ref()
                     .unwrap()
@@ -451,20 +485,24 @@ impl AuthActivity {
 
                 format!(
                     "{}://{}@{}:{}",
-                    entry.2.to_string().to_lowercase(),
-                    entry.3,
-                    entry.0,
-                    entry.1
+                    entry.protocol.to_string().to_lowercase(),
+                    entry.username.unwrap_or_default(),
+                    entry.address,
+                    entry.port
                 )
             })
             .collect();
+        let title: String = match self.recents_filter {
+            None => String::from("Recent connections"),
+            Some(protocol) => format!("Recent connections ({})", protocol.to_string()),
+        };
         match self.view.get_props(super::COMPONENT_RECENTS_LIST) {
             None => None,
             Some(props) => {
                 let msg = self.view.update(
                     super::COMPONENT_RECENTS_LIST,
                     BookmarkListPropsBuilder::from(props)
-                        .with_bookmarks(Some(String::from("Recent connections")), bookmarks)
+                        .with_bookmarks(Some(title), bookmarks)
                         .build(),
                 );
                 msg
@@ -635,12 +673,15 @@ impl AuthActivity {
     pub(super) fn mount_bookmark_save_dialog(&mut self) {
         let save_color = self.theme().misc_save_dialog;
         let warn_color = self.theme().misc_warn_dialog;
+        // When editing a bookmark, propose its name
+        let name: String = self.editing_bookmark().unwrap_or_default().to_string();
         self.view.mount(
             super::COMPONENT_INPUT_BOOKMARK_NAME,
             Box::new(Input::new(
                 InputPropsBuilder::default()
                     .with_foreground(save_color)
                     .with_label(String::from("Save bookmark as…"))
+                    .with_value(name)
                     .with_borders(
                         Borders::TOP | Borders::RIGHT | Borders::LEFT,
                         BorderType::Rounded,
@@ -742,6 +783,22 @@ impl AuthActivity {
                             )
                             .add_col(TextSpan::from("         Delete selected bookmark"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<M>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("             Modify selected bookmark"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<F>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("             Filter recents by protocol"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<CTRL+C>")
                                     .bold()
@@ -851,4 +908,37 @@ impl AuthActivity {
             _ => String::new(),
         }
     }
+
+    pub(super) fn get_input_ssh_key(&self) -> String {
+        match self.view.get_state(super::COMPONENT_INPUT_SSH_KEY) {
+            Some(Payload::One(Value::Str(x))) => x,
+            _ => String::new(),
+        }
+    }
+
+    pub(super) fn get_input_proxy_jump(&self) -> String {
+        match self.view.get_state(super::COMPONENT_INPUT_PROXY_JUMP) {
+            Some(Payload::One(Value::Str(x))) => x,
+            _ => String::new(),
+        }
+    }
+
+    /// ### get_input_params
+    ///
+    /// Get the values of the form fields as connection parameters; empty fields are `None`.
+    /// SSH options are set only for SSH protocols
+    pub(super) fn get_input_params(&self) -> FileTransferParams {
+        let (address, port, protocol, username, password) = self.get_input();
+        let params: FileTransferParams = FileTransferParams::new(address)
+            .port(port)
+            .protocol(protocol)
+            .username(Some(username).filter(|x| !x.is_empty()))
+            .password(Some(password).filter(|x| !x.is_empty()));
+        match Self::is_ssh_protocol(protocol) {
+            true => params
+                .ssh_key_path(Some(self.get_input_ssh_key()).filter(|x| !x.is_empty()))
+                .proxy_jump(Some(self.get_input_proxy_jump()).filter(|x| !x.is_empty())),
+            false => params,
+        }
+    }
 }
@@ -26,8 +26,9 @@
  * SOFTWARE.
  */
 // locals
-use super::{FileTransferActivity, FsEntry};
-use std::path::PathBuf;
+use super::super::browser::{check_dir, home_dir, sync_dir, FileExplorerTab};
+use super::{FileTransferActivity, FsEntry, LogLevel};
+use std::path::{Path, PathBuf};
 
 impl FileTransferActivity {
     /// ### action_enter_local_dir
@@ -39,7 +40,7 @@ impl FileTransferActivity {
             FsEntry::Directory(dir) => {
                 self.local_changedir(dir.abs_path.as_path(), true);
                 if self.browser.sync_browsing && !block_sync {
-                    self.action_change_remote_dir(dir.name, true);
+                    self.sync_changedir(FileExplorerTab::Remote, dir.name.as_str());
                 }
                 true
             }
@@ -52,7 +53,7 @@ impl FileTransferActivity {
                                 self.local_changedir(dir.abs_path.as_path(), true);
                                 // Check whether to sync
                                 if self.browser.sync_browsing && !block_sync {
-                                    self.action_change_remote_dir(dir.name.clone(), true);
+                                    self.sync_changedir(FileExplorerTab::Remote, dir.name.as_str());
                                 }
                                 true
                             }
@@ -74,7 +75,7 @@ impl FileTransferActivity {
             FsEntry::Directory(dir) => {
                 self.remote_changedir(dir.abs_path.as_path(), true);
                 if self.browser.sync_browsing && !block_sync {
-                    self.action_change_local_dir(dir.name, true);
+                    self.sync_changedir(FileExplorerTab::Local, dir.name.as_str());
                 }
                 true
             }
@@ -87,7 +88,7 @@ impl FileTransferActivity {
                                 self.remote_changedir(dir.abs_path.as_path(), true);
                                 // Check whether to sync
                                 if self.browser.sync_browsing && !block_sync {
-                                    self.action_change_local_dir(dir.name.clone(), true);
+                                    self.sync_changedir(FileExplorerTab::Local, dir.name.as_str());
                                 }
                                 true
                             }
@@ -105,10 +106,16 @@ impl FileTransferActivity {
     /// Change local directory reading value from input
     pub(crate) fn action_change_local_dir(&mut self, input: String, block_sync: bool) {
         let dir_path: PathBuf = self.local_to_abs_path(PathBuf::from(input.as_str()).as_path());
-        self.local_changedir(dir_path.as_path(), true);
+        if let Err(err) = self.change_dir_absolute(FileExplorerTab::Local, dir_path.as_path()) {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not change working directory: {}", err),
+            );
+            return;
+        }
         // Check whether to sync
         if self.browser.sync_browsing && !block_sync {
-            self.action_change_remote_dir(input, true);
+            self.sync_changedir(FileExplorerTab::Remote, input.as_str());
         }
     }
 
@@ -117,10 +124,104 @@ impl FileTransferActivity {
     /// Change remote directory reading value from input
     pub(crate) fn action_change_remote_dir(&mut self, input: String, block_sync: bool) {
         let dir_path: PathBuf = self.remote_to_abs_path(PathBuf::from(input.as_str()).as_path());
-        self.remote_changedir(dir_path.as_path(), true);
+        if let Err(err) = self.change_dir_absolute(FileExplorerTab::Remote, dir_path.as_path()) {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not change working directory: {}", err),
+            );
+            return;
+        }
         // Check whether to sync
         if self.browser.sync_browsing && !block_sync {
-            self.action_change_local_dir(input, true);
+            self.sync_changedir(FileExplorerTab::Local, input.as_str());
+        }
+    }
+
+    /// ### change_dir_absolute
+    ///
+    /// Jump to the absolute `path` in the local or remote explorer, according to `tab`.
+    /// Returns error if `path` is not a directory; otherwise the previous working directory is pushed to the stack
+    pub(crate) fn change_dir_absolute(
+        &mut self,
+        tab: FileExplorerTab,
+        path: &Path,
+    ) -> Result<(), String> {
+        match tab {
+            FileExplorerTab::Local | FileExplorerTab::FindLocal => {
+                let host = &self.host;
+                let dir: PathBuf = check_dir(path, |p| host.stat(p).map_err(|e| e.to_string()))?;
+                self.local_changedir(dir.as_path(), true);
+            }
+            FileExplorerTab::Remote | FileExplorerTab::FindRemote => {
+                let client = &mut self.sessions.client;
+                let dir: PathBuf = check_dir(path, |p| client.stat(p).map_err(|e| e.to_string()))?;
+                self.remote_changedir(dir.as_path(), true);
+            }
+        }
+        Ok(())
+    }
+
+    /// ### action_go_home
+    ///
+    /// Go to the home directory in the explorer in use: the home directory of the user on localhost,
+    /// the directory reported by the server once connected on remote
+    pub(crate) fn action_go_home(&mut self) {
+        let tab: FileExplorerTab = match self.browser.tab() {
+            FileExplorerTab::Local => FileExplorerTab::Local,
+            FileExplorerTab::Remote => FileExplorerTab::Remote,
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => return,
+        };
+        match home_dir(tab, dirs::home_dir(), self.sessions.home.as_deref()) {
+            Some(home) => {
+                if let Err(err) = self.change_dir_absolute(tab, home.as_path()) {
+                    self.log_and_alert(
+                        LogLevel::Error,
+                        format!("Could not change working directory: {}", err),
+                    );
+                }
+            }
+            None => self.log(
+                LogLevel::Warn,
+                String::from("Could not go to the home directory: it can't be determined"),
+            ),
+        }
+    }
+
+    /// ### sync_changedir
+    ///
+    /// Enter `name` on the explorer of `tab`, when browsing is synchronized and `name` has been entered on the other one.
+    /// If the directory doesn't exist there, the explorer is left unchanged and the user is not alerted
+    fn sync_changedir(&mut self, tab: FileExplorerTab, name: &str) {
+        let dir: Option<PathBuf> = match tab {
+            FileExplorerTab::Local | FileExplorerTab::FindLocal => {
+                let wrkdir: PathBuf = self.local().wrkdir.clone();
+                let host = &self.host;
+                sync_dir(wrkdir.as_path(), name, |p| {
+                    host.stat(p).map_err(|e| e.to_string())
+                })
+            }
+            FileExplorerTab::Remote | FileExplorerTab::FindRemote => {
+                let wrkdir: PathBuf = self.remote().wrkdir.clone();
+                let client = &mut self.sessions.client;
+                sync_dir(wrkdir.as_path(), name, |p| {
+                    client.stat(p).map_err(|e| e.to_string())
+                })
+            }
+        };
+        match (dir, tab) {
+            (Some(dir), FileExplorerTab::Local | FileExplorerTab::FindLocal) => {
+                self.local_changedir(dir.as_path(), true)
+            }
+            (Some(dir), FileExplorerTab::Remote | FileExplorerTab::FindRemote) => {
+                self.remote_changedir(dir.as_path(), true)
+            }
+            (None, _) => self.log(
+                LogLevel::Info,
+                format!(
+                    "Synchronized browsing: \"{}\" is not a directory on the other panel; skipped",
+                    name
+                ),
+            ),
         }
     }
 
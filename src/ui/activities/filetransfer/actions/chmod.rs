@@ -0,0 +1,150 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry};
+use crate::filetransfer::read_only::Operation;
+use crate::utils::parser::parse_unix_pex;
+
+impl FileTransferActivity {
+    /// ### action_local_chmod
+    ///
+    /// Change the permissions of the selected local files, applying `input` mode (octal or symbolic)
+    pub(crate) fn action_local_chmod(&mut self, input: String) {
+        if self.deny_read_only(Operation::Chmod) {
+            return;
+        }
+        let entries: Vec<FsEntry> = match self.get_local_selected_entries() {
+            SelectedEntry::One(entry) => vec![entry],
+            SelectedEntry::Many(entries) => entries,
+            SelectedEntry::None => return,
+        };
+        for entry in entries.iter() {
+            if let Some(pex) = self.chmod_pex(entry, input.as_str()) {
+                #[cfg(target_family = "unix")]
+                let result: Result<(), String> = self
+                    .host
+                    .chmod(entry.get_abs_path().as_path(), pex)
+                    .map_err(|e| e.to_string());
+                #[cfg(not(target_family = "unix"))]
+                let result: Result<(), String> =
+                    Err(String::from("not supported on this platform"));
+                if let Err(err) = result {
+                    self.log_and_alert(
+                        LogLevel::Error,
+                        format!(
+                            "Could not change mode for \"{}\": {}",
+                            entry.get_abs_path().display(),
+                            err
+                        ),
+                    );
+                } else {
+                    self.log_chmod(entry, pex);
+                }
+            }
+        }
+        self.reload_local_dir();
+    }
+
+    /// ### action_remote_chmod
+    ///
+    /// Change the permissions of the selected remote files, applying `input` mode (octal or symbolic)
+    pub(crate) fn action_remote_chmod(&mut self, input: String) {
+        if self.deny_read_only(Operation::Chmod) {
+            return;
+        }
+        let entries: Vec<FsEntry> = match self.get_remote_selected_entries() {
+            SelectedEntry::One(entry) => vec![entry],
+            SelectedEntry::Many(entries) => entries,
+            SelectedEntry::None => return,
+        };
+        for entry in entries.iter() {
+            if let Some(pex) = self.chmod_pex(entry, input.as_str()) {
+                if let Err(err) = self
+                    .sessions
+                    .client
+                    .chmod(entry.get_abs_path().as_path(), pex)
+                {
+                    self.log_and_alert_remote(
+                        LogLevel::Error,
+                        format!(
+                            "Could not change mode for \"{}\"",
+                            entry.get_abs_path().display()
+                        ),
+                        &err,
+                    );
+                } else {
+                    self.log_chmod(entry, pex);
+                }
+            }
+        }
+        self.reload_remote_dir();
+    }
+
+    /// ### chmod_pex
+    ///
+    /// Get the permissions to set for `entry` applying `mode`.
+    /// Returns `None` if the mode is invalid or if running in dry-run mode
+    fn chmod_pex(&mut self, entry: &FsEntry, mode: &str) -> Option<(u8, u8, u8)> {
+        match parse_unix_pex(mode, entry.get_unix_pex()) {
+            Err(err) => {
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!("Invalid mode \"{}\": {}", mode, err),
+                );
+                None
+            }
+            Ok(pex) if self.dry_run() => {
+                self.log_dry_run(&[format!(
+                    "change mode for \"{}\" to {}{}{}",
+                    entry.get_abs_path().display(),
+                    pex.0,
+                    pex.1,
+                    pex.2
+                )]);
+                None
+            }
+            Ok(pex) => Some(pex),
+        }
+    }
+
+    /// ### log_chmod
+    ///
+    /// Log the mode change for `entry`
+    fn log_chmod(&mut self, entry: &FsEntry, pex: (u8, u8, u8)) {
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Changed mode for \"{}\" to {}{}{}",
+                entry.get_abs_path().display(),
+                pex.0,
+                pex.1,
+                pex.2
+            ),
+        );
+    }
+}
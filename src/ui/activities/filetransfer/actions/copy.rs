@@ -26,9 +26,14 @@
  * SOFTWARE.
  */
 // locals
+use super::super::lib::pool::run_jobs;
+use super::super::lib::sync::SyncOp;
 use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry, TransferPayload};
+use crate::filetransfer::read_only::Operation;
 use crate::filetransfer::FileTransferErrorType;
 use crate::fs::FsFile;
+use crate::host::Localhost;
+use crate::utils::file::{duplicate_path, numbered_path};
 use std::path::{Path, PathBuf};
 
 impl FileTransferActivity {
@@ -36,22 +41,36 @@ impl FileTransferActivity {
     ///
     /// Copy file on local
     pub(crate) fn action_local_copy(&mut self, input: String) {
+        if self.deny_read_only(Operation::Copy) {
+            return;
+        }
+        // Relative destinations are resolved against the local working directory
+        let dest: PathBuf = self.local_to_abs_path(Path::new(&input));
+        if self.dry_run() {
+            let selected = self.get_local_selected_entries();
+            self.log_dry_run_copy(selected, dest.as_path());
+            return;
+        }
         match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => {
-                let dest_path: PathBuf = PathBuf::from(input);
+                let dest_path: PathBuf = self.local_copy_dest(&entry, dest);
                 self.local_copy_file(&entry, dest_path.as_path());
                 // Reload entries
                 self.reload_local_dir();
             }
             SelectedEntry::Many(entries) => {
                 // Try to copy each file to Input/{FILE_NAME}
-                let base_path: PathBuf = PathBuf::from(input);
-                // Iter files
-                for entry in entries.iter() {
-                    let mut dest_path: PathBuf = base_path.clone();
-                    dest_path.push(entry.get_name());
-                    self.local_copy_file(entry, dest_path.as_path());
-                }
+                let base_path: PathBuf = dest;
+                let jobs: Vec<(FsEntry, PathBuf)> = entries
+                    .into_iter()
+                    .map(|x| {
+                        let mut dest_path: PathBuf = base_path.clone();
+                        dest_path.push(x.get_name());
+                        let dest_path: PathBuf = self.local_copy_dest(&x, dest_path);
+                        (x, dest_path)
+                    })
+                    .collect();
+                self.local_copy_many(jobs);
                 // Reload entries
                 self.reload_local_dir();
             }
@@ -63,20 +82,31 @@ impl FileTransferActivity {
     ///
     /// Copy file on remote
     pub(crate) fn action_remote_copy(&mut self, input: String) {
+        if self.deny_read_only(Operation::Copy) {
+            return;
+        }
+        // Relative destinations are resolved against the remote working directory
+        let dest: PathBuf = self.remote_to_abs_path(Path::new(&input));
+        if self.dry_run() {
+            let selected = self.get_remote_selected_entries();
+            self.log_dry_run_copy(selected, dest.as_path());
+            return;
+        }
         match self.get_remote_selected_entries() {
             SelectedEntry::One(entry) => {
-                let dest_path: PathBuf = PathBuf::from(input);
+                let dest_path: PathBuf = self.remote_copy_dest(&entry, dest);
                 self.remote_copy_file(entry, dest_path.as_path());
                 // Reload entries
                 self.reload_remote_dir();
             }
             SelectedEntry::Many(entries) => {
                 // Try to copy each file to Input/{FILE_NAME}
-                let base_path: PathBuf = PathBuf::from(input);
+                let base_path: PathBuf = dest;
                 // Iter files
                 for entry in entries.into_iter() {
                     let mut dest_path: PathBuf = base_path.clone();
                     dest_path.push(entry.get_name());
+                    let dest_path: PathBuf = self.remote_copy_dest(&entry, dest_path);
                     self.remote_copy_file(entry, dest_path.as_path());
                 }
                 // Reload entries
@@ -86,6 +116,100 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### action_local_duplicate
+    ///
+    /// Duplicate the selected entries in the local working directory, naming each copy `name copy.ext`
+    pub(crate) fn action_local_duplicate(&mut self) {
+        if self.deny_read_only(Operation::Copy) {
+            return;
+        }
+        let jobs: Vec<(FsEntry, PathBuf)> =
+            Self::selected_entries_list(self.get_local_selected_entries())
+                .into_iter()
+                .map(|x| {
+                    let dest: PathBuf =
+                        duplicate_path(x.get_abs_path().as_path(), x.is_dir(), |p| p.exists());
+                    (x, dest)
+                })
+                .collect();
+        if self.dry_run() {
+            let ops: Vec<SyncOp> = jobs.into_iter().map(|(x, y)| SyncOp::Copy(x, y)).collect();
+            self.log_dry_run(&ops);
+            return;
+        }
+        if !jobs.is_empty() {
+            self.local_copy_many(jobs);
+            self.reload_local_dir();
+        }
+    }
+
+    /// ### action_remote_duplicate
+    ///
+    /// Duplicate the selected entries in the remote working directory, naming each copy `name copy.ext`
+    pub(crate) fn action_remote_duplicate(&mut self) {
+        if self.deny_read_only(Operation::Copy) {
+            return;
+        }
+        let entries: Vec<FsEntry> = Self::selected_entries_list(self.get_remote_selected_entries());
+        if entries.is_empty() {
+            return;
+        }
+        let mut jobs: Vec<(FsEntry, PathBuf)> = Vec::with_capacity(entries.len());
+        for entry in entries.into_iter() {
+            let client = self.sessions.client.as_mut();
+            let dest: PathBuf =
+                duplicate_path(entry.get_abs_path().as_path(), entry.is_dir(), |p| {
+                    client.stat(p).is_ok()
+                });
+            jobs.push((entry, dest));
+        }
+        if self.dry_run() {
+            let ops: Vec<SyncOp> = jobs.into_iter().map(|(x, y)| SyncOp::Copy(x, y)).collect();
+            self.log_dry_run(&ops);
+            return;
+        }
+        for (entry, dest) in jobs.into_iter() {
+            self.remote_copy_file(entry, dest.as_path());
+        }
+        self.reload_remote_dir();
+    }
+
+    /// ### selected_entries_list
+    ///
+    /// Get selected entries as a list
+    fn selected_entries_list(selected: SelectedEntry) -> Vec<FsEntry> {
+        match selected {
+            SelectedEntry::One(entry) => vec![entry],
+            SelectedEntry::Many(entries) => entries,
+            SelectedEntry::None => Vec::new(),
+        }
+    }
+
+    /// ### local_copy_dest
+    ///
+    /// Resolve a conflict between `entry` and the copy destination: when copying an entry onto itself,
+    /// the copy gets renamed to `name (n).ext`
+    fn local_copy_dest(&self, entry: &FsEntry, dest: PathBuf) -> PathBuf {
+        match entry.get_abs_path() == dest {
+            true => numbered_path(dest.as_path(), |p| p.exists()),
+            false => dest,
+        }
+    }
+
+    /// ### remote_copy_dest
+    ///
+    /// Resolve a conflict between `entry` and the copy destination on remote: when copying an entry
+    /// onto itself, the copy gets renamed to `name (n).ext`
+    fn remote_copy_dest(&mut self, entry: &FsEntry, dest: PathBuf) -> PathBuf {
+        match entry.get_abs_path() == dest {
+            true => {
+                let client = self.sessions.client.as_mut();
+                numbered_path(dest.as_path(), |p| client.stat(p).is_ok())
+            }
+            false => dest,
+        }
+    }
+
     fn local_copy_file(&mut self, entry: &FsEntry, dest: &Path) {
         match self.host.copy(entry, dest) {
             Ok(_) => {
@@ -110,40 +234,76 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### local_copy_many
+    ///
+    /// Copy many entries on local, running up to `max_parallel_transfers` copies at the same time
+    fn local_copy_many(&mut self, jobs: Vec<(FsEntry, PathBuf)>) {
+        let workers: usize = self.max_parallel_transfers();
+        let host: &Localhost = &self.host;
+        let results: Vec<Result<String, String>> = run_jobs(jobs, workers, |(entry, dest)| {
+            match host.copy_entry(&entry, dest.as_path()) {
+                Ok(_) => Ok(format!(
+                    "Copied \"{}\" to \"{}\"",
+                    entry.get_abs_path().display(),
+                    dest.display()
+                )),
+                Err(err) => Err(format!(
+                    "Could not copy \"{}\" to \"{}\": {}",
+                    entry.get_abs_path().display(),
+                    dest.display(),
+                    err
+                )),
+            }
+        });
+        let mut errors: Vec<String> = Vec::new();
+        for result in results.into_iter() {
+            match result {
+                Ok(msg) => self.log(LogLevel::Info, msg),
+                Err(err) => errors.push(err),
+            }
+        }
+        if !errors.is_empty() {
+            self.log_errors_and_alert("Copy", errors);
+        }
+    }
+
     fn remote_copy_file(&mut self, entry: FsEntry, dest: &Path) {
-        match self.client.as_mut().copy(&entry, dest) {
+        let src: PathBuf = entry.get_abs_path();
+        match self.remote_copy_entry(entry, dest) {
             Ok(_) => {
                 self.log(
                     LogLevel::Info,
-                    format!(
-                        "Copied \"{}\" to \"{}\"",
-                        entry.get_abs_path().display(),
-                        dest.display()
-                    ),
+                    format!("Copied \"{}\" to \"{}\"", src.display(), dest.display()),
                 );
             }
+            Err(err) => self.log_and_alert(LogLevel::Error, err),
+        }
+    }
+
+    /// ### remote_copy_entry
+    ///
+    /// Copy `entry` to `dest` on remote; if copy is not supported by the protocol, the tricky copy is performed
+    pub(super) fn remote_copy_entry(&mut self, entry: FsEntry, dest: &Path) -> Result<(), String> {
+        match self.sessions.client.as_mut().copy(&entry, dest) {
+            Ok(_) => Ok(()),
             Err(err) => match err.kind() {
-                FileTransferErrorType::UnsupportedFeature => {
-                    // If copy is not supported, perform the tricky copy
-                    self.tricky_copy(entry, dest);
-                }
-                _ => self.log_and_alert(
-                    LogLevel::Error,
-                    format!(
-                        "Could not copy \"{}\" to \"{}\": {}",
-                        entry.get_abs_path().display(),
-                        dest.display(),
-                        err
-                    ),
-                ),
+                // If copy is not supported, perform the tricky copy
+                FileTransferErrorType::UnsupportedFeature => self.tricky_copy(entry, dest),
+                _ => Err(format!(
+                    "Could not copy \"{}\" to \"{}\": {}",
+                    entry.get_abs_path().display(),
+                    dest.display(),
+                    err
+                )),
             },
         }
     }
 
     /// ### tricky_copy
     ///
-    /// Tricky copy will be used whenever copy command is not available on remote host
-    fn tricky_copy(&mut self, entry: FsEntry, dest: &Path) {
+    /// Tricky copy will be used whenever copy command is not available on remote host:
+    /// the entry is downloaded to a temporary path and then uploaded to `dest`
+    fn tricky_copy(&mut self, entry: FsEntry, dest: &Path) -> Result<(), String> {
         // match entry
         match entry {
             FsEntry::File(entry) => {
@@ -151,11 +311,10 @@ impl FileTransferActivity {
                 let tmpfile: tempfile::NamedTempFile = match tempfile::NamedTempFile::new() {
                     Ok(f) => f,
                     Err(err) => {
-                        self.log_and_alert(
-                            LogLevel::Error,
-                            format!("Copy failed: could not create temporary file: {}", err),
-                        );
-                        return;
+                        return Err(format!(
+                            "Copy failed: could not create temporary file: {}",
+                            err
+                        ));
                     }
                 };
                 // Download file
@@ -164,25 +323,25 @@ impl FileTransferActivity {
                 if let Err(err) =
                     self.filetransfer_recv(TransferPayload::File(entry), tmpfile.path(), Some(name))
                 {
-                    self.log_and_alert(
-                        LogLevel::Error,
-                        format!("Copy failed: could not download to temporary file: {}", err),
-                    );
-                    return;
+                    return Err(format!(
+                        "Copy failed: could not download to temporary file: {}",
+                        err
+                    ));
+                }
+                if !self.transfer_completed() {
+                    return Err(String::from(
+                        "Copy failed: could not download to temporary file",
+                    ));
                 }
                 // Get local fs entry
                 let tmpfile_entry: FsFile = match self.host.stat(tmpfile.path()) {
                     Ok(e) => e.unwrap_file(),
                     Err(err) => {
-                        self.log_and_alert(
-                            LogLevel::Error,
-                            format!(
-                                "Copy failed: could not stat \"{}\": {}",
-                                tmpfile.path().display(),
-                                err
-                            ),
-                        );
-                        return;
+                        return Err(format!(
+                            "Copy failed: could not stat \"{}\": {}",
+                            tmpfile.path().display(),
+                            err
+                        ));
                     }
                 };
                 // Upload file to destination
@@ -192,26 +351,27 @@ impl FileTransferActivity {
                     wrkdir.as_path(),
                     Some(String::from(dest.to_string_lossy())),
                 ) {
-                    self.log_and_alert(
-                        LogLevel::Error,
-                        format!(
-                            "Copy failed: could not write file {}: {}",
-                            entry_path.display(),
-                            err
-                        ),
-                    );
-                    return;
+                    return Err(format!(
+                        "Copy failed: could not write file {}: {}",
+                        entry_path.display(),
+                        err
+                    ));
+                }
+                if !self.transfer_completed() {
+                    return Err(format!(
+                        "Copy failed: could not write file {}",
+                        entry_path.display()
+                    ));
                 }
             }
             FsEntry::Directory(_) => {
                 let tempdir: tempfile::TempDir = match tempfile::TempDir::new() {
                     Ok(d) => d,
                     Err(err) => {
-                        self.log_and_alert(
-                            LogLevel::Error,
-                            format!("Copy failed: could not create temporary directory: {}", err),
-                        );
-                        return;
+                        return Err(format!(
+                            "Copy failed: could not create temporary directory: {}",
+                            err
+                        ));
                     }
                 };
                 // Get path of dest
@@ -221,25 +381,20 @@ impl FileTransferActivity {
                 if let Err(err) =
                     self.filetransfer_recv(TransferPayload::Any(entry), tempdir.path(), None)
                 {
-                    self.log_and_alert(
-                        LogLevel::Error,
-                        format!("Copy failed: failed to download file: {}", err),
-                    );
-                    return;
+                    return Err(format!("Copy failed: failed to download file: {}", err));
+                }
+                if !self.transfer_completed() {
+                    return Err(String::from("Copy failed: failed to download file"));
                 }
                 // Stat dir
                 let tempdir_entry: FsEntry = match self.host.stat(tempdir_path.as_path()) {
                     Ok(e) => e,
                     Err(err) => {
-                        self.log_and_alert(
-                            LogLevel::Error,
-                            format!(
-                                "Copy failed: could not stat \"{}\": {}",
-                                tempdir.path().display(),
-                                err
-                            ),
-                        );
-                        return;
+                        return Err(format!(
+                            "Copy failed: could not stat \"{}\": {}",
+                            tempdir.path().display(),
+                            err
+                        ));
                     }
                 };
                 // Upload to destination
@@ -249,13 +404,20 @@ impl FileTransferActivity {
                     wrkdir.as_path(),
                     Some(String::from(dest.to_string_lossy())),
                 ) {
-                    self.log_and_alert(
-                        LogLevel::Error,
-                        format!("Copy failed: failed to send file: {}", err),
-                    );
-                    return;
+                    return Err(format!("Copy failed: failed to send file: {}", err));
+                }
+                if !self.transfer_completed() {
+                    return Err(String::from("Copy failed: failed to send file"));
                 }
             }
         }
+        Ok(())
+    }
+
+    /// ### transfer_completed
+    ///
+    /// Returns whether the last transfer has been completed, without being aborted and without failures
+    fn transfer_completed(&self) -> bool {
+        !self.transfer.aborted() && self.transfer.report.failed().is_empty()
     }
 }
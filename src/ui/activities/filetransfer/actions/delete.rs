@@ -27,9 +27,18 @@
  */
 // locals
 use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry};
+use crate::filetransfer::read_only::Operation;
 
 impl FileTransferActivity {
     pub(crate) fn action_local_delete(&mut self) {
+        if self.deny_read_only(Operation::Delete) {
+            return;
+        }
+        if self.dry_run() {
+            let selected = self.get_local_selected_entries();
+            self.log_dry_run_delete(selected);
+            return;
+        }
         match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => {
                 // Delete file
@@ -51,6 +60,14 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_remote_delete(&mut self) {
+        if self.deny_read_only(Operation::Delete) {
+            return;
+        }
+        if self.dry_run() {
+            let selected = self.get_remote_selected_entries();
+            self.log_dry_run_delete(selected);
+            return;
+        }
         match self.get_remote_selected_entries() {
             SelectedEntry::One(entry) => {
                 // Delete file
@@ -94,7 +111,7 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn remote_remove_file(&mut self, entry: &FsEntry) {
-        match self.client.remove(&entry) {
+        match self.sessions.client.remove(entry) {
             Ok(_) => {
                 self.log(
                     LogLevel::Info,
@@ -102,13 +119,13 @@ impl FileTransferActivity {
                 );
             }
             Err(err) => {
-                self.log_and_alert(
+                self.log_and_alert_remote(
                     LogLevel::Error,
                     format!(
-                        "Could not delete file \"{}\": {}",
-                        entry.get_abs_path().display(),
-                        err
+                        "Could not delete file \"{}\"",
+                        entry.get_abs_path().display()
                     ),
+                    &err,
                 );
             }
         }
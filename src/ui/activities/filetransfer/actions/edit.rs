@@ -27,6 +27,7 @@
  */
 // locals
 use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry, TransferPayload};
+use crate::filetransfer::read_only::Operation;
 use crate::fs::FsFile;
 // ext
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
@@ -37,6 +38,9 @@ use std::time::SystemTime;
 
 impl FileTransferActivity {
     pub(crate) fn action_edit_local_file(&mut self) {
+        if self.deny_read_only(Operation::Edit) {
+            return;
+        }
         let entries: Vec<FsEntry> = match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => vec![entry],
             SelectedEntry::Many(entries) => entries,
@@ -45,7 +49,7 @@ impl FileTransferActivity {
         // Edit all entries
         for entry in entries.iter() {
             // Check if file
-            if entry.is_file() {
+            if entry.is_file() && self.editable_file(entry.get_name(), entry.get_size()) {
                 self.log(
                     LogLevel::Info,
                     format!("Opening file \"{}\"…", entry.get_abs_path().display()),
@@ -61,6 +65,9 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_edit_remote_file(&mut self) {
+        if self.deny_read_only(Operation::Edit) {
+            return;
+        }
         let entries: Vec<FsEntry> = match self.get_remote_selected_entries() {
             SelectedEntry::One(entry) => vec![entry],
             SelectedEntry::Many(entries) => entries,
@@ -70,6 +77,9 @@ impl FileTransferActivity {
         for entry in entries.into_iter() {
             // Check if file
             if let FsEntry::File(file) = entry {
+                if !self.editable_file(file.name.as_str(), file.size) {
+                    continue;
+                }
                 self.log(
                     LogLevel::Info,
                     format!("Opening file \"{}\"…", file.abs_path.display()),
@@ -84,6 +94,15 @@ impl FileTransferActivity {
         self.reload_remote_dir();
     }
 
+    /// ### editable_file
+    ///
+    /// Returns whether the file `name`, which is `size` bytes long, can be opened in the text editor.
+    /// If the file exceeds the edit size limit, the user is asked whether to open it anyway
+    fn editable_file(&mut self, name: &str, size: usize) -> bool {
+        let limit: u64 = self.max_edit_bytes();
+        self.check_size_limit(name, size as u64, limit)
+    }
+
     /// ### edit_local_file
     ///
     /// Edit a file on localhost
@@ -27,9 +27,14 @@
  */
 // locals
 use super::{FileTransferActivity, LogLevel};
+use crate::filetransfer::read_only::Operation;
+use crate::filetransfer::FileTransferProtocol;
 
 impl FileTransferActivity {
     pub(crate) fn action_local_exec(&mut self, input: String) {
+        if self.deny_read_only(Operation::Exec) {
+            return;
+        }
         match self.host.exec(input.as_str()) {
             Ok(output) => {
                 // Reload files
@@ -48,10 +53,29 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_remote_exec(&mut self, input: String) {
-        match self.client.as_mut().exec(input.as_str()) {
+        if self.deny_read_only(Operation::Exec) {
+            return;
+        }
+        // Commands can be executed only through SSH sessions
+        let protocol: FileTransferProtocol = self.context().ft_params().unwrap().protocol;
+        if !protocol.supports_exec() {
+            self.log_and_alert(
+                LogLevel::Warn,
+                format!(
+                    "Executing commands is not supported with {}",
+                    protocol.to_string()
+                ),
+            );
+            return;
+        }
+        match self.sessions.client.as_mut().exec_output(input.as_str()) {
             Ok(output) => {
+                self.log(
+                    LogLevel::Info,
+                    format!("\"{}\" exited with code {}", input, output.exit_code),
+                );
+                self.mount_exec_output(format!("Output of \"{}\"", input), output.lines(&input));
                 // Reload files
-                self.log(LogLevel::Info, format!("\"{}\": {}", input, output));
                 self.reload_remote_dir();
             }
             Err(err) => {
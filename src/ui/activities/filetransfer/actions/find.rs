@@ -27,35 +27,83 @@
  */
 // locals
 use super::super::browser::FileExplorerTab;
+use super::super::lib::find::SearchWalk;
 use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry, TransferPayload};
+use crate::filetransfer::read_only::Operation;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 impl FileTransferActivity {
-    pub(crate) fn action_local_find(&mut self, input: String) -> Result<Vec<FsEntry>, String> {
-        match self.host.find(input.as_str()) {
-            Ok(entries) => Ok(entries),
-            Err(err) => Err(format!("Could not search for files: {}", err)),
-        }
+    pub(crate) fn action_local_find(&mut self, input: String) -> Vec<FsEntry> {
+        let root: PathBuf = self.local().wrkdir.clone();
+        self.find_walk(root.as_path(), input.as_str(), |activity, dir| {
+            activity.host.scan_dir(dir).map_err(|e| e.to_string())
+        })
+    }
+
+    pub(crate) fn action_remote_find(&mut self, input: String) -> Vec<FsEntry> {
+        let root: PathBuf = self.remote().wrkdir.clone();
+        self.find_walk(root.as_path(), input.as_str(), |activity, dir| {
+            activity
+                .sessions
+                .client
+                .list_dir(dir)
+                .map_err(|e| e.to_string())
+        })
     }
 
-    pub(crate) fn action_remote_find(&mut self, input: String) -> Result<Vec<FsEntry>, String> {
-        match self.client.as_mut().find(input.as_str()) {
-            Ok(entries) => Ok(entries),
-            Err(err) => Err(format!("Could not search for files: {}", err)),
+    /// ### find_walk
+    ///
+    /// Walk the tree starting from `root`, collecting the entries matching `search`.
+    /// Input events are handled during the walk, so that it can be cancelled with <CTRL+C>;
+    /// in that case the entries found so far are returned.
+    fn find_walk<F>(&mut self, root: &Path, search: &str, mut list_dir: F) -> Vec<FsEntry>
+    where
+        F: FnMut(&mut Self, &Path) -> Result<Vec<FsEntry>, String>,
+    {
+        self.transfer.reset();
+        self.mount_wait(format!("Searching for \"{}\"… (<CTRL+C> to cancel)", search).as_str());
+        self.view();
+        let max_depth: usize = self.config().get_find_max_depth();
+        let mut walk: SearchWalk = SearchWalk::new(root, search, max_depth);
+        let mut last_input_event_fetch: Instant = Instant::now();
+        while !walk.done() && !self.transfer.aborted() {
+            walk.step(|dir| list_dir(self, dir));
+            // Handle input events (each 100ms)
+            if last_input_event_fetch.elapsed() >= Duration::from_millis(100) {
+                last_input_event_fetch = Instant::now();
+                self.read_input_event();
+            }
         }
+        self.umount_wait();
+        for err in walk.errors.into_iter() {
+            self.log(LogLevel::Warn, err);
+        }
+        if self.transfer.aborted() {
+            self.log(
+                LogLevel::Warn,
+                format!(
+                    "Search for \"{}\" cancelled; {} entries found so far",
+                    search,
+                    walk.found.len()
+                ),
+            );
+        }
+        walk.found
     }
 
-    pub(crate) fn action_find_changedir(&mut self) {
+    /// ### action_find_changedir
+    ///
+    /// Change directory to the parent of the selected entry.
+    /// Returns the path of the entry, so that it can be highlighted in the explorer
+    pub(crate) fn action_find_changedir(&mut self) -> Option<PathBuf> {
         // Match entry
         if let SelectedEntry::One(entry) = self.get_found_selected_entries() {
-            // Get path: if a directory, use directory path; if it is a File, get parent path
-            let path: PathBuf = match entry {
-                FsEntry::Directory(dir) => dir.abs_path,
-                FsEntry::File(file) => match file.abs_path.parent() {
-                    None => PathBuf::from("."),
-                    Some(p) => p.to_path_buf(),
-                },
+            let entry_path: PathBuf = entry.get_abs_path();
+            let path: PathBuf = match entry_path.parent() {
+                None => PathBuf::from("."),
+                Some(p) => p.to_path_buf(),
             };
             // Change directory
             match self.browser.tab() {
@@ -66,10 +114,20 @@ impl FileTransferActivity {
                     self.remote_changedir(path.as_path(), true)
                 }
             }
+            Some(entry_path)
+        } else {
+            None
         }
     }
 
     pub(crate) fn action_find_transfer(&mut self, save_as: Option<String>) {
+        if matches!(
+            self.browser.tab(),
+            FileExplorerTab::FindLocal | FileExplorerTab::Local
+        ) && self.deny_read_only(Operation::Upload)
+        {
+            return;
+        }
         let wrkdir: PathBuf = match self.browser.tab() {
             FileExplorerTab::FindLocal | FileExplorerTab::Local => self.remote().wrkdir.clone(),
             FileExplorerTab::FindRemote | FileExplorerTab::Remote => self.local().wrkdir.clone(),
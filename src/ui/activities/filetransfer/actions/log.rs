@@ -0,0 +1,62 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::lib::logview::{fmt_record, osc52_copy};
+use super::{FileTransferActivity, LogLevel};
+// ext
+use std::io::{self, Write};
+
+impl FileTransferActivity {
+    /// ### action_copy_log
+    ///
+    /// Copy the log records matching the current filter to the clipboard, from the oldest to the newest
+    pub(crate) fn action_copy_log(&mut self) {
+        let lines: Vec<String> = self
+            .log_records
+            .iter()
+            .rev()
+            .filter(|x| self.log_filter.matches(&x.level))
+            .map(fmt_record)
+            .collect();
+        let sequence: String = osc52_copy(lines.join("\n").as_str());
+        let mut stdout = io::stdout();
+        match stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|_| stdout.flush())
+        {
+            Ok(_) => self.log(
+                LogLevel::Info,
+                format!("Copied {} log lines to clipboard", lines.len()),
+            ),
+            Err(err) => self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not copy log to clipboard: {}", err),
+            ),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::browser::FileExplorerTab;
+use super::FileTransferActivity;
+use crate::fs::explorer::FileExplorer;
+
+/// ## MarkOp
+///
+/// Describes how to change the marked entries of an explorer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum MarkOp {
+    Toggle, // Mark or unmark the highlighted entry
+    All,    // Mark all the entries
+    Invert, // Invert the marked entries
+}
+
+impl FileTransferActivity {
+    /// ### action_mark
+    ///
+    /// Change the marked entries of the explorer in use
+    pub(crate) fn action_mark(&mut self, op: MarkOp) {
+        let idx: Option<usize> = self.get_selected_index(self.explorer_component());
+        let explorer: &mut FileExplorer = match self.browser.tab() {
+            FileExplorerTab::Local => self.local_mut(),
+            FileExplorerTab::Remote => self.remote_mut(),
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => match self.found_mut() {
+                Some(explorer) => explorer,
+                None => return,
+            },
+        };
+        match (op, idx) {
+            (MarkOp::Toggle, Some(idx)) => explorer.toggle_mark(idx),
+            (MarkOp::Toggle, None) => {}
+            (MarkOp::All, _) => explorer.mark_all(),
+            (MarkOp::Invert, _) => explorer.invert_marks(),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::lib::sync::{plan_mirror, walk_tree, SyncDirection, SyncOp};
+use super::{FileTransferActivity, LogLevel};
+use crate::filetransfer::read_only::Operation;
+use crate::filetransfer::FileTransfer;
+use crate::host::Localhost;
+use std::path::{Path, PathBuf};
+
+/// ## MirrorPlan
+///
+/// Describes what to do to mirror the working directories
+#[derive(Debug)]
+enum MirrorPlan {
+    Aborted(Vec<String>), // Trees couldn't be scanned; errors encountered
+    DryRun(Vec<SyncOp>),  // Operations must only be reported
+    Execute(Vec<SyncOp>), // Operations must be performed
+}
+
+impl FileTransferActivity {
+    /// ### action_local_mirror
+    ///
+    /// Mirror local working directory onto remote working directory
+    pub(crate) fn action_local_mirror(&mut self) {
+        self.action_mirror(SyncDirection::Upload);
+    }
+
+    /// ### action_remote_mirror
+    ///
+    /// Mirror remote working directory onto local working directory
+    pub(crate) fn action_remote_mirror(&mut self) {
+        self.action_mirror(SyncDirection::Download);
+    }
+
+    fn action_mirror(&mut self, direction: SyncDirection) {
+        let delete: bool = self.context().transfer_opts().delete;
+        // Mirroring onto localhost is a download, unless entries must be deleted
+        let op: Operation = match (direction, delete) {
+            (SyncDirection::Upload, _) => Operation::Upload,
+            (SyncDirection::Download, true) => Operation::Delete,
+            (SyncDirection::Download, false) => Operation::Download,
+        };
+        if self.deny_read_only(op) {
+            return;
+        }
+        let local_wrkdir: PathBuf = self.local().wrkdir.clone();
+        let remote_wrkdir: PathBuf = self.remote().wrkdir.clone();
+        let dest_root: PathBuf = match direction {
+            SyncDirection::Upload => remote_wrkdir.clone(),
+            SyncDirection::Download => local_wrkdir.clone(),
+        };
+        let dry_run: bool = self.dry_run();
+        let ops: Vec<SyncOp> = match Self::plan_mirror_job(
+            &self.host,
+            self.sessions.client.as_mut(),
+            local_wrkdir.as_path(),
+            remote_wrkdir.as_path(),
+            direction,
+            delete,
+            dry_run,
+        ) {
+            MirrorPlan::Aborted(errors) => {
+                let count: usize = errors.len();
+                for err in errors.into_iter() {
+                    self.log(LogLevel::Error, err);
+                }
+                self.log_and_alert(
+                    LogLevel::Error,
+                    format!(
+                        "Mirror aborted: {} directories couldn't be scanned; see log for details",
+                        count
+                    ),
+                );
+                return;
+            }
+            MirrorPlan::DryRun(ops) => {
+                self.log_dry_run(&ops);
+                return;
+            }
+            MirrorPlan::Execute(ops) => ops,
+        };
+        // Execute
+        let errors: Vec<String> = self.filetransfer_mirror(&ops, direction);
+        match errors.is_empty() {
+            true => self.log(
+                LogLevel::Info,
+                format!(
+                    "Mirrored working directory onto \"{}\"",
+                    dest_root.display()
+                ),
+            ),
+            false => self.log_errors_and_alert("Mirror", errors),
+        }
+    }
+
+    /// ### plan_mirror_job
+    ///
+    /// Walk the local and remote working directories and plan the operations to mirror them.
+    /// Only directories are listed: nothing is changed on either side
+    fn plan_mirror_job(
+        host: &Localhost,
+        client: &mut dyn FileTransfer,
+        local_wrkdir: &Path,
+        remote_wrkdir: &Path,
+        direction: SyncDirection,
+        delete: bool,
+        dry_run: bool,
+    ) -> MirrorPlan {
+        // Walk trees
+        let (local_tree, mut errors) = walk_tree(local_wrkdir, |p| {
+            host.scan_dir(p).map_err(|x| x.to_string())
+        });
+        let (remote_tree, remote_errors) = walk_tree(remote_wrkdir, |p| {
+            client.list_dir(p).map_err(|x| x.to_string())
+        });
+        errors.extend(remote_errors);
+        // Don't plan from a partial tree: unscanned directories would look missing and be deleted
+        if !errors.is_empty() {
+            return MirrorPlan::Aborted(errors);
+        }
+        // Plan
+        let ops: Vec<SyncOp> = match direction {
+            SyncDirection::Upload => plan_mirror(&local_tree, &remote_tree, remote_wrkdir, delete),
+            SyncDirection::Download => plan_mirror(&remote_tree, &local_tree, local_wrkdir, delete),
+        };
+        match dry_run {
+            true => MirrorPlan::DryRun(ops),
+            false => MirrorPlan::Execute(ops),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::test_helpers::{make_file_at, make_fsentry, MockFileTransfer};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ui_activities_filetransfer_actions_mirror_dry_run() {
+        // Local tree has a new file; remote one has an extraneous file
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        assert!(make_file_at(tmpdir.path(), "README.md").is_ok());
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).ok().unwrap();
+        let mut client: MockFileTransfer = MockFileTransfer::default().dir(
+            Path::new("/home/omar"),
+            vec![make_fsentry(PathBuf::from("/home/omar/old.txt"), false)],
+        );
+        for direction in [SyncDirection::Upload, SyncDirection::Download] {
+            client.calls.clear();
+            match FileTransferActivity::plan_mirror_job(
+                &host,
+                &mut client,
+                tmpdir.path(),
+                Path::new("/home/omar"),
+                direction,
+                true,
+                true,
+            ) {
+                MirrorPlan::DryRun(ops) => assert_eq!(ops.len(), 2),
+                plan => panic!("unexpected plan: {:?}", plan),
+            }
+            // Remote has only been listed
+            assert_eq!(client.calls, vec![String::from("list_dir")]);
+        }
+        // Local tree is untouched
+        assert!(tmpdir.path().join("README.md").exists());
+        assert_eq!(std::fs::read_dir(tmpdir.path()).unwrap().count(), 1);
+        // Without dry-run, operations are executed
+        let plan = FileTransferActivity::plan_mirror_job(
+            &host,
+            &mut client,
+            tmpdir.path(),
+            Path::new("/home/omar"),
+            SyncDirection::Upload,
+            true,
+            false,
+        );
+        assert!(matches!(plan, MirrorPlan::Execute(ops) if ops.len() == 2));
+        // Partial trees are not planned
+        let plan = FileTransferActivity::plan_mirror_job(
+            &host,
+            &mut client,
+            tmpdir.path(),
+            Path::new("/home/unknown"),
+            SyncDirection::Upload,
+            true,
+            true,
+        );
+        assert!(matches!(plan, MirrorPlan::Aborted(errors) if errors.len() == 1));
+    }
+}
@@ -27,10 +27,14 @@
  */
 // locals
 use super::{FileTransferActivity, LogLevel};
+use crate::filetransfer::read_only::Operation;
 use std::path::PathBuf;
 
 impl FileTransferActivity {
     pub(crate) fn action_local_mkdir(&mut self, input: String) {
+        if self.deny_read_only(Operation::Mkdir) {
+            return;
+        }
         match self.host.mkdir(PathBuf::from(input.as_str()).as_path()) {
             Ok(_) => {
                 // Reload files
@@ -48,7 +52,11 @@ impl FileTransferActivity {
         }
     }
     pub(crate) fn action_remote_mkdir(&mut self, input: String) {
+        if self.deny_read_only(Operation::Mkdir) {
+            return;
+        }
         match self
+            .sessions
             .client
             .as_mut()
             .mkdir(PathBuf::from(input.as_str()).as_path())
@@ -60,9 +68,10 @@ impl FileTransferActivity {
             }
             Err(err) => {
                 // Report err
-                self.log_and_alert(
+                self.log_and_alert_remote(
                     LogLevel::Error,
-                    format!("Could not create directory \"{}\": {}", input, err),
+                    format!("Could not create directory \"{}\"", input),
+                    &err,
                 );
             }
         }
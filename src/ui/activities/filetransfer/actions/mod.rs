@@ -25,22 +25,37 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use super::browser::FileExplorerTab;
+use super::lib::sync::SyncOp;
 pub(self) use super::{FileTransferActivity, FsEntry, LogLevel, TransferPayload};
+use crate::fs::explorer::FileExplorer;
+use std::path::Path;
 use tuirealm::{Payload, Value};
 
 // actions
 pub(crate) mod change_dir;
+pub(crate) mod chmod;
+pub(crate) mod compare;
 pub(crate) mod copy;
 pub(crate) mod delete;
 pub(crate) mod edit;
 pub(crate) mod exec;
 pub(crate) mod find;
+pub(crate) mod log;
+pub(crate) mod mark;
+pub(crate) mod mirror;
 pub(crate) mod mkdir;
 pub(crate) mod newfile;
 pub(crate) mod open;
+pub(crate) mod preview;
+pub(crate) mod pull;
 pub(crate) mod rename;
 pub(crate) mod save;
+pub(crate) mod scroll;
+pub(crate) mod sessions;
+pub(crate) mod sort;
 pub(crate) mod submit;
+pub(crate) mod symlink;
 
 #[derive(Debug)]
 pub(crate) enum SelectedEntry {
@@ -49,13 +64,6 @@ pub(crate) enum SelectedEntry {
     None,
 }
 
-#[derive(Debug)]
-enum SelectedEntryIndex {
-    One(usize),
-    Many(Vec<usize>),
-    None,
-}
-
 impl From<Option<&FsEntry>> for SelectedEntry {
     fn from(opt: Option<&FsEntry>) -> Self {
         match opt {
@@ -76,74 +84,95 @@ impl FileTransferActivity {
     ///
     /// Get local file entry
     pub(crate) fn get_local_selected_entries(&self) -> SelectedEntry {
-        match self.get_selected_index(super::COMPONENT_EXPLORER_LOCAL) {
-            SelectedEntryIndex::One(idx) => SelectedEntry::from(self.local().get(idx)),
-            SelectedEntryIndex::Many(files) => {
-                let files: Vec<&FsEntry> = files
-                    .iter()
-                    .map(|x| self.local().get(*x)) // Usize to Option<FsEntry>
-                    .flatten()
-                    .collect();
-                SelectedEntry::from(files)
-            }
-            SelectedEntryIndex::None => SelectedEntry::None,
-        }
+        let idx: Option<usize> = self.get_selected_index(super::COMPONENT_EXPLORER_LOCAL);
+        Self::get_selected_entries(self.local(), idx)
     }
 
     /// ### get_remote_selected_entries
     ///
     /// Get remote file entry
     pub(crate) fn get_remote_selected_entries(&self) -> SelectedEntry {
-        match self.get_selected_index(super::COMPONENT_EXPLORER_REMOTE) {
-            SelectedEntryIndex::One(idx) => SelectedEntry::from(self.remote().get(idx)),
-            SelectedEntryIndex::Many(files) => {
-                let files: Vec<&FsEntry> = files
-                    .iter()
-                    .map(|x| self.remote().get(*x)) // Usize to Option<FsEntry>
-                    .flatten()
-                    .collect();
-                SelectedEntry::from(files)
-            }
-            SelectedEntryIndex::None => SelectedEntry::None,
-        }
+        let idx: Option<usize> = self.get_selected_index(super::COMPONENT_EXPLORER_REMOTE);
+        Self::get_selected_entries(self.remote(), idx)
     }
 
-    /// ### get_remote_selected_entries
+    /// ### get_found_selected_entries
     ///
-    /// Get remote file entry
+    /// Get file entry from find result
     pub(crate) fn get_found_selected_entries(&self) -> SelectedEntry {
-        match self.get_selected_index(super::COMPONENT_EXPLORER_FIND) {
-            SelectedEntryIndex::One(idx) => {
-                SelectedEntry::from(self.found().as_ref().unwrap().get(idx))
-            }
-            SelectedEntryIndex::Many(files) => {
-                let files: Vec<&FsEntry> = files
-                    .iter()
-                    .map(|x| self.found().as_ref().unwrap().get(*x)) // Usize to Option<FsEntry>
-                    .flatten()
-                    .collect();
-                SelectedEntry::from(files)
-            }
-            SelectedEntryIndex::None => SelectedEntry::None,
+        let idx: Option<usize> = self.get_selected_index(super::COMPONENT_EXPLORER_FIND);
+        match self.found() {
+            Some(found) => Self::get_selected_entries(found, idx),
+            None => SelectedEntry::None,
         }
     }
 
+    /// ### log_dry_run_copy
+    ///
+    /// Log the copies which would be performed for `selected` in dry-run mode.
+    /// If one entry is selected `dest` is its destination path, otherwise it's the destination directory
+    pub(crate) fn log_dry_run_copy(&mut self, selected: SelectedEntry, dest: &Path) {
+        let ops: Vec<SyncOp> = match selected {
+            SelectedEntry::One(entry) => vec![SyncOp::Copy(entry, dest.to_path_buf())],
+            SelectedEntry::Many(entries) => entries
+                .into_iter()
+                .map(|x| {
+                    let dest_path = dest.join(x.get_name());
+                    SyncOp::Copy(x, dest_path)
+                })
+                .collect(),
+            SelectedEntry::None => Vec::new(),
+        };
+        self.log_dry_run(&ops);
+    }
+
+    /// ### log_dry_run_delete
+    ///
+    /// Log the entries which would be removed in dry-run mode
+    pub(crate) fn log_dry_run_delete(&mut self, selected: SelectedEntry) {
+        let ops: Vec<SyncOp> = match selected {
+            SelectedEntry::One(entry) => vec![SyncOp::Delete(entry)],
+            SelectedEntry::Many(entries) => entries.into_iter().map(SyncOp::Delete).collect(),
+            SelectedEntry::None => Vec::new(),
+        };
+        self.log_dry_run(&ops);
+    }
+
     // -- private
 
-    fn get_selected_index(&self, component: &str) -> SelectedEntryIndex {
+    /// ### get_selected_entries
+    ///
+    /// Get the marked entries of `explorer`; if none is marked, the entry at `idx` is returned
+    fn get_selected_entries(explorer: &FileExplorer, idx: Option<usize>) -> SelectedEntry {
+        let marked: Vec<&FsEntry> = explorer.marked_entries();
+        match (marked.is_empty(), idx) {
+            _ if explorer.is_empty() => SelectedEntry::None,
+            (false, _) => SelectedEntry::from(marked),
+            (true, Some(idx)) => SelectedEntry::from(explorer.get(idx)),
+            (true, None) => SelectedEntry::None,
+        }
+    }
+
+    /// ### get_selected_index
+    ///
+    /// Get the index of the entry highlighted in `component`
+    fn get_selected_index(&self, component: &str) -> Option<usize> {
         match self.view.get_state(component) {
-            Some(Payload::One(Value::Usize(idx))) => SelectedEntryIndex::One(idx),
-            Some(Payload::Vec(files)) => {
-                let list: Vec<usize> = files
-                    .iter()
-                    .map(|x| match x {
-                        Value::Usize(v) => *v,
-                        _ => 0,
-                    })
-                    .collect();
-                SelectedEntryIndex::Many(list)
+            Some(Payload::One(Value::Usize(idx))) => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// ### explorer_component
+    ///
+    /// Get the id of the explorer component in use
+    pub(super) fn explorer_component(&self) -> &'static str {
+        match self.browser.tab() {
+            FileExplorerTab::Local => super::COMPONENT_EXPLORER_LOCAL,
+            FileExplorerTab::Remote => super::COMPONENT_EXPLORER_REMOTE,
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => {
+                super::COMPONENT_EXPLORER_FIND
             }
-            _ => SelectedEntryIndex::None,
         }
     }
 }
@@ -26,23 +26,22 @@
  * SOFTWARE.
  */
 // locals
+use super::super::lib::newfile::{check_file_name, NewFilePolicy};
 use super::{FileTransferActivity, FsEntry, LogLevel};
-use std::path::PathBuf;
+use crate::filetransfer::read_only::Operation;
+use std::path::{Path, PathBuf};
 
 impl FileTransferActivity {
     pub(crate) fn action_local_newfile(&mut self, input: String) {
-        // Check if file exists
-        let mut file_exists: bool = false;
-        for file in self.local().iter_files_all() {
-            if input == file.get_name() {
-                file_exists = true;
-            }
+        if self.deny_read_only(Operation::NewFile) {
+            return;
         }
-        if file_exists {
-            self.log_and_alert(
-                LogLevel::Warn,
-                format!("File \"{}\" already exists", input,),
-            );
+        // Check if file exists
+        let file_exists: bool = self
+            .local()
+            .iter_files_all()
+            .any(|x| x.get_name() == input.as_str());
+        if !self.newfile_allowed(input.as_str(), file_exists) {
             return;
         }
         // Create file
@@ -63,18 +62,15 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_remote_newfile(&mut self, input: String) {
-        // Check if file exists
-        let mut file_exists: bool = false;
-        for file in self.remote().iter_files_all() {
-            if input == file.get_name() {
-                file_exists = true;
-            }
+        if self.deny_read_only(Operation::NewFile) {
+            return;
         }
-        if file_exists {
-            self.log_and_alert(
-                LogLevel::Warn,
-                format!("File \"{}\" already exists", input,),
-            );
+        // Check if file exists
+        let file_exists: bool = self
+            .remote()
+            .iter_files_all()
+            .any(|x| x.get_name() == input.as_str());
+        if !self.newfile_allowed(input.as_str(), file_exists) {
             return;
         }
         // Get path on remote
@@ -99,14 +95,18 @@ impl FileTransferActivity {
                 };
                 if let FsEntry::File(local_file) = local_file {
                     // Create file
-                    match self.client.send_file(&local_file, file_path.as_path()) {
+                    match self
+                        .sessions
+                        .client
+                        .send_file(&local_file, file_path.as_path())
+                    {
                         Err(err) => self.log_and_alert(
                             LogLevel::Error,
                             format!("Could not create file \"{}\": {}", file_path.display(), err),
                         ),
                         Ok(writer) => {
                             // Finalize write
-                            if let Err(err) = self.client.on_sent(writer) {
+                            if let Err(err) = self.sessions.client.on_sent(writer) {
                                 self.log_and_alert(
                                     LogLevel::Warn,
                                     format!("Could not finalize file: {}", err),
@@ -125,4 +125,33 @@ impl FileTransferActivity {
             }
         }
     }
+
+    /// ### newfile_allowed
+    ///
+    /// Returns whether the file `name` can be created; if the file already exists,
+    /// it gets replaced only if the user allows it, or if prompting is disabled in configuration
+    fn newfile_allowed(&mut self, name: &str, exists: bool) -> bool {
+        if let Err(err) = check_file_name(name) {
+            self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not create file \"{}\": {}", name, err),
+            );
+            return false;
+        }
+        match NewFilePolicy::new(exists, self.prompt_on_file_replace()) {
+            NewFilePolicy::Create | NewFilePolicy::Replace => true,
+            NewFilePolicy::Ask => {
+                // Forget answers given during previous transfers
+                self.transfer.replace.reset();
+                let replace: bool = self.filetransfer_should_replace(Path::new(name));
+                if !replace {
+                    self.log(
+                        LogLevel::Info,
+                        format!("File \"{}\" already exists; not replaced", name),
+                    );
+                }
+                replace
+            }
+        }
+    }
 }
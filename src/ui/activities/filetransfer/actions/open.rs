@@ -33,12 +33,16 @@ use std::path::{Path, PathBuf};
 impl FileTransferActivity {
     /// ### action_open_local
     ///
-    /// Open local file
+    /// Open local file. If no entry is selected, the working directory is opened in the file manager
     pub(crate) fn action_open_local(&mut self) {
         let entries: Vec<FsEntry> = match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => vec![entry],
             SelectedEntry::Many(entries) => entries,
-            SelectedEntry::None => vec![],
+            SelectedEntry::None => {
+                let wrkdir: PathBuf = self.local().wrkdir.clone();
+                self.open_path_with(wrkdir.as_path(), None);
+                return;
+            }
         };
         entries
             .iter()
@@ -0,0 +1,136 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::lib::preview::{is_binary, preview_lines, read_preview, PREVIEW_LEN};
+use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry};
+use crate::fs::FsFile;
+use crate::utils::fmt::fmt_bytes;
+
+impl FileTransferActivity {
+    /// ### action_local_preview
+    ///
+    /// Preview selected local file
+    pub(crate) fn action_local_preview(&mut self) {
+        if let SelectedEntry::One(entry) = self.get_local_selected_entries() {
+            if let Some(file) = self.previewable_file(&entry) {
+                let content = self
+                    .host
+                    .open_file_read(file.abs_path.as_path())
+                    .map_err(|e| e.to_string())
+                    .and_then(|mut fhnd| {
+                        read_preview(&mut fhnd, PREVIEW_LEN as u64).map_err(|e| e.to_string())
+                    });
+                self.show_preview(&file, content);
+            }
+        }
+    }
+
+    /// ### action_remote_preview
+    ///
+    /// Preview selected remote file. The file is read from remote without being written to localhost.
+    /// Only the first `PREVIEW_LEN` bytes are transferred
+    pub(crate) fn action_remote_preview(&mut self) {
+        if let SelectedEntry::One(entry) = self.get_remote_selected_entries() {
+            if let Some(file) = self.previewable_file(&entry) {
+                let content = match self.sessions.client.recv_file(&file) {
+                    Ok(mut rhnd) => {
+                        let content =
+                            read_preview(&mut rhnd, PREVIEW_LEN as u64).map_err(|e| e.to_string());
+                        // The server may report the transfer as aborted if the file has been read partially
+                        let truncated: bool = file.size > PREVIEW_LEN;
+                        match self.sessions.client.on_recv(rhnd) {
+                            Err(err) if truncated => debug!(
+                                "Finalized partial read of \"{}\": {}",
+                                file.abs_path.display(),
+                                err
+                            ),
+                            Err(err) => self.log(
+                                LogLevel::Warn,
+                                format!(
+                                    "Could not finalize read of \"{}\": {}",
+                                    file.abs_path.display(),
+                                    err
+                                ),
+                            ),
+                            Ok(_) => {}
+                        }
+                        content
+                    }
+                    Err(err) => Err(err.to_string()),
+                };
+                self.show_preview(&file, content);
+            }
+        }
+    }
+
+    /// ### previewable_file
+    ///
+    /// Returns the file to preview, if `entry` can be previewed; otherwise the reason is reported to the user.
+    /// If the file exceeds the preview size limit, the user is asked whether to preview it anyway
+    fn previewable_file(&mut self, entry: &FsEntry) -> Option<FsFile> {
+        match entry.get_realfile() {
+            FsEntry::Directory(dir) => {
+                self.mount_error(format!("\"{}\" is a directory", dir.name).as_str());
+                None
+            }
+            FsEntry::File(file) => {
+                let limit: u64 = self.max_preview_bytes();
+                match self.check_size_limit(file.name.as_str(), file.size as u64, limit) {
+                    true => Some(file),
+                    false => None,
+                }
+            }
+        }
+    }
+
+    /// ### show_preview
+    ///
+    /// Show the preview for `file`, unless its content couldn't be read or is binary
+    fn show_preview(&mut self, file: &FsFile, content: Result<Vec<u8>, String>) {
+        match content {
+            Err(err) => self.log_and_alert(
+                LogLevel::Error,
+                format!("Could not read \"{}\": {}", file.abs_path.display(), err),
+            ),
+            Ok(content) if is_binary(content.as_slice()) => {
+                self.mount_error(format!("\"{}\" is a binary file", file.name).as_str())
+            }
+            Ok(content) => {
+                let title: String = match file.size > PREVIEW_LEN {
+                    true => format!(
+                        "{} (first {})",
+                        file.name,
+                        fmt_bytes(PREVIEW_LEN as u64, self.config().get_size_unit())
+                    ),
+                    false => file.name.clone(),
+                };
+                self.mount_preview(title, preview_lines(content.as_slice()));
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::lib::sync::DownloadPlan;
+use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry};
+use std::path::PathBuf;
+
+impl FileTransferActivity {
+    /// ### action_remote_pull
+    ///
+    /// Recursively download the selected remote entries into the local working directory,
+    /// preserving the directory structure
+    pub(crate) fn action_remote_pull(&mut self) {
+        let entries: Vec<FsEntry> = match self.get_remote_selected_entries() {
+            SelectedEntry::One(entry) => vec![entry.get_realfile()],
+            SelectedEntry::Many(entries) => entries.iter().map(|x| x.get_realfile()).collect(),
+            SelectedEntry::None => return,
+        };
+        let wrkdir: PathBuf = self.local().wrkdir.clone();
+        // Plan download
+        let mut plan: DownloadPlan =
+            DownloadPlan::new(self.context().transfer_opts().skip_existing);
+        for entry in entries.iter() {
+            plan.add(
+                self.sessions.client.as_mut(),
+                &self.host,
+                entry,
+                wrkdir.as_path(),
+            );
+        }
+        if self.dry_run() {
+            self.log_dry_run(&plan.ops);
+            for err in plan.errors.into_iter() {
+                self.log(LogLevel::Error, err);
+            }
+            return;
+        }
+        // Execute plan
+        let errors: Vec<String> = self.filetransfer_recv_plan(plan);
+        match errors.is_empty() {
+            true => self.log(
+                LogLevel::Info,
+                format!(
+                    "Pulled {} entries into \"{}\"",
+                    entries.len(),
+                    wrkdir.display()
+                ),
+            ),
+            false => self.log_errors_and_alert("Pull", errors),
+        }
+    }
+}
@@ -26,11 +26,68 @@
  * SOFTWARE.
  */
 // locals
+use super::super::lib::mover::{move_entry, EntryMover, MoveError, Moved, RenameError};
+use super::super::lib::sync::SyncOp;
 use super::{FileTransferActivity, FsEntry, LogLevel, SelectedEntry};
+use crate::filetransfer::read_only::Operation;
+use crate::host::Localhost;
 use std::path::{Path, PathBuf};
 
+/// ## LocalMover
+///
+/// Moves entries on localhost
+struct LocalMover<'a> {
+    host: &'a mut Localhost,
+}
+
+impl EntryMover for LocalMover<'_> {
+    fn rename(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), RenameError> {
+        self.host.rename(entry, dest).map_err(RenameError::from)
+    }
+
+    fn copy(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), String> {
+        self.host.copy(entry, dest).map_err(|e| e.to_string())
+    }
+
+    fn remove(&mut self, entry: &FsEntry) -> Result<(), String> {
+        self.host.remove(entry).map_err(|e| e.to_string())
+    }
+}
+
+/// ## RemoteMover
+///
+/// Moves entries on the remote host; entries are copied through localhost if copy is not supported
+struct RemoteMover<'a> {
+    activity: &'a mut FileTransferActivity,
+}
+
+impl EntryMover for RemoteMover<'_> {
+    fn rename(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), RenameError> {
+        self.activity
+            .sessions
+            .client
+            .rename(entry, dest)
+            .map_err(RenameError::from)
+    }
+
+    fn copy(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), String> {
+        self.activity.remote_copy_entry(entry.clone(), dest)
+    }
+
+    fn remove(&mut self, entry: &FsEntry) -> Result<(), String> {
+        self.activity
+            .sessions
+            .client
+            .remove(entry)
+            .map_err(|e| e.to_string())
+    }
+}
+
 impl FileTransferActivity {
     pub(crate) fn action_local_rename(&mut self, input: String) {
+        if self.deny_read_only(Operation::Rename) {
+            return;
+        }
         match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => {
                 let dest_path: PathBuf = PathBuf::from(input);
@@ -55,6 +112,9 @@ impl FileTransferActivity {
     }
 
     pub(crate) fn action_remote_rename(&mut self, input: String) {
+        if self.deny_read_only(Operation::Rename) {
+            return;
+        }
         match self.get_remote_selected_entries() {
             SelectedEntry::One(entry) => {
                 let dest_path: PathBuf = PathBuf::from(input);
@@ -78,6 +138,133 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### action_local_move
+    ///
+    /// Move the selected entries on local to another directory
+    pub(crate) fn action_local_move(&mut self, input: String) {
+        if self.deny_read_only(Operation::Move) {
+            return;
+        }
+        // Relative destinations are resolved against the local working directory
+        let dest: PathBuf = self.local_to_abs_path(Path::new(&input));
+        let dest_is_dir: bool = matches!(self.host.stat(dest.as_path()), Ok(x) if x.is_dir());
+        let jobs: Vec<(FsEntry, PathBuf)> = Self::move_jobs(
+            self.get_local_selected_entries(),
+            dest.as_path(),
+            dest_is_dir,
+        );
+        if self.dry_run() {
+            self.log_dry_run_move(jobs);
+            return;
+        }
+        for (entry, dest) in jobs.into_iter() {
+            let result = move_entry(
+                &mut LocalMover {
+                    host: &mut self.host,
+                },
+                &entry,
+                dest.as_path(),
+            );
+            self.log_move(&entry, dest.as_path(), result);
+        }
+        self.reload_local_dir();
+    }
+
+    /// ### action_remote_move
+    ///
+    /// Move the selected entries on remote to another directory
+    pub(crate) fn action_remote_move(&mut self, input: String) {
+        if self.deny_read_only(Operation::Move) {
+            return;
+        }
+        // Relative destinations are resolved against the remote working directory
+        let dest: PathBuf = self.remote_to_abs_path(Path::new(&input));
+        let dest_is_dir: bool =
+            matches!(self.sessions.client.stat(dest.as_path()), Ok(x) if x.is_dir());
+        let jobs: Vec<(FsEntry, PathBuf)> = Self::move_jobs(
+            self.get_remote_selected_entries(),
+            dest.as_path(),
+            dest_is_dir,
+        );
+        if self.dry_run() {
+            self.log_dry_run_move(jobs);
+            return;
+        }
+        for (entry, dest) in jobs.into_iter() {
+            let result = move_entry(&mut RemoteMover { activity: self }, &entry, dest.as_path());
+            self.log_move(&entry, dest.as_path(), result);
+        }
+        self.reload_remote_dir();
+    }
+
+    /// ### move_jobs
+    ///
+    /// Get the destination of each selected entry: entries are moved into `dest` if it is an existing directory
+    /// or if many entries are selected; otherwise the single selected entry is moved to `dest`
+    fn move_jobs(
+        selected: SelectedEntry,
+        dest: &Path,
+        dest_is_dir: bool,
+    ) -> Vec<(FsEntry, PathBuf)> {
+        match selected {
+            SelectedEntry::One(entry) if dest_is_dir => {
+                let dest_path: PathBuf = dest.join(entry.get_name());
+                vec![(entry, dest_path)]
+            }
+            SelectedEntry::One(entry) => vec![(entry, dest.to_path_buf())],
+            SelectedEntry::Many(entries) => entries
+                .into_iter()
+                .map(|x| {
+                    let dest_path: PathBuf = dest.join(x.get_name());
+                    (x, dest_path)
+                })
+                .collect(),
+            SelectedEntry::None => Vec::new(),
+        }
+    }
+
+    /// ### log_dry_run_move
+    ///
+    /// Log the entries which would be moved in dry-run mode
+    fn log_dry_run_move(&mut self, jobs: Vec<(FsEntry, PathBuf)>) {
+        let ops: Vec<SyncOp> = jobs
+            .into_iter()
+            .flat_map(|(entry, dest)| {
+                vec![SyncOp::Copy(entry.clone(), dest), SyncOp::Delete(entry)]
+            })
+            .collect();
+        self.log_dry_run(&ops);
+    }
+
+    /// ### log_move
+    ///
+    /// Report the outcome of moving `entry` to `dest`
+    fn log_move(&mut self, entry: &FsEntry, dest: &Path, result: Result<Moved, MoveError>) {
+        match result {
+            Ok(moved) => self.log(
+                LogLevel::Info,
+                format!(
+                    "Moved \"{}\" to \"{}\"{}",
+                    entry.get_abs_path().display(),
+                    dest.display(),
+                    match moved {
+                        Moved::Renamed => "",
+                        Moved::Copied => " (copied and removed)",
+                    }
+                ),
+            ),
+            Err(err) => self.log_and_alert(
+                LogLevel::Error,
+                format!(
+                    "Could not move \"{}\" to \"{}\": {}",
+                    entry.get_abs_path().display(),
+                    dest.display(),
+                    err
+                ),
+            ),
+        }
+    }
+
     fn local_rename_file(&mut self, entry: &FsEntry, dest: &Path) {
         match self.host.rename(entry, dest) {
             Ok(_) => {
@@ -103,7 +290,7 @@ impl FileTransferActivity {
     }
 
     fn remote_rename_file(&mut self, entry: &FsEntry, dest: &Path) {
-        match self.client.as_mut().rename(entry, dest) {
+        match self.sessions.client.as_mut().rename(entry, dest) {
             Ok(_) => {
                 self.log(
                     LogLevel::Info,
@@ -114,14 +301,14 @@ impl FileTransferActivity {
                     ),
                 );
             }
-            Err(err) => self.log_and_alert(
+            Err(err) => self.log_and_alert_remote(
                 LogLevel::Error,
                 format!(
-                    "Could not move \"{}\" to \"{}\": {}",
+                    "Could not move \"{}\" to \"{}\"",
                     entry.get_abs_path().display(),
-                    dest.display(),
-                    err
+                    dest.display()
                 ),
+                &err,
             ),
         }
     }
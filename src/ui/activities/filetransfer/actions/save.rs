@@ -27,6 +27,7 @@
  */
 // locals
 use super::{FileTransferActivity, LogLevel, SelectedEntry, TransferPayload};
+use crate::filetransfer::read_only::Operation;
 use std::path::PathBuf;
 
 impl FileTransferActivity {
@@ -47,7 +48,16 @@ impl FileTransferActivity {
     }
 
     fn action_local_send_file(&mut self, save_as: Option<String>) {
+        if self.deny_read_only(Operation::Upload) {
+            return;
+        }
         let wrkdir: PathBuf = self.remote().wrkdir.clone();
+        if self.dry_run() {
+            let selected = self.get_local_selected_entries();
+            let dest: PathBuf = Self::dry_run_transfer_dest(&selected, wrkdir, save_as);
+            self.log_dry_run_copy(selected, dest.as_path());
+            return;
+        }
         match self.get_local_selected_entries() {
             SelectedEntry::One(entry) => {
                 if let Err(err) = self.filetransfer_send(
@@ -92,6 +102,12 @@ impl FileTransferActivity {
 
     fn action_remote_recv_file(&mut self, save_as: Option<String>) {
         let wrkdir: PathBuf = self.local().wrkdir.clone();
+        if self.dry_run() {
+            let selected = self.get_remote_selected_entries();
+            let dest: PathBuf = Self::dry_run_transfer_dest(&selected, wrkdir, save_as);
+            self.log_dry_run_copy(selected, dest.as_path());
+            return;
+        }
         match self.get_remote_selected_entries() {
             SelectedEntry::One(entry) => {
                 if let Err(err) = self.filetransfer_recv(
@@ -133,4 +149,20 @@ impl FileTransferActivity {
             SelectedEntry::None => {}
         }
     }
+
+    /// ### dry_run_transfer_dest
+    ///
+    /// Get the destination which would be used to transfer `selected` into `wrkdir`
+    fn dry_run_transfer_dest(
+        selected: &SelectedEntry,
+        mut wrkdir: PathBuf,
+        save_as: Option<String>,
+    ) -> PathBuf {
+        match (selected, save_as) {
+            (_, Some(save_as)) => wrkdir.push(save_as),
+            (SelectedEntry::One(entry), None) => wrkdir.push(entry.get_name()),
+            (_, None) => {}
+        }
+        wrkdir
+    }
 }
@@ -0,0 +1,67 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::browser::FileExplorerTab;
+use super::FileTransferActivity;
+use crate::fs::explorer::{FileExplorer, PageDirection};
+
+/// Amount of rows the selection is moved by, when scrolling by a page
+const PAGE_SIZE: usize = 8;
+
+/// ## ScrollOp
+///
+/// Describes where to move the selection of an explorer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ScrollOp {
+    First,    // Move to the first entry
+    Last,     // Move to the last entry
+    PageUp,   // Move up by a page
+    PageDown, // Move down by a page
+}
+
+impl FileTransferActivity {
+    /// ### action_scroll
+    ///
+    /// Get the index the selection of the explorer in use must be moved to
+    pub(crate) fn action_scroll(&self, op: ScrollOp) -> Option<usize> {
+        let current: usize = self
+            .get_selected_index(self.explorer_component())
+            .unwrap_or(0);
+        let explorer: &FileExplorer = match self.browser.tab() {
+            FileExplorerTab::Local => self.local(),
+            FileExplorerTab::Remote => self.remote(),
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => self.found()?,
+        };
+        Some(match op {
+            ScrollOp::First => explorer.first_index(),
+            ScrollOp::Last => explorer.last_index(),
+            ScrollOp::PageUp => explorer.page_index(current, PAGE_SIZE, PageDirection::Up),
+            ScrollOp::PageDown => explorer.page_index(current, PAGE_SIZE, PageDirection::Down),
+        })
+    }
+}
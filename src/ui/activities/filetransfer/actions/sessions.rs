@@ -0,0 +1,99 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::browser::Browser;
+use super::super::lib::sessions::RemoteSession;
+use super::{FileTransferActivity, LogLevel};
+use crate::filetransfer::FileTransfer;
+use crate::ui::components::file_list::FileListPropsBuilder;
+// ext
+use tuirealm::PropsBuilder;
+
+impl FileTransferActivity {
+    /// ### action_open_session
+    ///
+    /// Open a new session to the remote host and make it the active one.
+    /// The session connects on the next draw, starting from the entry directory
+    pub(crate) fn action_open_session(&mut self) {
+        let params = self.context().ft_params().unwrap().clone();
+        let client: Box<dyn FileTransfer> =
+            Self::make_client(&params, self.config(), self.context().transfer_opts());
+        let explorer = Browser::build_remote_explorer(self.config());
+        self.sessions.open(RemoteSession::new(client, explorer));
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Opened session {} to {}",
+                self.sessions.active_index() + 1,
+                params.address
+            ),
+        );
+        self.refresh_remote_session();
+    }
+
+    /// ### action_next_session
+    ///
+    /// Activate the following remote session
+    pub(crate) fn action_next_session(&mut self) {
+        if self.sessions.count() > 1 {
+            self.sessions.next();
+            self.refresh_remote_session();
+        }
+    }
+
+    /// ### action_close_session
+    ///
+    /// Disconnect and close the active remote session; a neighbour session is activated.
+    /// If it's the only session left, the user is asked whether to disconnect instead
+    pub(crate) fn action_close_session(&mut self) {
+        let index: usize = self.sessions.active_index();
+        match self.sessions.close_active() {
+            Some(mut session) => {
+                if session.client.is_connected() {
+                    let _ = session.client.disconnect();
+                }
+                self.log(LogLevel::Info, format!("Closed session {}", index + 1));
+                self.refresh_remote_session();
+            }
+            None => self.mount_disconnect(),
+        }
+    }
+
+    /// ### refresh_remote_session
+    ///
+    /// Redraw the remote explorer after the active session has changed
+    fn refresh_remote_session(&mut self) {
+        if let Some(props) = self.view.get_props(super::super::COMPONENT_EXPLORER_REMOTE) {
+            let props = FileListPropsBuilder::from(props).with_index(0).build();
+            self.view
+                .update(super::super::COMPONENT_EXPLORER_REMOTE, props);
+        }
+        self.update_remote_filelist();
+        self.refresh_remote_status_bar();
+    }
+}
@@ -0,0 +1,50 @@
+//! ## FileTransferActivity
+//!
+//! `filetransfer_activiy` is the module which implements the Filetransfer activity, which is the main activity afterall
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use super::super::browser::FileExplorerTab;
+use super::FileTransferActivity;
+
+impl FileTransferActivity {
+    /// ### action_cycle_sort
+    ///
+    /// Sort the explorer in use by the next sorting method (or by the previous one if `reverse` is true)
+    /// and refresh its status bar
+    pub(crate) fn action_cycle_sort(&mut self, reverse: bool) {
+        match self.browser.tab() {
+            FileExplorerTab::Local => {
+                self.local_mut().cycle_sort(reverse);
+                self.refresh_local_status_bar();
+            }
+            FileExplorerTab::Remote => {
+                self.remote_mut().cycle_sort(reverse);
+                self.refresh_remote_status_bar();
+            }
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => {}
+        }
+    }
+}
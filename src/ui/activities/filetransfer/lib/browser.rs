@@ -25,9 +25,14 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::fs::explorer::{builder::FileExplorerBuilder, FileExplorer, FileSorting, GroupDirs};
+use crate::fs::explorer::{
+    builder::FileExplorerBuilder, FileExplorer, FileSorting, GroupDirs, LoadState,
+};
 use crate::fs::FsEntry;
 use crate::system::config_client::ConfigClient;
+use crate::utils::file::resolve_path;
+
+use std::path::{Path, PathBuf};
 
 /// ## FileExplorerTab
 ///
@@ -45,7 +50,6 @@ pub enum FileExplorerTab {
 /// Browser contains the browser options
 pub struct Browser {
     local: FileExplorer,         // Local File explorer state
-    remote: FileExplorer,        // Remote File explorer state
     found: Option<FileExplorer>, // File explorer for find result
     tab: FileExplorerTab,        // Current selected tab
     pub sync_browsing: bool,
@@ -58,7 +62,6 @@ impl Browser {
     pub fn new(cli: &ConfigClient) -> Self {
         Self {
             local: Self::build_local_explorer(cli),
-            remote: Self::build_remote_explorer(cli),
             found: None,
             tab: FileExplorerTab::Local,
             sync_browsing: false,
@@ -73,14 +76,6 @@ impl Browser {
         &mut self.local
     }
 
-    pub fn remote(&self) -> &FileExplorer {
-        &self.remote
-    }
-
-    pub fn remote_mut(&mut self) -> &mut FileExplorer {
-        &mut self.remote
-    }
-
     pub fn found(&self) -> Option<&FileExplorer> {
         self.found.as_ref()
     }
@@ -89,8 +84,12 @@ impl Browser {
         self.found.as_mut()
     }
 
-    pub fn set_found(&mut self, files: Vec<FsEntry>) {
+    /// ### set_found
+    ///
+    /// Set the results of a search started from `root`; results are displayed relative to it
+    pub fn set_found(&mut self, files: Vec<FsEntry>, root: &Path) {
         let mut explorer = Self::build_found_explorer();
+        explorer.wrkdir = root.to_path_buf();
         explorer.set_files(files);
         self.found = Some(explorer);
     }
@@ -145,7 +144,11 @@ impl Browser {
             .with_file_sorting(FileSorting::ByName)
             .with_stack_size(16)
             .with_group_dirs(cli.get_group_dirs())
-            .with_hidden_files(cli.get_show_hidden_files());
+            .with_dirs_first_locked(cli.get_lock_dirs_first())
+            .with_hidden_files(cli.get_show_hidden_files())
+            .with_always_show_parent(true)
+            .with_hidden_patterns(cli.get_hidden_patterns().as_slice())
+            .with_size_unit(cli.get_size_unit());
         builder
     }
 
@@ -157,8 +160,254 @@ impl Browser {
             .with_file_sorting(FileSorting::ByName)
             .with_group_dirs(Some(GroupDirs::First))
             .with_hidden_files(true)
+            .with_relative_paths(true)
             .with_stack_size(0)
             .with_formatter(Some("{NAME:32} {SYMLINK}"))
             .build()
     }
 }
+
+/// ### check_dir
+///
+/// Check whether `path` can be used as working directory: it must be an absolute path and
+/// `stat` must report a directory, or a symlink pointing to a directory.
+/// `stat` is the function used to get the entry, either from localhost or from remote.
+/// Returns the directory path
+pub fn check_dir<F>(path: &Path, stat: F) -> Result<PathBuf, String>
+where
+    F: FnOnce(&Path) -> Result<FsEntry, String>,
+{
+    if !path.is_absolute() {
+        return Err(format!("\"{}\" is not an absolute path", path.display()));
+    }
+    match stat(path)? {
+        FsEntry::Directory(_) => Ok(path.to_path_buf()),
+        FsEntry::File(file) => match file.symlink.as_deref() {
+            Some(FsEntry::Directory(_)) => Ok(path.to_path_buf()),
+            _ => Err(format!("\"{}\" is not a directory", path.display())),
+        },
+    }
+}
+
+/// ### home_dir
+///
+/// Get the home directory of the explorer of `tab`: on localhost the home directory of the user (`local_home`),
+/// on remote the working directory reported by the server once connected (`remote_home`).
+/// Returns `None` if it's unknown
+pub fn home_dir(
+    tab: FileExplorerTab,
+    local_home: Option<PathBuf>,
+    remote_home: Option<&Path>,
+) -> Option<PathBuf> {
+    match tab {
+        FileExplorerTab::Local | FileExplorerTab::FindLocal => local_home,
+        FileExplorerTab::Remote | FileExplorerTab::FindRemote => remote_home.map(Path::to_path_buf),
+    }
+}
+
+/// ### sync_dir
+///
+/// Get the directory to enter on the other panel, when browsing is synchronized and `name` has been entered on the current one.
+/// `name` is resolved against `wrkdir`, the working directory of the other panel.
+/// Returns `None` if it doesn't exist there or it is not a directory, in which case the other panel is left as it is
+pub fn sync_dir<F>(wrkdir: &Path, name: &str, stat: F) -> Option<PathBuf>
+where
+    F: FnOnce(&Path) -> Result<FsEntry, String>,
+{
+    check_dir(resolve_path(wrkdir, Path::new(name)).as_path(), stat).ok()
+}
+
+/// ### placeholder
+///
+/// Returns the text to display in place of the entries of `explorer`, if there are none to display
+pub fn placeholder(explorer: &FileExplorer) -> Option<&'static str> {
+    match explorer.is_empty() {
+        false => None,
+        true => Some(match explorer.load_state() {
+            LoadState::Loading => "(loading...)",
+            LoadState::Loaded => "(empty directory)",
+            LoadState::PermissionDenied => "(permission denied)",
+            LoadState::Failed => "(could not list directory)",
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::filetransfer::FileTransfer;
+    use crate::host::Localhost;
+    use crate::utils::test_helpers::{make_dir_at, make_file_at, make_fsentry, MockFileTransfer};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_hidden_files() {
+        let mut config: ConfigClient = ConfigClient::degraded();
+        // Hidden files disabled in configuration
+        config.set_show_hidden_files(false);
+        assert_eq!(
+            Browser::build_local_explorer(&config).hidden_files_visible(),
+            false
+        );
+        assert_eq!(
+            Browser::build_remote_explorer(&config).hidden_files_visible(),
+            false
+        );
+        // Hidden files enabled in configuration
+        config.set_show_hidden_files(true);
+        let browser: Browser = Browser::new(&config);
+        assert_eq!(browser.local().hidden_files_visible(), true);
+        assert_eq!(
+            Browser::build_remote_explorer(&config).hidden_files_visible(),
+            true
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_check_dir_remote() {
+        let mut client: MockFileTransfer = MockFileTransfer::default().dir(
+            Path::new("/home"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar"), true),
+                make_fsentry(PathBuf::from("/home/readme.txt"), false),
+            ],
+        );
+        // Directory
+        assert_eq!(
+            check_dir(Path::new("/home/omar"), |p| client
+                .stat(p)
+                .map_err(|e| e.to_string()))
+            .ok()
+            .unwrap(),
+            PathBuf::from("/home/omar")
+        );
+        // File
+        assert!(check_dir(Path::new("/home/readme.txt"), |p| client
+            .stat(p)
+            .map_err(|e| e.to_string()))
+        .is_err());
+        // Doesn't exist
+        assert!(check_dir(Path::new("/home/foo"), |p| client
+            .stat(p)
+            .map_err(|e| e.to_string()))
+        .is_err());
+        // Relative path; stat is not even called
+        assert!(check_dir(Path::new("omar"), |_| panic!("stat called")).is_err());
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_home_dir() {
+        let local_home: Option<PathBuf> = Some(PathBuf::from("/home/omar"));
+        let remote_home: Option<&Path> = Some(Path::new("/home/pi"));
+        assert_eq!(
+            home_dir(FileExplorerTab::Local, local_home.clone(), remote_home),
+            Some(PathBuf::from("/home/omar"))
+        );
+        assert_eq!(
+            home_dir(FileExplorerTab::Remote, local_home.clone(), remote_home),
+            Some(PathBuf::from("/home/pi"))
+        );
+        // Unknown home
+        assert_eq!(home_dir(FileExplorerTab::Local, None, remote_home), None);
+        assert_eq!(home_dir(FileExplorerTab::Remote, local_home, None), None);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_sync_dir() {
+        let mut client: MockFileTransfer = MockFileTransfer::default().dir(
+            Path::new("/home"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar"), true),
+                make_fsentry(PathBuf::from("/home/readme.txt"), false),
+            ],
+        );
+        // Counterpart exists
+        assert_eq!(
+            sync_dir(Path::new("/home"), "omar", |p| client
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            Some(PathBuf::from("/home/omar"))
+        );
+        // Counterpart is missing
+        assert_eq!(
+            sync_dir(Path::new("/home"), "foo", |p| client
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            None
+        );
+        // Counterpart is a file
+        assert_eq!(
+            sync_dir(Path::new("/home"), "readme.txt", |p| client
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            None
+        );
+        // Absolute paths are not resolved against the working directory
+        assert_eq!(
+            sync_dir(Path::new("/tmp"), "/home/omar", |p| client
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            Some(PathBuf::from("/home/omar"))
+        );
+        // On localhost
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        assert!(make_dir_at(tmpdir.path(), "docs").is_ok());
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).ok().unwrap();
+        assert_eq!(
+            sync_dir(tmpdir.path(), "docs", |p| host
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            Some(tmpdir.path().join("docs"))
+        );
+        assert_eq!(
+            sync_dir(tmpdir.path(), "src", |p| host
+                .stat(p)
+                .map_err(|e| e.to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_check_dir_local() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        assert!(make_dir_at(tmpdir.path(), "docs").is_ok());
+        assert!(make_file_at(tmpdir.path(), "readme.txt").is_ok());
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).ok().unwrap();
+        let dir: PathBuf = tmpdir.path().join("docs");
+        assert_eq!(
+            check_dir(dir.as_path(), |p| host.stat(p).map_err(|e| e.to_string()))
+                .ok()
+                .unwrap(),
+            dir
+        );
+        assert!(
+            check_dir(tmpdir.path().join("readme.txt").as_path(), |p| host
+                .stat(p)
+                .map_err(|e| e.to_string()))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_browser_placeholder() {
+        let mut explorer: FileExplorer = FileExplorer::default();
+        // Nothing listed yet
+        assert_eq!(placeholder(&explorer), Some("(loading...)"));
+        // Empty directory
+        explorer.set_files(vec![]);
+        assert_eq!(placeholder(&explorer), Some("(empty directory)"));
+        // Entries listed
+        explorer.set_files(vec![make_fsentry(PathBuf::from("README.md"), false)]);
+        assert_eq!(placeholder(&explorer), None);
+        // Listing again: entries are still displayed while loading
+        explorer.set_loading();
+        assert_eq!(placeholder(&explorer), None);
+        // Listing failed
+        explorer.set_load_failed(true);
+        assert_eq!(placeholder(&explorer), Some("(permission denied)"));
+        explorer.set_load_failed(false);
+        assert_eq!(placeholder(&explorer), Some("(could not list directory)"));
+    }
+}
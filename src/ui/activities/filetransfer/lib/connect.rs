@@ -0,0 +1,380 @@
+//! ## Connect
+//!
+//! `connect` establishes the connection to the remote on a background thread, so that the UI keeps running
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use crate::filetransfer::params::FileTransferParams;
+use crate::filetransfer::{
+    FileTransfer, FileTransferError, FileTransferErrorType, FileTransferProtocol,
+};
+// ext
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type ConnectResult = Result<Option<String>, FileTransferError>;
+
+/// ## Connector
+///
+/// Connector establishes a connection; it is moved to the background thread while connecting
+pub trait Connector: Send + 'static {
+    /// ### connect
+    ///
+    /// Connect to the remote, returning the welcome message if any
+    fn connect(&mut self) -> ConnectResult;
+
+    /// ### disconnect
+    ///
+    /// Tear down the connection established by `connect`
+    fn disconnect(&mut self);
+}
+
+/// ## RemoteConnector
+///
+/// Connects a file transfer client using the parameters provided in authentication
+pub struct RemoteConnector {
+    client: Box<dyn FileTransfer>,
+    params: FileTransferParams,
+}
+
+impl RemoteConnector {
+    /// ### new
+    ///
+    /// Instantiates a new `RemoteConnector`
+    pub fn new(client: Box<dyn FileTransfer>, params: FileTransferParams) -> Self {
+        Self { client, params }
+    }
+
+    /// ### into_client
+    ///
+    /// Take the client back from the connector
+    pub fn into_client(self) -> Box<dyn FileTransfer> {
+        self.client
+    }
+}
+
+impl Connector for RemoteConnector {
+    fn connect(&mut self) -> ConnectResult {
+        self.client.connect(
+            self.params.address.clone(),
+            self.params.port,
+            self.params.username.clone(),
+            self.params.password.clone(),
+        )
+    }
+
+    fn disconnect(&mut self) {
+        let _ = self.client.disconnect();
+    }
+}
+
+/// ## ConnectState
+///
+/// Describes the state of a pending connection.
+/// The connector is given back once the connection has been established or has failed;
+/// it is lost only if the connection thread terminated unexpectedly
+pub enum ConnectState<C> {
+    Pending,
+    Connected(C, Option<String>),
+    Failed(Option<C>, FileTransferError),
+    Cancelled,
+}
+
+/// ## PendingConnection
+///
+/// A connection being established on a background thread
+pub struct PendingConnection<C: Connector> {
+    receiver: Receiver<(C, ConnectResult)>,
+    cancelled: Arc<Mutex<bool>>,
+}
+
+impl<C: Connector> PendingConnection<C> {
+    /// ### start
+    ///
+    /// Start connecting with `connector` on a new thread
+    pub fn start(mut connector: C) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
+        let thread_cancelled: Arc<Mutex<bool>> = cancelled.clone();
+        thread::spawn(move || {
+            let result: ConnectResult = connector.connect();
+            // The lock is held until the result is sent, so that `cancel` can't miss it
+            let cancelled = thread_cancelled.lock().unwrap();
+            if *cancelled {
+                Self::teardown(connector, result);
+            } else if let Err(mpsc::SendError((connector, result))) =
+                sender.send((connector, result))
+            {
+                Self::teardown(connector, result);
+            }
+        });
+        Self {
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// ### poll
+    ///
+    /// Get the current state of the connection, without blocking
+    pub fn poll(&mut self) -> ConnectState<C> {
+        if *self.cancelled.lock().unwrap() {
+            return ConnectState::Cancelled;
+        }
+        match self.receiver.try_recv() {
+            Ok((connector, Ok(welcome))) => ConnectState::Connected(connector, welcome),
+            Ok((connector, Err(err))) => ConnectState::Failed(Some(connector), err),
+            Err(TryRecvError::Empty) => ConnectState::Pending,
+            Err(TryRecvError::Disconnected) => ConnectState::Failed(
+                None,
+                FileTransferError::new_ex(
+                    FileTransferErrorType::ConnectionError,
+                    String::from("connection terminated unexpectedly"),
+                ),
+            ),
+        }
+    }
+
+    /// ### cancel
+    ///
+    /// Abort the connection. If the connection gets established anyway, it is torn down
+    pub fn cancel(&mut self) {
+        let mut cancelled = self.cancelled.lock().unwrap();
+        if *cancelled {
+            return;
+        }
+        *cancelled = true;
+        // Tear down the connection, if it was already established
+        if let Ok((connector, result)) = self.receiver.try_recv() {
+            Self::teardown(connector, result);
+        }
+    }
+
+    /// ### teardown
+    ///
+    /// Disconnect a half-open connection
+    fn teardown(mut connector: C, result: ConnectResult) {
+        if result.is_ok() {
+            connector.disconnect();
+        }
+    }
+}
+
+impl<C: Connector> Drop for PendingConnection<C> {
+    fn drop(&mut self) {
+        // Nobody is going to receive the connection anymore
+        self.cancel();
+    }
+}
+
+/// ### banner_to_show
+///
+/// Get the banner received on connection with `protocol` to display to the user, if enabled.
+/// Blank banners (and protocols which don't send any) are not displayed.
+/// With SCP and SFTP the welcome message is the server identification string (e.g. `SSH-2.0-OpenSSH_8.4p1`),
+/// not a login banner, so it's never displayed
+pub fn banner_to_show(
+    banner: Option<&str>,
+    protocol: FileTransferProtocol,
+    enabled: bool,
+) -> Option<&str> {
+    match protocol {
+        FileTransferProtocol::Scp | FileTransferProtocol::Sftp => None,
+        FileTransferProtocol::Ftp(_) => banner
+            .map(|x| x.trim())
+            .filter(|x| enabled && !x.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::Sender;
+    use std::time::{Duration, Instant};
+
+    struct MockConnector {
+        gate: Receiver<()>,
+        result: Option<ConnectResult>,
+        disconnected: Arc<AtomicBool>,
+    }
+
+    impl Connector for MockConnector {
+        fn connect(&mut self) -> ConnectResult {
+            // Wait until the test lets the connection complete
+            let _ = self.gate.recv();
+            self.result.take().expect("connection failed unexpectedly")
+        }
+
+        fn disconnect(&mut self) {
+            self.disconnected.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn mock_connector(
+        result: Option<ConnectResult>,
+    ) -> (MockConnector, Sender<()>, Arc<AtomicBool>) {
+        let (gate_tx, gate) = mpsc::channel();
+        let disconnected: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        (
+            MockConnector {
+                gate,
+                result,
+                disconnected: disconnected.clone(),
+            },
+            gate_tx,
+            disconnected,
+        )
+    }
+
+    fn wait_state(conn: &mut PendingConnection<MockConnector>) -> ConnectState<MockConnector> {
+        let started: Instant = Instant::now();
+        loop {
+            match conn.poll() {
+                ConnectState::Pending if started.elapsed() < Duration::from_secs(5) => {
+                    thread::sleep(Duration::from_millis(10))
+                }
+                state => return state,
+            }
+        }
+    }
+
+    fn wait_flag(flag: &AtomicBool) -> bool {
+        let started: Instant = Instant::now();
+        while !flag.load(Ordering::SeqCst) && started.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        flag.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_connected() {
+        let (connector, gate, disconnected) =
+            mock_connector(Some(Ok(Some(String::from("welcome")))));
+        let mut conn = PendingConnection::start(connector);
+        assert!(matches!(conn.poll(), ConnectState::Pending));
+        gate.send(()).unwrap();
+        match wait_state(&mut conn) {
+            ConnectState::Connected(_, welcome) => {
+                assert_eq!(welcome.as_deref(), Some("welcome"))
+            }
+            _ => panic!("connection should have been established"),
+        }
+        drop(conn);
+        assert!(!disconnected.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_failed() {
+        let (connector, gate, disconnected) = mock_connector(Some(Err(FileTransferError::new(
+            FileTransferErrorType::AuthenticationFailed,
+        ))));
+        let mut conn = PendingConnection::start(connector);
+        gate.send(()).unwrap();
+        match wait_state(&mut conn) {
+            ConnectState::Failed(connector, err) => {
+                assert!(connector.is_some());
+                assert_eq!(err.kind(), FileTransferErrorType::AuthenticationFailed);
+            }
+            _ => panic!("connection should have failed"),
+        }
+        assert!(!disconnected.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_cancelled() {
+        let (connector, gate, disconnected) = mock_connector(Some(Ok(None)));
+        let mut conn = PendingConnection::start(connector);
+        assert!(matches!(conn.poll(), ConnectState::Pending));
+        conn.cancel();
+        assert!(matches!(conn.poll(), ConnectState::Cancelled));
+        // Connection established after cancel must be torn down
+        gate.send(()).unwrap();
+        assert!(wait_flag(disconnected.as_ref()));
+        assert!(matches!(conn.poll(), ConnectState::Cancelled));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_cancelled_after_connect() {
+        let (connector, gate, disconnected) = mock_connector(Some(Ok(None)));
+        let mut conn = PendingConnection::start(connector);
+        gate.send(()).unwrap();
+        // Wait for the connection to be sent, without receiving it
+        thread::sleep(Duration::from_millis(100));
+        conn.cancel();
+        assert!(wait_flag(disconnected.as_ref()));
+        assert!(matches!(conn.poll(), ConnectState::Cancelled));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_thread_lost() {
+        let (connector, gate, _) = mock_connector(None);
+        let mut conn = PendingConnection::start(connector);
+        gate.send(()).unwrap();
+        match wait_state(&mut conn) {
+            ConnectState::Failed(connector, err) => {
+                assert!(connector.is_none());
+                assert_eq!(err.kind(), FileTransferErrorType::ConnectionError);
+            }
+            _ => panic!("connection should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_connect_banner_to_show() {
+        let ftp: FileTransferProtocol = FileTransferProtocol::Ftp(false);
+        // Present and enabled
+        assert_eq!(
+            banner_to_show(Some("220 Welcome to the archive\r\n"), ftp, true),
+            Some("220 Welcome to the archive")
+        );
+        // Disabled
+        assert_eq!(banner_to_show(Some("220 Welcome"), ftp, false), None);
+        // Not present
+        assert_eq!(banner_to_show(None, ftp, true), None);
+        assert_eq!(banner_to_show(None, ftp, false), None);
+        assert_eq!(banner_to_show(Some(" \n"), ftp, true), None);
+        // SSH identification string is not a banner
+        assert_eq!(
+            banner_to_show(
+                Some("SSH-2.0-OpenSSH_8.4p1 Debian-5\r\n"),
+                FileTransferProtocol::Sftp,
+                true
+            ),
+            None
+        );
+        assert_eq!(
+            banner_to_show(
+                Some("SSH-2.0-OpenSSH_8.4p1 Debian-5\r\n"),
+                FileTransferProtocol::Scp,
+                true
+            ),
+            None
+        );
+    }
+}
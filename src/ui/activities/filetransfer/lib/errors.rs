@@ -0,0 +1,134 @@
+//! ## Errors
+//!
+//! `errors` translates the errors returned by the remote host into messages for the user
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::filetransfer::FileTransferErrorType;
+
+/// ### error_message
+///
+/// Translate the kind of a remote error into a message for the user, which describes
+/// the category of the error and suggests how to solve it.
+/// The raw error should be kept in the log, since it may contain details reported by the server
+pub fn error_message(kind: FileTransferErrorType) -> &'static str {
+    match kind {
+        FileTransferErrorType::AuthenticationFailed => {
+            "Authentication failed: check username, password and SSH key"
+        }
+        FileTransferErrorType::BadAddress => "Bad address: check the host name and the port",
+        FileTransferErrorType::ConnectionError => {
+            "Connection error: check your network connection and whether the server is reachable"
+        }
+        FileTransferErrorType::SslError => {
+            "TLS error: check whether the server supports FTPS and its certificate"
+        }
+        FileTransferErrorType::DirStatFailed => {
+            "Could not read directory: check whether it exists and it can be accessed"
+        }
+        FileTransferErrorType::FileCreateDenied => {
+            "Could not create file: check the permissions of the destination directory and the free space"
+        }
+        FileTransferErrorType::HostKeyUnknown => {
+            "Unknown host key: verify the fingerprint before trusting it"
+        }
+        FileTransferErrorType::HostKeyMismatch => {
+            "Host key has changed: the server may have been reinstalled or the connection may be intercepted"
+        }
+        FileTransferErrorType::HostKeyRevoked => {
+            "Host key has been revoked in the known hosts file: the connection can't be trusted"
+        }
+        FileTransferErrorType::NoSuchFileOrDirectory => {
+            "No such file or directory: check the path; the entry may have been moved or removed, reload the directory with <L>"
+        }
+        FileTransferErrorType::PexError => {
+            "Permission denied: check your credentials and the permissions of the entry"
+        }
+        FileTransferErrorType::ProtocolError => {
+            "Protocol error: the server returned an unexpected response"
+        }
+        FileTransferErrorType::UninitializedSession => {
+            "Not connected: the connection to the server has been lost; reconnect to go on"
+        }
+        FileTransferErrorType::UnsupportedFeature => {
+            "Not supported: this operation is not available with the current protocol"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_errors_credentials() {
+        assert_eq!(
+            error_message(FileTransferErrorType::PexError),
+            "Permission denied: check your credentials and the permissions of the entry"
+        );
+        assert!(error_message(FileTransferErrorType::AuthenticationFailed)
+            .starts_with("Authentication failed: check username, password"));
+        assert!(error_message(FileTransferErrorType::FileCreateDenied)
+            .contains("permissions of the destination directory"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_errors_paths() {
+        assert!(error_message(FileTransferErrorType::NoSuchFileOrDirectory)
+            .starts_with("No such file or directory: check the path"));
+        assert!(error_message(FileTransferErrorType::DirStatFailed)
+            .starts_with("Could not read directory: check whether it exists"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_errors_network() {
+        assert!(error_message(FileTransferErrorType::ConnectionError)
+            .contains("check your network connection"));
+        assert!(error_message(FileTransferErrorType::BadAddress)
+            .contains("check the host name and the port"));
+        assert!(error_message(FileTransferErrorType::SslError).starts_with("TLS error"));
+        assert!(error_message(FileTransferErrorType::UninitializedSession).contains("reconnect"));
+        assert!(error_message(FileTransferErrorType::ProtocolError).starts_with("Protocol error"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_errors_host_keys() {
+        assert!(
+            error_message(FileTransferErrorType::HostKeyUnknown).contains("verify the fingerprint")
+        );
+        assert!(error_message(FileTransferErrorType::HostKeyMismatch)
+            .starts_with("Host key has changed"));
+        assert!(error_message(FileTransferErrorType::HostKeyRevoked)
+            .starts_with("Host key has been revoked"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_errors_unsupported() {
+        assert!(error_message(FileTransferErrorType::UnsupportedFeature)
+            .contains("not available with the current protocol"));
+    }
+}
@@ -0,0 +1,295 @@
+//! ## Find
+//!
+//! `find` is the module which walks a directory tree collecting the entries matching a search
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use crate::fs::FsEntry;
+// ext
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use wildmatch::WildMatch;
+
+/// ## SearchPattern
+///
+/// Describes how entry names are matched.
+/// Searches containing wildcards ('?', '*') are matched as globs against the entire name,
+/// otherwise the search must be contained in the name
+pub enum SearchPattern {
+    Substring(String),
+    Glob(WildMatch),
+}
+
+impl SearchPattern {
+    /// ### new
+    ///
+    /// Instantiates a new `SearchPattern` from the search input
+    pub fn new(search: &str) -> Self {
+        match search.contains(&['*', '?'][..]) {
+            true => SearchPattern::Glob(WildMatch::new(search)),
+            false => SearchPattern::Substring(search.to_string()),
+        }
+    }
+
+    /// ### matches
+    ///
+    /// Returns whether `name` matches the pattern
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            SearchPattern::Substring(s) => name.contains(s.as_str()),
+            SearchPattern::Glob(glob) => glob.matches(name),
+        }
+    }
+}
+
+/// ## SearchWalk
+///
+/// Walks a directory tree breadth-first, one directory per step, up to `max_depth` levels below the root.
+/// Since the walk is performed step by step, it can be interrupted between two steps
+pub struct SearchWalk {
+    pattern: SearchPattern,
+    max_depth: usize,
+    queue: VecDeque<(PathBuf, usize)>, // Directories to scan, along with their depth
+    pub found: Vec<FsEntry>,           // Entries matching the search
+    pub errors: Vec<String>,           // Directories which couldn't be scanned
+}
+
+impl SearchWalk {
+    /// ### new
+    ///
+    /// Instantiates a new `SearchWalk` starting from `root`.
+    /// Entries of `root` have depth 1; directories at `max_depth` are not scanned
+    pub fn new(root: &Path, search: &str, max_depth: usize) -> Self {
+        let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
+        if max_depth > 0 {
+            queue.push_back((root.to_path_buf(), 0));
+        }
+        SearchWalk {
+            pattern: SearchPattern::new(search),
+            max_depth,
+            queue,
+            found: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// ### done
+    ///
+    /// Returns whether there are no more directories to scan
+    pub fn done(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// ### step
+    ///
+    /// Scan the next directory, listing it with `list_dir`.
+    /// Returns whether a directory has been scanned
+    pub fn step<F, E>(&mut self, list_dir: F) -> bool
+    where
+        F: FnOnce(&Path) -> Result<Vec<FsEntry>, E>,
+        E: fmt::Display,
+    {
+        let (dir, depth): (PathBuf, usize) = match self.queue.pop_front() {
+            Some(next) => next,
+            None => return false,
+        };
+        match list_dir(dir.as_path()) {
+            Ok(entries) => {
+                for entry in entries.into_iter() {
+                    // Don't follow symlinks, which may cause loops
+                    if let FsEntry::Directory(d) = &entry {
+                        if depth + 1 < self.max_depth && d.symlink.is_none() {
+                            self.queue.push_back((d.abs_path.clone(), depth + 1));
+                        }
+                    }
+                    if self.pattern.matches(entry.get_name()) {
+                        self.found.push(entry);
+                    }
+                }
+            }
+            Err(err) => self.errors.push(format!(
+                "Could not scan directory \"{}\": {}",
+                dir.display(),
+                err
+            )),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::filetransfer::FileTransfer;
+    use crate::host::Localhost;
+    use crate::utils::test_helpers::{make_dir_at, make_file_at, make_fsentry, MockFileTransfer};
+
+    use pretty_assertions::assert_eq;
+
+    fn mock_tree() -> MockFileTransfer {
+        MockFileTransfer::default()
+            .dir(
+                Path::new("/home/omar"),
+                vec![
+                    make_fsentry(PathBuf::from("/home/omar/docs"), true),
+                    make_fsentry(PathBuf::from("/home/omar/src"), true),
+                    make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+                ],
+            )
+            .dir(
+                Path::new("/home/omar/docs"),
+                vec![
+                    make_fsentry(PathBuf::from("/home/omar/docs/readme.txt"), false),
+                    make_fsentry(PathBuf::from("/home/omar/docs/old"), true),
+                ],
+            )
+            .dir(
+                Path::new("/home/omar/docs/old"),
+                vec![make_fsentry(
+                    PathBuf::from("/home/omar/docs/old/README.md"),
+                    false,
+                )],
+            )
+            .dir(
+                Path::new("/home/omar/src"),
+                vec![make_fsentry(PathBuf::from("/home/omar/src/main.rs"), false)],
+            )
+    }
+
+    fn walk(client: &mut MockFileTransfer, search: &str, max_depth: usize) -> SearchWalk {
+        let mut walk: SearchWalk = SearchWalk::new(Path::new("/home/omar"), search, max_depth);
+        while walk.step(|p| client.list_dir(p)) {}
+        walk
+    }
+
+    fn found(walk: &SearchWalk) -> Vec<String> {
+        walk.found
+            .iter()
+            .map(|x| x.get_abs_path().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_pattern() {
+        let pattern: SearchPattern = SearchPattern::new("READ");
+        assert!(pattern.matches("README.md"));
+        assert!(pattern.matches("UNREAD"));
+        assert!(!pattern.matches("readme.txt"));
+        let pattern: SearchPattern = SearchPattern::new("*.md");
+        assert!(pattern.matches("README.md"));
+        assert!(!pattern.matches("README.md.bak"));
+        let pattern: SearchPattern = SearchPattern::new("main.?s");
+        assert!(pattern.matches("main.rs"));
+        assert!(pattern.matches("main.js"));
+        assert!(!pattern.matches("main.ts.old"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_walk() {
+        let mut client: MockFileTransfer = mock_tree();
+        // Substring
+        let result: SearchWalk = walk(&mut client, "READ", 16);
+        assert!(result.done());
+        assert!(result.errors.is_empty());
+        assert_eq!(
+            found(&result),
+            vec!["/home/omar/README.md", "/home/omar/docs/old/README.md"]
+        );
+        // Glob
+        let result: SearchWalk = walk(&mut client, "*.rs", 16);
+        assert_eq!(found(&result), vec!["/home/omar/src/main.rs"]);
+        // Directories are matched too
+        let result: SearchWalk = walk(&mut client, "doc", 16);
+        assert_eq!(found(&result), vec!["/home/omar/docs"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_walk_depth() {
+        let mut client: MockFileTransfer = mock_tree();
+        // Only root
+        let result: SearchWalk = walk(&mut client, "READ", 1);
+        assert_eq!(found(&result), vec!["/home/omar/README.md"]);
+        assert_eq!(client.calls.len(), 1);
+        // Root and its subdirectories
+        client.calls.clear();
+        let result: SearchWalk = walk(&mut client, "*", 2);
+        assert_eq!(result.found.len(), 6);
+        assert_eq!(client.calls.len(), 3);
+        // Nothing
+        client.calls.clear();
+        let result: SearchWalk = walk(&mut client, "*", 0);
+        assert!(result.found.is_empty());
+        assert!(client.calls.is_empty());
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_walk_interrupted() {
+        let mut client: MockFileTransfer = mock_tree();
+        let mut walk: SearchWalk = SearchWalk::new(Path::new("/home/omar"), "READ", 16);
+        assert!(walk.step(|p| client.list_dir(p)));
+        // Interrupt after first step; only the entries of the root have been collected
+        assert_eq!(walk.done(), false);
+        assert_eq!(found(&walk), vec!["/home/omar/README.md"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_walk_localhost() {
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let dir_path: &Path = tmpdir.path();
+        assert!(make_file_at(dir_path, "pippo.txt").is_ok());
+        assert!(make_file_at(dir_path, "foo.jpg").is_ok());
+        assert!(make_dir_at(dir_path, "examples").is_ok());
+        let subdir: PathBuf = dir_path.join("examples");
+        assert!(make_file_at(subdir.as_path(), "omar.txt").is_ok());
+        assert!(make_file_at(subdir.as_path(), "examples.csv").is_ok());
+        let host: Localhost = Localhost::new(dir_path.to_path_buf()).ok().unwrap();
+        let mut walk: SearchWalk = SearchWalk::new(dir_path, "*.txt", 16);
+        while walk.step(|p| host.scan_dir(p)) {}
+        let mut names: Vec<&str> = walk.found.iter().map(|x| x.get_name()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["omar.txt", "pippo.txt"]);
+        let mut walk: SearchWalk = SearchWalk::new(dir_path, "examples", 16);
+        while walk.step(|p| host.scan_dir(p)) {}
+        let mut names: Vec<&str> = walk.found.iter().map(|x| x.get_name()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["examples", "examples.csv"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_find_walk_errors() {
+        let mut client: MockFileTransfer = MockFileTransfer::default().dir(
+            Path::new("/home/omar"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar/private"), true),
+                make_fsentry(PathBuf::from("/home/omar/notes.txt"), false),
+            ],
+        );
+        let result: SearchWalk = walk(&mut client, "notes", 16);
+        assert_eq!(found(&result), vec!["/home/omar/notes.txt"]);
+        assert_eq!(result.errors.len(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+//! ## Info
+//!
+//! `info` assembles the details about an entry shown in the file info popup
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::fs::FsEntry;
+use crate::utils::fmt::{fmt_bytes, fmt_pex, fmt_time, SizeUnit};
+
+const TIME_FMT: &str = "%b %d %Y %H:%M:%S";
+
+/// ## InfoField
+///
+/// Describes which property of the entry an info row reports
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoField {
+    Path,
+    Symlink,
+    FileType,
+    Size,
+    Mode,
+    CreationTime,
+    ModifiedTime,
+    AccessTime,
+    User,
+    Group,
+}
+
+impl InfoField {
+    /// ### label
+    ///
+    /// Returns the label displayed before the value of the field
+    pub fn label(&self) -> &'static str {
+        match self {
+            InfoField::Path => "Path: ",
+            InfoField::Symlink => "Symlink to: ",
+            InfoField::FileType => "File type: ",
+            InfoField::Size => "Size: ",
+            InfoField::Mode => "Mode: ",
+            InfoField::CreationTime => "Creation time: ",
+            InfoField::ModifiedTime => "Last modified time: ",
+            InfoField::AccessTime => "Last access time: ",
+            InfoField::User => "User: ",
+            InfoField::Group => "Group: ",
+        }
+    }
+}
+
+/// ### entry_info
+///
+/// Build the rows describing `entry`, in the order they're displayed.
+/// Fields which are not available for the entry (e.g. mode on Windows, size for directories) are omitted.
+/// `user` and `group` resolve an uid or a gid to its name, when possible
+pub fn entry_info<U, G>(
+    entry: &FsEntry,
+    unit: SizeUnit,
+    user: U,
+    group: G,
+) -> Vec<(InfoField, String)>
+where
+    U: Fn(u32) -> Option<String>,
+    G: Fn(u32) -> Option<String>,
+{
+    let mut rows: Vec<(InfoField, String)> =
+        vec![(InfoField::Path, entry.get_abs_path().display().to_string())];
+    let real: FsEntry = entry.get_realfile();
+    if real.get_abs_path() != entry.get_abs_path() {
+        rows.push((
+            InfoField::Symlink,
+            real.get_abs_path().display().to_string(),
+        ));
+    }
+    if let Some(ftype) = entry.get_ftype() {
+        rows.push((InfoField::FileType, ftype));
+    }
+    if let FsEntry::File(file) = entry {
+        rows.push((
+            InfoField::Size,
+            format!("{} ({})", fmt_bytes(file.size as u64, unit), file.size),
+        ));
+    }
+    if let Some((owner, grp, others)) = entry.get_unix_pex() {
+        rows.push((
+            InfoField::Mode,
+            format!(
+                "{}{}{} ({})",
+                owner,
+                grp,
+                others,
+                fmt_pex(owner, grp, others)
+            ),
+        ));
+    }
+    rows.push((
+        InfoField::CreationTime,
+        fmt_time(entry.get_creation_time(), TIME_FMT),
+    ));
+    rows.push((
+        InfoField::ModifiedTime,
+        fmt_time(entry.get_last_change_time(), TIME_FMT),
+    ));
+    rows.push((
+        InfoField::AccessTime,
+        fmt_time(entry.get_last_access_time(), TIME_FMT),
+    ));
+    if let Some(uid) = entry.get_user() {
+        rows.push((InfoField::User, fmt_id(uid, user(uid))));
+    }
+    if let Some(gid) = entry.get_group() {
+        rows.push((InfoField::Group, fmt_id(gid, group(gid))));
+    }
+    rows
+}
+
+/// ### fmt_id
+///
+/// Format an uid or gid along with its name, if resolved
+fn fmt_id(id: u32, name: Option<String>) -> String {
+    match name {
+        Some(name) => format!("{} ({})", name, id),
+        None => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fs::{FsDirectory, FsFile};
+
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_info_symlink() {
+        let target: FsEntry = FsEntry::File(FsFile {
+            name: String::from("omar.txt"),
+            abs_path: PathBuf::from("/home/omar/omar.txt"),
+            last_change_time: SystemTime::UNIX_EPOCH,
+            last_access_time: SystemTime::UNIX_EPOCH,
+            creation_time: SystemTime::UNIX_EPOCH,
+            size: 2048,
+            ftype: Some(String::from("txt")),
+            readonly: false,
+            symlink: None,
+            user: Some(1000),
+            group: Some(1000),
+            unix_pex: Some((6, 4, 4)),
+        });
+        let mtime: SystemTime = SystemTime::UNIX_EPOCH + Duration::from_secs(86400);
+        let link: FsEntry = FsEntry::File(FsFile {
+            name: String::from("link.txt"),
+            abs_path: PathBuf::from("/tmp/link.txt"),
+            last_change_time: mtime,
+            last_access_time: SystemTime::UNIX_EPOCH,
+            creation_time: SystemTime::UNIX_EPOCH,
+            size: 19,
+            ftype: Some(String::from("txt")),
+            readonly: false,
+            symlink: Some(Box::new(target)),
+            user: Some(1000),
+            group: Some(33),
+            unix_pex: Some((7, 7, 7)),
+        });
+        let epoch: String = fmt_time(SystemTime::UNIX_EPOCH, TIME_FMT);
+        assert_eq!(
+            entry_info(
+                &link,
+                SizeUnit::Binary,
+                |uid| match uid {
+                    1000 => Some(String::from("omar")),
+                    _ => None,
+                },
+                |_| None
+            ),
+            vec![
+                (InfoField::Path, String::from("/tmp/link.txt")),
+                (InfoField::Symlink, String::from("/home/omar/omar.txt")),
+                (InfoField::FileType, String::from("txt")),
+                (InfoField::Size, String::from("19 B (19)")),
+                (InfoField::Mode, String::from("777 (rwxrwxrwx)")),
+                (InfoField::CreationTime, epoch.clone()),
+                (InfoField::ModifiedTime, fmt_time(mtime, TIME_FMT)),
+                (InfoField::AccessTime, epoch),
+                (InfoField::User, String::from("omar (1000)")),
+                (InfoField::Group, String::from("33")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_info_directory() {
+        let dir: FsEntry = FsEntry::Directory(FsDirectory {
+            name: String::from("omar"),
+            abs_path: PathBuf::from("/home/omar"),
+            last_change_time: SystemTime::UNIX_EPOCH,
+            last_access_time: SystemTime::UNIX_EPOCH,
+            creation_time: SystemTime::UNIX_EPOCH,
+            readonly: false,
+            symlink: None,
+            user: Some(0),
+            group: None,
+            unix_pex: Some((7, 5, 5)),
+        });
+        let epoch: String = fmt_time(SystemTime::UNIX_EPOCH, TIME_FMT);
+        assert_eq!(
+            entry_info(
+                &dir,
+                SizeUnit::Si,
+                |_| Some(String::from("root")),
+                |_| panic!("no group")
+            ),
+            vec![
+                (InfoField::Path, String::from("/home/omar")),
+                (InfoField::Mode, String::from("755 (rwxr-xr-x)")),
+                (InfoField::CreationTime, epoch.clone()),
+                (InfoField::ModifiedTime, epoch.clone()),
+                (InfoField::AccessTime, epoch),
+                (InfoField::User, String::from("root (0)")),
+            ]
+        );
+        // Without mode and user
+        let dir: FsEntry = FsEntry::Directory(FsDirectory {
+            user: None,
+            unix_pex: None,
+            ..dir.unwrap_dir()
+        });
+        assert_eq!(entry_info(&dir, SizeUnit::Si, |_| None, |_| None).len(), 4);
+    }
+}
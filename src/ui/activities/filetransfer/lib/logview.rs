@@ -0,0 +1,162 @@
+//! ## LogView
+//!
+//! `logview` provides the level filter and the clipboard export used by the log viewer
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::super::{LogLevel, LogRecord};
+
+use std::fmt;
+
+/// ## LogFilter
+///
+/// Describes the minimum level of the records displayed in the log viewer
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LogFilter {
+    #[default]
+    All,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogFilter {
+    /// ### next
+    ///
+    /// Returns the filter which follows this one (All -> Info+ -> Warn+ -> Error -> All)
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Info,
+            Self::Info => Self::Warn,
+            Self::Warn => Self::Error,
+            Self::Error => Self::All,
+        }
+    }
+
+    /// ### matches
+    ///
+    /// Returns whether a record with the provided level must be displayed
+    pub fn matches(&self, level: &LogLevel) -> bool {
+        match self {
+            Self::All => true,
+            Self::Info => matches!(level, LogLevel::Info | LogLevel::Warn | LogLevel::Error),
+            Self::Warn => matches!(level, LogLevel::Warn | LogLevel::Error),
+            Self::Error => matches!(level, LogLevel::Error),
+        }
+    }
+}
+
+impl fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label: &str = match self {
+            Self::All => "All",
+            Self::Info => "Info+",
+            Self::Warn => "Warn+",
+            Self::Error => "Error",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// ### fmt_record
+///
+/// Format a log record as a single line of text
+pub fn fmt_record(record: &LogRecord) -> String {
+    format!(
+        "{} [{:5}]: {}",
+        record.time.format("%Y-%m-%dT%H:%M:%S%Z"),
+        record.level,
+        record.msg
+    )
+}
+
+/// ### osc52_copy
+///
+/// Make the OSC 52 escape sequence which asks the terminal to store `text` into the clipboard.
+/// Being handled by the terminal, it works through SSH sessions too
+pub fn osc52_copy(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64::encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn count_matching(records: &[LogRecord], filter: LogFilter) -> usize {
+        records.iter().filter(|x| filter.matches(&x.level)).count()
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_logview_filter() {
+        let records: Vec<LogRecord> = vec![
+            LogRecord::new(LogLevel::Info, String::from("connected")),
+            LogRecord::new(LogLevel::Warn, String::from("could not stat file")),
+            LogRecord::new(LogLevel::Info, String::from("uploaded file")),
+            LogRecord::new(LogLevel::Error, String::from("permission denied")),
+            LogRecord::new(LogLevel::Warn, String::from("transfer aborted")),
+            LogRecord::new(LogLevel::Info, String::from("disconnected")),
+        ];
+        assert_eq!(count_matching(&records, LogFilter::All), 6);
+        assert_eq!(count_matching(&records, LogFilter::Info), 6);
+        assert_eq!(count_matching(&records, LogFilter::Warn), 3);
+        assert_eq!(count_matching(&records, LogFilter::Error), 1);
+        let errors: Vec<&str> = records
+            .iter()
+            .filter(|x| LogFilter::Error.matches(&x.level))
+            .map(|x| x.msg.as_str())
+            .collect();
+        assert_eq!(errors, vec!["permission denied"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_logview_filter_cycle() {
+        let filter: LogFilter = LogFilter::default();
+        assert_eq!(filter, LogFilter::All);
+        assert_eq!(filter.next(), LogFilter::Info);
+        assert_eq!(filter.next().next(), LogFilter::Warn);
+        assert_eq!(filter.next().next().next(), LogFilter::Error);
+        assert_eq!(filter.next().next().next().next(), LogFilter::All);
+        assert_eq!(LogFilter::All.to_string().as_str(), "All");
+        assert_eq!(LogFilter::Info.to_string().as_str(), "Info+");
+        assert_eq!(LogFilter::Warn.to_string().as_str(), "Warn+");
+        assert_eq!(LogFilter::Error.to_string().as_str(), "Error");
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_logview_fmt_record() {
+        let record: LogRecord = LogRecord::new(LogLevel::Warn, String::from("file exists"));
+        let line: String = fmt_record(&record);
+        assert!(line.ends_with(" [WARN ]: file exists"));
+        let record: LogRecord = LogRecord::new(LogLevel::Error, String::from("oops"));
+        assert!(fmt_record(&record).ends_with(" [ERROR]: oops"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_logview_osc52() {
+        assert_eq!(osc52_copy("hello").as_str(), "\x1b]52;c;aGVsbG8=\x07");
+    }
+}
@@ -26,4 +26,20 @@
  * SOFTWARE.
  */
 pub(crate) mod browser;
+pub(crate) mod connect;
+pub(crate) mod errors;
+pub(crate) mod find;
+pub(crate) mod info;
+pub(crate) mod logview;
+pub(crate) mod mover;
+pub(crate) mod newfile;
+pub(crate) mod pool;
+pub(crate) mod preview;
+pub(crate) mod replace;
+pub(crate) mod scan;
+pub(crate) mod sessions;
+pub(crate) mod size_gate;
+pub(crate) mod stats;
+pub(crate) mod sync;
 pub(crate) mod transfer;
+pub(crate) mod watcher;
@@ -0,0 +1,247 @@
+//! ## Mover
+//!
+//! `mover` is the module which moves entries to another directory, renaming them when possible
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::filetransfer::{FileTransferError, FileTransferErrorType};
+use crate::fs::FsEntry;
+use crate::host::HostError;
+
+use std::fmt;
+use std::path::Path;
+
+/// ## RenameError
+///
+/// RenameError describes why an entry couldn't be renamed
+#[derive(Debug, Eq, PartialEq)]
+pub enum RenameError {
+    Unsupported(String), // Rename can't move the entry there; copy and delete it instead
+    Failed(String),
+}
+
+impl From<HostError> for RenameError {
+    fn from(err: HostError) -> Self {
+        // Rename can't move entries across file systems
+        match err.is_cross_device() {
+            true => RenameError::Unsupported(err.to_string()),
+            false => RenameError::Failed(err.to_string()),
+        }
+    }
+}
+
+impl From<FileTransferError> for RenameError {
+    fn from(err: FileTransferError) -> Self {
+        match err.kind() {
+            FileTransferErrorType::UnsupportedFeature => RenameError::Unsupported(err.to_string()),
+            _ => RenameError::Failed(err.to_string()),
+        }
+    }
+}
+
+/// ## Moved
+///
+/// Moved describes how an entry has been moved
+#[derive(Debug, Eq, PartialEq)]
+pub enum Moved {
+    Renamed,
+    Copied, // Copied to destination, then removed
+}
+
+/// ## MoveError
+///
+/// MoveError describes at which step moving an entry failed
+#[derive(Debug, Eq, PartialEq)]
+pub enum MoveError {
+    Rename(String),
+    Copy(String),
+    Remove(String), // Entry has been copied, but the source is still there
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::Rename(err) => write!(f, "{}", err),
+            MoveError::Copy(err) => write!(f, "could not copy: {}", err),
+            MoveError::Remove(err) => write!(f, "copied, but could not remove source: {}", err),
+        }
+    }
+}
+
+/// ## EntryMover
+///
+/// EntryMover provides the operations required to move an entry on a host
+pub trait EntryMover {
+    fn rename(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), RenameError>;
+    fn copy(&mut self, entry: &FsEntry, dest: &Path) -> Result<(), String>;
+    fn remove(&mut self, entry: &FsEntry) -> Result<(), String>;
+}
+
+/// ### move_entry
+///
+/// Move `entry` to `dest`, renaming it.
+/// If rename doesn't support moving the entry there, the entry is copied to `dest`
+/// and then removed; the source is never removed if copy fails
+pub fn move_entry(
+    mover: &mut dyn EntryMover,
+    entry: &FsEntry,
+    dest: &Path,
+) -> Result<Moved, MoveError> {
+    match mover.rename(entry, dest) {
+        Ok(_) => Ok(Moved::Renamed),
+        Err(RenameError::Failed(err)) => Err(MoveError::Rename(err)),
+        Err(RenameError::Unsupported(_)) => {
+            mover.copy(entry, dest).map_err(MoveError::Copy)?;
+            mover.remove(entry).map_err(MoveError::Remove)?;
+            Ok(Moved::Copied)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::host::HostErrorType;
+    use crate::utils::test_helpers::make_fsentry;
+
+    use pretty_assertions::assert_eq;
+    use std::io;
+    use std::path::PathBuf;
+
+    /// Mover which records the operations performed
+    struct MockMover {
+        rename: Result<(), RenameError>,
+        copy: Result<(), String>,
+        remove: Result<(), String>,
+        ops: Vec<&'static str>,
+    }
+
+    impl MockMover {
+        fn new(rename: Result<(), RenameError>) -> Self {
+            Self {
+                rename,
+                copy: Ok(()),
+                remove: Ok(()),
+                ops: Vec::new(),
+            }
+        }
+    }
+
+    impl EntryMover for MockMover {
+        fn rename(&mut self, _entry: &FsEntry, _dest: &Path) -> Result<(), RenameError> {
+            self.ops.push("rename");
+            std::mem::replace(&mut self.rename, Ok(()))
+        }
+
+        fn copy(&mut self, _entry: &FsEntry, _dest: &Path) -> Result<(), String> {
+            self.ops.push("copy");
+            self.copy.clone()
+        }
+
+        fn remove(&mut self, _entry: &FsEntry) -> Result<(), String> {
+            self.ops.push("remove");
+            self.remove.clone()
+        }
+    }
+
+    fn move_mock(mover: &mut MockMover) -> Result<Moved, MoveError> {
+        let entry: FsEntry = make_fsentry(PathBuf::from("/home/omar/README.md"), false);
+        move_entry(mover, &entry, Path::new("/mnt/usb/README.md"))
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_mover_rename_error() {
+        // Local
+        #[cfg(target_family = "unix")]
+        {
+            let err: HostError = HostError::new(
+                HostErrorType::CouldNotCreateFile,
+                Some(io::Error::from_raw_os_error(libc::EXDEV)),
+                Path::new("/home/omar/README.md"),
+            );
+            assert!(matches!(
+                RenameError::from(err),
+                RenameError::Unsupported(_)
+            ));
+        }
+        let err: HostError = HostError::new(
+            HostErrorType::CouldNotCreateFile,
+            Some(io::Error::from(io::ErrorKind::PermissionDenied)),
+            Path::new("/home/omar/README.md"),
+        );
+        assert!(matches!(RenameError::from(err), RenameError::Failed(_)));
+        // Remote
+        assert!(matches!(
+            RenameError::from(FileTransferError::new(
+                FileTransferErrorType::UnsupportedFeature
+            )),
+            RenameError::Unsupported(_)
+        ));
+        assert!(matches!(
+            RenameError::from(FileTransferError::new(FileTransferErrorType::PexError)),
+            RenameError::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_mover_rename() {
+        let mut mover: MockMover = MockMover::new(Ok(()));
+        assert_eq!(move_mock(&mut mover), Ok(Moved::Renamed));
+        assert_eq!(mover.ops, vec!["rename"]);
+        // Rename failed: nothing else is tried
+        let mut mover: MockMover = MockMover::new(Err(RenameError::Failed(String::from("denied"))));
+        assert_eq!(
+            move_mock(&mut mover),
+            Err(MoveError::Rename(String::from("denied")))
+        );
+        assert_eq!(mover.ops, vec!["rename"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_mover_fallback() {
+        let unsupported = || Err(RenameError::Unsupported(String::from("cross-device")));
+        let mut mover: MockMover = MockMover::new(unsupported());
+        assert_eq!(move_mock(&mut mover), Ok(Moved::Copied));
+        assert_eq!(mover.ops, vec!["rename", "copy", "remove"]);
+        // Copy fails: source is kept
+        let mut mover: MockMover = MockMover::new(unsupported());
+        mover.copy = Err(String::from("no space left"));
+        assert_eq!(
+            move_mock(&mut mover),
+            Err(MoveError::Copy(String::from("no space left")))
+        );
+        assert_eq!(mover.ops, vec!["rename", "copy"]);
+        // Remove fails
+        let mut mover: MockMover = MockMover::new(unsupported());
+        mover.remove = Err(String::from("busy"));
+        let err: MoveError = move_mock(&mut mover).unwrap_err();
+        assert_eq!(err, MoveError::Remove(String::from("busy")));
+        assert_eq!(
+            err.to_string().as_str(),
+            "copied, but could not remove source: busy"
+        );
+        assert_eq!(mover.ops, vec!["rename", "copy", "remove"]);
+    }
+}
@@ -0,0 +1,128 @@
+//! ## NewFile
+//!
+//! `newfile` is the module which validates the creation of new empty files
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+/// ## NewFileError
+///
+/// Describes why a file name can't be used to create a new file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NewFileError {
+    EmptyName,
+    InvalidName,
+    PathSeparator,
+}
+
+impl std::fmt::Display for NewFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let err: &str = match self {
+            NewFileError::EmptyName => "file name can't be empty",
+            NewFileError::InvalidName => "\".\" and \"..\" are not valid file names",
+            NewFileError::PathSeparator => "file name can't contain path separators",
+        };
+        write!(f, "{}", err)
+    }
+}
+
+/// ## NewFilePolicy
+///
+/// Describes what to do when creating a new file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NewFilePolicy {
+    Create,  // File doesn't exist; create it
+    Ask,     // File exists; ask the user whether to replace it
+    Replace, // File exists; replace it with an empty file
+}
+
+impl NewFilePolicy {
+    /// ### new
+    ///
+    /// Get the policy to apply, given whether the file exists and whether the user must be prompted before replacing files
+    pub fn new(exists: bool, prompt_on_replace: bool) -> Self {
+        match (exists, prompt_on_replace) {
+            (false, _) => NewFilePolicy::Create,
+            (true, true) => NewFilePolicy::Ask,
+            (true, false) => NewFilePolicy::Replace,
+        }
+    }
+}
+
+/// ### check_file_name
+///
+/// Check whether `name` can be used as name for a new file in the working directory.
+/// Both `/` and `\` are rejected, since the file may be created on a host with a different path separator
+pub fn check_file_name(name: &str) -> Result<(), NewFileError> {
+    if name.is_empty() {
+        Err(NewFileError::EmptyName)
+    } else if name.contains(&['/', '\\'][..]) {
+        Err(NewFileError::PathSeparator)
+    } else if name == "." || name == ".." {
+        Err(NewFileError::InvalidName)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_newfile_check_file_name() {
+        assert!(check_file_name("README.md").is_ok());
+        assert!(check_file_name(".gitignore").is_ok());
+        assert!(check_file_name("my file ...txt").is_ok());
+        assert_eq!(check_file_name(""), Err(NewFileError::EmptyName));
+        assert_eq!(check_file_name("."), Err(NewFileError::InvalidName));
+        assert_eq!(check_file_name(".."), Err(NewFileError::InvalidName));
+        assert_eq!(
+            check_file_name("src/main.rs"),
+            Err(NewFileError::PathSeparator)
+        );
+        assert_eq!(
+            check_file_name("/tmp/foo"),
+            Err(NewFileError::PathSeparator)
+        );
+        assert_eq!(
+            check_file_name("C:\\foo.txt"),
+            Err(NewFileError::PathSeparator)
+        );
+        assert_eq!(
+            NewFileError::PathSeparator.to_string().as_str(),
+            "file name can't contain path separators"
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_newfile_policy() {
+        assert_eq!(NewFilePolicy::new(false, true), NewFilePolicy::Create);
+        assert_eq!(NewFilePolicy::new(false, false), NewFilePolicy::Create);
+        assert_eq!(NewFilePolicy::new(true, true), NewFilePolicy::Ask);
+        assert_eq!(NewFilePolicy::new(true, false), NewFilePolicy::Replace);
+    }
+}
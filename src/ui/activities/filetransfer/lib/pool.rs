@@ -0,0 +1,124 @@
+//! ## Pool
+//!
+//! `pool` is the module which provides the worker pool used to run transfers concurrently
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// ext
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+
+/// ### run_jobs
+///
+/// Run `job` for each of `jobs`, dispatching them to a pool of at most `workers` threads.
+/// Results are returned in the same order of `jobs`
+pub fn run_jobs<T, R, F>(jobs: Vec<T>, workers: usize, job: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let total: usize = jobs.len();
+    let workers: usize = workers.max(1).min(total);
+    // Run on the current thread if no concurrency is required
+    if workers <= 1 {
+        return jobs.into_iter().map(job).collect();
+    }
+    let queue: Mutex<VecDeque<(usize, T)>> = Mutex::new(jobs.into_iter().enumerate().collect());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                // Take next job; the lock is released before running it
+                let next: Option<(usize, T)> = queue.lock().unwrap().pop_front();
+                match next {
+                    Some((i, x)) => {
+                        let result: R = job(x);
+                        results.lock().unwrap()[i] = Some(result);
+                    }
+                    None => break,
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_pool_run_jobs() {
+        let running: AtomicUsize = AtomicUsize::new(0);
+        let peak: AtomicUsize = AtomicUsize::new(0);
+        let completed: AtomicUsize = AtomicUsize::new(0);
+        let results: Vec<usize> = run_jobs((0..32).collect(), 4, |x: usize| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            completed.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        });
+        // No more than 4 jobs at once, but more than one
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+        // All jobs completed, results are ordered
+        assert_eq!(completed.load(Ordering::SeqCst), 32);
+        assert_eq!(results, (0..32).map(|x| x * 2).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_pool_run_jobs_serial() {
+        let running: AtomicUsize = AtomicUsize::new(0);
+        let peak: AtomicUsize = AtomicUsize::new(0);
+        // 0 workers means 1
+        let results: Vec<usize> = run_jobs((0..8).collect(), 0, |x: usize| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            running.fetch_sub(1, Ordering::SeqCst);
+            x
+        });
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert_eq!(results, (0..8).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_pool_run_jobs_empty() {
+        let results: Vec<usize> = run_jobs(Vec::new(), 4, |x: usize| x);
+        assert!(results.is_empty());
+    }
+}
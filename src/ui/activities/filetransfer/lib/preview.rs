@@ -0,0 +1,144 @@
+//! ## Preview
+//!
+//! `preview` is the module which provides the utilities to preview the content of text files
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::io::Read;
+
+/// Amount of bytes shown in preview
+pub const PREVIEW_LEN: usize = 65536; // 64KiB
+
+/// ### read_preview
+///
+/// Read the file content to preview from `reader`.
+/// At most `max` bytes are read, and never more than `PREVIEW_LEN`, so the rest of the file is not transferred
+pub fn read_preview(reader: &mut dyn Read, max: u64) -> std::io::Result<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::with_capacity(PREVIEW_LEN);
+    reader
+        .take(max.min(PREVIEW_LEN as u64))
+        .read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// ### is_binary
+///
+/// Returns whether the provided content looks like binary data.
+/// Content is considered binary if it contains NUL bytes or if it's not valid UTF-8.
+/// A multi-byte sequence truncated at the end of the content is tolerated, since the preview may be cut there
+pub fn is_binary(content: &[u8]) -> bool {
+    if content.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(content) {
+        Ok(_) => false,
+        Err(err) => err.error_len().is_some(),
+    }
+}
+
+/// ### preview_lines
+///
+/// Split text content into lines which can be safely printed to the terminal.
+/// Tabs are expanded, while control characters are replaced
+pub fn preview_lines(content: &[u8]) -> Vec<String> {
+    let text: String = String::from_utf8_lossy(content).to_string();
+    text.lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '\t' => String::from("    "),
+                    c if c.is_control() => String::from(char::REPLACEMENT_CHARACTER),
+                    c => c.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_preview_is_binary_text() {
+        assert_eq!(is_binary(b""), false);
+        assert_eq!(is_binary(b"Hello, world!\n"), false);
+        assert_eq!(is_binary(b"fn main() {\n\tprintln!(\"hi\");\r\n}\n"), false);
+        assert_eq!(is_binary("Perché così è più bello 🦀\n".as_bytes()), false);
+        // Truncated multi-byte sequence at the end
+        let crab: &[u8] = "crab 🦀".as_bytes();
+        assert_eq!(is_binary(&crab[..crab.len() - 1]), false);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_preview_is_binary_binary() {
+        // NUL bytes
+        assert_eq!(is_binary(b"hello\0world"), true);
+        // ELF header
+        assert_eq!(
+            is_binary(&[0x7f, 0x45, 0x4c, 0x46, 0x02, 0x01, 0x01, 0x00]),
+            true
+        );
+        // PNG header
+        assert_eq!(
+            is_binary(&[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]),
+            true
+        );
+        // Invalid UTF-8 in the middle of content
+        assert_eq!(is_binary(&[0x61, 0xff, 0xfe, 0x62]), true);
+        // Latin-1
+        assert_eq!(is_binary(&[0x63, 0x61, 0x66, 0xe9, 0x20, 0x21]), true);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_preview_lines() {
+        assert_eq!(
+            preview_lines(b"first\n\tsecond\r\nthird \x1b[31mred\n"),
+            vec![
+                String::from("first"),
+                String::from("    second"),
+                String::from("third \u{fffd}[31mred"),
+            ]
+        );
+        assert!(preview_lines(b"").is_empty());
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_preview_read() {
+        let content: Vec<u8> = vec![b'a'; PREVIEW_LEN * 2];
+        let mut reader: Cursor<Vec<u8>> = Cursor::new(content);
+        let preview: Vec<u8> = read_preview(&mut reader, u64::MAX).unwrap();
+        assert_eq!(preview.len(), PREVIEW_LEN);
+        // The rest of the content is not read
+        assert_eq!(reader.position(), PREVIEW_LEN as u64);
+        let preview: Vec<u8> = read_preview(&mut Cursor::new(b"hello".to_vec()), u64::MAX).unwrap();
+        assert_eq!(preview, b"hello".to_vec());
+        let preview: Vec<u8> = read_preview(&mut Cursor::new(b"hello".to_vec()), 4).unwrap();
+        assert_eq!(preview, b"hell".to_vec());
+    }
+}
@@ -0,0 +1,180 @@
+//! ## Replace
+//!
+//! `replace` is the module which keeps track of the answers given to the file replace prompt
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+/// ## ReplaceAnswer
+///
+/// Describes the answer given by the user to the file replace prompt
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplaceAnswer {
+    Yes,
+    No,
+    YesToAll,
+    NoToAll,
+}
+
+impl ReplaceAnswer {
+    /// ### from_choice
+    ///
+    /// Get answer from the index of the option selected in the prompt
+    pub fn from_choice(choice: usize) -> Self {
+        match choice {
+            0 => ReplaceAnswer::Yes,
+            2 => ReplaceAnswer::YesToAll,
+            3 => ReplaceAnswer::NoToAll,
+            _ => ReplaceAnswer::No,
+        }
+    }
+
+    /// ### replace
+    ///
+    /// Returns whether the answer allows replacing the file
+    fn replace(self) -> bool {
+        matches!(self, ReplaceAnswer::Yes | ReplaceAnswer::YesToAll)
+    }
+
+    /// ### for_all
+    ///
+    /// Returns whether the answer applies to all the files of the operation
+    fn for_all(self) -> bool {
+        matches!(self, ReplaceAnswer::YesToAll | ReplaceAnswer::NoToAll)
+    }
+}
+
+/// ## ReplaceStates
+///
+/// Keeps the answers given to the replace prompt for the duration of a transfer operation
+#[derive(Default)]
+pub struct ReplaceStates {
+    all: Option<bool>,    // Decision which applies to all the following files
+    answer: Option<bool>, // Answer to the current prompt, not consumed yet
+}
+
+impl ReplaceStates {
+    /// ### decision
+    ///
+    /// Returns the decision taken for all the files, if any.
+    /// If `None`, the user must be prompted
+    pub fn decision(&self) -> Option<bool> {
+        self.all
+    }
+
+    /// ### answer
+    ///
+    /// Register the answer given to the prompt
+    pub fn answer(&mut self, answer: ReplaceAnswer) {
+        self.answer = Some(answer.replace());
+        if answer.for_all() {
+            self.all = Some(answer.replace());
+        }
+    }
+
+    /// ### take_answer
+    ///
+    /// Take the answer given to the current prompt
+    pub fn take_answer(&mut self) -> Option<bool> {
+        self.answer.take()
+    }
+
+    /// ### reset
+    ///
+    /// Forget all the answers
+    pub fn reset(&mut self) {
+        self.all = None;
+        self.answer = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    /// Returns whether each file gets replaced, given the answers the user would give if prompted
+    fn replay(states: &mut ReplaceStates, files: usize, answers: &[ReplaceAnswer]) -> Vec<bool> {
+        let mut answers = answers.iter();
+        (0..files)
+            .map(|_| match states.decision() {
+                Some(decision) => decision,
+                None => {
+                    states.answer(*answers.next().unwrap());
+                    states.take_answer().unwrap()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_replace_answer() {
+        assert_eq!(ReplaceAnswer::from_choice(0), ReplaceAnswer::Yes);
+        assert_eq!(ReplaceAnswer::from_choice(1), ReplaceAnswer::No);
+        assert_eq!(ReplaceAnswer::from_choice(2), ReplaceAnswer::YesToAll);
+        assert_eq!(ReplaceAnswer::from_choice(3), ReplaceAnswer::NoToAll);
+        assert_eq!(ReplaceAnswer::from_choice(8), ReplaceAnswer::No);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_replace_states() {
+        let mut states: ReplaceStates = ReplaceStates::default();
+        assert_eq!(states.decision(), None);
+        assert_eq!(states.take_answer(), None);
+        // Single answers are asked for each file
+        assert_eq!(
+            replay(
+                &mut states,
+                3,
+                &[ReplaceAnswer::Yes, ReplaceAnswer::No, ReplaceAnswer::Yes]
+            ),
+            vec![true, false, true]
+        );
+        assert_eq!(states.decision(), None);
+        // Yes to all
+        assert_eq!(
+            replay(
+                &mut states,
+                4,
+                &[ReplaceAnswer::No, ReplaceAnswer::YesToAll]
+            ),
+            vec![false, true, true, true]
+        );
+        assert_eq!(states.decision(), Some(true));
+        // Answer has been consumed
+        assert_eq!(states.take_answer(), None);
+        // Reset
+        states.reset();
+        assert_eq!(states.decision(), None);
+        // No to all
+        assert_eq!(
+            replay(&mut states, 3, &[ReplaceAnswer::NoToAll]),
+            vec![false, false, false]
+        );
+        assert_eq!(states.decision(), Some(false));
+        states.reset();
+        assert_eq!(states.decision(), None);
+    }
+}
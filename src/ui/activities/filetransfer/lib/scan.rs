@@ -0,0 +1,222 @@
+//! ## Scan
+//!
+//! `scan` is the module which computes the total size of a transfer before it starts
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::fs::FsEntry;
+
+use std::path::{Path, PathBuf};
+
+/// ### SizeScan
+///
+/// SizeScan computes the total size and the amount of files of a transfer.
+/// Directories are walked one at a time through `walk`, so that the caller
+/// can report the progress of the scan and stop it in between; if stopped, the totals
+/// collected so far are kept.
+#[derive(Default)]
+pub struct SizeScan {
+    pending: Vec<PathBuf>,
+    size: usize,
+    files: usize,
+    dirs: usize,
+}
+
+impl SizeScan {
+    /// ### new
+    ///
+    /// Instantiates a new `SizeScan` for `entries`
+    pub fn new(entries: &[FsEntry]) -> Self {
+        let mut scan: Self = Self::default();
+        scan.push(entries);
+        scan
+    }
+
+    /// ### walk
+    ///
+    /// Walk the queued directories, listing them with `list_dir`.
+    /// After each directory, `proceed` is called with the progress of the scan; the walk stops if it returns `false`.
+    /// `ctx` is passed to both callbacks. Returns whether all the directories have been walked
+    pub fn walk<C, L, P>(&mut self, ctx: &mut C, mut list_dir: L, mut proceed: P) -> bool
+    where
+        L: FnMut(&mut C, &Path) -> Vec<FsEntry>,
+        P: FnMut(&mut C, &SizeScan, &Path) -> bool,
+    {
+        while let Some(dir) = self.next_dir() {
+            let entries: Vec<FsEntry> = list_dir(ctx, dir.as_path());
+            self.push(entries.as_slice());
+            if !proceed(ctx, self, dir.as_path()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// ### next_dir
+    ///
+    /// Get the next directory to list, if any.
+    /// Its entries must be given back through `push`
+    fn next_dir(&mut self) -> Option<PathBuf> {
+        let dir: Option<PathBuf> = self.pending.pop();
+        if dir.is_some() {
+            self.dirs += 1;
+        }
+        dir
+    }
+
+    /// ### push
+    ///
+    /// Account `entries`; directories are queued to be listed
+    fn push(&mut self, entries: &[FsEntry]) {
+        for entry in entries.iter() {
+            match entry {
+                FsEntry::File(file) => {
+                    self.size += file.size;
+                    self.files += 1;
+                }
+                FsEntry::Directory(dir) => self.pending.push(dir.abs_path.clone()),
+            }
+        }
+    }
+
+    /// ### size
+    ///
+    /// Returns the size of the files found so far
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// ### files
+    ///
+    /// Returns the amount of files found so far
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    /// ### dirs
+    ///
+    /// Returns the amount of directories scanned so far
+    pub fn dirs(&self) -> usize {
+        self.dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ui::activities::filetransfer::lib::transfer::TransferStates;
+    use crate::utils::test_helpers::make_fsentry;
+
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn make_tree() -> HashMap<PathBuf, Vec<FsEntry>> {
+        let mut tree: HashMap<PathBuf, Vec<FsEntry>> = HashMap::new();
+        tree.insert(
+            PathBuf::from("/home/omar"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar/docs"), true),
+                make_fsentry(PathBuf::from("/home/omar/src"), true),
+                make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+            ],
+        );
+        tree.insert(
+            PathBuf::from("/home/omar/docs"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar/docs/a.md"), false),
+                make_fsentry(PathBuf::from("/home/omar/docs/b.md"), false),
+            ],
+        );
+        tree.insert(
+            PathBuf::from("/home/omar/src"),
+            vec![make_fsentry(PathBuf::from("/home/omar/src/main.rs"), false)],
+        );
+        tree
+    }
+
+    /// Walk `scan` on `tree`, aborting after `abort_after` directories
+    fn walk(
+        scan: &mut SizeScan,
+        tree: &HashMap<PathBuf, Vec<FsEntry>>,
+        states: &mut TransferStates,
+        abort_after: usize,
+    ) -> bool {
+        scan.walk(
+            states,
+            |_, dir| tree.get(dir).cloned().unwrap_or_default(),
+            |states, scan, _| {
+                if scan.dirs() >= abort_after {
+                    states.abort();
+                }
+                !states.aborted()
+            },
+        )
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_scan_complete() {
+        let tree = make_tree();
+        let mut scan: SizeScan = SizeScan::new(&[make_fsentry(PathBuf::from("/home/omar"), true)]);
+        assert_eq!(scan.pending.is_empty(), false);
+        let mut states: TransferStates = TransferStates::default();
+        assert_eq!(walk(&mut scan, &tree, &mut states, usize::MAX), true);
+        assert_eq!(states.aborted(), false);
+        assert_eq!(scan.pending.is_empty(), true);
+        assert_eq!(scan.dirs(), 3);
+        assert_eq!(scan.files(), 4);
+        assert_eq!(scan.size(), 127 * 4);
+        // Files only
+        let scan: SizeScan = SizeScan::new(&[
+            make_fsentry(PathBuf::from("/tmp/a.txt"), false),
+            make_fsentry(PathBuf::from("/tmp/b.txt"), false),
+        ]);
+        assert_eq!(scan.pending.is_empty(), true);
+        assert_eq!(scan.files(), 2);
+        assert_eq!(scan.size(), 254);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_scan_aborted() {
+        let tree = make_tree();
+        let mut scan: SizeScan = SizeScan::new(&[make_fsentry(PathBuf::from("/home/omar"), true)]);
+        let mut states: TransferStates = TransferStates::default();
+        // Abort after the first directory
+        assert_eq!(walk(&mut scan, &tree, &mut states, 1), false);
+        assert_eq!(states.aborted(), true);
+        assert_eq!(scan.pending.is_empty(), false);
+        assert_eq!(scan.dirs(), 1);
+        // Partial results are kept
+        assert_eq!(scan.files(), 1);
+        assert_eq!(scan.size(), 127);
+        // Unlisted directories are ignored
+        let mut scan: SizeScan =
+            SizeScan::new(&[make_fsentry(Path::new("/nowhere").to_path_buf(), true)]);
+        states.reset();
+        assert_eq!(walk(&mut scan, &tree, &mut states, usize::MAX), true);
+        assert_eq!(scan.pending.is_empty(), true);
+        assert_eq!(scan.files(), 0);
+    }
+}
@@ -0,0 +1,208 @@
+//! ## Sessions
+//!
+//! `sessions` keeps the remote sessions opened in the file transfer activity and which one is active
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::filetransfer::FileTransfer;
+use crate::fs::explorer::FileExplorer;
+
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+/// ## RemoteSession
+///
+/// A remote session: the client connected to the remote host and the explorer of its files
+pub struct RemoteSession {
+    pub client: Box<dyn FileTransfer>,
+    pub explorer: FileExplorer,
+    pub home: Option<PathBuf>, // Working directory reported by the server once connected
+}
+
+impl RemoteSession {
+    /// ### new
+    ///
+    /// Instantiates a new `RemoteSession`
+    pub fn new(client: Box<dyn FileTransfer>, explorer: FileExplorer) -> Self {
+        Self {
+            client,
+            explorer,
+            home: None,
+        }
+    }
+}
+
+/// ## Sessions
+///
+/// Holds the open sessions; there's always at least one session, which is the active one.
+/// `Sessions` dereferences to the active session
+pub struct Sessions<T> {
+    sessions: Vec<T>,
+    active: usize,
+}
+
+impl<T> Sessions<T> {
+    /// ### new
+    ///
+    /// Instantiates a new `Sessions` holding `first` only
+    pub fn new(first: T) -> Self {
+        Self {
+            sessions: vec![first],
+            active: 0,
+        }
+    }
+
+    /// ### count
+    ///
+    /// Returns the amount of open sessions
+    pub fn count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// ### active_index
+    ///
+    /// Returns the index of the active session
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// ### open
+    ///
+    /// Add a new session after the existing ones and make it the active one
+    pub fn open(&mut self, session: T) {
+        self.sessions.push(session);
+        self.active = self.sessions.len() - 1;
+    }
+
+    /// ### next
+    ///
+    /// Activate the following session; after the last one, the first one is activated
+    pub fn next(&mut self) {
+        self.active = (self.active + 1) % self.sessions.len();
+    }
+
+    /// ### close_active
+    ///
+    /// Close the active session and return it; the following session is activated, or the previous one,
+    /// if the closed session was the last one.
+    /// The last remaining session can't be closed: in this case `None` is returned
+    pub fn close_active(&mut self) -> Option<T> {
+        if self.sessions.len() < 2 {
+            return None;
+        }
+        let session: T = self.sessions.remove(self.active);
+        self.active = self.active.min(self.sessions.len() - 1);
+        Some(session)
+    }
+
+    /// ### iter_mut
+    ///
+    /// Iterate over all the sessions
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.sessions.iter_mut()
+    }
+}
+
+impl<T> Deref for Sessions<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sessions[self.active]
+    }
+}
+
+impl<T> DerefMut for Sessions<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.sessions[self.active]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sessions_new() {
+        let sessions: Sessions<&str> = Sessions::new("omar@pi");
+        assert_eq!(sessions.count(), 1);
+        assert_eq!(sessions.active_index(), 0);
+        assert_eq!(*sessions, "omar@pi");
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sessions_open_and_next() {
+        let mut sessions: Sessions<&str> = Sessions::new("a");
+        // Single session: next stays there
+        sessions.next();
+        assert_eq!(sessions.active_index(), 0);
+        // New sessions become active
+        sessions.open("b");
+        assert_eq!(sessions.active_index(), 1);
+        assert_eq!(*sessions, "b");
+        sessions.open("c");
+        assert_eq!(sessions.count(), 3);
+        assert_eq!(*sessions, "c");
+        // Next wraps around
+        sessions.next();
+        assert_eq!(sessions.active_index(), 0);
+        assert_eq!(*sessions, "a");
+        sessions.next();
+        assert_eq!(*sessions, "b");
+        // Active session can be modified
+        *sessions = "B";
+        let all: Vec<&str> = sessions.iter_mut().map(|x| *x).collect();
+        assert_eq!(all, vec!["a", "B", "c"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sessions_close() {
+        let mut sessions: Sessions<&str> = Sessions::new("a");
+        // Last session can't be closed
+        assert!(sessions.close_active().is_none());
+        assert_eq!(sessions.count(), 1);
+        sessions.open("b");
+        sessions.open("c");
+        sessions.open("d");
+        // Close in the middle: following session is activated
+        sessions.next(); // a
+        sessions.next(); // b
+        assert_eq!(sessions.close_active(), Some("b"));
+        assert_eq!(sessions.active_index(), 1);
+        assert_eq!(*sessions, "c");
+        // Close the last one: previous session is activated
+        sessions.next(); // d
+        assert_eq!(sessions.close_active(), Some("d"));
+        assert_eq!(sessions.active_index(), 1);
+        assert_eq!(*sessions, "c");
+        // Close the first one
+        sessions.next(); // a
+        assert_eq!(sessions.close_active(), Some("a"));
+        assert_eq!(sessions.active_index(), 0);
+        assert_eq!(*sessions, "c");
+        assert!(sessions.close_active().is_none());
+    }
+}
@@ -0,0 +1,71 @@
+//! ## SizeGate
+//!
+//! `size_gate` decides whether files can be opened in preview or in the text editor, according to their size
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+/// ### size_gate
+///
+/// Returns whether a file of `size` bytes can be opened, given the size `limit`.
+/// If the limit is exceeded, `ask_override` is called to ask the user whether to open the file anyway
+pub fn size_gate<F>(size: u64, limit: u64, ask_override: F) -> bool
+where
+    F: FnOnce() -> bool,
+{
+    size <= limit || ask_override()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_size_gate_within_limit() {
+        // User is never asked
+        assert_eq!(size_gate(1024, 2048, || panic!("asked")), true);
+        assert_eq!(size_gate(2048, 2048, || panic!("asked")), true);
+        assert_eq!(size_gate(0, 0, || panic!("asked")), true);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_size_gate_override() {
+        // Refused
+        assert_eq!(size_gate(2049, 2048, || false), false);
+        // Overridden
+        assert_eq!(size_gate(2049, 2048, || true), true);
+        // Asked once
+        let mut asked: usize = 0;
+        assert_eq!(
+            size_gate(u64::MAX, 0, || {
+                asked += 1;
+                true
+            }),
+            true
+        );
+        assert_eq!(asked, 1);
+    }
+}
@@ -0,0 +1,179 @@
+//! ## Stats
+//!
+//! `stats` keeps the running totals of the transfers performed during the session
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::utils::fmt::{fmt_bytes, fmt_duration, SizeUnit};
+
+use std::time::{Duration, Instant};
+
+/// ## SessionStats
+///
+/// Running totals of the file transfers performed since the session started.
+/// Only files transferred successfully count towards the transferred bytes
+pub struct SessionStats {
+    started: Instant,
+    bytes_uploaded: u64,
+    bytes_downloaded: u64,
+    files_uploaded: usize,
+    files_downloaded: usize,
+    files_failed: usize,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            bytes_uploaded: 0,
+            bytes_downloaded: 0,
+            files_uploaded: 0,
+            files_downloaded: 0,
+            files_failed: 0,
+        }
+    }
+}
+
+impl SessionStats {
+    /// ### uploaded
+    ///
+    /// Account a file of `size` bytes which has been uploaded
+    pub fn uploaded(&mut self, size: usize) {
+        self.files_uploaded += 1;
+        self.bytes_uploaded += size as u64;
+    }
+
+    /// ### downloaded
+    ///
+    /// Account a file of `size` bytes which has been downloaded
+    pub fn downloaded(&mut self, size: usize) {
+        self.files_downloaded += 1;
+        self.bytes_downloaded += size as u64;
+    }
+
+    /// ### failed
+    ///
+    /// Account a file which couldn't be transferred
+    pub fn failed(&mut self) {
+        self.files_failed += 1;
+    }
+
+    /// ### files_transferred
+    ///
+    /// Returns the amount of files transferred successfully, in both directions
+    pub fn files_transferred(&self) -> usize {
+        self.files_uploaded + self.files_downloaded
+    }
+
+    /// ### elapsed
+    ///
+    /// Returns the time elapsed since the session started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// ### lines
+    ///
+    /// Describe the statistics as (label, value) pairs, formatting sizes with `unit`
+    pub fn lines(&self, unit: SizeUnit) -> Vec<(&'static str, String)> {
+        vec![
+            ("Session duration", fmt_duration(self.elapsed())),
+            (
+                "Uploaded",
+                format!(
+                    "{} ({} files)",
+                    fmt_bytes(self.bytes_uploaded, unit),
+                    self.files_uploaded
+                ),
+            ),
+            (
+                "Downloaded",
+                format!(
+                    "{} ({} files)",
+                    fmt_bytes(self.bytes_downloaded, unit),
+                    self.files_downloaded
+                ),
+            ),
+            ("Files transferred", self.files_transferred().to_string()),
+            ("Failed transfers", self.files_failed.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_stats_default() {
+        let stats: SessionStats = SessionStats::default();
+        assert_eq!(stats.bytes_uploaded, 0);
+        assert_eq!(stats.bytes_downloaded, 0);
+        assert_eq!(stats.files_transferred(), 0);
+        assert_eq!(stats.files_failed, 0);
+        assert!(stats.elapsed() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_stats_accumulate() {
+        let mut stats: SessionStats = SessionStats::default();
+        stats.uploaded(1024);
+        stats.uploaded(2048);
+        stats.downloaded(512);
+        assert_eq!(stats.bytes_uploaded, 3072);
+        assert_eq!(stats.files_uploaded, 2);
+        assert_eq!(stats.bytes_downloaded, 512);
+        assert_eq!(stats.files_downloaded, 1);
+        assert_eq!(stats.files_transferred(), 3);
+        // Failed transfers don't count towards bytes and transferred files
+        stats.failed();
+        stats.failed();
+        assert_eq!(stats.files_failed, 2);
+        assert_eq!(stats.bytes_uploaded, 3072);
+        assert_eq!(stats.bytes_downloaded, 512);
+        assert_eq!(stats.files_transferred(), 3);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_stats_lines() {
+        let mut stats: SessionStats = SessionStats::default();
+        stats.uploaded(2048);
+        stats.downloaded(1000);
+        stats.failed();
+        let lines: Vec<(&str, String)> = stats.lines(SizeUnit::Si);
+        assert_eq!(lines[0].0, "Session duration");
+        assert_eq!(
+            lines[1..].to_vec(),
+            vec![
+                ("Uploaded", String::from("2.0 KB (1 files)")),
+                ("Downloaded", String::from("1.0 KB (1 files)")),
+                ("Files transferred", String::from("2")),
+                ("Failed transfers", String::from("1")),
+            ]
+        );
+    }
+}
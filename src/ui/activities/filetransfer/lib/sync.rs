@@ -0,0 +1,901 @@
+//! ## Sync
+//!
+//! `sync` is the module which plans the operations required to synchronize directory trees
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// locals
+use crate::filetransfer::FileTransfer;
+use crate::fs::{FsEntry, FsFile};
+use crate::host::Localhost;
+// ext
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// ## DownloadOp
+///
+/// Describes an operation to perform on localhost while downloading a remote tree
+#[derive(Debug)]
+pub enum DownloadOp {
+    Mkdir(PathBuf),        // Create directory on localhost
+    Recv(FsFile, PathBuf), // Download remote file to local path
+    Skip(FsFile, PathBuf), // Local file is unchanged; don't download it
+}
+
+impl fmt::Display for DownloadOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadOp::Mkdir(dir) => write!(f, "create directory \"{}\"", dir.display()),
+            DownloadOp::Recv(file, local) => write!(
+                f,
+                "download \"{}\" to \"{}\"",
+                file.abs_path.display(),
+                local.display()
+            ),
+            DownloadOp::Skip(file, _) => write!(f, "skip \"{}\"", file.abs_path.display()),
+        }
+    }
+}
+
+/// ## DownloadPlan
+///
+/// Contains the operations to perform to download a remote tree, in depth-first order,
+/// and the errors encountered while walking the remote tree
+#[derive(Debug, Default)]
+pub struct DownloadPlan {
+    pub ops: Vec<DownloadOp>,
+    pub errors: Vec<String>,
+    skip_existing: bool,
+}
+
+impl DownloadPlan {
+    /// ### new
+    ///
+    /// Instantiates a new empty `DownloadPlan`.
+    /// If `skip_existing` is true, files which are unchanged on localhost will be skipped
+    pub fn new(skip_existing: bool) -> Self {
+        Self {
+            ops: Vec::new(),
+            errors: Vec::new(),
+            skip_existing,
+        }
+    }
+
+    /// ### add
+    ///
+    /// Walk remote `entry` and push the operations required to download it into `local_path`.
+    /// Directories which can't be scanned are reported into `errors`, but the walk goes on
+    pub fn add(
+        &mut self,
+        client: &mut dyn FileTransfer,
+        host: &Localhost,
+        entry: &FsEntry,
+        local_path: &Path,
+    ) {
+        match entry {
+            FsEntry::File(file) => {
+                let local_file_path: PathBuf = local_path.join(file.name.as_str());
+                let op =
+                    match self.skip_existing && Self::is_unchanged(host, file, &local_file_path) {
+                        true => DownloadOp::Skip(file.clone(), local_file_path),
+                        false => DownloadOp::Recv(file.clone(), local_file_path),
+                    };
+                self.ops.push(op);
+            }
+            FsEntry::Directory(dir) => {
+                let local_dir_path: PathBuf = local_path.join(dir.name.as_str());
+                self.ops.push(DownloadOp::Mkdir(local_dir_path.clone()));
+                match client.list_dir(dir.abs_path.as_path()) {
+                    Ok(entries) => {
+                        for entry in entries.iter() {
+                            self.add(client, host, entry, local_dir_path.as_path());
+                        }
+                    }
+                    Err(err) => self.errors.push(format!(
+                        "Could not scan directory \"{}\": {}",
+                        dir.abs_path.display(),
+                        err
+                    )),
+                }
+            }
+        }
+    }
+
+    /// ### total_size
+    ///
+    /// Returns the amount of bytes which will be downloaded
+    pub fn total_size(&self) -> usize {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                DownloadOp::Recv(file, _) => file.size,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// ### is_unchanged
+    ///
+    /// Returns whether the file at `local` exists and has the same size and mtime of `remote`
+    fn is_unchanged(host: &Localhost, remote: &FsFile, local: &Path) -> bool {
+        match host.stat(local) {
+            Ok(FsEntry::File(local)) => same_file_stat(&local, remote),
+            _ => false,
+        }
+    }
+}
+
+/// ## SyncDirection
+///
+/// Describes which host is the source of a mirror
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncDirection {
+    Upload,   // Localhost is mirrored onto remote
+    Download, // Remote is mirrored onto localhost
+}
+
+/// ## SyncOp
+///
+/// Describes an operation to perform on the destination tree while mirroring
+#[derive(Debug)]
+pub enum SyncOp {
+    Copy(FsEntry, PathBuf), // Copy source entry to destination path; directories are just created
+    Delete(FsEntry),        // Remove extraneous entry from destination
+    Skip(FsEntry, PathBuf), // Destination is up to date
+}
+
+impl fmt::Display for SyncOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncOp::Copy(entry, dst) => write!(
+                f,
+                "copy \"{}\" to \"{}\"",
+                entry.get_abs_path().display(),
+                dst.display()
+            ),
+            SyncOp::Delete(entry) => write!(f, "delete \"{}\"", entry.get_abs_path().display()),
+            SyncOp::Skip(entry, _) => write!(f, "skip \"{}\"", entry.get_abs_path().display()),
+        }
+    }
+}
+
+/// ### dry_run_report
+///
+/// Describe the operations which would be performed in dry-run mode
+pub fn dry_run_report<T: fmt::Display>(ops: &[T]) -> Vec<String> {
+    ops.iter()
+        .map(|x| format!("[dry-run] would {}", x))
+        .collect()
+}
+
+/// ### walk_tree
+///
+/// Walk the tree at `root` depth-first, listing directories with `list_dir`.
+/// Symlinks to directories are not followed, but listed as entries, so that loops and links pointing
+/// outside of the tree don't get walked.
+/// Returns the entries in the tree (except root) associated to their path relative to `root`
+/// and the errors encountered while scanning directories
+pub fn walk_tree<F>(root: &Path, mut list_dir: F) -> (Vec<(PathBuf, FsEntry)>, Vec<String>)
+where
+    F: FnMut(&Path) -> Result<Vec<FsEntry>, String>,
+{
+    let mut tree: Vec<(PathBuf, FsEntry)> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    walk_tree_recurse(root, Path::new(""), &mut list_dir, &mut tree, &mut errors);
+    (tree, errors)
+}
+
+fn walk_tree_recurse<F>(
+    dir: &Path,
+    rel: &Path,
+    list_dir: &mut F,
+    tree: &mut Vec<(PathBuf, FsEntry)>,
+    errors: &mut Vec<String>,
+) where
+    F: FnMut(&Path) -> Result<Vec<FsEntry>, String>,
+{
+    match list_dir(dir) {
+        Ok(entries) => {
+            for entry in entries.into_iter() {
+                let entry_rel: PathBuf = rel.join(entry.get_name());
+                let subdir: Option<PathBuf> = match &entry {
+                    FsEntry::Directory(d) if d.symlink.is_none() => Some(d.abs_path.clone()),
+                    FsEntry::Directory(_) | FsEntry::File(_) => None,
+                };
+                tree.push((entry_rel.clone(), entry));
+                if let Some(subdir) = subdir {
+                    walk_tree_recurse(
+                        subdir.as_path(),
+                        entry_rel.as_path(),
+                        list_dir,
+                        tree,
+                        errors,
+                    );
+                }
+            }
+        }
+        Err(err) => errors.push(format!(
+            "Could not scan directory \"{}\": {}",
+            dir.display(),
+            err
+        )),
+    }
+}
+
+/// ### plan_mirror
+///
+/// Compare `source` and `dest` trees, as returned by `walk_tree`, and return the operations
+/// required to make `dest_root` a mirror of source.
+/// Files are copied when missing or when their size differs or the source is newer.
+/// Entries which exist only in destination are deleted only if `delete` is true.
+/// Deletions come first, then copies in depth-first order, so parents always precede their children
+pub fn plan_mirror(
+    source: &[(PathBuf, FsEntry)],
+    dest: &[(PathBuf, FsEntry)],
+    dest_root: &Path,
+    delete: bool,
+) -> Vec<SyncOp> {
+    let source_map: HashMap<&Path, &FsEntry> =
+        source.iter().map(|(p, e)| (p.as_path(), e)).collect();
+    let dest_map: HashMap<&Path, &FsEntry> = dest.iter().map(|(p, e)| (p.as_path(), e)).collect();
+    let mut ops: Vec<SyncOp> = Vec::new();
+    // Delete extraneous entries (or entries which have a different kind)
+    let mut deleted: Vec<&Path> = Vec::new();
+    if delete {
+        for (rel, entry) in dest.iter() {
+            // Removing a directory removes its content too
+            if deleted.iter().any(|x| rel.starts_with(x)) {
+                continue;
+            }
+            let keep: bool = match source_map.get(rel.as_path()) {
+                Some(src) => src.is_dir() == entry.is_dir(),
+                None => false,
+            };
+            if !keep {
+                ops.push(SyncOp::Delete(entry.clone()));
+                deleted.push(rel.as_path());
+            }
+        }
+    }
+    // Copy missing or changed entries
+    for (rel, entry) in source.iter() {
+        let dest_path: PathBuf = dest_root.join(rel);
+        let copy: bool = match dest_map.get(rel.as_path()) {
+            None => true,
+            Some(_) if deleted.iter().any(|x| rel.starts_with(x)) => true,
+            Some(FsEntry::File(dst)) => match entry {
+                FsEntry::File(src) => {
+                    src.size != dst.size
+                        || unix_secs(src.last_change_time) > unix_secs(dst.last_change_time)
+                }
+                FsEntry::Directory(_) => false,
+            },
+            Some(FsEntry::Directory(_)) => false,
+        };
+        match copy {
+            true => ops.push(SyncOp::Copy(entry.clone(), dest_path)),
+            false => ops.push(SyncOp::Skip(entry.clone(), dest_path)),
+        }
+    }
+    ops
+}
+
+/// ## CompareStatus
+///
+/// Describes how an entry of the local working directory relates to the remote one
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompareStatus {
+    LocalOnly,  // Entry exists only on localhost
+    RemoteOnly, // Entry exists only on remote
+    Differs,    // Files have different size or mtime, or entries have a different kind
+    Same,       // Entries are considered equal
+}
+
+impl fmt::Display for CompareStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            CompareStatus::LocalOnly => "local only",
+            CompareStatus::RemoteOnly => "remote only",
+            CompareStatus::Differs => "differs",
+            CompareStatus::Same => "same",
+        })
+    }
+}
+
+/// ## CompareRow
+///
+/// An entry of the merged listing of two directories
+#[derive(Debug, Eq, PartialEq)]
+pub struct CompareRow {
+    pub name: String,
+    pub is_dir: bool,
+    pub status: CompareStatus,
+}
+
+/// ### compare_entries
+///
+/// Merge `local` and `remote` entries by name and compare them. Rows are sorted by name.
+/// Files are the same if they have the same size and mtime (see `same_file_stat`);
+/// directories are the same if they exist on both sides, since their content is not scanned
+pub fn compare_entries<'a, L, R>(local: L, remote: R) -> Vec<CompareRow>
+where
+    L: IntoIterator<Item = &'a FsEntry>,
+    R: IntoIterator<Item = &'a FsEntry>,
+{
+    let mut merged: BTreeMap<&str, (Option<&FsEntry>, Option<&FsEntry>)> = BTreeMap::new();
+    for entry in local.into_iter() {
+        merged.entry(entry.get_name()).or_default().0 = Some(entry);
+    }
+    for entry in remote.into_iter() {
+        merged.entry(entry.get_name()).or_default().1 = Some(entry);
+    }
+    merged
+        .into_iter()
+        .filter_map(|(name, pair)| {
+            let (is_dir, status) = match pair {
+                (Some(local), None) => (local.is_dir(), CompareStatus::LocalOnly),
+                (None, Some(remote)) => (remote.is_dir(), CompareStatus::RemoteOnly),
+                (Some(FsEntry::File(local)), Some(FsEntry::File(remote))) => {
+                    match same_file_stat(local, remote) {
+                        true => (false, CompareStatus::Same),
+                        false => (false, CompareStatus::Differs),
+                    }
+                }
+                (Some(FsEntry::Directory(_)), Some(FsEntry::Directory(_))) => {
+                    (true, CompareStatus::Same)
+                }
+                (Some(local), Some(_)) => (local.is_dir(), CompareStatus::Differs),
+                (None, None) => return None,
+            };
+            Some(CompareRow {
+                name: name.to_string(),
+                is_dir,
+                status,
+            })
+        })
+        .collect()
+}
+
+/// ### same_file_stat
+///
+/// Returns whether the two files have the same size and modification time.
+/// Times are compared with a precision of seconds, since most protocols don't provide anything better
+pub fn same_file_stat(a: &FsFile, b: &FsFile) -> bool {
+    a.size == b.size && unix_secs(a.last_change_time) == unix_secs(b.last_change_time)
+}
+
+/// ### should_copy
+///
+/// Returns whether `src` must be copied when unchanged files are skipped:
+/// that's the case if `dest` doesn't exist or it has a different size or modification time
+pub fn should_copy(src: &FsFile, dest: Option<&FsFile>) -> bool {
+    dest.map(|dest| !same_file_stat(src, dest)).unwrap_or(true)
+}
+
+fn unix_secs(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::test_helpers::{make_file_at, make_fsentry, MockFileTransfer};
+
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_download() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).unwrap();
+        let mut client = MockFileTransfer::default()
+            .dir(
+                Path::new("/home/omar"),
+                vec![
+                    make_fsentry(PathBuf::from("/home/omar/docs"), true),
+                    make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+                ],
+            )
+            .dir(
+                Path::new("/home/omar/docs"),
+                vec![make_fsentry(PathBuf::from("/home/omar/docs/a.txt"), false)],
+            );
+        let mut plan: DownloadPlan = DownloadPlan::new(false);
+        plan.add(
+            &mut client,
+            &host,
+            &make_fsentry(PathBuf::from("/home/omar"), true),
+            tmpdir.path(),
+        );
+        assert!(plan.errors.is_empty());
+        let ops: Vec<String> = plan.ops.iter().map(|x| fmt_op(x, tmpdir.path())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                "mkdir omar",
+                "mkdir omar/docs",
+                "recv /home/omar/docs/a.txt omar/docs/a.txt",
+                "recv /home/omar/README.md omar/README.md",
+            ]
+        );
+        assert_eq!(plan.total_size(), 254);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_download_file() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).unwrap();
+        let mut client = MockFileTransfer::default();
+        let mut plan: DownloadPlan = DownloadPlan::new(true);
+        plan.add(
+            &mut client,
+            &host,
+            &make_fsentry(PathBuf::from("/tmp/a.txt"), false),
+            tmpdir.path(),
+        );
+        let ops: Vec<String> = plan.ops.iter().map(|x| fmt_op(x, tmpdir.path())).collect();
+        assert_eq!(ops, vec!["recv /tmp/a.txt a.txt"]);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_download_skip_existing() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        // Make local files; `make_file_at` writes 127 bytes, as `make_fsentry` reports
+        assert!(make_file_at(tmpdir.path(), "same.txt").is_ok());
+        assert!(make_file_at(tmpdir.path(), "older.txt").is_ok());
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).unwrap();
+        let local_mtime: SystemTime = match host.stat(&tmpdir.path().join("same.txt")).unwrap() {
+            FsEntry::File(f) => f.last_change_time,
+            FsEntry::Directory(_) => panic!("not a file"),
+        };
+        let mut same: FsFile = make_fsentry(PathBuf::from("/tmp/same.txt"), false).unwrap_file();
+        same.last_change_time = local_mtime;
+        let mut newer: FsFile = make_fsentry(PathBuf::from("/tmp/older.txt"), false).unwrap_file();
+        newer.last_change_time = local_mtime + Duration::from_secs(60);
+        let remote: Vec<FsEntry> = vec![
+            FsEntry::File(same),
+            FsEntry::File(newer),
+            make_fsentry(PathBuf::from("/tmp/new.txt"), false),
+        ];
+        let mut client = MockFileTransfer::default();
+        let mut plan: DownloadPlan = DownloadPlan::new(true);
+        for entry in remote.iter() {
+            plan.add(&mut client, &host, entry, tmpdir.path());
+        }
+        let ops: Vec<String> = plan.ops.iter().map(|x| fmt_op(x, tmpdir.path())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                "skip /tmp/same.txt same.txt",
+                "recv /tmp/older.txt older.txt",
+                "recv /tmp/new.txt new.txt",
+            ]
+        );
+        // Without skip existing, everything is downloaded
+        let mut plan: DownloadPlan = DownloadPlan::new(false);
+        for entry in remote.iter() {
+            plan.add(&mut client, &host, entry, tmpdir.path());
+        }
+        assert_eq!(
+            plan.ops
+                .iter()
+                .filter(|x| matches!(x, DownloadOp::Recv(_, _)))
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_download_errors() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).unwrap();
+        // `/home/omar/secret` can't be listed
+        let mut client = MockFileTransfer::default().dir(
+            Path::new("/home/omar"),
+            vec![
+                make_fsentry(PathBuf::from("/home/omar/secret"), true),
+                make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+            ],
+        );
+        let mut plan: DownloadPlan = DownloadPlan::new(false);
+        plan.add(
+            &mut client,
+            &host,
+            &make_fsentry(PathBuf::from("/home/omar"), true),
+            tmpdir.path(),
+        );
+        // Walk must go on after error
+        assert_eq!(plan.errors.len(), 1);
+        let ops: Vec<String> = plan.ops.iter().map(|x| fmt_op(x, tmpdir.path())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                "mkdir omar",
+                "mkdir omar/secret",
+                "recv /home/omar/README.md omar/README.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_same_file_stat() {
+        let a: FsFile = make_fsentry(PathBuf::from("/tmp/a.txt"), false).unwrap_file();
+        let mut b: FsFile = a.clone();
+        // Sub-second precision is ignored
+        b.last_change_time = a.last_change_time + Duration::from_millis(300);
+        assert_eq!(same_file_stat(&a, &b), true);
+        b.last_change_time = a.last_change_time + Duration::from_secs(2);
+        assert_eq!(same_file_stat(&a, &b), false);
+        let mut b: FsFile = a.clone();
+        b.size = 1024;
+        assert_eq!(same_file_stat(&a, &b), false);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_should_copy() {
+        let src: FsFile = make_fsentry(PathBuf::from("/tmp/a.txt"), false).unwrap_file();
+        // Missing destination
+        assert_eq!(should_copy(&src, None), true);
+        // Identical destination
+        let dest: FsFile = src.clone();
+        assert_eq!(should_copy(&src, Some(&dest)), false);
+        // Newer source
+        let mut newer: FsFile = src.clone();
+        newer.last_change_time = src.last_change_time + Duration::from_secs(60);
+        assert_eq!(should_copy(&newer, Some(&dest)), true);
+        // Larger source
+        let mut larger: FsFile = src.clone();
+        larger.size = src.size + 1024;
+        assert_eq!(should_copy(&larger, Some(&dest)), true);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_walk_tree() {
+        let mut client = MockFileTransfer::default()
+            .dir(
+                Path::new("/home/omar"),
+                vec![
+                    make_fsentry(PathBuf::from("/home/omar/docs"), true),
+                    make_fsentry(PathBuf::from("/home/omar/secret"), true),
+                    make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+                ],
+            )
+            .dir(
+                Path::new("/home/omar/docs"),
+                vec![make_fsentry(PathBuf::from("/home/omar/docs/a.txt"), false)],
+            );
+        let (tree, errors) = walk_tree(Path::new("/home/omar"), |p| {
+            client.list_dir(p).map_err(|e| e.to_string())
+        });
+        assert_eq!(errors.len(), 1);
+        let paths: Vec<PathBuf> = tree.into_iter().map(|(p, _)| p).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("docs"),
+                PathBuf::from("docs/a.txt"),
+                PathBuf::from("secret"),
+                PathBuf::from("README.md"),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_ui_activities_filetransfer_lib_sync_walk_tree_symlink_loop() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        assert!(std::fs::create_dir(tmpdir.path().join("docs")).is_ok());
+        assert!(make_file_at(tmpdir.path().join("docs").as_path(), "a.txt").is_ok());
+        // Link pointing to one of its ancestors
+        assert!(std::os::unix::fs::symlink(tmpdir.path(), tmpdir.path().join("docs/loop")).is_ok());
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).ok().unwrap();
+        let (tree, errors) = walk_tree(tmpdir.path(), |p| {
+            host.scan_dir(p).map_err(|e| e.to_string())
+        });
+        assert!(errors.is_empty());
+        let mut entries: Vec<(PathBuf, bool)> =
+            tree.into_iter().map(|(p, e)| (p, e.is_symlink())).collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                (PathBuf::from("docs"), false),
+                (PathBuf::from("docs/a.txt"), false),
+                (PathBuf::from("docs/loop"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_mirror() {
+        let now: SystemTime = SystemTime::UNIX_EPOCH + Duration::from_secs(1627000000);
+        let source: Vec<(PathBuf, FsEntry)> = vec![
+            tree_entry("/src", "docs", true, 0, now),
+            tree_entry("/src", "docs/added.txt", false, 64, now),
+            tree_entry("/src", "docs/same.txt", false, 64, now),
+            tree_entry(
+                "/src",
+                "docs/newer.txt",
+                false,
+                64,
+                now + Duration::from_secs(60),
+            ),
+            tree_entry("/src", "docs/resized.txt", false, 128, now),
+            tree_entry("/src", "docs/older.txt", false, 64, now),
+        ];
+        let dest: Vec<(PathBuf, FsEntry)> = vec![
+            tree_entry("/dst", "docs", true, 0, now),
+            tree_entry("/dst", "docs/same.txt", false, 64, now),
+            tree_entry("/dst", "docs/newer.txt", false, 64, now),
+            tree_entry("/dst", "docs/resized.txt", false, 64, now),
+            tree_entry(
+                "/dst",
+                "docs/older.txt",
+                false,
+                64,
+                now + Duration::from_secs(60),
+            ),
+            tree_entry("/dst", "docs/extraneous.txt", false, 64, now),
+            tree_entry("/dst", "old", true, 0, now),
+            tree_entry("/dst", "old/file.txt", false, 64, now),
+        ];
+        // Without delete
+        let ops: Vec<String> = plan_mirror(&source, &dest, Path::new("/dst"), false)
+            .iter()
+            .map(fmt_sync_op)
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                "skip /src/docs /dst/docs",
+                "copy /src/docs/added.txt /dst/docs/added.txt",
+                "skip /src/docs/same.txt /dst/docs/same.txt",
+                "copy /src/docs/newer.txt /dst/docs/newer.txt",
+                "copy /src/docs/resized.txt /dst/docs/resized.txt",
+                "skip /src/docs/older.txt /dst/docs/older.txt",
+            ]
+        );
+        // With delete; `old/file.txt` is removed along with `old`
+        let ops: Vec<String> = plan_mirror(&source, &dest, Path::new("/dst"), true)
+            .iter()
+            .map(fmt_sync_op)
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                "delete /dst/docs/extraneous.txt",
+                "delete /dst/old",
+                "skip /src/docs /dst/docs",
+                "copy /src/docs/added.txt /dst/docs/added.txt",
+                "skip /src/docs/same.txt /dst/docs/same.txt",
+                "copy /src/docs/newer.txt /dst/docs/newer.txt",
+                "copy /src/docs/resized.txt /dst/docs/resized.txt",
+                "skip /src/docs/older.txt /dst/docs/older.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_plan_mirror_kind_mismatch() {
+        let now: SystemTime = SystemTime::UNIX_EPOCH;
+        let source: Vec<(PathBuf, FsEntry)> = vec![
+            tree_entry("/src", "conf", true, 0, now),
+            tree_entry("/src", "conf/a.txt", false, 64, now),
+        ];
+        let dest: Vec<(PathBuf, FsEntry)> = vec![tree_entry("/dst", "conf", false, 64, now)];
+        let ops: Vec<String> = plan_mirror(&source, &dest, Path::new("/dst"), true)
+            .iter()
+            .map(fmt_sync_op)
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                "delete /dst/conf",
+                "copy /src/conf /dst/conf",
+                "copy /src/conf/a.txt /dst/conf/a.txt",
+            ]
+        );
+        // Empty destination
+        let ops: Vec<String> = plan_mirror(&source, &[], Path::new("/dst"), true)
+            .iter()
+            .map(fmt_sync_op)
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                "copy /src/conf /dst/conf",
+                "copy /src/conf/a.txt /dst/conf/a.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_dry_run() {
+        let tmpdir: TempDir = TempDir::new().unwrap();
+        let host: Localhost = Localhost::new(tmpdir.path().to_path_buf()).unwrap();
+        let mut client = MockFileTransfer::default()
+            .dir(
+                Path::new("/home/omar"),
+                vec![
+                    make_fsentry(PathBuf::from("/home/omar/docs"), true),
+                    make_fsentry(PathBuf::from("/home/omar/README.md"), false),
+                ],
+            )
+            .dir(Path::new("/home/omar/docs"), vec![]);
+        // Plan download
+        let mut plan: DownloadPlan = DownloadPlan::new(false);
+        plan.add(
+            &mut client,
+            &host,
+            &make_fsentry(PathBuf::from("/home/omar"), true),
+            Path::new("/tmp"),
+        );
+        assert_eq!(
+            dry_run_report(&plan.ops),
+            vec![
+                "[dry-run] would create directory \"/tmp/omar\"",
+                "[dry-run] would create directory \"/tmp/omar/docs\"",
+                "[dry-run] would download \"/home/omar/README.md\" to \"/tmp/omar/README.md\"",
+            ]
+        );
+        // Plan mirror
+        let (remote_tree, _) = walk_tree(Path::new("/home/omar"), |p| {
+            client.list_dir(p).map_err(|e| e.to_string())
+        });
+        let local_tree: Vec<(PathBuf, FsEntry)> = vec![(
+            PathBuf::from("old.txt"),
+            make_fsentry(PathBuf::from("/tmp/old.txt"), false),
+        )];
+        let ops: Vec<SyncOp> = plan_mirror(&remote_tree, &local_tree, Path::new("/tmp"), true);
+        assert_eq!(
+            dry_run_report(&ops),
+            vec![
+                "[dry-run] would delete \"/tmp/old.txt\"",
+                "[dry-run] would copy \"/home/omar/docs\" to \"/tmp/docs\"",
+                "[dry-run] would copy \"/home/omar/README.md\" to \"/tmp/README.md\"",
+            ]
+        );
+        // Planning mustn't have called anything which alters the remote
+        assert_eq!(client.calls.is_empty(), false);
+        assert!(client.calls.iter().all(|x| x.as_str() == "list_dir"));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_compare_entries_overlapping() {
+        let t_now: SystemTime = SystemTime::now();
+        let t_old: SystemTime = t_now - Duration::from_secs(3600);
+        let local: Vec<FsEntry> = vec![
+            tree_entry("/home/omar", "README.md", false, 64, t_now).1,
+            tree_entry("/home/omar", "main.rs", false, 128, t_now).1,
+            tree_entry("/home/omar", "docs", true, 0, t_now).1,
+            tree_entry("/home/omar", "build", false, 8, t_now).1,
+            tree_entry("/home/omar", "local.txt", false, 8, t_now).1,
+            tree_entry("/home/omar", "touched.txt", false, 8, t_now).1,
+        ];
+        let remote: Vec<FsEntry> = vec![
+            tree_entry("/srv", "README.md", false, 64, t_now).1,
+            tree_entry("/srv", "main.rs", false, 256, t_now).1,
+            tree_entry("/srv", "docs", true, 0, t_old).1,
+            tree_entry("/srv", "build", true, 0, t_now).1,
+            tree_entry("/srv", "remote.txt", false, 8, t_now).1,
+            tree_entry("/srv", "touched.txt", false, 8, t_old).1,
+        ];
+        let rows: Vec<(String, bool, CompareStatus)> = compare_entries(&local, &remote)
+            .into_iter()
+            .map(|x| (x.name, x.is_dir, x.status))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (String::from("README.md"), false, CompareStatus::Same),
+                (String::from("build"), false, CompareStatus::Differs),
+                (String::from("docs"), true, CompareStatus::Same),
+                (String::from("local.txt"), false, CompareStatus::LocalOnly),
+                (String::from("main.rs"), false, CompareStatus::Differs),
+                (String::from("remote.txt"), false, CompareStatus::RemoteOnly),
+                (String::from("touched.txt"), false, CompareStatus::Differs),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_sync_compare_entries_disjoint() {
+        let t_now: SystemTime = SystemTime::now();
+        let local: Vec<FsEntry> = vec![
+            tree_entry("/home/omar", "a.txt", false, 8, t_now).1,
+            tree_entry("/home/omar", "src", true, 0, t_now).1,
+        ];
+        let remote: Vec<FsEntry> = vec![tree_entry("/srv", "b.txt", false, 8, t_now).1];
+        let rows: Vec<CompareRow> = compare_entries(&local, &remote);
+        assert_eq!(
+            rows,
+            vec![
+                CompareRow {
+                    name: String::from("a.txt"),
+                    is_dir: false,
+                    status: CompareStatus::LocalOnly
+                },
+                CompareRow {
+                    name: String::from("b.txt"),
+                    is_dir: false,
+                    status: CompareStatus::RemoteOnly
+                },
+                CompareRow {
+                    name: String::from("src"),
+                    is_dir: true,
+                    status: CompareStatus::LocalOnly
+                },
+            ]
+        );
+        // Empty listings
+        assert!(compare_entries(&[], &[]).is_empty());
+        assert_eq!(compare_entries(&[], &remote).len(), 1);
+        assert_eq!(
+            format!("{:<11}|", CompareStatus::RemoteOnly),
+            "remote only|"
+        );
+        assert_eq!(CompareStatus::Same.to_string(), "same");
+    }
+
+    fn tree_entry(
+        root: &str,
+        rel: &str,
+        is_dir: bool,
+        size: usize,
+        mtime: SystemTime,
+    ) -> (PathBuf, FsEntry) {
+        let mut entry: FsEntry = make_fsentry(Path::new(root).join(rel), is_dir);
+        match &mut entry {
+            FsEntry::Directory(d) => d.last_change_time = mtime,
+            FsEntry::File(f) => {
+                f.size = size;
+                f.last_change_time = mtime;
+            }
+        }
+        (PathBuf::from(rel), entry)
+    }
+
+    fn fmt_sync_op(op: &SyncOp) -> String {
+        match op {
+            SyncOp::Copy(e, p) => format!("copy {} {}", e.get_abs_path().display(), p.display()),
+            SyncOp::Delete(e) => format!("delete {}", e.get_abs_path().display()),
+            SyncOp::Skip(e, p) => format!("skip {} {}", e.get_abs_path().display(), p.display()),
+        }
+    }
+
+    fn fmt_op(op: &DownloadOp, base: &Path) -> String {
+        let rel = |p: &PathBuf| p.strip_prefix(base).unwrap().display().to_string();
+        match op {
+            DownloadOp::Mkdir(p) => format!("mkdir {}", rel(p)),
+            DownloadOp::Recv(f, p) => format!("recv {} {}", f.abs_path.display(), rel(p)),
+            DownloadOp::Skip(f, p) => format!("skip {} {}", f.abs_path.display(), rel(p)),
+        }
+    }
+}
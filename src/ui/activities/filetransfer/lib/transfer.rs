@@ -25,17 +25,25 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use bytesize::ByteSize;
+use super::replace::ReplaceStates;
+use crate::utils::fmt::{fmt_bytes, fmt_millis, SizeUnit};
 use std::fmt;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// ### TransferStates
 ///
 /// TransferStates contains the states related to the transfer process
 pub struct TransferStates {
     aborted: bool,               // Describes whether the transfer process has been aborted
+    paused: Option<Instant>,     // Describes since when the transfer process has been paused
     pub full: ProgressStates,    // full transfer states
     pub partial: ProgressStates, // Partial transfer states
+    files_total: usize,          // Amount of files to transfer
+    files_done: usize,           // Amount of files processed so far
+    bytes_done: usize,           // Size of the files processed so far
+    pub replace: ReplaceStates,  // Answers given to the replace prompt
+    pub report: TransferReport,  // Outcome of each file processed
 }
 
 /// ### ProgressStates
@@ -45,6 +53,20 @@ pub struct ProgressStates {
     started: Instant,
     total: usize,
     written: usize,
+    size_unit: SizeUnit,
+}
+
+/// ### TransferReport
+///
+/// TransferReport collects the outcome of each file processed by a transfer,
+/// in order to report a summary once the transfer is complete
+pub struct TransferReport {
+    started: Instant,
+    succeeded: usize,
+    skipped: usize,
+    failed: Vec<(PathBuf, String)>,
+    bytes: usize,
+    size_unit: SizeUnit,
 }
 
 impl Default for TransferStates {
@@ -60,16 +82,71 @@ impl TransferStates {
     pub fn new() -> TransferStates {
         TransferStates {
             aborted: false,
+            paused: None,
             full: ProgressStates::default(),
             partial: ProgressStates::default(),
+            files_total: 0,
+            files_done: 0,
+            bytes_done: 0,
+            replace: ReplaceStates::default(),
+            report: TransferReport::default(),
         }
     }
 
+    /// ### size_unit
+    ///
+    /// Set the unit used to display the transferred sizes
+    pub fn size_unit(mut self, unit: SizeUnit) -> Self {
+        self.full.size_unit = unit;
+        self.partial.size_unit = unit;
+        self.report.size_unit = unit;
+        self
+    }
+
+    /// ### init
+    ///
+    /// Initialize the aggregate progress for a transfer of `files` files, for a total of `total_size` bytes
+    pub fn init(&mut self, total_size: usize, files: usize) {
+        self.full.init(total_size);
+        self.files_total = files;
+        self.files_done = 0;
+        self.bytes_done = 0;
+        self.report.init();
+    }
+
+    /// ### file_done
+    ///
+    /// Mark a file of `size` bytes as processed.
+    /// The aggregate progress is moved to the end of the file, even if the file hasn't been transferred entirely
+    /// (e.g. because of an error), so that the aggregate progress stays consistent with the total size
+    pub fn file_done(&mut self, size: usize) {
+        self.files_done += 1;
+        self.bytes_done += size;
+        self.full.written = self.bytes_done;
+    }
+
+    /// ### file_retry
+    ///
+    /// Discard the progress made by a failed attempt on the current file from the aggregate progress,
+    /// before the file is transferred again
+    pub fn file_retry(&mut self) {
+        self.full.written = self.bytes_done;
+    }
+
+    /// ### fmt_files
+    ///
+    /// Format the amount of files processed out of the total (e.g. `3 / 10 files`)
+    pub fn fmt_files(&self) -> String {
+        format!("{} / {} files", self.files_done, self.files_total)
+    }
+
     /// ### reset
     ///
     /// Re-intiialize transfer states
     pub fn reset(&mut self) {
         self.aborted = false;
+        self.paused = None;
+        self.replace.reset();
     }
 
     /// ### abort
@@ -77,6 +154,45 @@ impl TransferStates {
     /// Set aborted to true
     pub fn abort(&mut self) {
         self.aborted = true;
+        self.paused = None;
+    }
+
+    /// ### toggle_pause
+    ///
+    /// Pause transfer if running, resume it otherwise
+    pub fn toggle_pause(&mut self) {
+        match self.paused {
+            Some(_) => self.resume(),
+            None => self.pause(),
+        }
+    }
+
+    /// ### pause
+    ///
+    /// Pause transfer
+    pub fn pause(&mut self) {
+        if self.paused.is_none() && !self.aborted {
+            self.paused = Some(Instant::now());
+        }
+    }
+
+    /// ### resume
+    ///
+    /// Resume transfer. The time spent paused is not accounted in transfer rates and ETA
+    pub fn resume(&mut self) {
+        if let Some(since) = self.paused.take() {
+            let elapsed: Duration = since.elapsed();
+            self.full.started += elapsed;
+            self.partial.started += elapsed;
+            self.report.started += elapsed;
+        }
+    }
+
+    /// ### paused
+    ///
+    /// Returns whether transfer is paused
+    pub fn paused(&self) -> bool {
+        self.paused.is_some()
     }
 
     /// ### aborted
@@ -93,6 +209,7 @@ impl Default for ProgressStates {
             started: Instant::now(),
             written: 0,
             total: 0,
+            size_unit: SizeUnit::default(),
         }
     }
 }
@@ -113,7 +230,7 @@ impl fmt::Display for ProgressStates {
             "{:.2}% - ETA {} ({}/s)",
             self.calc_progress_percentage(),
             eta,
-            ByteSize(self.calc_bytes_per_second())
+            fmt_bytes(self.calc_bytes_per_second(), self.size_unit)
         )
     }
 }
@@ -154,6 +271,17 @@ impl ProgressStates {
         self.started
     }
 
+    /// ### fmt_bytes
+    ///
+    /// Format the amount of bytes written out of the total (e.g. `1.2 GB / 4.0 GB`)
+    pub fn fmt_bytes(&self) -> String {
+        format!(
+            "{} / {}",
+            fmt_bytes(self.written as u64, self.size_unit),
+            fmt_bytes(self.total as u64, self.size_unit)
+        )
+    }
+
     /// ### calc_progress_percentage
     ///
     /// Calculate the current transfer progress as percentage
@@ -178,6 +306,17 @@ impl ProgressStates {
         }
     }
 
+    /// ### calc_rate
+    ///
+    /// Returns the transfer rate in bytes per second, or `None` if the transfer started less than a second ago,
+    /// since the rate can't be calculated yet
+    pub fn calc_rate(&self) -> Option<u64> {
+        match self.started.elapsed().as_secs() {
+            0 => None,
+            _ => Some(self.calc_bytes_per_second()),
+        }
+    }
+
     /// ### calc_eta
     ///
     /// Calculate ETA for current transfer as seconds
@@ -191,6 +330,137 @@ impl ProgressStates {
     }
 }
 
+impl Default for TransferReport {
+    fn default() -> Self {
+        TransferReport {
+            started: Instant::now(),
+            succeeded: 0,
+            skipped: 0,
+            failed: Vec::new(),
+            bytes: 0,
+            size_unit: SizeUnit::default(),
+        }
+    }
+}
+
+impl fmt::Display for TransferReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transferred {} files ({} succeeded, {} failed",
+            self.total(),
+            self.succeeded(),
+            self.failed().len()
+        )?;
+        if self.skipped > 0 {
+            write!(f, ", {} skipped", self.skipped)?;
+        }
+        write!(
+            f,
+            "): {} in {} seconds ({}/s)",
+            fmt_bytes(self.bytes as u64, self.size_unit),
+            fmt_millis(self.elapsed()),
+            fmt_bytes(self.calc_bytes_per_second(), self.size_unit)
+        )
+    }
+}
+
+impl TransferReport {
+    /// ### init
+    ///
+    /// Clear the report and restart the elapsed time
+    pub fn init(&mut self) {
+        self.started = Instant::now();
+        self.succeeded = 0;
+        self.skipped = 0;
+        self.failed.clear();
+        self.bytes = 0;
+    }
+
+    /// ### success
+    ///
+    /// Account a file of `size` bytes which has been transferred successfully
+    pub fn success(&mut self, size: usize) {
+        self.succeeded += 1;
+        self.bytes += size;
+    }
+
+    /// ### skip
+    ///
+    /// Account a file which hasn't been transferred, since the user chose not to replace it
+    pub fn skip(&mut self) {
+        self.skipped += 1;
+    }
+
+    /// ### failure
+    ///
+    /// Account a file which couldn't be transferred because of `err`
+    pub fn failure(&mut self, path: &Path, err: String) {
+        self.failed.push((path.to_path_buf(), err));
+    }
+
+    /// ### total
+    ///
+    /// Returns the amount of files processed
+    pub fn total(&self) -> usize {
+        self.succeeded + self.skipped + self.failed.len()
+    }
+
+    /// ### succeeded
+    ///
+    /// Returns the amount of files transferred successfully
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    /// ### failed
+    ///
+    /// Returns the files which couldn't be transferred, along with the reason
+    pub fn failed(&self) -> &[(PathBuf, String)] {
+        self.failed.as_slice()
+    }
+
+    /// ### elapsed
+    ///
+    /// Returns the time elapsed since the transfer started, without the time spent paused
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// ### calc_bytes_per_second
+    ///
+    /// Calculate the average transfer rate
+    pub fn calc_bytes_per_second(&self) -> u64 {
+        match self.elapsed().as_millis() {
+            0 => self.bytes as u64,
+            millis => (self.bytes as u128 * 1000 / millis) as u64,
+        }
+    }
+}
+
+/// Terminal bell emitted once a transfer has completed successfully
+pub const BELL_SUCCESS: &str = "\x07";
+/// Terminal bells emitted once a transfer has failed
+pub const BELL_FAILURE: &str = "\x07\x07\x07";
+
+/// ### transfer_bell
+///
+/// Get the terminal bells to emit once a transfer which took `elapsed` has completed:
+/// one bell on success, three bells on failure.
+/// Returns `None` if bells are disabled or the transfer took less than `threshold`
+pub fn transfer_bell(
+    enabled: bool,
+    threshold: Duration,
+    elapsed: Duration,
+    success: bool,
+) -> Option<&'static str> {
+    match (enabled && elapsed >= threshold, success) {
+        (false, _) => None,
+        (true, true) => Some(BELL_SUCCESS),
+        (true, false) => Some(BELL_FAILURE),
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -214,6 +484,8 @@ mod test {
         assert_eq!(states.calc_progress_percentage(), 0.0);
         assert_eq!(states.calc_progress(), 0.0);
         assert_eq!(states.to_string().as_str(), "0.00% - ETA --:-- (0 B/s)");
+        assert_eq!(states.fmt_bytes().as_str(), "0 B / 1.0 KB");
+        assert_eq!(states.calc_rate(), None);
         // Wait 4 second (virtually)
         states.started = states.started.checked_sub(Duration::from_secs(4)).unwrap();
         // Update state
@@ -221,6 +493,7 @@ mod test {
         assert_eq!(states.total, 1024);
         assert_eq!(states.written, 256);
         assert_eq!(states.calc_bytes_per_second(), 64); // 256 bytes in 4 seconds
+        assert_eq!(states.calc_rate(), Some(64));
         assert_eq!(states.calc_eta(), 12); // 16 total sub 4
         assert_eq!(states.calc_progress_percentage(), 25.0);
         assert_eq!(states.calc_progress(), 0.25);
@@ -256,4 +529,174 @@ mod test {
         states.reset();
         assert_eq!(states.aborted(), false);
     }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_transfer_pause() {
+        let mut states: TransferStates = TransferStates::default();
+        assert_eq!(states.paused(), false);
+        states.init(2048, 1);
+        states.partial.init(2048);
+        states.partial.update_progress(1024);
+        states.full.update_progress(1024);
+        // Pause
+        states.pause();
+        assert_eq!(states.paused(), true);
+        // Pausing twice doesn't change anything
+        states.pause();
+        assert_eq!(states.paused(), true);
+        // Pretend pause lasted 3 seconds
+        states.paused = states
+            .paused
+            .map(|x| x.checked_sub(Duration::from_secs(3)).unwrap());
+        let started: Instant = states.partial.started();
+        states.resume();
+        assert_eq!(states.paused(), false);
+        // Progress is resumed from the same offset
+        assert_eq!(states.partial.written, 1024);
+        assert_eq!(states.full.written, 1024);
+        // Time spent paused is not accounted
+        assert!(states.partial.started() >= started + Duration::from_secs(3));
+        // Resuming while running doesn't change anything
+        states.resume();
+        assert_eq!(states.paused(), false);
+        // Toggle
+        states.toggle_pause();
+        assert_eq!(states.paused(), true);
+        states.toggle_pause();
+        assert_eq!(states.paused(), false);
+        // Abort while paused
+        states.toggle_pause();
+        states.abort();
+        assert_eq!(states.paused(), false);
+        assert_eq!(states.aborted(), true);
+        // Can't pause an aborted transfer
+        states.pause();
+        assert_eq!(states.paused(), false);
+        // Reset
+        states.reset();
+        assert_eq!(states.aborted(), false);
+        assert_eq!(states.paused(), false);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_transfer_aggregate() {
+        let mut states: TransferStates = TransferStates::default();
+        // Three files: 1024, 2048 and 1024 bytes
+        states.init(4096, 3);
+        assert_eq!(states.full.calc_progress(), 0.0);
+        assert_eq!(states.fmt_files().as_str(), "0 / 3 files");
+        // First file completes
+        states.partial.init(1024);
+        states.partial.update_progress(1024);
+        states.full.update_progress(1024);
+        states.file_done(1024);
+        assert_eq!(states.full.written, 1024);
+        assert_eq!(states.full.calc_progress(), 0.25);
+        assert_eq!(states.fmt_files().as_str(), "1 / 3 files");
+        // Second file is resumed from 1024 and completes
+        states.partial.init(1024);
+        states.full.update_progress(1024);
+        states.partial.update_progress(1024);
+        states.full.update_progress(1024);
+        states.file_done(2048);
+        assert_eq!(states.full.written, 3072);
+        assert_eq!(states.full.calc_progress(), 0.75);
+        assert_eq!(states.fmt_files().as_str(), "2 / 3 files");
+        assert_eq!(states.full.fmt_bytes().as_str(), "3.1 KB / 4.1 KB");
+        let mut binary: TransferStates = TransferStates::default().size_unit(SizeUnit::Binary);
+        binary.init(4096, 1);
+        binary.full.update_progress(2048);
+        assert_eq!(binary.full.fmt_bytes().as_str(), "2 KiB / 4 KiB");
+        // Third file fails half way; aggregate still reaches the end
+        states.partial.init(1024);
+        states.partial.update_progress(512);
+        states.full.update_progress(512);
+        assert_eq!(states.full.written, 3584);
+        states.file_done(1024);
+        assert_eq!(states.full.written, 4096);
+        assert_eq!(states.full.calc_progress(), 1.0);
+        assert_eq!(states.fmt_files().as_str(), "3 / 3 files");
+        // A failed attempt is discarded before retrying
+        states.init(2048, 2);
+        states.full.update_progress(1024);
+        states.file_done(1024);
+        states.full.update_progress(512);
+        states.file_retry();
+        assert_eq!(states.full.written, 1024);
+        assert_eq!(states.fmt_files().as_str(), "1 / 2 files");
+        // Re-init
+        states.init(10, 1);
+        assert_eq!(states.full.written, 0);
+        assert_eq!(states.fmt_files().as_str(), "0 / 1 files");
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_transfer_report() {
+        let mut states: TransferStates = TransferStates::default();
+        states.init(4096, 4);
+        assert_eq!(states.report.total(), 0);
+        // Mixed results
+        states.report.success(1024);
+        states
+            .report
+            .failure(Path::new("/tmp/b.txt"), String::from("permission denied"));
+        states.report.success(2048);
+        states.report.skip();
+        assert_eq!(states.report.total(), 4);
+        assert_eq!(states.report.succeeded(), 2);
+        assert_eq!(states.report.bytes, 3072);
+        assert_eq!(
+            states.report.failed(),
+            &[(
+                PathBuf::from("/tmp/b.txt"),
+                String::from("permission denied")
+            )]
+        );
+        // Pretend transfer lasted 2 seconds
+        states.report.started = Instant::now().checked_sub(Duration::from_secs(2)).unwrap();
+        let rate: u64 = states.report.calc_bytes_per_second();
+        assert!(rate > 1500 && rate <= 1536);
+        assert!(states
+            .report
+            .to_string()
+            .starts_with("Transferred 4 files (2 succeeded, 1 failed, 1 skipped): 3.1 KB in 2."));
+        // Init clears the report
+        states.init(1024, 1);
+        assert_eq!(states.report.total(), 0);
+        assert_eq!(states.report.bytes, 0);
+        assert!(states.report.failed().is_empty());
+        states.report.success(1024);
+        assert!(states
+            .report
+            .to_string()
+            .starts_with("Transferred 1 files (1 succeeded, 0 failed): 1.0 KB in "));
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_transfer_bell() {
+        let threshold: Duration = Duration::from_secs(10);
+        let long: Duration = Duration::from_secs(60);
+        // Enabled
+        assert_eq!(transfer_bell(true, threshold, long, true), Some("\x07"));
+        assert_eq!(
+            transfer_bell(true, threshold, long, false),
+            Some("\x07\x07\x07")
+        );
+        assert_eq!(
+            transfer_bell(true, threshold, threshold, true),
+            Some("\x07")
+        );
+        // Shorter than threshold
+        let short: Duration = Duration::from_secs(9);
+        assert_eq!(transfer_bell(true, threshold, short, true), None);
+        assert_eq!(transfer_bell(true, threshold, short, false), None);
+        // Disabled
+        assert_eq!(transfer_bell(false, threshold, long, true), None);
+        assert_eq!(transfer_bell(false, threshold, long, false), None);
+        // No threshold
+        assert_eq!(
+            transfer_bell(true, Duration::ZERO, Duration::ZERO, true),
+            Some("\x07")
+        );
+    }
 }
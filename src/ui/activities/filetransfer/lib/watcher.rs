@@ -0,0 +1,217 @@
+//! ## Watcher
+//!
+//! `watcher` is the module which watches the local working directory, to refresh it when it changes
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use notify::{raw_watcher, RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+/// Time without changes to wait before reloading the directory
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+/// Maximum time to wait before reloading the directory, if it keeps changing
+const DEBOUNCE_MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// ### Debouncer
+///
+/// Debouncer coalesces bursts of change events: it becomes ready once no event has been received for `delay`,
+/// or once `max_delay` has elapsed since the first event of the burst, if events keep coming
+pub struct Debouncer {
+    delay: Duration,
+    max_delay: Duration,
+    first: Option<Instant>, // First event received since the last time the debouncer was ready
+    last: Option<Instant>,  // Last event received
+}
+
+impl Debouncer {
+    /// ### new
+    ///
+    /// Instantiates a new `Debouncer`
+    pub fn new(delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            delay,
+            max_delay,
+            first: None,
+            last: None,
+        }
+    }
+
+    /// ### event
+    ///
+    /// Record an event received at `now`
+    pub fn event(&mut self, now: Instant) {
+        if self.first.is_none() {
+            self.first = Some(now);
+        }
+        self.last = Some(now);
+    }
+
+    /// ### ready
+    ///
+    /// Returns whether the events received so far must be handled at `now`.
+    /// Once ready, the pending events are discarded
+    pub fn ready(&mut self, now: Instant) -> bool {
+        let ready: bool = match (self.first, self.last) {
+            (Some(first), Some(last)) => {
+                now.saturating_duration_since(last) >= self.delay
+                    || now.saturating_duration_since(first) >= self.max_delay
+            }
+            _ => false,
+        };
+        if ready {
+            self.first = None;
+            self.last = None;
+        }
+        ready
+    }
+}
+
+/// ### LocalWatcher
+///
+/// LocalWatcher watches a local directory (not recursively) and reports debounced changes
+pub struct LocalWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<RawEvent>,
+    path: PathBuf,
+    debouncer: Debouncer,
+}
+
+impl LocalWatcher {
+    /// ### new
+    ///
+    /// Start watching `path`
+    pub fn new(path: &Path) -> Result<Self, String> {
+        let (tx, events) = channel();
+        let mut watcher: RecommendedWatcher =
+            raw_watcher(tx).map_err(|e| format!("Could not start watcher: {}", e))?;
+        watcher
+            .watch(path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Could not watch \"{}\": {}", path.display(), e))?;
+        Ok(Self {
+            watcher,
+            events,
+            path: path.to_path_buf(),
+            debouncer: Debouncer::new(DEBOUNCE_DELAY, DEBOUNCE_MAX_DELAY),
+        })
+    }
+
+    /// ### path
+    ///
+    /// Returns the directory being watched
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// ### watch
+    ///
+    /// Watch `path` instead of the current directory; pending changes are discarded
+    pub fn watch(&mut self, path: &Path) -> Result<(), String> {
+        let _ = self.watcher.unwatch(self.path.as_path());
+        // Discard events of the previous directory
+        while self.events.try_recv().is_ok() {}
+        self.debouncer = Debouncer::new(DEBOUNCE_DELAY, DEBOUNCE_MAX_DELAY);
+        self.path = path.to_path_buf();
+        self.watcher
+            .watch(path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Could not watch \"{}\": {}", path.display(), e))
+    }
+
+    /// ### poll
+    ///
+    /// Collect the events received so far; returns whether the directory must be reloaded
+    pub fn poll(&mut self) -> bool {
+        let now: Instant = Instant::now();
+        while self.events.try_recv().is_ok() {
+            self.debouncer.event(now);
+        }
+        self.debouncer.ready(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn debouncer() -> (Debouncer, Instant) {
+        (
+            Debouncer::new(Duration::from_millis(500), Duration::from_secs(3)),
+            Instant::now(),
+        )
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_watcher_debouncer() {
+        let (mut debouncer, t0) = debouncer();
+        // No events
+        assert_eq!(debouncer.first.is_some(), false);
+        assert_eq!(debouncer.ready(t0 + Duration::from_secs(10)), false);
+        // Burst of events
+        debouncer.event(t0);
+        debouncer.event(t0 + Duration::from_millis(100));
+        debouncer.event(t0 + Duration::from_millis(200));
+        assert_eq!(debouncer.first.is_some(), true);
+        // Not quiet for long enough
+        assert_eq!(debouncer.ready(t0 + Duration::from_millis(600)), false);
+        // Quiet for delay since last event
+        assert_eq!(debouncer.ready(t0 + Duration::from_millis(700)), true);
+        // Consumed
+        assert_eq!(debouncer.first.is_some(), false);
+        assert_eq!(debouncer.ready(t0 + Duration::from_secs(1)), false);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_watcher_debouncer_max_delay() {
+        let (mut debouncer, t0) = debouncer();
+        // Events keep coming every 400ms
+        let mut now: Instant = t0;
+        let mut fired: Option<Instant> = None;
+        while now < t0 + Duration::from_secs(5) {
+            debouncer.event(now);
+            if debouncer.ready(now) {
+                fired = Some(now);
+                break;
+            }
+            now += Duration::from_millis(400);
+        }
+        // Fired once max delay has elapsed since the first event
+        assert_eq!(fired, Some(t0 + Duration::from_millis(3200)));
+        // A new burst starts from scratch
+        debouncer.event(now + Duration::from_millis(100));
+        assert_eq!(debouncer.ready(now + Duration::from_millis(200)), false);
+        assert_eq!(debouncer.ready(now + Duration::from_millis(600)), true);
+    }
+
+    #[test]
+    fn test_ui_activities_filetransfer_lib_watcher_time_going_backwards() {
+        let (mut debouncer, t0) = debouncer();
+        debouncer.event(t0 + Duration::from_secs(1));
+        // Never panics
+        assert_eq!(debouncer.ready(t0), false);
+    }
+}
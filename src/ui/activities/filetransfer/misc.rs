@@ -22,12 +22,25 @@
  * SOFTWARE.
  */
 // Locals
+use super::lib::errors::error_message;
+use super::lib::size_gate::size_gate;
+use super::lib::sync::dry_run_report;
+use super::lib::watcher::LocalWatcher;
 use super::{ConfigClient, FileTransferActivity, LogLevel, LogRecord};
+use crate::filetransfer::read_only::{check_read_only, Operation};
+use crate::filetransfer::{FileTransferError, FileTransferParams, TransferOpts};
+use crate::system::bookmarks_client::BookmarksClient;
 use crate::system::environment;
+use crate::system::known_hosts::{self, HostKeyVerifier};
+use crate::system::ssh_config::SshConfig;
 use crate::system::sshkey_storage::SshKeyStorage;
+use crate::utils::file::resolve_path;
+use crate::utils::fmt::fmt_bytes;
 // Ext
 use std::env;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tuirealm::Update;
 
 const LOG_CAPACITY: usize = 256;
@@ -67,11 +80,264 @@ impl FileTransferActivity {
         self.update(msg);
     }
 
+    /// ### log_and_alert_remote
+    ///
+    /// Log `msg` along with the raw remote error and display an alert which explains the error
+    /// and how to solve it, according to its category
+    pub(super) fn log_and_alert_remote(
+        &mut self,
+        level: LogLevel,
+        msg: String,
+        err: &FileTransferError,
+    ) {
+        self.mount_error(format!("{}: {}", msg, error_message(err.kind())).as_str());
+        self.log(level, format!("{}: {}", msg, err));
+        // Update log
+        let msg = self.update_logbox();
+        self.update(msg);
+    }
+
+    /// ### log_errors_and_alert
+    ///
+    /// Log each of the errors occurred during `what` and display an alert which summarizes them
+    pub(super) fn log_errors_and_alert(&mut self, what: &str, errors: Vec<String>) {
+        let count: usize = errors.len();
+        for err in errors.into_iter() {
+            self.log(LogLevel::Error, err);
+        }
+        self.log_and_alert(
+            LogLevel::Error,
+            format!(
+                "{} completed with {} errors; see log for details",
+                what, count
+            ),
+        );
+    }
+
+    /// ### dry_run
+    ///
+    /// Returns whether dry-run mode is enabled
+    pub(super) fn dry_run(&self) -> bool {
+        self.context().transfer_opts().dry_run
+    }
+
+    /// ### toggle_dry_run
+    ///
+    /// Enable or disable dry-run mode
+    pub(super) fn toggle_dry_run(&mut self) {
+        let dry_run: bool = !self.dry_run();
+        self.context_mut().transfer_opts_mut().dry_run = dry_run;
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Dry-run mode {}",
+                match dry_run {
+                    true => "enabled",
+                    false => "disabled",
+                }
+            ),
+        );
+    }
+
+    /// ### skip_unchanged
+    ///
+    /// Returns whether the files which are unchanged on the destination are skipped by transfers;
+    /// enabled at startup by `--skip-existing`
+    pub(super) fn skip_unchanged(&self) -> bool {
+        self.context().transfer_opts().skip_existing
+    }
+
+    /// ### start_local_watcher
+    ///
+    /// Start watching the local working directory, to reload it when it changes on disk
+    pub(super) fn start_local_watcher(&mut self) {
+        let wrkdir: PathBuf = self.local().wrkdir.clone();
+        match LocalWatcher::new(wrkdir.as_path()) {
+            Ok(watcher) => self.local_watcher = Some(watcher),
+            Err(err) => self.log(
+                LogLevel::Warn,
+                format!("Local directory won't be refreshed automatically: {}", err),
+            ),
+        }
+    }
+
+    /// ### poll_local_watcher
+    ///
+    /// Reload the local explorer if the working directory has changed on disk.
+    /// Returns whether the explorer has been reloaded
+    pub(super) fn poll_local_watcher(&mut self) -> bool {
+        let wrkdir: PathBuf = self.local().wrkdir.clone();
+        let watcher: &mut LocalWatcher = match self.local_watcher.as_mut() {
+            Some(watcher) => watcher,
+            None => return false,
+        };
+        // Follow the working directory
+        if watcher.path() != wrkdir.as_path() {
+            if let Err(err) = watcher.watch(wrkdir.as_path()) {
+                self.log(LogLevel::Warn, err);
+            }
+            return false;
+        }
+        if !watcher.poll() {
+            return false;
+        }
+        debug!("Local directory changed; reloading it");
+        self.reload_local_dir();
+        self.update_local_filelist();
+        true
+    }
+
+    /// ### toggle_skip_unchanged
+    ///
+    /// Enable or disable skipping unchanged files
+    pub(super) fn toggle_skip_unchanged(&mut self) {
+        let skip: bool = !self.skip_unchanged();
+        self.context_mut().transfer_opts_mut().skip_existing = skip;
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Skip unchanged files {}",
+                match skip {
+                    true => "enabled",
+                    false => "disabled",
+                }
+            ),
+        );
+    }
+
+    /// ### max_parallel_transfers
+    ///
+    /// Returns the maximum amount of copies on localhost to run at the same time.
+    /// The `--jobs` option takes precedence over configuration.
+    /// Uploads, downloads and remote operations always run one at a time, since all of them share the same session
+    pub(super) fn max_parallel_transfers(&self) -> usize {
+        match self.context().transfer_opts().jobs {
+            Some(jobs) => jobs.max(1),
+            None => self.config().get_max_parallel_transfers(),
+        }
+    }
+
+    /// ### max_preview_bytes
+    ///
+    /// Returns the size limit of the files which can be previewed without asking.
+    /// The `--max-preview-bytes` option takes precedence over configuration
+    pub(super) fn max_preview_bytes(&self) -> u64 {
+        self.context()
+            .transfer_opts()
+            .max_preview_bytes
+            .unwrap_or_else(|| self.config().get_max_preview_bytes())
+    }
+
+    /// ### max_edit_bytes
+    ///
+    /// Returns the size limit of the files which can be opened in the text editor without asking.
+    /// The `--max-edit-bytes` option takes precedence over configuration
+    pub(super) fn max_edit_bytes(&self) -> u64 {
+        self.context()
+            .transfer_opts()
+            .max_edit_bytes
+            .unwrap_or_else(|| self.config().get_max_edit_bytes())
+    }
+
+    /// ### check_size_limit
+    ///
+    /// Returns whether `file_name`, which is `size` bytes long, can be opened, given the size `limit`.
+    /// If the limit is exceeded, the user is asked whether to open the file anyway
+    pub(super) fn check_size_limit(&mut self, file_name: &str, size: u64, limit: u64) -> bool {
+        let allowed: bool = size_gate(size, limit, || {
+            let unit = self.config().get_size_unit();
+            self.mount_radio_size_limit(
+                format!(
+                    "\"{}\" is too big ({} > {}). Open anyway?",
+                    file_name,
+                    fmt_bytes(size, unit),
+                    fmt_bytes(limit, unit)
+                )
+                .as_str(),
+            );
+            loop {
+                self.view();
+                self.wait_input_event(Duration::from_millis(250));
+                if let Some(answer) = self.size_override.take() {
+                    return answer;
+                }
+            }
+        });
+        if !allowed {
+            self.log(
+                LogLevel::Info,
+                format!(
+                    "Did not open \"{}\": file exceeds the size limit ({})",
+                    file_name,
+                    fmt_bytes(limit, self.config().get_size_unit())
+                ),
+            );
+        }
+        allowed
+    }
+
+    /// ### transfer_max_attempts
+    ///
+    /// Returns the maximum amount of attempts made to transfer a file on transient errors.
+    /// The `--max-attempts` option takes precedence over configuration
+    pub(super) fn transfer_max_attempts(&self) -> usize {
+        match self.context().transfer_opts().max_attempts {
+            Some(attempts) => attempts.max(1),
+            None => self.config().get_transfer_max_attempts(),
+        }
+    }
+
+    /// ### resume_transfers
+    ///
+    /// Returns whether interrupted transfers must be resumed; enabled either by `--resume` or by configuration
+    pub(super) fn resume_transfers(&self) -> bool {
+        self.context().transfer_opts().resume || self.config().get_resume_transfers()
+    }
+
+    /// ### save_local_wrkdir
+    ///
+    /// Store the local working directory in configuration, so that next session starts from there
+    pub(super) fn save_local_wrkdir(&mut self) {
+        let wrkdir: PathBuf = self.local().wrkdir.clone();
+        self.context_mut()
+            .config_mut()
+            .set_last_local_wrkdir(wrkdir);
+        if let Err(err) = self.config().write_config() {
+            error!("Could not save last local working directory: {}", err);
+        }
+    }
+
+    /// ### prompt_on_file_replace
+    ///
+    /// Returns whether the user must be asked before replacing an existing file
+    pub(super) fn prompt_on_file_replace(&self) -> bool {
+        self.config().get_prompt_on_file_replace()
+    }
+
+    /// ### quit
+    ///
+    /// Ask the user to confirm before quitting, or quit straight away if confirmation is disabled
+    pub(super) fn quit(&mut self) {
+        match self.config().get_confirm_on_quit() {
+            true => self.mount_quit(),
+            false => self.disconnect_and_quit(),
+        }
+    }
+
+    /// ### log_dry_run
+    ///
+    /// Log the operations which would be performed, without executing them
+    pub(super) fn log_dry_run<T: fmt::Display>(&mut self, ops: &[T]) {
+        for msg in dry_run_report(ops).into_iter() {
+            self.log(LogLevel::Info, msg);
+        }
+    }
+
     /// ### init_config_client
     ///
     /// Initialize configuration client if possible.
     /// This function doesn't return errors.
-    pub(super) fn init_config_client() -> ConfigClient {
+    pub(crate) fn init_config_client() -> ConfigClient {
         match environment::init_config_dir() {
             Ok(termscp_dir) => match termscp_dir {
                 Some(termscp_dir) => {
@@ -96,11 +362,142 @@ impl FileTransferActivity {
         SshKeyStorage::storage_from_config(cli)
     }
 
+    /// ### make_host_key_verifier
+    ///
+    /// Make the verifier for SSH host keys, expecting the fingerprint saved in the bookmark, if any.
+    /// The known hosts file is resolved from CLI options, then configuration, then the `UserKnownHostsFile`
+    /// in `~/.ssh/config` and finally `~/.ssh/known_hosts`;
+    /// if the home directory is unknown, the file in the termscp configuration directory is used
+    pub(super) fn make_host_key_verifier(
+        params: &FileTransferParams,
+        cli: &ConfigClient,
+        opts: &TransferOpts,
+        ssh_config: &SshConfig,
+    ) -> HostKeyVerifier {
+        let config_path: Option<PathBuf> = cli.get_known_hosts();
+        let path: Option<PathBuf> = known_hosts::resolve_known_hosts_path(
+            opts.known_hosts.as_deref(),
+            config_path.as_deref(),
+            ssh_config
+                .user_known_hosts_file(params.address.as_str())
+                .map(|x| x.as_path()),
+            dirs::home_dir().as_deref(),
+        )
+        .or_else(|| match environment::init_config_dir() {
+            Ok(Some(termscp_dir)) => Some(environment::get_known_hosts_path(termscp_dir.as_path())),
+            _ => None,
+        });
+        HostKeyVerifier::new(path)
+            .strict(opts.strict_host_key_checking || cli.get_strict_host_key_checking())
+            .fingerprint(params.host_key_fingerprint.clone())
+    }
+
+    /// ### pin_host_key
+    ///
+    /// Save the host key accepted by the active session in the bookmarks of the remote host,
+    /// which either have no host key yet or expected the one which has just been trusted
+    pub(super) fn pin_host_key(&mut self) {
+        let params: FileTransferParams = self.context().ft_params().unwrap().clone();
+        let fingerprint: String = match self.sessions.client.host_key_fingerprint() {
+            Some(fingerprint) if params.host_key_fingerprint.as_ref() != Some(&fingerprint) => {
+                fingerprint
+            }
+            _ => return,
+        };
+        let result: Result<bool, String> = match environment::init_config_dir() {
+            Ok(Some(config_dir)) => BookmarksClient::new(
+                environment::get_bookmarks_paths(config_dir.as_path()).as_path(),
+                config_dir.as_path(),
+                self.config().get_max_recents(),
+            )
+            .and_then(
+                |mut client| match client.pin_host_key(&params, &fingerprint) {
+                    true => client.write_bookmarks().map(|_| true),
+                    false => Ok(false),
+                },
+            )
+            .map_err(|err| err.to_string()),
+            Ok(None) => Ok(false),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(true) => self.log(
+                LogLevel::Info,
+                format!("Saved host key {} in bookmarks", fingerprint),
+            ),
+            Ok(false) => {}
+            Err(err) => self.log(
+                LogLevel::Error,
+                format!("Could not save host key in bookmarks: {}", err),
+            ),
+        }
+        // Sessions opened from now on expect the accepted key
+        self.context_mut()
+            .set_ftparams(params.host_key_fingerprint(Some(fingerprint)));
+    }
+
+    /// ### make_ssh_compression
+    ///
+    /// Returns whether the SSH session with `host` must be compressed.
+    /// `--compression` and `--no-compression` take precedence over configuration,
+    /// then the `Compression` option in the ssh config is used; disabled otherwise
+    pub(super) fn make_ssh_compression(
+        host: &str,
+        cli: &ConfigClient,
+        opts: &TransferOpts,
+        ssh_config: &SshConfig,
+    ) -> bool {
+        opts.compression
+            .or_else(|| cli.get_ssh_compression())
+            .or_else(|| ssh_config.compression(host))
+            .unwrap_or(false)
+    }
+
+    /// ### create_missing_wrkdir
+    ///
+    /// Returns whether the remote working directory requested on connect must be created if missing;
+    /// enabled either by `--create-wrkdir` or by configuration, unless read-only mode is enabled
+    pub(super) fn create_missing_wrkdir(&self) -> bool {
+        (self.context().transfer_opts().create_wrkdir || self.config().get_create_missing_wrkdir())
+            && !self.read_only()
+    }
+
+    /// ### read_only
+    ///
+    /// Returns whether the actions which modify files are disabled;
+    /// enabled either by `--read-only` or by configuration
+    pub(super) fn read_only(&self) -> bool {
+        self.context().transfer_opts().read_only || self.config().get_read_only()
+    }
+
+    /// ### deny_read_only
+    ///
+    /// Returns whether `op` must not be performed, since read-only mode is enabled.
+    /// In this case the user is alerted
+    pub(super) fn deny_read_only(&mut self, op: Operation) -> bool {
+        match check_read_only(self.read_only(), op) {
+            Ok(()) => false,
+            Err(err) => {
+                self.log_and_alert(LogLevel::Warn, err);
+                true
+            }
+        }
+    }
+
+    /// ### strict_host_key_checking
+    ///
+    /// Returns whether unknown and changed host keys are rejected without prompting;
+    /// enabled either by `--strict-host-key-checking` or by configuration
+    pub(super) fn strict_host_key_checking(&self) -> bool {
+        self.context().transfer_opts().strict_host_key_checking
+            || self.config().get_strict_host_key_checking()
+    }
+
     /// ### setup_text_editor
     ///
     /// Set text editor to use
     pub(super) fn setup_text_editor(&self) {
-        env::set_var("EDITOR", self.config().get_text_editor());
+        env::set_var("EDITOR", self.config().resolve_text_editor());
     }
 
     /// ### read_input_event
@@ -108,7 +505,15 @@ impl FileTransferActivity {
     /// Read one event.
     /// Returns whether at least one event has been handled
     pub(super) fn read_input_event(&mut self) -> bool {
-        if let Ok(Some(event)) = self.context().input_hnd().read_event() {
+        self.wait_input_event(Duration::from_millis(10))
+    }
+
+    /// ### wait_input_event
+    ///
+    /// Wait up to `timeout` for one event and handle it.
+    /// Returns whether an event has been handled
+    pub(super) fn wait_input_event(&mut self, timeout: Duration) -> bool {
+        if let Ok(Some(event)) = self.context().input_hnd().wait_event(timeout) {
             // Handle event
             let msg = self.view.on(event);
             self.update(msg);
@@ -124,27 +529,66 @@ impl FileTransferActivity {
     ///
     /// Convert a path to absolute according to local explorer
     pub(super) fn local_to_abs_path(&self, path: &Path) -> PathBuf {
-        match path.is_relative() {
-            true => {
-                let mut d: PathBuf = self.local().wrkdir.clone();
-                d.push(path);
-                d
-            }
-            false => path.to_path_buf(),
-        }
+        resolve_path(self.local().wrkdir.as_path(), path)
     }
 
     /// ### remote_to_abs_path
     ///
     /// Convert a path to absolute according to remote explorer
     pub(super) fn remote_to_abs_path(&self, path: &Path) -> PathBuf {
-        match path.is_relative() {
-            true => {
-                let mut wrkdir: PathBuf = self.remote().wrkdir.clone();
-                wrkdir.push(path);
-                wrkdir
-            }
-            false => path.to_path_buf(),
-        }
+        resolve_path(self.remote().wrkdir.as_path(), path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::system::ssh_config::parse_ssh2_config;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ui_activities_filetransfer_misc_ssh_compression() {
+        let tmp_dir: TempDir = TempDir::new().unwrap();
+        let (cfg_path, key_path): (PathBuf, PathBuf) =
+            environment::get_config_paths(tmp_dir.path());
+        let mut config: ConfigClient =
+            ConfigClient::new(cfg_path.as_path(), key_path.as_path()).unwrap();
+        let ssh_config_path: PathBuf = tmp_dir.path().join("ssh_config");
+        assert!(
+            std::fs::write(ssh_config_path.as_path(), "Host slow\n\tCompression yes\n").is_ok()
+        );
+        let ssh_config: SshConfig = parse_ssh2_config(ssh_config_path.as_path(), None).unwrap();
+        // Default is off
+        let opts: TransferOpts = TransferOpts::default();
+        assert_eq!(
+            FileTransferActivity::make_ssh_compression("fast", &config, &opts, &ssh_config),
+            false
+        );
+        // ssh config
+        assert_eq!(
+            FileTransferActivity::make_ssh_compression("slow", &config, &opts, &ssh_config),
+            true
+        );
+        // Configuration wins over ssh config
+        config.set_ssh_compression(Some(false));
+        assert_eq!(
+            FileTransferActivity::make_ssh_compression("slow", &config, &opts, &ssh_config),
+            false
+        );
+        // CLI wins over configuration, in both directions
+        let cli_opts: TransferOpts = TransferOpts::default().compression(Some(true));
+        assert_eq!(
+            FileTransferActivity::make_ssh_compression("slow", &config, &cli_opts, &ssh_config),
+            true
+        );
+        config.set_ssh_compression(Some(true));
+        let cli_opts: TransferOpts = TransferOpts::default().compression(Some(false));
+        assert_eq!(
+            FileTransferActivity::make_ssh_compression("fast", &config, &cli_opts, &ssh_config),
+            false
+        );
     }
 }
@@ -35,24 +35,31 @@ pub(self) mod view;
 
 // locals
 use super::{Activity, Context, ExitReason};
-use crate::config::themes::Theme;
+use crate::config::{keybindings::KeyBindings, themes::Theme};
 use crate::filetransfer::ftp_transfer::FtpFileTransfer;
 use crate::filetransfer::scp_transfer::ScpFileTransfer;
 use crate::filetransfer::sftp_transfer::SftpFileTransfer;
-use crate::filetransfer::{FileTransfer, FileTransferProtocol};
+use crate::filetransfer::{FileTransfer, FileTransferParams, FileTransferProtocol, TransferOpts};
 use crate::fs::explorer::FileExplorer;
 use crate::fs::FsEntry;
 use crate::host::Localhost;
 use crate::system::config_client::ConfigClient;
+use crate::system::ssh_config::{self, SshConfig};
 pub(self) use lib::browser;
 use lib::browser::Browser;
+use lib::connect::{PendingConnection, RemoteConnector};
+use lib::logview::LogFilter;
+use lib::sessions::{RemoteSession, Sessions};
+use lib::stats::SessionStats;
 use lib::transfer::TransferStates;
+use lib::watcher::LocalWatcher;
 pub(self) use session::TransferPayload;
 
 // Includes
 use chrono::{DateTime, Local};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use std::collections::VecDeque;
+use std::fmt;
 use tempfile::TempDir;
 use tuirealm::View;
 
@@ -66,42 +73,66 @@ const COMPONENT_EXPLORER_LOCAL: &str = "EXPLORER_LOCAL";
 const COMPONENT_EXPLORER_REMOTE: &str = "EXPLORER_REMOTE";
 const COMPONENT_EXPLORER_FIND: &str = "EXPLORER_FIND";
 const COMPONENT_LOG_BOX: &str = "LOG_BOX";
+const COMPONENT_LOG_VIEWER: &str = "LOG_VIEWER";
 const COMPONENT_PROGRESS_BAR_FULL: &str = "PROGRESS_BAR_FULL";
 const COMPONENT_PROGRESS_BAR_PARTIAL: &str = "PROGRESS_BAR_PARTIAL";
 const COMPONENT_TEXT_ERROR: &str = "TEXT_ERROR";
 const COMPONENT_TEXT_FATAL: &str = "TEXT_FATAL";
 const COMPONENT_TEXT_HELP: &str = "TEXT_HELP";
+const COMPONENT_TEXT_HOST_KEY: &str = "TEXT_HOST_KEY";
+const COMPONENT_TEXT_INFO: &str = "TEXT_INFO";
 const COMPONENT_TEXT_WAIT: &str = "TEXT_WAIT";
+const COMPONENT_BREADCRUMB: &str = "BREADCRUMB";
+const COMPONENT_INPUT_CHMOD: &str = "INPUT_CHMOD";
 const COMPONENT_INPUT_COPY: &str = "INPUT_COPY";
 const COMPONENT_INPUT_EXEC: &str = "INPUT_EXEC";
 const COMPONENT_INPUT_FIND: &str = "INPUT_FIND";
 const COMPONENT_INPUT_GOTO: &str = "INPUT_GOTO";
 const COMPONENT_INPUT_MKDIR: &str = "INPUT_MKDIR";
+const COMPONENT_INPUT_MOVE: &str = "INPUT_MOVE";
 const COMPONENT_INPUT_NEWFILE: &str = "INPUT_NEWFILE";
 const COMPONENT_INPUT_OPEN_WITH: &str = "INPUT_OPEN_WITH";
 const COMPONENT_INPUT_RENAME: &str = "INPUT_RENAME";
 const COMPONENT_INPUT_SAVEAS: &str = "INPUT_SAVEAS";
+const COMPONENT_INPUT_SYMLINK: &str = "INPUT_SYMLINK";
 const COMPONENT_RADIO_DELETE: &str = "RADIO_DELETE";
 const COMPONENT_RADIO_DISCONNECT: &str = "RADIO_DISCONNECT";
+const COMPONENT_RADIO_HOST_KEY: &str = "RADIO_HOST_KEY";
 const COMPONENT_RADIO_QUIT: &str = "RADIO_QUIT";
+const COMPONENT_RADIO_REPLACE: &str = "RADIO_REPLACE";
+const COMPONENT_RADIO_SIZE_LIMIT: &str = "RADIO_SIZE_LIMIT";
 const COMPONENT_RADIO_SORTING: &str = "RADIO_SORTING";
 const COMPONENT_SPAN_STATUS_BAR_LOCAL: &str = "STATUS_BAR_LOCAL";
 const COMPONENT_SPAN_STATUS_BAR_REMOTE: &str = "STATUS_BAR_REMOTE";
 const COMPONENT_LIST_FILEINFO: &str = "LIST_FILEINFO";
+const COMPONENT_LIST_PREVIEW: &str = "LIST_PREVIEW";
+const COMPONENT_LIST_EXEC_OUTPUT: &str = "LIST_EXEC_OUTPUT";
+const COMPONENT_LIST_COMPARE: &str = "LIST_COMPARE";
+const COMPONENT_LIST_STATS: &str = "LIST_STATS";
 
 /// ## LogLevel
 ///
 /// Log level type
-enum LogLevel {
+pub(crate) enum LogLevel {
     Error,
     Warn,
     Info,
 }
 
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+        })
+    }
+}
+
 /// ## LogRecord
 ///
 /// Log record entry
-struct LogRecord {
+pub(crate) struct LogRecord {
     pub time: DateTime<Local>,
     pub level: LogLevel,
     pub msg: String,
@@ -124,22 +155,33 @@ impl LogRecord {
 ///
 /// FileTransferActivity is the data holder for the file transfer activity
 pub struct FileTransferActivity {
-    exit_reason: Option<ExitReason>,  // Exit reason
-    context: Option<Context>,         // Context holder
-    view: View,                       // View
-    host: Localhost,                  // Localhost
-    client: Box<dyn FileTransfer>,    // File transfer client
-    browser: Browser,                 // Browser
-    log_records: VecDeque<LogRecord>, // Log records
-    transfer: TransferStates,         // Transfer states
-    cache: Option<TempDir>,           // Temporary directory where to store stuff
+    exit_reason: Option<ExitReason>,                        // Exit reason
+    context: Option<Context>,                               // Context holder
+    view: View,                                             // View
+    host: Localhost,                                        // Localhost
+    sessions: Sessions<RemoteSession>,                      // Remote sessions (client and explorer)
+    connection: Option<PendingConnection<RemoteConnector>>, // Connection being established
+    browser: Browser,                                       // Browser
+    log_records: VecDeque<LogRecord>,                       // Log records
+    log_filter: LogFilter,                                  // Levels displayed in log viewer
+    transfer: TransferStates,                               // Transfer states
+    cache: Option<TempDir>,      // Temporary directory where to store stuff
+    keybindings: KeyBindings,    // Keys bound to explorer actions
+    busy: bool,                  // Has an event been handled on last draw?
+    size_override: Option<bool>, // Answer to the size limit prompt, not consumed yet
+    stats: SessionStats,         // Transfer statistics for the session
+    local_watcher: Option<LocalWatcher>, // Watches the local working directory, if enabled
 }
 
 impl FileTransferActivity {
     /// ### new
     ///
     /// Instantiates a new FileTransferActivity
-    pub fn new(host: Localhost, protocol: FileTransferProtocol) -> FileTransferActivity {
+    pub fn new(
+        host: Localhost,
+        params: &FileTransferParams,
+        opts: &TransferOpts,
+    ) -> FileTransferActivity {
         // Get config client
         let config_client: ConfigClient = Self::init_config_client();
         FileTransferActivity {
@@ -147,22 +189,78 @@ impl FileTransferActivity {
             context: None,
             view: View::init(),
             host,
-            client: match protocol {
-                FileTransferProtocol::Sftp => Box::new(SftpFileTransfer::new(
-                    Self::make_ssh_storage(&config_client),
-                )),
-                FileTransferProtocol::Ftp(ftps) => Box::new(FtpFileTransfer::new(ftps)),
-                FileTransferProtocol::Scp => {
-                    Box::new(ScpFileTransfer::new(Self::make_ssh_storage(&config_client)))
-                }
-            },
+            sessions: Sessions::new(RemoteSession::new(
+                Self::make_client(params, &config_client, opts),
+                Browser::build_remote_explorer(&config_client),
+            )),
+            connection: None,
             browser: Browser::new(&config_client),
             log_records: VecDeque::with_capacity(256), // 256 events is enough I guess
-            transfer: TransferStates::default(),
+            log_filter: LogFilter::default(),
+            transfer: TransferStates::default().size_unit(config_client.get_size_unit()),
             cache: match TempDir::new() {
                 Ok(d) => Some(d),
                 Err(_) => None,
             },
+            keybindings: KeyBindings::default(),
+            busy: false,
+            size_override: None,
+            stats: SessionStats::default(),
+            local_watcher: None,
+        }
+    }
+
+    /// ### make_client
+    ///
+    /// Make a new file transfer client for `protocol`
+    pub(crate) fn make_client(
+        params: &FileTransferParams,
+        config_client: &ConfigClient,
+        opts: &TransferOpts,
+    ) -> Box<dyn FileTransfer> {
+        let ssh_config: SshConfig = match params.protocol {
+            FileTransferProtocol::Sftp | FileTransferProtocol::Scp => {
+                ssh_config::load_user_ssh_config(config_client.get_ssh_config())
+            }
+            FileTransferProtocol::Ftp(_) => SshConfig::default(),
+        };
+        let host: &str = params.address.as_str();
+        match params.protocol {
+            FileTransferProtocol::Sftp => Box::new(
+                SftpFileTransfer::new(Self::make_ssh_storage(config_client))
+                    .host_key_verifier(Self::make_host_key_verifier(
+                        params,
+                        config_client,
+                        opts,
+                        &ssh_config,
+                    ))
+                    .compression(Self::make_ssh_compression(
+                        host,
+                        config_client,
+                        opts,
+                        &ssh_config,
+                    ))
+                    .ssh_key(params.ssh_key_path.clone())
+                    .proxy_jump(params.proxy_jump.clone()),
+            ),
+            FileTransferProtocol::Ftp(ftps) => Box::new(FtpFileTransfer::new(ftps)),
+            FileTransferProtocol::Scp => Box::new(
+                ScpFileTransfer::new(Self::make_ssh_storage(config_client))
+                    .host_key_verifier(Self::make_host_key_verifier(
+                        params,
+                        config_client,
+                        opts,
+                        &ssh_config,
+                    ))
+                    .compression(Self::make_ssh_compression(
+                        host,
+                        config_client,
+                        opts,
+                        &ssh_config,
+                    ))
+                    .ssh_key(params.ssh_key_path.clone())
+                    .proxy_jump(params.proxy_jump.clone()),
+            ),
         }
     }
 
@@ -175,11 +273,11 @@ impl FileTransferActivity {
     }
 
     fn remote(&self) -> &FileExplorer {
-        self.browser.remote()
+        &self.sessions.explorer
     }
 
     fn remote_mut(&mut self) -> &mut FileExplorer {
-        self.browser.remote_mut()
+        &mut self.sessions.explorer
     }
 
     fn found(&self) -> Option<&FileExplorer> {
@@ -269,6 +367,20 @@ impl Activity for FileTransferActivity {
         // init view
         self.init();
         debug!("Initialized view");
+        // Watch local working directory
+        if self.config().get_watch_local_dir() {
+            self.start_local_watcher();
+        }
+        // Load key bindings
+        match self.config().get_keybindings() {
+            Ok(keybindings) => self.keybindings = keybindings,
+            Err(err) => {
+                error!("Invalid key bindings: {}", err);
+                self.mount_error(
+                    format!("Invalid key bindings: {}; using default key bindings", err).as_str(),
+                );
+            }
+        }
         // Verify error state from context
         if let Some(err) = self.context.as_mut().unwrap().error() {
             error!("Fatal error on create: {}", err);
@@ -288,29 +400,47 @@ impl Activity for FileTransferActivity {
         if self.context.is_none() {
             return;
         }
+        // Check whether the pending connection has completed
+        if self.connection.is_some() {
+            redraw |= self.poll_connection();
+        }
         // Check if connected (popup must be None, otherwise would try reconnecting in loop in case of error)
-        if !self.client.is_connected() && self.view.get_props(COMPONENT_TEXT_FATAL).is_none() {
+        else if !self.sessions.client.is_connected()
+            && self.view.get_props(COMPONENT_TEXT_FATAL).is_none()
+            && self.view.get_props(COMPONENT_RADIO_HOST_KEY).is_none()
+        {
             let params = self.context().ft_params().unwrap();
             info!(
                 "Client is not connected to remote; connecting to {}:{}",
                 params.address, params.port
             );
-            let msg: String = format!("Connecting to {}:{}…", params.address, params.port);
+            let msg: String = format!(
+                "Connecting to {}:{}… (press <ESC> to cancel)",
+                params.address, params.port
+            );
             // Set init state to connecting popup
             self.mount_wait(msg.as_str());
-            // Force ui draw
-            self.view();
-            // Connect to remote
+            // Connect to remote in background
             self.connect();
             // Redraw
             redraw = true;
         }
+        // Reload local directory if it has changed on disk
+        redraw |= self.poll_local_watcher();
         // Handle input events (if false, becomes true; otherwise remains true)
         redraw |= self.read_input_event();
         // @! draw interface
         if redraw {
             self.view();
         }
+        self.busy = redraw;
+    }
+
+    /// ### is_busy
+    ///
+    /// `is_busy` reports to the activity manager whether the last call to `on_draw` has handled any event
+    fn is_busy(&self) -> bool {
+        self.busy
     }
 
     /// ### will_umount
@@ -337,10 +467,18 @@ impl Activity for FileTransferActivity {
         if let Err(err) = disable_raw_mode() {
             error!("Failed to disable raw mode: {}", err);
         }
-        // Disconnect client
-        if self.client.is_connected() {
-            let _ = self.client.disconnect();
+        // Abort pending connection
+        if let Some(mut connection) = self.connection.take() {
+            connection.cancel();
+        }
+        // Disconnect clients
+        for session in self.sessions.iter_mut() {
+            if session.client.is_connected() {
+                let _ = session.client.disconnect();
+            }
         }
+        // Store local working directory, to start from there next time
+        self.save_local_wrkdir();
         // Clear terminal and return
         match self.context.take() {
             Some(mut ctx) => {
@@ -26,17 +26,25 @@
  * SOFTWARE.
  */
 // Locals
+use super::lib::connect::{banner_to_show, ConnectState, PendingConnection, RemoteConnector};
+use super::lib::errors::error_message;
+use super::lib::scan::SizeScan;
+use super::lib::sync::{should_copy, DownloadOp, DownloadPlan, SyncDirection, SyncOp};
+use super::lib::transfer::transfer_bell;
 use super::{FileTransferActivity, LogLevel};
-use crate::filetransfer::FileTransferError;
+use crate::filetransfer::resume::{download_resume_offset, upload_resume_offset};
+use crate::filetransfer::retry::{backoff_delay, is_transient_error, is_transient_io_error};
+use crate::filetransfer::wrkdir::{entry_dir_action, missing_dirs, EntryDirAction};
+use crate::filetransfer::{FileTransfer, FileTransferError, FileTransferErrorType};
 use crate::fs::{FsEntry, FsFile};
 use crate::host::HostError;
-use crate::utils::fmt::fmt_millis;
+use crate::utils::fmt::{fmt_bytes, fmt_millis};
 
 // Ext
-use bytesize::ByteSize;
+use std::fs::File;
 use std::io::{Read, Seek, Write};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// ## TransferErrorReason
@@ -58,6 +66,19 @@ enum TransferErrorReason {
     FileTransferError(FileTransferError),
 }
 
+impl TransferErrorReason {
+    /// ### is_transient
+    ///
+    /// Returns whether the error may be temporary (e.g. connection reset), so that the transfer is worth retrying
+    fn is_transient(&self) -> bool {
+        match self {
+            TransferErrorReason::RemoteIoError(err) => is_transient_io_error(err.kind()),
+            TransferErrorReason::FileTransferError(err) => is_transient_error(err.kind()),
+            _ => false,
+        }
+    }
+}
+
 /// ## TransferPayload
 ///
 /// Represents the entity to send or receive during a transfer.
@@ -74,49 +95,227 @@ pub(super) enum TransferPayload {
 impl FileTransferActivity {
     /// ### connect
     ///
-    /// Connect to remote
+    /// Start connecting to remote in background; the client is handed to the connection thread
+    /// and given back once the connection has completed (see `poll_connection`)
     pub(super) fn connect(&mut self) {
         let params = self.context().ft_params().unwrap().clone();
-        let addr: String = params.address.clone();
-        let entry_dir: Option<PathBuf> = params.entry_directory.clone();
-        // Connect to remote
-        match self.client.connect(
-            params.address,
-            params.port,
-            params.username,
-            params.password,
+        let client: Box<dyn FileTransfer> =
+            Self::make_client(&params, self.config(), self.context().transfer_opts());
+        let client: Box<dyn FileTransfer> = std::mem::replace(&mut self.sessions.client, client);
+        self.connection = Some(PendingConnection::start(RemoteConnector::new(
+            client, params,
+        )));
+    }
+
+    /// ### poll_connection
+    ///
+    /// Check whether the pending connection has completed. Returns whether its state has changed
+    pub(super) fn poll_connection(&mut self) -> bool {
+        let state: ConnectState<RemoteConnector> = match self.connection.as_mut() {
+            Some(connection) => connection.poll(),
+            None => return false,
+        };
+        match state {
+            ConnectState::Pending => false,
+            ConnectState::Connected(connector, welcome) => {
+                self.connection = None;
+                self.sessions.client = connector.into_client();
+                self.on_connected(welcome);
+                true
+            }
+            ConnectState::Failed(connector, err) => {
+                self.connection = None;
+                // Keep the client, since it may be asked to trust the host key
+                if let Some(connector) = connector {
+                    self.sessions.client = connector.into_client();
+                }
+                self.on_connection_error(err);
+                true
+            }
+            ConnectState::Cancelled => {
+                self.connection = None;
+                true
+            }
+        }
+    }
+
+    /// ### cancel_connection
+    ///
+    /// Abort the pending connection and go back to authentication
+    pub(super) fn cancel_connection(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            connection.cancel();
+        }
+        self.umount_wait();
+        let addr: String = self.context().ft_params().unwrap().address.clone();
+        info!("Connection to {} cancelled by user", addr);
+        // Other sessions are open: just close this one
+        if self.sessions.count() > 1 {
+            self.action_close_session();
+            return;
+        }
+        self.context_mut()
+            .set_error(format!("Connection to {} cancelled", addr));
+        self.exit_reason = Some(super::ExitReason::Disconnect);
+    }
+
+    /// ### on_connected
+    ///
+    /// Initialize the explorers once the connection has been established
+    fn on_connected(&mut self, welcome: Option<String>) {
+        let params = self.context().ft_params().unwrap().clone();
+        if let Some(banner) = welcome.as_ref() {
+            // Log welcome
+            self.log(
+                LogLevel::Info,
+                format!(
+                    "Established connection with '{}': \"{}\"",
+                    params.address, banner
+                ),
+            );
+        }
+        self.pin_host_key();
+        // Keep the default directory as home
+        self.sessions.home = self.sessions.client.pwd().ok();
+        // Try to change directory to entry directory
+        if let Some(entry_directory) = params.entry_directory {
+            self.enter_entry_directory(entry_directory.as_path());
+        }
+        // Set state to explorer
+        self.umount_wait();
+        self.reload_remote_dir();
+        // Update file lists
+        self.update_local_filelist();
+        self.update_remote_filelist();
+        // Show banner once, on top of the explorers
+        if let Some(banner) = banner_to_show(
+            welcome.as_deref(),
+            params.protocol,
+            self.config().get_show_banner(),
         ) {
-            Ok(welcome) => {
-                if let Some(banner) = welcome {
-                    // Log welcome
+            self.mount_info(params.address.as_str(), banner);
+        }
+    }
+
+    /// ### enter_entry_directory
+    ///
+    /// Change the remote working directory to the one requested for the connection.
+    /// If it doesn't exist, it's created along with its parents when enabled by `--create-wrkdir` or configuration;
+    /// otherwise the explorer stays in the default directory, reporting it. With dry-run, missing directories are only reported
+    fn enter_entry_directory(&mut self, path: &Path) {
+        let wrkdir: Option<PathBuf> = self.sessions.client.pwd().ok();
+        let path: PathBuf = match wrkdir.as_ref() {
+            Some(wrkdir) => wrkdir.join(path),
+            None => path.to_path_buf(),
+        };
+        let exists: bool = self.sessions.client.change_dir(path.as_path()).is_ok();
+        match entry_dir_action(exists, self.create_missing_wrkdir()) {
+            EntryDirAction::ChangeDir => self.remote_changedir(path.as_path(), false),
+            EntryDirAction::Create => {
+                let client = self.sessions.client.as_mut();
+                let missing: Vec<PathBuf> =
+                    missing_dirs(path.as_path(), |p| client.change_dir(p).is_ok());
+                // With dry-run, directories are only reported and the default directory is kept
+                if self.dry_run() {
+                    for dir in missing.iter() {
+                        self.log(
+                            LogLevel::Info,
+                            format!("[dry-run] would create directory \"{}\"", dir.display()),
+                        );
+                    }
+                    if let Some(wrkdir) = wrkdir {
+                        let _ = self.sessions.client.change_dir(wrkdir.as_path());
+                    }
+                    return;
+                }
+                for dir in missing.iter() {
+                    if let Err(err) = self.sessions.client.mkdir(dir.as_path()) {
+                        self.log_and_alert_remote(
+                            LogLevel::Error,
+                            format!("Could not create directory \"{}\"", dir.display()),
+                            &err,
+                        );
+                        // Go back to the default directory
+                        if let Some(wrkdir) = wrkdir {
+                            let _ = self.sessions.client.change_dir(wrkdir.as_path());
+                        }
+                        return;
+                    }
                     self.log(
                         LogLevel::Info,
-                        format!("Established connection with '{}': \"{}\"", addr, banner),
+                        format!("Created directory \"{}\"", dir.display()),
                     );
                 }
-                // Try to change directory to entry directory
-                let mut remote_chdir: Option<PathBuf> = None;
-                if let Some(entry_directory) = &entry_dir {
-                    remote_chdir = Some(entry_directory.clone());
-                }
-                if let Some(entry_directory) = remote_chdir {
-                    self.remote_changedir(entry_directory.as_path(), false);
-                }
-                // Set state to explorer
-                self.umount_wait();
-                self.reload_remote_dir();
-                // Update file lists
-                self.update_local_filelist();
-                self.update_remote_filelist();
+                self.remote_changedir(path.as_path(), false);
             }
-            Err(err) => {
-                // Set popup fatal error
-                self.umount_wait();
-                self.mount_fatal(&err.to_string());
+            EntryDirAction::Warn => {
+                self.log_and_alert(
+                    LogLevel::Warn,
+                    format!(
+                        "Working directory \"{}\" doesn't exist or can't be entered; enable `create_missing_wrkdir` in configuration or use `--create-wrkdir` to create it",
+                        path.display()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// ### on_connection_error
+    ///
+    /// Report a connection failure
+    fn on_connection_error(&mut self, err: FileTransferError) {
+        let addr: String = self.context().ft_params().unwrap().address.clone();
+        self.umount_wait();
+        match err.kind() {
+            // Ask whether to trust host key
+            FileTransferErrorType::HostKeyUnknown | FileTransferErrorType::HostKeyMismatch
+                if !self.strict_host_key_checking() =>
+            {
+                self.log(LogLevel::Warn, format!("{}: {}", addr, err));
+                self.mount_host_key(addr.as_str(), &err);
+            }
+            // Set popup fatal error; details are kept in the log
+            _ => {
+                self.log(
+                    LogLevel::Error,
+                    format!("Could not connect to {}: {}", addr, err),
+                );
+                self.session_failed(&format!(
+                    "Could not connect to {}: {}",
+                    addr,
+                    error_message(err.kind())
+                ));
             }
         }
     }
 
+    /// ### session_failed
+    ///
+    /// Report that the active session couldn't be established.
+    /// If other sessions are open, the failed one is closed; otherwise it's a fatal error
+    pub(super) fn session_failed(&mut self, err: &str) {
+        match self.sessions.count() {
+            1 => self.mount_fatal(err),
+            _ => {
+                self.action_close_session();
+                self.mount_error(err);
+            }
+        }
+    }
+
+    /// ### trust_host_key
+    ///
+    /// Trust the host key presented by the remote; the connection will be established again on the next draw
+    pub(super) fn trust_host_key(&mut self) {
+        match self.sessions.client.trust_host_key() {
+            Ok(_) => self.log(
+                LogLevel::Info,
+                String::from("Host key added to known hosts"),
+            ),
+            Err(err) => self.session_failed(&format!("Could not trust host key: {}", err)),
+        }
+    }
+
     /// ### disconnect
     ///
     /// disconnect from remote
@@ -126,7 +325,9 @@ impl FileTransferActivity {
         // Show popup disconnecting
         self.mount_wait(msg.as_str());
         // Disconnect
-        let _ = self.client.disconnect();
+        let _ = self.sessions.client.disconnect();
+        // Drop remote entries; they're stale now
+        self.remote_mut().clear();
         // Quit
         self.exit_reason = Some(super::ExitReason::Disconnect);
     }
@@ -144,7 +345,7 @@ impl FileTransferActivity {
     /// Reload remote directory entries and update browser
     pub(super) fn reload_remote_dir(&mut self) {
         // Get current entries
-        if let Ok(wrkdir) = self.client.pwd() {
+        if let Ok(wrkdir) = self.sessions.client.pwd() {
             self.remote_scan(wrkdir.as_path());
             // Set wrkdir
             self.remote_mut().wrkdir = wrkdir;
@@ -164,12 +365,28 @@ impl FileTransferActivity {
     ///
     /// Scan current local directory
     fn local_scan(&mut self, path: &Path) {
-        match self.host.scan_dir(path) {
-            Ok(files) => {
-                // Set files and sort (sorting is implicit)
-                self.local_mut().set_files(files);
+        self.local_mut().set_loading();
+        match self.host.scan_dir_listing(path) {
+            Ok(listing) => {
+                // Report the entries which couldn't be read, but still display the other ones
+                if listing.is_partial() {
+                    for (entry, err) in listing.errors.iter() {
+                        self.log(
+                            LogLevel::Warn,
+                            format!("Could not read \"{}\": {}", entry.display(), err),
+                        );
+                    }
+                }
+                let files: Vec<FsEntry> = listing.entries;
+                // Set files and sort (sorting is implicit); marks are kept if directory hasn't changed
+                match self.local().wrkdir.as_path() == path {
+                    true => self.local_mut().reload_preserving_selection(files),
+                    false => self.local_mut().set_files(files),
+                }
             }
             Err(err) => {
+                let permission_denied: bool = err.is_permission_denied();
+                self.local_mut().set_load_failed(permission_denied);
                 self.log_and_alert(
                     LogLevel::Error,
                     format!("Could not scan current directory: {}", err),
@@ -182,15 +399,22 @@ impl FileTransferActivity {
     ///
     /// Scan current remote directory
     fn remote_scan(&mut self, path: &Path) {
-        match self.client.list_dir(path) {
+        self.remote_mut().set_loading();
+        match self.sessions.client.list_dir(path) {
             Ok(files) => {
-                // Set files and sort (sorting is implicit)
-                self.remote_mut().set_files(files);
+                // Set files and sort (sorting is implicit); marks are kept if directory hasn't changed
+                match self.remote().wrkdir.as_path() == path {
+                    true => self.remote_mut().reload_preserving_selection(files),
+                    false => self.remote_mut().set_files(files),
+                }
             }
             Err(err) => {
-                self.log_and_alert(
+                let permission_denied: bool = err.kind() == FileTransferErrorType::PexError;
+                self.remote_mut().set_load_failed(permission_denied);
+                self.log_and_alert_remote(
                     LogLevel::Error,
-                    format!("Could not scan current directory: {}", err),
+                    String::from("Could not scan current directory"),
+                    &err,
                 );
             }
         }
@@ -207,8 +431,9 @@ impl FileTransferActivity {
         curr_remote_path: &Path,
         dst_name: Option<String>,
     ) -> Result<(), String> {
+        let started: Instant = Instant::now();
         // Use different method based on payload
-        match payload {
+        let result = match payload {
             TransferPayload::Any(entry) => {
                 self.filetransfer_send_any(&entry, curr_remote_path, dst_name)
             }
@@ -218,7 +443,9 @@ impl FileTransferActivity {
             TransferPayload::Many(entries) => {
                 self.filetransfer_send_many(entries, curr_remote_path)
             }
-        }
+        };
+        self.ring_transfer_bell(started.elapsed(), result.is_ok());
+        result
     }
 
     /// ### filetransfer_send_file
@@ -233,8 +460,7 @@ impl FileTransferActivity {
         // Reset states
         self.transfer.reset();
         // Calculate total size of transfer
-        let total_transfer_size: usize = file.size;
-        self.transfer.full.init(total_transfer_size);
+        self.transfer.init(file.size, 1);
         // Mount progress bar
         self.mount_progress_bar(format!("Uploading {}…", file.abs_path.display()));
         // Get remote path
@@ -245,6 +471,21 @@ impl FileTransferActivity {
             None => PathBuf::from(file_name.as_str()),
         };
         remote_path.push(remote_file_name);
+        // Skip the file if unchanged on remote
+        if self.skip_unchanged() && self.is_remote_unchanged(file, remote_path.as_path()) {
+            self.filetransfer_skip(file, remote_path.as_path(), "is unchanged");
+            self.umount_progress_bar();
+            return Ok(());
+        }
+        // Ask whether to replace the remote file, if it exists
+        if self.prompt_on_file_replace()
+            && self.sessions.client.stat(remote_path.as_path()).is_ok()
+            && !self.filetransfer_should_replace(remote_path.as_path())
+        {
+            self.filetransfer_skip(file, remote_path.as_path(), "already exists");
+            self.umount_progress_bar();
+            return Ok(());
+        }
         // Send
         let result = self.filetransfer_send_one(file, remote_path.as_path(), file_name);
         // Umount progress bar
@@ -264,11 +505,18 @@ impl FileTransferActivity {
     ) -> Result<(), String> {
         // Reset states
         self.transfer.reset();
-        // Calculate total size of transfer
-        let total_transfer_size: usize = self.get_total_transfer_size_local(entry);
-        self.transfer.full.init(total_transfer_size);
         // Mount progress bar
         self.mount_progress_bar(format!("Uploading {}…", entry.get_abs_path().display()));
+        // Calculate total size of transfer
+        let (total_transfer_size, files): (usize, usize) =
+            match self.get_total_transfer_size_local(std::slice::from_ref(entry)) {
+                Some(totals) => totals,
+                None => {
+                    self.umount_progress_bar();
+                    return Ok(());
+                }
+            };
+        self.transfer.init(total_transfer_size, files);
         // Send recurse
         self.filetransfer_send_recurse(entry, curr_remote_path, dst_name);
         // Umount progress bar
@@ -286,20 +534,26 @@ impl FileTransferActivity {
     ) -> Result<(), String> {
         // Reset states
         self.transfer.reset();
-        // Calculate total size of transfer
-        let total_transfer_size: usize = entries
-            .iter()
-            .map(|x| self.get_total_transfer_size_local(x))
-            .sum();
-        self.transfer.full.init(total_transfer_size);
         // Mount progress bar
         self.mount_progress_bar(format!("Uploading {} entries…", entries.len()));
+        // Calculate total size of transfer
+        let (total_transfer_size, files): (usize, usize) =
+            match self.get_total_transfer_size_local(entries.as_slice()) {
+                Some(totals) => totals,
+                None => {
+                    self.umount_progress_bar();
+                    return Ok(());
+                }
+            };
+        self.transfer.init(total_transfer_size, files);
         // Send recurse
         entries
             .iter()
             .for_each(|x| self.filetransfer_send_recurse(x, curr_remote_path, None));
         // Umount progress bar
         self.umount_progress_bar();
+        // Log report
+        self.log_transfer_report();
         Ok(())
     }
 
@@ -324,7 +578,16 @@ impl FileTransferActivity {
         // Match entry
         match entry {
             FsEntry::File(file) => {
-                if let Err(err) = self.filetransfer_send_one(file, remote_path.as_path(), file_name)
+                // Skip the file if unchanged on remote; otherwise ask whether to replace it, if it exists
+                if self.skip_unchanged() && self.is_remote_unchanged(file, remote_path.as_path()) {
+                    self.filetransfer_skip(file, remote_path.as_path(), "is unchanged");
+                } else if self.prompt_on_file_replace()
+                    && self.sessions.client.stat(remote_path.as_path()).is_ok()
+                    && !self.filetransfer_should_replace(remote_path.as_path())
+                {
+                    self.filetransfer_skip(file, remote_path.as_path(), "already exists");
+                } else if let Err(err) =
+                    self.filetransfer_send_one(file, remote_path.as_path(), file_name)
                 {
                     // Log error
                     self.log_and_alert(
@@ -337,7 +600,7 @@ impl FileTransferActivity {
                         TransferErrorReason::Abrupted | TransferErrorReason::RemoteIoError(_)
                     ) {
                         // Stat file on remote and remove it if exists
-                        match self.client.stat(remote_path.as_path()) {
+                        match self.sessions.client.stat(remote_path.as_path()) {
                             Err(err) => self.log(
                                 LogLevel::Error,
                                 format!(
@@ -347,7 +610,7 @@ impl FileTransferActivity {
                                 ),
                             ),
                             Ok(entry) => {
-                                if let Err(err) = self.client.remove(&entry) {
+                                if let Err(err) = self.sessions.client.remove(&entry) {
                                     self.log(
                                         LogLevel::Error,
                                         format!(
@@ -364,7 +627,7 @@ impl FileTransferActivity {
             }
             FsEntry::Directory(dir) => {
                 // Create directory on remote
-                match self.client.mkdir(remote_path.as_path()) {
+                match self.sessions.client.mkdir(remote_path.as_path()) {
                     Ok(_) => {
                         self.log(
                             LogLevel::Info,
@@ -424,31 +687,60 @@ impl FileTransferActivity {
         }
     }
 
-    /// ### filetransfer_send_file
+    /// ### filetransfer_send_one
     ///
-    /// Send local file and write it to remote path
+    /// Send local file and write it to remote path, then account it in the aggregate progress
     fn filetransfer_send_one(
         &mut self,
         local: &FsFile,
         remote: &Path,
         file_name: String,
+    ) -> Result<(), TransferErrorReason> {
+        let result = self.filetransfer_retry(file_name.as_str(), |activity| {
+            activity.filetransfer_send_stream(local, remote, file_name.clone())
+        });
+        self.transfer.file_done(local.size);
+        match result.as_ref() {
+            Ok(_) => {
+                self.transfer.report.success(local.size);
+                self.stats.uploaded(local.size);
+            }
+            Err(err) => {
+                self.transfer
+                    .report
+                    .failure(local.abs_path.as_path(), err.to_string());
+                self.stats.failed();
+            }
+        }
+        result
+    }
+
+    /// ### filetransfer_send_stream
+    ///
+    /// Send local file and write it to remote path
+    fn filetransfer_send_stream(
+        &mut self,
+        local: &FsFile,
+        remote: &Path,
+        file_name: String,
     ) -> Result<(), TransferErrorReason> {
         // Upload file
         // Try to open local file
         match self.host.open_file_read(local.abs_path.as_path()) {
-            Ok(mut fhnd) => match self.client.send_file(local, remote) {
-                Ok(mut rhnd) => {
+            Ok(mut fhnd) => match self.filetransfer_send_open(local, remote, &mut fhnd) {
+                Ok((mut rhnd, offset)) => {
                     // Write file
                     let file_size: usize =
                         fhnd.seek(std::io::SeekFrom::End(0)).unwrap_or(0) as usize;
-                    // Init transfer
-                    self.transfer.partial.init(file_size);
+                    // Init transfer; the resumed part is already done
+                    self.transfer.partial.init(file_size.saturating_sub(offset));
+                    self.transfer.full.update_progress(offset);
                     // rewind
-                    if let Err(err) = fhnd.seek(std::io::SeekFrom::Start(0)) {
+                    if let Err(err) = fhnd.seek(std::io::SeekFrom::Start(offset as u64)) {
                         return Err(TransferErrorReason::CouldNotRewind(err));
                     }
                     // Write remote file
-                    let mut total_bytes_written: usize = 0;
+                    let mut total_bytes_written: usize = offset;
                     let mut last_progress_val: f64 = 0.0;
                     let mut last_input_event_fetch: Option<Instant> = None;
                     // While the entire file hasn't been completely written,
@@ -467,6 +759,10 @@ impl FileTransferActivity {
                             // Reset instant
                             last_input_event_fetch = Some(Instant::now());
                         }
+                        // Wait while paused
+                        if self.transfer.paused() {
+                            self.wait_while_paused(format!("Uploading \"{}\"…", file_name));
+                        }
                         // Read till you can
                         let mut buffer: [u8; 65536] = [0; 65536];
                         let delta: usize = match fhnd.read(&mut buffer) {
@@ -508,7 +804,7 @@ impl FileTransferActivity {
                         }
                     }
                     // Finalize stream
-                    if let Err(err) = self.client.on_sent(rhnd) {
+                    if let Err(err) = self.sessions.client.on_sent(rhnd) {
                         self.log(
                             LogLevel::Warn,
                             format!("Could not finalize remote stream: \"{}\"", err),
@@ -525,7 +821,10 @@ impl FileTransferActivity {
                             local.abs_path.display(),
                             remote.display(),
                             fmt_millis(self.transfer.partial.started().elapsed()),
-                            ByteSize(self.transfer.partial.calc_bytes_per_second()),
+                            fmt_bytes(
+                                self.transfer.partial.calc_bytes_per_second(),
+                                self.config().get_size_unit()
+                            ),
                         ),
                     );
                 }
@@ -536,6 +835,99 @@ impl FileTransferActivity {
         Ok(())
     }
 
+    /// ### filetransfer_send_open
+    ///
+    /// Open the remote file for write.
+    /// If resume is enabled and a partial remote file exists, the write continues from its end.
+    /// Returns the remote stream and the offset where the write starts from
+    fn filetransfer_send_open(
+        &mut self,
+        local: &FsFile,
+        remote: &Path,
+        fhnd: &mut File,
+    ) -> Result<(Box<dyn Write>, usize), FileTransferError> {
+        let offset: usize = self.upload_resume_offset(local, remote, fhnd);
+        if offset > 0 {
+            if let Ok(rhnd) = self
+                .sessions
+                .client
+                .send_file_at(local, remote, offset as u64)
+            {
+                self.log(
+                    LogLevel::Info,
+                    format!(
+                        "Resuming upload of \"{}\" from {}",
+                        local.abs_path.display(),
+                        fmt_bytes(offset as u64, self.config().get_size_unit())
+                    ),
+                );
+                return Ok((rhnd, offset));
+            }
+        }
+        self.sessions
+            .client
+            .send_file(local, remote)
+            .map(|rhnd| (rhnd, 0))
+    }
+
+    /// ### upload_resume_offset
+    ///
+    /// Get the offset where to resume the upload of `local` to `remote` from.
+    /// Returns 0 if resume is disabled, not supported by the protocol or if the remote file
+    /// doesn't start with the same bytes of the local file.
+    fn upload_resume_offset(&mut self, local: &FsFile, remote: &Path, fhnd: &mut File) -> usize {
+        if !self.resume_transfers() {
+            return 0;
+        }
+        match upload_resume_offset(self.sessions.client.as_mut(), local, fhnd, remote) {
+            Some(offset) => offset,
+            None => {
+                self.log(
+                    LogLevel::Warn,
+                    format!(
+                        "\"{}\" differs from \"{}\"; it will be overwritten",
+                        remote.display(),
+                        local.abs_path.display()
+                    ),
+                );
+                0
+            }
+        }
+    }
+
+    /// ### download_resume_offset
+    ///
+    /// Get the offset where to resume the download of `remote` to `local` from.
+    /// Returns 0 if resume is disabled, not supported by the protocol or if the local file
+    /// doesn't start with the same bytes of the remote file.
+    fn download_resume_offset(&mut self, local: &Path, remote: &FsFile) -> usize {
+        if !self.resume_transfers() {
+            return 0;
+        }
+        let dest_size: usize = match self.host.stat(local) {
+            Ok(FsEntry::File(dest)) => dest.size,
+            _ => return 0,
+        };
+        let mut fhnd: File = match self.host.open_file_read(local) {
+            Ok(fhnd) => fhnd,
+            Err(_) => return 0,
+        };
+        match download_resume_offset(self.sessions.client.as_mut(), remote, dest_size, &mut fhnd) {
+            Some(offset) => offset,
+            None => {
+                self.log(
+                    LogLevel::Warn,
+                    format!(
+                        "\"{}\" differs from \"{}\"; it will be overwritten",
+                        local.display(),
+                        remote.abs_path.display()
+                    ),
+                );
+                0
+            }
+        }
+    }
+
     /// ### filetransfer_recv
     ///
     /// Recv fs entry from remote.
@@ -547,11 +939,14 @@ impl FileTransferActivity {
         local_path: &Path,
         dst_name: Option<String>,
     ) -> Result<(), String> {
-        match payload {
+        let started: Instant = Instant::now();
+        let result = match payload {
             TransferPayload::Any(entry) => self.filetransfer_recv_any(&entry, local_path, dst_name),
             TransferPayload::File(file) => self.filetransfer_recv_file(&file, local_path),
             TransferPayload::Many(entries) => self.filetransfer_recv_many(entries, local_path),
-        }
+        };
+        self.ring_transfer_bell(started.elapsed(), result.is_ok());
+        result
     }
 
     /// ### filetransfer_recv_any
@@ -567,11 +962,18 @@ impl FileTransferActivity {
     ) -> Result<(), String> {
         // Reset states
         self.transfer.reset();
-        // Calculate total transfer size
-        let total_transfer_size: usize = self.get_total_transfer_size_remote(entry);
-        self.transfer.full.init(total_transfer_size);
         // Mount progress bar
         self.mount_progress_bar(format!("Downloading {}…", entry.get_abs_path().display()));
+        // Calculate total transfer size
+        let (total_transfer_size, files): (usize, usize) =
+            match self.get_total_transfer_size_remote(std::slice::from_ref(entry)) {
+                Some(totals) => totals,
+                None => {
+                    self.umount_progress_bar();
+                    return Ok(());
+                }
+            };
+        self.transfer.init(total_transfer_size, files);
         // Receive
         self.filetransfer_recv_recurse(entry, local_path, dst_name);
         // Umount progress bar
@@ -586,10 +988,24 @@ impl FileTransferActivity {
         // Reset states
         self.transfer.reset();
         // Calculate total transfer size
-        let total_transfer_size: usize = entry.size;
-        self.transfer.full.init(total_transfer_size);
+        self.transfer.init(entry.size, 1);
         // Mount progress bar
         self.mount_progress_bar(format!("Downloading {}…", entry.abs_path.display()));
+        // Skip the file if unchanged on localhost
+        if self.skip_unchanged() && self.is_local_unchanged(entry, local_path) {
+            self.filetransfer_skip(entry, local_path, "is unchanged");
+            self.umount_progress_bar();
+            return Ok(());
+        }
+        // Ask whether to replace the local file, if it exists
+        if self.prompt_on_file_replace()
+            && local_path.exists()
+            && !self.filetransfer_should_replace(local_path)
+        {
+            self.filetransfer_skip(entry, local_path, "already exists");
+            self.umount_progress_bar();
+            return Ok(());
+        }
         // Receive
         let result = self.filetransfer_recv_one(local_path, entry, entry.name.clone());
         // Umount progress bar
@@ -608,23 +1024,235 @@ impl FileTransferActivity {
     ) -> Result<(), String> {
         // Reset states
         self.transfer.reset();
-        // Calculate total size of transfer
-        let total_transfer_size: usize = entries
-            .iter()
-            .map(|x| self.get_total_transfer_size_remote(x))
-            .sum();
-        self.transfer.full.init(total_transfer_size);
         // Mount progress bar
         self.mount_progress_bar(format!("Downloading {} entries…", entries.len()));
+        // Calculate total size of transfer
+        let (total_transfer_size, files): (usize, usize) =
+            match self.get_total_transfer_size_remote(entries.as_slice()) {
+                Some(totals) => totals,
+                None => {
+                    self.umount_progress_bar();
+                    return Ok(());
+                }
+            };
+        self.transfer.init(total_transfer_size, files);
         // Send recurse
         entries
             .iter()
             .for_each(|x| self.filetransfer_recv_recurse(x, curr_remote_path, None));
         // Umount progress bar
         self.umount_progress_bar();
+        // Log report
+        self.log_transfer_report();
         Ok(())
     }
 
+    /// ### filetransfer_recv_plan
+    ///
+    /// Execute a `DownloadPlan`, creating local directories and downloading files.
+    /// A failure doesn't stop the transfer; all the errors are returned once the plan has been executed
+    pub(super) fn filetransfer_recv_plan(&mut self, plan: DownloadPlan) -> Vec<String> {
+        let mut errors: Vec<String> = plan.errors.clone();
+        // Reset states
+        self.transfer.reset();
+        let files: usize = plan
+            .ops
+            .iter()
+            .filter(|x| matches!(x, DownloadOp::Recv(_, _)))
+            .count();
+        self.transfer.init(plan.total_size(), files);
+        // Mount progress bar
+        self.mount_progress_bar(format!("Downloading {} files…", files));
+        for op in plan.ops.iter() {
+            // If transfer has been aborted; break
+            if self.transfer.aborted() {
+                break;
+            }
+            match op {
+                DownloadOp::Mkdir(dir) => match self.host.mkdir_ex(dir.as_path(), true) {
+                    Ok(_) => self.log(
+                        LogLevel::Info,
+                        format!("Created directory \"{}\"", dir.display()),
+                    ),
+                    Err(err) => errors.push(format!(
+                        "Could not create directory \"{}\": {}",
+                        dir.display(),
+                        err
+                    )),
+                },
+                DownloadOp::Skip(file, local) => self.log(
+                    LogLevel::Info,
+                    format!(
+                        "Skipped \"{}\": \"{}\" is unchanged",
+                        file.abs_path.display(),
+                        local.display()
+                    ),
+                ),
+                DownloadOp::Recv(file, local) => {
+                    match self.filetransfer_recv_one(local.as_path(), file, file.name.clone()) {
+                        Ok(_) => {
+                            // Preserve mtime, so that file is recognized as unchanged next time
+                            if let Err(err) =
+                                self.host.set_mtime(local.as_path(), file.last_change_time)
+                            {
+                                self.log(
+                                    LogLevel::Warn,
+                                    format!(
+                                        "Could not set modification time for \"{}\": {}",
+                                        local.display(),
+                                        err
+                                    ),
+                                );
+                            }
+                        }
+                        Err(err) => errors.push(format!(
+                            "Could not download file \"{}\": {}",
+                            file.abs_path.display(),
+                            err
+                        )),
+                    }
+                }
+            }
+        }
+        // Umount progress bar
+        self.umount_progress_bar();
+        // Reload directory on local
+        self.reload_local_dir();
+        if self.transfer.aborted() {
+            errors.push(String::from("Download aborted"));
+        }
+        errors
+    }
+
+    /// ### filetransfer_mirror
+    ///
+    /// Execute the operations planned to mirror a tree in the provided direction.
+    /// A failure doesn't stop the mirror; all the errors are returned once the operations have been executed
+    pub(super) fn filetransfer_mirror(
+        &mut self,
+        ops: &[SyncOp],
+        direction: SyncDirection,
+    ) -> Vec<String> {
+        let mut errors: Vec<String> = Vec::new();
+        // Reset states
+        self.transfer.reset();
+        let files: Vec<&FsFile> = ops
+            .iter()
+            .filter_map(|x| match x {
+                SyncOp::Copy(FsEntry::File(f), _) => Some(f),
+                _ => None,
+            })
+            .collect();
+        let total_transfer_size: usize = files.iter().map(|x| x.size).sum();
+        self.transfer.init(total_transfer_size, files.len());
+        // Mount progress bar
+        self.mount_progress_bar(String::from("Mirroring…"));
+        for op in ops.iter() {
+            // If transfer has been aborted; break
+            if self.transfer.aborted() {
+                break;
+            }
+            match op {
+                SyncOp::Delete(entry) => {
+                    let result: Result<(), String> = match direction {
+                        SyncDirection::Upload => self
+                            .sessions
+                            .client
+                            .remove(entry)
+                            .map_err(|x| x.to_string()),
+                        SyncDirection::Download => {
+                            self.host.remove(entry).map_err(|x| x.to_string())
+                        }
+                    };
+                    match result {
+                        Ok(_) => self.log(
+                            LogLevel::Info,
+                            format!("Removed \"{}\"", entry.get_abs_path().display()),
+                        ),
+                        Err(err) => errors.push(format!(
+                            "Could not delete \"{}\": {}",
+                            entry.get_abs_path().display(),
+                            err
+                        )),
+                    }
+                }
+                SyncOp::Copy(FsEntry::Directory(_), dst) => {
+                    let result: Result<(), String> = match direction {
+                        SyncDirection::Upload => self
+                            .sessions
+                            .client
+                            .mkdir(dst.as_path())
+                            .map_err(|x| x.to_string()),
+                        SyncDirection::Download => self
+                            .host
+                            .mkdir_ex(dst.as_path(), true)
+                            .map_err(|x| x.to_string()),
+                    };
+                    match result {
+                        Ok(_) => self.log(
+                            LogLevel::Info,
+                            format!("Created directory \"{}\"", dst.display()),
+                        ),
+                        Err(err) => errors.push(format!(
+                            "Could not create directory \"{}\": {}",
+                            dst.display(),
+                            err
+                        )),
+                    }
+                }
+                SyncOp::Copy(FsEntry::File(file), dst) => {
+                    let result: Result<(), TransferErrorReason> = match direction {
+                        SyncDirection::Upload => {
+                            self.filetransfer_send_one(file, dst.as_path(), file.name.clone())
+                        }
+                        SyncDirection::Download => self
+                            .filetransfer_recv_one(dst.as_path(), file, file.name.clone())
+                            .map(|_| {
+                                // Preserve mtime, so that file is recognized as unchanged next time
+                                if let Err(err) =
+                                    self.host.set_mtime(dst.as_path(), file.last_change_time)
+                                {
+                                    self.log(
+                                        LogLevel::Warn,
+                                        format!(
+                                            "Could not set modification time for \"{}\": {}",
+                                            dst.display(),
+                                            err
+                                        ),
+                                    );
+                                }
+                            }),
+                    };
+                    if let Err(err) = result {
+                        errors.push(format!(
+                            "Could not copy \"{}\" to \"{}\": {}",
+                            file.abs_path.display(),
+                            dst.display(),
+                            err
+                        ));
+                    }
+                }
+                SyncOp::Skip(entry, dst) => self.log(
+                    LogLevel::Info,
+                    format!(
+                        "Skipped \"{}\": \"{}\" is up to date",
+                        entry.get_abs_path().display(),
+                        dst.display()
+                    ),
+                ),
+            }
+        }
+        // Umount progress bar
+        self.umount_progress_bar();
+        // Reload directories
+        self.reload_local_dir();
+        self.reload_remote_dir();
+        if self.transfer.aborted() {
+            errors.push(String::from("Mirror aborted"));
+        }
+        errors
+    }
+
     fn filetransfer_recv_recurse(
         &mut self,
         entry: &FsEntry,
@@ -646,9 +1274,22 @@ impl FileTransferActivity {
                     None => file.name.clone(),
                 };
                 local_file_path.push(local_file_name.as_str());
-                // Download file
-                if let Err(err) =
-                    self.filetransfer_recv_one(local_file_path.as_path(), file, file_name)
+                // Skip the file if unchanged on localhost; otherwise ask whether to replace it, if it exists
+                if self.skip_unchanged() && self.is_local_unchanged(file, local_file_path.as_path())
+                {
+                    self.filetransfer_skip(file, local_file_path.as_path(), "is unchanged");
+                } else if self.prompt_on_file_replace()
+                    && local_file_path.exists()
+                    && !self.filetransfer_should_replace(local_file_path.as_path())
+                {
+                    self.filetransfer_skip(file, local_file_path.as_path(), "already exists");
+                } else if let Err(err) =
+                    // Download file
+                    self.filetransfer_recv_one(
+                        local_file_path.as_path(),
+                        file,
+                        file_name,
+                    )
                 {
                     self.log_and_alert(
                         LogLevel::Error,
@@ -719,7 +1360,7 @@ impl FileTransferActivity {
                             format!("Created directory \"{}\"", local_dir_path.display()),
                         );
                         // Get files in dir
-                        match self.client.list_dir(dir.abs_path.as_path()) {
+                        match self.sessions.client.list_dir(dir.abs_path.as_path()) {
                             Ok(entries) => {
                                 // Iterate over files
                                 for entry in entries.iter() {
@@ -778,22 +1419,145 @@ impl FileTransferActivity {
 
     /// ### filetransfer_recv_one
     ///
-    /// Receive file from remote and write it to local path
+    /// Receive file from remote and write it to local path, then account it in the aggregate progress
     fn filetransfer_recv_one(
         &mut self,
         local: &Path,
         remote: &FsFile,
         file_name: String,
     ) -> Result<(), TransferErrorReason> {
-        // Try to open local file
-        match self.host.open_file_write(local) {
+        let result = self.filetransfer_retry(file_name.as_str(), |activity| {
+            activity.filetransfer_recv_stream(local, remote, file_name.clone())
+        });
+        self.transfer.file_done(remote.size);
+        match result.as_ref() {
+            Ok(_) => {
+                self.transfer.report.success(remote.size);
+                self.stats.downloaded(remote.size);
+            }
+            Err(err) => {
+                self.transfer
+                    .report
+                    .failure(remote.abs_path.as_path(), err.to_string());
+                self.stats.failed();
+            }
+        }
+        result
+    }
+
+    /// ### filetransfer_retry
+    ///
+    /// Run `transfer` until it succeeds, fails with a permanent error or the maximum amount of attempts is reached.
+    /// Attempts are spaced out by an exponential backoff; before each retry the session is reestablished
+    fn filetransfer_retry<F>(
+        &mut self,
+        file_name: &str,
+        mut transfer: F,
+    ) -> Result<(), TransferErrorReason>
+    where
+        F: FnMut(&mut Self) -> Result<(), TransferErrorReason>,
+    {
+        let max_attempts: usize = self.transfer_max_attempts();
+        let mut attempt: usize = 1;
+        loop {
+            let result: Result<(), TransferErrorReason> = match attempt {
+                1 => transfer(self),
+                _ => self.filetransfer_reconnect().and_then(|_| transfer(self)),
+            };
+            match result {
+                Err(err)
+                    if err.is_transient() && attempt < max_attempts && !self.transfer.aborted() =>
+                {
+                    let delay: Duration = backoff_delay(attempt);
+                    self.log(
+                        LogLevel::Warn,
+                        format!(
+                            "Transfer of \"{}\" failed: {}; retrying in {} seconds (attempt {} of {})",
+                            file_name,
+                            err,
+                            fmt_millis(delay),
+                            attempt + 1,
+                            max_attempts
+                        ),
+                    );
+                    self.transfer.file_retry();
+                    self.wait_before_retry(
+                        delay,
+                        format!(
+                            "Retrying \"{}\" ({}/{})…",
+                            file_name,
+                            attempt + 1,
+                            max_attempts
+                        ),
+                    );
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// ### filetransfer_reconnect
+    ///
+    /// Close the session with the remote, which may have been dropped, then connect again
+    /// and go back to the remote working directory
+    fn filetransfer_reconnect(&mut self) -> Result<(), TransferErrorReason> {
+        let params = self.context().ft_params().unwrap().clone();
+        let wrkdir: PathBuf = self.remote().wrkdir.clone();
+        if let Err(err) = self.sessions.client.disconnect() {
+            debug!("Could not close the session before reconnecting: {}", err);
+        }
+        self.sessions
+            .client
+            .connect(
+                params.address,
+                params.port,
+                params.username,
+                params.password,
+            )
+            .and_then(|_| self.sessions.client.change_dir(wrkdir.as_path()))
+            .map(|_| ())
+            .map_err(TransferErrorReason::FileTransferError)
+    }
+
+    /// ### filetransfer_recv_stream
+    ///
+    /// Receive file from remote and write it to local path
+    fn filetransfer_recv_stream(
+        &mut self,
+        local: &Path,
+        remote: &FsFile,
+        file_name: String,
+    ) -> Result<(), TransferErrorReason> {
+        // Try to open local file; if resuming, write at the end of the partial file
+        let offset: usize = self.download_resume_offset(local, remote);
+        let local_file = match offset {
+            0 => self.host.open_file_write(local),
+            _ => self.host.open_file_append(local),
+        };
+        match local_file {
             Ok(mut local_file) => {
                 // Download file from remote
-                match self.client.recv_file(remote) {
+                let rhnd = match offset {
+                    0 => self.sessions.client.recv_file(remote),
+                    _ => {
+                        self.log(
+                            LogLevel::Info,
+                            format!(
+                                "Resuming download of \"{}\" from {}",
+                                remote.abs_path.display(),
+                                fmt_bytes(offset as u64, self.config().get_size_unit())
+                            ),
+                        );
+                        self.sessions.client.recv_file_at(remote, offset as u64)
+                    }
+                };
+                match rhnd {
                     Ok(mut rhnd) => {
-                        let mut total_bytes_written: usize = 0;
-                        // Init transfer
-                        self.transfer.partial.init(remote.size);
+                        let mut total_bytes_written: usize = offset;
+                        // Init transfer; the resumed part is already done
+                        self.transfer.partial.init(remote.size - offset);
+                        self.transfer.full.update_progress(offset);
                         // Write local file
                         let mut last_progress_val: f64 = 0.0;
                         let mut last_input_event_fetch: Option<Instant> = None;
@@ -813,6 +1577,10 @@ impl FileTransferActivity {
                                 // Reset instant
                                 last_input_event_fetch = Some(Instant::now());
                             }
+                            // Wait while paused
+                            if self.transfer.paused() {
+                                self.wait_while_paused(format!("Downloading \"{}\"", file_name));
+                            }
                             // Read till you can
                             let mut buffer: [u8; 65536] = [0; 65536];
                             let delta: usize = match rhnd.read(&mut buffer) {
@@ -852,7 +1620,7 @@ impl FileTransferActivity {
                             }
                         }
                         // Finalize stream
-                        if let Err(err) = self.client.on_recv(rhnd) {
+                        if let Err(err) = self.sessions.client.on_recv(rhnd) {
                             self.log(
                                 LogLevel::Warn,
                                 format!("Could not finalize remote stream: \"{}\"", err),
@@ -889,7 +1657,10 @@ impl FileTransferActivity {
                                 remote.abs_path.display(),
                                 local.display(),
                                 fmt_millis(self.transfer.partial.started().elapsed()),
-                                ByteSize(self.transfer.partial.calc_bytes_per_second()),
+                                fmt_bytes(
+                                    self.transfer.partial.calc_bytes_per_second(),
+                                    self.config().get_size_unit()
+                                ),
                             ),
                         );
                     }
@@ -935,7 +1706,7 @@ impl FileTransferActivity {
         // Get current directory
         let prev_dir: PathBuf = self.remote().wrkdir.clone();
         // Change directory
-        match self.client.as_mut().change_dir(path) {
+        match self.sessions.client.as_mut().change_dir(path) {
             Ok(_) => {
                 self.log(
                     LogLevel::Info,
@@ -950,9 +1721,10 @@ impl FileTransferActivity {
             }
             Err(err) => {
                 // Report err
-                self.log_and_alert(
+                self.log_and_alert_remote(
                     LogLevel::Error,
-                    format!("Could not change working directory: {}", err),
+                    String::from("Could not change working directory"),
+                    &err,
                 );
             }
         }
@@ -989,63 +1761,210 @@ impl FileTransferActivity {
         }
     }
 
-    // -- transfer sizes
+    /// ### wait_while_paused
+    ///
+    /// Block the transfer while it's paused, still handling input events,
+    /// so that the user can resume or abort it
+    fn wait_while_paused(&mut self, title: String) {
+        self.update_progress_bar(title.clone());
+        self.view();
+        while self.transfer.paused() && !self.transfer.aborted() {
+            self.wait_input_event(Duration::from_millis(250));
+        }
+        self.update_progress_bar(title);
+        self.view();
+    }
 
-    /// ### get_total_transfer_size_local
+    /// ### wait_before_retry
     ///
-    /// Get total size of transfer for localhost
-    fn get_total_transfer_size_local(&mut self, entry: &FsEntry) -> usize {
-        match entry {
-            FsEntry::File(file) => file.size,
-            FsEntry::Directory(dir) => {
-                // List dir
-                match self.host.scan_dir(dir.abs_path.as_path()) {
-                    Ok(files) => files
-                        .iter()
-                        .map(|x| self.get_total_transfer_size_local(x))
-                        .sum(),
-                    Err(err) => {
-                        self.log(
-                            LogLevel::Error,
-                            format!(
-                                "Could not list directory {}: {}",
-                                dir.abs_path.display(),
-                                err
-                            ),
-                        );
-                        0
-                    }
-                }
+    /// Wait `delay` before retrying a transfer, still handling input events, so that the user can abort it
+    fn wait_before_retry(&mut self, delay: Duration, title: String) {
+        self.update_progress_bar(title);
+        self.view();
+        let started: Instant = Instant::now();
+        while started.elapsed() < delay && !self.transfer.aborted() {
+            self.wait_input_event(
+                delay
+                    .saturating_sub(started.elapsed())
+                    .min(Duration::from_millis(250)),
+            );
+        }
+    }
+
+    /// ### filetransfer_should_replace
+    ///
+    /// Ask the user whether the existing file at `dest` must be replaced,
+    /// unless a decision has already been taken for all the files of the current transfer
+    pub(super) fn filetransfer_should_replace(&mut self, dest: &Path) -> bool {
+        if let Some(decision) = self.transfer.replace.decision() {
+            return decision;
+        }
+        let file_name: String = dest
+            .file_name()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_else(|| dest.display().to_string());
+        self.mount_radio_replace(file_name.as_str());
+        loop {
+            self.view();
+            self.wait_input_event(Duration::from_millis(250));
+            if let Some(answer) = self.transfer.replace.take_answer() {
+                return answer;
             }
         }
     }
 
+    /// ### filetransfer_skip
+    ///
+    /// Skip the transfer of `src` onto `dest`, logging the `reason` why `dest` is left untouched
+    fn filetransfer_skip(&mut self, src: &FsFile, dest: &Path, reason: &str) {
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Skipped \"{}\": \"{}\" {}",
+                src.abs_path.display(),
+                dest.display(),
+                reason
+            ),
+        );
+        self.transfer.file_done(src.size);
+        self.transfer.report.skip();
+    }
+
+    /// ### is_remote_unchanged
+    ///
+    /// Returns whether the remote file at `dest` exists and has the same size and mtime of `src`
+    fn is_remote_unchanged(&mut self, src: &FsFile, dest: &Path) -> bool {
+        match self.sessions.client.stat(dest) {
+            Ok(FsEntry::File(dest)) => !should_copy(src, Some(&dest)),
+            _ => false,
+        }
+    }
+
+    /// ### is_local_unchanged
+    ///
+    /// Returns whether the local file at `dest` exists and has the same size and mtime of `src`
+    fn is_local_unchanged(&self, src: &FsFile, dest: &Path) -> bool {
+        match self.host.stat(dest) {
+            Ok(FsEntry::File(dest)) => !should_copy(src, Some(&dest)),
+            _ => false,
+        }
+    }
+
+    /// ### log_transfer_report
+    ///
+    /// Log the summary of the last transfer, followed by the files which couldn't be transferred
+    fn log_transfer_report(&mut self) {
+        let level: LogLevel = match self.transfer.report.failed().is_empty() {
+            true => LogLevel::Info,
+            false => LogLevel::Warn,
+        };
+        let summary: String = self.transfer.report.to_string();
+        self.log(level, summary);
+        let failed: Vec<String> = self
+            .transfer
+            .report
+            .failed()
+            .iter()
+            .map(|(path, err)| format!("Failed to transfer \"{}\": {}", path.display(), err))
+            .collect();
+        for msg in failed.into_iter() {
+            self.log(LogLevel::Error, msg);
+        }
+    }
+
+    /// ### ring_transfer_bell
+    ///
+    /// Ring the terminal bell once a transfer which took `elapsed` has completed, if enabled in configuration.
+    /// Aborted transfers never ring; transfers with failed files ring as failures
+    fn ring_transfer_bell(&mut self, elapsed: Duration, ok: bool) {
+        if self.transfer.aborted() {
+            return;
+        }
+        let success: bool = ok && self.transfer.report.failed().is_empty();
+        if let Some(bells) = transfer_bell(
+            self.config().get_transfer_bell(),
+            self.config().get_transfer_bell_threshold(),
+            elapsed,
+            success,
+        ) {
+            self.context_mut().ring_bell(bells);
+        }
+    }
+
+    // -- transfer sizes
+
+    /// ### get_total_transfer_size_local
+    ///
+    /// Get total size of transfer for localhost, along with the amount of files to transfer.
+    /// Returns `None` if the scan has been aborted by the user
+    fn get_total_transfer_size_local(&mut self, entries: &[FsEntry]) -> Option<(usize, usize)> {
+        self.get_total_transfer_size(entries, |activity, dir| {
+            activity.host.scan_dir(dir).map_err(|e| e.to_string())
+        })
+    }
+
     /// ### get_total_transfer_size_remote
     ///
-    /// Get total size of transfer for remote host
-    fn get_total_transfer_size_remote(&mut self, entry: &FsEntry) -> usize {
-        match entry {
-            FsEntry::File(file) => file.size,
-            FsEntry::Directory(dir) => {
-                // List directory
-                match self.client.list_dir(dir.abs_path.as_path()) {
-                    Ok(files) => files
-                        .iter()
-                        .map(|x| self.get_total_transfer_size_remote(x))
-                        .sum(),
-                    Err(err) => {
-                        self.log(
-                            LogLevel::Error,
-                            format!(
-                                "Could not list directory {}: {}",
-                                dir.abs_path.display(),
-                                err
-                            ),
-                        );
-                        0
-                    }
+    /// Get total size of transfer for remote host, along with the amount of files to transfer.
+    /// Returns `None` if the scan has been aborted by the user
+    fn get_total_transfer_size_remote(&mut self, entries: &[FsEntry]) -> Option<(usize, usize)> {
+        self.get_total_transfer_size(entries, |activity, dir| {
+            activity
+                .sessions
+                .client
+                .list_dir(dir)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    /// ### get_total_transfer_size
+    ///
+    /// Walk `entries`, listing directories with `list_dir`, to get the total size of transfer.
+    /// The progress of the scan is reported on the progress bar, while input events are still handled,
+    /// so that the user can abort it
+    fn get_total_transfer_size<F>(
+        &mut self,
+        entries: &[FsEntry],
+        mut list_dir: F,
+    ) -> Option<(usize, usize)>
+    where
+        F: FnMut(&mut Self, &Path) -> Result<Vec<FsEntry>, String>,
+    {
+        let mut scan: SizeScan = SizeScan::new(entries);
+        let mut last_redraw: Instant = Instant::now();
+        let completed: bool = scan.walk(
+            self,
+            |this, dir| match list_dir(this, dir) {
+                Ok(files) => files,
+                Err(err) => {
+                    this.log(
+                        LogLevel::Error,
+                        format!("Could not list directory {}: {}", dir.display(), err),
+                    );
+                    Vec::new()
                 }
-            }
+            },
+            |this, scan, dir| {
+                // Draw and handle input only once in a while (performance improvement)
+                if last_redraw.elapsed() >= Duration::from_millis(100) {
+                    this.update_scan_progress(scan, dir);
+                    this.view();
+                    this.read_input_event();
+                    last_redraw = Instant::now();
+                }
+                !this.transfer.aborted()
+            },
+        );
+        if !completed {
+            self.log(
+                LogLevel::Warn,
+                format!(
+                    "Transfer aborted while computing its size ({} files found so far)",
+                    scan.files()
+                ),
+            );
+            return None;
         }
+        Some((scan.size(), scan.files()))
     }
 }
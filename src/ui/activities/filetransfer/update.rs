@@ -27,24 +27,38 @@
  */
 // locals
 use super::{
-    actions::SelectedEntry, browser::FileExplorerTab, FileTransferActivity, LogLevel,
-    COMPONENT_EXPLORER_FIND, COMPONENT_EXPLORER_LOCAL, COMPONENT_EXPLORER_REMOTE,
+    actions::{mark::MarkOp, scroll::ScrollOp, SelectedEntry},
+    browser::{placeholder, FileExplorerTab},
+    lib::logview::LogFilter,
+    lib::replace::ReplaceAnswer,
+    lib::scan::SizeScan,
+    FileTransferActivity, LogLevel, COMPONENT_BREADCRUMB, COMPONENT_EXPLORER_FIND,
+    COMPONENT_EXPLORER_LOCAL, COMPONENT_EXPLORER_REMOTE, COMPONENT_INPUT_CHMOD,
     COMPONENT_INPUT_COPY, COMPONENT_INPUT_EXEC, COMPONENT_INPUT_FIND, COMPONENT_INPUT_GOTO,
-    COMPONENT_INPUT_MKDIR, COMPONENT_INPUT_NEWFILE, COMPONENT_INPUT_OPEN_WITH,
-    COMPONENT_INPUT_RENAME, COMPONENT_INPUT_SAVEAS, COMPONENT_LIST_FILEINFO, COMPONENT_LOG_BOX,
-    COMPONENT_PROGRESS_BAR_FULL, COMPONENT_PROGRESS_BAR_PARTIAL, COMPONENT_RADIO_DELETE,
-    COMPONENT_RADIO_DISCONNECT, COMPONENT_RADIO_QUIT, COMPONENT_RADIO_SORTING,
-    COMPONENT_TEXT_ERROR, COMPONENT_TEXT_FATAL, COMPONENT_TEXT_HELP,
+    COMPONENT_INPUT_MKDIR, COMPONENT_INPUT_MOVE, COMPONENT_INPUT_NEWFILE,
+    COMPONENT_INPUT_OPEN_WITH, COMPONENT_INPUT_RENAME, COMPONENT_INPUT_SAVEAS,
+    COMPONENT_INPUT_SYMLINK, COMPONENT_LIST_COMPARE, COMPONENT_LIST_EXEC_OUTPUT,
+    COMPONENT_LIST_FILEINFO, COMPONENT_LIST_PREVIEW, COMPONENT_LIST_STATS, COMPONENT_LOG_BOX,
+    COMPONENT_LOG_VIEWER, COMPONENT_PROGRESS_BAR_FULL, COMPONENT_PROGRESS_BAR_PARTIAL,
+    COMPONENT_RADIO_DELETE, COMPONENT_RADIO_DISCONNECT, COMPONENT_RADIO_HOST_KEY,
+    COMPONENT_RADIO_QUIT, COMPONENT_RADIO_REPLACE, COMPONENT_RADIO_SIZE_LIMIT,
+    COMPONENT_RADIO_SORTING, COMPONENT_TEXT_ERROR, COMPONENT_TEXT_FATAL, COMPONENT_TEXT_HELP,
+    COMPONENT_TEXT_INFO, COMPONENT_TEXT_WAIT,
 };
+use crate::filetransfer::read_only::Operation;
 use crate::fs::explorer::FileSorting;
 use crate::fs::FsEntry;
-use crate::ui::components::{file_list::FileListPropsBuilder, logbox::LogboxPropsBuilder};
+use crate::ui::components::{
+    file_list::FileListPropsBuilder, logbox::LogboxPropsBuilder,
+    progress_bar::ProgressBarPropsBuilder,
+};
 use crate::ui::keymap::*;
-use crate::utils::fmt::fmt_path_elide_ex;
+use crate::utils::fmt::{fmt_bytes, fmt_path_elide_ex};
 // externals
+use std::path::{Path, PathBuf};
 use tuirealm::{
-    components::progress_bar::ProgressBarPropsBuilder,
-    props::{PropsBuilder, TableBuilder, TextSpan, TextSpanBuilder},
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    props::{PropsBuilder, Table, TableBuilder, TextSpan, TextSpanBuilder},
     tui::style::Color,
     Msg, Payload, Update, Value,
 };
@@ -57,6 +71,10 @@ impl Update for FileTransferActivity {
     /// Update auth activity model based on msg
     /// The function exits when returns None
     fn update(&mut self, msg: Option<(String, Msg)>) -> Option<(String, Msg)> {
+        let msg: Option<(String, Msg)> = msg.map(|(id, msg)| {
+            let msg: Msg = self.apply_keybindings(id.as_str(), msg);
+            (id, msg)
+        });
         let ref_msg: Option<(&str, &Msg)> = msg.as_ref().map(|(s, msg)| (s.as_str(), msg));
         // Match msg
         match ref_msg {
@@ -112,13 +130,19 @@ impl Update for FileTransferActivity {
                 }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_I) => {
                     if let SelectedEntry::One(file) = self.get_local_selected_entries() {
-                        self.mount_file_info(&file);
+                        let disk_usage = self.host.disk_usage(self.local().wrkdir.as_path());
+                        self.mount_file_info(&file, disk_usage);
                     }
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_V) => {
+                    self.action_local_preview();
+                    None
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_L) => {
-                    // Reload directory
+                    // Reload directory and clear selection
                     self.reload_local_dir();
+                    self.local_mut().clear_marks();
                     // Reload file list component
                     self.update_local_filelist()
                 }
@@ -127,6 +151,10 @@ impl Update for FileTransferActivity {
                     // Reload file list component
                     self.update_local_filelist()
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_T) => {
+                    self.action_local_mirror();
+                    self.update_remote_filelist()
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_U) => {
                     self.action_go_to_local_upper_dir(false);
                     if self.browser.sync_browsing {
@@ -186,13 +214,20 @@ impl Update for FileTransferActivity {
                 }
                 (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_I) => {
                     if let SelectedEntry::One(file) = self.get_remote_selected_entries() {
-                        self.mount_file_info(&file);
+                        let wrkdir: PathBuf = self.remote().wrkdir.clone();
+                        let disk_usage = self.sessions.client.disk_usage(wrkdir.as_path()).ok();
+                        self.mount_file_info(&file, disk_usage);
                     }
                     None
                 }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_V) => {
+                    self.action_remote_preview();
+                    None
+                }
                 (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_L) => {
-                    // Reload directory
+                    // Reload directory and clear selection
                     self.reload_remote_dir();
+                    self.remote_mut().clear_marks();
                     // Reload file list component
                     self.update_remote_filelist()
                 }
@@ -202,6 +237,14 @@ impl Update for FileTransferActivity {
                     // Reload file list component
                     self.update_remote_filelist()
                 }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_P) => {
+                    self.action_remote_pull();
+                    self.update_local_filelist()
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_T) => {
+                    self.action_remote_mirror();
+                    self.update_local_filelist()
+                }
                 (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_U) => {
                     self.action_go_to_remote_upper_dir(false);
                     if self.browser.sync_browsing {
@@ -217,6 +260,24 @@ impl Update for FileTransferActivity {
                     self.mount_file_sorting();
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_GREATER)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_GREATER) => {
+                    self.action_cycle_sort(false);
+                    self.update_browser_file_list()
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_TILDE) => {
+                    self.action_go_home();
+                    self.update_local_filelist()
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_TILDE) => {
+                    self.action_go_home();
+                    self.update_remote_filelist()
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_LESS)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_LESS) => {
+                    self.action_cycle_sort(true);
+                    self.update_browser_file_list()
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_C)
                 | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_C) => {
                     self.mount_copy();
@@ -237,20 +298,65 @@ impl Update for FileTransferActivity {
                     self.mount_goto();
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_J)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_J) => {
+                    self.mount_breadcrumb();
+                    None
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_H)
                 | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_H) => {
                     self.mount_help();
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_K)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_K) => {
+                    self.mount_symlink();
+                    None
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_N)
                 | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_N) => {
                     self.mount_newfile();
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_E)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_E) => {
+                    self.action_compare_dirs();
+                    None
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_G)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_G) => {
+                    self.mount_session_stats();
+                    None
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_R)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_R) => {
+                    self.mount_chmod();
+                    None
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_D) => {
+                    self.action_local_duplicate();
+                    self.update_local_filelist()
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_D) => {
+                    self.action_remote_duplicate();
+                    self.update_remote_filelist()
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_N) => {
+                    self.action_open_session();
+                    None
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_S) => {
+                    self.action_next_session();
+                    None
+                }
+                (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_W) => {
+                    self.action_close_session();
+                    None
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_Q)
                 | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_Q)
                 | (COMPONENT_LOG_BOX, &MSG_KEY_CHAR_Q) => {
-                    self.mount_quit();
+                    self.quit();
                     None
                 }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_R)
@@ -292,6 +398,25 @@ impl Update for FileTransferActivity {
                     self.mount_exec();
                     None
                 }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_Z)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_Z) => {
+                    self.toggle_dry_run();
+                    // Update status bar
+                    self.refresh_remote_status_bar();
+                    None
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_U)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_U) => {
+                    self.toggle_skip_unchanged();
+                    // Update status bar
+                    self.refresh_remote_status_bar();
+                    None
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_X)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_X) => {
+                    self.mount_move();
+                    None
+                }
                 (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_Y)
                 | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_Y) => {
                     // Toggle browser sync
@@ -315,6 +440,46 @@ impl Update for FileTransferActivity {
                     self.mount_radio_delete();
                     None
                 }
+                // -- scroll
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_HOME)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_HOME)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_HOME) => {
+                    self.update_explorer_index(ScrollOp::First)
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_END)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_END)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_END) => {
+                    self.update_explorer_index(ScrollOp::Last)
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_PAGE_UP)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_PAGE_UP)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_PAGE_UP) => {
+                    self.update_explorer_index(ScrollOp::PageUp)
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_PAGE_DOWN)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_PAGE_DOWN)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_PAGE_DOWN) => {
+                    self.update_explorer_index(ScrollOp::PageDown)
+                }
+                // -- marks
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CHAR_M)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CHAR_M)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_CHAR_M) => {
+                    self.action_mark(MarkOp::Toggle);
+                    self.update_browser_file_list()
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_A)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_A)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_CTRL_A) => {
+                    self.action_mark(MarkOp::All);
+                    self.update_browser_file_list()
+                }
+                (COMPONENT_EXPLORER_LOCAL, &MSG_KEY_CTRL_T)
+                | (COMPONENT_EXPLORER_REMOTE, &MSG_KEY_CTRL_T)
+                | (COMPONENT_EXPLORER_FIND, &MSG_KEY_CTRL_T) => {
+                    self.action_mark(MarkOp::Invert);
+                    self.update_browser_file_list()
+                }
                 // -- find result explorer
                 (COMPONENT_EXPLORER_FIND, &MSG_KEY_ESC) => {
                     // Umount find
@@ -325,7 +490,7 @@ impl Update for FileTransferActivity {
                 }
                 (COMPONENT_EXPLORER_FIND, Msg::OnSubmit(_)) => {
                     // Find changedir
-                    self.action_find_changedir();
+                    let entry: Option<PathBuf> = self.action_find_changedir();
                     // Umount find
                     self.umount_find();
                     // Finalize find
@@ -335,6 +500,11 @@ impl Update for FileTransferActivity {
                         FileExplorerTab::Local => self.update_local_filelist(),
                         FileExplorerTab::Remote => self.update_remote_filelist(),
                         _ => None,
+                    };
+                    // Highlight entry
+                    match entry {
+                        Some(entry) => self.highlight_explorer_entry(entry.as_path()),
+                        None => None,
                     }
                 }
                 (COMPONENT_EXPLORER_FIND, &MSG_KEY_SPACE) => {
@@ -359,6 +529,25 @@ impl Update for FileTransferActivity {
                     self.view.blur(); // Blur log box
                     None
                 }
+                (COMPONENT_LOG_BOX, &MSG_KEY_ENTER) => {
+                    self.mount_log_viewer();
+                    None
+                }
+                // -- log viewer
+                (COMPONENT_LOG_VIEWER, &MSG_KEY_CHAR_F) => {
+                    self.log_filter = self.log_filter.next();
+                    self.mount_log_viewer();
+                    None
+                }
+                (COMPONENT_LOG_VIEWER, &MSG_KEY_CHAR_C) => {
+                    self.action_copy_log();
+                    None
+                }
+                (COMPONENT_LOG_VIEWER, &MSG_KEY_ENTER) | (COMPONENT_LOG_VIEWER, &MSG_KEY_ESC) => {
+                    self.umount_log_viewer();
+                    None
+                }
+                (COMPONENT_LOG_VIEWER, _) => None,
                 // -- copy popup
                 (COMPONENT_INPUT_COPY, &MSG_KEY_ESC) => {
                     self.umount_copy();
@@ -382,6 +571,29 @@ impl Update for FileTransferActivity {
                     }
                 }
                 (COMPONENT_INPUT_COPY, _) => None,
+                // -- move popup
+                (COMPONENT_INPUT_MOVE, &MSG_KEY_ESC) => {
+                    self.umount_move();
+                    None
+                }
+                (COMPONENT_INPUT_MOVE, Msg::OnSubmit(Payload::One(Value::Str(input)))) => {
+                    // Move file
+                    self.umount_move();
+                    self.mount_blocking_wait("Moving file(s)…");
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.action_local_move(input.to_string()),
+                        FileExplorerTab::Remote => self.action_remote_move(input.to_string()),
+                        _ => panic!("Found tab doesn't support MOVE"),
+                    }
+                    self.umount_wait();
+                    // Reload files
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.update_local_filelist(),
+                        FileExplorerTab::Remote => self.update_remote_filelist(),
+                        _ => None,
+                    }
+                }
+                (COMPONENT_INPUT_MOVE, _) => None,
                 // -- exec popup
                 (COMPONENT_INPUT_EXEC, &MSG_KEY_ESC) => {
                     self.umount_exec();
@@ -405,6 +617,26 @@ impl Update for FileTransferActivity {
                     }
                 }
                 (COMPONENT_INPUT_EXEC, _) => None,
+                // -- exec output
+                (COMPONENT_LIST_EXEC_OUTPUT, &MSG_KEY_ENTER)
+                | (COMPONENT_LIST_EXEC_OUTPUT, &MSG_KEY_ESC) => {
+                    self.umount_exec_output();
+                    None
+                }
+                (COMPONENT_LIST_EXEC_OUTPUT, _) => None,
+                // -- compare
+                (COMPONENT_LIST_COMPARE, &MSG_KEY_ENTER)
+                | (COMPONENT_LIST_COMPARE, &MSG_KEY_ESC) => {
+                    self.umount_compare();
+                    None
+                }
+                (COMPONENT_LIST_COMPARE, _) => None,
+                // -- session stats
+                (COMPONENT_LIST_STATS, &MSG_KEY_ENTER) | (COMPONENT_LIST_STATS, &MSG_KEY_ESC) => {
+                    self.umount_session_stats();
+                    None
+                }
+                (COMPONENT_LIST_STATS, _) => None,
                 // -- find popup
                 (COMPONENT_INPUT_FIND, &MSG_KEY_ESC) => {
                     self.umount_find_input();
@@ -413,33 +645,50 @@ impl Update for FileTransferActivity {
                 (COMPONENT_INPUT_FIND, Msg::OnSubmit(Payload::One(Value::Str(input)))) => {
                     self.umount_find_input();
                     // Find
-                    let res: Result<Vec<FsEntry>, String> = match self.browser.tab() {
+                    let files: Vec<FsEntry> = match self.browser.tab() {
                         FileExplorerTab::Local => self.action_local_find(input.to_string()),
                         FileExplorerTab::Remote => self.action_remote_find(input.to_string()),
                         _ => panic!("Trying to search for files, while already in a find result"),
                     };
-                    // Match result
-                    match res {
-                        Err(err) => {
-                            // Mount error
-                            self.mount_error(err.as_str());
-                        }
-                        Ok(files) => {
-                            // Create explorer and load files
-                            self.browser.set_found(files);
-                            // Mount result widget
-                            self.mount_find(input);
-                            self.update_find_list();
-                            // Initialize tab
-                            self.browser.change_tab(match self.browser.tab() {
-                                FileExplorerTab::Local => FileExplorerTab::FindLocal,
-                                FileExplorerTab::Remote => FileExplorerTab::FindRemote,
-                                _ => FileExplorerTab::FindLocal,
-                            });
-                        }
-                    }
+                    // Create explorer and load files; results are displayed relative to the searched directory
+                    let root: PathBuf = match self.browser.tab() {
+                        FileExplorerTab::Remote => self.remote().wrkdir.clone(),
+                        _ => self.local().wrkdir.clone(),
+                    };
+                    self.browser.set_found(files, root.as_path());
+                    // Mount result widget
+                    self.mount_find(input);
+                    self.update_find_list();
+                    // Initialize tab
+                    self.browser.change_tab(match self.browser.tab() {
+                        FileExplorerTab::Local => FileExplorerTab::FindLocal,
+                        FileExplorerTab::Remote => FileExplorerTab::FindRemote,
+                        _ => FileExplorerTab::FindLocal,
+                    });
                     None
                 }
+                // -- breadcrumb
+                (COMPONENT_BREADCRUMB, &MSG_KEY_ESC) => {
+                    self.umount_breadcrumb();
+                    None
+                }
+                (COMPONENT_BREADCRUMB, Msg::OnSubmit(Payload::One(Value::Str(path)))) => {
+                    let path: PathBuf = PathBuf::from(path.as_str());
+                    self.umount_breadcrumb();
+                    if let Err(err) = self.change_dir_absolute(self.browser.tab(), path.as_path()) {
+                        self.log_and_alert(
+                            LogLevel::Error,
+                            format!("Could not change working directory: {}", err),
+                        );
+                    }
+                    // Reload files
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.update_local_filelist(),
+                        FileExplorerTab::Remote => self.update_remote_filelist(),
+                        _ => None,
+                    }
+                }
+                (COMPONENT_BREADCRUMB, _) => None,
                 // -- goto popup
                 (COMPONENT_INPUT_GOTO, &MSG_KEY_ESC) => {
                     self.umount_goto();
@@ -473,6 +722,26 @@ impl Update for FileTransferActivity {
                     }
                 }
                 (COMPONENT_INPUT_GOTO, _) => None,
+                // -- change mode
+                (COMPONENT_INPUT_CHMOD, &MSG_KEY_ESC) => {
+                    self.umount_chmod();
+                    None
+                }
+                (COMPONENT_INPUT_CHMOD, Msg::OnSubmit(Payload::One(Value::Str(input)))) => {
+                    self.umount_chmod();
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.action_local_chmod(input.to_string()),
+                        FileExplorerTab::Remote => self.action_remote_chmod(input.to_string()),
+                        _ => panic!("Found tab doesn't support CHMOD"),
+                    }
+                    // Reload files
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.update_local_filelist(),
+                        FileExplorerTab::Remote => self.update_remote_filelist(),
+                        _ => None,
+                    }
+                }
+                (COMPONENT_INPUT_CHMOD, _) => None,
                 // -- make directory
                 (COMPONENT_INPUT_MKDIR, &MSG_KEY_ESC) => {
                     self.umount_mkdir();
@@ -499,12 +768,13 @@ impl Update for FileTransferActivity {
                     None
                 }
                 (COMPONENT_INPUT_NEWFILE, Msg::OnSubmit(Payload::One(Value::Str(input)))) => {
+                    // Umount input before creating the file, since the user may be asked whether to replace it
+                    self.umount_newfile();
                     match self.browser.tab() {
                         FileExplorerTab::Local => self.action_local_newfile(input.to_string()),
                         FileExplorerTab::Remote => self.action_remote_newfile(input.to_string()),
                         _ => panic!("Found tab doesn't support NEWFILE"),
                     }
-                    self.umount_newfile();
                     // Reload files
                     match self.browser.tab() {
                         FileExplorerTab::Local => self.update_local_filelist(),
@@ -577,6 +847,26 @@ impl Update for FileTransferActivity {
                     }
                 }
                 (COMPONENT_INPUT_SAVEAS, _) => None,
+                // -- symlink
+                (COMPONENT_INPUT_SYMLINK, &MSG_KEY_ESC) => {
+                    self.umount_symlink();
+                    None
+                }
+                (COMPONENT_INPUT_SYMLINK, Msg::OnSubmit(Payload::One(Value::Str(input)))) => {
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.action_local_symlink(input.to_string()),
+                        FileExplorerTab::Remote => self.action_remote_symlink(input.to_string()),
+                        _ => panic!("Found tab doesn't support SYMLINK"),
+                    }
+                    self.umount_symlink();
+                    // Reload files
+                    match self.browser.tab() {
+                        FileExplorerTab::Local => self.update_local_filelist(),
+                        FileExplorerTab::Remote => self.update_remote_filelist(),
+                        _ => None,
+                    }
+                }
+                (COMPONENT_INPUT_SYMLINK, _) => None,
                 // -- fileinfo
                 (COMPONENT_LIST_FILEINFO, &MSG_KEY_ENTER)
                 | (COMPONENT_LIST_FILEINFO, &MSG_KEY_ESC) => {
@@ -584,6 +874,13 @@ impl Update for FileTransferActivity {
                     None
                 }
                 (COMPONENT_LIST_FILEINFO, _) => None,
+                // -- preview
+                (COMPONENT_LIST_PREVIEW, &MSG_KEY_ENTER)
+                | (COMPONENT_LIST_PREVIEW, &MSG_KEY_ESC) => {
+                    self.umount_preview();
+                    None
+                }
+                (COMPONENT_LIST_PREVIEW, _) => None,
                 // -- delete
                 (COMPONENT_RADIO_DELETE, &MSG_KEY_ESC)
                 | (COMPONENT_RADIO_DELETE, Msg::OnSubmit(Payload::One(Value::Usize(1)))) => {
@@ -597,25 +894,22 @@ impl Update for FileTransferActivity {
                     match self.browser.tab() {
                         FileExplorerTab::Local => self.action_local_delete(),
                         FileExplorerTab::Remote => self.action_remote_delete(),
+                        FileExplorerTab::FindLocal | FileExplorerTab::FindRemote
+                            if self.deny_read_only(Operation::Delete) => {}
                         FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => {
                             // Get entry
                             self.action_find_delete();
-                            // Delete entries
-                            match self.view.get_state(COMPONENT_EXPLORER_FIND) {
-                                Some(Payload::One(Value::Usize(idx))) => {
-                                    // Reload entries
-                                    self.found_mut().unwrap().del_entry(idx);
-                                }
-                                Some(Payload::Vec(values)) => {
-                                    values
-                                        .iter()
-                                        .map(|x| match x {
-                                            Value::Usize(v) => *v,
-                                            _ => 0,
-                                        })
-                                        .for_each(|x| self.found_mut().unwrap().del_entry(x));
+                            // Delete entries; starting from the last one, so that indexes are kept
+                            let mut entries: Vec<usize> = self.found().unwrap().marks();
+                            if entries.is_empty() {
+                                if let Some(Payload::One(Value::Usize(idx))) =
+                                    self.view.get_state(COMPONENT_EXPLORER_FIND)
+                                {
+                                    entries.push(idx);
                                 }
-                                _ => {}
+                            }
+                            for idx in entries.into_iter().rev() {
+                                self.found_mut().unwrap().del_entry(idx);
                             }
                             self.update_find_list();
                         }
@@ -654,6 +948,46 @@ impl Update for FileTransferActivity {
                     None
                 }
                 (COMPONENT_RADIO_QUIT, _) => None,
+                // -- host key
+                (COMPONENT_RADIO_HOST_KEY, Msg::OnSubmit(Payload::One(Value::Usize(0)))) => {
+                    self.umount_host_key();
+                    // Client will reconnect on next draw
+                    self.trust_host_key();
+                    None
+                }
+                (COMPONENT_RADIO_HOST_KEY, &MSG_KEY_ESC)
+                | (COMPONENT_RADIO_HOST_KEY, Msg::OnSubmit(_)) => {
+                    self.umount_host_key();
+                    self.session_failed("Host key verification failed");
+                    None
+                }
+                (COMPONENT_RADIO_HOST_KEY, _) => None,
+                // -- replace
+                (COMPONENT_RADIO_REPLACE, &MSG_KEY_ESC) => {
+                    self.umount_radio_replace();
+                    self.transfer.replace.answer(ReplaceAnswer::No);
+                    None
+                }
+                (COMPONENT_RADIO_REPLACE, Msg::OnSubmit(Payload::One(Value::Usize(choice)))) => {
+                    self.umount_radio_replace();
+                    self.transfer
+                        .replace
+                        .answer(ReplaceAnswer::from_choice(*choice));
+                    None
+                }
+                (COMPONENT_RADIO_REPLACE, _) => None,
+                // -- size limit
+                (COMPONENT_RADIO_SIZE_LIMIT, &MSG_KEY_ESC) => {
+                    self.umount_radio_size_limit();
+                    self.size_override = Some(false);
+                    None
+                }
+                (COMPONENT_RADIO_SIZE_LIMIT, Msg::OnSubmit(Payload::One(Value::Usize(choice)))) => {
+                    self.umount_radio_size_limit();
+                    self.size_override = Some(*choice == 0);
+                    None
+                }
+                (COMPONENT_RADIO_SIZE_LIMIT, _) => None,
                 // -- sorting
                 (COMPONENT_RADIO_SORTING, &MSG_KEY_ESC)
                 | (COMPONENT_RADIO_SORTING, Msg::OnSubmit(_)) => {
@@ -693,6 +1027,12 @@ impl Update for FileTransferActivity {
                     None
                 }
                 (COMPONENT_TEXT_ERROR, _) => None,
+                // -- info
+                (COMPONENT_TEXT_INFO, &MSG_KEY_ESC) | (COMPONENT_TEXT_INFO, &MSG_KEY_ENTER) => {
+                    self.umount_info();
+                    None
+                }
+                (COMPONENT_TEXT_INFO, _) => None,
                 // -- fatal
                 (COMPONENT_TEXT_FATAL, &MSG_KEY_ESC) | (COMPONENT_TEXT_FATAL, &MSG_KEY_ENTER) => {
                     self.exit_reason = Some(super::ExitReason::Disconnect);
@@ -705,12 +1045,30 @@ impl Update for FileTransferActivity {
                     None
                 }
                 (COMPONENT_TEXT_HELP, _) => None,
+                // -- wait
+                (COMPONENT_TEXT_WAIT, &MSG_KEY_ESC) | (COMPONENT_TEXT_WAIT, &MSG_KEY_CTRL_C)
+                    if self.connection.is_some() =>
+                {
+                    // Cancel the connection being established
+                    self.cancel_connection();
+                    None
+                }
+                (COMPONENT_TEXT_WAIT, &MSG_KEY_CTRL_C) => {
+                    // Cancel the pending operation (e.g. a search)
+                    self.transfer.abort();
+                    None
+                }
                 // -- progress bar
                 (COMPONENT_PROGRESS_BAR_PARTIAL, &MSG_KEY_CTRL_C) => {
                     // Set transfer aborted to True
                     self.transfer.abort();
                     None
                 }
+                (COMPONENT_PROGRESS_BAR_PARTIAL, &MSG_KEY_CTRL_P) => {
+                    // Pause or resume transfer
+                    self.transfer.toggle_pause();
+                    None
+                }
                 (COMPONENT_PROGRESS_BAR_PARTIAL, _) => None,
                 // -- fallback
                 (_, _) => None, // Nothing to do
@@ -753,6 +1111,8 @@ impl FileTransferActivity {
                 // Update
                 let props = FileListPropsBuilder::from(props)
                     .with_files(Some(hostname), files)
+                    .with_marks(self.local().marks())
+                    .with_placeholder(placeholder(self.local()))
                     .build();
                 // Update
                 self.view.update(super::COMPONENT_EXPLORER_LOCAL, props)
@@ -761,6 +1121,44 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### update_explorer_index
+    ///
+    /// Move the cursor of the current explorer as described by `op`
+    fn update_explorer_index(&mut self, op: ScrollOp) -> Option<(String, Msg)> {
+        let component: &str = self.explorer_component();
+        match (self.action_scroll(op), self.view.get_props(component)) {
+            (Some(idx), Some(props)) => {
+                let props = FileListPropsBuilder::from(props).with_index(idx).build();
+                self.view.update(component, props)
+            }
+            _ => None,
+        }
+    }
+
+    /// ### highlight_explorer_entry
+    ///
+    /// Move the cursor of the current explorer onto the entry at `path`, if it's listed
+    fn highlight_explorer_entry(&mut self, path: &Path) -> Option<(String, Msg)> {
+        let (component, idx): (&str, Option<usize>) = match self.browser.tab() {
+            FileExplorerTab::Local => (
+                super::COMPONENT_EXPLORER_LOCAL,
+                self.local().get_by_path(path).map(|(idx, _)| idx),
+            ),
+            FileExplorerTab::Remote => (
+                super::COMPONENT_EXPLORER_REMOTE,
+                self.remote().get_by_path(path).map(|(idx, _)| idx),
+            ),
+            _ => return None,
+        };
+        match (idx, self.view.get_props(component)) {
+            (Some(idx), Some(props)) => {
+                let props = FileListPropsBuilder::from(props).with_index(idx).build();
+                self.view.update(component, props)
+            }
+            _ => None,
+        }
+    }
+
     /// ### update_remote_filelist
     ///
     /// Update remote file list
@@ -774,13 +1172,23 @@ impl FileTransferActivity {
                     .get_unsigned(super::STORAGE_EXPLORER_WIDTH)
                     .unwrap_or(256);
                 let params = self.context().ft_params().unwrap();
+                // Show which session is active, if there are many
+                let address: String = match self.sessions.count() {
+                    1 => params.address.clone(),
+                    count => format!(
+                        "[{}/{}] {}",
+                        self.sessions.active_index() + 1,
+                        count,
+                        params.address
+                    ),
+                };
                 let hostname: String = format!(
                     "{}:{} ",
-                    params.address,
+                    address,
                     fmt_path_elide_ex(
                         self.remote().wrkdir.as_path(),
                         width,
-                        params.address.len() + 3 // 3 because of '/…/'
+                        address.len() + 3 // 3 because of '/…/'
                     )
                 );
                 let files: Vec<String> = self
@@ -791,6 +1199,8 @@ impl FileTransferActivity {
                 // Update
                 let props = FileListPropsBuilder::from(props)
                     .with_files(Some(hostname), files)
+                    .with_marks(self.remote().marks())
+                    .with_placeholder(placeholder(self.remote()))
                     .build();
                 self.view.update(super::COMPONENT_EXPLORER_REMOTE, props)
             }
@@ -804,43 +1214,7 @@ impl FileTransferActivity {
     pub(super) fn update_logbox(&mut self) -> Option<(String, Msg)> {
         match self.view.get_props(super::COMPONENT_LOG_BOX) {
             Some(props) => {
-                // Make log entries
-                let mut table: TableBuilder = TableBuilder::default();
-                for (idx, record) in self.log_records.iter().enumerate() {
-                    // Add row if not first row
-                    if idx > 0 {
-                        table.add_row();
-                    }
-                    let fg = match record.level {
-                        LogLevel::Error => Color::Red,
-                        LogLevel::Warn => Color::Yellow,
-                        LogLevel::Info => Color::Green,
-                    };
-                    table
-                        .add_col(TextSpan::from(format!(
-                            "{}",
-                            record.time.format("%Y-%m-%dT%H:%M:%S%Z")
-                        )))
-                        .add_col(TextSpan::from(" ["))
-                        .add_col(
-                            TextSpanBuilder::new(
-                                format!(
-                                    "{:5}",
-                                    match record.level {
-                                        LogLevel::Error => "ERROR",
-                                        LogLevel::Warn => "WARN",
-                                        LogLevel::Info => "INFO",
-                                    }
-                                )
-                                .as_str(),
-                            )
-                            .with_foreground(fg)
-                            .build(),
-                        )
-                        .add_col(TextSpan::from("]: "))
-                        .add_col(TextSpan::from(record.msg.as_ref()));
-                }
-                let table = table.build();
+                let table: Table = self.make_log_table(LogFilter::All);
                 let props = LogboxPropsBuilder::from(props)
                     .with_log(Some(String::from("Log")), table)
                     .build();
@@ -850,20 +1224,116 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### make_log_table
+    ///
+    /// Make the log entries table, with the records matching `filter` only
+    pub(super) fn make_log_table(&self, filter: LogFilter) -> Table {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, record) in self
+            .log_records
+            .iter()
+            .filter(|x| filter.matches(&x.level))
+            .enumerate()
+        {
+            // Add row if not first row
+            if idx > 0 {
+                table.add_row();
+            }
+            let fg = match record.level {
+                LogLevel::Error => Color::Red,
+                LogLevel::Warn => Color::Yellow,
+                LogLevel::Info => Color::Green,
+            };
+            table
+                .add_col(TextSpan::from(format!(
+                    "{}",
+                    record.time.format("%Y-%m-%dT%H:%M:%S%Z")
+                )))
+                .add_col(TextSpan::from(" ["))
+                .add_col(
+                    TextSpanBuilder::new(format!("{:5}", record.level).as_str())
+                        .with_foreground(fg)
+                        .build(),
+                )
+                .add_col(TextSpan::from("]: "))
+                .add_col(TextSpan::from(record.msg.as_ref()));
+        }
+        table.build()
+    }
+
     pub(super) fn update_progress_bar(&mut self, filename: String) -> Option<(String, Msg)> {
         if let Some(props) = self.view.get_props(COMPONENT_PROGRESS_BAR_FULL) {
             let root_name: String = props.texts.title.as_deref().unwrap_or("").to_string();
             let props = ProgressBarPropsBuilder::from(props)
-                .with_texts(Some(root_name), self.transfer.full.to_string())
+                .with_texts(
+                    Some(root_name),
+                    self.transfer.full.to_string(),
+                    Some(format!(
+                        "{} ({})",
+                        self.transfer.full.fmt_bytes(),
+                        self.transfer.fmt_files()
+                    )),
+                )
                 .with_progress(self.transfer.full.calc_progress())
+                .with_rate(self.transfer.full.calc_rate())
                 .build();
             let _ = self.view.update(COMPONENT_PROGRESS_BAR_FULL, props);
         }
         match self.view.get_props(COMPONENT_PROGRESS_BAR_PARTIAL) {
             Some(props) => {
+                let label: String = match self.transfer.paused() {
+                    true => String::from("Paused - press <CTRL+P> to resume"),
+                    false => self.transfer.partial.to_string(),
+                };
                 let props = ProgressBarPropsBuilder::from(props)
-                    .with_texts(Some(filename), self.transfer.partial.to_string())
+                    .with_texts(
+                        Some(filename),
+                        label,
+                        Some(self.transfer.partial.fmt_bytes()),
+                    )
                     .with_progress(self.transfer.partial.calc_progress())
+                    .with_rate(self.transfer.partial.calc_rate())
+                    .build();
+                self.view.update(COMPONENT_PROGRESS_BAR_PARTIAL, props)
+            }
+            None => None,
+        }
+    }
+
+    /// ### update_scan_progress
+    ///
+    /// Report the progress of the transfer size computation on the progress bar, while scanning `dir`
+    pub(super) fn update_scan_progress(
+        &mut self,
+        scan: &SizeScan,
+        dir: &Path,
+    ) -> Option<(String, Msg)> {
+        if let Some(props) = self.view.get_props(COMPONENT_PROGRESS_BAR_FULL) {
+            let root_name: String = props.texts.title.as_deref().unwrap_or("").to_string();
+            let props = ProgressBarPropsBuilder::from(props)
+                .with_texts(
+                    Some(root_name),
+                    String::from("Computing transfer size…"),
+                    Some(format!(
+                        "{} files ({}) in {} directories",
+                        scan.files(),
+                        fmt_bytes(scan.size() as u64, self.config().get_size_unit()),
+                        scan.dirs()
+                    )),
+                )
+                .with_progress(0.0)
+                .build();
+            let _ = self.view.update(COMPONENT_PROGRESS_BAR_FULL, props);
+        }
+        match self.view.get_props(COMPONENT_PROGRESS_BAR_PARTIAL) {
+            Some(props) => {
+                let props = ProgressBarPropsBuilder::from(props)
+                    .with_texts(
+                        Some(format!("Scanning \"{}\"…", dir.display())),
+                        String::from("Press <CTRL+C> to abort"),
+                        None,
+                    )
+                    .with_progress(0.0)
                     .build();
                 self.view.update(COMPONENT_PROGRESS_BAR_PARTIAL, props)
             }
@@ -871,6 +1341,29 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### apply_keybindings
+    ///
+    /// Translate keys pressed on the explorers into the default keys of the actions they're bound to,
+    /// so that the user key bindings are honoured
+    fn apply_keybindings(&self, id: &str, msg: Msg) -> Msg {
+        match (id, msg) {
+            (
+                COMPONENT_EXPLORER_LOCAL
+                | COMPONENT_EXPLORER_REMOTE
+                | COMPONENT_EXPLORER_FIND
+                | COMPONENT_LOG_BOX,
+                Msg::OnKey(KeyEvent {
+                    code: KeyCode::Char(ch),
+                    modifiers: KeyModifiers::NONE,
+                }),
+            ) => Msg::OnKey(KeyEvent {
+                code: KeyCode::Char(self.keybindings.translate(ch)),
+                modifiers: KeyModifiers::NONE,
+            }),
+            (_, msg) => msg,
+        }
+    }
+
     /// ### finalize_find
     ///
     /// Finalize find process
@@ -885,6 +1378,17 @@ impl FileTransferActivity {
         });
     }
 
+    /// ### update_browser_file_list
+    ///
+    /// Update the file list of the explorer in use
+    fn update_browser_file_list(&mut self) -> Option<(String, Msg)> {
+        match self.browser.tab() {
+            FileExplorerTab::Local => self.update_local_filelist(),
+            FileExplorerTab::Remote => self.update_remote_filelist(),
+            FileExplorerTab::FindLocal | FileExplorerTab::FindRemote => self.update_find_list(),
+        }
+    }
+
     fn update_find_list(&mut self) -> Option<(String, Msg)> {
         match self.view.get_props(COMPONENT_EXPLORER_FIND) {
             None => None,
@@ -899,6 +1403,7 @@ impl FileTransferActivity {
                     .collect();
                 let props = FileListPropsBuilder::from(props)
                     .with_files(Some(title), files)
+                    .with_marks(self.found().unwrap().marks())
                     .build();
                 self.view.update(COMPONENT_EXPLORER_FIND, props)
             }
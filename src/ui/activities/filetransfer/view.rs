@@ -26,23 +26,26 @@
  * SOFTWARE.
  */
 // locals
+use super::lib::info::{entry_info, InfoField};
+use super::lib::sync::{CompareRow, CompareStatus};
 use super::{browser::FileExplorerTab, Context, FileTransferActivity};
+use crate::filetransfer::{FileTransferError, FileTransferErrorType};
 use crate::fs::explorer::FileSorting;
-use crate::fs::FsEntry;
+use crate::fs::{DiskUsage, FsEntry};
 use crate::ui::components::{
+    breadcrumb::{Breadcrumb, BreadcrumbPropsBuilder},
     file_list::{FileList, FileListPropsBuilder},
     logbox::{LogBox, LogboxPropsBuilder},
     msgbox::{MsgBox, MsgBoxPropsBuilder},
+    progress_bar::{ProgressBar, ProgressBarPropsBuilder},
 };
 use crate::ui::store::Store;
-use crate::utils::fmt::fmt_time;
+use crate::utils::fmt::fmt_disk_usage;
 use crate::utils::ui::draw_area_in;
 // Ext
-use bytesize::ByteSize;
 use std::path::PathBuf;
 use tuirealm::components::{
     input::{Input, InputPropsBuilder},
-    progress_bar::{ProgressBar, ProgressBarPropsBuilder},
     radio::{Radio, RadioPropsBuilder},
     scrolltable::{ScrollTablePropsBuilder, Scrolltable},
     span::{Span, SpanPropsBuilder},
@@ -210,6 +213,14 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_INPUT_COPY, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_MOVE) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 40, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_INPUT_MOVE, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_FIND) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 40, 10);
@@ -218,6 +229,14 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_INPUT_FIND, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_BREADCRUMB) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 80, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_BREADCRUMB, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_GOTO) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 40, 10);
@@ -226,6 +245,14 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_INPUT_GOTO, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_CHMOD) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 40, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_INPUT_CHMOD, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_MKDIR) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 40, 10);
@@ -266,6 +293,14 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_INPUT_SAVEAS, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_SYMLINK) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 40, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_INPUT_SYMLINK, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_EXEC) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 40, 10);
@@ -282,9 +317,50 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_LIST_FILEINFO, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_LIST_EXEC_OUTPUT) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 80, 80);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view
+                        .render(super::COMPONENT_LIST_EXEC_OUTPUT, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_LIST_COMPARE) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 80, 80);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_LIST_COMPARE, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_LIST_STATS) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 50, 30);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_LIST_STATS, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_LOG_VIEWER) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 90, 80);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_LOG_VIEWER, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_LIST_PREVIEW) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 80, 80);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_LIST_PREVIEW, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_PROGRESS_BAR_PARTIAL) {
                 if props.visible {
-                    let popup = draw_area_in(f.size(), 50, 20);
+                    let popup = draw_area_in(f.size(), 50, 30);
                     f.render_widget(Clear, popup);
                     // make popup
                     let popup_chunks = Layout::default()
@@ -336,6 +412,38 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_RADIO_SORTING, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_REPLACE) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 50, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_RADIO_REPLACE, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_SIZE_LIMIT) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 60, 10);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view
+                        .render(super::COMPONENT_RADIO_SIZE_LIMIT, f, popup);
+                }
+            }
+            if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_HOST_KEY) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 60, 30);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    let popup_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+                        .split(popup);
+                    self.view
+                        .render(super::COMPONENT_TEXT_HOST_KEY, f, popup_chunks[0]);
+                    self.view
+                        .render(super::COMPONENT_RADIO_HOST_KEY, f, popup_chunks[1]);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_TEXT_ERROR) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 50, 10);
@@ -344,6 +452,14 @@ impl FileTransferActivity {
                     self.view.render(super::COMPONENT_TEXT_ERROR, f, popup);
                 }
             }
+            if let Some(props) = self.view.get_props(super::COMPONENT_TEXT_INFO) {
+                if props.visible {
+                    let popup = draw_area_in(f.size(), 60, 20);
+                    f.render_widget(Clear, popup);
+                    // make popup
+                    self.view.render(super::COMPONENT_TEXT_INFO, f, popup);
+                }
+            }
             if let Some(props) = self.view.get_props(super::COMPONENT_TEXT_FATAL) {
                 if props.visible {
                     let popup = draw_area_in(f.size(), 50, 10);
@@ -403,6 +519,34 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_TEXT_ERROR);
     }
 
+    /// ### mount_info
+    ///
+    /// Mount info box, titled `title`, displaying each line of `text`
+    pub(super) fn mount_info(&mut self, title: &str, text: &str) {
+        let info_color = self.theme().misc_input_dialog;
+        self.view.mount(
+            super::COMPONENT_TEXT_INFO,
+            Box::new(MsgBox::new(
+                MsgBoxPropsBuilder::default()
+                    .with_foreground(info_color)
+                    .with_borders(Borders::ALL, BorderType::Rounded, info_color)
+                    .with_texts(
+                        Some(title.to_string()),
+                        text.lines().map(TextSpan::from).collect(),
+                    )
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_TEXT_INFO);
+    }
+
+    /// ### umount_info
+    ///
+    /// Umount info message
+    pub(super) fn umount_info(&mut self) {
+        self.view.umount(super::COMPONENT_TEXT_INFO);
+    }
+
     pub(super) fn mount_fatal(&mut self, text: &str) {
         // Mount
         let error_color = self.theme().misc_error_dialog;
@@ -532,6 +676,25 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_INPUT_COPY);
     }
 
+    pub(super) fn mount_move(&mut self) {
+        let input_color = self.theme().misc_input_dialog;
+        self.view.mount(
+            super::COMPONENT_INPUT_MOVE,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, input_color)
+                    .with_foreground(input_color)
+                    .with_label(String::from("Move file(s) to…"))
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_INPUT_MOVE);
+    }
+
+    pub(super) fn umount_move(&mut self) {
+        self.view.umount(super::COMPONENT_INPUT_MOVE);
+    }
+
     pub(super) fn mount_exec(&mut self) {
         let input_color = self.theme().misc_input_dialog;
         self.view.mount(
@@ -626,6 +789,30 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_INPUT_GOTO);
     }
 
+    pub(super) fn mount_breadcrumb(&mut self) {
+        let color = self.theme().misc_input_dialog;
+        let wrkdir: PathBuf = match self.browser.tab() {
+            FileExplorerTab::Local => self.local().wrkdir.clone(),
+            FileExplorerTab::Remote => self.remote().wrkdir.clone(),
+            _ => panic!("You can't mount breadcrumb when in found result"),
+        };
+        self.view.mount(
+            super::COMPONENT_BREADCRUMB,
+            Box::new(Breadcrumb::new(
+                BreadcrumbPropsBuilder::default()
+                    .with_color(color)
+                    .with_borders(Borders::ALL, BorderType::Rounded, color)
+                    .with_path(Some(String::from("Jump to")), wrkdir.as_path())
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_BREADCRUMB);
+    }
+
+    pub(super) fn umount_breadcrumb(&mut self) {
+        self.view.umount(super::COMPONENT_BREADCRUMB);
+    }
+
     pub(super) fn mount_mkdir(&mut self) {
         let input_color = self.theme().misc_input_dialog;
         self.view.mount(
@@ -645,6 +832,25 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_INPUT_MKDIR);
     }
 
+    pub(super) fn mount_chmod(&mut self) {
+        let input_color = self.theme().misc_input_dialog;
+        self.view.mount(
+            super::COMPONENT_INPUT_CHMOD,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, input_color)
+                    .with_foreground(input_color)
+                    .with_label(String::from("Change mode (e.g. 755, u+x,g-w)"))
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_INPUT_CHMOD);
+    }
+
+    pub(super) fn umount_chmod(&mut self) {
+        self.view.umount(super::COMPONENT_INPUT_CHMOD);
+    }
+
     pub(super) fn mount_newfile(&mut self) {
         let input_color = self.theme().misc_input_dialog;
         self.view.mount(
@@ -721,21 +927,42 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_INPUT_SAVEAS);
     }
 
+    pub(super) fn mount_symlink(&mut self) {
+        let input_color = self.theme().misc_input_dialog;
+        self.view.mount(
+            super::COMPONENT_INPUT_SYMLINK,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, input_color)
+                    .with_foreground(input_color)
+                    .with_label(String::from("Symlink name"))
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_INPUT_SYMLINK);
+    }
+
+    pub(super) fn umount_symlink(&mut self) {
+        self.view.umount(super::COMPONENT_INPUT_SYMLINK);
+    }
+
     pub(super) fn mount_progress_bar(&mut self, root_name: String) {
         let prog_color_full = self.theme().transfer_progress_bar_full;
         let prog_color_partial = self.theme().transfer_progress_bar_partial;
+        let stalled_color = self.theme().misc_warn_dialog;
         self.view.mount(
             super::COMPONENT_PROGRESS_BAR_FULL,
             Box::new(ProgressBar::new(
                 ProgressBarPropsBuilder::default()
                     .with_progbar_color(prog_color_full)
+                    .with_stalled_color(1, stalled_color)
                     .with_background(Color::Black)
                     .with_borders(
                         Borders::TOP | Borders::RIGHT | Borders::LEFT,
                         BorderType::Rounded,
                         Color::Reset,
                     )
-                    .with_texts(Some(root_name), String::new())
+                    .with_texts(Some(root_name), String::new(), None)
                     .build(),
             )),
         );
@@ -744,13 +971,15 @@ impl FileTransferActivity {
             Box::new(ProgressBar::new(
                 ProgressBarPropsBuilder::default()
                     .with_progbar_color(prog_color_partial)
+                    .with_threshold(1.0, prog_color_full)
+                    .with_stalled_color(1, stalled_color)
                     .with_background(Color::Black)
                     .with_borders(
                         Borders::BOTTOM | Borders::RIGHT | Borders::LEFT,
                         BorderType::Rounded,
                         Color::Reset,
                     )
-                    .with_texts(Some(String::from("Please wait")), String::new())
+                    .with_texts(Some(String::from("Please wait")), String::new(), None)
                     .build(),
             )),
         );
@@ -826,101 +1055,169 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_RADIO_DELETE);
     }
 
-    pub(super) fn mount_file_info(&mut self, file: &FsEntry) {
-        let mut texts: TableBuilder = TableBuilder::default();
-        // Abs path
-        let real_path: Option<PathBuf> = {
-            let real_file: FsEntry = file.get_realfile();
-            match real_file.get_abs_path() != file.get_abs_path() {
-                true => Some(real_file.get_abs_path()),
-                false => None,
-            }
-        };
-        let path: String = match real_path {
-            Some(symlink) => format!("{} -> {}", file.get_abs_path().display(), symlink.display()),
-            None => format!("{}", file.get_abs_path().display()),
-        };
-        // Make texts
-        texts.add_col(TextSpan::from("Path: ")).add_col(
-            TextSpanBuilder::new(path.as_str())
-                .with_foreground(Color::Yellow)
-                .build(),
+    pub(super) fn mount_radio_replace(&mut self, file_name: &str) {
+        let warn_color = self.theme().misc_warn_dialog;
+        self.view.mount(
+            super::COMPONENT_RADIO_REPLACE,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(warn_color)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Plain, warn_color)
+                    .with_options(
+                        Some(format!("Replace file \"{}\"?", file_name)),
+                        vec![
+                            String::from("Yes"),
+                            String::from("No"),
+                            String::from("Yes to all"),
+                            String::from("No to all"),
+                        ],
+                    )
+                    .with_value(1)
+                    .build(),
+            )),
         );
-        if let Some(filetype) = file.get_ftype() {
-            texts
-                .add_row()
-                .add_col(TextSpan::from("File type: "))
-                .add_col(
-                    TextSpanBuilder::new(filetype.as_str())
-                        .with_foreground(Color::LightGreen)
-                        .build(),
-                );
-        }
-        let (bsize, size): (ByteSize, usize) = (ByteSize(file.get_size() as u64), file.get_size());
-        texts.add_row().add_col(TextSpan::from("Size: ")).add_col(
-            TextSpanBuilder::new(format!("{} ({})", bsize, size).as_str())
-                .with_foreground(Color::Cyan)
-                .build(),
+        self.view.active(super::COMPONENT_RADIO_REPLACE);
+    }
+
+    pub(super) fn umount_radio_replace(&mut self) {
+        self.view.umount(super::COMPONENT_RADIO_REPLACE);
+    }
+
+    /// ### mount_radio_size_limit
+    ///
+    /// Mount the prompt asking whether to open a file which exceeds the size limit
+    pub(super) fn mount_radio_size_limit(&mut self, text: &str) {
+        let warn_color = self.theme().misc_warn_dialog;
+        self.view.mount(
+            super::COMPONENT_RADIO_SIZE_LIMIT,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(warn_color)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Plain, warn_color)
+                    .with_options(
+                        Some(text.to_string()),
+                        vec![String::from("Yes"), String::from("No")],
+                    )
+                    .with_value(1)
+                    .build(),
+            )),
         );
-        let ctime: String = fmt_time(file.get_creation_time(), "%b %d %Y %H:%M:%S");
-        let atime: String = fmt_time(file.get_last_access_time(), "%b %d %Y %H:%M:%S");
-        let mtime: String = fmt_time(file.get_creation_time(), "%b %d %Y %H:%M:%S");
-        texts
-            .add_row()
-            .add_col(TextSpan::from("Creation time: "))
-            .add_col(
-                TextSpanBuilder::new(ctime.as_str())
-                    .with_foreground(Color::LightGreen)
+        self.view.active(super::COMPONENT_RADIO_SIZE_LIMIT);
+    }
+
+    pub(super) fn umount_radio_size_limit(&mut self) {
+        self.view.umount(super::COMPONENT_RADIO_SIZE_LIMIT);
+    }
+
+    /// ### mount_host_key
+    ///
+    /// Mount host key verification popup
+    pub(super) fn mount_host_key(&mut self, address: &str, err: &FileTransferError) {
+        let bookmarked: bool = self
+            .context()
+            .ft_params()
+            .map(|x| x.host_key_fingerprint.is_some())
+            .unwrap_or(false);
+        let (color, text) = match err.kind() {
+            FileTransferErrorType::HostKeyMismatch if bookmarked => (
+                self.theme().misc_error_dialog,
+                format!(
+                    "WARNING: the host key of '{}' differs from the one saved in its bookmark!",
+                    address
+                ),
+            ),
+            FileTransferErrorType::HostKeyMismatch => (
+                self.theme().misc_error_dialog,
+                format!("WARNING: the host key of '{}' has changed!", address),
+            ),
+            _ => (
+                self.theme().misc_warn_dialog,
+                format!(
+                    "The authenticity of host '{}' can't be established",
+                    address
+                ),
+            ),
+        };
+        self.view.mount(
+            super::COMPONENT_TEXT_HOST_KEY,
+            Box::new(MsgBox::new(
+                MsgBoxPropsBuilder::default()
+                    .with_foreground(color)
+                    .with_borders(Borders::ALL, BorderType::Rounded, color)
+                    .bold()
+                    .with_texts(
+                        None,
+                        vec![TextSpan::from(text), TextSpan::from(err.to_string())],
+                    )
                     .build(),
-            );
-        texts
-            .add_row()
-            .add_col(TextSpan::from("Last modified time: "))
-            .add_col(
-                TextSpanBuilder::new(mtime.as_str())
-                    .with_foreground(Color::LightBlue)
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_RADIO_HOST_KEY,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(color)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Plain, color)
+                    .with_options(
+                        Some(String::from("Trust host key?")),
+                        vec![String::from("Trust"), String::from("Reject")],
+                    )
+                    .with_value(1)
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_RADIO_HOST_KEY);
+    }
+
+    pub(super) fn umount_host_key(&mut self) {
+        self.view.umount(super::COMPONENT_RADIO_HOST_KEY);
+        self.view.umount(super::COMPONENT_TEXT_HOST_KEY);
+    }
+
+    pub(super) fn mount_file_info(&mut self, file: &FsEntry, disk_usage: Option<DiskUsage>) {
+        let mut texts: TableBuilder = TableBuilder::default();
+        #[cfg(target_family = "unix")]
+        let rows = entry_info(
+            file,
+            self.config().get_size_unit(),
+            |uid| get_user_by_uid(uid).map(|x| x.name().to_string_lossy().to_string()),
+            |gid| get_group_by_gid(gid).map(|x| x.name().to_string_lossy().to_string()),
+        );
+        #[cfg(target_os = "windows")]
+        let rows = entry_info(file, self.config().get_size_unit(), |_| None, |_| None);
+        for (i, (field, value)) in rows.into_iter().enumerate() {
+            if i > 0 {
+                texts.add_row();
+            }
+            let color: Color = match field {
+                InfoField::Path | InfoField::Symlink => Color::Yellow,
+                InfoField::FileType | InfoField::CreationTime => Color::LightGreen,
+                InfoField::Size => Color::Cyan,
+                InfoField::Mode => Color::LightCyan,
+                InfoField::ModifiedTime => Color::LightBlue,
+                InfoField::AccessTime => Color::LightRed,
+                InfoField::User => Color::LightYellow,
+                InfoField::Group => Color::Blue,
+            };
+            texts.add_col(TextSpan::from(field.label())).add_col(
+                TextSpanBuilder::new(value.as_str())
+                    .with_foreground(color)
                     .build(),
             );
+        }
         texts
             .add_row()
-            .add_col(TextSpan::from("Last access time: "))
+            .add_col(TextSpan::from("Disk usage: "))
             .add_col(
-                TextSpanBuilder::new(atime.as_str())
-                    .with_foreground(Color::LightRed)
-                    .build(),
-            );
-        // User
-        #[cfg(target_family = "unix")]
-        let username: String = match file.get_user() {
-            Some(uid) => match get_user_by_uid(uid) {
-                Some(user) => user.name().to_string_lossy().to_string(),
-                None => uid.to_string(),
-            },
-            None => String::from("0"),
-        };
-        #[cfg(target_os = "windows")]
-        let username: String = format!("{}", file.get_user().unwrap_or(0));
-        // Group
-        #[cfg(target_family = "unix")]
-        let group: String = match file.get_group() {
-            Some(gid) => match get_group_by_gid(gid) {
-                Some(group) => group.name().to_string_lossy().to_string(),
-                None => gid.to_string(),
-            },
-            None => String::from("0"),
-        };
-        #[cfg(target_os = "windows")]
-        let group: String = format!("{}", file.get_group().unwrap_or(0));
-        texts.add_row().add_col(TextSpan::from("User: ")).add_col(
-            TextSpanBuilder::new(username.as_str())
-                .with_foreground(Color::LightYellow)
-                .build(),
-        );
-        texts.add_row().add_col(TextSpan::from("Group: ")).add_col(
-            TextSpanBuilder::new(group.as_str())
-                .with_foreground(Color::Blue)
+                TextSpanBuilder::new(
+                    fmt_disk_usage(disk_usage.as_ref(), self.config().get_size_unit()).as_str(),
+                )
+                .with_foreground(Color::LightMagenta)
                 .build(),
-        );
+            );
         self.view.mount(
             super::COMPONENT_LIST_FILEINFO,
             Box::new(Table::new(
@@ -937,6 +1234,176 @@ impl FileTransferActivity {
         self.view.umount(super::COMPONENT_LIST_FILEINFO);
     }
 
+    pub(super) fn mount_preview(&mut self, title: String, lines: Vec<String>) {
+        let mut texts: TableBuilder = TableBuilder::default();
+        let mut lines = lines.into_iter();
+        texts.add_col(TextSpan::from(lines.next().unwrap_or_default().as_str()));
+        for line in lines {
+            texts.add_row().add_col(TextSpan::from(line.as_str()));
+        }
+        self.view.mount(
+            super::COMPONENT_LIST_PREVIEW,
+            Box::new(Scrolltable::new(
+                ScrollTablePropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::White)
+                    .with_max_scroll_step(16)
+                    .with_table(Some(title), texts.build())
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_LIST_PREVIEW);
+    }
+
+    pub(super) fn umount_preview(&mut self) {
+        self.view.umount(super::COMPONENT_LIST_PREVIEW);
+    }
+
+    /// ### mount_exec_output
+    ///
+    /// Mount the read-only popup with the output of a remote command
+    pub(super) fn mount_exec_output(&mut self, title: String, lines: Vec<String>) {
+        let mut texts: TableBuilder = TableBuilder::default();
+        let mut lines = lines.into_iter();
+        texts.add_col(TextSpan::from(lines.next().unwrap_or_default().as_str()));
+        for line in lines {
+            texts.add_row().add_col(TextSpan::from(line.as_str()));
+        }
+        self.view.mount(
+            super::COMPONENT_LIST_EXEC_OUTPUT,
+            Box::new(Scrolltable::new(
+                ScrollTablePropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::White)
+                    .with_max_scroll_step(16)
+                    .with_table(Some(title), texts.build())
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_LIST_EXEC_OUTPUT);
+    }
+
+    pub(super) fn umount_exec_output(&mut self) {
+        self.view.umount(super::COMPONENT_LIST_EXEC_OUTPUT);
+    }
+
+    /// ### mount_compare
+    ///
+    /// Mount the result of the comparison between the local and the remote directory
+    pub(super) fn mount_compare(&mut self, title: String, rows: Vec<CompareRow>) {
+        let local_color = self.theme().transfer_local_explorer_highlighted;
+        let remote_color = self.theme().transfer_remote_explorer_highlighted;
+        let differs_color = self.theme().misc_warn_dialog;
+        let mut texts: TableBuilder = TableBuilder::default();
+        if rows.is_empty() {
+            texts.add_col(TextSpan::from("Both directories are empty"));
+        }
+        for (idx, row) in rows.iter().enumerate() {
+            if idx > 0 {
+                texts.add_row();
+            }
+            let color: Color = match row.status {
+                CompareStatus::LocalOnly => local_color,
+                CompareStatus::RemoteOnly => remote_color,
+                CompareStatus::Differs => differs_color,
+                CompareStatus::Same => Color::Reset,
+            };
+            let name: String = match row.is_dir {
+                true => format!("{}/", row.name),
+                false => row.name.clone(),
+            };
+            texts
+                .add_col(
+                    TextSpanBuilder::new(format!("{:<12}", row.status).as_str())
+                        .with_foreground(color)
+                        .build(),
+                )
+                .add_col(
+                    TextSpanBuilder::new(name.as_str())
+                        .with_foreground(color)
+                        .build(),
+                );
+        }
+        self.view.mount(
+            super::COMPONENT_LIST_COMPARE,
+            Box::new(Scrolltable::new(
+                ScrollTablePropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::White)
+                    .with_max_scroll_step(16)
+                    .with_table(Some(title), texts.build())
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_LIST_COMPARE);
+    }
+
+    pub(super) fn umount_compare(&mut self) {
+        self.view.umount(super::COMPONENT_LIST_COMPARE);
+    }
+
+    pub(super) fn mount_session_stats(&mut self) {
+        let mut texts: TableBuilder = TableBuilder::default();
+        for (i, (label, value)) in self
+            .stats
+            .lines(self.config().get_size_unit())
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                texts.add_row();
+            }
+            texts
+                .add_col(TextSpan::from(format!("{}: ", label).as_str()))
+                .add_col(
+                    TextSpanBuilder::new(value.as_str())
+                        .with_foreground(Color::LightYellow)
+                        .build(),
+                );
+        }
+        self.view.mount(
+            super::COMPONENT_LIST_STATS,
+            Box::new(Table::new(
+                TablePropsBuilder::default()
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::White)
+                    .with_table(Some(String::from("Session statistics")), texts.build())
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_LIST_STATS);
+    }
+
+    pub(super) fn umount_session_stats(&mut self) {
+        self.view.umount(super::COMPONENT_LIST_STATS);
+    }
+
+    /// ### mount_log_viewer
+    ///
+    /// Mount the log viewer, displaying the records which match the current log filter
+    pub(super) fn mount_log_viewer(&mut self) {
+        let log_panel = self.theme().transfer_log_window;
+        let log_background = self.theme().transfer_log_background;
+        let table = self.make_log_table(self.log_filter);
+        self.view.mount(
+            super::COMPONENT_LOG_VIEWER,
+            Box::new(LogBox::new(
+                LogboxPropsBuilder::default()
+                    .with_background(log_background)
+                    .with_borders(Borders::ALL, BorderType::Rounded, log_panel)
+                    .with_log(
+                        Some(format!(
+                            "Log ({}) - <F> filter, <C> copy, <ESC> close",
+                            self.log_filter
+                        )),
+                        table,
+                    )
+                    .build(),
+            )),
+        );
+        self.view.active(super::COMPONENT_LOG_VIEWER);
+    }
+
+    pub(super) fn umount_log_viewer(&mut self) {
+        self.view.umount(super::COMPONENT_LOG_VIEWER);
+    }
+
     pub(super) fn refresh_local_status_bar(&mut self) {
         let sorting_color = self.theme().transfer_status_sorting;
         let hidden_color = self.theme().transfer_status_hidden;
@@ -999,6 +1466,26 @@ impl FileTransferActivity {
             .with_foreground(sync_color)
             .reversed()
             .build(),
+            TextSpanBuilder::new(" Dry run: ")
+                .with_foreground(sync_color)
+                .build(),
+            TextSpanBuilder::new(match self.dry_run() {
+                true => "ON ",
+                false => "OFF",
+            })
+            .with_foreground(sync_color)
+            .reversed()
+            .build(),
+            TextSpanBuilder::new(" Skip unchanged: ")
+                .with_foreground(sync_color)
+                .build(),
+            TextSpanBuilder::new(match self.skip_unchanged() {
+                true => "ON ",
+                false => "OFF",
+            })
+            .with_foreground(sync_color)
+            .reversed()
+            .build(),
         ];
         if let Some(props) = self.view.get_props(super::COMPONENT_SPAN_STATUS_BAR_REMOTE) {
             self.view.update(
@@ -1044,6 +1531,14 @@ impl FileTransferActivity {
                                 "           Switch between explorer and logs",
                             ))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<ENTER>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("         Open log viewer (from logs)"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<BACKSPACE>")
                                     .bold()
@@ -1068,6 +1563,22 @@ impl FileTransferActivity {
                             )
                             .add_col(TextSpan::from("       Move up/down in list"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<PGUP/PGDOWN>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("   Move up/down by 8 rows"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<HOME/END>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("      Go to first/last entry"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<ENTER>")
                                     .bold()
@@ -1100,6 +1611,22 @@ impl FileTransferActivity {
                             )
                             .add_col(TextSpan::from("             Change file sorting mode"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<<>, <>>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Sort by previous/next sorting mode"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<~>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("             Go to home directory"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<C>")
                                     .bold()
@@ -1140,6 +1667,88 @@ impl FileTransferActivity {
                             )
                             .add_col(TextSpan::from("             Show info about selected file"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+V>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Preview selected text file"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+R>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Change permissions of selected file"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+D>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Duplicate selected file"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+E>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Compare local and remote directory"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+G>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Show session transfer statistics"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+N>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Open a new remote session"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+S>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Switch to the next remote session"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+W>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Close the remote session"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<J>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from(
+                                "             Jump to a parent of the working directory",
+                            ))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<K>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("             Create symlink pointing to the current selected entry"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<L>")
                                     .bold()
@@ -1154,7 +1763,7 @@ impl FileTransferActivity {
                                     .with_foreground(key_color)
                                     .build(),
                             )
-                            .add_col(TextSpan::from("             Select file"))
+                            .add_col(TextSpan::from("             Mark file"))
                             .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<N>")
@@ -1174,6 +1783,16 @@ impl FileTransferActivity {
                                 "             Open text file with preferred editor",
                             ))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<P>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from(
+                                "             Pull remote directory recursively",
+                            ))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<Q>")
                                     .bold()
@@ -1198,6 +1817,16 @@ impl FileTransferActivity {
                             )
                             .add_col(TextSpan::from("             Save file as"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<T>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from(
+                                "             Mirror working directory onto the other panel",
+                            ))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<U>")
                                     .bold()
@@ -1250,13 +1879,45 @@ impl FileTransferActivity {
                             )
                             .add_col(TextSpan::from("         Delete selected file"))
                             .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<Z>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("             Toggle dry-run mode"))
+                            .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<CTRL+A>")
                                     .bold()
                                     .with_foreground(key_color)
                                     .build(),
                             )
-                            .add_col(TextSpan::from("        Select all files"))
+                            .add_col(TextSpan::from("        Mark all files"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+T>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Invert marked files"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+U>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Toggle skipping unchanged files"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+X>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Move file(s) to another directory"))
                             .add_row()
                             .add_col(
                                 TextSpanBuilder::new("<CTRL+C>")
@@ -1265,6 +1926,14 @@ impl FileTransferActivity {
                                     .build(),
                             )
                             .add_col(TextSpan::from("        Interrupt file transfer"))
+                            .add_row()
+                            .add_col(
+                                TextSpanBuilder::new("<CTRL+P>")
+                                    .bold()
+                                    .with_foreground(key_color)
+                                    .build(),
+                            )
+                            .add_col(TextSpan::from("        Pause or resume file transfer"))
                             .build(),
                     )
                     .build(),
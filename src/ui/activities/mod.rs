@@ -58,6 +58,13 @@ pub trait Activity {
     /// This function must be called at each tick to refresh the interface
     fn on_draw(&mut self);
 
+    /// ### is_busy
+    ///
+    /// `is_busy` reports to the activity manager whether the last call to `on_draw` has handled any event
+    /// or has performed any task (e.g. a transfer).
+    /// When the activity is not busy for a while, the activity manager slows down the ticks
+    fn is_busy(&self) -> bool;
+
     /// ### will_umount
     ///
     /// `will_umount` is the method which must be able to report to the activity manager, whether
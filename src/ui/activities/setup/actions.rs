@@ -175,7 +175,7 @@ impl SetupActivity {
             _ => String::new(),
         };
         // Prepare text editor
-        env::set_var("EDITOR", self.config().get_text_editor());
+        env::set_var("EDITOR", self.config().resolve_text_editor());
         let placeholder: String = format!("# Type private SSH key for {}@{}\n", username, host);
         // Put input mode back to normal
         if let Err(err) = disable_raw_mode() {
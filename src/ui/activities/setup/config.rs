@@ -92,7 +92,7 @@ impl SetupActivity {
             None => Ok(()),
             Some(ctx) => {
                 // Set editor if config client exists
-                env::set_var("EDITOR", ctx.config().get_text_editor());
+                env::set_var("EDITOR", ctx.config().resolve_text_editor());
                 // Prepare terminal
                 if let Err(err) = disable_raw_mode() {
                     error!("Failed to disable raw mode: {}", err);
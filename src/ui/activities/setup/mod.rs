@@ -57,6 +57,12 @@ const COMPONENT_RADIO_UPDATES: &str = "RADIO_CHECK_UPDATES";
 const COMPONENT_RADIO_GROUP_DIRS: &str = "RADIO_GROUP_DIRS";
 const COMPONENT_INPUT_LOCAL_FILE_FMT: &str = "INPUT_LOCAL_FILE_FMT";
 const COMPONENT_INPUT_REMOTE_FILE_FMT: &str = "INPUT_REMOTE_FILE_FMT";
+const COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS: &str = "INPUT_MAX_PARALLEL_TRANSFERS";
+const COMPONENT_RADIO_RESUME_TRANSFERS: &str = "RADIO_RESUME_TRANSFERS";
+const COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE: &str = "RADIO_PROMPT_ON_FILE_REPLACE";
+const COMPONENT_INPUT_MAX_RECENTS: &str = "INPUT_MAX_RECENTS";
+const COMPONENT_RADIO_CONFIRM_ON_QUIT: &str = "RADIO_CONFIRM_ON_QUIT";
+const COMPONENT_RADIO_SSH_COMPRESSION: &str = "RADIO_SSH_COMPRESSION";
 // -- ssh keys
 const COMPONENT_LIST_SSH_KEYS: &str = "LIST_SSH_KEYS";
 const COMPONENT_INPUT_SSH_HOST: &str = "INPUT_SSH_HOST";
@@ -122,6 +128,7 @@ pub struct SetupActivity {
     view: View,               // View
     layout: ViewLayout,       // View layout
     redraw: bool,
+    busy: bool, // Has an event been handled on last draw?
 }
 
 impl Default for SetupActivity {
@@ -132,6 +139,7 @@ impl Default for SetupActivity {
             view: View::init(),
             layout: ViewLayout::SetupForm,
             redraw: true, // Draw at first `on_draw`
+            busy: false,
         }
     }
 }
@@ -226,9 +234,11 @@ impl Activity for SetupActivity {
             return;
         }
         // Read one event
+        self.busy = false;
         if let Ok(Some(event)) = self.context().input_hnd().read_event() {
             // Set redraw to true
             self.redraw = true;
+            self.busy = true;
             // Handle event
             let msg = self.view.on(event);
             self.update(msg);
@@ -242,6 +252,13 @@ impl Activity for SetupActivity {
         }
     }
 
+    /// ### is_busy
+    ///
+    /// `is_busy` reports to the activity manager whether the last call to `on_draw` has handled any event
+    fn is_busy(&self) -> bool {
+        self.busy
+    }
+
     /// ### will_umount
     ///
     /// `will_umount` is the method which must be able to report to the activity manager, whether
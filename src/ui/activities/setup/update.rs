@@ -40,11 +40,13 @@ use super::{
     COMPONENT_COLOR_TRANSFER_PROG_BAR_FULL, COMPONENT_COLOR_TRANSFER_PROG_BAR_PARTIAL,
     COMPONENT_COLOR_TRANSFER_STATUS_HIDDEN, COMPONENT_COLOR_TRANSFER_STATUS_SORTING,
     COMPONENT_COLOR_TRANSFER_STATUS_SYNC, COMPONENT_INPUT_LOCAL_FILE_FMT,
+    COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS, COMPONENT_INPUT_MAX_RECENTS,
     COMPONENT_INPUT_REMOTE_FILE_FMT, COMPONENT_INPUT_SSH_HOST, COMPONENT_INPUT_SSH_USERNAME,
-    COMPONENT_INPUT_TEXT_EDITOR, COMPONENT_LIST_SSH_KEYS, COMPONENT_RADIO_DEFAULT_PROTOCOL,
-    COMPONENT_RADIO_DEL_SSH_KEY, COMPONENT_RADIO_GROUP_DIRS, COMPONENT_RADIO_HIDDEN_FILES,
-    COMPONENT_RADIO_QUIT, COMPONENT_RADIO_SAVE, COMPONENT_RADIO_UPDATES, COMPONENT_TEXT_ERROR,
-    COMPONENT_TEXT_HELP,
+    COMPONENT_INPUT_TEXT_EDITOR, COMPONENT_LIST_SSH_KEYS, COMPONENT_RADIO_CONFIRM_ON_QUIT,
+    COMPONENT_RADIO_DEFAULT_PROTOCOL, COMPONENT_RADIO_DEL_SSH_KEY, COMPONENT_RADIO_GROUP_DIRS,
+    COMPONENT_RADIO_HIDDEN_FILES, COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE, COMPONENT_RADIO_QUIT,
+    COMPONENT_RADIO_RESUME_TRANSFERS, COMPONENT_RADIO_SAVE, COMPONENT_RADIO_SSH_COMPRESSION,
+    COMPONENT_RADIO_UPDATES, COMPONENT_TEXT_ERROR, COMPONENT_TEXT_HELP,
 };
 use crate::ui::keymap::*;
 use crate::utils::parser::parse_color;
@@ -99,10 +101,58 @@ impl SetupActivity {
                     None
                 }
                 (COMPONENT_INPUT_REMOTE_FILE_FMT, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS);
+                    None
+                }
+                (COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_RADIO_RESUME_TRANSFERS);
+                    None
+                }
+                (COMPONENT_RADIO_RESUME_TRANSFERS, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE);
+                    None
+                }
+                (COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_INPUT_MAX_RECENTS);
+                    None
+                }
+                (COMPONENT_INPUT_MAX_RECENTS, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_RADIO_CONFIRM_ON_QUIT);
+                    None
+                }
+                (COMPONENT_RADIO_CONFIRM_ON_QUIT, &MSG_KEY_DOWN) => {
+                    self.view.active(COMPONENT_RADIO_SSH_COMPRESSION);
+                    None
+                }
+                (COMPONENT_RADIO_SSH_COMPRESSION, &MSG_KEY_DOWN) => {
                     self.view.active(COMPONENT_INPUT_TEXT_EDITOR);
                     None
                 }
                 // Input field <UP>
+                (COMPONENT_RADIO_SSH_COMPRESSION, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_RADIO_CONFIRM_ON_QUIT);
+                    None
+                }
+                (COMPONENT_RADIO_CONFIRM_ON_QUIT, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_INPUT_MAX_RECENTS);
+                    None
+                }
+                (COMPONENT_INPUT_MAX_RECENTS, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE);
+                    None
+                }
+                (COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_RADIO_RESUME_TRANSFERS);
+                    None
+                }
+                (COMPONENT_RADIO_RESUME_TRANSFERS, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS);
+                    None
+                }
+                (COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS, &MSG_KEY_UP) => {
+                    self.view.active(COMPONENT_INPUT_REMOTE_FILE_FMT);
+                    None
+                }
                 (COMPONENT_INPUT_REMOTE_FILE_FMT, &MSG_KEY_UP) => {
                     self.view.active(COMPONENT_INPUT_LOCAL_FILE_FMT);
                     None
@@ -128,7 +178,7 @@ impl SetupActivity {
                     None
                 }
                 (COMPONENT_INPUT_TEXT_EDITOR, &MSG_KEY_UP) => {
-                    self.view.active(COMPONENT_INPUT_REMOTE_FILE_FMT);
+                    self.view.active(COMPONENT_RADIO_SSH_COMPRESSION);
                     None
                 }
                 // Error <ENTER> or <ESC>
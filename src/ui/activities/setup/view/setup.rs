@@ -44,7 +44,7 @@ use tuirealm::tui::{
     widgets::{BorderType, Borders, Clear},
 };
 use tuirealm::{
-    props::{PropsBuilder, TextSpanBuilder},
+    props::{InputType, PropsBuilder, TextSpanBuilder},
     Payload, Value, View,
 };
 
@@ -191,6 +191,90 @@ impl SetupActivity {
                     .build(),
             )),
         );
+        self.view.mount(
+            super::COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_foreground(Color::LightYellow)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightYellow)
+                    .with_label(String::from("Max parallel copies"))
+                    .with_input(InputType::Number)
+                    .with_input_len(2)
+                    .build(),
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_RADIO_RESUME_TRANSFERS,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(Color::LightCyan)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightCyan)
+                    .with_options(
+                        Some(String::from("Resume interrupted transfers?")),
+                        vec![String::from("Yes"), String::from("No")],
+                    )
+                    .build(),
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(Color::LightRed)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightRed)
+                    .with_options(
+                        Some(String::from("Prompt when replacing existing files?")),
+                        vec![String::from("Yes"), String::from("No")],
+                    )
+                    .build(),
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_INPUT_MAX_RECENTS,
+            Box::new(Input::new(
+                InputPropsBuilder::default()
+                    .with_foreground(Color::LightBlue)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightBlue)
+                    .with_label(String::from("Max recent connections"))
+                    .with_input(InputType::Number)
+                    .with_input_len(3)
+                    .build(),
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_RADIO_CONFIRM_ON_QUIT,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(Color::LightGreen)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightGreen)
+                    .with_options(
+                        Some(String::from("Confirm on quit?")),
+                        vec![String::from("Yes"), String::from("No")],
+                    )
+                    .build(),
+            )),
+        );
+        self.view.mount(
+            super::COMPONENT_RADIO_SSH_COMPRESSION,
+            Box::new(Radio::new(
+                RadioPropsBuilder::default()
+                    .with_color(Color::LightCyan)
+                    .with_inverted_color(Color::Black)
+                    .with_borders(Borders::ALL, BorderType::Rounded, Color::LightCyan)
+                    .with_options(
+                        Some(String::from("SSH compression")),
+                        vec![
+                            String::from("Yes"),
+                            String::from("No"),
+                            String::from("From ssh config"),
+                        ],
+                    )
+                    .build(),
+            )),
+        );
         // Load values
         self.load_input_values();
     }
@@ -204,9 +288,9 @@ impl SetupActivity {
                 .margin(1)
                 .constraints(
                     [
-                        Constraint::Length(3),  // Current tab
-                        Constraint::Length(21), // Main body
-                        Constraint::Length(3),  // Help footer
+                        Constraint::Length(3),      // Current tab
+                        Constraint::Percentage(90), // Main body
+                        Constraint::Length(3),      // Help footer
                     ]
                     .as_ref(),
                 )
@@ -226,6 +310,12 @@ impl SetupActivity {
                         Constraint::Length(3), // Group dirs
                         Constraint::Length(3), // Local Format input
                         Constraint::Length(3), // Remote Format input
+                        Constraint::Length(3), // Max parallel copies
+                        Constraint::Length(3), // Resume transfers
+                        Constraint::Length(3), // Prompt on file replace
+                        Constraint::Length(3), // Max recents
+                        Constraint::Length(3), // Confirm on quit
+                        Constraint::Length(3), // SSH compression
                     ]
                     .as_ref(),
                 )
@@ -244,6 +334,24 @@ impl SetupActivity {
                 .render(super::COMPONENT_INPUT_LOCAL_FILE_FMT, f, ui_cfg_chunks[5]);
             self.view
                 .render(super::COMPONENT_INPUT_REMOTE_FILE_FMT, f, ui_cfg_chunks[6]);
+            self.view.render(
+                super::COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS,
+                f,
+                ui_cfg_chunks[7],
+            );
+            self.view
+                .render(super::COMPONENT_RADIO_RESUME_TRANSFERS, f, ui_cfg_chunks[8]);
+            self.view.render(
+                super::COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE,
+                f,
+                ui_cfg_chunks[9],
+            );
+            self.view
+                .render(super::COMPONENT_INPUT_MAX_RECENTS, f, ui_cfg_chunks[10]);
+            self.view
+                .render(super::COMPONENT_RADIO_CONFIRM_ON_QUIT, f, ui_cfg_chunks[11]);
+            self.view
+                .render(super::COMPONENT_RADIO_SSH_COMPRESSION, f, ui_cfg_chunks[12]);
             // Popups
             if let Some(props) = self.view.get_props(super::COMPONENT_TEXT_ERROR) {
                 if props.visible {
@@ -352,6 +460,73 @@ impl SetupActivity {
                 .view
                 .update(super::COMPONENT_INPUT_REMOTE_FILE_FMT, props);
         }
+        // Max parallel copies
+        if let Some(props) = self
+            .view
+            .get_props(super::COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS)
+        {
+            let jobs: String = self.config().get_max_parallel_transfers().to_string();
+            let props = InputPropsBuilder::from(props).with_value(jobs).build();
+            let _ = self
+                .view
+                .update(super::COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS, props);
+        }
+        // Resume transfers
+        if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_RESUME_TRANSFERS) {
+            let resume: usize = match self.config().get_resume_transfers() {
+                true => 0,
+                false => 1,
+            };
+            let props = RadioPropsBuilder::from(props).with_value(resume).build();
+            let _ = self
+                .view
+                .update(super::COMPONENT_RADIO_RESUME_TRANSFERS, props);
+        }
+        // Prompt on file replace
+        if let Some(props) = self
+            .view
+            .get_props(super::COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE)
+        {
+            let prompt: usize = match self.config().get_prompt_on_file_replace() {
+                true => 0,
+                false => 1,
+            };
+            let props = RadioPropsBuilder::from(props).with_value(prompt).build();
+            let _ = self
+                .view
+                .update(super::COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE, props);
+        }
+        // Max recents
+        if let Some(props) = self.view.get_props(super::COMPONENT_INPUT_MAX_RECENTS) {
+            let max: String = self.config().get_max_recents().to_string();
+            let props = InputPropsBuilder::from(props).with_value(max).build();
+            let _ = self.view.update(super::COMPONENT_INPUT_MAX_RECENTS, props);
+        }
+        // Confirm on quit
+        if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_CONFIRM_ON_QUIT) {
+            let confirm: usize = match self.config().get_confirm_on_quit() {
+                true => 0,
+                false => 1,
+            };
+            let props = RadioPropsBuilder::from(props).with_value(confirm).build();
+            let _ = self
+                .view
+                .update(super::COMPONENT_RADIO_CONFIRM_ON_QUIT, props);
+        }
+        // SSH compression
+        if let Some(props) = self.view.get_props(super::COMPONENT_RADIO_SSH_COMPRESSION) {
+            let compression: usize = match self.config().get_ssh_compression() {
+                Some(true) => 0,
+                Some(false) => 1,
+                None => 2,
+            };
+            let props = RadioPropsBuilder::from(props)
+                .with_value(compression)
+                .build();
+            let _ = self
+                .view
+                .update(super::COMPONENT_RADIO_SSH_COMPRESSION, props);
+        }
     }
 
     /// ### collect_input_values
@@ -397,6 +572,46 @@ impl SetupActivity {
         {
             self.config_mut().set_remote_file_fmt(fmt);
         }
+        if let Some(Payload::One(Value::Usize(jobs))) = self
+            .view
+            .get_state(super::COMPONENT_INPUT_MAX_PARALLEL_TRANSFERS)
+        {
+            self.config_mut().set_max_parallel_transfers(jobs);
+        }
+        if let Some(Payload::One(Value::Usize(opt))) =
+            self.view.get_state(super::COMPONENT_RADIO_RESUME_TRANSFERS)
+        {
+            let resume: bool = matches!(opt, 0);
+            self.config_mut().set_resume_transfers(resume);
+        }
+        if let Some(Payload::One(Value::Usize(opt))) = self
+            .view
+            .get_state(super::COMPONENT_RADIO_PROMPT_ON_FILE_REPLACE)
+        {
+            let prompt: bool = matches!(opt, 0);
+            self.config_mut().set_prompt_on_file_replace(prompt);
+        }
+        if let Some(Payload::One(Value::Usize(max))) =
+            self.view.get_state(super::COMPONENT_INPUT_MAX_RECENTS)
+        {
+            self.config_mut().set_max_recents(max);
+        }
+        if let Some(Payload::One(Value::Usize(opt))) =
+            self.view.get_state(super::COMPONENT_RADIO_CONFIRM_ON_QUIT)
+        {
+            let confirm: bool = matches!(opt, 0);
+            self.config_mut().set_confirm_on_quit(confirm);
+        }
+        if let Some(Payload::One(Value::Usize(opt))) =
+            self.view.get_state(super::COMPONENT_RADIO_SSH_COMPRESSION)
+        {
+            let compression: Option<bool> = match opt {
+                0 => Some(true),
+                1 => Some(false),
+                _ => None,
+            };
+            self.config_mut().set_ssh_compression(compression);
+        }
         if let Some(Payload::One(Value::Usize(opt))) =
             self.view.get_state(super::COMPONENT_RADIO_GROUP_DIRS)
         {
@@ -0,0 +1,329 @@
+//! ## Breadcrumb
+//!
+//! `Breadcrumb` component extends a `Radio` component in order to display a path
+//! as a list of selectable segments, one for each ancestor
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// ext
+use std::path::{Component as PathComponent, Path, PathBuf};
+use tuirealm::components::radio::{Radio, RadioPropsBuilder};
+use tuirealm::event::Event;
+use tuirealm::props::{Props, PropsBuilder};
+use tuirealm::tui::{
+    layout::Rect,
+    style::Color,
+    widgets::{BorderType, Borders},
+};
+use tuirealm::{Canvas, Component, Msg, Payload, Value};
+
+// -- props
+
+/// ## BreadcrumbPropsBuilder
+///
+/// A wrapper around a `RadioPropsBuilder`
+#[derive(Default)]
+pub struct BreadcrumbPropsBuilder {
+    puppet: RadioPropsBuilder,
+}
+
+impl PropsBuilder for BreadcrumbPropsBuilder {
+    fn build(&mut self) -> Props {
+        self.puppet.build()
+    }
+
+    fn hidden(&mut self) -> &mut Self {
+        self.puppet.hidden();
+        self
+    }
+
+    fn visible(&mut self) -> &mut Self {
+        self.puppet.visible();
+        self
+    }
+}
+
+impl From<Props> for BreadcrumbPropsBuilder {
+    fn from(props: Props) -> Self {
+        BreadcrumbPropsBuilder {
+            puppet: RadioPropsBuilder::from(props),
+        }
+    }
+}
+
+impl BreadcrumbPropsBuilder {
+    /// ### with_color
+    ///
+    /// Set color for the selected segment
+    pub fn with_color(&mut self, color: Color) -> &mut Self {
+        self.puppet.with_color(color);
+        self
+    }
+
+    /// ### with_borders
+    ///
+    /// Set component borders style
+    pub fn with_borders(
+        &mut self,
+        borders: Borders,
+        variant: BorderType,
+        color: Color,
+    ) -> &mut Self {
+        self.puppet.with_borders(borders, variant, color);
+        self
+    }
+
+    /// ### with_path
+    ///
+    /// Set the path to display, with its label. The last segment is selected
+    pub fn with_path(&mut self, label: Option<String>, path: &Path) -> &mut Self {
+        let segments: Vec<String> = path_segments(path);
+        let last: usize = segments.len().saturating_sub(1);
+        self.puppet.with_options(label, segments).with_value(last);
+        self
+    }
+}
+
+// -- component
+
+/// ## Breadcrumb
+///
+/// a wrapper component of `Radio` which displays each component of a path as a selectable segment.
+/// When a segment is submitted, the path of the selected ancestor is returned
+pub struct Breadcrumb {
+    radio: Radio,
+}
+
+impl Breadcrumb {
+    /// ### new
+    ///
+    /// Instantiate a new `Breadcrumb`
+    pub fn new(props: Props) -> Self {
+        Self {
+            radio: Radio::new(props),
+        }
+    }
+
+    /// ### ancestor
+    ///
+    /// Get the path of the ancestor at `index`
+    fn ancestor(&self, index: usize) -> PathBuf {
+        let props: Props = self.radio.get_props();
+        props
+            .texts
+            .spans
+            .unwrap_or_default()
+            .iter()
+            .take(index + 1)
+            .map(|x| x.content.as_str())
+            .collect()
+    }
+}
+
+impl Component for Breadcrumb {
+    /// ### render
+    ///
+    /// Based on the current properties and states, renders a widget using the provided render engine in the provided Area
+    /// If focused, cursor is also set (if supported by widget)
+    #[cfg(not(tarpaulin_include))]
+    fn render(&self, render: &mut Canvas, area: Rect) {
+        self.radio.render(render, area);
+    }
+
+    /// ### update
+    ///
+    /// Update component properties
+    /// Properties should first be retrieved through `get_props` which creates a builder from
+    /// existing properties and then edited before calling update.
+    /// Returns a Msg to the view
+    fn update(&mut self, props: Props) -> Msg {
+        match self.radio.update(props) {
+            Msg::OnChange(_) => Msg::OnChange(self.get_state()),
+            msg => msg,
+        }
+    }
+
+    /// ### get_props
+    ///
+    /// Returns a props builder starting from component properties.
+    /// This returns a prop builder in order to make easier to create
+    /// new properties for the element.
+    fn get_props(&self) -> Props {
+        self.radio.get_props()
+    }
+
+    /// ### on
+    ///
+    /// Handle input event and update internal states.
+    /// Returns a Msg to the view
+    fn on(&mut self, ev: Event) -> Msg {
+        match self.radio.on(ev) {
+            Msg::OnChange(_) => Msg::OnChange(self.get_state()),
+            Msg::OnSubmit(_) => Msg::OnSubmit(self.get_state()),
+            msg => msg,
+        }
+    }
+
+    /// ### get_state
+    ///
+    /// Get current state from component
+    /// For this component returns the path of the selected ancestor
+    fn get_state(&self) -> Payload {
+        match self.radio.get_state() {
+            Payload::One(Value::Usize(index)) => Payload::One(Value::Str(
+                self.ancestor(index).to_string_lossy().to_string(),
+            )),
+            _ => Payload::None,
+        }
+    }
+
+    // -- events
+
+    /// ### blur
+    ///
+    /// Blur component; basically remove focus
+    fn blur(&mut self) {
+        self.radio.blur();
+    }
+
+    /// ### active
+    ///
+    /// Active component; basically give focus
+    fn active(&mut self) {
+        self.radio.active();
+    }
+}
+
+/// ### path_segments
+///
+/// Split a path into the segments displayed by the breadcrumb.
+/// The root (and the prefix on Windows) is kept as the first segment, so that
+/// joining the first `n` segments returns the path of the n-th ancestor
+pub fn path_segments(path: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            PathComponent::RootDir => match segments.last_mut() {
+                // Join prefix and root (e.g. `C:\`)
+                Some(prefix) => prefix.push(std::path::MAIN_SEPARATOR),
+                None => segments.push(std::path::MAIN_SEPARATOR.to_string()),
+            },
+            component => segments.push(component.as_os_str().to_string_lossy().to_string()),
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crossterm::event::{KeyCode, KeyEvent};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_ui_components_breadcrumb_path_segments() {
+        assert_eq!(
+            path_segments(Path::new("/home/omar/docs")),
+            vec![
+                String::from("/"),
+                String::from("home"),
+                String::from("omar"),
+                String::from("docs")
+            ]
+        );
+        // Root
+        assert_eq!(path_segments(Path::new("/")), vec![String::from("/")]);
+        // Trailing slash
+        assert_eq!(
+            path_segments(Path::new("/home/omar/")),
+            vec![
+                String::from("/"),
+                String::from("home"),
+                String::from("omar")
+            ]
+        );
+        // Relative path
+        assert_eq!(
+            path_segments(Path::new("omar/docs")),
+            vec![String::from("omar"), String::from("docs")]
+        );
+        // Empty
+        assert!(path_segments(Path::new("")).is_empty());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_ui_components_breadcrumb() {
+        let mut component: Breadcrumb = Breadcrumb::new(
+            BreadcrumbPropsBuilder::default()
+                .visible()
+                .with_color(Color::Yellow)
+                .with_borders(Borders::ALL, BorderType::Rounded, Color::Yellow)
+                .with_path(Some(String::from("Jump to")), Path::new("/home/omar/docs"))
+                .build(),
+        );
+        // Focus
+        component.blur();
+        component.active();
+        // Last segment is selected
+        assert_eq!(
+            component.get_state(),
+            Payload::One(Value::Str(String::from("/home/omar/docs")))
+        );
+        // Move left
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Left))),
+            Msg::OnChange(Payload::One(Value::Str(String::from("/home/omar"))))
+        );
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Left))),
+            Msg::OnChange(Payload::One(Value::Str(String::from("/home"))))
+        );
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Left))),
+            Msg::OnChange(Payload::One(Value::Str(String::from("/"))))
+        );
+        // Submit
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Enter))),
+            Msg::OnSubmit(Payload::One(Value::Str(String::from("/"))))
+        );
+        // Any key
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Char('a')))),
+            Msg::OnKey(KeyEvent::from(KeyCode::Char('a')))
+        );
+        // Update path
+        let props = BreadcrumbPropsBuilder::from(component.get_props())
+            .with_path(None, Path::new("/tmp"))
+            .build();
+        component.update(props);
+        assert_eq!(
+            component.get_state(),
+            Payload::One(Value::Str(String::from("/tmp")))
+        );
+    }
+}
@@ -27,13 +27,13 @@
  */
 // ext
 use tuirealm::components::utils::get_block;
-use tuirealm::event::{Event, KeyCode, KeyModifiers};
+use tuirealm::event::{Event, KeyCode};
 use tuirealm::props::{
     BordersProps, PropPayload, PropValue, Props, PropsBuilder, TextParts, TextSpan,
 };
 use tuirealm::tui::{
     layout::{Corner, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::Span,
     widgets::{BorderType, Borders, List, ListItem, ListState},
 };
@@ -42,6 +42,9 @@ use tuirealm::{Canvas, Component, Msg, Payload, Value};
 // -- props
 
 const PROP_HIGHLIGHT_COLOR: &str = "props-highlight-color";
+const PROP_INDEX: &str = "props-index";
+const PROP_MARKS: &str = "props-marks";
+const PROP_PLACEHOLDER: &str = "props-placeholder";
 
 pub struct FileListPropsBuilder {
     props: Option<Props>,
@@ -141,6 +144,51 @@ impl FileListPropsBuilder {
         }
         self
     }
+
+    /// ### with_placeholder
+    ///
+    /// Set the text displayed when there are no files; if `None`, the list is left blank
+    pub fn with_placeholder(&mut self, placeholder: Option<&str>) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            match placeholder {
+                Some(text) => {
+                    props.own.insert(
+                        PROP_PLACEHOLDER,
+                        PropPayload::One(PropValue::Str(text.to_string())),
+                    );
+                }
+                None => {
+                    props.own.remove(PROP_PLACEHOLDER);
+                }
+            }
+        }
+        self
+    }
+
+    /// ### with_index
+    ///
+    /// Move the cursor to the file at `idx` once props are applied
+    pub fn with_index(&mut self, idx: usize) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props
+                .own
+                .insert(PROP_INDEX, PropPayload::One(PropValue::Usize(idx)));
+        }
+        self
+    }
+
+    /// ### with_marks
+    ///
+    /// Set the indexes of the marked files
+    pub fn with_marks(&mut self, marks: Vec<usize>) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.own.insert(
+                PROP_MARKS,
+                PropPayload::Vec(marks.into_iter().map(PropValue::Usize).collect()),
+            );
+        }
+        self
+    }
 }
 
 // -- states
@@ -201,6 +249,15 @@ impl OwnStates {
         }
     }
 
+    /// ### set_list_index
+    ///
+    /// Move list index to `idx`, if in range
+    pub fn set_list_index(&mut self, idx: usize) {
+        if idx < self.list_len() {
+            self.list_index = idx;
+        }
+    }
+
     /// ### list_len
     ///
     /// Returns the length of the file list, which is actually the capacity of the selection vector
@@ -215,20 +272,6 @@ impl OwnStates {
         self.selected.contains(&entry)
     }
 
-    /// ### is_selection_empty
-    ///
-    /// Returns whether the selection is currently empty
-    pub fn is_selection_empty(&self) -> bool {
-        self.selected.is_empty()
-    }
-
-    /// ### get_selection
-    ///
-    /// Returns current file selection
-    pub fn get_selection(&self) -> Vec<usize> {
-        self.selected.clone()
-    }
-
     /// ### fix_list_index
     ///
     /// Keep index if possible, otherwise set to lenght - 1
@@ -242,22 +285,12 @@ impl OwnStates {
 
     // -- select manipulation
 
-    /// ### toggle_file
-    ///
-    /// Select or deselect file with provided entry index
-    pub fn toggle_file(&mut self, entry: usize) {
-        match self.is_selected(entry) {
-            true => self.deselect(entry),
-            false => self.select(entry),
-        }
-    }
-
-    /// ### select_all
+    /// ### set_selection
     ///
-    /// Select all files
-    pub fn select_all(&mut self) {
-        for i in 0..self.list_len() {
-            self.select(i);
+    /// Set the selected files; indexes out of the list are ignored
+    pub fn set_selection(&mut self, entries: &[usize]) {
+        for entry in entries.iter() {
+            self.select(*entry);
         }
     }
 
@@ -265,19 +298,10 @@ impl OwnStates {
     ///
     /// Select provided index if not selected yet
     fn select(&mut self, entry: usize) {
-        if !self.is_selected(entry) {
+        if entry < self.list_len() && !self.is_selected(entry) {
             self.selected.push(entry);
         }
     }
-
-    /// ### deselect
-    ///
-    /// Remove element file with associated index
-    fn deselect(&mut self, entry: usize) {
-        if self.is_selected(entry) {
-            self.selected.retain(|&x| x != entry);
-        }
-    }
 }
 
 // -- Component
@@ -300,8 +324,25 @@ impl FileList {
         let mut states: OwnStates = OwnStates::default();
         // Init list states
         states.init_list_states(props.texts.spans.as_ref().map(|x| x.len()).unwrap_or(0));
+        states.set_selection(&Self::marks(&props));
         FileList { props, states }
     }
+
+    /// ### marks
+    ///
+    /// Get the indexes of the marked files from props
+    fn marks(props: &Props) -> Vec<usize> {
+        match props.own.get(PROP_MARKS) {
+            Some(PropPayload::Vec(marks)) => marks
+                .iter()
+                .filter_map(|x| match x {
+                    PropValue::Usize(idx) => Some(*idx),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl Component for FileList {
@@ -323,6 +364,18 @@ impl Component for FileList {
                     })
                     .collect(),
             };
+            // Display placeholder if there are no files; it can't be selected
+            let placeholder: Option<&String> = match self.props.own.get(PROP_PLACEHOLDER) {
+                Some(PropPayload::One(PropValue::Str(text))) if list_item.is_empty() => Some(text),
+                _ => None,
+            };
+            let list_item: Vec<ListItem> = match placeholder {
+                Some(text) => vec![ListItem::new(Span::styled(
+                    text.as_str(),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                ))],
+                None => list_item,
+            };
             let highlighted_color: Color = match self.props.own.get(PROP_HIGHLIGHT_COLOR) {
                 Some(PropPayload::One(PropValue::Color(c))) => *c,
                 _ => Color::Reset,
@@ -333,7 +386,9 @@ impl Component for FileList {
             };
             // Render
             let mut state: ListState = ListState::default();
-            state.select(Some(self.states.list_index));
+            if placeholder.is_none() {
+                state.select(Some(self.states.list_index));
+            }
             render.render_stateful_widget(
                 List::new(list_item)
                     .block(get_block(
@@ -370,6 +425,11 @@ impl Component for FileList {
                 .map(|x| x.len())
                 .unwrap_or(0),
         );
+        self.states.set_selection(&Self::marks(&self.props));
+        // Move cursor, if requested; the index is applied once
+        if let Some(PropPayload::One(PropValue::Usize(idx))) = self.props.own.remove(PROP_INDEX) {
+            self.states.set_list_index(idx);
+        }
         Msg::None
     }
 
@@ -391,34 +451,6 @@ impl Component for FileList {
                     self.states.decr_list_index();
                     Msg::None
                 }
-                KeyCode::PageDown => {
-                    // Update states
-                    for _ in 0..8 {
-                        self.states.incr_list_index();
-                    }
-                    Msg::None
-                }
-                KeyCode::PageUp => {
-                    // Update states
-                    for _ in 0..8 {
-                        self.states.decr_list_index();
-                    }
-                    Msg::None
-                }
-                KeyCode::Char('a') => match key.modifiers.intersects(KeyModifiers::CONTROL) {
-                    // CTRL+A
-                    true => {
-                        // Select all
-                        self.states.select_all();
-                        Msg::None
-                    }
-                    false => Msg::OnKey(key),
-                },
-                KeyCode::Char('m') => {
-                    // Toggle current file in selection
-                    self.states.toggle_file(self.states.list_index());
-                    Msg::None
-                }
                 KeyCode::Enter => Msg::OnSubmit(self.get_state()),
                 _ => {
                     // Return key event to activity
@@ -433,20 +465,10 @@ impl Component for FileList {
 
     /// ### get_state
     ///
-    /// Get state returns for this component two different payloads based on the states:
-    /// - if the file selection is empty, returns the highlighted item as `One` of `Usize`
-    /// - if at least one item is selected, return the selected as a `Vec` of `Usize`
+    /// Get state returns the highlighted item as `One` of `Usize`.
+    /// Marked files are owned by the activity, which provides them through props
     fn get_state(&self) -> Payload {
-        match self.states.is_selection_empty() {
-            true => Payload::One(Value::Usize(self.states.list_index())),
-            false => Payload::Vec(
-                self.states
-                    .get_selection()
-                    .into_iter()
-                    .map(Value::Usize)
-                    .collect(),
-            ),
-        }
+        Payload::One(Value::Usize(self.states.list_index()))
     }
 
     // -- events
@@ -484,40 +506,23 @@ mod tests {
         states.init_list_states(4);
         assert_eq!(states.list_len(), 4);
         assert_eq!(states.selected.len(), 0);
-        assert!(states.is_selection_empty());
-        // Select all files
-        states.select_all();
+        // Set selection
+        states.set_selection(&[0, 1, 2, 3]);
         assert_eq!(states.list_len(), 4);
         assert_eq!(states.selected.len(), 4);
-        assert_eq!(states.is_selection_empty(), false);
-        assert_eq!(states.get_selection(), vec![0, 1, 2, 3]);
+        assert!(states.is_selected(2));
         // Verify reset
         states.init_list_states(5);
         assert_eq!(states.list_len(), 5);
         assert_eq!(states.selected.len(), 0);
-        // Toggle file
-        states.toggle_file(2);
-        assert_eq!(states.list_len(), 5);
-        assert_eq!(states.selected.len(), 1);
-        assert_eq!(states.selected[0], 2);
-        states.toggle_file(4);
+        // Out of range and duplicated indexes are ignored
+        states.set_selection(&[2, 4, 4, 8]);
         assert_eq!(states.list_len(), 5);
-        assert_eq!(states.selected.len(), 2);
-        assert_eq!(states.selected[1], 4);
-        states.toggle_file(2);
-        assert_eq!(states.list_len(), 5);
-        assert_eq!(states.selected.len(), 1);
-        assert_eq!(states.selected[0], 4);
+        assert_eq!(states.selected, vec![2, 4]);
         // Select twice (nothing should change)
         states.select(4);
         assert_eq!(states.list_len(), 5);
-        assert_eq!(states.selected.len(), 1);
-        assert_eq!(states.selected[0], 4);
-        // Deselect not-selectd item
-        states.deselect(2);
-        assert_eq!(states.list_len(), 5);
-        assert_eq!(states.selected.len(), 1);
-        assert_eq!(states.selected[0], 4);
+        assert_eq!(states.selected.len(), 2);
         // Index
         states.init_list_states(2);
         states.incr_list_index();
@@ -588,6 +593,20 @@ mod tests {
         assert_eq!(component.update(props), Msg::None);
         assert_eq!(component.props.visible, false);
         assert_eq!(component.props.foreground, Color::Yellow);
+        // Placeholder
+        let props = FileListPropsBuilder::from(component.get_props())
+            .with_placeholder(Some("(empty directory)"))
+            .build();
+        component.update(props);
+        assert_eq!(
+            *component.props.own.get(PROP_PLACEHOLDER).unwrap(),
+            PropPayload::One(PropValue::Str(String::from("(empty directory)")))
+        );
+        let props = FileListPropsBuilder::from(component.get_props())
+            .with_placeholder(None)
+            .build();
+        component.update(props);
+        assert!(!component.props.own.contains_key(PROP_PLACEHOLDER));
         // Increment list index
         component.states.list_index += 1;
         assert_eq!(component.states.list_index, 1);
@@ -609,6 +628,22 @@ mod tests {
         assert_eq!(component.states.list_len(), 3);
         // get value
         assert_eq!(component.get_state(), Payload::One(Value::Usize(1)));
+        // Move cursor
+        component.update(
+            FileListPropsBuilder::from(component.get_props())
+                .with_index(2)
+                .build(),
+        );
+        assert_eq!(component.states.list_index, 2);
+        assert!(!component.get_props().own.contains_key(PROP_INDEX));
+        // Out of range index is ignored
+        component.update(
+            FileListPropsBuilder::from(component.get_props())
+                .with_index(8)
+                .build(),
+        );
+        assert_eq!(component.states.list_index, 2);
+        component.states.list_index = 1;
         // Render
         assert_eq!(component.states.list_index, 1);
         // Handle inputs
@@ -625,20 +660,20 @@ mod tests {
         );
         // Index should be incremented
         assert_eq!(component.states.list_index, 1);
-        // Index should be 2
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::PageDown))),
-            Msg::None
-        );
-        // Index should be incremented
-        assert_eq!(component.states.list_index, 2);
-        // Index should be 0
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::PageUp))),
-            Msg::None
-        );
-        // Index should be incremented
-        assert_eq!(component.states.list_index, 0);
+        // Page and jump keys are returned to the activity, which sets the index
+        for code in [
+            KeyCode::PageDown,
+            KeyCode::PageUp,
+            KeyCode::Home,
+            KeyCode::End,
+        ] {
+            assert_eq!(
+                component.on(Event::Key(KeyEvent::from(code))),
+                Msg::OnKey(KeyEvent::from(code))
+            );
+            assert_eq!(component.states.list_index, 1);
+        }
+        component.states.list_index = 0;
         // Enter
         assert_eq!(
             component.on(Event::Key(KeyEvent::from(KeyCode::Enter))),
@@ -654,15 +689,15 @@ mod tests {
             component.on(Event::Key(KeyEvent::from(KeyCode::Char('a')))),
             Msg::OnKey(KeyEvent::from(KeyCode::Char('a')))
         );
-        // Ctrl + a
+        // Ctrl + a is returned to the activity
         assert_eq!(
             component.on(Event::Key(KeyEvent::new(
                 KeyCode::Char('a'),
                 KeyModifiers::CONTROL
             ))),
-            Msg::None
+            Msg::OnKey(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL))
         );
-        assert_eq!(component.states.selected.len(), component.states.list_len());
+        assert_eq!(component.states.selected.len(), 0);
     }
 
     #[test]
@@ -678,61 +713,26 @@ mod tests {
                         String::from("file3"),
                     ],
                 )
+                .with_marks(vec![1])
                 .build(),
         );
-        // Get state
+        assert!(component.states.is_selected(1));
+        // Get state returns the highlighted item
         assert_eq!(component.get_state(), Payload::One(Value::Usize(0)));
-        // Select one
+        // Marking is up to the activity
         assert_eq!(
             component.on(Event::Key(KeyEvent::from(KeyCode::Char('m')))),
-            Msg::None
+            Msg::OnKey(KeyEvent::from(KeyCode::Char('m')))
         );
-        // Now should be a vec
-        assert_eq!(component.get_state(), Payload::Vec(vec![Value::Usize(0)]));
-        // De-select
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::Char('m')))),
-            Msg::None
-        );
-        assert_eq!(component.get_state(), Payload::One(Value::Usize(0)));
-        // Go down
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::Down))),
-            Msg::None
-        );
-        // Select
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::Char('m')))),
-            Msg::None
-        );
-        assert_eq!(component.get_state(), Payload::Vec(vec![Value::Usize(1)]));
-        // Go down and select
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::Down))),
-            Msg::None
-        );
-        assert_eq!(
-            component.on(Event::Key(KeyEvent::from(KeyCode::Char('m')))),
-            Msg::None
-        );
-        assert_eq!(
-            component.get_state(),
-            Payload::Vec(vec![Value::Usize(1), Value::Usize(2)])
-        );
-        // Select all
-        assert_eq!(
-            component.on(Event::Key(KeyEvent {
-                code: KeyCode::Char('a'),
-                modifiers: KeyModifiers::CONTROL,
-            })),
-            Msg::None
-        );
-        // All selected
-        assert_eq!(
-            component.get_state(),
-            Payload::Vec(vec![Value::Usize(1), Value::Usize(2), Value::Usize(0)])
+        assert_eq!(component.states.selected, vec![1]);
+        // Update marks
+        component.update(
+            FileListPropsBuilder::from(component.get_props())
+                .with_marks(vec![0, 2])
+                .build(),
         );
-        // Update files
+        assert_eq!(component.states.selected, vec![0, 2]);
+        // Update files; marks out of the list are discarded
         component.update(
             FileListPropsBuilder::from(component.get_props())
                 .with_files(
@@ -741,7 +741,7 @@ mod tests {
                 )
                 .build(),
         );
-        // Selection should now be empty
-        assert_eq!(component.get_state(), Payload::One(Value::Usize(1)));
+        assert_eq!(component.states.selected, vec![0]);
+        assert_eq!(component.get_state(), Payload::One(Value::Usize(0)));
     }
 }
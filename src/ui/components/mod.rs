@@ -27,7 +27,9 @@
  */
 // exports
 pub mod bookmark_list;
+pub mod breadcrumb;
 pub mod color_picker;
 pub mod file_list;
 pub mod logbox;
 pub mod msgbox;
+pub mod progress_bar;
@@ -0,0 +1,718 @@
+//! ## ProgressBar
+//!
+//! `ProgressBar` component renders a gauge with a label and, optionally, a secondary row beneath it
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// ext
+use std::cmp::Ordering;
+use std::collections::LinkedList;
+use tuirealm::components::utils::get_block;
+use tuirealm::event::Event;
+use tuirealm::props::{
+    BordersProps, PropPayload, PropValue, Props, PropsBuilder, TextParts, TextSpan,
+};
+use tuirealm::tui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{BorderType, Borders, Gauge, Paragraph},
+};
+use tuirealm::{Canvas, Component, Msg, Payload, Value};
+
+// -- Props
+
+const PROP_PROGRESS: &str = "progress";
+const PROP_RATE: &str = "rate";
+const PROP_STALLED: &str = "stalled";
+const PROP_THRESHOLDS: &str = "thresholds";
+
+pub struct ProgressBarPropsBuilder {
+    props: Option<Props>,
+}
+
+impl Default for ProgressBarPropsBuilder {
+    fn default() -> Self {
+        ProgressBarPropsBuilder {
+            props: Some(Props::default()),
+        }
+    }
+}
+
+impl PropsBuilder for ProgressBarPropsBuilder {
+    fn build(&mut self) -> Props {
+        self.props.take().unwrap()
+    }
+
+    fn hidden(&mut self) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.visible = false;
+        }
+        self
+    }
+
+    fn visible(&mut self) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.visible = true;
+        }
+        self
+    }
+}
+
+impl From<Props> for ProgressBarPropsBuilder {
+    fn from(props: Props) -> Self {
+        ProgressBarPropsBuilder { props: Some(props) }
+    }
+}
+
+impl ProgressBarPropsBuilder {
+    /// ### with_progbar_color
+    ///
+    /// Set progbar color for component
+    pub fn with_progbar_color(&mut self, color: Color) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.foreground = color;
+        }
+        self
+    }
+
+    /// ### with_background
+    ///
+    /// Set background color for component
+    pub fn with_background(&mut self, color: Color) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.background = color;
+        }
+        self
+    }
+
+    /// ### with_borders
+    ///
+    /// Set component borders style
+    pub fn with_borders(
+        &mut self,
+        borders: Borders,
+        variant: BorderType,
+        color: Color,
+    ) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.borders = BordersProps {
+                borders,
+                variant,
+                color,
+            }
+        }
+        self
+    }
+
+    /// ### with_texts
+    ///
+    /// Set block title text and progress bar label.
+    /// If `details` is provided, it is displayed in a second row beneath the gauge
+    pub fn with_texts(
+        &mut self,
+        title: Option<String>,
+        label: String,
+        details: Option<String>,
+    ) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            let mut rows: Vec<TextSpan> = vec![TextSpan::from(label)];
+            if let Some(details) = details {
+                rows.push(TextSpan::from(details));
+            }
+            props.texts = TextParts::new(title, Some(rows));
+        }
+        self
+    }
+
+    /// ### with_threshold
+    ///
+    /// Use `color` for the gauge once progress reaches `ratio`.
+    /// When many thresholds are reached, the one with the highest ratio is used
+    pub fn with_threshold(&mut self, ratio: f64, color: Color) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            let threshold: PropPayload =
+                PropPayload::Tup2((PropValue::F64(ratio), PropValue::Color(color)));
+            match props.own.get_mut(PROP_THRESHOLDS) {
+                Some(PropPayload::Linked(thresholds)) => thresholds.push_back(threshold),
+                _ => {
+                    let mut thresholds: LinkedList<PropPayload> = LinkedList::new();
+                    thresholds.push_back(threshold);
+                    props
+                        .own
+                        .insert(PROP_THRESHOLDS, PropPayload::Linked(thresholds));
+                }
+            }
+        }
+        self
+    }
+
+    /// ### with_stalled_color
+    ///
+    /// Use `color` for the gauge while the transfer rate is below `min_rate` bytes per second.
+    /// The stalled color takes precedence over thresholds
+    pub fn with_stalled_color(&mut self, min_rate: u64, color: Color) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            props.own.insert(
+                PROP_STALLED,
+                PropPayload::Tup2((PropValue::U64(min_rate), PropValue::Color(color))),
+            );
+        }
+        self
+    }
+
+    /// ### with_rate
+    ///
+    /// Set current transfer rate in bytes per second; `None` if not known yet
+    pub fn with_rate(&mut self, rate: Option<u64>) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            match rate {
+                Some(rate) => {
+                    props
+                        .own
+                        .insert(PROP_RATE, PropPayload::One(PropValue::U64(rate)));
+                }
+                None => {
+                    props.own.remove(PROP_RATE);
+                }
+            }
+        }
+        self
+    }
+
+    /// ### with_progress
+    ///
+    /// Set progress percentage
+    /// Progress must be in range [0.0,1.0] or will panic
+    pub fn with_progress(&mut self, prog: f64) -> &mut Self {
+        if let Some(props) = self.props.as_mut() {
+            assert!(
+                (0.0..=1.0).contains(&prog),
+                "Progress must be in range [0.0,1.0]"
+            );
+            props
+                .own
+                .insert(PROP_PROGRESS, PropPayload::One(PropValue::F64(prog)));
+        }
+        self
+    }
+}
+
+// -- Component
+
+/// ## ProgressBar
+///
+/// provides a component which shows the progress, with a label on the gauge and an optional row beneath it
+pub struct ProgressBar {
+    props: Props,
+}
+
+impl ProgressBar {
+    /// ### new
+    ///
+    /// Instantiates a new `ProgressBar` component.
+    pub fn new(props: Props) -> Self {
+        ProgressBar { props }
+    }
+
+    /// ### percentage
+    ///
+    /// Returns the current progress as percentage, in range [0.0,100.0]
+    #[allow(dead_code)]
+    pub fn percentage(&self) -> f64 {
+        self.progress() * 100.0
+    }
+
+    /// ### label
+    ///
+    /// Returns the label to display on the gauge
+    fn label(&self) -> String {
+        self.row(0).unwrap_or_default()
+    }
+
+    /// ### details
+    ///
+    /// Returns the secondary row to display beneath the gauge, if any
+    fn details(&self) -> Option<String> {
+        self.row(1)
+    }
+
+    fn row(&self, idx: usize) -> Option<String> {
+        self.props
+            .texts
+            .spans
+            .as_ref()
+            .and_then(|rows| rows.get(idx))
+            .map(|x| x.content.clone())
+    }
+
+    /// ### progress
+    ///
+    /// Returns the progress ratio, clamped in range [0.0,1.0]
+    fn progress(&self) -> f64 {
+        match self.props.own.get(PROP_PROGRESS) {
+            Some(PropPayload::One(PropValue::F64(ratio))) => clamp_ratio(*ratio),
+            _ => 0.0,
+        }
+    }
+
+    /// ### has_block
+    ///
+    /// Returns whether the gauge must be wrapped in a block.
+    /// The block is omitted when there are no borders and no title, so the gauge can use the entire area
+    fn has_block(&self) -> bool {
+        self.props.borders.borders != Borders::NONE || self.props.texts.title.is_some()
+    }
+
+    /// ### progbar_color
+    ///
+    /// Returns the color to use for the gauge, based on progress, rate and configured thresholds
+    fn progbar_color(&self) -> Color {
+        let thresholds: Vec<(f64, Color)> = match self.props.own.get(PROP_THRESHOLDS) {
+            Some(PropPayload::Linked(thresholds)) => thresholds
+                .iter()
+                .filter_map(|x| match x {
+                    PropPayload::Tup2((PropValue::F64(ratio), PropValue::Color(color))) => {
+                        Some((*ratio, *color))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let rate: Option<u64> = match self.props.own.get(PROP_RATE) {
+            Some(PropPayload::One(PropValue::U64(rate))) => Some(*rate),
+            _ => None,
+        };
+        let stalled: Option<(u64, Color)> = match self.props.own.get(PROP_STALLED) {
+            Some(PropPayload::Tup2((PropValue::U64(min_rate), PropValue::Color(color)))) => {
+                Some((*min_rate, *color))
+            }
+            _ => None,
+        };
+        select_color(
+            self.progress(),
+            rate,
+            thresholds.as_slice(),
+            stalled,
+            self.props.foreground,
+        )
+    }
+}
+
+/// ### clamp_ratio
+///
+/// Clamp `ratio` in range [0.0,1.0], since the gauge panics otherwise. NaN is coerced to 0.0
+fn clamp_ratio(ratio: f64) -> f64 {
+    match ratio.is_nan() {
+        true => 0.0,
+        false => ratio.clamp(0.0, 1.0),
+    }
+}
+
+/// ### select_color
+///
+/// Select the gauge color given the progress `ratio` and the transfer `rate`.
+/// If the rate is known and below the stalled rate, the stalled color is returned;
+/// otherwise the color of the highest threshold reached is returned, or `default` if none has been reached
+fn select_color(
+    ratio: f64,
+    rate: Option<u64>,
+    thresholds: &[(f64, Color)],
+    stalled: Option<(u64, Color)>,
+    default: Color,
+) -> Color {
+    match (rate, stalled) {
+        (Some(rate), Some((min_rate, color))) if rate < min_rate && ratio < 1.0 => color,
+        _ => thresholds
+            .iter()
+            .filter(|(min, _)| ratio >= *min)
+            .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(_, color)| *color)
+            .unwrap_or(default),
+    }
+}
+
+impl Component for ProgressBar {
+    /// ### render
+    ///
+    /// Based on the current properties and states, renders a widget using the provided render engine in the provided Area
+    /// If focused, cursor is also set (if supported by widget)
+    #[cfg(not(tarpaulin_include))]
+    fn render(&self, render: &mut Canvas, area: Rect) {
+        if self.props.visible {
+            let gauge = Gauge::default()
+                .gauge_style(
+                    Style::default()
+                        .fg(self.progbar_color())
+                        .bg(self.props.background)
+                        .add_modifier(self.props.modifiers),
+                )
+                .label(self.label())
+                .ratio(self.progress());
+            // Render block, unless the gauge can use the entire area
+            let area: Rect = match self.has_block() {
+                true => {
+                    let div = get_block(&self.props.borders, &self.props.texts.title, true);
+                    let inner: Rect = div.inner(area);
+                    render.render_widget(div, area);
+                    inner
+                }
+                false => area,
+            };
+            match self.details() {
+                None => render.render_widget(gauge, area),
+                Some(details) => {
+                    // Split area into gauge and details row
+                    let chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                        .split(area);
+                    render.render_widget(gauge, chunks[0]);
+                    render.render_widget(
+                        Paragraph::new(details).alignment(Alignment::Center),
+                        chunks[1],
+                    );
+                }
+            }
+        }
+    }
+
+    /// ### update
+    ///
+    /// Update component properties
+    /// Properties should first be retrieved through `get_props` which creates a builder from
+    /// existing properties and then edited before calling update.
+    /// Returns a Msg to the view
+    fn update(&mut self, props: Props) -> Msg {
+        self.props = props;
+        // Return None
+        Msg::None
+    }
+
+    /// ### get_props
+    ///
+    /// Returns a copy of the component properties.
+    fn get_props(&self) -> Props {
+        self.props.clone()
+    }
+
+    /// ### on
+    ///
+    /// Handle input event and update internal states.
+    /// Returns a Msg to the view.
+    fn on(&mut self, ev: Event) -> Msg {
+        // Return key
+        if let Event::Key(key) = ev {
+            Msg::OnKey(key)
+        } else {
+            Msg::None
+        }
+    }
+
+    /// ### get_state
+    ///
+    /// Get current state from component
+    /// For this component returns the progress ratio, in range [0.0,1.0].
+    /// Since `Value` has no float variant, the ratio is returned as string, which parses back to the same value
+    fn get_state(&self) -> Payload {
+        Payload::One(Value::Str(self.progress().to_string()))
+    }
+
+    // -- events
+
+    /// ### blur
+    ///
+    /// Blur component
+    fn blur(&mut self) {}
+
+    /// ### active
+    ///
+    /// Active component
+    fn active(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use tuirealm::event::{KeyCode, KeyEvent};
+
+    #[test]
+    fn test_ui_components_progress_bar() {
+        let mut component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .hidden()
+                .visible()
+                .with_progress(0.60)
+                .with_progbar_color(Color::Red)
+                .with_background(Color::Blue)
+                .with_texts(None, String::from("60% - ETA: 00:20"), None)
+                .with_borders(Borders::ALL, BorderType::Double, Color::Red)
+                .build(),
+        );
+        assert_eq!(component.props.foreground, Color::Red);
+        assert_eq!(component.props.background, Color::Blue);
+        assert_eq!(component.props.visible, true);
+        assert_eq!(component.props.borders.borders, Borders::ALL);
+        assert_eq!(component.props.borders.variant, BorderType::Double);
+        assert_eq!(component.props.borders.color, Color::Red);
+        assert_eq!(component.progress(), 0.60);
+        assert_eq!(component.label().as_str(), "60% - ETA: 00:20");
+        assert_eq!(component.details(), None);
+        // Get value
+        assert_eq!(
+            component.get_state(),
+            Payload::One(Value::Str(String::from("0.6")))
+        );
+        component.active();
+        component.blur();
+        // Update
+        let props = ProgressBarPropsBuilder::from(component.get_props())
+            .with_progbar_color(Color::Yellow)
+            .hidden()
+            .build();
+        assert_eq!(component.update(props), Msg::None);
+        assert_eq!(component.props.foreground, Color::Yellow);
+        assert_eq!(component.props.visible, false);
+        // Event
+        assert_eq!(
+            component.on(Event::Key(KeyEvent::from(KeyCode::Delete))),
+            Msg::OnKey(KeyEvent::from(KeyCode::Delete))
+        );
+        assert_eq!(component.on(Event::Resize(0, 0)), Msg::None);
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_block() {
+        // Default has borders
+        let component: ProgressBar = ProgressBar::new(ProgressBarPropsBuilder::default().build());
+        assert_eq!(component.has_block(), true);
+        // No borders and no title
+        let component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_borders(Borders::NONE, BorderType::Plain, Color::Reset)
+                .with_texts(None, String::from("50%"), None)
+                .build(),
+        );
+        assert_eq!(component.has_block(), false);
+        // No borders, but title
+        let component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_borders(Borders::NONE, BorderType::Plain, Color::Reset)
+                .with_texts(Some(String::from("file.txt")), String::from("50%"), None)
+                .build(),
+        );
+        assert_eq!(component.has_block(), true);
+        // Borders, but no title
+        let component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_borders(Borders::TOP, BorderType::Plain, Color::Reset)
+                .with_texts(None, String::from("50%"), None)
+                .build(),
+        );
+        assert_eq!(component.has_block(), true);
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_percentage() {
+        let component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_progress(0.25)
+                .build(),
+        );
+        assert_eq!(component.percentage(), 25.0);
+        match component.get_state() {
+            Payload::One(Value::Str(ratio)) => {
+                assert_eq!(ratio.parse::<f64>().ok().unwrap(), 0.25)
+            }
+            state => panic!("Unexpected state {:?}", state),
+        }
+        // No progress set
+        let component: ProgressBar = ProgressBar::new(ProgressBarPropsBuilder::default().build());
+        assert_eq!(component.percentage(), 0.0);
+        assert_eq!(
+            component.get_state(),
+            Payload::One(Value::Str(String::from("0")))
+        );
+        // Complete
+        let component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_progress(1.0)
+                .build(),
+        );
+        assert_eq!(component.percentage(), 100.0);
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_details() {
+        let mut component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_progress(0.30)
+                .with_texts(
+                    Some(String::from("file.txt")),
+                    String::from("30.00% - ETA 00:07 (1.0 MB/s)"),
+                    Some(String::from("1.2 GB / 4.0 GB")),
+                )
+                .build(),
+        );
+        assert_eq!(component.label().as_str(), "30.00% - ETA 00:07 (1.0 MB/s)");
+        assert_eq!(component.details().unwrap().as_str(), "1.2 GB / 4.0 GB");
+        // Back to a single row
+        let props = ProgressBarPropsBuilder::from(component.get_props())
+            .with_texts(Some(String::from("file.txt")), String::from("100%"), None)
+            .with_progress(1.0)
+            .build();
+        assert_eq!(component.update(props), Msg::None);
+        assert_eq!(component.label().as_str(), "100%");
+        assert_eq!(component.details(), None);
+        assert_eq!(component.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_colors() {
+        let mut component: ProgressBar = ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_progbar_color(Color::Green)
+                .with_progress(0.10)
+                .build(),
+        );
+        // Default color
+        assert_eq!(component.progbar_color(), Color::Green);
+        let props = ProgressBarPropsBuilder::from(component.get_props())
+            .with_threshold(0.5, Color::Yellow)
+            .with_threshold(0.9, Color::LightGreen)
+            .with_stalled_color(1, Color::Red)
+            .with_rate(Some(1024))
+            .with_progress(0.95)
+            .build();
+        component.update(props);
+        assert_eq!(component.progbar_color(), Color::LightGreen);
+        // Stalled
+        let props = ProgressBarPropsBuilder::from(component.get_props())
+            .with_rate(Some(0))
+            .build();
+        component.update(props);
+        assert_eq!(component.progbar_color(), Color::Red);
+        // Rate unknown
+        let props = ProgressBarPropsBuilder::from(component.get_props())
+            .with_rate(None)
+            .with_progress(0.6)
+            .build();
+        component.update(props);
+        assert_eq!(component.progbar_color(), Color::Yellow);
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_select_color() {
+        let thresholds: Vec<(f64, Color)> = vec![
+            (0.9, Color::LightGreen),
+            (0.0, Color::Blue),
+            (0.5, Color::Yellow),
+        ];
+        let stalled: Option<(u64, Color)> = Some((512, Color::Red));
+        // No thresholds
+        assert_eq!(
+            select_color(0.5, None, &[], None, Color::Reset),
+            Color::Reset
+        );
+        // Highest reached threshold wins, regardless of order
+        assert_eq!(
+            select_color(0.1, None, thresholds.as_slice(), None, Color::Reset),
+            Color::Blue
+        );
+        assert_eq!(
+            select_color(0.5, None, thresholds.as_slice(), None, Color::Reset),
+            Color::Yellow
+        );
+        assert_eq!(
+            select_color(0.89, None, thresholds.as_slice(), None, Color::Reset),
+            Color::Yellow
+        );
+        assert_eq!(
+            select_color(1.0, None, thresholds.as_slice(), None, Color::Reset),
+            Color::LightGreen
+        );
+        // Below every threshold
+        assert_eq!(
+            select_color(0.2, None, &[(0.5, Color::Yellow)], None, Color::Reset),
+            Color::Reset
+        );
+        // Stalled
+        assert_eq!(
+            select_color(0.2, Some(100), thresholds.as_slice(), stalled, Color::Reset),
+            Color::Red
+        );
+        assert_eq!(
+            select_color(0.2, Some(512), thresholds.as_slice(), stalled, Color::Reset),
+            Color::Blue
+        );
+        // Unknown rate or completed transfer are never stalled
+        assert_eq!(
+            select_color(0.2, None, thresholds.as_slice(), stalled, Color::Reset),
+            Color::Blue
+        );
+        assert_eq!(
+            select_color(1.0, Some(0), thresholds.as_slice(), stalled, Color::Reset),
+            Color::LightGreen
+        );
+    }
+
+    #[test]
+    fn test_ui_components_progress_bar_clamp_ratio() {
+        assert_eq!(clamp_ratio(0.0), 0.0);
+        assert_eq!(clamp_ratio(0.5), 0.5);
+        assert_eq!(clamp_ratio(1.0), 1.0);
+        assert_eq!(clamp_ratio(1.5), 1.0);
+        assert_eq!(clamp_ratio(-0.2), 0.0);
+        assert_eq!(clamp_ratio(f64::NAN), 0.0);
+        assert_eq!(clamp_ratio(f64::INFINITY), 1.0);
+        assert_eq!(clamp_ratio(f64::NEG_INFINITY), 0.0);
+        // Progress set bypassing the builder is clamped too
+        let mut props: Props = ProgressBarPropsBuilder::default().build();
+        props
+            .own
+            .insert(PROP_PROGRESS, PropPayload::One(PropValue::F64(1.5)));
+        let component: ProgressBar = ProgressBar::new(props);
+        assert_eq!(component.progress(), 1.0);
+        assert_eq!(component.percentage(), 100.0);
+        let mut props: Props = ProgressBarPropsBuilder::default().build();
+        props
+            .own
+            .insert(PROP_PROGRESS, PropPayload::One(PropValue::F64(f64::NAN)));
+        let component: ProgressBar = ProgressBar::new(props);
+        assert_eq!(component.progress(), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ui_components_progress_bar_bad_prog() {
+        ProgressBar::new(
+            ProgressBarPropsBuilder::default()
+                .with_progress(60.0)
+                .build(),
+        );
+    }
+}
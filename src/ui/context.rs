@@ -28,7 +28,7 @@
 // Locals
 use super::input::InputHandler;
 use super::store::Store;
-use crate::filetransfer::FileTransferParams;
+use crate::filetransfer::{FileTransferParams, TransferOpts};
 use crate::system::config_client::ConfigClient;
 use crate::system::theme_provider::ThemeProvider;
 
@@ -36,7 +36,7 @@ use crate::system::theme_provider::ThemeProvider;
 use crossterm::event::DisableMouseCapture;
 use crossterm::execute;
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
-use std::io::{stdout, Stdout};
+use std::io::{stdout, Stdout, Write};
 use tuirealm::tui::backend::CrosstermBackend;
 use tuirealm::tui::Terminal;
 
@@ -47,6 +47,7 @@ type TuiTerminal = Terminal<CrosstermBackend<Stdout>>;
 /// Context holds data structures used by the ui
 pub struct Context {
     ft_params: Option<FileTransferParams>,
+    transfer_opts: TransferOpts,
     config_client: ConfigClient,
     pub(crate) store: Store,
     input_hnd: InputHandler,
@@ -69,6 +70,7 @@ impl Context {
         assert!(execute!(stdout, EnterAlternateScreen).is_ok());
         Context {
             ft_params: None,
+            transfer_opts: TransferOpts::default(),
             config_client,
             store: Store::init(),
             input_hnd: InputHandler::new(),
@@ -84,6 +86,14 @@ impl Context {
         self.ft_params.as_ref()
     }
 
+    pub fn transfer_opts(&self) -> &TransferOpts {
+        &self.transfer_opts
+    }
+
+    pub fn transfer_opts_mut(&mut self) -> &mut TransferOpts {
+        &mut self.transfer_opts
+    }
+
     pub fn config(&self) -> &ConfigClient {
         &self.config_client
     }
@@ -122,6 +132,10 @@ impl Context {
         self.ft_params = Some(params);
     }
 
+    pub fn set_transfer_opts(&mut self, opts: TransferOpts) {
+        self.transfer_opts = opts;
+    }
+
     // -- error
 
     /// ### set_error
@@ -167,6 +181,20 @@ impl Context {
         }
     }
 
+    /// ### ring_bell
+    ///
+    /// Write terminal bells (`\x07`) through the terminal backend; bells don't move the cursor,
+    /// so they don't affect what has been drawn
+    pub fn ring_bell(&mut self, bells: &str) {
+        let backend: &mut CrosstermBackend<Stdout> = self.terminal.backend_mut();
+        if let Err(err) = backend
+            .write_all(bells.as_bytes())
+            .and_then(|_| backend.flush())
+        {
+            error!("Failed to ring terminal bell: {}", err);
+        }
+    }
+
     /// ### clear_screen
     ///
     /// Clear terminal screen
@@ -64,7 +64,14 @@ impl InputHandler {
     ///
     /// Read event from input listener
     pub(crate) fn read_event(&self) -> Result<Option<Event>, ()> {
-        if let Ok(available) = poll(Duration::from_millis(10)) {
+        self.wait_event(Duration::from_millis(10))
+    }
+
+    /// ### wait_event
+    ///
+    /// Wait up to `timeout` for an event from input listener
+    pub(crate) fn wait_event(&self, timeout: Duration) -> Result<Option<Event>, ()> {
+        if let Ok(available) = poll(timeout) {
             match available {
                 true => {
                     // Read event
@@ -50,6 +50,22 @@ pub const MSG_KEY_BACKSPACE: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Backspace,
     modifiers: KeyModifiers::NONE,
 });
+pub const MSG_KEY_HOME: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Home,
+    modifiers: KeyModifiers::NONE,
+});
+pub const MSG_KEY_END: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::End,
+    modifiers: KeyModifiers::NONE,
+});
+pub const MSG_KEY_PAGE_UP: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::PageUp,
+    modifiers: KeyModifiers::NONE,
+});
+pub const MSG_KEY_PAGE_DOWN: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::PageDown,
+    modifiers: KeyModifiers::NONE,
+});
 pub const MSG_KEY_DOWN: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Down,
     modifiers: KeyModifiers::NONE,
@@ -109,7 +125,6 @@ pub const MSG_KEY_CHAR_I: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('i'),
     modifiers: KeyModifiers::NONE,
 });
-/*
 pub const MSG_KEY_CHAR_J: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('j'),
     modifiers: KeyModifiers::NONE,
@@ -118,17 +133,14 @@ pub const MSG_KEY_CHAR_K: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('k'),
     modifiers: KeyModifiers::NONE,
 });
-*/
 pub const MSG_KEY_CHAR_L: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('l'),
     modifiers: KeyModifiers::NONE,
 });
-/*
-pub const MSG_KEY_CHAR_M: Msg = Msg::OnKey(KeyEvent { NOTE: used for mark
+pub const MSG_KEY_CHAR_M: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('m'),
     modifiers: KeyModifiers::NONE,
 });
-*/
 pub const MSG_KEY_CHAR_N: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('n'),
     modifiers: KeyModifiers::NONE,
@@ -137,12 +149,10 @@ pub const MSG_KEY_CHAR_O: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('o'),
     modifiers: KeyModifiers::NONE,
 });
-/*
 pub const MSG_KEY_CHAR_P: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('p'),
     modifiers: KeyModifiers::NONE,
 });
-*/
 pub const MSG_KEY_CHAR_Q: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('q'),
     modifiers: KeyModifiers::NONE,
@@ -155,12 +165,10 @@ pub const MSG_KEY_CHAR_S: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('s'),
     modifiers: KeyModifiers::NONE,
 });
-/*
 pub const MSG_KEY_CHAR_T: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('t'),
     modifiers: KeyModifiers::NONE,
 });
-*/
 pub const MSG_KEY_CHAR_U: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('u'),
     modifiers: KeyModifiers::NONE,
@@ -181,22 +189,44 @@ pub const MSG_KEY_CHAR_Y: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('y'),
     modifiers: KeyModifiers::NONE,
 });
-/*
 pub const MSG_KEY_CHAR_Z: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('z'),
     modifiers: KeyModifiers::NONE,
 });
-*/
+pub const MSG_KEY_CHAR_LESS: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('<'),
+    modifiers: KeyModifiers::NONE,
+});
+pub const MSG_KEY_CHAR_GREATER: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('>'),
+    modifiers: KeyModifiers::NONE,
+});
+pub const MSG_KEY_CHAR_TILDE: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('~'),
+    modifiers: KeyModifiers::NONE,
+});
 
 // -- control
+pub const MSG_KEY_CTRL_A: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('a'),
+    modifiers: KeyModifiers::CONTROL,
+});
 pub const MSG_KEY_CTRL_C: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('c'),
     modifiers: KeyModifiers::CONTROL,
 });
+pub const MSG_KEY_CTRL_D: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('d'),
+    modifiers: KeyModifiers::CONTROL,
+});
 pub const MSG_KEY_CTRL_E: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('e'),
     modifiers: KeyModifiers::CONTROL,
 });
+pub const MSG_KEY_CTRL_G: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('g'),
+    modifiers: KeyModifiers::CONTROL,
+});
 pub const MSG_KEY_CTRL_H: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('h'),
     modifiers: KeyModifiers::CONTROL,
@@ -205,6 +235,10 @@ pub const MSG_KEY_CTRL_N: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('n'),
     modifiers: KeyModifiers::CONTROL,
 });
+pub const MSG_KEY_CTRL_P: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('p'),
+    modifiers: KeyModifiers::CONTROL,
+});
 pub const MSG_KEY_CTRL_R: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('r'),
     modifiers: KeyModifiers::CONTROL,
@@ -213,3 +247,23 @@ pub const MSG_KEY_CTRL_S: Msg = Msg::OnKey(KeyEvent {
     code: KeyCode::Char('s'),
     modifiers: KeyModifiers::CONTROL,
 });
+pub const MSG_KEY_CTRL_T: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('t'),
+    modifiers: KeyModifiers::CONTROL,
+});
+pub const MSG_KEY_CTRL_U: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('u'),
+    modifiers: KeyModifiers::CONTROL,
+});
+pub const MSG_KEY_CTRL_V: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('v'),
+    modifiers: KeyModifiers::CONTROL,
+});
+pub const MSG_KEY_CTRL_W: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('w'),
+    modifiers: KeyModifiers::CONTROL,
+});
+pub const MSG_KEY_CTRL_X: Msg = Msg::OnKey(KeyEvent {
+    code: KeyCode::Char('x'),
+    modifiers: KeyModifiers::CONTROL,
+});
@@ -28,7 +28,7 @@
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
 
 /// ### open_file
 ///
@@ -45,13 +45,383 @@ where
         .open(filename)
 }
 
+/// ### numbered_path
+///
+/// Returns the first path in the form of `name (n).ext` which doesn't exist according to `exists`.
+/// The extension of the file is preserved
+pub fn numbered_path<F>(p: &Path, exists: F) -> PathBuf
+where
+    F: FnMut(&Path) -> bool,
+{
+    let stem: String = p
+        .file_stem()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension: Option<String> = p.extension().map(|x| x.to_string_lossy().to_string());
+    numbered_name(p, stem.as_str(), extension.as_deref(), exists)
+}
+
+/// ### duplicate_path
+///
+/// Returns the path for a duplicate of `p` in the same directory, in the form of `name copy.ext`.
+/// If it already exists according to `exists`, the copy is numbered as `name copy (n).ext`.
+/// Directories are never considered to have an extension
+pub fn duplicate_path<F>(p: &Path, is_dir: bool, mut exists: F) -> PathBuf
+where
+    F: FnMut(&Path) -> bool,
+{
+    let (stem, extension): (String, Option<String>) = match is_dir {
+        true => (
+            p.file_name()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            None,
+        ),
+        false => (
+            p.file_stem()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            p.extension().map(|x| x.to_string_lossy().to_string()),
+        ),
+    };
+    let stem: String = format!("{} copy", stem);
+    let candidate: PathBuf = match extension.as_ref() {
+        Some(ext) => p.with_file_name(format!("{}.{}", stem, ext)),
+        None => p.with_file_name(stem.as_str()),
+    };
+    match exists(candidate.as_path()) {
+        false => candidate,
+        true => numbered_name(p, stem.as_str(), extension.as_deref(), exists),
+    }
+}
+
+/// ### numbered_name
+///
+/// Returns the first sibling of `p` named `stem (n).extension` which doesn't exist according to `exists`
+fn numbered_name<F>(p: &Path, stem: &str, extension: Option<&str>, mut exists: F) -> PathBuf
+where
+    F: FnMut(&Path) -> bool,
+{
+    let mut n: usize = 1;
+    loop {
+        let name: String = match extension {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        };
+        let candidate: PathBuf = p.with_file_name(name);
+        if !exists(candidate.as_path()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// ### resolve_path
+///
+/// Resolve `path` against the working directory `wrkdir`.
+/// Relative paths are joined to `wrkdir` and `.` and `..` are resolved, while absolute paths are returned unchanged
+pub fn resolve_path(wrkdir: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    let mut resolved: PathBuf = PathBuf::new();
+    for component in wrkdir.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match resolved.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    resolved.pop();
+                }
+                // Can't go above root
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => resolved.push(component),
+            },
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
+
+/// ### relative_path
+///
+/// Get `path` relative to `base`; if `path` is not under `base`, `path` is returned unchanged.
+/// `base` itself is returned as `.`
+pub fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    match path.strip_prefix(base) {
+        Ok(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// ### expand_path
+///
+/// Expand a leading `~` with `home` and `$VAR` or `${VAR}` with the value returned by `lookup`.
+/// Variables which are not set are left untouched
+pub fn expand_path<F>(path: &str, home: Option<&Path>, lookup: F) -> PathBuf
+where
+    F: Fn(&str) -> Option<String>,
+{
+    // Expand home
+    let path: String = match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home))
+            if rest.is_empty() || rest.starts_with('/') || rest.starts_with(MAIN_SEPARATOR) =>
+        {
+            format!("{}{}", home.display(), rest)
+        }
+        _ => path.to_string(),
+    };
+    // Expand variables
+    let mut expanded: String = String::with_capacity(path.len());
+    let mut rest: &str = path.as_str();
+    while let Some(pos) = rest.find('$') {
+        expanded.push_str(&rest[..pos]);
+        let after: &str = &rest[pos + 1..];
+        // Get variable name and the text which follows it
+        let (name, tail, literal): (&str, &str, &str) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (
+                    &braced[..end],
+                    &braced[end + 1..],
+                    &rest[pos..pos + end + 3],
+                ),
+                None => ("", after, "$"),
+            },
+            None => {
+                let end: usize = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], &after[end..], &rest[pos..pos + end + 1])
+            }
+        };
+        match lookup(name) {
+            Some(value) if !name.is_empty() => expanded.push_str(value.as_str()),
+            _ => expanded.push_str(literal),
+        }
+        rest = tail;
+    }
+    expanded.push_str(rest);
+    PathBuf::from(expanded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use pretty_assertions::assert_eq;
+
     #[test]
     fn test_utils_file_open() {
         let tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
         assert!(open_file(tmpfile.path(), true, true, true).is_ok());
     }
+
+    #[test]
+    fn test_utils_file_resolve_path() {
+        let wrkdir: &Path = Path::new("/home/omar/Documents");
+        // Relative
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("backup")),
+            PathBuf::from("/home/omar/Documents/backup")
+        );
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("../backup/")),
+            PathBuf::from("/home/omar/backup")
+        );
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("./notes/../todo.txt")),
+            PathBuf::from("/home/omar/Documents/todo.txt")
+        );
+        assert_eq!(resolve_path(wrkdir, Path::new(".")), wrkdir.to_path_buf());
+        // Can't go above root
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("../../../../../tmp")),
+            PathBuf::from("/tmp")
+        );
+        // Absolute paths are unchanged
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("/tmp/backup")),
+            PathBuf::from("/tmp/backup")
+        );
+        assert_eq!(
+            resolve_path(wrkdir, Path::new("/tmp/../var")),
+            PathBuf::from("/tmp/../var")
+        );
+        // Relative working directory
+        assert_eq!(
+            resolve_path(Path::new("omar"), Path::new("../../tmp")),
+            PathBuf::from("../tmp")
+        );
+    }
+
+    #[test]
+    fn test_utils_file_duplicate_path() {
+        // File
+        assert_eq!(
+            duplicate_path(Path::new("/home/omar/foo.txt"), false, |_| false),
+            PathBuf::from("/home/omar/foo copy.txt")
+        );
+        assert_eq!(
+            duplicate_path(Path::new("/home/omar/README"), false, |_| false),
+            PathBuf::from("/home/omar/README copy")
+        );
+        // Directories keep the whole name
+        assert_eq!(
+            duplicate_path(Path::new("/etc/conf.d"), true, |_| false),
+            PathBuf::from("/etc/conf.d copy")
+        );
+        // Copy already exists
+        let existing: Vec<PathBuf> = vec![
+            PathBuf::from("/tmp/foo copy.txt"),
+            PathBuf::from("/tmp/foo copy (1).txt"),
+            PathBuf::from("/tmp/conf.d copy"),
+        ];
+        let exists = |p: &Path| existing.iter().any(|x| x.as_path() == p);
+        assert_eq!(
+            duplicate_path(Path::new("/tmp/foo.txt"), false, exists),
+            PathBuf::from("/tmp/foo copy (2).txt")
+        );
+        assert_eq!(
+            duplicate_path(Path::new("/tmp/conf.d"), true, exists),
+            PathBuf::from("/tmp/conf.d copy (1)")
+        );
+    }
+
+    #[test]
+    fn test_utils_file_numbered_path() {
+        // With extension
+        assert_eq!(
+            numbered_path(Path::new("/home/omar/foo.txt"), |_| false),
+            PathBuf::from("/home/omar/foo (1).txt")
+        );
+        // Without extension
+        assert_eq!(
+            numbered_path(Path::new("/home/omar/README"), |_| false),
+            PathBuf::from("/home/omar/README (1)")
+        );
+        // Hidden file
+        assert_eq!(
+            numbered_path(Path::new("/home/omar/.bashrc"), |_| false),
+            PathBuf::from("/home/omar/.bashrc (1)")
+        );
+        // Only last extension is preserved
+        assert_eq!(
+            numbered_path(Path::new("archive.tar.gz"), |_| false),
+            PathBuf::from("archive.tar (1).gz")
+        );
+        // Multiple existing variants
+        let existing: Vec<PathBuf> = vec![
+            PathBuf::from("/tmp/foo (1).txt"),
+            PathBuf::from("/tmp/foo (2).txt"),
+            PathBuf::from("/tmp/foo (4).txt"),
+        ];
+        assert_eq!(
+            numbered_path(Path::new("/tmp/foo.txt"), |p| existing
+                .iter()
+                .any(|x| x.as_path() == p)),
+            PathBuf::from("/tmp/foo (3).txt")
+        );
+        // Real filesystem
+        let tmpdir: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+        let file: PathBuf = tmpdir.path().join("bar.rs");
+        assert!(open_file(file.as_path(), true, true, false).is_ok());
+        assert!(open_file(tmpdir.path().join("bar (1).rs"), true, true, false).is_ok());
+        assert_eq!(
+            numbered_path(file.as_path(), |p| p.exists()),
+            tmpdir.path().join("bar (2).rs")
+        );
+    }
+
+    #[test]
+    fn test_utils_file_relative_path() {
+        let base: &Path = Path::new("/home/omar");
+        // Under base
+        assert_eq!(
+            relative_path(base, Path::new("/home/omar/Documents/readme.txt")),
+            PathBuf::from("Documents/readme.txt")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/home/omar/readme.txt")),
+            PathBuf::from("readme.txt")
+        );
+        assert_eq!(relative_path(base, base), PathBuf::from("."));
+        // Outside base
+        assert_eq!(
+            relative_path(base, Path::new("/tmp/readme.txt")),
+            PathBuf::from("/tmp/readme.txt")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/home/omarino/readme.txt")),
+            PathBuf::from("/home/omarino/readme.txt")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/home")),
+            PathBuf::from("/home")
+        );
+        // Root base
+        assert_eq!(
+            relative_path(Path::new("/"), Path::new("/tmp/readme.txt")),
+            PathBuf::from("tmp/readme.txt")
+        );
+    }
+
+    #[test]
+    fn test_utils_file_expand_path() {
+        let home: &Path = Path::new("/home/omar");
+        let lookup = |name: &str| match name {
+            "HOME" => Some(String::from("/home/omar")),
+            "EDITOR_DIR" => Some(String::from("/opt/editors")),
+            _ => None,
+        };
+        // Nothing to expand
+        assert_eq!(
+            expand_path("/usr/bin/vim", Some(home), lookup),
+            PathBuf::from("/usr/bin/vim")
+        );
+        assert_eq!(expand_path("vim", Some(home), lookup), PathBuf::from("vim"));
+        // Home
+        assert_eq!(
+            expand_path("~/bin/editor", Some(home), lookup),
+            PathBuf::from("/home/omar/bin/editor")
+        );
+        assert_eq!(
+            expand_path("~", Some(home), lookup),
+            PathBuf::from("/home/omar")
+        );
+        assert_eq!(
+            expand_path("~omar/bin/editor", Some(home), lookup),
+            PathBuf::from("~omar/bin/editor")
+        );
+        assert_eq!(
+            expand_path("~/bin/editor", None, lookup),
+            PathBuf::from("~/bin/editor")
+        );
+        // Variables
+        assert_eq!(
+            expand_path("$HOME/bin/editor", Some(home), lookup),
+            PathBuf::from("/home/omar/bin/editor")
+        );
+        assert_eq!(
+            expand_path("${EDITOR_DIR}/vim", Some(home), lookup),
+            PathBuf::from("/opt/editors/vim")
+        );
+        assert_eq!(
+            expand_path("$EDITOR_DIR-nightly/vim", Some(home), lookup),
+            PathBuf::from("/opt/editors-nightly/vim")
+        );
+        // Unset variables are left untouched
+        assert_eq!(
+            expand_path("$UNSET/vim", Some(home), lookup),
+            PathBuf::from("$UNSET/vim")
+        );
+        assert_eq!(
+            expand_path("${UNSET}/vim", Some(home), lookup),
+            PathBuf::from("${UNSET}/vim")
+        );
+        assert_eq!(
+            expand_path("/bin/$/${vim", Some(home), lookup),
+            PathBuf::from("/bin/$/${vim")
+        );
+        assert_eq!(expand_path("$", Some(home), lookup), PathBuf::from("$"));
+    }
 }
@@ -25,11 +25,68 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use crate::fs::DiskUsage;
+
+use bytesize::ByteSize;
 use chrono::prelude::*;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 use tuirealm::tui::style::Color;
 
+/// ## SizeUnit
+///
+/// SizeUnit defines the unit used to display sizes
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SizeUnit {
+    #[default]
+    Si, // Multiples of 1000 (KB, MB, ...)
+    Binary, // Multiples of 1024 (KiB, MiB, ...)
+}
+
+impl fmt::Display for SizeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SizeUnit::Si => "si",
+            SizeUnit::Binary => "binary",
+        })
+    }
+}
+
+impl FromStr for SizeUnit {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "si" => Ok(SizeUnit::Si),
+            "binary" => Ok(SizeUnit::Binary),
+            _ => Err(()),
+        }
+    }
+}
+
+/// ### fmt_bytes
+///
+/// Format an amount of bytes using the provided unit (e.g. `8.2 KB` or `8 KiB`)
+pub fn fmt_bytes(bytes: u64, unit: SizeUnit) -> String {
+    match unit {
+        SizeUnit::Si => ByteSize(bytes).to_string(),
+        SizeUnit::Binary if bytes < 1024 => format!("{} B", bytes),
+        SizeUnit::Binary => {
+            const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+            let mut size: f64 = bytes as f64 / 1024.0;
+            let mut unit: usize = 0;
+            while size >= 1024.0 && unit < UNITS.len() - 1 {
+                size /= 1024.0;
+                unit += 1;
+            }
+            // Omit decimal for exact multiples (e.g. `8 KiB`)
+            let size: String = format!("{:.1}", size);
+            format!("{} {}", size.trim_end_matches(".0"), UNITS[unit])
+        }
+    }
+}
+
 /// ### fmt_pex
 ///
 /// Convert 3 bytes of permissions value into ls notation (e.g. rwx-wx--x)
@@ -100,6 +157,19 @@ pub fn fmt_millis(duration: Duration) -> String {
     format!("{}.{:0width$}", seconds, millis, width = 3)
 }
 
+/// ### fmt_duration
+///
+/// Format duration as {hours}:{minutes}:{seconds}
+pub fn fmt_duration(duration: Duration) -> String {
+    let secs: u64 = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
 /// align_text_center
 ///
 /// Align text to center for a given width
@@ -327,11 +397,47 @@ pub fn fmt_color(color: &Color) -> String {
     }
 }
 
-/// ### shadow_password
+/// ### fmt_disk_usage
 ///
-/// Return a string with the same length of input string, but each character is replaced by '*'
-pub fn shadow_password(s: &str) -> String {
-    (0..s.len()).map(|_| '*').collect()
+/// Format disk usage as `used / total (pct%)`; if disk usage is not available, returns `n/a`
+pub fn fmt_disk_usage(usage: Option<&DiskUsage>, unit: SizeUnit) -> String {
+    match usage {
+        None => String::from("n/a"),
+        Some(usage) => {
+            let pct: u64 = match usage.total {
+                0 => 0,
+                total => (usage.used * 100) / total,
+            };
+            format!(
+                "{} / {} ({}%)",
+                fmt_bytes(usage.used, unit),
+                fmt_bytes(usage.total, unit),
+                pct
+            )
+        }
+    }
+}
+
+/// ### redact_password
+///
+/// Return a fixed mask for the provided password, which doesn't reveal its length.
+/// An empty password is returned as an empty string
+pub fn redact_password(s: &str) -> &'static str {
+    match s.is_empty() {
+        true => "",
+        false => REDACTED_PASSWORD,
+    }
+}
+
+/// Mask displayed in place of passwords
+pub const REDACTED_PASSWORD: &str = "***";
+
+/// ### shell_quote
+///
+/// Quote `p` to be passed as a single argument to a POSIX shell.
+/// The path is wrapped in single quotes and each single quote in it is written as `'\''`
+pub fn shell_quote(p: &Path) -> String {
+    format!("'{}'", p.to_string_lossy().replace('\'', "'\\''"))
 }
 
 #[cfg(test)]
@@ -390,6 +496,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_utils_fmt_duration() {
+        assert_eq!(fmt_duration(Duration::from_millis(999)), "00:00:00");
+        assert_eq!(fmt_duration(Duration::from_secs(62)), "00:01:02");
+        assert_eq!(fmt_duration(Duration::from_secs(7384)), "02:03:04");
+        assert_eq!(fmt_duration(Duration::from_secs(360000)), "100:00:00");
+    }
+
     #[test]
     #[cfg(target_family = "unix")]
     fn test_utils_fmt_path_elide() {
@@ -660,7 +774,96 @@ mod tests {
     }
 
     #[test]
-    fn test_utils_fmt_shadow_password() {
-        assert_eq!(shadow_password("foobar"), String::from("******"));
+    fn test_utils_fmt_disk_usage() {
+        assert_eq!(
+            fmt_disk_usage(
+                Some(&DiskUsage {
+                    total: 100000000,
+                    used: 25000000,
+                    free: 75000000,
+                }),
+                SizeUnit::Si
+            ),
+            String::from("25.0 MB / 100.0 MB (25%)")
+        );
+        assert_eq!(
+            fmt_disk_usage(
+                Some(&DiskUsage {
+                    total: 1000,
+                    used: 999,
+                    free: 1,
+                }),
+                SizeUnit::Si
+            ),
+            String::from("999 B / 1.0 KB (99%)")
+        );
+        // Empty file system
+        assert_eq!(
+            fmt_disk_usage(
+                Some(&DiskUsage {
+                    total: 0,
+                    used: 0,
+                    free: 0,
+                }),
+                SizeUnit::Si
+            ),
+            String::from("0 B / 0 B (0%)")
+        );
+        assert_eq!(
+            fmt_disk_usage(
+                Some(&DiskUsage {
+                    total: 4194304,
+                    used: 1048576,
+                    free: 3145728,
+                }),
+                SizeUnit::Binary
+            ),
+            String::from("1 MiB / 4 MiB (25%)")
+        );
+        // Not available
+        assert_eq!(fmt_disk_usage(None, SizeUnit::Si), String::from("n/a"));
+    }
+
+    #[test]
+    fn test_utils_fmt_bytes() {
+        assert_eq!(fmt_bytes(8192, SizeUnit::Si).as_str(), "8.2 KB");
+        assert_eq!(fmt_bytes(8192, SizeUnit::Binary).as_str(), "8 KiB");
+        assert_eq!(fmt_bytes(512, SizeUnit::Si).as_str(), "512 B");
+        assert_eq!(fmt_bytes(512, SizeUnit::Binary).as_str(), "512 B");
+        assert_eq!(fmt_bytes(1536, SizeUnit::Binary).as_str(), "1.5 KiB");
+        assert_eq!(fmt_bytes(1000, SizeUnit::Si).as_str(), "1.0 KB");
+        assert_eq!(fmt_bytes(1048576, SizeUnit::Binary).as_str(), "1 MiB");
+        assert_eq!(
+            fmt_bytes(1073741824 * 5 / 2, SizeUnit::Binary).as_str(),
+            "2.5 GiB"
+        );
+        assert_eq!(SizeUnit::default(), SizeUnit::Si);
+        assert_eq!(SizeUnit::from_str("BINARY").ok().unwrap(), SizeUnit::Binary);
+        assert_eq!(SizeUnit::from_str("si").ok().unwrap(), SizeUnit::Si);
+        assert!(SizeUnit::from_str("foo").is_err());
+        assert_eq!(SizeUnit::Binary.to_string().as_str(), "binary");
+    }
+
+    #[test]
+    fn test_utils_fmt_redact_password() {
+        assert_eq!(redact_password("foobar"), "***");
+        assert_eq!(redact_password("a-much-longer-password"), "***");
+        assert_eq!(redact_password(""), "");
+    }
+
+    #[test]
+    fn test_utils_fmt_shell_quote() {
+        assert_eq!(
+            shell_quote(Path::new("/tmp/a b.txt")).as_str(),
+            "'/tmp/a b.txt'"
+        );
+        assert_eq!(
+            shell_quote(Path::new("/tmp/$(rm -rf ~)\"; echo")).as_str(),
+            "'/tmp/$(rm -rf ~)\"; echo'"
+        );
+        assert_eq!(
+            shell_quote(Path::new("/tmp/it's")).as_str(),
+            "'/tmp/it'\\''s'"
+        );
     }
 }
@@ -25,12 +25,13 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use crate::filetransfer::{FileTransfer, FileTransferError, FileTransferErrorType};
 use crate::fs::{FsDirectory, FsEntry, FsFile};
 // ext
+use std::collections::HashMap;
 use std::fs::File;
 #[cfg(feature = "with-containers")]
 use std::fs::OpenOptions;
-#[cfg(feature = "with-containers")]
 use std::io::Read;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -192,6 +193,140 @@ pub fn create_file_ioers(p: &Path) -> (File, File) {
     (File::open(p).ok().unwrap(), File::create(p).ok().unwrap())
 }
 
+/// ## MockFileTransfer
+///
+/// A `FileTransfer` which serves a fake remote tree from memory and records the name of the methods called.
+/// Only `list_dir` and `stat` are supported; other operations fail with `UnsupportedFeature`
+#[derive(Default)]
+pub struct MockFileTransfer {
+    tree: HashMap<PathBuf, Vec<FsEntry>>,
+    pub calls: Vec<String>,
+}
+
+impl MockFileTransfer {
+    /// ### dir
+    ///
+    /// Set the entries returned when listing `path`
+    pub fn dir(mut self, path: &Path, entries: Vec<FsEntry>) -> Self {
+        self.tree.insert(path.to_path_buf(), entries);
+        self
+    }
+
+    fn record(&mut self, call: &str) {
+        self.calls.push(call.to_string());
+    }
+
+    fn unsupported<T>() -> Result<T, FileTransferError> {
+        Err(FileTransferError::new(
+            FileTransferErrorType::UnsupportedFeature,
+        ))
+    }
+}
+
+impl FileTransfer for MockFileTransfer {
+    fn connect(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _username: Option<String>,
+        _password: Option<String>,
+    ) -> Result<Option<String>, FileTransferError> {
+        self.record("connect");
+        Ok(None)
+    }
+
+    fn disconnect(&mut self) -> Result<(), FileTransferError> {
+        self.record("disconnect");
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn pwd(&mut self) -> Result<PathBuf, FileTransferError> {
+        self.record("pwd");
+        Ok(PathBuf::from("/"))
+    }
+
+    fn change_dir(&mut self, dir: &Path) -> Result<PathBuf, FileTransferError> {
+        self.record("change_dir");
+        Ok(dir.to_path_buf())
+    }
+
+    fn copy(&mut self, _src: &FsEntry, _dst: &Path) -> Result<(), FileTransferError> {
+        self.record("copy");
+        Self::unsupported()
+    }
+
+    fn list_dir(&mut self, path: &Path) -> Result<Vec<FsEntry>, FileTransferError> {
+        self.record("list_dir");
+        self.tree
+            .get(path)
+            .cloned()
+            .ok_or_else(|| FileTransferError::new(FileTransferErrorType::NoSuchFileOrDirectory))
+    }
+
+    fn mkdir(&mut self, _dir: &Path) -> Result<(), FileTransferError> {
+        self.record("mkdir");
+        Self::unsupported()
+    }
+
+    fn remove(&mut self, _file: &FsEntry) -> Result<(), FileTransferError> {
+        self.record("remove");
+        Self::unsupported()
+    }
+
+    fn rename(&mut self, _file: &FsEntry, _dst: &Path) -> Result<(), FileTransferError> {
+        self.record("rename");
+        Self::unsupported()
+    }
+
+    fn symlink(&mut self, _src: &Path, _dst: &Path) -> Result<(), FileTransferError> {
+        self.record("symlink");
+        Self::unsupported()
+    }
+
+    fn stat(&mut self, path: &Path) -> Result<FsEntry, FileTransferError> {
+        self.record("stat");
+        self.tree
+            .values()
+            .flatten()
+            .find(|x| x.get_abs_path() == path)
+            .cloned()
+            .ok_or_else(|| FileTransferError::new(FileTransferErrorType::NoSuchFileOrDirectory))
+    }
+
+    fn exec(&mut self, _cmd: &str) -> Result<String, FileTransferError> {
+        self.record("exec");
+        Self::unsupported()
+    }
+
+    fn send_file(
+        &mut self,
+        _local: &FsFile,
+        _file_name: &Path,
+    ) -> Result<Box<dyn Write>, FileTransferError> {
+        self.record("send_file");
+        Self::unsupported()
+    }
+
+    fn recv_file(&mut self, _file: &FsFile) -> Result<Box<dyn Read>, FileTransferError> {
+        self.record("recv_file");
+        Self::unsupported()
+    }
+
+    fn on_sent(&mut self, _writable: Box<dyn Write>) -> Result<(), FileTransferError> {
+        self.record("on_sent");
+        Ok(())
+    }
+
+    fn on_recv(&mut self, _readable: Box<dyn Read>) -> Result<(), FileTransferError> {
+        self.record("on_recv");
+        Ok(())
+    }
+}
+
 mod test {
     use super::*;
 